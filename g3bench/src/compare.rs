@@ -0,0 +1,309 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint, value_parser};
+
+use crate::report::{BenchReport, ReportUnit};
+
+pub const COMMAND: &str = "compare";
+
+const ARG_BASELINE: &str = "baseline";
+const ARG_CURRENT: &str = "current";
+const ARG_THRESHOLD: &str = "threshold";
+const ARG_PERCENTILE: &str = "percentile";
+const ARG_HISTOGRAM: &str = "histogram";
+const ARG_IGNORE_RATE: &str = "ignore-rate";
+
+struct CompareArgs {
+    baseline: PathBuf,
+    current: PathBuf,
+    threshold: f64,
+    percentiles: Vec<f64>,
+    histograms: Vec<String>,
+    ignore_rate: bool,
+}
+
+impl CompareArgs {
+    fn parse(args: &ArgMatches) -> anyhow::Result<Self> {
+        let baseline = args.get_one::<PathBuf>(ARG_BASELINE).unwrap().clone();
+        let current = args.get_one::<PathBuf>(ARG_CURRENT).unwrap().clone();
+
+        let threshold = args.get_one::<String>(ARG_THRESHOLD).unwrap();
+        let threshold = threshold
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|e| anyhow!("invalid threshold value {threshold}: {e}"))?;
+        if !threshold.is_finite() || threshold < 0.0 {
+            return Err(anyhow!("the threshold value should be a positive number"));
+        }
+
+        let percentiles = args
+            .get_many::<f64>(ARG_PERCENTILE)
+            .unwrap()
+            .copied()
+            .collect::<Vec<_>>();
+        for p in &percentiles {
+            if !(0.0..=100.0).contains(p) {
+                return Err(anyhow!("invalid percentile value {p}"));
+            }
+        }
+
+        let histograms = args
+            .get_many::<String>(ARG_HISTOGRAM)
+            .map(|v| v.cloned().collect())
+            .unwrap_or_default();
+
+        Ok(CompareArgs {
+            baseline,
+            current,
+            threshold,
+            percentiles,
+            histograms,
+            ignore_rate: args.get_flag(ARG_IGNORE_RATE),
+        })
+    }
+
+    fn check_histogram(&self, name: &str) -> bool {
+        self.histograms.is_empty() || self.histograms.iter().any(|v| v == name)
+    }
+}
+
+pub fn command() -> Command {
+    Command::new(COMMAND)
+        .about("Compare two result files and report the regressions")
+        .arg(
+            Arg::new(ARG_BASELINE)
+                .help("The baseline result file")
+                .value_name("BASELINE FILE")
+                .required(true)
+                .num_args(1)
+                .value_hint(ValueHint::FilePath)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new(ARG_CURRENT)
+                .help("The result file to check")
+                .value_name("CURRENT FILE")
+                .required(true)
+                .num_args(1)
+                .value_hint(ValueHint::FilePath)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new(ARG_THRESHOLD)
+                .help("Regression threshold in percentage for latency increase or rate decrease")
+                .value_name("PERCENTAGE")
+                .long(ARG_THRESHOLD)
+                .num_args(1)
+                .default_value("5%"),
+        )
+        .arg(
+            Arg::new(ARG_PERCENTILE)
+                .help("Percentiles of the duration histograms to compare")
+                .value_name("PERCENTILE")
+                .long(ARG_PERCENTILE)
+                .short('p')
+                .num_args(1)
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .value_parser(value_parser!(f64))
+                .default_value("50,90,99"),
+        )
+        .arg(
+            Arg::new(ARG_HISTOGRAM)
+                .help("Only compare the duration histograms with these names")
+                .value_name("NAME")
+                .long(ARG_HISTOGRAM)
+                .num_args(1)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(ARG_IGNORE_RATE)
+                .help("Do not check the request rate")
+                .long(ARG_IGNORE_RATE)
+                .action(ArgAction::SetTrue),
+        )
+}
+
+struct CompareLine {
+    name: String,
+    baseline: String,
+    current: String,
+    delta: f64,
+    regression: bool,
+}
+
+impl CompareLine {
+    fn print(&self) {
+        let delta = if self.delta.is_finite() {
+            format!("{:+.2}%", self.delta)
+        } else {
+            "-".to_string()
+        };
+        let mark = if self.regression { " REGRESSION" } else { "" };
+        println!(
+            "{:<28} {:>14} {:>14} {delta:>10}{mark}",
+            self.name, self.baseline, self.current
+        );
+    }
+}
+
+fn delta_percentage(baseline: f64, current: f64) -> f64 {
+    if baseline == current {
+        0.0
+    } else {
+        (current - baseline) / baseline * 100.0
+    }
+}
+
+fn compare(args: &CompareArgs, baseline: &BenchReport, current: &BenchReport) -> Vec<CompareLine> {
+    let mut lines = Vec::new();
+
+    let b_rate = baseline.request_rate();
+    let c_rate = current.request_rate();
+    let delta = delta_percentage(b_rate, c_rate);
+    lines.push(CompareLine {
+        name: "rate".to_string(),
+        baseline: format!("{b_rate:.3}/s"),
+        current: format!("{c_rate:.3}/s"),
+        delta,
+        regression: !args.ignore_rate && -delta > args.threshold,
+    });
+
+    for b in &baseline.histograms {
+        if b.unit != ReportUnit::Nanos || !args.check_histogram(&b.name) {
+            continue;
+        }
+        let Some(c) = current.histogram(&b.name) else {
+            continue;
+        };
+        if b.data.is_empty() || c.data.is_empty() {
+            continue;
+        }
+
+        for p in &args.percentiles {
+            let b_v = b.data.value_at_percentile(*p);
+            let c_v = c.data.value_at_percentile(*p);
+            let delta = delta_percentage(b_v as f64, c_v as f64);
+            lines.push(CompareLine {
+                name: format!("{} p{p}", b.name),
+                baseline: format!("{:.3?}", Duration::from_nanos(b_v)),
+                current: format!("{:.3?}", Duration::from_nanos(c_v)),
+                delta,
+                regression: delta > args.threshold,
+            });
+        }
+    }
+
+    lines
+}
+
+pub fn run(args: &ArgMatches) -> anyhow::Result<ExitCode> {
+    let args = CompareArgs::parse(args)?;
+
+    let baseline = BenchReport::load_file(&args.baseline)?;
+    let current = BenchReport::load_file(&args.current)?;
+    if baseline.target != current.target {
+        eprintln!(
+            "WARNING: comparing results of different targets: {} vs {}",
+            baseline.target, current.target
+        );
+    }
+
+    println!(
+        "Baseline: {} ({}, {} passed, {} failed)",
+        args.baseline.display(),
+        baseline.target,
+        baseline.passed,
+        baseline.failed
+    );
+    println!(
+        "Current:  {} ({}, {} passed, {} failed)",
+        args.current.display(),
+        current.target,
+        current.passed,
+        current.failed
+    );
+    println!("Threshold: {}%", args.threshold);
+    println!();
+
+    let lines = compare(&args, &baseline, &current);
+    println!(
+        "{:<28} {:>14} {:>14} {:>10}",
+        "Metric", "Baseline", "Current", "Delta"
+    );
+    for line in &lines {
+        line.print();
+    }
+
+    let regressions = lines.iter().filter(|l| l.regression).count();
+    println!();
+    if regressions > 0 {
+        println!("Found {regressions} regression(s)");
+        Ok(ExitCode::FAILURE)
+    } else {
+        println!("No regression found");
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdrhistogram::Histogram;
+
+    fn new_report(rate: u64, latency: u64) -> BenchReport {
+        let mut report = BenchReport::new("h1", Duration::from_secs(1));
+        report.passed = rate;
+        let mut h = Histogram::<u64>::new(3).unwrap();
+        for _ in 0..100 {
+            h.record(latency).unwrap();
+        }
+        report.add_duration_histogram("time.total", &h);
+        report.add_count_histogram("connection.reuse", &h);
+        report
+    }
+
+    fn new_args(threshold: f64) -> CompareArgs {
+        CompareArgs {
+            baseline: PathBuf::new(),
+            current: PathBuf::new(),
+            threshold,
+            percentiles: vec![50.0, 99.0],
+            histograms: Vec::new(),
+            ignore_rate: false,
+        }
+    }
+
+    #[test]
+    fn no_regression() {
+        let baseline = new_report(1000, 1_000_000);
+        let current = new_report(980, 1_030_000);
+        let lines = compare(&new_args(5.0), &baseline, &current);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|l| !l.regression));
+    }
+
+    #[test]
+    fn regression() {
+        let baseline = new_report(1000, 1_000_000);
+        let current = new_report(900, 1_100_000);
+        let lines = compare(&new_args(5.0), &baseline, &current);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|l| l.regression));
+
+        let mut args = new_args(5.0);
+        args.ignore_rate = true;
+        args.histograms.push("time.other".to_string());
+        let lines = compare(&args, &baseline, &current);
+        assert_eq!(lines.len(), 1);
+        assert!(!lines[0].regression);
+    }
+}
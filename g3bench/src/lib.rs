@@ -3,11 +3,14 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+mod load_profile;
 mod module;
 mod opts;
 mod progress;
+mod report;
 
 pub mod build;
+pub mod compare;
 pub mod target;
 pub mod worker;
 
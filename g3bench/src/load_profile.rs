@@ -0,0 +1,485 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use hdrhistogram::Histogram;
+use tokio::time::Instant;
+use yaml_rust::{Yaml, YamlLoader};
+
+use crate::report::BenchReport;
+
+/// A load stage with the request arrival rate changing linearly from
+/// `start_rate` to `end_rate`
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LoadStage {
+    start_rate: f64,
+    end_rate: f64,
+    duration: Duration,
+}
+
+impl LoadStage {
+    fn new(start_rate: f64, end_rate: f64, duration: Duration) -> anyhow::Result<Self> {
+        if !start_rate.is_finite() || start_rate < 0.0 {
+            return Err(anyhow!("invalid rate value {start_rate}"));
+        }
+        if !end_rate.is_finite() || end_rate < 0.0 {
+            return Err(anyhow!("invalid rate value {end_rate}"));
+        }
+        if duration.is_zero() {
+            return Err(anyhow!("zero stage duration is not allowed"));
+        }
+        Ok(LoadStage {
+            start_rate,
+            end_rate,
+            duration,
+        })
+    }
+
+    fn constant(rate: f64, duration: Duration) -> anyhow::Result<Self> {
+        LoadStage::new(rate, rate, duration)
+    }
+
+    /// Get the total count of request arrivals in this stage
+    fn arrivals(&self) -> u64 {
+        ((self.start_rate + self.end_rate) / 2.0 * self.duration.as_secs_f64()) as u64
+    }
+
+    /// Get the time offset of the arrival with the index `k` in this stage
+    fn arrival_offset(&self, k: u64) -> Duration {
+        let k = k as f64;
+        let secs = if self.start_rate == self.end_rate {
+            k / self.start_rate
+        } else {
+            // solve r0*t + (r1-r0)/(2d)*t^2 = k
+            let a = (self.end_rate - self.start_rate) / (2.0 * self.duration.as_secs_f64());
+            let b = self.start_rate;
+            let delta = (b * b + 4.0 * a * k).max(0.0);
+            (delta.sqrt() - b) / (2.0 * a)
+        };
+        Duration::from_secs_f64(secs.max(0.0)).min(self.duration)
+    }
+}
+
+impl fmt::Display for LoadStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start_rate == self.end_rate {
+            write!(f, "{}/s for {:?}", self.start_rate, self.duration)
+        } else {
+            write!(
+                f,
+                "{}/s -> {}/s for {:?}",
+                self.start_rate, self.end_rate, self.duration
+            )
+        }
+    }
+}
+
+fn parse_rate(s: &str) -> anyhow::Result<f64> {
+    let s = s.trim();
+    let s = s.strip_suffix("/s").unwrap_or(s);
+    f64::from_str(s).map_err(|e| anyhow!("invalid rate value {s}: {e}"))
+}
+
+fn push_step_stages(
+    stages: &mut Vec<LoadStage>,
+    from: f64,
+    to: f64,
+    step: f64,
+    duration: Duration,
+) -> anyhow::Result<()> {
+    if !step.is_finite() || step <= 0.0 {
+        return Err(anyhow!("invalid step value {step}"));
+    }
+    let mut rate = from;
+    if from <= to {
+        while rate <= to {
+            stages.push(LoadStage::constant(rate, duration)?);
+            rate += step;
+        }
+    } else {
+        while rate >= to {
+            stages.push(LoadStage::constant(rate, duration)?);
+            rate -= step;
+        }
+    }
+    Ok(())
+}
+
+/// Parse a single stage spec, which can be:
+///  - `RATE@DURATION` for constant arrival rate
+///  - `FROM..TO@DURATION` for linear ramp
+///  - `FROM..TO/STEP@DURATION` for step stages, each will last for DURATION
+fn parse_stage_spec(stages: &mut Vec<LoadStage>, s: &str) -> anyhow::Result<()> {
+    let Some((rate, duration)) = s.trim().rsplit_once('@') else {
+        return Err(anyhow!("no '@' found in stage spec {s}"));
+    };
+    let duration = g3_clap::humanize::parse_duration(duration.trim())?;
+
+    if let Some((from, to)) = rate.split_once("..") {
+        let from = parse_rate(from)?;
+        if let Some((to, step)) = to.split_once('/')
+            && step.trim() != "s"
+        {
+            let to = parse_rate(to)?;
+            let step = parse_rate(step)?;
+            push_step_stages(stages, from, to, step, duration)
+        } else {
+            let to = parse_rate(to)?;
+            stages.push(LoadStage::new(from, to, duration)?);
+            Ok(())
+        }
+    } else {
+        let rate = parse_rate(rate)?;
+        stages.push(LoadStage::constant(rate, duration)?);
+        Ok(())
+    }
+}
+
+fn parse_yaml_stage(stages: &mut Vec<LoadStage>, v: &Yaml) -> anyhow::Result<()> {
+    match v {
+        Yaml::String(s) => parse_stage_spec(stages, s),
+        Yaml::Hash(map) => {
+            let mut rate = None;
+            let mut from = None;
+            let mut to = None;
+            let mut step = None;
+            let mut duration = None;
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "rate" => {
+                    rate = Some(g3_yaml::value::as_f64(v)?);
+                    Ok(())
+                }
+                "from" => {
+                    from = Some(g3_yaml::value::as_f64(v)?);
+                    Ok(())
+                }
+                "to" => {
+                    to = Some(g3_yaml::value::as_f64(v)?);
+                    Ok(())
+                }
+                "step" => {
+                    step = Some(g3_yaml::value::as_f64(v)?);
+                    Ok(())
+                }
+                "duration" => {
+                    duration = Some(g3_yaml::humanize::as_duration(v)?);
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+
+            let duration = duration.ok_or_else(|| anyhow!("no duration set"))?;
+            match (rate, from, to) {
+                (Some(rate), None, None) => {
+                    stages.push(LoadStage::constant(rate, duration)?);
+                    Ok(())
+                }
+                (None, Some(from), Some(to)) => {
+                    if let Some(step) = step {
+                        push_step_stages(stages, from, to, step, duration)
+                    } else {
+                        stages.push(LoadStage::new(from, to, duration)?);
+                        Ok(())
+                    }
+                }
+                _ => Err(anyhow!("either 'rate' or 'from' and 'to' should be set")),
+            }
+        }
+        _ => Err(anyhow!("invalid yaml value type for load stage")),
+    }
+}
+
+pub(crate) struct LoadProfile {
+    stages: Vec<LoadStage>,
+    /// the index of the first request and the start time offset of each stage
+    stage_starts: Vec<(u64, Duration)>,
+    total_requests: u64,
+    total_duration: Duration,
+}
+
+impl LoadProfile {
+    fn new(stages: Vec<LoadStage>) -> anyhow::Result<Self> {
+        if stages.is_empty() {
+            return Err(anyhow!("no load stage set"));
+        }
+
+        let mut stage_starts = Vec::with_capacity(stages.len());
+        let mut total_requests = 0u64;
+        let mut total_duration = Duration::ZERO;
+        for stage in &stages {
+            stage_starts.push((total_requests, total_duration));
+            total_requests += stage.arrivals();
+            total_duration += stage.duration;
+        }
+        Ok(LoadProfile {
+            stages,
+            stage_starts,
+            total_requests,
+            total_duration,
+        })
+    }
+
+    pub(crate) fn parse_spec(s: &str) -> anyhow::Result<Self> {
+        let mut stages = Vec::new();
+        for (i, v) in s.split(',').enumerate() {
+            parse_stage_spec(&mut stages, v).context(format!("invalid stage spec #{i}"))?;
+        }
+        LoadProfile::new(stages)
+    }
+
+    pub(crate) fn load_yaml_file(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+        let docs = YamlLoader::load_from_str(&content)
+            .map_err(|e| anyhow!("invalid yaml file {}: {e}", path.display()))?;
+        let Some(doc) = docs.first() else {
+            return Err(anyhow!("no yaml doc found in file {}", path.display()));
+        };
+        LoadProfile::parse_yaml(doc)
+    }
+
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        match v {
+            Yaml::Hash(map) => {
+                let v = g3_yaml::hash_get_required(map, "stages")?;
+                LoadProfile::parse_yaml(v)
+            }
+            Yaml::Array(seq) => {
+                let mut stages = Vec::new();
+                for (i, v) in seq.iter().enumerate() {
+                    parse_yaml_stage(&mut stages, v).context(format!("invalid stage #{i}"))?;
+                }
+                LoadProfile::new(stages)
+            }
+            Yaml::String(s) => LoadProfile::parse_spec(s),
+            _ => Err(anyhow!("invalid yaml value type for load profile")),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn stages(&self) -> &[LoadStage] {
+        &self.stages
+    }
+
+    #[inline]
+    pub(crate) fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
+    /// Get the stage index and the scheduled time offset of the request with index `n`
+    fn schedule(&self, n: u64) -> Option<(usize, Duration)> {
+        if n >= self.total_requests {
+            return None;
+        }
+        let i = self
+            .stage_starts
+            .partition_point(|(first, _)| *first <= n)
+            .checked_sub(1)?;
+        let (first, start) = self.stage_starts[i];
+        let stage = &self.stages[i];
+        Some((i, start + stage.arrival_offset(n - first)))
+    }
+}
+
+/// Open-loop request scheduler shared by all task contexts
+pub(crate) struct LoadScheduler {
+    profile: Arc<LoadProfile>,
+    next_request: AtomicU64,
+    time_start: OnceLock<Instant>,
+}
+
+impl LoadScheduler {
+    pub(crate) fn new(profile: Arc<LoadProfile>) -> Self {
+        LoadScheduler {
+            profile,
+            next_request: AtomicU64::new(0),
+            time_start: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn set_time_start(&self, time: Instant) {
+        let _ = self.time_start.set(time);
+    }
+
+    /// Get the stage index and scheduled time of the next request
+    pub(crate) fn fetch(&self) -> Option<(usize, Instant)> {
+        let time_start = *self.time_start.get()?;
+        let n = self.next_request.fetch_add(1, Ordering::Relaxed);
+        let (stage, offset) = self.profile.schedule(n)?;
+        Some((stage, time_start + offset))
+    }
+
+    pub(crate) fn new_stage_stats(&self) -> Vec<LoadStageStats> {
+        self.profile
+            .stages
+            .iter()
+            .map(|_| LoadStageStats::default())
+            .collect()
+    }
+
+    pub(crate) fn summary(&self, stats: &[LoadStageStats]) {
+        const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+
+        let print_duration_line = |name: &str, h: &Histogram<u64>| {
+            let t_pct50 = Duration::from_nanos(h.value_at_quantile(0.50));
+            let t_pct90 = Duration::from_nanos(h.value_at_quantile(0.90));
+            let t_pct99 = Duration::from_nanos(h.value_at_quantile(0.99));
+            let t_mean = Duration::from_secs_f64(h.mean() / NANOS_PER_SEC);
+            let t_max = Duration::from_nanos(h.max());
+            println!(
+                "  {name:<12} {t_mean:>9.3?} {t_pct50:>9.3?} {t_pct90:>9.3?} {t_pct99:>9.3?} {t_max:>9.3?}"
+            );
+        };
+
+        println!("# Load Stages");
+        for (i, (stage, s)) in self.profile.stages.iter().zip(stats).enumerate() {
+            println!("Stage #{i}: {stage}");
+            println!("  Scheduled:   {}", stage.arrivals());
+            println!("  Complete:    {}", s.passed);
+            if s.failed > 0 {
+                println!("  Failed:      {}", s.failed);
+            }
+            println!(
+                "  Throughput:  {:.3} [#/sec]",
+                s.passed as f64 / stage.duration.as_secs_f64()
+            );
+            if s.latency.is_empty() {
+                continue;
+            }
+            println!("                    mean     pct50     pct90     pct99       max");
+            print_duration_line("Latency:", &s.latency);
+            print_duration_line("Start delay:", &s.start_delay);
+        }
+        println!();
+    }
+
+    pub(crate) fn report(&self, stats: &[LoadStageStats], report: &mut BenchReport) {
+        for (i, s) in stats.iter().enumerate() {
+            report.add_counter(format!("stage.{i}.passed"), s.passed);
+            report.add_counter(format!("stage.{i}.failed"), s.failed);
+            report.add_duration_histogram(format!("stage.{i}.latency"), &s.latency);
+            report.add_duration_histogram(format!("stage.{i}.start_delay"), &s.start_delay);
+        }
+    }
+}
+
+pub(crate) struct LoadStageStats {
+    passed: u64,
+    failed: u64,
+    /// latency since the scheduled time, corrected for coordinated omission
+    latency: Histogram<u64>,
+    /// delay between the scheduled time and the actual start time
+    start_delay: Histogram<u64>,
+}
+
+impl Default for LoadStageStats {
+    fn default() -> Self {
+        LoadStageStats {
+            passed: 0,
+            failed: 0,
+            latency: Histogram::new(3).unwrap(),
+            start_delay: Histogram::new(3).unwrap(),
+        }
+    }
+}
+
+impl LoadStageStats {
+    pub(crate) fn record(&mut self, passed: bool, start_delay: Duration, latency: Duration) {
+        if passed {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+        }
+        let _ = self
+            .start_delay
+            .record(start_delay.as_nanos().min(u64::MAX as u128) as u64);
+        let _ = self
+            .latency
+            .record(latency.as_nanos().min(u64::MAX as u128) as u64);
+    }
+
+    pub(crate) fn merge(&mut self, other: &LoadStageStats) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        let _ = self.latency.add(&other.latency);
+        let _ = self.start_delay.add(&other.start_delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_spec() {
+        let profile = LoadProfile::parse_spec("100@10s, 100..200@10s, 200..400/100@5s").unwrap();
+        assert_eq!(profile.stages().len(), 5);
+        assert_eq!(
+            profile.stages()[1],
+            LoadStage::new(100.0, 200.0, Duration::from_secs(10)).unwrap()
+        );
+        assert_eq!(
+            profile.stages()[4],
+            LoadStage::constant(400.0, Duration::from_secs(5)).unwrap()
+        );
+        assert_eq!(profile.total_duration(), Duration::from_secs(35));
+        assert_eq!(profile.total_requests, 1000 + 1500 + 1000 + 1500 + 2000);
+
+        let profile = LoadProfile::parse_spec("50/s..10/s@2s").unwrap();
+        assert_eq!(profile.total_requests, 60);
+
+        assert!(LoadProfile::parse_spec("100").is_err());
+        assert!(LoadProfile::parse_spec("100@0s").is_err());
+        assert!(LoadProfile::parse_spec("-1@1s").is_err());
+        assert!(LoadProfile::parse_spec("1..10/0@1s").is_err());
+    }
+
+    #[test]
+    fn parse_yaml() {
+        let yaml = r#"
+stages:
+  - rate: 10
+    duration: 1s
+  - from: 10
+    to: 20
+    duration: 2s
+  - from: 20
+    to: 40
+    step: 10
+    duration: 1s
+  - "40..0@1s"
+"#;
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let profile = LoadProfile::parse_yaml(&docs[0]).unwrap();
+        assert_eq!(profile.stages().len(), 6);
+        assert_eq!(profile.total_duration(), Duration::from_secs(7));
+
+        let docs = YamlLoader::load_from_str("- duration: 1s").unwrap();
+        assert!(LoadProfile::parse_yaml(&docs[0]).is_err());
+    }
+
+    #[test]
+    fn schedule() {
+        let profile = LoadProfile::parse_spec("10@1s,0..20@1s").unwrap();
+        assert_eq!(profile.schedule(0), Some((0, Duration::ZERO)));
+        assert_eq!(profile.schedule(5), Some((0, Duration::from_millis(500))));
+        let (stage, offset) = profile.schedule(10).unwrap();
+        assert_eq!(stage, 1);
+        assert_eq!(offset, Duration::from_secs(1));
+        // N(t) = 10t^2 for the ramp stage, so the 5th arrival is at 1s + sqrt(0.5)s
+        let (stage, offset) = profile.schedule(15).unwrap();
+        assert_eq!(stage, 1);
+        assert!((offset.as_secs_f64() - 1.0 - 0.5f64.sqrt()).abs() < 1e-6);
+        assert!(profile.schedule(19).is_some());
+        assert!(profile.schedule(20).is_none());
+    }
+}
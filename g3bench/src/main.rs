@@ -37,6 +37,11 @@ fn build_cli_args() -> Command {
         .subcommand(g3bench::target::keyless::command())
         .subcommand(g3bench::target::thrift::command())
         .subcommand(g3bench::target::websocket::command())
+        .subcommand(g3bench::target::grpc::command())
+        .subcommand(g3bench::target::icap::command())
+        .subcommand(g3bench::target::smtp::command())
+        .subcommand(g3bench::target::imap::command())
+        .subcommand(g3bench::compare::command())
 }
 
 fn main() -> anyhow::Result<ExitCode> {
@@ -78,6 +83,7 @@ fn main() -> anyhow::Result<ExitCode> {
             generate_completion(sub_args);
             return Ok(ExitCode::SUCCESS);
         }
+        g3bench::compare::COMMAND => return g3bench::compare::run(sub_args),
         _ => {}
     }
 
@@ -116,6 +122,18 @@ fn main() -> anyhow::Result<ExitCode> {
             g3bench::target::websocket::COMMAND => {
                 g3bench::target::websocket::run(&proc_args, sub_args).await
             }
+            g3bench::target::grpc::COMMAND => {
+                g3bench::target::grpc::run(&proc_args, sub_args).await
+            }
+            g3bench::target::icap::COMMAND => {
+                g3bench::target::icap::run(&proc_args, sub_args).await
+            }
+            g3bench::target::smtp::COMMAND => {
+                g3bench::target::smtp::run(&proc_args, sub_args).await
+            }
+            g3bench::target::imap::COMMAND => {
+                g3bench::target::imap::run(&proc_args, sub_args).await
+            }
             cmd => Err(anyhow!("invalid subcommand {}", cmd)),
         }
     })
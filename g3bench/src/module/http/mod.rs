@@ -9,6 +9,10 @@ pub(crate) use stats::{HttpHistogram, HttpHistogramRecorder, HttpRuntimeStats};
 mod opts;
 pub(crate) use opts::{AppendHttpArgs, HttpClientArgs};
 
+mod scenario;
+pub(crate) use scenario::HttpRequestTemplate;
+use scenario::{HttpScenario, HttpTemplateDefaults};
+
 mod connection;
 pub(crate) use connection::{
     AppendH1ConnectArgs, AppendH2ConnectArgs, H1ConnectArgs, H2ConnectArgs,
@@ -0,0 +1,646 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{Context, anyhow};
+use http::{HeaderName, HeaderValue, Method, Request, StatusCode, Version};
+use url::Url;
+use yaml_rust::{Yaml, YamlLoader};
+
+use g3_types::collection::{SelectiveVec, SelectiveVecBuilder, WeightedValue};
+use g3_types::net::{HttpAuth, HttpBasicAuth, UpstreamAddr};
+
+/// the max total weight after reduced by the gcd, to limit the size of the cycle sequence
+const MAX_CYCLE_WEIGHT: u32 = 65536;
+
+pub(crate) struct HttpRequestTemplate {
+    pub(crate) name: String,
+    weight: u32,
+    pub(crate) method: Method,
+    pub(crate) target_url: Url,
+    pub(crate) target: UpstreamAddr,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    ok_status: Vec<StatusCode>,
+    body: Option<Vec<u8>>,
+    binary_payload: bool,
+    auth: HttpAuth,
+    pub(crate) proxy_auth: HttpAuth,
+}
+
+impl HttpRequestTemplate {
+    pub(super) fn new(name: String, url: Url) -> anyhow::Result<Self> {
+        let upstream = UpstreamAddr::try_from(&url)?;
+        let auth = HttpAuth::try_from(&url)
+            .map_err(|e| anyhow!("failed to detect upstream auth method: {e}"))?;
+        Ok(HttpRequestTemplate {
+            name,
+            weight: 1,
+            method: Method::GET,
+            target_url: url,
+            target: upstream,
+            headers: Vec::new(),
+            ok_status: Vec::new(),
+            body: None,
+            binary_payload: false,
+            auth,
+            proxy_auth: HttpAuth::None,
+        })
+    }
+
+    pub(super) fn set_method(&mut self, method: Method) {
+        self.method = method;
+    }
+
+    pub(super) fn set_body(&mut self, body: Vec<u8>, binary: bool) {
+        self.body = Some(body);
+        self.binary_payload = binary;
+    }
+
+    pub(super) fn set_headers(&mut self, headers: Vec<(HeaderName, HeaderValue)>) {
+        self.headers = headers;
+    }
+
+    pub(super) fn set_ok_status(&mut self, ok_status: Option<StatusCode>) {
+        self.ok_status = ok_status.into_iter().collect();
+    }
+
+    pub(crate) fn build_static_request(&self, version: Version) -> anyhow::Result<Request<()>> {
+        let path_and_query = if let Some(q) = self.target_url.query() {
+            format!("{}?{q}", self.target_url.path())
+        } else {
+            self.target_url.path().to_string()
+        };
+        let uri = http::Uri::builder()
+            .scheme(self.target_url.scheme())
+            .authority(self.target.to_string())
+            .path_and_query(path_and_query)
+            .build()
+            .map_err(|e| anyhow!("failed to build request: {e:?}"))?;
+
+        let mut req = Request::builder()
+            .version(version)
+            .method(self.method.clone())
+            .uri(uri)
+            .body(())
+            .map_err(|e| anyhow!("failed to build request: {e:?}"))?;
+
+        for (key, value) in self.headers.iter() {
+            req.headers_mut().append(key, value.clone());
+        }
+
+        if let Some(payload) = self.payload() {
+            if !req.headers().contains_key(http::header::CONTENT_LENGTH) {
+                req.headers_mut().append(
+                    http::header::CONTENT_LENGTH,
+                    HeaderValue::from(payload.len()),
+                );
+            }
+            if !req.headers().contains_key(http::header::CONTENT_TYPE) {
+                let ctype = if self.binary_payload {
+                    "application/octet-stream"
+                } else {
+                    "text/html; charset=utf-8"
+                };
+                req.headers_mut()
+                    .append(http::header::CONTENT_TYPE, HeaderValue::from_static(ctype));
+            }
+        }
+
+        if !req.headers().contains_key(http::header::AUTHORIZATION) {
+            match &self.auth {
+                HttpAuth::None => {}
+                HttpAuth::Basic(basic) => {
+                    let value = HeaderValue::try_from(basic)
+                        .map_err(|e| anyhow!("invalid auth value: {e:?}"))?;
+                    req.headers_mut().insert(http::header::AUTHORIZATION, value);
+                }
+            }
+        }
+
+        Ok(req)
+    }
+
+    pub(crate) fn payload(&self) -> Option<&Vec<u8>> {
+        self.body.as_ref()
+    }
+
+    pub(crate) fn check_status(&self, code: u16) -> anyhow::Result<()> {
+        if self.ok_status.is_empty() || self.ok_status.iter().any(|s| s.as_u16() == code) {
+            return Ok(());
+        }
+
+        if let [ok_status] = self.ok_status.as_slice() {
+            Err(anyhow!(
+                "Got rsp code {code} while {} is expected",
+                ok_status.as_u16()
+            ))
+        } else {
+            let expected = self
+                .ok_status
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            Err(anyhow!(
+                "Got rsp code {code} while one of {expected} is expected"
+            ))
+        }
+    }
+
+    fn parse_yaml(
+        map: &yaml_rust::yaml::Hash,
+        index: usize,
+        lookup_dir: &Path,
+        defaults: &HttpTemplateDefaults,
+    ) -> anyhow::Result<Self> {
+        let mut name = None;
+        let mut url = None;
+        let mut weight = 1;
+        let mut method = Method::GET;
+        let mut headers = Vec::new();
+        let mut body = None;
+        let mut ok_status = None;
+        let mut proxy_auth = HttpAuth::None;
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "name" => {
+                name = Some(g3_yaml::value::as_string(v)?);
+                Ok(())
+            }
+            "url" => {
+                url = Some(g3_yaml::value::as_url(v)?);
+                Ok(())
+            }
+            "weight" => {
+                weight = g3_yaml::value::as_u32(v)?;
+                if weight == 0 {
+                    return Err(anyhow!("weight should not be zero"));
+                }
+                Ok(())
+            }
+            "method" => {
+                let s = g3_yaml::value::as_string(v)?;
+                method = Method::from_str(&s.to_uppercase())
+                    .map_err(|e| anyhow!("invalid http method {s}: {e}"))?;
+                Ok(())
+            }
+            "headers" | "header" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!(
+                        "invalid yaml value type for headers: should be map"
+                    ));
+                };
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let name = HeaderName::from_str(k)
+                        .map_err(|e| anyhow!("invalid http header name {k}: {e}"))?;
+                    let value = g3_yaml::value::as_string(v)?;
+                    let value = HeaderValue::from_str(&value)
+                        .map_err(|e| anyhow!("invalid value for http header {k}: {e}"))?;
+                    headers.push((name, value));
+                    Ok(())
+                })
+                .context(format!("invalid value for key {k}"))
+            }
+            "body" => {
+                let s = g3_yaml::value::as_string(v)?;
+                body = Some((s.into_bytes(), false));
+                Ok(())
+            }
+            "body_file" => {
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)?;
+                let data = std::fs::read(&path)
+                    .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+                body = Some((data, true));
+                Ok(())
+            }
+            "ok_status" => {
+                ok_status = Some(parse_status_list(v)?);
+                Ok(())
+            }
+            "proxy_auth" => {
+                proxy_auth = parse_basic_auth(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        let url = match url {
+            Some(url) => url,
+            None => defaults
+                .url
+                .clone()
+                .ok_or_else(|| anyhow!("no url set and no default target url found"))?,
+        };
+        let name = name.unwrap_or_else(|| format!("template{index}"));
+        let mut template = HttpRequestTemplate::new(name, url)?;
+        template.weight = weight;
+        template.method = method;
+        if let Some((data, binary)) = body {
+            template.set_body(data, binary);
+        }
+        match ok_status {
+            Some(codes) => template.ok_status = codes,
+            None => template.set_ok_status(defaults.ok_status),
+        }
+        template.proxy_auth = proxy_auth;
+
+        // the headers in the template will take precedence over the common ones
+        for (name, value) in &defaults.headers {
+            if !headers.iter().any(|(n, _)| n == name) {
+                headers.push((name.clone(), value.clone()));
+            }
+        }
+        template.headers = headers;
+
+        Ok(template)
+    }
+}
+
+fn parse_status_list(v: &Yaml) -> anyhow::Result<Vec<StatusCode>> {
+    let parse_one = |v: &Yaml| {
+        let code = g3_yaml::value::as_u16(v)?;
+        StatusCode::from_u16(code).map_err(|e| anyhow!("invalid status code {code}: {e}"))
+    };
+    match v {
+        Yaml::Array(seq) => {
+            let mut codes = Vec::with_capacity(seq.len());
+            for (i, v) in seq.iter().enumerate() {
+                codes.push(parse_one(v).context(format!("invalid status code #{i}"))?);
+            }
+            Ok(codes)
+        }
+        _ => Ok(vec![parse_one(v)?]),
+    }
+}
+
+fn parse_basic_auth(v: &Yaml) -> anyhow::Result<HttpAuth> {
+    match v {
+        Yaml::Hash(map) => {
+            let username = g3_yaml::hash_get_required(map, "username")?;
+            let username = g3_yaml::value::as_username(username).context("invalid username")?;
+            let password = g3_yaml::hash_get_required(map, "password")?;
+            let password = g3_yaml::value::as_password(password).context("invalid password")?;
+            Ok(HttpAuth::Basic(HttpBasicAuth::new(username, password)))
+        }
+        Yaml::String(s) => {
+            let Some((username, password)) = s.split_once(':') else {
+                return Err(anyhow!(
+                    "the basic auth string should be in 'user:pass' format"
+                ));
+            };
+            let username = g3_yaml::value::as_username(&Yaml::String(username.to_string()))
+                .context("invalid username")?;
+            let password = g3_yaml::value::as_password(&Yaml::String(password.to_string()))
+                .context("invalid password")?;
+            Ok(HttpAuth::Basic(HttpBasicAuth::new(username, password)))
+        }
+        _ => Err(anyhow!("invalid yaml value type for basic auth")),
+    }
+}
+
+/// The values set in command line that will be used for all templates in the scenario file
+pub(super) struct HttpTemplateDefaults {
+    pub(super) url: Option<Url>,
+    pub(super) headers: Vec<(HeaderName, HeaderValue)>,
+    pub(super) ok_status: Option<StatusCode>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HttpScenarioPick {
+    Cycle,
+    Random,
+}
+
+impl FromStr for HttpScenarioPick {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cycle" | "serial" | "round_robin" => Ok(HttpScenarioPick::Cycle),
+            "random" => Ok(HttpScenarioPick::Random),
+            _ => Err(anyhow!("unsupported pick mode {s}")),
+        }
+    }
+}
+
+enum HttpTemplatePicker {
+    Single,
+    Cycle {
+        sequence: Vec<usize>,
+        next_id: AtomicUsize,
+    },
+    Random(SelectiveVec<WeightedValue<usize>>),
+}
+
+pub(crate) struct HttpScenario {
+    templates: Vec<HttpRequestTemplate>,
+    picker: HttpTemplatePicker,
+    from_file: bool,
+}
+
+impl HttpScenario {
+    pub(super) fn single(template: HttpRequestTemplate) -> Self {
+        HttpScenario {
+            templates: vec![template],
+            picker: HttpTemplatePicker::Single,
+            from_file: false,
+        }
+    }
+
+    fn new(templates: Vec<HttpRequestTemplate>, pick: HttpScenarioPick) -> anyhow::Result<Self> {
+        if templates.is_empty() {
+            return Err(anyhow!("no request template set"));
+        }
+        for (i, t) in templates.iter().enumerate() {
+            if templates[..i].iter().any(|v| v.name == t.name) {
+                return Err(anyhow!("duplicate request template name {}", t.name));
+            }
+        }
+
+        let picker = if templates.len() == 1 {
+            HttpTemplatePicker::Single
+        } else {
+            match pick {
+                HttpScenarioPick::Cycle => {
+                    let weights = templates.iter().map(|t| t.weight).collect::<Vec<_>>();
+                    HttpTemplatePicker::Cycle {
+                        sequence: build_cycle_sequence(&weights)?,
+                        next_id: AtomicUsize::new(0),
+                    }
+                }
+                HttpScenarioPick::Random => {
+                    let mut builder = SelectiveVecBuilder::with_capacity(templates.len());
+                    for (i, t) in templates.iter().enumerate() {
+                        builder.insert(WeightedValue::with_weight(i, t.weight as f64));
+                    }
+                    let nodes = builder
+                        .build()
+                        .ok_or_else(|| anyhow!("no request template set"))?;
+                    HttpTemplatePicker::Random(nodes)
+                }
+            }
+        };
+
+        Ok(HttpScenario {
+            templates,
+            picker,
+            from_file: true,
+        })
+    }
+
+    pub(super) fn load_yaml_file(
+        path: &Path,
+        defaults: &HttpTemplateDefaults,
+    ) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+        let docs = YamlLoader::load_from_str(&content)
+            .map_err(|e| anyhow!("invalid yaml file {}: {e}", path.display()))?;
+        let Some(doc) = docs.first() else {
+            return Err(anyhow!("no yaml doc found in file {}", path.display()));
+        };
+        let lookup_dir = path.parent().unwrap_or(Path::new("."));
+        HttpScenario::parse_yaml(doc, lookup_dir, defaults)
+    }
+
+    fn parse_yaml(
+        v: &Yaml,
+        lookup_dir: &Path,
+        defaults: &HttpTemplateDefaults,
+    ) -> anyhow::Result<Self> {
+        match v {
+            Yaml::Hash(map) => {
+                let mut pick = HttpScenarioPick::Cycle;
+                let mut templates = Vec::new();
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "pick" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        pick = HttpScenarioPick::from_str(&s)?;
+                        Ok(())
+                    }
+                    "templates" => {
+                        templates = parse_yaml_templates(v, lookup_dir, defaults)?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                HttpScenario::new(templates, pick)
+            }
+            Yaml::Array(_) => {
+                let templates = parse_yaml_templates(v, lookup_dir, defaults)?;
+                HttpScenario::new(templates, HttpScenarioPick::Cycle)
+            }
+            _ => Err(anyhow!("invalid yaml value type for http scenario")),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn templates(&self) -> &[HttpRequestTemplate] {
+        &self.templates
+    }
+
+    /// Get the template names if per template stats is needed
+    pub(crate) fn stats_names(&self) -> Vec<String> {
+        if self.from_file {
+            self.templates.iter().map(|t| t.name.clone()).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Pick the index of the template to use for the next request
+    pub(crate) fn pick(&self) -> usize {
+        match &self.picker {
+            HttpTemplatePicker::Single => 0,
+            HttpTemplatePicker::Cycle { sequence, next_id } => {
+                let id = next_id.fetch_add(1, Ordering::Relaxed);
+                sequence[id % sequence.len()]
+            }
+            HttpTemplatePicker::Random(nodes) => *nodes.pick_random().inner(),
+        }
+    }
+
+    pub(crate) fn check_single_target(&self) -> anyhow::Result<()> {
+        let first = &self.templates[0];
+        for t in &self.templates[1..] {
+            if t.target_url.scheme() != first.target_url.scheme() || t.target != first.target {
+                return Err(anyhow!(
+                    "the target of template {} should be the same as template {}, \
+                    as it's only allowed to be different in forward proxy mode",
+                    t.name,
+                    first.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_no_proxy_auth(&self) -> anyhow::Result<()> {
+        for t in &self.templates {
+            if !matches!(t.proxy_auth, HttpAuth::None) {
+                return Err(anyhow!(
+                    "proxy auth in template {} is only allowed in forward proxy mode",
+                    t.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_yaml_templates(
+    v: &Yaml,
+    lookup_dir: &Path,
+    defaults: &HttpTemplateDefaults,
+) -> anyhow::Result<Vec<HttpRequestTemplate>> {
+    let Yaml::Array(seq) = v else {
+        return Err(anyhow!(
+            "invalid yaml value type for templates: should be seq"
+        ));
+    };
+
+    let mut templates = Vec::with_capacity(seq.len());
+    for (i, v) in seq.iter().enumerate() {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("invalid yaml value type for template #{i}"));
+        };
+        let template = HttpRequestTemplate::parse_yaml(map, i, lookup_dir, defaults)
+            .context(format!("invalid template #{i}"))?;
+        templates.push(template);
+    }
+    Ok(templates)
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Build the smooth weighted round-robin sequence, which is the same as nginx
+fn build_cycle_sequence(weights: &[u32]) -> anyhow::Result<Vec<usize>> {
+    let g = weights.iter().fold(0, |g, w| gcd(g, *w));
+    let weights = weights.iter().map(|w| (w / g) as i64).collect::<Vec<_>>();
+    let total: i64 = weights.iter().sum();
+    if total > MAX_CYCLE_WEIGHT as i64 {
+        return Err(anyhow!(
+            "the total weight {total} is too large, the max allowed is {MAX_CYCLE_WEIGHT}"
+        ));
+    }
+
+    let mut current = vec![0i64; weights.len()];
+    let mut sequence = Vec::with_capacity(total as usize);
+    for _ in 0..total {
+        let mut best = 0;
+        for (i, w) in weights.iter().enumerate() {
+            current[i] += w;
+            if current[i] > current[best] {
+                best = i;
+            }
+        }
+        current[best] -= total;
+        sequence.push(best);
+    }
+    Ok(sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> HttpTemplateDefaults {
+        HttpTemplateDefaults {
+            url: Some(Url::parse("http://127.0.0.1:8080/").unwrap()),
+            headers: vec![(
+                HeaderName::from_static("x-common"),
+                HeaderValue::from_static("1"),
+            )],
+            ok_status: Some(StatusCode::OK),
+        }
+    }
+
+    #[test]
+    fn cycle_sequence() {
+        let sequence = build_cycle_sequence(&[5, 1, 1]).unwrap();
+        assert_eq!(sequence, vec![0, 0, 1, 0, 2, 0, 0]);
+
+        let sequence = build_cycle_sequence(&[2, 4]).unwrap();
+        assert_eq!(sequence, vec![1, 0, 1]);
+
+        assert!(build_cycle_sequence(&[MAX_CYCLE_WEIGHT, 1]).is_err());
+    }
+
+    #[test]
+    fn parse_yaml() {
+        let yaml = r#"
+pick: cycle
+templates:
+  - name: index
+    weight: 3
+  - name: login
+    url: http://127.0.0.1:8080/login
+    method: post
+    body: "user=test"
+    headers:
+      x-common: 2
+      content-type: application/x-www-form-urlencoded
+    ok_status: [200, 302]
+    proxy_auth: "user:pass"
+"#;
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let scenario = HttpScenario::parse_yaml(&docs[0], Path::new("."), &defaults()).unwrap();
+        assert_eq!(scenario.templates().len(), 2);
+        assert_eq!(scenario.stats_names(), vec!["index", "login"]);
+
+        let index = &scenario.templates()[0];
+        assert_eq!(index.method, Method::GET);
+        assert!(index.payload().is_none());
+        assert!(index.check_status(200).is_ok());
+        assert!(index.check_status(302).is_err());
+        let req = index.build_static_request(Version::HTTP_11).unwrap();
+        assert_eq!(req.headers().get("x-common").unwrap(), "1");
+
+        let login = &scenario.templates()[1];
+        assert_eq!(login.method, Method::POST);
+        assert_eq!(login.payload().unwrap().as_slice(), b"user=test");
+        assert!(login.check_status(302).is_ok());
+        assert!(login.check_status(404).is_err());
+        assert!(matches!(login.proxy_auth, HttpAuth::Basic(_)));
+        let req = login.build_static_request(Version::HTTP_11).unwrap();
+        assert_eq!(req.uri().path(), "/login");
+        assert_eq!(req.headers().get_all("x-common").iter().count(), 1);
+        assert_eq!(req.headers().get("x-common").unwrap(), "2");
+        assert_eq!(
+            req.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "application/x-www-form-urlencoded"
+        );
+
+        let picked = (0..8).map(|_| scenario.pick()).collect::<Vec<_>>();
+        assert_eq!(picked, vec![0, 0, 1, 0, 0, 0, 1, 0]);
+
+        assert!(scenario.check_single_target().is_ok());
+        assert!(scenario.check_no_proxy_auth().is_err());
+    }
+
+    #[test]
+    fn parse_yaml_err() {
+        let docs = YamlLoader::load_from_str("templates: []").unwrap();
+        assert!(HttpScenario::parse_yaml(&docs[0], Path::new("."), &defaults()).is_err());
+
+        let docs = YamlLoader::load_from_str("- name: a\n- name: a").unwrap();
+        assert!(HttpScenario::parse_yaml(&docs[0], Path::new("."), &defaults()).is_err());
+
+        let docs = YamlLoader::load_from_str("- weight: 0").unwrap();
+        assert!(HttpScenario::parse_yaml(&docs[0], Path::new("."), &defaults()).is_err());
+
+        let docs = YamlLoader::load_from_str("- url: https://127.0.0.1/\n- {}").unwrap();
+        let scenario = HttpScenario::parse_yaml(&docs[0], Path::new("."), &defaults()).unwrap();
+        assert!(scenario.check_single_target().is_err());
+    }
+}
@@ -9,6 +9,7 @@ use g3_histogram::{HistogramRecorder, KeepingHistogram};
 use g3_statsd_client::StatsdClient;
 use g3_std_ext::time::DurationExt;
 
+use crate::report::BenchReport;
 use crate::target::BenchHistogram;
 
 pub(crate) struct HttpHistogram {
@@ -17,21 +18,34 @@ pub(crate) struct HttpHistogram {
     recv_hdr_time: KeepingHistogram<u64>,
     total_time: KeepingHistogram<u64>,
     conn_reuse_count: KeepingHistogram<u64>,
+    template_total_time: Vec<(String, KeepingHistogram<u64>)>,
 }
 
 impl HttpHistogram {
     pub(crate) fn new() -> (Self, HttpHistogramRecorder) {
+        HttpHistogram::with_templates(Vec::new())
+    }
+
+    pub(crate) fn with_templates(templates: Vec<String>) -> (Self, HttpHistogramRecorder) {
         let (send_hdr_time_h, send_hdr_time_r) = KeepingHistogram::new();
         let (send_all_time_h, send_all_time_r) = KeepingHistogram::new();
         let (recv_hdr_time_h, recv_hdr_time_r) = KeepingHistogram::new();
         let (total_time_h, total_time_r) = KeepingHistogram::new();
         let (conn_reuse_count_h, conn_reuse_count_r) = KeepingHistogram::new();
+        let mut template_total_time_h = Vec::with_capacity(templates.len());
+        let mut template_total_time_r = Vec::with_capacity(templates.len());
+        for name in templates {
+            let (h, r) = KeepingHistogram::new();
+            template_total_time_h.push((name, h));
+            template_total_time_r.push(r);
+        }
         let h = HttpHistogram {
             send_hdr_time: send_hdr_time_h,
             send_all_time: send_all_time_h,
             recv_hdr_time: recv_hdr_time_h,
             total_time: total_time_h,
             conn_reuse_count: conn_reuse_count_h,
+            template_total_time: template_total_time_h,
         };
         let r = HttpHistogramRecorder {
             send_hdr_time: send_hdr_time_r,
@@ -39,6 +53,7 @@ impl HttpHistogram {
             recv_hdr_time: recv_hdr_time_r,
             total_time: total_time_r,
             conn_reuse_count: conn_reuse_count_r,
+            template_total_time: template_total_time_r,
         };
         (h, r)
     }
@@ -51,6 +66,9 @@ impl BenchHistogram for HttpHistogram {
         self.recv_hdr_time.refresh().unwrap();
         self.total_time.refresh().unwrap();
         self.conn_reuse_count.refresh().unwrap();
+        for (_, h) in &mut self.template_total_time {
+            h.refresh().unwrap();
+        }
     }
 
     fn emit(&self, client: &mut StatsdClient) {
@@ -68,9 +86,26 @@ impl BenchHistogram for HttpHistogram {
         Self::summary_duration_line("SendAll:", self.send_all_time.inner());
         Self::summary_duration_line("RecvHdr:", self.recv_hdr_time.inner());
         Self::summary_duration_line("Total:", self.total_time.inner());
+        if !self.template_total_time.is_empty() {
+            Self::summary_histogram_title("# Template Total Times");
+            for (name, h) in &self.template_total_time {
+                Self::summary_duration_line(&format!("{name}:"), h.inner());
+            }
+        }
         Self::summary_newline();
         Self::summary_total_percentage(self.total_time.inner());
     }
+
+    fn report(&self, report: &mut BenchReport) {
+        report.add_count_histogram("connection.reuse", self.conn_reuse_count.inner());
+        report.add_duration_histogram("time.send_hdr", self.send_hdr_time.inner());
+        report.add_duration_histogram("time.send_all", self.send_all_time.inner());
+        report.add_duration_histogram("time.recv_hdr", self.recv_hdr_time.inner());
+        report.add_duration_histogram("time.total", self.total_time.inner());
+        for (name, h) in &self.template_total_time {
+            report.add_duration_histogram(format!("template.{name}.time.total"), h.inner());
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -80,6 +115,7 @@ pub(crate) struct HttpHistogramRecorder {
     recv_hdr_time: HistogramRecorder<u64>,
     total_time: HistogramRecorder<u64>,
     conn_reuse_count: HistogramRecorder<u64>,
+    template_total_time: Vec<HistogramRecorder<u64>>,
 }
 
 impl HttpHistogramRecorder {
@@ -99,6 +135,12 @@ impl HttpHistogramRecorder {
         let _ = self.total_time.record(dur.as_nanos_u64());
     }
 
+    pub(crate) fn record_template_total_time(&mut self, template: usize, dur: Duration) {
+        if let Some(r) = self.template_total_time.get_mut(template) {
+            let _ = r.record(dur.as_nanos_u64());
+        }
+    }
+
     pub(crate) fn record_conn_reuse_count(&mut self, count: u64) {
         let _ = self.conn_reuse_count.record(count);
     }
@@ -10,6 +10,7 @@ use g3_io_ext::{LimitedReaderStats, LimitedRecvStats, LimitedSendStats, LimitedW
 use g3_statsd_client::StatsdClient;
 
 use crate::module::ssl::SslSessionStats;
+use crate::report::BenchReport;
 use crate::target::BenchRuntimeStats;
 
 #[derive(Default)]
@@ -32,6 +33,12 @@ struct HttpTcpIoStats {
     write_total: AtomicU64,
 }
 
+struct HttpTemplateStats {
+    name: String,
+    passed: AtomicU64,
+    failed: AtomicU64,
+}
+
 enum HttpIoStats {
     Tcp(HttpTcpIoStats),
     #[allow(unused)]
@@ -56,6 +63,7 @@ pub(crate) struct HttpRuntimeStats {
     conn_close_timeout: AtomicU64,
 
     io: HttpIoStats,
+    templates: Vec<HttpTemplateStats>,
 }
 
 impl HttpRuntimeStats {
@@ -84,9 +92,22 @@ impl HttpRuntimeStats {
             conn_close_error: AtomicU64::new(0),
             conn_close_timeout: AtomicU64::new(0),
             io,
+            templates: Vec::new(),
         }
     }
 
+    pub(crate) fn with_templates(mut self, templates: Vec<String>) -> Self {
+        self.templates = templates
+            .into_iter()
+            .map(|name| HttpTemplateStats {
+                name,
+                passed: AtomicU64::new(0),
+                failed: AtomicU64::new(0),
+            })
+            .collect();
+        self
+    }
+
     pub(crate) fn add_task_total(&self) {
         self.task_total.fetch_add(1, Ordering::Relaxed);
     }
@@ -107,6 +128,18 @@ impl HttpRuntimeStats {
         self.task_failed.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub(crate) fn add_template_passed(&self, template: usize) {
+        if let Some(t) = self.templates.get(template) {
+            t.passed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn add_template_failed(&self, template: usize) {
+        if let Some(t) = self.templates.get(template) {
+            t.failed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     pub(crate) fn add_conn_attempt(&self) {
         self.conn_attempt.fetch_add(1, Ordering::Relaxed);
     }
@@ -288,5 +321,65 @@ impl BenchRuntimeStats for HttpRuntimeStats {
                 );
             }
         }
+
+        if !self.templates.is_empty() {
+            println!("# Request Templates");
+            for t in &self.templates {
+                let passed = t.passed.load(Ordering::Relaxed);
+                let failed = t.failed.load(Ordering::Relaxed);
+                println!(
+                    "{}: passed {passed}, failed {failed}, rate {:.3}/s",
+                    t.name,
+                    passed as f64 / total_secs
+                );
+            }
+        }
+    }
+
+    fn report(&self, report: &mut BenchReport) {
+        macro_rules! report_total {
+            ($obj:expr, $field:ident, $total:ident, $name:literal) => {
+                let v = $obj.$total.load(Ordering::Relaxed) + $obj.$field.load(Ordering::Relaxed);
+                report.add_counter($name, v);
+            };
+        }
+
+        report_total!(self, conn_attempt, conn_attempt_total, "connection.attempt");
+        report_total!(self, conn_success, conn_success_total, "connection.success");
+        report.add_counter(
+            "connection.close_error",
+            self.conn_close_error.load(Ordering::Relaxed),
+        );
+        report.add_counter(
+            "connection.close_timeout",
+            self.conn_close_timeout.load(Ordering::Relaxed),
+        );
+
+        self.proxy_ssl_session.report("proxy_tls", report);
+        self.target_ssl_session.report("target_tls", report);
+
+        match &self.io {
+            HttpIoStats::Tcp(tcp) => {
+                report_total!(tcp, write, write_total, "io.tcp.write");
+                report_total!(tcp, read, read_total, "io.tcp.read");
+            }
+            HttpIoStats::Udp(udp) => {
+                report_total!(udp, send_bytes, send_bytes_total, "io.udp.send_bytes");
+                report_total!(udp, send_packets, send_packets_total, "io.udp.send_packets");
+                report_total!(udp, recv_bytes, recv_bytes_total, "io.udp.recv_bytes");
+                report_total!(udp, recv_packets, recv_packets_total, "io.udp.recv_packets");
+            }
+        }
+
+        for t in &self.templates {
+            report.add_counter(
+                format!("template.{}.passed", t.name),
+                t.passed.load(Ordering::Relaxed),
+            );
+            report.add_counter(
+                format!("template.{}.failed", t.name),
+                t.failed.load(Ordering::Relaxed),
+            );
+        }
     }
 }
@@ -0,0 +1,265 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, anyhow};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use url::Url;
+
+use g3_types::collection::{SelectiveVec, WeightedValue};
+use g3_types::net::{OpensslClientConfigBuilder, Proxy, UpstreamAddr};
+
+use crate::ProcArgs;
+use crate::module::openssl::{AppendOpensslArgs, OpensslTlsClientArgs};
+use crate::module::proxy_protocol::{AppendProxyProtocolArgs, ProxyProtocolArgs};
+use crate::module::socket::{AppendSocketArgs, SocketArgs};
+
+const MAIL_ARG_PROXY: &str = "proxy";
+const MAIL_ARG_TLS: &str = "tls";
+const MAIL_ARG_STARTTLS: &str = "starttls";
+
+pub(crate) trait MailStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T> MailStream for T where T: AsyncRead + AsyncWrite + Unpin + Send {}
+
+pub(crate) type BoxMailStream = Box<dyn MailStream>;
+
+pub(crate) trait AppendMailConnectArgs {
+    fn append_mail_connect_args(self) -> Self;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MailTlsMode {
+    None,
+    /// TLS handshake right after the connection is established
+    Implicit,
+    /// TLS handshake after the STARTTLS command
+    StartTls,
+}
+
+pub(crate) struct MailConnectArgs {
+    pub(crate) target: UpstreamAddr,
+    pub(crate) tls_mode: MailTlsMode,
+    proxy: Option<Proxy>,
+
+    socket: SocketArgs,
+    target_tls: OpensslTlsClientArgs,
+    proxy_tls: OpensslTlsClientArgs,
+    proxy_protocol: ProxyProtocolArgs,
+
+    peer_addrs: Option<SelectiveVec<WeightedValue<SocketAddr>>>,
+}
+
+impl MailConnectArgs {
+    pub(crate) fn new(target: UpstreamAddr) -> Self {
+        MailConnectArgs {
+            target,
+            tls_mode: MailTlsMode::None,
+            proxy: None,
+            socket: SocketArgs::default(),
+            target_tls: OpensslTlsClientArgs::default(),
+            proxy_tls: OpensslTlsClientArgs::default(),
+            proxy_protocol: ProxyProtocolArgs::default(),
+            peer_addrs: None,
+        }
+    }
+
+    pub(crate) async fn resolve_target_address(
+        &mut self,
+        proc_args: &ProcArgs,
+    ) -> anyhow::Result<()> {
+        let host = if let Some(proxy) = &self.proxy {
+            proxy.peer()
+        } else {
+            &self.target
+        };
+        let addrs = proc_args.resolve(host).await?;
+        self.peer_addrs = Some(addrs);
+        Ok(())
+    }
+
+    async fn new_tcp_connection(&self, proc_args: &ProcArgs) -> anyhow::Result<TcpStream> {
+        let addrs = self
+            .peer_addrs
+            .as_ref()
+            .ok_or_else(|| anyhow!("no peer address set"))?;
+        let peer = *proc_args.select_peer(addrs);
+
+        let mut stream = self.socket.tcp_connect_to(peer).await?;
+
+        if let Some(data) = self.proxy_protocol.data() {
+            stream
+                .write_all(data) // no need to flush data
+                .await
+                .map_err(|e| anyhow!("failed to send proxy protocol data: {e:?}"))?;
+        }
+
+        Ok(stream)
+    }
+
+    /// Connect to the mail server, either directly or through the proxy.
+    /// The TLS handshake will also be done if implicit TLS is in use.
+    pub(crate) async fn new_connection(
+        &self,
+        proc_args: &ProcArgs,
+    ) -> anyhow::Result<BoxMailStream> {
+        let stream: BoxMailStream = match &self.proxy {
+            Some(Proxy::Http(http_proxy)) => {
+                let stream = self.new_tcp_connection(proc_args).await.context(format!(
+                    "failed to connect to http proxy {}",
+                    http_proxy.peer()
+                ))?;
+
+                let stream: BoxMailStream = if let Some(tls_client) = &self.proxy_tls.client {
+                    let tls_stream = self
+                        .proxy_tls
+                        .connect_target(tls_client, stream, http_proxy.peer())
+                        .await?;
+                    Box::new(tls_stream)
+                } else {
+                    Box::new(stream)
+                };
+
+                let mut buf_stream = BufReader::new(stream);
+                g3_http::connect::client::http_connect_to(
+                    &mut buf_stream,
+                    &http_proxy.auth,
+                    &self.target,
+                )
+                .await
+                .map_err(|e| anyhow!("http connect to {} failed: {e}", http_proxy.peer()))?;
+                buf_stream.into_inner()
+            }
+            Some(Proxy::Socks4(socks4_proxy)) => {
+                let mut stream = self.new_tcp_connection(proc_args).await.context(format!(
+                    "failed to connect to socks4 proxy {}",
+                    socks4_proxy.peer()
+                ))?;
+
+                g3_socks::v4a::client::socks4a_connect_to(&mut stream, &self.target)
+                    .await
+                    .map_err(|e| {
+                        anyhow!("socks4a connect to {} failed: {e}", socks4_proxy.peer())
+                    })?;
+                Box::new(stream)
+            }
+            Some(Proxy::Socks5(socks5_proxy)) => {
+                let mut stream = self.new_tcp_connection(proc_args).await.context(format!(
+                    "failed to connect to socks5 proxy {}",
+                    socks5_proxy.peer()
+                ))?;
+
+                g3_socks::v5::client::socks5_connect_to(
+                    &mut stream,
+                    &socks5_proxy.auth,
+                    &self.target,
+                )
+                .await
+                .map_err(|e| anyhow!("socks5 connect to {} failed: {e}", socks5_proxy.peer()))?;
+                Box::new(stream)
+            }
+            None => {
+                let stream = self
+                    .new_tcp_connection(proc_args)
+                    .await
+                    .context(format!("failed to connect to target host {}", self.target))?;
+                Box::new(stream)
+            }
+        };
+
+        if self.tls_mode == MailTlsMode::Implicit {
+            self.tls_handshake(stream).await
+        } else {
+            Ok(stream)
+        }
+    }
+
+    /// Do TLS handshake with the mail server on an established connection
+    pub(crate) async fn tls_handshake(
+        &self,
+        stream: BoxMailStream,
+    ) -> anyhow::Result<BoxMailStream> {
+        let tls_client = self
+            .target_tls
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow!("no tls client config set"))?;
+        let tls_stream = self
+            .target_tls
+            .connect_target(tls_client, stream, &self.target)
+            .await?;
+        Ok(Box::new(tls_stream))
+    }
+
+    pub(crate) fn parse_args(&mut self, args: &ArgMatches) -> anyhow::Result<()> {
+        if let Some(v) = args.get_one::<String>(MAIL_ARG_PROXY) {
+            let url = Url::parse(v).context(format!("invalid {MAIL_ARG_PROXY} value"))?;
+            let proxy = Proxy::try_from(&url).map_err(|e| anyhow!("invalid proxy: {e}"))?;
+            if let Proxy::Http(mut http_proxy) = proxy {
+                self.proxy_tls.config = http_proxy.tls_config.take();
+                self.proxy = Some(Proxy::Http(http_proxy));
+            } else {
+                self.proxy = Some(proxy);
+            }
+        }
+
+        if args.get_flag(MAIL_ARG_TLS) {
+            self.tls_mode = MailTlsMode::Implicit;
+        } else if args.get_flag(MAIL_ARG_STARTTLS) {
+            self.tls_mode = MailTlsMode::StartTls;
+        }
+        if self.tls_mode != MailTlsMode::None {
+            self.target_tls.config = Some(OpensslClientConfigBuilder::with_cache_for_one_site());
+        }
+
+        self.socket
+            .parse_args(args)
+            .context("invalid socket config")?;
+        self.target_tls
+            .parse_tls_args(args)
+            .context("invalid target tls config")?;
+        self.proxy_tls
+            .parse_proxy_tls_args(args)
+            .context("invalid proxy tls config")?;
+        self.proxy_protocol
+            .parse_args(args)
+            .context("invalid proxy protocol config")?;
+        Ok(())
+    }
+}
+
+impl AppendMailConnectArgs for Command {
+    fn append_mail_connect_args(self) -> Self {
+        self.arg(
+            Arg::new(MAIL_ARG_PROXY)
+                .value_name("PROXY URL")
+                .short('x')
+                .help("Connect through a socks or http CONNECT proxy")
+                .long(MAIL_ARG_PROXY)
+                .num_args(1),
+        )
+        .arg(
+            Arg::new(MAIL_ARG_TLS)
+                .help("Use implicit TLS")
+                .long(MAIL_ARG_TLS)
+                .action(ArgAction::SetTrue)
+                .conflicts_with(MAIL_ARG_STARTTLS),
+        )
+        .arg(
+            Arg::new(MAIL_ARG_STARTTLS)
+                .help("Upgrade to TLS by using the STARTTLS command")
+                .long(MAIL_ARG_STARTTLS)
+                .action(ArgAction::SetTrue)
+                .conflicts_with(MAIL_ARG_TLS),
+        )
+        .append_socket_args()
+        .append_openssl_args()
+        .append_proxy_openssl_args()
+        .append_proxy_protocol_args()
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::Write;
+
+const BODY_LINE: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789abcd";
+
+/// Generate a plain text message of about the given size.
+///
+/// All lines end with CRLF and no line starts with a dot,
+/// so it can be sent in SMTP DATA or as an IMAP literal directly.
+pub(crate) fn generate_message(from: &str, to: &[String], size: usize) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(size + 256);
+    let _ = write!(buf, "From: <{from}>\r\n");
+    if !to.is_empty() {
+        buf.extend_from_slice(b"To: ");
+        for (i, addr) in to.iter().enumerate() {
+            if i > 0 {
+                buf.extend_from_slice(b", ");
+            }
+            let _ = write!(buf, "<{addr}>");
+        }
+        buf.extend_from_slice(b"\r\n");
+    }
+    buf.extend_from_slice(b"Subject: g3bench test message\r\n");
+    buf.extend_from_slice(b"Content-Type: text/plain; charset=us-ascii\r\n\r\n");
+
+    while buf.len() + 2 < size {
+        let left = size - buf.len() - 2;
+        let n = if left > BODY_LINE.len() && left - BODY_LINE.len() < 3 {
+            // leave enough space for the last line
+            BODY_LINE.len() - 3
+        } else {
+            left.min(BODY_LINE.len())
+        };
+        buf.extend_from_slice(&BODY_LINE[..n]);
+        buf.extend_from_slice(b"\r\n");
+    }
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate() {
+        let to = vec!["a@example.net".to_string(), "b@example.net".to_string()];
+        let msg = generate_message("g3bench@example.net", &to, 1024);
+        assert_eq!(msg.len(), 1024);
+        assert!(msg.ends_with(b"\r\n"));
+        assert!(msg.starts_with(b"From: <g3bench@example.net>\r\n"));
+        let to_line = b"To: <a@example.net>, <b@example.net>\r\n";
+        assert!(msg.windows(to_line.len()).any(|w| w == to_line));
+        for line in msg.split(|c| *c == b'\n') {
+            assert!(!line.starts_with(b"."));
+        }
+
+        for size in 1000..1100 {
+            let msg = generate_message("g3bench@example.net", &to, size);
+            assert_eq!(msg.len(), size);
+        }
+
+        let msg = generate_message("g3bench@example.net", &[], 0);
+        assert!(msg.ends_with(b"\r\n\r\n"));
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod connect;
+pub(crate) use connect::{AppendMailConnectArgs, BoxMailStream, MailConnectArgs, MailTlsMode};
+
+mod message;
+pub(crate) use message::generate_message;
+
+mod stats;
+pub(crate) use stats::{MailHistogram, MailHistogramRecorder, MailRuntimeStats};
@@ -0,0 +1,95 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use g3_histogram::{HistogramRecorder, KeepingHistogram};
+use g3_statsd_client::StatsdClient;
+use g3_std_ext::time::DurationExt;
+
+use crate::report::BenchReport;
+use crate::target::BenchHistogram;
+
+pub(crate) struct MailHistogram {
+    command_time: Vec<(&'static str, KeepingHistogram<u64>)>,
+    total_time: KeepingHistogram<u64>,
+}
+
+impl MailHistogram {
+    /// Create histograms for each of the commands, which should be indexed by the recorder
+    pub(crate) fn new(commands: &[&'static str]) -> (Self, MailHistogramRecorder) {
+        let mut command_time_h = Vec::with_capacity(commands.len());
+        let mut command_time_r = Vec::with_capacity(commands.len());
+        for name in commands {
+            let (h, r) = KeepingHistogram::new();
+            command_time_h.push((*name, h));
+            command_time_r.push(r);
+        }
+        let (total_time_h, total_time_r) = KeepingHistogram::new();
+        let h = MailHistogram {
+            command_time: command_time_h,
+            total_time: total_time_h,
+        };
+        let r = MailHistogramRecorder {
+            command_time: command_time_r,
+            total_time: total_time_r,
+        };
+        (h, r)
+    }
+}
+
+impl BenchHistogram for MailHistogram {
+    fn refresh(&mut self) {
+        for (_, h) in &mut self.command_time {
+            h.refresh().unwrap();
+        }
+        self.total_time.refresh().unwrap();
+    }
+
+    fn emit(&self, client: &mut StatsdClient) {
+        for (name, h) in &self.command_time {
+            let key = format!("mail.time.{}", name.to_ascii_lowercase());
+            self.emit_histogram(client, h.inner(), &key);
+        }
+        self.emit_histogram(client, self.total_time.inner(), "mail.time.total");
+    }
+
+    fn summary(&self) {
+        Self::summary_histogram_title("# Command Times");
+        for (name, h) in &self.command_time {
+            if !h.inner().is_empty() {
+                Self::summary_duration_line(&format!("{name}:"), h.inner());
+            }
+        }
+        Self::summary_duration_line("Total:", self.total_time.inner());
+        Self::summary_newline();
+        Self::summary_total_percentage(self.total_time.inner());
+    }
+
+    fn report(&self, report: &mut BenchReport) {
+        for (name, h) in &self.command_time {
+            report.add_duration_histogram(format!("time.{}", name.to_ascii_lowercase()), h.inner());
+        }
+        report.add_duration_histogram("time.total", self.total_time.inner());
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct MailHistogramRecorder {
+    command_time: Vec<HistogramRecorder<u64>>,
+    total_time: HistogramRecorder<u64>,
+}
+
+impl MailHistogramRecorder {
+    pub(crate) fn record_command_time(&mut self, command: usize, dur: Duration) {
+        if let Some(r) = self.command_time.get_mut(command) {
+            let _ = r.record(dur.as_nanos_u64());
+        }
+    }
+
+    pub(crate) fn record_total_time(&mut self, dur: Duration) {
+        let _ = self.total_time.record(dur.as_nanos_u64());
+    }
+}
@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod histogram;
+mod runtime;
+
+pub(crate) use histogram::{MailHistogram, MailHistogramRecorder};
+pub(crate) use runtime::MailRuntimeStats;
@@ -0,0 +1,127 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+use g3_statsd_client::StatsdClient;
+
+use crate::report::BenchReport;
+use crate::target::BenchRuntimeStats;
+
+pub(crate) struct MailRuntimeStats {
+    target: &'static str,
+    task_total: AtomicU64,
+    task_alive: AtomicI64,
+    task_passed: AtomicU64,
+    task_failed: AtomicU64,
+
+    message_sent: AtomicU64,
+    message_sent_total: AtomicU64,
+    message_bytes: AtomicU64,
+    message_bytes_total: AtomicU64,
+}
+
+impl MailRuntimeStats {
+    pub(crate) fn new(target: &'static str) -> Self {
+        MailRuntimeStats {
+            target,
+            task_total: AtomicU64::new(0),
+            task_alive: AtomicI64::new(0),
+            task_passed: AtomicU64::new(0),
+            task_failed: AtomicU64::new(0),
+            message_sent: AtomicU64::new(0),
+            message_sent_total: AtomicU64::new(0),
+            message_bytes: AtomicU64::new(0),
+            message_bytes_total: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn add_task_total(&self) {
+        self.task_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_task_alive(&self) {
+        self.task_alive.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dec_task_alive(&self) {
+        self.task_alive.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_task_passed(&self) {
+        self.task_passed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_task_failed(&self) {
+        self.task_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_message_sent(&self, size: usize) {
+        self.message_sent.fetch_add(1, Ordering::Relaxed);
+        self.message_bytes.fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+impl BenchRuntimeStats for MailRuntimeStats {
+    fn emit(&self, client: &mut StatsdClient) {
+        const TAG_NAME_TARGET: &str = "target";
+
+        macro_rules! emit_count {
+            ($field:ident, $name:literal) => {
+                let $field = self.$field.swap(0, Ordering::Relaxed);
+                client
+                    .count(concat!("mail.", $name), $field)
+                    .with_tag(TAG_NAME_TARGET, self.target)
+                    .send();
+            };
+        }
+
+        macro_rules! emit_count_with_total {
+            ($field:ident, $total:ident, $name:literal) => {
+                emit_count!($field, $name);
+                self.$total.fetch_add($field, Ordering::Relaxed);
+            };
+        }
+
+        let task_alive = self.task_alive.load(Ordering::Relaxed);
+        client
+            .gauge("mail.task.alive", task_alive)
+            .with_tag(TAG_NAME_TARGET, self.target)
+            .send();
+
+        emit_count!(task_total, "task.total");
+        emit_count!(task_passed, "task.passed");
+        emit_count!(task_failed, "task.failed");
+        emit_count_with_total!(message_sent, message_sent_total, "message.sent");
+        emit_count_with_total!(message_bytes, message_bytes_total, "message.bytes");
+    }
+
+    fn summary(&self, _total_time: Duration) {
+        macro_rules! get_total {
+            ($field:ident, $total:ident) => {
+                self.$total.load(Ordering::Relaxed) + self.$field.load(Ordering::Relaxed)
+            };
+        }
+
+        println!("# Messages");
+        let total_sent = get_total!(message_sent, message_sent_total);
+        println!("Sent count:    {total_sent}");
+        let total_bytes = get_total!(message_bytes, message_bytes_total);
+        println!("Sent bytes:    {total_bytes}");
+    }
+
+    fn report(&self, report: &mut BenchReport) {
+        macro_rules! report_total {
+            ($field:ident, $total:ident, $name:literal) => {
+                let v = self.$total.load(Ordering::Relaxed) + self.$field.load(Ordering::Relaxed);
+                report.add_counter($name, v);
+            };
+        }
+
+        report_total!(message_sent, message_sent_total, "message.sent");
+        report_total!(message_bytes, message_bytes_total, "message.bytes");
+    }
+}
@@ -8,6 +8,7 @@ pub(crate) mod socket;
 pub(crate) mod proxy_protocol;
 
 pub(crate) mod http;
+pub(crate) mod mail;
 
 pub(crate) mod openssl;
 pub(crate) mod rustls;
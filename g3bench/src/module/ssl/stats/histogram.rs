@@ -9,6 +9,7 @@ use g3_histogram::{HistogramRecorder, KeepingHistogram};
 use g3_statsd_client::StatsdClient;
 use g3_std_ext::time::DurationExt;
 
+use crate::report::BenchReport;
 use crate::target::BenchHistogram;
 
 pub(crate) struct SslHistogram {
@@ -41,6 +42,10 @@ impl BenchHistogram for SslHistogram {
         Self::summary_newline();
         Self::summary_total_percentage(total_time);
     }
+
+    fn report(&self, report: &mut BenchReport) {
+        report.add_duration_histogram("time.total", self.total_time.inner());
+    }
 }
 
 #[derive(Clone)]
@@ -10,6 +10,7 @@ use g3_io_ext::{LimitedReaderStats, LimitedWriterStats};
 use g3_statsd_client::StatsdClient;
 
 use super::SslSessionStats;
+use crate::report::BenchReport;
 use crate::target::BenchRuntimeStats;
 
 #[derive(Default)]
@@ -148,4 +149,29 @@ impl BenchRuntimeStats for SslRuntimeStats {
         println!("Recv bytes:    {total_recv}");
         println!("Recv rate:     {:.3}B/s", total_recv as f64 / total_secs);
     }
+
+    fn report(&self, report: &mut BenchReport) {
+        macro_rules! report_total {
+            ($field:ident, $total:ident, $name:literal) => {
+                let v = self.$total.load(Ordering::Relaxed) + self.$field.load(Ordering::Relaxed);
+                report.add_counter($name, v);
+            };
+        }
+
+        report_total!(conn_attempt, conn_attempt_total, "connection.attempt");
+        report_total!(conn_success, conn_success_total, "connection.success");
+        report.add_counter(
+            "connection.close_error",
+            self.conn_close_error.load(Ordering::Relaxed),
+        );
+        report.add_counter(
+            "connection.close_timeout",
+            self.conn_close_timeout.load(Ordering::Relaxed),
+        );
+
+        self.session.report("tls", report);
+
+        report_total!(tcp_write, tcp_write_total, "io.tcp.write");
+        report_total!(tcp_read, tcp_read_total, "io.tcp.read");
+    }
 }
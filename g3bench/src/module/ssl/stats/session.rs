@@ -5,6 +5,8 @@
 
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::report::BenchReport;
+
 #[derive(Default)]
 pub(crate) struct SslSessionStats {
     total: AtomicU64,
@@ -36,4 +38,15 @@ impl SslSessionStats {
             (session_reused as f64 / total as f64) * 100.0
         );
     }
+
+    pub(crate) fn report(&self, prefix: &'static str, report: &mut BenchReport) {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return;
+        }
+
+        report.add_counter(format!("{prefix}.session.total"), total);
+        let session_reused = self.reused.load(Ordering::Relaxed);
+        report.add_counter(format!("{prefix}.session.reused"), session_reused);
+    }
 }
@@ -9,6 +9,7 @@ use std::net::{IpAddr, SocketAddr};
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use ahash::AHashMap;
@@ -23,7 +24,9 @@ use g3_types::limit::RateLimitQuota;
 use g3_types::metrics::NodeName;
 use g3_types::net::{TcpSockSpeedLimitConfig, UdpSockSpeedLimitConfig, UpstreamAddr};
 
+use super::load_profile::LoadProfile;
 use super::progress::BenchProgress;
+use super::report::{ReportFormat, ReportOutput};
 
 const GLOBAL_ARG_UNAIDED: &str = "unaided";
 const GLOBAL_ARG_UNCONSTRAINED: &str = "unconstrained";
@@ -35,6 +38,8 @@ const GLOBAL_ARG_CONCURRENCY: &str = "concurrency";
 const GLOBAL_ARG_LATENCY: &str = "latency";
 const GLOBAL_ARG_TIME_LIMIT: &str = "time-limit";
 const GLOBAL_ARG_RATE_LIMIT: &str = "rate-limit";
+const GLOBAL_ARG_LOAD_PROFILE: &str = "load-profile";
+const GLOBAL_ARG_LOAD_PROFILE_FILE: &str = "load-profile-file";
 const GLOBAL_ARG_REQUESTS: &str = "requests";
 const GLOBAL_ARG_RESOLVE: &str = "resolve";
 const GLOBAL_ARG_LOG_ERROR: &str = "log-error";
@@ -44,6 +49,8 @@ const GLOBAL_ARG_STATSD_TARGET_UDP: &str = "statsd-target-udp";
 const GLOBAL_ARG_STATSD_TARGET_UNIX: &str = "statsd-target-unix";
 const GLOBAL_ARG_NO_PROGRESS_BAR: &str = "no-progress-bar";
 const GLOBAL_ARG_NO_SUMMARY: &str = "no-summary";
+const GLOBAL_ARG_OUTPUT: &str = "output";
+const GLOBAL_ARG_OUTPUT_FILE: &str = "output-file";
 
 const GLOBAL_ARG_PEER_PICK_POLICY: &str = "peer-pick-policy";
 const GLOBAL_ARG_TCP_LIMIT_SHIFT: &str = "tcp-limit-shift";
@@ -58,6 +65,7 @@ pub struct ProcArgs {
     pub(super) requests: Option<usize>,
     pub(super) time_limit: Option<Duration>,
     pub(super) rate_limit: Option<RateLimitQuota>,
+    pub(super) load_profile: Option<Arc<LoadProfile>>,
     pub(super) log_error_count: usize,
     pub(super) ignore_fatal_error: bool,
     pub(super) task_unconstrained: bool,
@@ -69,6 +77,7 @@ pub struct ProcArgs {
     statsd_client_config: Option<StatsdClientConfig>,
     no_progress_bar: bool,
     pub(super) no_summary: bool,
+    pub(super) report_output: Option<ReportOutput>,
 
     peer_pick_policy: SelectivePickPolicy,
     pub(super) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
@@ -83,6 +92,7 @@ impl Default for ProcArgs {
             requests: None,
             time_limit: None,
             rate_limit: None,
+            load_profile: None,
             log_error_count: 0,
             ignore_fatal_error: false,
             task_unconstrained: false,
@@ -93,6 +103,7 @@ impl Default for ProcArgs {
             statsd_client_config: None,
             no_progress_bar: false,
             no_summary: false,
+            report_output: None,
             peer_pick_policy: SelectivePickPolicy::RoundRobin,
             tcp_sock_speed_limit: TcpSockSpeedLimitConfig::default(),
             udp_sock_speed_limit: UdpSockSpeedLimitConfig::default(),
@@ -107,6 +118,12 @@ impl ProcArgs {
         }
 
         println!("Concurrency Level: {}", self.concurrency);
+        if let Some(profile) = &self.load_profile {
+            println!("Load Stages:");
+            for (i, stage) in profile.stages().iter().enumerate() {
+                println!("  #{i} {stage}");
+            }
+        }
         println!();
     }
 
@@ -115,6 +132,10 @@ impl ProcArgs {
             None
         } else if let Some(requests) = self.requests {
             Some(BenchProgress::new_fixed(requests))
+        } else if let Some(profile) = &self.load_profile {
+            let total = profile.total_duration();
+            let time = self.time_limit.map(|t| t.min(total)).unwrap_or(total);
+            Some(BenchProgress::new_timed(time))
         } else {
             self.time_limit.map(BenchProgress::new_timed)
         }
@@ -246,6 +267,37 @@ pub fn add_global_args(app: Command) -> Command {
             .long(GLOBAL_ARG_RATE_LIMIT)
             .num_args(1),
     )
+    .arg(
+        Arg::new(GLOBAL_ARG_LOAD_PROFILE)
+            .help(
+                "Schedule requests open-loop by the load stages, separated by ','.\n\
+                    Each stage can be 'RATE@DURATION', 'FROM..TO@DURATION' or 'FROM..TO/STEP@DURATION'",
+            )
+            .value_name("STAGES")
+            .global(true)
+            .long(GLOBAL_ARG_LOAD_PROFILE)
+            .num_args(1)
+            .conflicts_with_all([
+                GLOBAL_ARG_LOAD_PROFILE_FILE,
+                GLOBAL_ARG_RATE_LIMIT,
+                GLOBAL_ARG_LATENCY,
+            ]),
+    )
+    .arg(
+        Arg::new(GLOBAL_ARG_LOAD_PROFILE_FILE)
+            .help("Schedule requests open-loop by the load stages in this yaml file")
+            .value_name("FILE PATH")
+            .global(true)
+            .long(GLOBAL_ARG_LOAD_PROFILE_FILE)
+            .num_args(1)
+            .value_hint(ValueHint::FilePath)
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all([
+                GLOBAL_ARG_LOAD_PROFILE,
+                GLOBAL_ARG_RATE_LIMIT,
+                GLOBAL_ARG_LATENCY,
+            ]),
+    )
     .arg(
         Arg::new(GLOBAL_ARG_REQUESTS)
             .help("Number of requests to perform")
@@ -370,6 +422,29 @@ pub fn add_global_args(app: Command) -> Command {
             .long(GLOBAL_ARG_NO_SUMMARY)
             .global(true),
     )
+    .arg(
+        Arg::new(GLOBAL_ARG_OUTPUT)
+            .help(
+                "Write the result in this format, which can be used by the compare command.\n\
+                    The summary output will be disabled if no output file is set",
+            )
+            .value_name("FORMAT")
+            .long(GLOBAL_ARG_OUTPUT)
+            .global(true)
+            .num_args(1)
+            .value_parser(["json", "csv"]),
+    )
+    .arg(
+        Arg::new(GLOBAL_ARG_OUTPUT_FILE)
+            .help("Write the result to this file instead of stdout")
+            .value_name("FILE PATH")
+            .long(GLOBAL_ARG_OUTPUT_FILE)
+            .global(true)
+            .num_args(1)
+            .value_hint(ValueHint::FilePath)
+            .value_parser(value_parser!(PathBuf))
+            .requires(GLOBAL_ARG_OUTPUT),
+    )
     .arg(
         Arg::new(GLOBAL_ARG_PEER_PICK_POLICY)
             .help("Set the pick policy for selecting peers")
@@ -454,6 +529,13 @@ pub fn parse_global_args(args: &ArgMatches) -> anyhow::Result<ProcArgs> {
 
     proc_args.time_limit = g3_clap::humanize::get_duration(args, GLOBAL_ARG_TIME_LIMIT)?;
     proc_args.rate_limit = g3_clap::limit::get_rate_limit(args, GLOBAL_ARG_RATE_LIMIT)?;
+    if let Some(s) = args.get_one::<String>(GLOBAL_ARG_LOAD_PROFILE) {
+        let profile = LoadProfile::parse_spec(s).context("invalid load profile")?;
+        proc_args.load_profile = Some(Arc::new(profile));
+    } else if let Some(path) = args.get_one::<PathBuf>(GLOBAL_ARG_LOAD_PROFILE_FILE) {
+        let profile = LoadProfile::load_yaml_file(path).context("invalid load profile file")?;
+        proc_args.load_profile = Some(Arc::new(profile));
+    }
 
     if args.get_flag(GLOBAL_ARG_UNAIDED) {
         proc_args.use_unaided_worker = true;
@@ -513,6 +595,20 @@ pub fn parse_global_args(args: &ArgMatches) -> anyhow::Result<ProcArgs> {
     }
     proc_args.no_summary = args.get_flag(GLOBAL_ARG_NO_SUMMARY);
 
+    if let Some(s) = args.get_one::<String>(GLOBAL_ARG_OUTPUT) {
+        let format = ReportFormat::from_str(s)?;
+        let file = args.get_one::<PathBuf>(GLOBAL_ARG_OUTPUT_FILE).cloned();
+        if file.is_none() {
+            // the result will be written to stdout
+            proc_args.no_summary = true;
+        }
+        proc_args.report_output = Some(ReportOutput {
+            target: args.subcommand_name().unwrap_or_default().to_string(),
+            format,
+            file,
+        });
+    }
+
     if let Some(s) = args.get_one::<String>(GLOBAL_ARG_PEER_PICK_POLICY) {
         proc_args.peer_pick_policy = SelectivePickPolicy::from_str(s).unwrap();
     }
@@ -542,7 +638,10 @@ pub fn parse_global_args(args: &ArgMatches) -> anyhow::Result<ProcArgs> {
         proc_args.tcp_sock_speed_limit.shift_millis = shift;
     }
 
-    if proc_args.time_limit.is_none() && proc_args.requests.is_none() {
+    if proc_args.time_limit.is_none()
+        && proc_args.requests.is_none()
+        && proc_args.load_profile.is_none()
+    {
         proc_args.requests = Some(1);
     }
 
@@ -0,0 +1,156 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use super::{BenchReport, REPORT_PERCENTILES, ReportHistogram, ReportUnit};
+
+const KIND_INFO: &str = "info";
+const KIND_REQUESTS: &str = "requests";
+const KIND_COUNTER: &str = "counter";
+const KIND_HISTOGRAM: &str = "histogram";
+
+const FIXED_COLUMNS: usize = 4;
+// count, min, mean, stdev, max, encoded
+const HISTOGRAM_COLUMNS: usize = 6 + REPORT_PERCENTILES.len();
+
+pub(super) fn write<W: Write>(report: &BenchReport, writer: &mut W) -> io::Result<()> {
+    let padding = ",".repeat(HISTOGRAM_COLUMNS);
+
+    write!(writer, "kind,name,unit,value,count,min,mean,stdev")?;
+    for p in REPORT_PERCENTILES {
+        write!(writer, ",p{p}")?;
+    }
+    writeln!(writer, ",max,encoded")?;
+
+    writeln!(writer, "{KIND_INFO},target,,{}{padding}", report.target)?;
+    writeln!(
+        writer,
+        "{KIND_INFO},time_taken,s,{}{padding}",
+        report.total_time.as_secs_f64()
+    )?;
+    writeln!(writer, "{KIND_REQUESTS},passed,,{}{padding}", report.passed)?;
+    writeln!(writer, "{KIND_REQUESTS},failed,,{}{padding}", report.failed)?;
+    writeln!(writer, "{KIND_REQUESTS},left,,{}{padding}", report.left)?;
+    writeln!(
+        writer,
+        "{KIND_REQUESTS},rate,1/s,{:.3}{padding}",
+        report.request_rate()
+    )?;
+
+    for (name, v) in &report.counters {
+        writeln!(writer, "{KIND_COUNTER},{name},,{v}{padding}")?;
+    }
+
+    for h in &report.histograms {
+        let data = &h.data;
+        write!(
+            writer,
+            "{KIND_HISTOGRAM},{},{},,{},{},{:.3},{:.3}",
+            h.name,
+            h.unit.as_str(),
+            data.len(),
+            data.min(),
+            data.mean(),
+            data.stdev()
+        )?;
+        for p in REPORT_PERCENTILES {
+            write!(writer, ",{}", data.value_at_percentile(*p))?;
+        }
+        writeln!(writer, ",{},{}", data.max(), h.encode())?;
+    }
+
+    Ok(())
+}
+
+fn parse_u64(name: &str, value: &str) -> anyhow::Result<u64> {
+    value
+        .parse()
+        .map_err(|e| anyhow!("invalid u64 value for {name}: {e}"))
+}
+
+pub(super) fn parse(content: &str) -> anyhow::Result<BenchReport> {
+    let mut target = None;
+    let mut total_time = None;
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut left = 0;
+    let mut counters = Vec::new();
+    let mut histograms = Vec::new();
+
+    for (i, line) in content.lines().enumerate().skip(1) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let columns: Vec<&str> = line.split(',').collect();
+        if columns.len() != FIXED_COLUMNS + HISTOGRAM_COLUMNS {
+            return Err(anyhow!("invalid column count at line {}", i + 1));
+        }
+        let name = columns[1];
+        let value = columns[3];
+
+        match columns[0] {
+            KIND_INFO => match name {
+                "target" => target = Some(value.to_string()),
+                "time_taken" => {
+                    let v = value
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(|v| Duration::try_from_secs_f64(v).ok())
+                        .ok_or_else(|| anyhow!("invalid time_taken value {value}"))?;
+                    total_time = Some(v);
+                }
+                _ => {}
+            },
+            KIND_REQUESTS => match name {
+                "passed" => passed = parse_u64(name, value)?,
+                "failed" => failed = parse_u64(name, value)?,
+                "left" => left = parse_u64(name, value)?,
+                _ => {}
+            },
+            KIND_COUNTER => counters.push((name.to_string(), parse_u64(name, value)?)),
+            KIND_HISTOGRAM => {
+                let unit = ReportUnit::parse(columns[2])?;
+                let encoded = columns[columns.len() - 1];
+                histograms.push(ReportHistogram::decode(name, unit, encoded)?);
+            }
+            kind => return Err(anyhow!("unknown kind {kind} at line {}", i + 1)),
+        }
+    }
+
+    let target = target.ok_or_else(|| anyhow!("no target found"))?;
+    let total_time = total_time.ok_or_else(|| anyhow!("no time_taken found"))?;
+    let mut report = BenchReport::new(&target, total_time);
+    report.passed = passed;
+    report.failed = failed;
+    report.left = left;
+    report.counters = counters;
+    report.histograms = histograms;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::{check_report, sample_report};
+
+    #[test]
+    fn write_and_parse() {
+        let report = sample_report();
+        let mut buf = Vec::new();
+        write(&report, &mut buf).unwrap();
+        let content = String::from_utf8(buf).unwrap();
+        let parsed = parse(&content).unwrap();
+        check_report(&parsed);
+
+        assert!(parse("kind,name\ninfo,target").is_err());
+        assert!(parse("kind,name\n").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use serde_json::{Map, Value};
+
+use super::{BenchReport, REPORT_PERCENTILES, ReportHistogram, ReportUnit};
+
+pub(super) fn write<W: Write>(report: &BenchReport, writer: &mut W) -> io::Result<()> {
+    let mut counters = Map::new();
+    for (name, v) in &report.counters {
+        counters.insert(name.clone(), Value::from(*v));
+    }
+
+    let mut histograms = Map::new();
+    for h in &report.histograms {
+        let mut percentiles = Map::new();
+        for p in REPORT_PERCENTILES {
+            percentiles.insert(p.to_string(), Value::from(h.data.value_at_percentile(*p)));
+        }
+
+        let mut map = Map::new();
+        map.insert("unit".to_string(), Value::from(h.unit.as_str()));
+        map.insert("count".to_string(), Value::from(h.data.len()));
+        map.insert("min".to_string(), Value::from(h.data.min()));
+        map.insert("mean".to_string(), Value::from(h.data.mean()));
+        map.insert("stdev".to_string(), Value::from(h.data.stdev()));
+        map.insert("max".to_string(), Value::from(h.data.max()));
+        map.insert("percentiles".to_string(), Value::Object(percentiles));
+        map.insert("encoded".to_string(), Value::from(h.encode()));
+        histograms.insert(h.name.clone(), Value::Object(map));
+    }
+
+    let mut map = Map::new();
+    map.insert("target".to_string(), Value::from(report.target.as_str()));
+    map.insert(
+        "time_taken".to_string(),
+        Value::from(report.total_time.as_secs_f64()),
+    );
+    map.insert("passed".to_string(), Value::from(report.passed));
+    map.insert("failed".to_string(), Value::from(report.failed));
+    map.insert("left".to_string(), Value::from(report.left));
+    map.insert("rate".to_string(), Value::from(report.request_rate()));
+    map.insert("counters".to_string(), Value::Object(counters));
+    map.insert("histograms".to_string(), Value::Object(histograms));
+
+    serde_json::to_writer_pretty(&mut *writer, &Value::Object(map))?;
+    writer.write_all(b"\n")
+}
+
+fn get_u64(map: &Map<String, Value>, key: &str) -> anyhow::Result<u64> {
+    map.get(key)
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| anyhow!("no valid u64 value found for key {key}"))
+}
+
+pub(super) fn parse(content: &str) -> anyhow::Result<BenchReport> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| anyhow!("invalid json content: {e}"))?;
+    let Value::Object(map) = value else {
+        return Err(anyhow!("the root value should be a json map"));
+    };
+
+    let target = map
+        .get("target")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("no valid target found"))?;
+    let total_time = map
+        .get("time_taken")
+        .and_then(|v| v.as_f64())
+        .and_then(|v| Duration::try_from_secs_f64(v).ok())
+        .ok_or_else(|| anyhow!("no valid time_taken found"))?;
+
+    let mut report = BenchReport::new(target, total_time);
+    report.passed = get_u64(&map, "passed")?;
+    report.failed = get_u64(&map, "failed")?;
+    report.left = get_u64(&map, "left")?;
+
+    if let Some(v) = map.get("counters") {
+        let Value::Object(counters) = v else {
+            return Err(anyhow!("invalid value for key counters: should be a map"));
+        };
+        for name in counters.keys() {
+            let v = get_u64(counters, name)?;
+            report.add_counter(name.as_str(), v);
+        }
+    }
+
+    if let Some(v) = map.get("histograms") {
+        let Value::Object(histograms) = v else {
+            return Err(anyhow!("invalid value for key histograms: should be a map"));
+        };
+        for (name, v) in histograms {
+            let Value::Object(map) = v else {
+                return Err(anyhow!(
+                    "invalid value for histogram {name}: should be a map"
+                ));
+            };
+            let unit = map
+                .get("unit")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("no valid unit found for histogram {name}"))?;
+            let unit = ReportUnit::parse(unit)?;
+            let encoded = map
+                .get("encoded")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("no valid encoded data found for histogram {name}"))?;
+            report
+                .histograms
+                .push(ReportHistogram::decode(name, unit, encoded)?);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::{check_report, sample_report};
+
+    #[test]
+    fn write_and_parse() {
+        let report = sample_report();
+        let mut buf = Vec::new();
+        write(&report, &mut buf).unwrap();
+        let content = String::from_utf8(buf).unwrap();
+        let parsed = parse(&content).unwrap();
+        check_report(&parsed);
+
+        assert!(parse("[]").is_err());
+        assert!(parse("{\"target\": \"h1\"}").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs::File;
+use std::io::{self, BufWriter, Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use base64::prelude::*;
+use hdrhistogram::Histogram;
+use hdrhistogram::serialization::{Deserializer, Serializer, V2DeflateSerializer};
+
+mod csv;
+mod json;
+
+/// the percentiles that will be shown in the result file,
+/// the full histogram is always encoded in the result file
+const REPORT_PERCENTILES: &[f64] = &[50.0, 75.0, 90.0, 95.0, 99.0, 99.9];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(anyhow!("unsupported output format {s}")),
+        }
+    }
+}
+
+pub(crate) struct ReportOutput {
+    pub(crate) target: String,
+    pub(crate) format: ReportFormat,
+    pub(crate) file: Option<PathBuf>,
+}
+
+impl ReportOutput {
+    pub(crate) fn write(&self, report: &BenchReport) -> anyhow::Result<()> {
+        match &self.file {
+            Some(path) => {
+                let file = File::create(path)
+                    .map_err(|e| anyhow!("failed to create file {}: {e}", path.display()))?;
+                let mut writer = BufWriter::new(file);
+                self.write_to(report, &mut writer)
+                    .and_then(|_| writer.flush())
+                    .map_err(|e| anyhow!("failed to write to file {}: {e}", path.display()))
+            }
+            None => {
+                let mut writer = io::stdout().lock();
+                self.write_to(report, &mut writer)
+                    .and_then(|_| writer.flush())
+                    .map_err(|e| anyhow!("failed to write to stdout: {e}"))
+            }
+        }
+    }
+
+    fn write_to<W: Write>(&self, report: &BenchReport, writer: &mut W) -> io::Result<()> {
+        match self.format {
+            ReportFormat::Json => json::write(report, writer),
+            ReportFormat::Csv => csv::write(report, writer),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ReportUnit {
+    Nanos,
+    Count,
+}
+
+impl ReportUnit {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ReportUnit::Nanos => "ns",
+            ReportUnit::Count => "",
+        }
+    }
+
+    fn parse(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "ns" => Ok(ReportUnit::Nanos),
+            "" => Ok(ReportUnit::Count),
+            _ => Err(anyhow!("unsupported histogram unit {s}")),
+        }
+    }
+}
+
+pub(crate) struct ReportHistogram {
+    pub(crate) name: String,
+    pub(crate) unit: ReportUnit,
+    pub(crate) data: Histogram<u64>,
+}
+
+impl ReportHistogram {
+    /// Encode the histogram in the same way as the HdrHistogram interval log,
+    /// i.e. base64 of the V2 compressed histogram
+    fn encode(&self) -> String {
+        let mut buf = Vec::with_capacity(1024);
+        // serialize to a Vec won't fail
+        let _ = V2DeflateSerializer::new().serialize(&self.data, &mut buf);
+        BASE64_STANDARD.encode(&buf)
+    }
+
+    fn decode(name: &str, unit: ReportUnit, encoded: &str) -> anyhow::Result<Self> {
+        let buf = BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|e| anyhow!("invalid base64 encoded histogram {name}: {e}"))?;
+        let data = Deserializer::new()
+            .deserialize(&mut Cursor::new(buf))
+            .map_err(|e| anyhow!("invalid encoded histogram {name}: {e}"))?;
+        Ok(ReportHistogram {
+            name: name.to_string(),
+            unit,
+            data,
+        })
+    }
+}
+
+pub(crate) struct BenchReport {
+    pub(crate) target: String,
+    pub(crate) total_time: Duration,
+    pub(crate) passed: u64,
+    pub(crate) failed: u64,
+    pub(crate) left: u64,
+    pub(crate) counters: Vec<(String, u64)>,
+    pub(crate) histograms: Vec<ReportHistogram>,
+}
+
+impl BenchReport {
+    pub(crate) fn new(target: &str, total_time: Duration) -> Self {
+        BenchReport {
+            target: target.to_string(),
+            total_time,
+            passed: 0,
+            failed: 0,
+            left: 0,
+            counters: Vec::new(),
+            histograms: Vec::new(),
+        }
+    }
+
+    pub(crate) fn load_file(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+        let report = if content.trim_start().starts_with('{') {
+            json::parse(&content)
+        } else {
+            csv::parse(&content)
+        };
+        report.context(format!("invalid result file {}", path.display()))
+    }
+
+    pub(crate) fn request_rate(&self) -> f64 {
+        self.passed as f64 / self.total_time.as_secs_f64()
+    }
+
+    pub(crate) fn add_counter<N: Into<String>>(&mut self, name: N, value: u64) {
+        self.counters.push((name.into(), value));
+    }
+
+    pub(crate) fn add_duration_histogram<N: Into<String>>(&mut self, name: N, h: &Histogram<u64>) {
+        self.histograms.push(ReportHistogram {
+            name: name.into(),
+            unit: ReportUnit::Nanos,
+            data: h.clone(),
+        });
+    }
+
+    pub(crate) fn add_count_histogram<N: Into<String>>(&mut self, name: N, h: &Histogram<u64>) {
+        self.histograms.push(ReportHistogram {
+            name: name.into(),
+            unit: ReportUnit::Count,
+            data: h.clone(),
+        });
+    }
+
+    pub(crate) fn histogram(&self, name: &str) -> Option<&ReportHistogram> {
+        self.histograms.iter().find(|h| h.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) fn sample_report() -> BenchReport {
+        let mut report = BenchReport::new("h1", Duration::from_millis(2500));
+        report.passed = 1000;
+        report.failed = 2;
+        report.add_counter("connection.attempt", 10);
+        report.add_counter("io.tcp.write", 123456);
+
+        let mut h = Histogram::<u64>::new(3).unwrap();
+        for i in 1..=1000 {
+            h.record(i * 1000).unwrap();
+        }
+        report.add_duration_histogram("time.total", &h);
+
+        let mut h = Histogram::<u64>::new(3).unwrap();
+        h.record(100).unwrap();
+        report.add_count_histogram("connection.reuse", &h);
+        report
+    }
+
+    pub(super) fn check_report(report: &BenchReport) {
+        let origin = sample_report();
+        assert_eq!(report.target, origin.target);
+        assert_eq!(report.total_time, origin.total_time);
+        assert_eq!(report.passed, origin.passed);
+        assert_eq!(report.failed, origin.failed);
+        assert_eq!(report.left, origin.left);
+        let mut counters = report.counters.clone();
+        counters.sort();
+        assert_eq!(counters, origin.counters);
+        assert_eq!(report.histograms.len(), origin.histograms.len());
+        for o in &origin.histograms {
+            let h = report.histogram(&o.name).unwrap();
+            assert_eq!(h.unit, o.unit);
+            assert_eq!(h.data, o.data);
+        }
+    }
+
+    #[test]
+    fn encode_histogram() {
+        let report = sample_report();
+        let h = &report.histograms[0];
+        let encoded = h.encode();
+        let decoded = ReportHistogram::decode(&h.name, h.unit, &encoded).unwrap();
+        assert_eq!(decoded.data, h.data);
+        assert_eq!(
+            decoded.data.value_at_quantile(0.5),
+            h.data.value_at_quantile(0.5)
+        );
+
+        assert!(ReportHistogram::decode("x", ReportUnit::Nanos, "invalid").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use bytes::{Buf, BytesMut};
+use http::HeaderMap;
+use prost_reflect::prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MethodDescriptor};
+
+const GRPC_MESSAGE_HEADER_LEN: usize = 5;
+
+pub(super) const GRPC_STATUS_CODE_COUNT: usize = 17;
+
+const GRPC_STATUS_NAMES: [&str; GRPC_STATUS_CODE_COUNT] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+pub(super) fn status_name(code: u32) -> &'static str {
+    GRPC_STATUS_NAMES
+        .get(code as usize)
+        .copied()
+        .unwrap_or("INVALID")
+}
+
+/// Get the grpc-status value from the response headers or trailers
+pub(super) fn get_status(headers: &HeaderMap) -> anyhow::Result<Option<u32>> {
+    let Some(v) = headers.get("grpc-status") else {
+        return Ok(None);
+    };
+    let s = v
+        .to_str()
+        .map_err(|e| anyhow!("invalid grpc-status header value: {e}"))?;
+    let code = s
+        .parse::<u32>()
+        .map_err(|e| anyhow!("invalid grpc-status value {s}: {e}"))?;
+    Ok(Some(code))
+}
+
+pub(super) fn get_message(headers: &HeaderMap) -> Option<&str> {
+    headers.get("grpc-message").and_then(|v| v.to_str().ok())
+}
+
+/// Encode a single uncompressed length-prefixed message
+pub(super) fn encode_frame(msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let len = u32::try_from(msg.len()).map_err(|_| anyhow!("too large grpc message"))?;
+    let mut buf = Vec::with_capacity(GRPC_MESSAGE_HEADER_LEN + msg.len());
+    buf.push(0);
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(msg);
+    Ok(buf)
+}
+
+/// Decoder of length-prefixed messages in the response body
+#[derive(Default)]
+pub(super) struct FrameDecoder {
+    buf: BytesMut,
+    message_count: usize,
+}
+
+impl FrameDecoder {
+    pub(super) fn message_count(&self) -> usize {
+        self.message_count
+    }
+
+    pub(super) fn push(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        self.buf.extend_from_slice(data);
+        while self.buf.len() >= GRPC_MESSAGE_HEADER_LEN {
+            let len = u32::from_be_bytes([self.buf[1], self.buf[2], self.buf[3], self.buf[4]]);
+            let frame_len = GRPC_MESSAGE_HEADER_LEN + len as usize;
+            if self.buf.len() < frame_len {
+                break;
+            }
+            if self.buf[0] > 1 {
+                return Err(anyhow!("invalid compressed flag {}", self.buf[0]));
+            }
+            self.buf.advance(frame_len);
+            self.message_count += 1;
+        }
+        Ok(())
+    }
+
+    pub(super) fn finish(&self) -> anyhow::Result<()> {
+        if self.buf.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "{} bytes of incomplete message left in response body",
+                self.buf.len()
+            ))
+        }
+    }
+}
+
+/// Parse the method path in the format of `package.Service/Method`
+pub(super) fn parse_method_path(s: &str) -> anyhow::Result<(&str, &str)> {
+    let s = s.strip_prefix('/').unwrap_or(s);
+    let Some((service, method)) = s.split_once('/') else {
+        return Err(anyhow!("no '/' found in grpc method path"));
+    };
+    if service.is_empty() || method.is_empty() || method.contains('/') {
+        return Err(anyhow!("invalid grpc method path"));
+    }
+    Ok((service, method))
+}
+
+pub(super) fn find_method(
+    descriptor_set: &[u8],
+    service: &str,
+    method: &str,
+) -> anyhow::Result<MethodDescriptor> {
+    let pool = DescriptorPool::decode(descriptor_set)
+        .map_err(|e| anyhow!("invalid file descriptor set: {e}"))?;
+    let service_desc = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| anyhow!("no service {service} found in file descriptor set"))?;
+    service_desc
+        .methods()
+        .find(|m| m.name() == method)
+        .ok_or_else(|| anyhow!("no method {method} found in service {service}"))
+}
+
+/// Build the request message from the JSON mapping of the input type
+pub(super) fn build_request(method: &MethodDescriptor, json: &str) -> anyhow::Result<Vec<u8>> {
+    let mut de = serde_json::Deserializer::from_str(json);
+    let msg = DynamicMessage::deserialize(method.input(), &mut de).context(format!(
+        "failed to parse json as message {}",
+        method.input().full_name()
+    ))?;
+    de.end()
+        .map_err(|e| anyhow!("trailing data found after json message: {e}"))?;
+    Ok(msg.encode_to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame() {
+        let frame = encode_frame(b"abc").unwrap();
+        assert_eq!(frame, b"\x00\x00\x00\x00\x03abc");
+
+        let mut decoder = FrameDecoder::default();
+        decoder.push(&frame[..2]).unwrap();
+        assert_eq!(decoder.message_count(), 0);
+        decoder.push(&frame[2..]).unwrap();
+        decoder
+            .push(b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01")
+            .unwrap();
+        assert_eq!(decoder.message_count(), 2);
+        assert!(decoder.finish().is_err());
+        decoder.push(b"x").unwrap();
+        assert_eq!(decoder.message_count(), 3);
+        decoder.finish().unwrap();
+
+        let mut decoder = FrameDecoder::default();
+        assert!(decoder.push(b"\x02\x00\x00\x00\x00").is_err());
+    }
+
+    #[test]
+    fn method_path() {
+        assert_eq!(
+            parse_method_path("/grpc.health.v1.Health/Check").unwrap(),
+            ("grpc.health.v1.Health", "Check")
+        );
+        assert_eq!(
+            parse_method_path("helloworld.Greeter/SayHello").unwrap(),
+            ("helloworld.Greeter", "SayHello")
+        );
+        assert!(parse_method_path("helloworld.Greeter").is_err());
+        assert!(parse_method_path("/helloworld.Greeter/").is_err());
+        assert!(parse_method_path("a/b/c").is_err());
+    }
+
+    #[test]
+    fn status() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(get_status(&headers).unwrap(), None);
+        headers.insert("grpc-status", "14".parse().unwrap());
+        assert_eq!(get_status(&headers).unwrap(), Some(14));
+        assert_eq!(status_name(14), "UNAVAILABLE");
+        assert_eq!(status_name(17), "INVALID");
+        headers.insert("grpc-status", "x".parse().unwrap());
+        assert!(get_status(&headers).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{ArgMatches, Command};
+
+use super::h2::H2ConnectionPool;
+use super::{BenchTarget, BenchTaskContext, ProcArgs};
+use crate::module::http::{HttpHistogram, HttpHistogramRecorder};
+
+mod message;
+
+mod opts;
+use opts::BenchGrpcArgs;
+
+mod stats;
+use stats::GrpcRuntimeStats;
+
+mod task;
+use task::GrpcTaskContext;
+
+pub const COMMAND: &str = "grpc";
+
+struct GrpcTarget {
+    args: Arc<BenchGrpcArgs>,
+    proc_args: Arc<ProcArgs>,
+    stats: Arc<GrpcRuntimeStats>,
+    histogram: Option<HttpHistogram>,
+    histogram_recorder: HttpHistogramRecorder,
+    pool: Option<Arc<H2ConnectionPool<BenchGrpcArgs>>>,
+}
+
+impl BenchTarget<GrpcRuntimeStats, HttpHistogram, GrpcTaskContext> for GrpcTarget {
+    fn new_context(&self) -> anyhow::Result<GrpcTaskContext> {
+        GrpcTaskContext::new(
+            &self.args,
+            &self.proc_args,
+            &self.stats,
+            self.histogram_recorder.clone(),
+            self.pool.clone(),
+        )
+    }
+
+    fn fetch_runtime_stats(&self) -> Arc<GrpcRuntimeStats> {
+        self.stats.clone()
+    }
+
+    fn take_histogram(&mut self) -> Option<HttpHistogram> {
+        self.histogram.take()
+    }
+
+    fn notify_finish(&mut self) {
+        self.pool = None;
+    }
+}
+
+pub fn command() -> Command {
+    opts::add_grpc_args(Command::new(COMMAND))
+}
+
+pub async fn run(proc_args: &Arc<ProcArgs>, cmd_args: &ArgMatches) -> anyhow::Result<ExitCode> {
+    let mut grpc_args = opts::parse_grpc_args(cmd_args)?;
+    grpc_args
+        .connect
+        .resolve_target_address(proc_args, &grpc_args.target)
+        .await?;
+    let grpc_args = Arc::new(grpc_args);
+
+    let runtime_stats = Arc::new(GrpcRuntimeStats::new(COMMAND));
+    let (histogram, histogram_recorder) = HttpHistogram::new();
+
+    let pool = grpc_args.pool_size.map(|s| {
+        Arc::new(H2ConnectionPool::new(
+            &grpc_args,
+            proc_args,
+            s,
+            &runtime_stats.http,
+            &histogram_recorder,
+        ))
+    });
+
+    let target = GrpcTarget {
+        args: grpc_args,
+        proc_args: Arc::clone(proc_args),
+        stats: runtime_stats,
+        histogram: Some(histogram),
+        histogram_recorder,
+        pool,
+    };
+
+    super::run(target, proc_args).await
+}
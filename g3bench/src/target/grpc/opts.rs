@@ -0,0 +1,308 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command, value_parser};
+use http::{HeaderName, HeaderValue, Method, Request, Version};
+use url::Url;
+
+use g3_types::net::UpstreamAddr;
+
+use super::message;
+use crate::module::http::{AppendH2ConnectArgs, H2ConnectArgs};
+use crate::target::h2::H2ConnectionPoolArgs;
+
+const GRPC_ARG_URL: &str = "url";
+const GRPC_ARG_METHOD: &str = "method";
+const GRPC_ARG_PAYLOAD: &str = "payload";
+const GRPC_ARG_PAYLOAD_FILE: &str = "payload-file";
+const GRPC_ARG_DESCRIPTOR_SET: &str = "descriptor-set";
+const GRPC_ARG_JSON: &str = "json";
+const GRPC_ARG_SERVER_STREAMING: &str = "server-streaming";
+const GRPC_ARG_HEADER: &str = "header";
+const GRPC_ARG_OK_STATUS: &str = "ok-status";
+const GRPC_ARG_TIMEOUT: &str = "timeout";
+const GRPC_ARG_CONNECT_TIMEOUT: &str = "connect-timeout";
+const GRPC_ARG_CONNECTION_POOL: &str = "connection-pool";
+const GRPC_ARG_NO_MULTIPLEX: &str = "no-multiplex";
+
+const GRPC_ARG_GROUP_PAYLOAD: &str = "payload-source";
+
+pub(super) struct BenchGrpcArgs {
+    target_url: Url,
+    path: String,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    pub(super) body: Bytes,
+    pub(super) server_streaming: bool,
+    pub(super) ok_status: u32,
+    pub(super) timeout: Duration,
+    pub(super) connect_timeout: Duration,
+    pub(super) pool_size: Option<usize>,
+    pub(super) no_multiplex: bool,
+
+    pub(super) target: UpstreamAddr,
+    pub(super) connect: H2ConnectArgs,
+}
+
+impl BenchGrpcArgs {
+    fn new(url: Url) -> anyhow::Result<Self> {
+        let target = UpstreamAddr::try_from(&url)?;
+        let connect = H2ConnectArgs::new(url.scheme() == "https");
+
+        Ok(BenchGrpcArgs {
+            target_url: url,
+            path: String::new(),
+            headers: Vec::new(),
+            body: Bytes::new(),
+            server_streaming: false,
+            ok_status: 0,
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(15),
+            pool_size: None,
+            no_multiplex: false,
+            target,
+            connect,
+        })
+    }
+
+    pub(super) fn build_static_request(&self) -> anyhow::Result<Request<()>> {
+        let uri = http::Uri::builder()
+            .scheme(self.target_url.scheme())
+            .authority(self.target.to_string())
+            .path_and_query(self.path.as_str())
+            .build()
+            .map_err(|e| anyhow!("failed to build request: {e:?}"))?;
+
+        let mut req = Request::builder()
+            .version(Version::HTTP_2)
+            .method(Method::POST)
+            .uri(uri)
+            .body(())
+            .map_err(|e| anyhow!("failed to build request: {e:?}"))?;
+
+        for (key, value) in self.headers.iter() {
+            req.headers_mut().append(key, value.clone());
+        }
+
+        req.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/grpc"),
+        );
+        req.headers_mut()
+            .insert(http::header::TE, HeaderValue::from_static("trailers"));
+        if !req.headers().contains_key(http::header::USER_AGENT) {
+            req.headers_mut().insert(
+                http::header::USER_AGENT,
+                HeaderValue::from_static("g3bench"),
+            );
+        }
+
+        Ok(req)
+    }
+}
+
+impl H2ConnectionPoolArgs for BenchGrpcArgs {
+    fn connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    fn target(&self) -> &UpstreamAddr {
+        &self.target
+    }
+
+    fn connect_args(&self) -> &H2ConnectArgs {
+        &self.connect
+    }
+}
+
+pub(super) fn add_grpc_args(app: Command) -> Command {
+    app.arg(
+        Arg::new(GRPC_ARG_URL)
+            .help("Target url, the path will be ignored")
+            .required(true)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_METHOD)
+            .help("Full method name, in the format of 'package.Service/Method'")
+            .required(true)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_PAYLOAD)
+            .help("Request message in hex encoding")
+            .value_name("HEX DATA")
+            .long(GRPC_ARG_PAYLOAD)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_PAYLOAD_FILE)
+            .help("Read the binary request message from this file")
+            .value_name("FILE PATH")
+            .long(GRPC_ARG_PAYLOAD_FILE)
+            .num_args(1)
+            .value_parser(value_parser!(std::path::PathBuf)),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_JSON)
+            .help("Request message in JSON format, require --descriptor-set")
+            .value_name("JSON DATA")
+            .long(GRPC_ARG_JSON)
+            .num_args(1)
+            .requires(GRPC_ARG_DESCRIPTOR_SET),
+    )
+    .group(ArgGroup::new(GRPC_ARG_GROUP_PAYLOAD).args([
+        GRPC_ARG_PAYLOAD,
+        GRPC_ARG_PAYLOAD_FILE,
+        GRPC_ARG_JSON,
+    ]))
+    .arg(
+        Arg::new(GRPC_ARG_DESCRIPTOR_SET)
+            .help("Binary FileDescriptorSet file generated by protoc --descriptor_set_out")
+            .value_name("FILE PATH")
+            .long(GRPC_ARG_DESCRIPTOR_SET)
+            .num_args(1)
+            .value_parser(value_parser!(std::path::PathBuf)),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_SERVER_STREAMING)
+            .help("Allow multiple response messages for server-streaming methods")
+            .long(GRPC_ARG_SERVER_STREAMING)
+            .action(ArgAction::SetTrue),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_HEADER)
+            .help("Add custom metadata")
+            .value_name("HEADER")
+            .short('H')
+            .long(GRPC_ARG_HEADER)
+            .action(ArgAction::Append),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_OK_STATUS)
+            .help("Only treat this grpc-status code as success")
+            .value_name("STATUS CODE")
+            .long(GRPC_ARG_OK_STATUS)
+            .num_args(1)
+            .value_parser(value_parser!(u32))
+            .default_value("0"),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_TIMEOUT)
+            .help("Grpc response timeout")
+            .value_name("TIMEOUT DURATION")
+            .default_value("30s")
+            .long(GRPC_ARG_TIMEOUT)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_CONNECT_TIMEOUT)
+            .help("Timeout for connection to next peer")
+            .value_name("TIMEOUT DURATION")
+            .default_value("15s")
+            .long(GRPC_ARG_CONNECT_TIMEOUT)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_CONNECTION_POOL)
+            .help(
+                "Set the number of pooled underlying h2 connections.\n\
+                        If not set, each concurrency will use it's own h2 connection",
+            )
+            .value_name("POOL SIZE")
+            .long(GRPC_ARG_CONNECTION_POOL)
+            .short('C')
+            .num_args(1)
+            .value_parser(value_parser!(usize))
+            .conflicts_with(GRPC_ARG_NO_MULTIPLEX),
+    )
+    .arg(
+        Arg::new(GRPC_ARG_NO_MULTIPLEX)
+            .help("Disable h2 connection multiplexing")
+            .action(ArgAction::SetTrue)
+            .long(GRPC_ARG_NO_MULTIPLEX)
+            .conflicts_with(GRPC_ARG_CONNECTION_POOL),
+    )
+    .append_h2_connect_args()
+}
+
+pub(super) fn parse_grpc_args(args: &ArgMatches) -> anyhow::Result<BenchGrpcArgs> {
+    let url = if let Some(v) = args.get_one::<String>(GRPC_ARG_URL) {
+        Url::parse(v).context(format!("invalid {GRPC_ARG_URL} value"))?
+    } else {
+        return Err(anyhow!("no target url set"));
+    };
+    match url.scheme() {
+        "http" | "https" => {}
+        _ => return Err(anyhow!("unsupported target url {url}")),
+    }
+    let mut grpc_args = BenchGrpcArgs::new(url)?;
+
+    let method_path = args.get_one::<String>(GRPC_ARG_METHOD).unwrap();
+    let (service, method) = message::parse_method_path(method_path)?;
+    grpc_args.path = format!("/{service}/{method}");
+
+    let msg = if let Some(file) = args.get_one::<std::path::PathBuf>(GRPC_ARG_PAYLOAD_FILE) {
+        std::fs::read(file)
+            .map_err(|e| anyhow!("failed to read payload file {}: {e}", file.display()))?
+    } else if args.contains_id(GRPC_ARG_PAYLOAD) {
+        g3_clap::data::get(args, GRPC_ARG_PAYLOAD, true)?
+    } else if let Some(file) = args.get_one::<std::path::PathBuf>(GRPC_ARG_DESCRIPTOR_SET) {
+        let descriptor_set = std::fs::read(file)
+            .map_err(|e| anyhow!("failed to read descriptor set file {}: {e}", file.display()))?;
+        let method_desc = message::find_method(&descriptor_set, service, method)?;
+        if method_desc.is_client_streaming() {
+            return Err(anyhow!("client streaming method is not supported"));
+        }
+        if method_desc.is_server_streaming() {
+            grpc_args.server_streaming = true;
+        }
+        let json = g3_clap::data::get(args, GRPC_ARG_JSON, false)?;
+        if json.is_empty() {
+            // use the default message
+            message::build_request(&method_desc, "{}")?
+        } else {
+            let json = std::str::from_utf8(&json).map_err(|e| anyhow!("invalid json data: {e}"))?;
+            message::build_request(&method_desc, json)?
+        }
+    } else {
+        Vec::new()
+    };
+    grpc_args.body = Bytes::from(message::encode_frame(&msg)?);
+
+    if args.get_flag(GRPC_ARG_SERVER_STREAMING) {
+        grpc_args.server_streaming = true;
+    }
+
+    grpc_args.headers = g3_clap::http::get_headers(args, GRPC_ARG_HEADER)?;
+    if let Some(code) = args.get_one::<u32>(GRPC_ARG_OK_STATUS) {
+        grpc_args.ok_status = *code;
+    }
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, GRPC_ARG_TIMEOUT)? {
+        grpc_args.timeout = timeout;
+    }
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, GRPC_ARG_CONNECT_TIMEOUT)? {
+        grpc_args.connect_timeout = timeout;
+    }
+
+    if let Some(c) = args.get_one::<usize>(GRPC_ARG_CONNECTION_POOL)
+        && *c > 0
+    {
+        grpc_args.pool_size = Some(*c);
+    }
+    if args.get_flag(GRPC_ARG_NO_MULTIPLEX) {
+        grpc_args.no_multiplex = true;
+    }
+
+    grpc_args
+        .connect
+        .parse_args(args)
+        .context("invalid h2 connect args")?;
+
+    Ok(grpc_args)
+}
@@ -0,0 +1,117 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::array;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use g3_statsd_client::StatsdClient;
+
+use super::message::{self, GRPC_STATUS_CODE_COUNT};
+use crate::module::http::HttpRuntimeStats;
+use crate::report::BenchReport;
+use crate::target::BenchRuntimeStats;
+
+struct GrpcStatusCount {
+    current: AtomicU64,
+    total: AtomicU64,
+}
+
+impl GrpcStatusCount {
+    fn new() -> Self {
+        GrpcStatusCount {
+            current: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    fn swap(&self) -> u64 {
+        let v = self.current.swap(0, Ordering::Relaxed);
+        self.total.fetch_add(v, Ordering::Relaxed);
+        v
+    }
+
+    fn get_total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed) + self.current.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct GrpcRuntimeStats {
+    target: &'static str,
+    pub(super) http: Arc<HttpRuntimeStats>,
+    status: [GrpcStatusCount; GRPC_STATUS_CODE_COUNT],
+    status_invalid: GrpcStatusCount,
+}
+
+impl GrpcRuntimeStats {
+    pub(super) fn new(target: &'static str) -> Self {
+        GrpcRuntimeStats {
+            target,
+            http: Arc::new(HttpRuntimeStats::new_tcp(target)),
+            status: array::from_fn(|_| GrpcStatusCount::new()),
+            status_invalid: GrpcStatusCount::new(),
+        }
+    }
+
+    pub(super) fn add_status(&self, code: u32) {
+        let c = self
+            .status
+            .get(code as usize)
+            .unwrap_or(&self.status_invalid);
+        c.current.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn status_iter(&self) -> impl Iterator<Item = (&'static str, &GrpcStatusCount)> {
+        self.status
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (message::status_name(i as u32), c))
+            .chain([(message::status_name(u32::MAX), &self.status_invalid)])
+    }
+}
+
+impl BenchRuntimeStats for GrpcRuntimeStats {
+    fn emit(&self, client: &mut StatsdClient) {
+        const TAG_NAME_TARGET: &str = "target";
+        const TAG_NAME_STATUS: &str = "status";
+
+        self.http.emit(client);
+
+        for (name, c) in self.status_iter() {
+            let v = c.swap();
+            if v > 0 {
+                client
+                    .count("grpc.status", v)
+                    .with_tag(TAG_NAME_TARGET, self.target)
+                    .with_tag(TAG_NAME_STATUS, name)
+                    .send();
+            }
+        }
+    }
+
+    fn summary(&self, total_time: Duration) {
+        self.http.summary(total_time);
+
+        println!("# gRPC Status");
+        for (name, c) in self.status_iter() {
+            let total = c.get_total();
+            if total > 0 {
+                println!("{:<20} {total}", format!("{name}:"));
+            }
+        }
+    }
+
+    fn report(&self, report: &mut BenchReport) {
+        self.http.report(report);
+
+        for (name, c) in self.status_iter() {
+            let total = c.get_total();
+            if total > 0 {
+                report.add_counter(format!("grpc.status.{name}"), total);
+            }
+        }
+    }
+}
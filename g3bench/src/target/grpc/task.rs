@@ -0,0 +1,257 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::poll_fn;
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use h2::client::SendRequest;
+use http::{HeaderMap, Request, StatusCode};
+use tokio::time::Instant;
+
+use super::message::{self, FrameDecoder};
+use super::{
+    BenchGrpcArgs, BenchTaskContext, GrpcRuntimeStats, H2ConnectionPool, HttpHistogramRecorder,
+    ProcArgs,
+};
+use crate::target::BenchError;
+
+pub(super) struct GrpcTaskContext {
+    args: Arc<BenchGrpcArgs>,
+    proc_args: Arc<ProcArgs>,
+
+    pool: Option<Arc<H2ConnectionPool<BenchGrpcArgs>>>,
+    h2s: Option<SendRequest<Bytes>>,
+
+    reuse_conn_count: u64,
+    static_headers: Request<()>,
+
+    runtime_stats: Arc<GrpcRuntimeStats>,
+    histogram_recorder: HttpHistogramRecorder,
+}
+
+impl Drop for GrpcTaskContext {
+    fn drop(&mut self) {
+        self.histogram_recorder
+            .record_conn_reuse_count(self.reuse_conn_count);
+    }
+}
+
+impl GrpcTaskContext {
+    pub(super) fn new(
+        args: &Arc<BenchGrpcArgs>,
+        proc_args: &Arc<ProcArgs>,
+        runtime_stats: &Arc<GrpcRuntimeStats>,
+        histogram_recorder: HttpHistogramRecorder,
+        pool: Option<Arc<H2ConnectionPool<BenchGrpcArgs>>>,
+    ) -> anyhow::Result<Self> {
+        let static_request = args
+            .build_static_request()
+            .context("failed to build static request header")?;
+        Ok(GrpcTaskContext {
+            args: Arc::clone(args),
+            proc_args: Arc::clone(proc_args),
+            pool,
+            h2s: None,
+            reuse_conn_count: 0,
+            static_headers: static_request,
+            runtime_stats: Arc::clone(runtime_stats),
+            histogram_recorder,
+        })
+    }
+
+    fn drop_connection(&mut self) {
+        self.h2s = None;
+    }
+
+    async fn fetch_stream(&mut self) -> anyhow::Result<SendRequest<Bytes>> {
+        if let Some(pool) = &self.pool {
+            return pool.fetch_stream().await;
+        }
+
+        if let Some(h2s) = self.h2s.clone()
+            && let Ok(ups_send_req) = h2s.ready().await
+        {
+            self.reuse_conn_count += 1;
+            return Ok(ups_send_req);
+        }
+
+        if self.reuse_conn_count > 0 {
+            self.histogram_recorder
+                .record_conn_reuse_count(self.reuse_conn_count);
+            self.reuse_conn_count = 0;
+        }
+
+        let http_stats = &self.runtime_stats.http;
+        http_stats.add_conn_attempt();
+        let h2s = match tokio::time::timeout(
+            self.args.connect_timeout,
+            self.args
+                .connect
+                .new_h2_connection(&self.args.target, http_stats, &self.proc_args),
+        )
+        .await
+        {
+            Ok(Ok(h2s)) => h2s,
+            Ok(Err(e)) => return Err(e),
+            Err(_) => return Err(anyhow!("timeout to get new connection")),
+        };
+        http_stats.add_conn_success();
+
+        let s = h2s
+            .clone()
+            .ready()
+            .await
+            .map_err(|e| anyhow!("failed to open new stream on new connection: {e:?}"))?;
+        self.h2s = Some(h2s);
+        Ok(s)
+    }
+
+    async fn run_with_stream(
+        &mut self,
+        time_started: Instant,
+        mut send_req: SendRequest<Bytes>,
+    ) -> anyhow::Result<(u32, HeaderMap)> {
+        let req = self.static_headers.clone();
+
+        // send hdr
+        let (rsp_fut, mut send_stream) = send_req
+            .send_request(req, false)
+            .map_err(|e| anyhow!("failed to send request: {e:?}"))?;
+        let send_hdr_time = time_started.elapsed();
+        self.histogram_recorder.record_send_hdr_time(send_hdr_time);
+
+        // send message
+        let mut data = self.args.body.clone();
+        send_stream.reserve_capacity(data.len());
+        loop {
+            match poll_fn(|cx| send_stream.poll_capacity(cx)).await {
+                Some(Ok(nw)) => {
+                    if nw >= data.len() {
+                        send_stream.send_data(data, true)?;
+                        self.histogram_recorder
+                            .record_send_all_time(time_started.elapsed());
+                        break;
+                    } else {
+                        let to_write = data.split_to(nw);
+                        send_stream.send_data(to_write, false)?;
+                    }
+                }
+                Some(Err(e)) => return Err(anyhow!("error when poll send capacity: {e}")),
+                None => {
+                    return Err(anyhow!("send stream not in send state when poll capacity"));
+                }
+            }
+        }
+
+        // recv hdr
+        let rsp = match tokio::time::timeout(self.args.timeout, rsp_fut).await {
+            Ok(Ok(rsp)) => rsp,
+            Ok(Err(e)) => return Err(anyhow!("failed to read response: {e}")),
+            Err(_) => return Err(anyhow!("timeout to read response")),
+        };
+        let (rsp, mut rsp_recv_body) = rsp.into_parts();
+        let recv_hdr_time = time_started.elapsed();
+        self.histogram_recorder.record_recv_hdr_time(recv_hdr_time);
+        if rsp.status != StatusCode::OK {
+            return Err(anyhow!(
+                "Got http rsp code {} while 200 is expected",
+                rsp.status.as_u16()
+            ));
+        }
+
+        // a Trailers-Only response will have grpc-status in the headers
+        if let Some(status) = message::get_status(&rsp.headers)? {
+            return Ok((status, rsp.headers));
+        }
+
+        // recv body
+        let mut decoder = FrameDecoder::default();
+        let recv_body = async {
+            while let Some(r) = rsp_recv_body.data().await {
+                let bytes = r.map_err(|e| anyhow!("failed to recv rsp body: {e:?}"))?;
+                rsp_recv_body
+                    .flow_control()
+                    .release_capacity(bytes.len())
+                    .map_err(|e| anyhow!("failed to release capacity while reading body: {e:?}"))?;
+                decoder.push(&bytes)?;
+            }
+            rsp_recv_body
+                .trailers()
+                .await
+                .map_err(|e| anyhow!("failed to recv rsp trailers: {e:?}"))
+        };
+        let trailers = match tokio::time::timeout(self.args.timeout, recv_body).await {
+            Ok(Ok(Some(trailers))) => trailers,
+            Ok(Ok(None)) => return Err(anyhow!("no trailers found in response")),
+            Ok(Err(e)) => return Err(e),
+            Err(_) => return Err(anyhow!("timeout to read response body")),
+        };
+        decoder.finish()?;
+
+        let status = message::get_status(&trailers)?
+            .ok_or_else(|| anyhow!("no grpc-status found in response trailers"))?;
+        if status == 0 && !self.args.server_streaming && decoder.message_count() != 1 {
+            self.runtime_stats.add_status(status);
+            return Err(anyhow!(
+                "Got {} response messages for unary call",
+                decoder.message_count()
+            ));
+        }
+
+        Ok((status, trailers))
+    }
+}
+
+impl BenchTaskContext for GrpcTaskContext {
+    fn mark_task_start(&self) {
+        self.runtime_stats.http.add_task_total();
+        self.runtime_stats.http.inc_task_alive();
+    }
+
+    fn mark_task_passed(&self) {
+        self.runtime_stats.http.add_task_passed();
+        self.runtime_stats.http.dec_task_alive();
+    }
+
+    fn mark_task_failed(&self) {
+        self.runtime_stats.http.add_task_failed();
+        self.runtime_stats.http.dec_task_alive();
+    }
+
+    async fn run(&mut self, _task_id: usize, time_started: Instant) -> Result<(), BenchError> {
+        let send_req = self
+            .fetch_stream()
+            .await
+            .context("fetch new stream failed")
+            .map_err(BenchError::Fatal)?;
+
+        match self.run_with_stream(time_started, send_req).await {
+            Ok((status, trailers)) => {
+                let total_time = time_started.elapsed();
+                self.histogram_recorder.record_total_time(total_time);
+                if self.args.no_multiplex {
+                    self.drop_connection();
+                }
+
+                self.runtime_stats.add_status(status);
+                if status != self.args.ok_status {
+                    return Err(BenchError::Task(anyhow!(
+                        "Got grpc-status {status} ({}) while {} is expected, message: {}",
+                        message::status_name(status),
+                        self.args.ok_status,
+                        message::get_message(&trailers).unwrap_or_default()
+                    )));
+                }
+                Ok(())
+            }
+            Err(e) => {
+                self.drop_connection();
+                Err(BenchError::Task(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use url::Url;
+
+use g3_icap_client::{IcapMethod, IcapServiceConfig};
+use g3_types::net::{ConnectionPoolConfig, RustlsClientConfigBuilder};
+
+use crate::module::rustls::{AppendRustlsArgs, RustlsTlsClientArgs};
+
+const ICAP_ARG_URL: &str = "url";
+const ICAP_ARG_METHOD: &str = "method";
+const ICAP_ARG_REQ_HEADER: &str = "req-header";
+const ICAP_ARG_REQ_BODY: &str = "req-body";
+const ICAP_ARG_RSP_HEADER: &str = "rsp-header";
+const ICAP_ARG_RSP_BODY: &str = "rsp-body";
+const ICAP_ARG_PREVIEW: &str = "preview";
+const ICAP_ARG_NO_PREVIEW: &str = "no-preview";
+const ICAP_ARG_ALLOW_204: &str = "allow-204";
+const ICAP_ARG_TIMEOUT: &str = "timeout";
+const ICAP_ARG_CONNECT_TIMEOUT: &str = "connect-timeout";
+const ICAP_ARG_POOL_MAX_IDLE: &str = "pool-max-idle";
+const ICAP_ARG_POOL_MIN_IDLE: &str = "pool-min-idle";
+const ICAP_ARG_NO_KEEPALIVE: &str = "no-keepalive";
+
+const ICAP_METHOD_VALUES: [&str; 3] = ["OPTIONS", "REQMOD", "RESPMOD"];
+
+pub(super) struct BenchIcapArgs {
+    pub(super) method: IcapMethod,
+    /// the encapsulated http req and/or rsp header
+    pub(super) http_header: Vec<u8>,
+    /// the encapsulated http body, will be sent in chunked encoding
+    pub(super) http_body: Vec<u8>,
+    pub(super) encapsulated: String,
+    pub(super) preview_size: Option<usize>,
+    pub(super) no_preview: bool,
+    pub(super) allow_204: bool,
+    pub(super) timeout: Duration,
+    pub(super) connect_timeout: Duration,
+    pub(super) no_keepalive: bool,
+}
+
+impl BenchIcapArgs {
+    fn new(method: IcapMethod) -> Self {
+        BenchIcapArgs {
+            method,
+            http_header: Vec::new(),
+            http_body: Vec::new(),
+            encapsulated: String::new(),
+            preview_size: None,
+            no_preview: false,
+            allow_204: false,
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(15),
+            no_keepalive: false,
+        }
+    }
+
+    fn set_encapsulated(
+        &mut self,
+        req_header: Option<Vec<u8>>,
+        rsp_header: Option<Vec<u8>>,
+        body: Option<Vec<u8>>,
+    ) {
+        let mut parts = Vec::with_capacity(3);
+        if let Some(header) = req_header {
+            parts.push(format!("req-hdr={}", self.http_header.len()));
+            self.http_header.extend_from_slice(&header);
+        }
+        if let Some(header) = rsp_header {
+            parts.push(format!("res-hdr={}", self.http_header.len()));
+            self.http_header.extend_from_slice(&header);
+        }
+        match body {
+            Some(body) if !body.is_empty() => {
+                let name = if self.method == IcapMethod::Reqmod {
+                    "req-body"
+                } else {
+                    "res-body"
+                };
+                parts.push(format!("{name}={}", self.http_header.len()));
+                self.http_body = body;
+            }
+            _ => parts.push(format!("null-body={}", self.http_header.len())),
+        }
+        self.encapsulated = parts.join(", ");
+    }
+
+    /// Get the preview size to use with the options returned by the server
+    pub(super) fn preview_size(&self, server_preview: Option<usize>) -> Option<usize> {
+        if self.no_preview || self.http_body.is_empty() {
+            return None;
+        }
+        self.preview_size.or(server_preview)
+    }
+}
+
+fn read_http_header(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut data = std::fs::read(path)
+        .map_err(|e| anyhow!("failed to read http header file {}: {e}", path.display()))?;
+    // strip all the trailing line ends, and add a standard header end
+    while let Some(b'\r' | b'\n') = data.last() {
+        data.pop();
+    }
+    if data.is_empty() {
+        return Err(anyhow!("empty http header file {}", path.display()));
+    }
+    data.extend_from_slice(b"\r\n\r\n");
+    Ok(data)
+}
+
+fn read_http_body(path: &Path) -> anyhow::Result<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| anyhow!("failed to read http body file {}: {e}", path.display()))
+}
+
+pub(super) fn add_icap_args(app: Command) -> Command {
+    app.arg(
+        Arg::new(ICAP_ARG_URL)
+            .help("ICAP service url, the scheme should be icap or icaps")
+            .required(true)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_METHOD)
+            .help("ICAP method")
+            .value_name("METHOD")
+            .short('m')
+            .long(ICAP_ARG_METHOD)
+            .num_args(1)
+            .value_parser(ICAP_METHOD_VALUES)
+            .ignore_case(true)
+            .default_value("OPTIONS"),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_REQ_HEADER)
+            .help("Read the encapsulated http request header from this file")
+            .value_name("FILE PATH")
+            .long(ICAP_ARG_REQ_HEADER)
+            .num_args(1)
+            .value_parser(value_parser!(PathBuf)),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_REQ_BODY)
+            .help("Read the encapsulated http request body from this file, for REQMOD only")
+            .value_name("FILE PATH")
+            .long(ICAP_ARG_REQ_BODY)
+            .num_args(1)
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with(ICAP_ARG_RSP_BODY),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_RSP_HEADER)
+            .help("Read the encapsulated http response header from this file, for RESPMOD only")
+            .value_name("FILE PATH")
+            .long(ICAP_ARG_RSP_HEADER)
+            .num_args(1)
+            .value_parser(value_parser!(PathBuf)),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_RSP_BODY)
+            .help("Read the encapsulated http response body from this file, for RESPMOD only")
+            .value_name("FILE PATH")
+            .long(ICAP_ARG_RSP_BODY)
+            .num_args(1)
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with(ICAP_ARG_REQ_BODY),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_PREVIEW)
+            .help(
+                "Set the preview size.\n\
+                    If not set, the one returned by the OPTIONS response will be used",
+            )
+            .value_name("SIZE")
+            .long(ICAP_ARG_PREVIEW)
+            .num_args(1)
+            .value_parser(value_parser!(usize))
+            .conflicts_with(ICAP_ARG_NO_PREVIEW),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_NO_PREVIEW)
+            .help("Disable preview")
+            .long(ICAP_ARG_NO_PREVIEW)
+            .action(ArgAction::SetTrue)
+            .conflicts_with(ICAP_ARG_PREVIEW),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_ALLOW_204)
+            .help("Add 'Allow: 204' header to the request")
+            .long(ICAP_ARG_ALLOW_204)
+            .action(ArgAction::SetTrue),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_TIMEOUT)
+            .help("ICAP response timeout")
+            .value_name("TIMEOUT DURATION")
+            .default_value("30s")
+            .long(ICAP_ARG_TIMEOUT)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_CONNECT_TIMEOUT)
+            .help("Timeout for fetching a connection, including the OPTIONS request on new ones")
+            .value_name("TIMEOUT DURATION")
+            .default_value("15s")
+            .long(ICAP_ARG_CONNECT_TIMEOUT)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_POOL_MAX_IDLE)
+            .help("Max idle connections in the connection pool")
+            .value_name("COUNT")
+            .long(ICAP_ARG_POOL_MAX_IDLE)
+            .num_args(1)
+            .value_parser(value_parser!(usize))
+            .default_value("1024"),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_POOL_MIN_IDLE)
+            .help("Min idle connections in the connection pool")
+            .value_name("COUNT")
+            .long(ICAP_ARG_POOL_MIN_IDLE)
+            .num_args(1)
+            .value_parser(value_parser!(usize))
+            .default_value("0"),
+    )
+    .arg(
+        Arg::new(ICAP_ARG_NO_KEEPALIVE)
+            .help("Do not reuse connections")
+            .long(ICAP_ARG_NO_KEEPALIVE)
+            .action(ArgAction::SetTrue),
+    )
+    .append_rustls_args()
+}
+
+pub(super) fn parse_icap_args(
+    args: &ArgMatches,
+) -> anyhow::Result<(BenchIcapArgs, IcapServiceConfig)> {
+    let url = if let Some(v) = args.get_one::<String>(ICAP_ARG_URL) {
+        Url::parse(v).context(format!("invalid {ICAP_ARG_URL} value"))?
+    } else {
+        return Err(anyhow!("no target url set"));
+    };
+    let method = match args
+        .get_one::<String>(ICAP_ARG_METHOD)
+        .map(|s| s.to_ascii_uppercase())
+        .as_deref()
+    {
+        Some("REQMOD") => IcapMethod::Reqmod,
+        Some("RESPMOD") => IcapMethod::Respmod,
+        _ => IcapMethod::Options,
+    };
+    let is_icaps = url.scheme().eq_ignore_ascii_case("icaps");
+    let mut service = IcapServiceConfig::new(method, url)?;
+    let mut icap_args = BenchIcapArgs::new(method);
+
+    let req_header = match args.get_one::<PathBuf>(ICAP_ARG_REQ_HEADER) {
+        Some(p) => Some(read_http_header(p)?),
+        None => None,
+    };
+    let rsp_header = match args.get_one::<PathBuf>(ICAP_ARG_RSP_HEADER) {
+        Some(p) => Some(read_http_header(p)?),
+        None => None,
+    };
+    let req_body = match args.get_one::<PathBuf>(ICAP_ARG_REQ_BODY) {
+        Some(p) => Some(read_http_body(p)?),
+        None => None,
+    };
+    let rsp_body = match args.get_one::<PathBuf>(ICAP_ARG_RSP_BODY) {
+        Some(p) => Some(read_http_body(p)?),
+        None => None,
+    };
+    match method {
+        IcapMethod::Options => {
+            if req_header.is_some()
+                || rsp_header.is_some()
+                || req_body.is_some()
+                || rsp_body.is_some()
+            {
+                return Err(anyhow!(
+                    "no encapsulated http message is allowed for OPTIONS"
+                ));
+            }
+        }
+        IcapMethod::Reqmod => {
+            if req_header.is_none() {
+                return Err(anyhow!("http request header is required for REQMOD"));
+            }
+            if rsp_header.is_some() || rsp_body.is_some() {
+                return Err(anyhow!("http response is not allowed for REQMOD"));
+            }
+            icap_args.set_encapsulated(req_header, None, req_body);
+        }
+        IcapMethod::Respmod => {
+            if rsp_header.is_none() {
+                return Err(anyhow!("http response header is required for RESPMOD"));
+            }
+            if req_body.is_some() {
+                return Err(anyhow!("http request body is not allowed for RESPMOD"));
+            }
+            icap_args.set_encapsulated(req_header, rsp_header, rsp_body);
+        }
+    }
+
+    if let Some(size) = args.get_one::<usize>(ICAP_ARG_PREVIEW) {
+        icap_args.preview_size = Some(*size);
+    }
+    if args.get_flag(ICAP_ARG_NO_PREVIEW) {
+        icap_args.no_preview = true;
+    }
+    if args.get_flag(ICAP_ARG_ALLOW_204) {
+        icap_args.allow_204 = true;
+    }
+
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, ICAP_ARG_TIMEOUT)? {
+        icap_args.timeout = timeout;
+    }
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, ICAP_ARG_CONNECT_TIMEOUT)? {
+        icap_args.connect_timeout = timeout;
+    }
+
+    let max_idle = args
+        .get_one::<usize>(ICAP_ARG_POOL_MAX_IDLE)
+        .copied()
+        .unwrap_or(1024);
+    let min_idle = args
+        .get_one::<usize>(ICAP_ARG_POOL_MIN_IDLE)
+        .copied()
+        .unwrap_or(0);
+    if min_idle > max_idle {
+        return Err(anyhow!(
+            "{ICAP_ARG_POOL_MIN_IDLE} should not be larger than {ICAP_ARG_POOL_MAX_IDLE}"
+        ));
+    }
+    service.set_connection_pool(ConnectionPoolConfig::new(max_idle, min_idle));
+    if args.get_flag(ICAP_ARG_NO_KEEPALIVE) {
+        icap_args.no_keepalive = true;
+    }
+
+    if is_icaps {
+        let mut tls = RustlsTlsClientArgs {
+            config: Some(RustlsClientConfigBuilder::default()),
+            ..Default::default()
+        };
+        tls.parse_tls_args(args)?;
+        if let Some(config) = tls.config.take() {
+            service.set_tls_client(config);
+        }
+        if let Some(name) = tls.tls_name.take() {
+            service.set_tls_name(name);
+        }
+    }
+
+    Ok((icap_args, service))
+}
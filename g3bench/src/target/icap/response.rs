@@ -0,0 +1,188 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+use tokio::io::AsyncBufRead;
+
+use g3_icap_client::parse::{HeaderLine, StatusLine};
+use g3_io_ext::LimitedBufReadExt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Encapsulated {
+    /// the size of the encapsulated http header(s)
+    pub(super) header_size: usize,
+    pub(super) has_body: bool,
+}
+
+impl Encapsulated {
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        let mut last = None;
+        for p in value.split(',') {
+            let Some((name, offset)) = p.trim().split_once('=') else {
+                return Err(anyhow!("invalid Encapsulated header value {value}"));
+            };
+            let offset = offset
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| anyhow!("invalid offset in Encapsulated header value {value}"))?;
+            let name = name.trim().to_ascii_lowercase();
+            match name.as_str() {
+                "req-hdr" | "res-hdr" | "req-body" | "res-body" | "opt-body" | "null-body" => {}
+                _ => return Err(anyhow!("unknown entity {name} in Encapsulated header")),
+            }
+            last = Some((name, offset));
+        }
+        match last {
+            Some((name, offset)) if name.ends_with("-body") => Ok(Encapsulated {
+                header_size: offset,
+                has_body: name != "null-body",
+            }),
+            _ => Err(anyhow!(
+                "no body entity found in Encapsulated header {value}"
+            )),
+        }
+    }
+}
+
+pub(super) struct IcapResponseHead {
+    pub(super) code: u16,
+    pub(super) istag: Option<String>,
+    pub(super) keep_alive: bool,
+    pub(super) encapsulated: Option<Encapsulated>,
+}
+
+impl IcapResponseHead {
+    fn parse_status_line(line: &[u8]) -> anyhow::Result<Self> {
+        let status =
+            StatusLine::parse(line).map_err(|e| anyhow!("invalid icap status line: {e}"))?;
+        Ok(IcapResponseHead {
+            code: status.code,
+            istag: None,
+            keep_alive: true,
+            encapsulated: None,
+        })
+    }
+
+    fn parse_header_line(&mut self, line: &[u8]) -> anyhow::Result<()> {
+        let header =
+            HeaderLine::parse(line).map_err(|e| anyhow!("invalid icap header line: {e}"))?;
+        match header.name.to_lowercase().as_str() {
+            "istag" => self.istag = Some(header.value.to_string()),
+            "connection" => {
+                for v in header.value.split(',') {
+                    if v.trim().eq_ignore_ascii_case("close") {
+                        self.keep_alive = false;
+                    }
+                }
+            }
+            "encapsulated" => self.encapsulated = Some(Encapsulated::parse(header.value)?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(super) async fn recv<R>(reader: &mut R, max_header_size: usize) -> anyhow::Result<Self>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let mut line_buf = Vec::<u8>::with_capacity(1024);
+        let mut header_size = 0;
+
+        let (found, nr) = reader
+            .limited_read_until(b'\n', max_header_size, &mut line_buf)
+            .await
+            .map_err(|e| anyhow!("failed to read icap status line: {e}"))?;
+        if nr == 0 {
+            return Err(anyhow!("connection closed by remote"));
+        }
+        if !found {
+            return Err(anyhow!("too long icap status line"));
+        }
+        header_size += nr;
+        let mut head = IcapResponseHead::parse_status_line(&line_buf)?;
+
+        loop {
+            if header_size >= max_header_size {
+                return Err(anyhow!("too large icap response header"));
+            }
+            line_buf.clear();
+            let max_len = max_header_size - header_size;
+            let (found, nr) = reader
+                .limited_read_until(b'\n', max_len, &mut line_buf)
+                .await
+                .map_err(|e| anyhow!("failed to read icap header line: {e}"))?;
+            if nr == 0 {
+                return Err(anyhow!("connection closed by remote"));
+            }
+            if !found {
+                return Err(anyhow!("too large icap response header"));
+            }
+            header_size += nr;
+            if line_buf == b"\n" || line_buf == b"\r\n" {
+                break;
+            }
+            head.parse_header_line(&line_buf)?;
+        }
+
+        Ok(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encapsulated() {
+        let v = Encapsulated::parse("null-body=0").unwrap();
+        assert_eq!(
+            v,
+            Encapsulated {
+                header_size: 0,
+                has_body: false
+            }
+        );
+
+        let v = Encapsulated::parse("req-hdr=0, res-hdr=137, res-body=296").unwrap();
+        assert_eq!(
+            v,
+            Encapsulated {
+                header_size: 296,
+                has_body: true
+            }
+        );
+
+        let v = Encapsulated::parse("req-hdr=0, null-body=170").unwrap();
+        assert_eq!(
+            v,
+            Encapsulated {
+                header_size: 170,
+                has_body: false
+            }
+        );
+
+        assert!(Encapsulated::parse("req-hdr=0").is_err());
+        assert!(Encapsulated::parse("req-hdr=x, null-body=0").is_err());
+        assert!(Encapsulated::parse("foo=0, null-body=0").is_err());
+    }
+
+    #[test]
+    fn head() {
+        let mut head = IcapResponseHead::parse_status_line(b"ICAP/1.0 204 No Content\r\n").unwrap();
+        assert_eq!(head.code, 204);
+        assert!(head.keep_alive);
+
+        head.parse_header_line(b"ISTag: \"W3E4R7U9-L2E4-2\"\r\n")
+            .unwrap();
+        assert_eq!(head.istag.as_deref(), Some("\"W3E4R7U9-L2E4-2\""));
+        head.parse_header_line(b"Connection: close\r\n").unwrap();
+        assert!(!head.keep_alive);
+        head.parse_header_line(b"Encapsulated: null-body=0\r\n")
+            .unwrap();
+        assert!(head.encapsulated.is_some());
+
+        assert!(IcapResponseHead::parse_status_line(b"HTTP/1.1 200 OK\r\n").is_err());
+    }
+}
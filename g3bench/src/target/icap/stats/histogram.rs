@@ -0,0 +1,88 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use g3_histogram::{HistogramRecorder, KeepingHistogram};
+use g3_statsd_client::StatsdClient;
+use g3_std_ext::time::DurationExt;
+
+use crate::report::BenchReport;
+use crate::target::BenchHistogram;
+
+pub(crate) struct IcapHistogram {
+    send_all_time: KeepingHistogram<u64>,
+    recv_hdr_time: KeepingHistogram<u64>,
+    total_time: KeepingHistogram<u64>,
+}
+
+impl IcapHistogram {
+    pub(crate) fn new() -> (Self, IcapHistogramRecorder) {
+        let (send_all_time_h, send_all_time_r) = KeepingHistogram::new();
+        let (recv_hdr_time_h, recv_hdr_time_r) = KeepingHistogram::new();
+        let (total_time_h, total_time_r) = KeepingHistogram::new();
+        let h = IcapHistogram {
+            send_all_time: send_all_time_h,
+            recv_hdr_time: recv_hdr_time_h,
+            total_time: total_time_h,
+        };
+        let r = IcapHistogramRecorder {
+            send_all_time: send_all_time_r,
+            recv_hdr_time: recv_hdr_time_r,
+            total_time: total_time_r,
+        };
+        (h, r)
+    }
+}
+
+impl BenchHistogram for IcapHistogram {
+    fn refresh(&mut self) {
+        self.send_all_time.refresh().unwrap();
+        self.recv_hdr_time.refresh().unwrap();
+        self.total_time.refresh().unwrap();
+    }
+
+    fn emit(&self, client: &mut StatsdClient) {
+        self.emit_histogram(client, self.send_all_time.inner(), "icap.time.send_all");
+        self.emit_histogram(client, self.recv_hdr_time.inner(), "icap.time.recv_hdr");
+        self.emit_histogram(client, self.total_time.inner(), "icap.time.total");
+    }
+
+    fn summary(&self) {
+        Self::summary_histogram_title("# Duration Times");
+        Self::summary_duration_line("Send all:", self.send_all_time.inner());
+        Self::summary_duration_line("Recv hdr:", self.recv_hdr_time.inner());
+        Self::summary_duration_line("Total:", self.total_time.inner());
+        Self::summary_newline();
+        Self::summary_total_percentage(self.total_time.inner());
+    }
+
+    fn report(&self, report: &mut BenchReport) {
+        report.add_duration_histogram("time.send_all", self.send_all_time.inner());
+        report.add_duration_histogram("time.recv_hdr", self.recv_hdr_time.inner());
+        report.add_duration_histogram("time.total", self.total_time.inner());
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct IcapHistogramRecorder {
+    send_all_time: HistogramRecorder<u64>,
+    recv_hdr_time: HistogramRecorder<u64>,
+    total_time: HistogramRecorder<u64>,
+}
+
+impl IcapHistogramRecorder {
+    pub(crate) fn record_send_all_time(&mut self, dur: Duration) {
+        let _ = self.send_all_time.record(dur.as_nanos_u64());
+    }
+
+    pub(crate) fn record_recv_hdr_time(&mut self, dur: Duration) {
+        let _ = self.recv_hdr_time.record(dur.as_nanos_u64());
+    }
+
+    pub(crate) fn record_total_time(&mut self, dur: Duration) {
+        let _ = self.total_time.record(dur.as_nanos_u64());
+    }
+}
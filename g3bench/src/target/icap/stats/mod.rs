@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod runtime;
+pub(crate) use runtime::IcapRuntimeStats;
+
+mod histogram;
+pub(crate) use histogram::{IcapHistogram, IcapHistogramRecorder};
@@ -0,0 +1,170 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+use g3_statsd_client::StatsdClient;
+
+use crate::report::BenchReport;
+use crate::target::BenchRuntimeStats;
+
+#[derive(Default)]
+pub(crate) struct IcapRuntimeStats {
+    task_total: AtomicU64,
+    task_alive: AtomicI64,
+    task_passed: AtomicU64,
+    task_failed: AtomicU64,
+    conn_new: AtomicU64,
+    conn_new_total: AtomicU64,
+    conn_reuse: AtomicU64,
+    conn_reuse_total: AtomicU64,
+
+    rsp_200: AtomicU64,
+    rsp_200_total: AtomicU64,
+    rsp_204: AtomicU64,
+    rsp_204_total: AtomicU64,
+    rsp_other: AtomicU64,
+    rsp_other_total: AtomicU64,
+
+    istag_change: AtomicU64,
+    istag_change_total: AtomicU64,
+    istag: Mutex<String>,
+}
+
+impl IcapRuntimeStats {
+    pub(crate) fn add_task_total(&self) {
+        self.task_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_task_alive(&self) {
+        self.task_alive.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dec_task_alive(&self) {
+        self.task_alive.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_task_passed(&self) {
+        self.task_passed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_task_failed(&self) {
+        self.task_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_conn(&self, reused: bool) {
+        if reused {
+            self.conn_reuse.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.conn_new.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn add_rsp_code(&self, code: u16) {
+        match code {
+            200 => self.rsp_200.fetch_add(1, Ordering::Relaxed),
+            204 => self.rsp_204.fetch_add(1, Ordering::Relaxed),
+            _ => self.rsp_other.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub(crate) fn check_istag(&self, istag: &str) {
+        let mut last = self.istag.lock().unwrap();
+        if last.as_str() != istag {
+            if !last.is_empty() {
+                self.istag_change.fetch_add(1, Ordering::Relaxed);
+            }
+            *last = istag.to_string();
+        }
+    }
+}
+
+impl BenchRuntimeStats for IcapRuntimeStats {
+    fn emit(&self, client: &mut StatsdClient) {
+        macro_rules! emit_count {
+            ($field:ident, $name:literal) => {
+                let $field = self.$field.swap(0, Ordering::Relaxed);
+                client.count(concat!("icap.", $name), $field).send();
+            };
+        }
+
+        macro_rules! emit_count_with_total {
+            ($field:ident, $total:ident, $name:literal) => {
+                emit_count!($field, $name);
+                self.$total.fetch_add($field, Ordering::Relaxed);
+            };
+        }
+
+        let task_alive = self.task_alive.load(Ordering::Relaxed);
+        client.gauge("icap.task.alive", task_alive).send();
+
+        emit_count!(task_total, "task.total");
+        emit_count!(task_passed, "task.passed");
+        emit_count!(task_failed, "task.failed");
+        emit_count_with_total!(conn_new, conn_new_total, "connection.new");
+        emit_count_with_total!(conn_reuse, conn_reuse_total, "connection.reuse");
+        emit_count_with_total!(rsp_200, rsp_200_total, "response.200");
+        emit_count_with_total!(rsp_204, rsp_204_total, "response.204");
+        emit_count_with_total!(rsp_other, rsp_other_total, "response.other");
+        emit_count_with_total!(istag_change, istag_change_total, "istag.change");
+    }
+
+    fn summary(&self, _total_time: Duration) {
+        macro_rules! get_total {
+            ($field:ident, $total:ident) => {
+                self.$total.load(Ordering::Relaxed) + self.$field.load(Ordering::Relaxed)
+            };
+        }
+
+        println!("# Connections");
+        let total_new = get_total!(conn_new, conn_new_total);
+        println!("New count:     {total_new}");
+        let total_reuse = get_total!(conn_reuse, conn_reuse_total);
+        println!("Reuse count:   {total_reuse}");
+
+        println!("# Responses");
+        let total_200 = get_total!(rsp_200, rsp_200_total);
+        let total_204 = get_total!(rsp_204, rsp_204_total);
+        let total_other = get_total!(rsp_other, rsp_other_total);
+        let total = total_200 + total_204 + total_other;
+        println!("200 count:     {total_200}");
+        println!("204 count:     {total_204}");
+        println!("Other count:   {total_other}");
+        if total > 0 {
+            println!(
+                "200 ratio:     {:.2}%",
+                (total_200 as f64 / total as f64) * 100.0
+            );
+            println!(
+                "204 ratio:     {:.2}%",
+                (total_204 as f64 / total as f64) * 100.0
+            );
+        }
+
+        println!("# ISTag");
+        let istag = self.istag.lock().unwrap();
+        println!("Last value:    {istag}");
+        let total_change = get_total!(istag_change, istag_change_total);
+        println!("Change count:  {total_change}");
+    }
+
+    fn report(&self, report: &mut BenchReport) {
+        macro_rules! report_total {
+            ($field:ident, $total:ident, $name:literal) => {
+                let v = self.$total.load(Ordering::Relaxed) + self.$field.load(Ordering::Relaxed);
+                report.add_counter($name, v);
+            };
+        }
+
+        report_total!(conn_new, conn_new_total, "connection.new");
+        report_total!(conn_reuse, conn_reuse_total, "connection.reuse");
+        report_total!(rsp_200, rsp_200_total, "response.200");
+        report_total!(rsp_204, rsp_204_total, "response.204");
+        report_total!(rsp_other, rsp_other_total, "response.other");
+        report_total!(istag_change, istag_change_total, "istag.change");
+    }
+}
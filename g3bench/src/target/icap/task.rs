@@ -0,0 +1,254 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::Write;
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_http::HttpBodyDecodeReader;
+use g3_icap_client::{IcapClientConnection, IcapMethod, IcapServiceClient};
+
+use super::response::IcapResponseHead;
+use super::{BenchIcapArgs, IcapHistogramRecorder, IcapRuntimeStats};
+use crate::target::{BenchError, BenchTaskContext};
+
+const ICAP_MAX_HEADER_SIZE: usize = 8192;
+
+pub(super) struct IcapTaskContext {
+    args: Arc<BenchIcapArgs>,
+    client: Arc<IcapServiceClient>,
+
+    runtime_stats: Arc<IcapRuntimeStats>,
+    histogram_recorder: IcapHistogramRecorder,
+}
+
+impl IcapTaskContext {
+    pub(super) fn new(
+        args: &Arc<BenchIcapArgs>,
+        client: &Arc<IcapServiceClient>,
+        runtime_stats: &Arc<IcapRuntimeStats>,
+        histogram_recorder: IcapHistogramRecorder,
+    ) -> Self {
+        IcapTaskContext {
+            args: Arc::clone(args),
+            client: Arc::clone(client),
+            runtime_stats: Arc::clone(runtime_stats),
+            histogram_recorder,
+        }
+    }
+
+    /// Build the request, with the size of body data that has been included
+    fn build_request(&self, preview_size: Option<usize>) -> (Vec<u8>, usize) {
+        let partial_header = self.client.partial_request_header();
+        let body = self.args.http_body.as_slice();
+
+        let mut buf = Vec::with_capacity(
+            partial_header.len() + self.args.http_header.len() + body.len() + 128,
+        );
+        buf.extend_from_slice(partial_header);
+        if self.args.allow_204 {
+            buf.extend_from_slice(b"Allow: 204\r\n");
+        }
+        if self.args.method == IcapMethod::Options {
+            buf.extend_from_slice(b"Encapsulated: null-body=0\r\n\r\n");
+            return (buf, 0);
+        }
+        if let Some(size) = preview_size {
+            let _ = write!(buf, "Preview: {size}\r\n");
+        }
+        let _ = write!(buf, "Encapsulated: {}\r\n\r\n", self.args.encapsulated);
+        buf.extend_from_slice(&self.args.http_header);
+        if body.is_empty() {
+            return (buf, 0);
+        }
+
+        match preview_size {
+            Some(size) => {
+                let size = size.min(body.len());
+                push_chunk(&mut buf, &body[..size]);
+                if size == body.len() {
+                    buf.extend_from_slice(b"0; ieof\r\n\r\n");
+                } else {
+                    buf.extend_from_slice(b"0\r\n\r\n");
+                }
+                (buf, size)
+            }
+            None => {
+                push_chunk(&mut buf, body);
+                buf.extend_from_slice(b"0\r\n\r\n");
+                (buf, body.len())
+            }
+        }
+    }
+
+    async fn run_with_connection(
+        &mut self,
+        time_started: Instant,
+        conn: &mut IcapClientConnection,
+        preview_size: Option<usize>,
+    ) -> anyhow::Result<IcapResponseHead> {
+        let (req, body_sent) = self.build_request(preview_size);
+        let left_body = &self.args.http_body[body_sent..];
+        conn.writer
+            .write_all(&req)
+            .await
+            .map_err(|e| anyhow!("failed to send request: {e}"))?;
+        conn.writer
+            .flush()
+            .await
+            .map_err(|e| anyhow!("failed to flush request: {e}"))?;
+        if left_body.is_empty() {
+            self.histogram_recorder
+                .record_send_all_time(time_started.elapsed());
+        }
+
+        let mut head = self.recv_response_head(conn).await?;
+        if head.code == 100 {
+            if left_body.is_empty() {
+                return Err(anyhow!("unexpected 100 Continue response"));
+            }
+            let mut buf = Vec::with_capacity(left_body.len() + 32);
+            push_chunk(&mut buf, left_body);
+            buf.extend_from_slice(b"0\r\n\r\n");
+            conn.writer
+                .write_all(&buf)
+                .await
+                .map_err(|e| anyhow!("failed to send body after preview: {e}"))?;
+            conn.writer
+                .flush()
+                .await
+                .map_err(|e| anyhow!("failed to flush body after preview: {e}"))?;
+            self.histogram_recorder
+                .record_send_all_time(time_started.elapsed());
+
+            head = self.recv_response_head(conn).await?;
+        }
+        self.histogram_recorder
+            .record_recv_hdr_time(time_started.elapsed());
+
+        self.runtime_stats.add_rsp_code(head.code);
+        if let Some(istag) = &head.istag {
+            self.runtime_stats.check_istag(istag);
+        }
+
+        match head.code {
+            204 => {}
+            200 => {
+                let encapsulated = match &head.encapsulated {
+                    Some(v) => v,
+                    None if self.args.method == IcapMethod::Options => return Ok(head),
+                    None => return Err(anyhow!("no Encapsulated header found in response")),
+                };
+                let recv_body = async {
+                    let mut header_reader =
+                        (&mut conn.reader).take(encapsulated.header_size as u64);
+                    let nr = tokio::io::copy(&mut header_reader, &mut tokio::io::sink())
+                        .await
+                        .map_err(|e| anyhow!("failed to read encapsulated header: {e}"))?;
+                    if nr != encapsulated.header_size as u64 {
+                        return Err(anyhow!(
+                            "connection closed while reading encapsulated header"
+                        ));
+                    }
+
+                    if encapsulated.has_body {
+                        let mut body_reader =
+                            HttpBodyDecodeReader::new_chunked(&mut conn.reader, 256);
+                        tokio::io::copy(&mut body_reader, &mut tokio::io::sink())
+                            .await
+                            .map_err(|e| anyhow!("failed to read encapsulated body: {e}"))?;
+                        body_reader.trailer(128).await.map_err(|e| {
+                            anyhow!("failed to read encapsulated body trailer: {e}")
+                        })?;
+                    }
+                    Ok(())
+                };
+                match tokio::time::timeout(self.args.timeout, recv_body).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => return Err(e),
+                    Err(_) => return Err(anyhow!("timeout to read response body")),
+                }
+            }
+            code => return Err(anyhow!("Got icap rsp code {code}")),
+        }
+
+        Ok(head)
+    }
+
+    async fn recv_response_head(
+        &self,
+        conn: &mut IcapClientConnection,
+    ) -> anyhow::Result<IcapResponseHead> {
+        match tokio::time::timeout(
+            self.args.timeout,
+            IcapResponseHead::recv(&mut conn.reader, ICAP_MAX_HEADER_SIZE),
+        )
+        .await
+        {
+            Ok(r) => r,
+            Err(_) => Err(anyhow!("timeout to read response")),
+        }
+    }
+}
+
+fn push_chunk(buf: &mut Vec<u8>, data: &[u8]) {
+    if !data.is_empty() {
+        let _ = write!(buf, "{:x}\r\n", data.len());
+        buf.extend_from_slice(data);
+        buf.extend_from_slice(b"\r\n");
+    }
+}
+
+impl BenchTaskContext for IcapTaskContext {
+    fn mark_task_start(&self) {
+        self.runtime_stats.add_task_total();
+        self.runtime_stats.inc_task_alive();
+    }
+
+    fn mark_task_passed(&self) {
+        self.runtime_stats.add_task_passed();
+        self.runtime_stats.dec_task_alive();
+    }
+
+    fn mark_task_failed(&self) {
+        self.runtime_stats.add_task_failed();
+        self.runtime_stats.dec_task_alive();
+    }
+
+    async fn run(&mut self, _task_id: usize, time_started: Instant) -> Result<(), BenchError> {
+        let (mut conn, options) =
+            match tokio::time::timeout(self.args.connect_timeout, self.client.fetch_connection())
+                .await
+            {
+                Ok(Ok(v)) => v,
+                Ok(Err(e)) => {
+                    return Err(BenchError::Fatal(e.context("fetch connection failed")));
+                }
+                Err(_) => {
+                    return Err(BenchError::Fatal(anyhow!("timeout to fetch connection")));
+                }
+            };
+        self.runtime_stats.add_conn(conn.is_reused());
+
+        let preview_size = self.args.preview_size(options.preview_size());
+        let head = self
+            .run_with_connection(time_started, &mut conn, preview_size)
+            .await
+            .context("icap request failed")
+            .map_err(BenchError::Task)?;
+        self.histogram_recorder
+            .record_total_time(time_started.elapsed());
+
+        if head.keep_alive && !self.args.no_keepalive {
+            conn.mark_reader_finished();
+            conn.mark_writer_finished();
+            self.client.save_connection(conn);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{ArgMatches, Command};
+
+use super::{BenchTarget, ProcArgs};
+use crate::module::mail::{MailHistogram, MailHistogramRecorder, MailRuntimeStats};
+
+mod opts;
+use opts::BenchImapArgs;
+
+mod task;
+use task::ImapTaskContext;
+
+pub const COMMAND: &str = "imap";
+
+struct ImapTarget {
+    args: Arc<BenchImapArgs>,
+    proc_args: Arc<ProcArgs>,
+    stats: Arc<MailRuntimeStats>,
+    histogram: Option<MailHistogram>,
+    histogram_recorder: MailHistogramRecorder,
+}
+
+impl BenchTarget<MailRuntimeStats, MailHistogram, ImapTaskContext> for ImapTarget {
+    fn new_context(&self) -> anyhow::Result<ImapTaskContext> {
+        Ok(ImapTaskContext::new(
+            &self.args,
+            &self.proc_args,
+            &self.stats,
+            self.histogram_recorder.clone(),
+        ))
+    }
+
+    fn fetch_runtime_stats(&self) -> Arc<MailRuntimeStats> {
+        self.stats.clone()
+    }
+
+    fn take_histogram(&mut self) -> Option<MailHistogram> {
+        self.histogram.take()
+    }
+}
+
+pub fn command() -> Command {
+    opts::add_imap_args(Command::new(COMMAND).about("Test IMAP server"))
+}
+
+pub async fn run(proc_args: &Arc<ProcArgs>, cmd_args: &ArgMatches) -> anyhow::Result<ExitCode> {
+    let mut imap_args = opts::parse_imap_args(cmd_args)?;
+    imap_args.connect.resolve_target_address(proc_args).await?;
+
+    let runtime_stats = Arc::new(MailRuntimeStats::new(COMMAND));
+    let (histogram, histogram_recorder) = MailHistogram::new(&task::IMAP_COMMANDS);
+
+    let target = ImapTarget {
+        args: Arc::new(imap_args),
+        proc_args: Arc::clone(proc_args),
+        stats: runtime_stats,
+        histogram: Some(histogram),
+        histogram_recorder,
+    };
+
+    super::run(target, proc_args).await
+}
@@ -0,0 +1,218 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+
+use g3_types::net::UpstreamAddr;
+
+use crate::module::mail::{AppendMailConnectArgs, MailConnectArgs, generate_message};
+
+const IMAP_ARG_TARGET: &str = "target";
+const IMAP_ARG_USERNAME: &str = "username";
+const IMAP_ARG_PASSWORD: &str = "password";
+const IMAP_ARG_MAILBOX: &str = "mailbox";
+const IMAP_ARG_FETCH: &str = "fetch";
+const IMAP_ARG_FETCH_ITEMS: &str = "fetch-items";
+const IMAP_ARG_APPEND: &str = "append";
+const IMAP_ARG_MESSAGE_SIZE: &str = "message-size";
+const IMAP_ARG_MESSAGE_COUNT: &str = "message-count";
+const IMAP_ARG_TIMEOUT: &str = "timeout";
+const IMAP_ARG_CONNECT_TIMEOUT: &str = "connect-timeout";
+
+pub(super) struct BenchImapArgs {
+    pub(super) connect: MailConnectArgs,
+    pub(super) timeout: Duration,
+    pub(super) connect_timeout: Duration,
+
+    /// the quoted username and password
+    pub(super) login: String,
+    /// the quoted mailbox name
+    pub(super) mailbox: String,
+    /// the sequence set and data items
+    pub(super) fetch: Option<String>,
+    pub(super) append_message: Option<Vec<u8>>,
+    pub(super) append_count: usize,
+}
+
+impl BenchImapArgs {
+    fn new(target: UpstreamAddr) -> Self {
+        BenchImapArgs {
+            connect: MailConnectArgs::new(target),
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(15),
+            login: String::new(),
+            mailbox: quote_string("INBOX"),
+            fetch: None,
+            append_message: None,
+            append_count: 1,
+        }
+    }
+}
+
+fn quote_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+pub(super) fn add_imap_args(app: Command) -> Command {
+    app.arg(
+        Arg::new(IMAP_ARG_TARGET)
+            .help("IMAP server address, in host:port format")
+            .required(true)
+            .num_args(1)
+            .value_parser(value_parser!(UpstreamAddr)),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_USERNAME)
+            .help("Username for LOGIN")
+            .value_name("USERNAME")
+            .long(IMAP_ARG_USERNAME)
+            .required(true)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_PASSWORD)
+            .help("Password for LOGIN")
+            .value_name("PASSWORD")
+            .long(IMAP_ARG_PASSWORD)
+            .required(true)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_MAILBOX)
+            .help("Mailbox to SELECT and APPEND to")
+            .value_name("MAILBOX")
+            .long(IMAP_ARG_MAILBOX)
+            .num_args(1)
+            .default_value("INBOX"),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_FETCH)
+            .help("Run FETCH with this sequence set, e.g. 1:10")
+            .value_name("SEQUENCE SET")
+            .long(IMAP_ARG_FETCH)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_FETCH_ITEMS)
+            .help("Data items for FETCH")
+            .value_name("ITEMS")
+            .long(IMAP_ARG_FETCH_ITEMS)
+            .num_args(1)
+            .default_value("(FLAGS BODY.PEEK[])")
+            .requires(IMAP_ARG_FETCH),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_APPEND)
+            .help("Run APPEND with generated messages")
+            .long(IMAP_ARG_APPEND)
+            .action(ArgAction::SetTrue),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_MESSAGE_SIZE)
+            .help("Size of the generated message for APPEND")
+            .value_name("SIZE")
+            .long(IMAP_ARG_MESSAGE_SIZE)
+            .num_args(1)
+            .default_value("1K"),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_MESSAGE_COUNT)
+            .help("Number of messages to APPEND in each session")
+            .value_name("COUNT")
+            .long(IMAP_ARG_MESSAGE_COUNT)
+            .num_args(1)
+            .value_parser(value_parser!(usize))
+            .default_value("1"),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_TIMEOUT)
+            .help("Timeout for each IMAP response")
+            .value_name("TIMEOUT DURATION")
+            .default_value("30s")
+            .long(IMAP_ARG_TIMEOUT)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(IMAP_ARG_CONNECT_TIMEOUT)
+            .help("Timeout for connection to the server, including proxy negotiation")
+            .value_name("TIMEOUT DURATION")
+            .default_value("15s")
+            .long(IMAP_ARG_CONNECT_TIMEOUT)
+            .num_args(1),
+    )
+    .append_mail_connect_args()
+}
+
+pub(super) fn parse_imap_args(args: &ArgMatches) -> anyhow::Result<BenchImapArgs> {
+    let target = if let Some(v) = args.get_one::<UpstreamAddr>(IMAP_ARG_TARGET) {
+        v.clone()
+    } else {
+        return Err(anyhow!("no target set"));
+    };
+
+    let mut imap_args = BenchImapArgs::new(target);
+    imap_args.connect.parse_args(args)?;
+
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, IMAP_ARG_TIMEOUT)? {
+        imap_args.timeout = timeout;
+    }
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, IMAP_ARG_CONNECT_TIMEOUT)? {
+        imap_args.connect_timeout = timeout;
+    }
+
+    let Some(username) = args.get_one::<String>(IMAP_ARG_USERNAME) else {
+        return Err(anyhow!("no username set"));
+    };
+    let Some(password) = args.get_one::<String>(IMAP_ARG_PASSWORD) else {
+        return Err(anyhow!("no password set"));
+    };
+    imap_args.login = format!("{} {}", quote_string(username), quote_string(password));
+
+    if let Some(mailbox) = args.get_one::<String>(IMAP_ARG_MAILBOX) {
+        imap_args.mailbox = quote_string(mailbox);
+    }
+
+    if let Some(set) = args.get_one::<String>(IMAP_ARG_FETCH) {
+        let items = args
+            .get_one::<String>(IMAP_ARG_FETCH_ITEMS)
+            .map(|s| s.as_str())
+            .unwrap_or("(FLAGS BODY.PEEK[])");
+        imap_args.fetch = Some(format!("{set} {items}"));
+    }
+
+    if args.get_flag(IMAP_ARG_APPEND) {
+        if let Some(count) = args.get_one::<usize>(IMAP_ARG_MESSAGE_COUNT) {
+            imap_args.append_count = *count;
+        }
+        let size = g3_clap::humanize::get_usize(args, IMAP_ARG_MESSAGE_SIZE)?.unwrap_or(1024);
+        let to = std::slice::from_ref(username);
+        imap_args.append_message = Some(generate_message("g3bench@localhost", to, size));
+    }
+
+    Ok(imap_args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote() {
+        assert_eq!(quote_string("INBOX"), "\"INBOX\"");
+        assert_eq!(quote_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+}
@@ -0,0 +1,332 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_imap_proto::response::{CommandResult, Response, ResponseLineError, ServerStatus};
+use g3_io_ext::LineRecvVec;
+
+use super::{BenchImapArgs, MailHistogramRecorder, MailRuntimeStats, ProcArgs};
+use crate::module::mail::{BoxMailStream, MailConnectArgs, MailTlsMode};
+use crate::target::{BenchError, BenchTaskContext};
+
+const IMAP_MAX_LINE_SIZE: usize = 8192;
+
+pub(super) const IMAP_COMMANDS: [&str; 8] = [
+    "Connect", "Greeting", "STARTTLS", "LOGIN", "SELECT", "FETCH", "APPEND", "LOGOUT",
+];
+const IMAP_CMD_CONNECT: usize = 0;
+const IMAP_CMD_GREETING: usize = 1;
+const IMAP_CMD_STARTTLS: usize = 2;
+const IMAP_CMD_LOGIN: usize = 3;
+const IMAP_CMD_SELECT: usize = 4;
+const IMAP_CMD_FETCH: usize = 5;
+const IMAP_CMD_APPEND: usize = 6;
+const IMAP_CMD_LOGOUT: usize = 7;
+
+struct ImapConnection {
+    stream: BoxMailStream,
+    recv_buf: LineRecvVec,
+    timeout: Duration,
+    tag_id: usize,
+    tag: String,
+}
+
+impl ImapConnection {
+    fn new(stream: BoxMailStream, timeout: Duration) -> Self {
+        ImapConnection {
+            stream,
+            recv_buf: LineRecvVec::with_capacity(IMAP_MAX_LINE_SIZE),
+            timeout,
+            tag_id: 0,
+            tag: String::new(),
+        }
+    }
+
+    async fn send(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        self.stream
+            .write_all(data)
+            .await
+            .map_err(|e| anyhow!("failed to send data: {e}"))?;
+        self.stream
+            .flush()
+            .await
+            .map_err(|e| anyhow!("failed to flush data: {e}"))
+    }
+
+    async fn send_command(&mut self, cmd: &str, args: &str) -> anyhow::Result<()> {
+        self.tag_id += 1;
+        self.tag = format!("G{:04}", self.tag_id);
+        let line = if args.is_empty() {
+            format!("{} {cmd}\r\n", self.tag)
+        } else {
+            format!("{} {cmd} {args}\r\n", self.tag)
+        };
+        self.send(line.as_bytes()).await
+    }
+
+    /// Run the command and wait for the tagged response
+    async fn run_command(&mut self, cmd: &str, args: &str) -> anyhow::Result<()> {
+        self.send_command(cmd, args).await?;
+        if self.recv_response(cmd).await? {
+            return Err(anyhow!("{cmd}: unexpected continuation request"));
+        }
+        Ok(())
+    }
+
+    async fn recv_greeting(&mut self) -> anyhow::Result<bool> {
+        let line = self
+            .recv_buf
+            .read_line_with_timeout(&mut self.stream, self.timeout)
+            .await
+            .map_err(|e| anyhow!("failed to read greeting: {e}"))?;
+        let pre_authenticated = match Response::parse_line(line) {
+            Ok(Response::ServerStatus(ServerStatus::Information)) => false,
+            Ok(Response::ServerStatus(ServerStatus::Authenticated)) => true,
+            Ok(Response::ServerStatus(ServerStatus::Close)) => {
+                return Err(anyhow!(
+                    "server refused service: {}",
+                    String::from_utf8_lossy(line).trim_end()
+                ));
+            }
+            Ok(_) => {
+                return Err(anyhow!(
+                    "unexpected greeting: {}",
+                    String::from_utf8_lossy(line).trim_end()
+                ));
+            }
+            Err(e) => return Err(anyhow!("invalid greeting: {e}")),
+        };
+        self.recv_buf.consume_line();
+        Ok(pre_authenticated)
+    }
+
+    /// Receive responses until the tagged response or a continuation request.
+    /// Return true if it's a continuation request.
+    async fn recv_response(&mut self, cmd: &str) -> anyhow::Result<bool> {
+        loop {
+            let line = self
+                .recv_buf
+                .read_line_with_timeout(&mut self.stream, self.timeout)
+                .await
+                .map_err(|e| anyhow!("{cmd}: failed to read response: {e}"))?;
+            if line.starts_with(b"+") {
+                // the text part is optional for continuation request
+                self.recv_buf.consume_line();
+                return Ok(true);
+            }
+            let data = match Response::parse_line(line) {
+                Ok(Response::CommandResult(r)) => {
+                    if r.tag != self.tag {
+                        return Err(anyhow!("{cmd}: unexpected tag {} in response", r.tag));
+                    }
+                    if r.result != CommandResult::Success {
+                        return Err(anyhow!(
+                            "{cmd} failed: {}",
+                            String::from_utf8_lossy(line).trim_end()
+                        ));
+                    }
+                    self.recv_buf.consume_line();
+                    return Ok(false);
+                }
+                Ok(Response::CommandData(data)) => Some(data),
+                Ok(Response::ServerStatus(_) | Response::ContinuationRequest) => None,
+                Err(ResponseLineError::UnknownUntaggedResult) => None,
+                Err(e) => return Err(anyhow!("{cmd}: invalid response line: {e}")),
+            };
+            self.recv_buf.consume_line();
+
+            if let Some(mut data) = data {
+                while let Some(size) = data.literal_data {
+                    self.recv_literal(size)
+                        .await
+                        .map_err(|e| anyhow!("{cmd}: {e}"))?;
+                    let line = self
+                        .recv_buf
+                        .read_line_with_timeout(&mut self.stream, self.timeout)
+                        .await
+                        .map_err(|e| anyhow!("{cmd}: failed to read response: {e}"))?;
+                    data.parse_continue_line(line)
+                        .map_err(|e| anyhow!("{cmd}: invalid response line: {e}"))?;
+                    self.recv_buf.consume_line();
+                }
+            }
+        }
+    }
+
+    async fn recv_literal(&mut self, size: u64) -> anyhow::Result<()> {
+        let max_buffered = usize::try_from(size).unwrap_or(usize::MAX);
+        let buffered = self.recv_buf.consume_left(max_buffered).len() as u64;
+        let left = size - buffered;
+        if left == 0 {
+            return Ok(());
+        }
+
+        let mut reader = (&mut self.stream).take(left);
+        match tokio::time::timeout(
+            self.timeout,
+            tokio::io::copy(&mut reader, &mut tokio::io::sink()),
+        )
+        .await
+        {
+            Ok(Ok(nr)) => {
+                if nr != left {
+                    return Err(anyhow!("connection closed while reading literal data"));
+                }
+                Ok(())
+            }
+            Ok(Err(e)) => Err(anyhow!("failed to read literal data: {e}")),
+            Err(_) => Err(anyhow!("timeout to read literal data")),
+        }
+    }
+
+    async fn start_tls(self, connect: &MailConnectArgs) -> anyhow::Result<Self> {
+        if !self.recv_buf.is_empty() {
+            return Err(anyhow!("unexpected data received after STARTTLS response"));
+        }
+        let stream = connect.tls_handshake(self.stream).await?;
+        let mut conn = ImapConnection::new(stream, self.timeout);
+        conn.tag_id = self.tag_id;
+        Ok(conn)
+    }
+}
+
+pub(super) struct ImapTaskContext {
+    args: Arc<BenchImapArgs>,
+    proc_args: Arc<ProcArgs>,
+
+    runtime_stats: Arc<MailRuntimeStats>,
+    histogram_recorder: MailHistogramRecorder,
+}
+
+impl ImapTaskContext {
+    pub(super) fn new(
+        args: &Arc<BenchImapArgs>,
+        proc_args: &Arc<ProcArgs>,
+        runtime_stats: &Arc<MailRuntimeStats>,
+        histogram_recorder: MailHistogramRecorder,
+    ) -> Self {
+        ImapTaskContext {
+            args: Arc::clone(args),
+            proc_args: Arc::clone(proc_args),
+            runtime_stats: Arc::clone(runtime_stats),
+            histogram_recorder,
+        }
+    }
+
+    async fn connect(&self) -> anyhow::Result<ImapConnection> {
+        match tokio::time::timeout(
+            self.args.connect_timeout,
+            self.args.connect.new_connection(&self.proc_args),
+        )
+        .await
+        {
+            Ok(Ok(stream)) => Ok(ImapConnection::new(stream, self.args.timeout)),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(anyhow!("timeout to connect to the server")),
+        }
+    }
+
+    async fn run_session(&mut self, mut conn: ImapConnection) -> anyhow::Result<()> {
+        let time_start = Instant::now();
+        let pre_authenticated = conn.recv_greeting().await?;
+        self.histogram_recorder
+            .record_command_time(IMAP_CMD_GREETING, time_start.elapsed());
+
+        if self.args.connect.tls_mode == MailTlsMode::StartTls {
+            let time_start = Instant::now();
+            conn.run_command("STARTTLS", "").await?;
+            conn = conn.start_tls(&self.args.connect).await?;
+            self.histogram_recorder
+                .record_command_time(IMAP_CMD_STARTTLS, time_start.elapsed());
+        }
+
+        if !pre_authenticated {
+            let time_start = Instant::now();
+            conn.run_command("LOGIN", &self.args.login).await?;
+            self.histogram_recorder
+                .record_command_time(IMAP_CMD_LOGIN, time_start.elapsed());
+        }
+
+        let time_start = Instant::now();
+        conn.run_command("SELECT", &self.args.mailbox).await?;
+        self.histogram_recorder
+            .record_command_time(IMAP_CMD_SELECT, time_start.elapsed());
+
+        if let Some(fetch) = &self.args.fetch {
+            let time_start = Instant::now();
+            conn.run_command("FETCH", fetch).await?;
+            self.histogram_recorder
+                .record_command_time(IMAP_CMD_FETCH, time_start.elapsed());
+        }
+
+        if let Some(message) = &self.args.append_message {
+            let append_args = format!("{} {{{}}}", self.args.mailbox, message.len());
+            for _ in 0..self.args.append_count {
+                let time_start = Instant::now();
+                conn.send_command("APPEND", &append_args).await?;
+                if !conn.recv_response("APPEND").await? {
+                    return Err(anyhow!("APPEND: no continuation request received"));
+                }
+                conn.stream
+                    .write_all(message)
+                    .await
+                    .map_err(|e| anyhow!("failed to send message: {e}"))?;
+                conn.send(b"\r\n").await?;
+                if conn.recv_response("APPEND").await? {
+                    return Err(anyhow!("APPEND: unexpected continuation request"));
+                }
+                self.histogram_recorder
+                    .record_command_time(IMAP_CMD_APPEND, time_start.elapsed());
+
+                self.runtime_stats.add_message_sent(message.len());
+            }
+        }
+
+        let time_start = Instant::now();
+        conn.run_command("LOGOUT", "").await?;
+        self.histogram_recorder
+            .record_command_time(IMAP_CMD_LOGOUT, time_start.elapsed());
+
+        let _ = conn.stream.shutdown().await;
+        Ok(())
+    }
+}
+
+impl BenchTaskContext for ImapTaskContext {
+    fn mark_task_start(&self) {
+        self.runtime_stats.add_task_total();
+        self.runtime_stats.inc_task_alive();
+    }
+
+    fn mark_task_passed(&self) {
+        self.runtime_stats.add_task_passed();
+        self.runtime_stats.dec_task_alive();
+    }
+
+    fn mark_task_failed(&self) {
+        self.runtime_stats.add_task_failed();
+        self.runtime_stats.dec_task_alive();
+    }
+
+    async fn run(&mut self, _task_id: usize, time_started: Instant) -> Result<(), BenchError> {
+        let conn = self
+            .connect()
+            .await
+            .map_err(|e| BenchError::Fatal(e.context("connect to upstream failed")))?;
+        self.histogram_recorder
+            .record_command_time(IMAP_CMD_CONNECT, time_started.elapsed());
+
+        self.run_session(conn).await.map_err(BenchError::Task)?;
+        self.histogram_recorder
+            .record_total_time(time_started.elapsed());
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{ArgMatches, Command};
+
+use super::{BenchTarget, ProcArgs};
+use crate::module::mail::{MailHistogram, MailHistogramRecorder, MailRuntimeStats};
+
+mod opts;
+use opts::{BenchSmtpArgs, SmtpAuth};
+
+mod task;
+use task::SmtpTaskContext;
+
+pub const COMMAND: &str = "smtp";
+
+struct SmtpTarget {
+    args: Arc<BenchSmtpArgs>,
+    proc_args: Arc<ProcArgs>,
+    stats: Arc<MailRuntimeStats>,
+    histogram: Option<MailHistogram>,
+    histogram_recorder: MailHistogramRecorder,
+}
+
+impl BenchTarget<MailRuntimeStats, MailHistogram, SmtpTaskContext> for SmtpTarget {
+    fn new_context(&self) -> anyhow::Result<SmtpTaskContext> {
+        Ok(SmtpTaskContext::new(
+            &self.args,
+            &self.proc_args,
+            &self.stats,
+            self.histogram_recorder.clone(),
+        ))
+    }
+
+    fn fetch_runtime_stats(&self) -> Arc<MailRuntimeStats> {
+        self.stats.clone()
+    }
+
+    fn take_histogram(&mut self) -> Option<MailHistogram> {
+        self.histogram.take()
+    }
+}
+
+pub fn command() -> Command {
+    opts::add_smtp_args(Command::new(COMMAND).about("Test SMTP server"))
+}
+
+pub async fn run(proc_args: &Arc<ProcArgs>, cmd_args: &ArgMatches) -> anyhow::Result<ExitCode> {
+    let mut smtp_args = opts::parse_smtp_args(cmd_args)?;
+    smtp_args.connect.resolve_target_address(proc_args).await?;
+
+    let runtime_stats = Arc::new(MailRuntimeStats::new(COMMAND));
+    let (histogram, histogram_recorder) = MailHistogram::new(&task::SMTP_COMMANDS);
+
+    let target = SmtpTarget {
+        args: Arc::new(smtp_args),
+        proc_args: Arc::clone(proc_args),
+        stats: runtime_stats,
+        histogram: Some(histogram),
+        histogram_recorder,
+    };
+
+    super::run(target, proc_args).await
+}
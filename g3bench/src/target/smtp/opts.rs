@@ -0,0 +1,240 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use base64::prelude::*;
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+
+use g3_types::net::UpstreamAddr;
+
+use crate::module::mail::{AppendMailConnectArgs, MailConnectArgs, generate_message};
+
+const SMTP_ARG_TARGET: &str = "target";
+const SMTP_ARG_EHLO: &str = "ehlo";
+const SMTP_ARG_USERNAME: &str = "username";
+const SMTP_ARG_PASSWORD: &str = "password";
+const SMTP_ARG_AUTH_METHOD: &str = "auth-method";
+const SMTP_ARG_FROM: &str = "from";
+const SMTP_ARG_TO: &str = "to";
+const SMTP_ARG_MESSAGE_SIZE: &str = "message-size";
+const SMTP_ARG_MESSAGE_COUNT: &str = "message-count";
+const SMTP_ARG_TIMEOUT: &str = "timeout";
+const SMTP_ARG_CONNECT_TIMEOUT: &str = "connect-timeout";
+
+const AUTH_METHOD_VALUES: [&str; 2] = ["plain", "login"];
+
+pub(super) enum SmtpAuth {
+    /// The full AUTH PLAIN command line
+    Plain(Vec<u8>),
+    /// The base64 encoded username and password lines
+    Login {
+        username: Vec<u8>,
+        password: Vec<u8>,
+    },
+}
+
+impl SmtpAuth {
+    fn new(method: &str, username: &str, password: &str) -> Self {
+        if method.eq_ignore_ascii_case("login") {
+            SmtpAuth::Login {
+                username: format!("{}\r\n", BASE64_STANDARD.encode(username)).into_bytes(),
+                password: format!("{}\r\n", BASE64_STANDARD.encode(password)).into_bytes(),
+            }
+        } else {
+            let token = BASE64_STANDARD.encode(format!("\0{username}\0{password}"));
+            SmtpAuth::Plain(format!("AUTH PLAIN {token}\r\n").into_bytes())
+        }
+    }
+}
+
+pub(super) struct BenchSmtpArgs {
+    pub(super) connect: MailConnectArgs,
+    pub(super) timeout: Duration,
+    pub(super) connect_timeout: Duration,
+
+    pub(super) ehlo: Vec<u8>,
+    pub(super) auth: Option<SmtpAuth>,
+    pub(super) mail_from: Vec<u8>,
+    pub(super) rcpt_to: Vec<Vec<u8>>,
+    pub(super) message: Vec<u8>,
+    pub(super) message_count: usize,
+}
+
+impl BenchSmtpArgs {
+    fn new(target: UpstreamAddr) -> Self {
+        BenchSmtpArgs {
+            connect: MailConnectArgs::new(target),
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(15),
+            ehlo: Vec::new(),
+            auth: None,
+            mail_from: Vec::new(),
+            rcpt_to: Vec::new(),
+            message: Vec::new(),
+            message_count: 1,
+        }
+    }
+
+    /// Whether there will be any mail transaction in the session
+    pub(super) fn send_message(&self) -> bool {
+        !self.rcpt_to.is_empty() && self.message_count > 0
+    }
+}
+
+pub(super) fn add_smtp_args(app: Command) -> Command {
+    app.arg(
+        Arg::new(SMTP_ARG_TARGET)
+            .help("SMTP server address, in host:port format")
+            .required(true)
+            .num_args(1)
+            .value_parser(value_parser!(UpstreamAddr)),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_EHLO)
+            .help("Domain to send in the EHLO command")
+            .value_name("DOMAIN")
+            .long(SMTP_ARG_EHLO)
+            .num_args(1)
+            .default_value("localhost"),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_USERNAME)
+            .help("Username for AUTH")
+            .value_name("USERNAME")
+            .long(SMTP_ARG_USERNAME)
+            .num_args(1)
+            .requires(SMTP_ARG_PASSWORD),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_PASSWORD)
+            .help("Password for AUTH")
+            .value_name("PASSWORD")
+            .long(SMTP_ARG_PASSWORD)
+            .num_args(1)
+            .requires(SMTP_ARG_USERNAME),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_AUTH_METHOD)
+            .help("SASL mechanism for AUTH")
+            .value_name("METHOD")
+            .long(SMTP_ARG_AUTH_METHOD)
+            .num_args(1)
+            .value_parser(AUTH_METHOD_VALUES)
+            .ignore_case(true)
+            .default_value("plain"),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_FROM)
+            .help("Reverse path to send in the MAIL command")
+            .value_name("ADDRESS")
+            .long(SMTP_ARG_FROM)
+            .num_args(1)
+            .default_value("g3bench@localhost"),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_TO)
+            .help(
+                "Forward path to send in the RCPT command, can be set multiple times.\n\
+                    No message will be sent if not set",
+            )
+            .value_name("ADDRESS")
+            .long(SMTP_ARG_TO)
+            .num_args(1)
+            .action(ArgAction::Append),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_MESSAGE_SIZE)
+            .help("Size of the generated message")
+            .value_name("SIZE")
+            .long(SMTP_ARG_MESSAGE_SIZE)
+            .num_args(1)
+            .default_value("1K"),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_MESSAGE_COUNT)
+            .help("Number of messages to send in each session")
+            .value_name("COUNT")
+            .long(SMTP_ARG_MESSAGE_COUNT)
+            .num_args(1)
+            .value_parser(value_parser!(usize))
+            .default_value("1"),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_TIMEOUT)
+            .help("Timeout for each SMTP reply")
+            .value_name("TIMEOUT DURATION")
+            .default_value("30s")
+            .long(SMTP_ARG_TIMEOUT)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(SMTP_ARG_CONNECT_TIMEOUT)
+            .help("Timeout for connection to the server, including proxy negotiation")
+            .value_name("TIMEOUT DURATION")
+            .default_value("15s")
+            .long(SMTP_ARG_CONNECT_TIMEOUT)
+            .num_args(1),
+    )
+    .append_mail_connect_args()
+}
+
+pub(super) fn parse_smtp_args(args: &ArgMatches) -> anyhow::Result<BenchSmtpArgs> {
+    let target = if let Some(v) = args.get_one::<UpstreamAddr>(SMTP_ARG_TARGET) {
+        v.clone()
+    } else {
+        return Err(anyhow!("no target set"));
+    };
+
+    let mut smtp_args = BenchSmtpArgs::new(target);
+    smtp_args.connect.parse_args(args)?;
+
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, SMTP_ARG_TIMEOUT)? {
+        smtp_args.timeout = timeout;
+    }
+    if let Some(timeout) = g3_clap::humanize::get_duration(args, SMTP_ARG_CONNECT_TIMEOUT)? {
+        smtp_args.connect_timeout = timeout;
+    }
+
+    if let Some(domain) = args.get_one::<String>(SMTP_ARG_EHLO) {
+        smtp_args.ehlo = format!("EHLO {domain}\r\n").into_bytes();
+    }
+
+    if let Some(username) = args.get_one::<String>(SMTP_ARG_USERNAME) {
+        let Some(password) = args.get_one::<String>(SMTP_ARG_PASSWORD) else {
+            return Err(anyhow!("no password set for user {username}"));
+        };
+        let method = args
+            .get_one::<String>(SMTP_ARG_AUTH_METHOD)
+            .map(|s| s.as_str())
+            .unwrap_or("plain");
+        smtp_args.auth = Some(SmtpAuth::new(method, username, password));
+    }
+
+    let from = args
+        .get_one::<String>(SMTP_ARG_FROM)
+        .map(|s| s.as_str())
+        .unwrap_or_default();
+    smtp_args.mail_from = format!("MAIL FROM:<{from}>\r\n").into_bytes();
+
+    let to = args
+        .get_many::<String>(SMTP_ARG_TO)
+        .map(|v| v.cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    smtp_args.rcpt_to = to
+        .iter()
+        .map(|addr| format!("RCPT TO:<{addr}>\r\n").into_bytes())
+        .collect();
+
+    if let Some(count) = args.get_one::<usize>(SMTP_ARG_MESSAGE_COUNT) {
+        smtp_args.message_count = *count;
+    }
+    if let Some(size) = g3_clap::humanize::get_usize(args, SMTP_ARG_MESSAGE_SIZE)? {
+        smtp_args.message = generate_message(from, &to, size);
+    }
+
+    Ok(smtp_args)
+}
@@ -0,0 +1,271 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use tokio::io::AsyncWriteExt;
+use tokio::time::Instant;
+
+use g3_io_ext::LineRecvBuf;
+use g3_smtp_proto::response::{ReplyCode, ResponseParser};
+
+use super::{BenchSmtpArgs, MailHistogramRecorder, MailRuntimeStats, ProcArgs, SmtpAuth};
+use crate::module::mail::{BoxMailStream, MailConnectArgs, MailTlsMode};
+use crate::target::{BenchError, BenchTaskContext};
+
+pub(super) const SMTP_COMMANDS: [&str; 9] = [
+    "Connect", "Greeting", "EHLO", "STARTTLS", "AUTH", "MAIL", "RCPT", "DATA", "QUIT",
+];
+const SMTP_CMD_CONNECT: usize = 0;
+const SMTP_CMD_GREETING: usize = 1;
+const SMTP_CMD_EHLO: usize = 2;
+const SMTP_CMD_STARTTLS: usize = 3;
+const SMTP_CMD_AUTH: usize = 4;
+const SMTP_CMD_MAIL: usize = 5;
+const SMTP_CMD_RCPT: usize = 6;
+const SMTP_CMD_DATA: usize = 7;
+const SMTP_CMD_QUIT: usize = 8;
+
+struct SmtpConnection {
+    stream: BoxMailStream,
+    recv_buf: LineRecvBuf<{ ResponseParser::MAX_LINE_SIZE }>,
+    timeout: Duration,
+}
+
+impl SmtpConnection {
+    fn new(stream: BoxMailStream, timeout: Duration) -> Self {
+        SmtpConnection {
+            stream,
+            recv_buf: LineRecvBuf::default(),
+            timeout,
+        }
+    }
+
+    async fn send(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        self.stream
+            .write_all(data)
+            .await
+            .map_err(|e| anyhow!("failed to send data: {e}"))?;
+        self.stream
+            .flush()
+            .await
+            .map_err(|e| anyhow!("failed to flush data: {e}"))
+    }
+
+    async fn recv_reply(&mut self) -> anyhow::Result<(ReplyCode, String)> {
+        let mut rsp = ResponseParser::default();
+        let mut first_msg = String::new();
+        loop {
+            let line = self
+                .recv_buf
+                .read_line_with_timeout(&mut self.stream, self.timeout)
+                .await
+                .map_err(|e| anyhow!("failed to read reply: {e}"))?;
+            let msg = rsp
+                .feed_line(line)
+                .map_err(|e| anyhow!("invalid reply line: {e}"))?;
+            if rsp.is_first_line() {
+                first_msg = String::from_utf8_lossy(msg).to_string();
+            }
+            let finished = rsp.finished();
+            self.recv_buf.consume_line();
+            if finished {
+                return Ok((rsp.code(), first_msg));
+            }
+        }
+    }
+
+    async fn expect_reply(&mut self, cmd: &str, expected: ReplyCode) -> anyhow::Result<()> {
+        let (code, msg) = self.recv_reply().await?;
+        if code != expected {
+            return Err(anyhow!(
+                "{cmd}: got reply code {code} while {expected} is expected: {msg}"
+            ));
+        }
+        Ok(())
+    }
+
+    async fn send_command(
+        &mut self,
+        cmd: &str,
+        line: &[u8],
+        expected: ReplyCode,
+    ) -> anyhow::Result<()> {
+        self.send(line).await?;
+        self.expect_reply(cmd, expected).await
+    }
+
+    async fn start_tls(self, connect: &MailConnectArgs) -> anyhow::Result<Self> {
+        if !self.recv_buf.is_empty() {
+            return Err(anyhow!("unexpected data received after STARTTLS reply"));
+        }
+        let stream = connect.tls_handshake(self.stream).await?;
+        Ok(SmtpConnection::new(stream, self.timeout))
+    }
+}
+
+pub(super) struct SmtpTaskContext {
+    args: Arc<BenchSmtpArgs>,
+    proc_args: Arc<ProcArgs>,
+
+    runtime_stats: Arc<MailRuntimeStats>,
+    histogram_recorder: MailHistogramRecorder,
+}
+
+impl SmtpTaskContext {
+    pub(super) fn new(
+        args: &Arc<BenchSmtpArgs>,
+        proc_args: &Arc<ProcArgs>,
+        runtime_stats: &Arc<MailRuntimeStats>,
+        histogram_recorder: MailHistogramRecorder,
+    ) -> Self {
+        SmtpTaskContext {
+            args: Arc::clone(args),
+            proc_args: Arc::clone(proc_args),
+            runtime_stats: Arc::clone(runtime_stats),
+            histogram_recorder,
+        }
+    }
+
+    async fn connect(&self) -> anyhow::Result<SmtpConnection> {
+        match tokio::time::timeout(
+            self.args.connect_timeout,
+            self.args.connect.new_connection(&self.proc_args),
+        )
+        .await
+        {
+            Ok(Ok(stream)) => Ok(SmtpConnection::new(stream, self.args.timeout)),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(anyhow!("timeout to connect to the server")),
+        }
+    }
+
+    async fn run_session(&mut self, mut conn: SmtpConnection) -> anyhow::Result<()> {
+        let time_start = Instant::now();
+        conn.expect_reply("greeting", ReplyCode::SERVICE_READY)
+            .await?;
+        self.histogram_recorder
+            .record_command_time(SMTP_CMD_GREETING, time_start.elapsed());
+
+        self.send_ehlo(&mut conn).await?;
+
+        if self.args.connect.tls_mode == MailTlsMode::StartTls {
+            let time_start = Instant::now();
+            conn.send_command("STARTTLS", b"STARTTLS\r\n", ReplyCode::SERVICE_READY)
+                .await?;
+            conn = conn.start_tls(&self.args.connect).await?;
+            self.histogram_recorder
+                .record_command_time(SMTP_CMD_STARTTLS, time_start.elapsed());
+
+            self.send_ehlo(&mut conn).await?;
+        }
+
+        if let Some(auth) = &self.args.auth {
+            let time_start = Instant::now();
+            match auth {
+                SmtpAuth::Plain(line) => {
+                    conn.send_command("AUTH", line, ReplyCode::AUTHENTICATION_SUCCESSFUL)
+                        .await?;
+                }
+                SmtpAuth::Login { username, password } => {
+                    conn.send_command("AUTH", b"AUTH LOGIN\r\n", ReplyCode::AUTH_CONTINUE)
+                        .await?;
+                    conn.send_command("AUTH", username, ReplyCode::AUTH_CONTINUE)
+                        .await?;
+                    conn.send_command("AUTH", password, ReplyCode::AUTHENTICATION_SUCCESSFUL)
+                        .await?;
+                }
+            }
+            self.histogram_recorder
+                .record_command_time(SMTP_CMD_AUTH, time_start.elapsed());
+        }
+
+        if self.args.send_message() {
+            for _ in 0..self.args.message_count {
+                self.send_message(&mut conn).await?;
+            }
+        }
+
+        let time_start = Instant::now();
+        conn.send_command("QUIT", b"QUIT\r\n", ReplyCode::SERVICE_CLOSING)
+            .await?;
+        self.histogram_recorder
+            .record_command_time(SMTP_CMD_QUIT, time_start.elapsed());
+
+        let _ = conn.stream.shutdown().await;
+        Ok(())
+    }
+
+    async fn send_ehlo(&mut self, conn: &mut SmtpConnection) -> anyhow::Result<()> {
+        let time_start = Instant::now();
+        conn.send_command("EHLO", &self.args.ehlo, ReplyCode::OK)
+            .await?;
+        self.histogram_recorder
+            .record_command_time(SMTP_CMD_EHLO, time_start.elapsed());
+        Ok(())
+    }
+
+    async fn send_message(&mut self, conn: &mut SmtpConnection) -> anyhow::Result<()> {
+        let time_start = Instant::now();
+        conn.send_command("MAIL", &self.args.mail_from, ReplyCode::OK)
+            .await?;
+        self.histogram_recorder
+            .record_command_time(SMTP_CMD_MAIL, time_start.elapsed());
+
+        for rcpt in &self.args.rcpt_to {
+            let time_start = Instant::now();
+            conn.send_command("RCPT", rcpt, ReplyCode::OK).await?;
+            self.histogram_recorder
+                .record_command_time(SMTP_CMD_RCPT, time_start.elapsed());
+        }
+
+        let time_start = Instant::now();
+        conn.send_command("DATA", b"DATA\r\n", ReplyCode::START_MAIL_INPUT)
+            .await?;
+        conn.stream
+            .write_all(&self.args.message)
+            .await
+            .map_err(|e| anyhow!("failed to send message: {e}"))?;
+        conn.send_command("DATA", b".\r\n", ReplyCode::OK).await?;
+        self.histogram_recorder
+            .record_command_time(SMTP_CMD_DATA, time_start.elapsed());
+
+        self.runtime_stats.add_message_sent(self.args.message.len());
+        Ok(())
+    }
+}
+
+impl BenchTaskContext for SmtpTaskContext {
+    fn mark_task_start(&self) {
+        self.runtime_stats.add_task_total();
+        self.runtime_stats.inc_task_alive();
+    }
+
+    fn mark_task_passed(&self) {
+        self.runtime_stats.add_task_passed();
+        self.runtime_stats.dec_task_alive();
+    }
+
+    fn mark_task_failed(&self) {
+        self.runtime_stats.add_task_failed();
+        self.runtime_stats.dec_task_alive();
+    }
+
+    async fn run(&mut self, _task_id: usize, time_started: Instant) -> Result<(), BenchError> {
+        let conn = self
+            .connect()
+            .await
+            .map_err(|e| BenchError::Fatal(e.context("connect to upstream failed")))?;
+        self.histogram_recorder
+            .record_command_time(SMTP_CMD_CONNECT, time_started.elapsed());
+
+        self.run_session(conn).await.map_err(BenchError::Task)?;
+        self.histogram_recorder
+            .record_total_time(time_started.elapsed());
+        Ok(())
+    }
+}
@@ -54,16 +54,30 @@ impl OpensslBackend {
 
     fn generate(&mut self, req: &Request) -> anyhow::Result<GeneratedData> {
         self.stats.add_request_total();
+        let (ca_cert, ca_key, ca_cert_pem) = if req.untrusted() {
+            match (
+                &self.config.untrusted_ca_cert,
+                &self.config.untrusted_ca_key,
+            ) {
+                (Some(cert), Some(key)) => (cert, key, &self.config.untrusted_ca_cert_pem),
+                _ => return Err(anyhow!("no untrusted ca configured")),
+            }
+        } else {
+            (
+                &self.config.ca_cert,
+                &self.config.ca_key,
+                &self.config.ca_cert_pem,
+            )
+        };
+
         if let Some(mimic_cert) = req.cert() {
-            self.generate_mimic(mimic_cert, req.cert_usage())
+            self.generate_mimic(mimic_cert, req.cert_usage(), ca_cert, ca_key, ca_cert_pem)
         } else {
             let host = Host::from_str(req.host_str())?;
             self.builder.refresh_serial()?;
-            let cert =
-                self.builder
-                    .build_fake(&host, &self.config.ca_cert, &self.config.ca_key, None)?;
+            let cert = self.builder.build_fake(&host, ca_cert, ca_key, None)?;
             let ttl = self.builder.valid_seconds()?;
-            self.pack_data(cert, self.builder.pkey(), ttl)
+            self.pack_data(cert, self.builder.pkey(), ttl, ca_cert_pem)
         }
     }
 
@@ -71,34 +85,29 @@ impl OpensslBackend {
         &self,
         mimic_cert: &X509,
         cert_usage: TlsCertUsage,
+        ca_cert: &X509,
+        ca_key: &PKey<Private>,
+        ca_cert_pem: &[u8],
     ) -> anyhow::Result<GeneratedData> {
         let mut mimic_builder = MimicCertBuilder::new(mimic_cert)?;
         mimic_builder.set_keep_serial(self.config.keep_serial);
 
         let cert = match cert_usage {
-            TlsCertUsage::TlsServer => {
-                mimic_builder.build_tls_cert(&self.config.ca_cert, &self.config.ca_key, None)?
+            TlsCertUsage::TlsServer => mimic_builder.build_tls_cert(ca_cert, ca_key, None)?,
+            TlsCertUsage::TLsServerTongsuo => {
+                mimic_builder.build_tls_cert_with_new_usage(ca_cert, ca_key, None)?
+            }
+            TlsCertUsage::TlcpServerEncryption => {
+                mimic_builder.build_tlcp_enc_cert(ca_cert, ca_key, None)?
+            }
+            TlsCertUsage::TlcpServerSignature => {
+                mimic_builder.build_tlcp_sign_cert(ca_cert, ca_key, None)?
             }
-            TlsCertUsage::TLsServerTongsuo => mimic_builder.build_tls_cert_with_new_usage(
-                &self.config.ca_cert,
-                &self.config.ca_key,
-                None,
-            )?,
-            TlsCertUsage::TlcpServerEncryption => mimic_builder.build_tlcp_enc_cert(
-                &self.config.ca_cert,
-                &self.config.ca_key,
-                None,
-            )?,
-            TlsCertUsage::TlcpServerSignature => mimic_builder.build_tlcp_sign_cert(
-                &self.config.ca_cert,
-                &self.config.ca_key,
-                None,
-            )?,
         };
 
         let ttl = mimic_builder.valid_seconds()?;
 
-        self.pack_data(cert, mimic_builder.pkey(), ttl)
+        self.pack_data(cert, mimic_builder.pkey(), ttl, ca_cert_pem)
     }
 
     fn pack_data(
@@ -106,13 +115,14 @@ impl OpensslBackend {
         cert: X509,
         pkey: &PKey<Private>,
         ttl: i32,
+        ca_cert_pem: &[u8],
     ) -> anyhow::Result<GeneratedData> {
         let ttl = ttl.clamp(0, self.config.max_ttl) as u32;
         let mut cert_pem = cert
             .to_pem()
             .map_err(|e| anyhow!("failed to encode cert to PEM format: {e}"))?;
-        if !self.config.ca_cert_pem.is_empty() {
-            cert_pem.extend_from_slice(&self.config.ca_cert_pem);
+        if !ca_cert_pem.is_empty() {
+            cert_pem.extend_from_slice(ca_cert_pem);
         }
         let key = pkey
             .private_key_to_der()
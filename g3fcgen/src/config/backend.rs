@@ -23,6 +23,9 @@ pub(crate) struct OpensslBackendConfig {
     pub(crate) ca_cert: X509,
     pub(crate) ca_key: PKey<Private>,
     pub(crate) ca_cert_pem: Vec<u8>,
+    pub(crate) untrusted_ca_cert: Option<X509>,
+    pub(crate) untrusted_ca_key: Option<PKey<Private>>,
+    pub(crate) untrusted_ca_cert_pem: Vec<u8>,
     pub(crate) keep_serial: bool,
     pub(crate) max_ttl: i32,
     pub(crate) duration_stats: HistogramMetricsConfig,
@@ -34,6 +37,9 @@ pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
         let mut ca_cert_pem = Vec::new();
         let mut ca_cert: Option<X509> = None;
         let mut ca_key: Option<PKey<Private>> = None;
+        let mut untrusted_ca_cert_pem = Vec::new();
+        let mut untrusted_ca_cert: Option<X509> = None;
+        let mut untrusted_ca_key: Option<PKey<Private>> = None;
         let mut keep_serial = false;
         let mut max_ttl = 24 * 3600; // 1 day
         let mut duration_stats = HistogramMetricsConfig::default();
@@ -63,6 +69,29 @@ pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
                 ca_key = Some(key);
                 Ok(())
             }
+            "untrusted_ca_certificate" => {
+                let certs = g3_yaml::value::as_openssl_certificates(v, Some(lookup_dir))
+                    .context(format!("invalid openssl certificate value for key {k}"))?;
+                for (i, cert) in certs.iter().enumerate() {
+                    let pem = cert.to_pem().map_err(|e| {
+                        anyhow!("failed to convert cert {i} back to pem format: {e}")
+                    })?;
+                    untrusted_ca_cert_pem.extend(pem);
+                }
+
+                let cert = certs
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow!("no valid openssl certificate key found"))?;
+                untrusted_ca_cert = Some(cert);
+                Ok(())
+            }
+            "untrusted_ca_private_key" => {
+                let key = g3_yaml::value::as_openssl_private_key(v, Some(lookup_dir))
+                    .context(format!("invalid openssl private key value for key {k}"))?;
+                untrusted_ca_key = Some(key);
+                Ok(())
+            }
             "no_append_ca_cert" => {
                 no_append_ca_cert = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -92,14 +121,24 @@ pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
             return Err(anyhow!("no ca private key set"));
         };
 
+        if untrusted_ca_cert.is_some() != untrusted_ca_key.is_some() {
+            return Err(anyhow!(
+                "untrusted ca certificate and private key should be set together"
+            ));
+        }
+
         if no_append_ca_cert {
             ca_cert_pem.clear();
+            untrusted_ca_cert_pem.clear();
         }
         BACKEND_CONFIG_LOCK
             .set(Arc::new(OpensslBackendConfig {
                 ca_cert,
                 ca_key,
                 ca_cert_pem,
+                untrusted_ca_cert,
+                untrusted_ca_key,
+                untrusted_ca_cert_pem,
                 keep_serial,
                 max_ttl,
                 duration_stats,
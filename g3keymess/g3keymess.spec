@@ -25,7 +25,7 @@ Keyless server for G3 Project
 %build
 G3_PACKAGE_VERSION="%{version}-%{release}"
 export G3_PACKAGE_VERSION
-cargo build --frozen --offline --profile %{build_profile} --no-default-features --features openssl-async-job --package g3keymess --package g3keymess-ctl
+cargo build --frozen --offline --profile %{build_profile} --no-default-features --features openssl-async-job,rustls-ring,quic --package g3keymess --package g3keymess-ctl
 sh %{name}/service/generate_systemd.sh
 
 
@@ -0,0 +1,71 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+
+use openssl::nid::Nid;
+use openssl::x509::X509Ref;
+
+/// The client identity taken from the peer certificate of a mTLS connection
+#[derive(Debug, Default)]
+pub(crate) struct ClientIdentity {
+    pub(crate) subject_cn: Option<String>,
+    /// DNS, email, URI and IP address values in the SAN extension
+    pub(crate) subject_alt_names: Vec<String>,
+}
+
+impl ClientIdentity {
+    pub(crate) fn from_x509(cert: &X509Ref) -> Self {
+        let subject_cn = cert
+            .subject_name()
+            .entries_by_nid(Nid::COMMONNAME)
+            .next()
+            .and_then(|entry| entry.data().as_utf8().ok())
+            .map(|s| s.to_string());
+
+        let mut subject_alt_names = Vec::new();
+        if let Some(names) = cert.subject_alt_names() {
+            for name in names.iter() {
+                if let Some(s) = name.dnsname() {
+                    subject_alt_names.push(s.to_string());
+                } else if let Some(s) = name.email() {
+                    subject_alt_names.push(s.to_string());
+                } else if let Some(s) = name.uri() {
+                    subject_alt_names.push(s.to_string());
+                } else if let Some(b) = name.ipaddress() {
+                    let ip = match b.len() {
+                        4 => IpAddr::from(<[u8; 4]>::try_from(b).unwrap()),
+                        16 => IpAddr::from(<[u8; 16]>::try_from(b).unwrap()),
+                        _ => continue,
+                    };
+                    subject_alt_names.push(ip.to_string());
+                }
+            }
+        }
+
+        ClientIdentity {
+            subject_cn,
+            subject_alt_names,
+        }
+    }
+
+    #[cfg(feature = "quic")]
+    pub(crate) fn from_quic_connection(connection: &quinn::Connection) -> Option<Self> {
+        use openssl::x509::X509;
+        use rustls_pki_types::CertificateDer;
+
+        let identity = connection.peer_identity()?;
+        let certs = identity.downcast::<Vec<CertificateDer<'static>>>().ok()?;
+        let cert = X509::from_der(certs.first()?.as_ref()).ok()?;
+        Some(ClientIdentity::from_x509(&cert))
+    }
+
+    /// The name to identify this client in rate limit and logs
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.subject_cn
+            .as_deref()
+            .or_else(|| self.subject_alt_names.first().map(|s| s.as_str()))
+    }
+}
@@ -0,0 +1,397 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use foldhash::fast::FixedState;
+use thiserror::Error;
+
+use g3_types::limit::{GlobalRateLimitState, RateLimitQuota, RateLimiter};
+
+use crate::config::server::{KeyAuthorizationConfig, KeyAuthorizationRuleConfig, KeyOperation};
+
+mod identity;
+pub(crate) use identity::ClientIdentity;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub(crate) enum KeyAuthorizationError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("rate limited")]
+    RateLimited,
+}
+
+const RATE_LIMITER_SHARDS: usize = 16;
+
+struct KeyRateLimiterEntry {
+    limiter: RateLimiter<GlobalRateLimitState>,
+    last_used: Instant,
+}
+
+struct KeyRateLimiterShard {
+    limiters: HashMap<(String, Vec<u8>), KeyRateLimiterEntry, FixedState>,
+    last_sweep: Instant,
+}
+
+/// Rate limiters keyed by client name and key SKI.
+///
+/// The limiters are sharded, and the ones that have been idle for long enough to be fully
+/// replenished will be evicted, as they are the same as new ones.
+struct KeyRateLimiters {
+    quota: RateLimitQuota,
+    idle_expire: Duration,
+    shard_hasher: FixedState,
+    shards: Box<[Mutex<KeyRateLimiterShard>]>,
+}
+
+impl KeyRateLimiters {
+    fn new(quota: RateLimitQuota) -> Self {
+        let now = Instant::now();
+        KeyRateLimiters {
+            quota,
+            idle_expire: quota.full_replenish_duration(),
+            shard_hasher: FixedState::with_seed(1),
+            shards: (0..RATE_LIMITER_SHARDS)
+                .map(|_| {
+                    Mutex::new(KeyRateLimiterShard {
+                        limiters: HashMap::with_hasher(FixedState::with_seed(0)),
+                        last_sweep: now,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn check(&self, client_name: String, ski: &[u8]) -> Result<(), KeyAuthorizationError> {
+        self.check_at(client_name, ski, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        client_name: String,
+        ski: &[u8],
+        now: Instant,
+    ) -> Result<(), KeyAuthorizationError> {
+        let key = (client_name, ski.to_vec());
+        let i = (self.shard_hasher.hash_one(&key) as usize) % RATE_LIMITER_SHARDS;
+        let mut shard = self.shards[i].lock().unwrap();
+
+        if now.saturating_duration_since(shard.last_sweep) >= self.idle_expire {
+            shard
+                .limiters
+                .retain(|_, e| now.saturating_duration_since(e.last_used) < self.idle_expire);
+            shard.last_sweep = now;
+        }
+
+        let entry = shard
+            .limiters
+            .entry(key)
+            .or_insert_with(|| KeyRateLimiterEntry {
+                limiter: RateLimiter::new_global(self.quota),
+                last_used: now,
+            });
+        entry.last_used = now;
+        entry
+            .limiter
+            .check()
+            .map_err(|_| KeyAuthorizationError::RateLimited)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|s| s.lock().unwrap().limiters.len())
+            .sum()
+    }
+}
+
+struct KeyAuthorizationRule {
+    config: KeyAuthorizationRuleConfig,
+    name: Arc<str>,
+    rate_limiters: Option<KeyRateLimiters>,
+}
+
+impl KeyAuthorizationRule {
+    fn new(config: KeyAuthorizationRuleConfig) -> Self {
+        KeyAuthorizationRule {
+            name: Arc::from(config.name.as_str()),
+            rate_limiters: config.rate_limit.map(KeyRateLimiters::new),
+            config,
+        }
+    }
+
+    fn match_client(&self, client_ip: IpAddr, identity: Option<&ClientIdentity>) -> bool {
+        if !self.config.network.is_empty()
+            && !self
+                .config
+                .network
+                .iter()
+                .any(|net| net.contains(client_ip))
+        {
+            return false;
+        }
+
+        if !self.config.subject_cn.is_empty() {
+            let Some(cn) = identity.and_then(|id| id.subject_cn.as_ref()) else {
+                return false;
+            };
+            if !self.config.subject_cn.contains(cn) {
+                return false;
+            }
+        }
+
+        if !self.config.subject_alt_name.is_empty() {
+            let Some(identity) = identity else {
+                return false;
+            };
+            if !identity
+                .subject_alt_names
+                .iter()
+                .any(|name| self.config.subject_alt_name.contains(name))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn match_key(&self, ski: &[u8], operation: KeyOperation) -> bool {
+        if let Some(keys) = &self.config.keys
+            && !keys.iter().any(|k| k.as_slice() == ski)
+        {
+            return false;
+        }
+        if let Some(operations) = &self.config.operations
+            && !operations.contains(&operation)
+        {
+            return false;
+        }
+        true
+    }
+
+    fn check_rate_limit(
+        &self,
+        client_ip: IpAddr,
+        identity: Option<&ClientIdentity>,
+        ski: &[u8],
+    ) -> Result<(), KeyAuthorizationError> {
+        let Some(rate_limiters) = &self.rate_limiters else {
+            return Ok(());
+        };
+
+        let client_name = identity
+            .and_then(|id| id.name())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| client_ip.to_string());
+        rate_limiters.check(client_name, ski)
+    }
+}
+
+pub(crate) struct KeyAuthorization {
+    rules: Vec<KeyAuthorizationRule>,
+    default_permit: bool,
+}
+
+impl KeyAuthorization {
+    pub(crate) fn new(config: &KeyAuthorizationConfig) -> Self {
+        KeyAuthorization {
+            rules: config
+                .rules
+                .iter()
+                .map(|c| KeyAuthorizationRule::new(c.clone()))
+                .collect(),
+            default_permit: config.default_permit,
+        }
+    }
+
+    /// Check if the client is allowed to run the operation with the key.
+    /// The name of the matched rule will be returned on success.
+    pub(crate) fn check(
+        &self,
+        client_ip: IpAddr,
+        identity: Option<&ClientIdentity>,
+        ski: &[u8],
+        operation: KeyOperation,
+    ) -> Result<Option<Arc<str>>, KeyAuthorizationError> {
+        for rule in &self.rules {
+            if !rule.match_client(client_ip, identity) || !rule.match_key(ski, operation) {
+                continue;
+            }
+
+            rule.check_rate_limit(client_ip, identity, ski)?;
+            return Ok(Some(rule.name.clone()));
+        }
+
+        if self.default_permit {
+            Ok(None)
+        } else {
+            Err(KeyAuthorizationError::Forbidden)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use ip_network::IpNetwork;
+
+    fn rule(name: &str) -> KeyAuthorizationRuleConfig {
+        KeyAuthorizationRuleConfig {
+            name: name.to_string(),
+            subject_cn: Vec::new(),
+            subject_alt_name: Vec::new(),
+            network: Vec::new(),
+            keys: None,
+            operations: None,
+            rate_limit: None,
+        }
+    }
+
+    fn identity(cn: Option<&str>, san: &[&str]) -> ClientIdentity {
+        ClientIdentity {
+            subject_cn: cn.map(|s| s.to_string()),
+            subject_alt_names: san.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    const CLIENT_IP: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 10));
+    const SKI: &[u8] = &[0x01, 0x02, 0x03];
+
+    #[test]
+    fn match_cn_and_san() {
+        let mut cn_rule = rule("cn");
+        cn_rule.subject_cn = vec!["client-a".to_string()];
+        let mut san_rule = rule("san");
+        san_rule.subject_alt_name = vec!["b.example.net".to_string()];
+        let auth = KeyAuthorization::new(&KeyAuthorizationConfig {
+            rules: vec![cn_rule, san_rule],
+            default_permit: false,
+        });
+
+        let id = identity(Some("client-a"), &[]);
+        let r = auth.check(CLIENT_IP, Some(&id), SKI, KeyOperation::RsaSign);
+        assert_eq!(r.unwrap().as_deref(), Some("cn"));
+
+        let id = identity(Some("client-b"), &["a.example.net", "b.example.net"]);
+        let r = auth.check(CLIENT_IP, Some(&id), SKI, KeyOperation::RsaSign);
+        assert_eq!(r.unwrap().as_deref(), Some("san"));
+
+        let id = identity(Some("client-c"), &["c.example.net"]);
+        let r = auth.check(CLIENT_IP, Some(&id), SKI, KeyOperation::RsaSign);
+        assert!(matches!(r, Err(KeyAuthorizationError::Forbidden)));
+
+        // no client certificate
+        let r = auth.check(CLIENT_IP, None, SKI, KeyOperation::RsaSign);
+        assert!(matches!(r, Err(KeyAuthorizationError::Forbidden)));
+    }
+
+    #[test]
+    fn match_network_key_and_operation() {
+        let mut r1 = rule("net");
+        r1.network = vec![IpNetwork::from_str("10.0.0.0/8").unwrap()];
+        let mut r2 = rule("key");
+        r2.keys = Some(vec![SKI.to_vec()]);
+        r2.operations = Some(vec![KeyOperation::EcdsaSign]);
+        let auth = KeyAuthorization::new(&KeyAuthorizationConfig {
+            rules: vec![r1, r2],
+            default_permit: false,
+        });
+
+        let ip = IpAddr::from_str("10.1.2.3").unwrap();
+        let r = auth.check(ip, None, b"other", KeyOperation::RsaDecrypt);
+        assert_eq!(r.unwrap().as_deref(), Some("net"));
+
+        let r = auth.check(CLIENT_IP, None, SKI, KeyOperation::EcdsaSign);
+        assert_eq!(r.unwrap().as_deref(), Some("key"));
+        let r = auth.check(CLIENT_IP, None, SKI, KeyOperation::RsaSign);
+        assert!(matches!(r, Err(KeyAuthorizationError::Forbidden)));
+        let r = auth.check(CLIENT_IP, None, b"other", KeyOperation::EcdsaSign);
+        assert!(matches!(r, Err(KeyAuthorizationError::Forbidden)));
+    }
+
+    #[test]
+    fn first_match_and_default() {
+        let mut r1 = rule("deny-rsa");
+        r1.operations = Some(vec![KeyOperation::RsaDecrypt]);
+        let r2 = rule("all");
+        let auth = KeyAuthorization::new(&KeyAuthorizationConfig {
+            rules: vec![r1, r2],
+            default_permit: false,
+        });
+        let r = auth.check(CLIENT_IP, None, SKI, KeyOperation::RsaDecrypt);
+        assert_eq!(r.unwrap().as_deref(), Some("deny-rsa"));
+        let r = auth.check(CLIENT_IP, None, SKI, KeyOperation::RsaSign);
+        assert_eq!(r.unwrap().as_deref(), Some("all"));
+
+        let auth = KeyAuthorization::new(&KeyAuthorizationConfig {
+            rules: Vec::new(),
+            default_permit: true,
+        });
+        let r = auth.check(CLIENT_IP, None, SKI, KeyOperation::RsaSign);
+        assert_eq!(r.unwrap(), None);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let mut r1 = rule("limited");
+        r1.rate_limit = Some(RateLimitQuota::from_str("1/h").unwrap());
+        let auth = KeyAuthorization::new(&KeyAuthorizationConfig {
+            rules: vec![r1],
+            default_permit: false,
+        });
+
+        let id = identity(Some("client-a"), &[]);
+        assert!(
+            auth.check(CLIENT_IP, Some(&id), SKI, KeyOperation::RsaSign)
+                .is_ok()
+        );
+        let r = auth.check(CLIENT_IP, Some(&id), SKI, KeyOperation::RsaSign);
+        assert!(matches!(r, Err(KeyAuthorizationError::RateLimited)));
+        // limited by client and key
+        let id = identity(Some("client-b"), &[]);
+        assert!(
+            auth.check(CLIENT_IP, Some(&id), SKI, KeyOperation::RsaSign)
+                .is_ok()
+        );
+        assert!(
+            auth.check(CLIENT_IP, Some(&id), b"other", KeyOperation::RsaSign)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rate_limiter_evict() {
+        let quota = RateLimitQuota::from_str("10/s").unwrap();
+        let limiters = KeyRateLimiters::new(quota);
+        let now = Instant::now();
+        for i in 0..1000 {
+            limiters.check_at(format!("client-{i}"), SKI, now).unwrap();
+        }
+        assert_eq!(limiters.len(), 1000);
+
+        // not idle for long enough
+        let t = now + Duration::from_millis(500);
+        for i in 0..1000 {
+            limiters.check_at(format!("client-{i}"), b"other", t).unwrap();
+        }
+        assert_eq!(limiters.len(), 2000);
+
+        // the old entries have been fully replenished
+        let t = now + limiters.idle_expire + Duration::from_millis(100);
+        limiters.check_at("client-new".to_string(), SKI, t).unwrap();
+        for i in 0..1000 {
+            limiters.check_at(format!("client-{i}"), b"other", t).unwrap();
+        }
+        assert_eq!(limiters.len(), 1001);
+    }
+}
@@ -15,13 +15,16 @@ static REQUEST_DEFAULT_LOG_CONFIG_CONTAINER: GlobalInit<LogConfigContainer> =
     GlobalInit::new(LogConfigContainer::new());
 static TASK_DEFAULT_LOG_CONFIG_CONTAINER: GlobalInit<LogConfigContainer> =
     GlobalInit::new(LogConfigContainer::new());
+static AUDIT_DEFAULT_LOG_CONFIG_CONTAINER: GlobalInit<LogConfigContainer> =
+    GlobalInit::new(LogConfigContainer::new());
 
 pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     match v {
         Yaml::String(s) => {
             let config = LogConfig::with_driver_name(s, crate::build::PKG_NAME)?;
             REQUEST_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config.clone()));
-            TASK_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config));
+            TASK_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config.clone()));
+            AUDIT_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config));
             Ok(())
         }
         Yaml::Hash(map) => {
@@ -31,7 +34,8 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                         .context(format!("invalid value for key {k}"))?;
                     REQUEST_DEFAULT_LOG_CONFIG_CONTAINER
                         .with_mut(|l| l.set_default(config.clone()));
-                    TASK_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config));
+                    TASK_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config.clone()));
+                    AUDIT_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set_default(config));
                     Ok(())
                 }
                 "request" => {
@@ -46,6 +50,12 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                     TASK_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set(config));
                     Ok(())
                 }
+                "audit" => {
+                    let config = LogConfig::parse_yaml(v, conf_dir, crate::build::PKG_NAME)
+                        .context(format!("invalid value for key {k}"))?;
+                    AUDIT_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set(config));
+                    Ok(())
+                }
                 _ => Err(anyhow!("invalid key {k}")),
             })?;
             Ok(())
@@ -66,3 +76,9 @@ pub(crate) fn get_task_default_config() -> LogConfig {
         .as_ref()
         .get(crate::build::PKG_NAME)
 }
+
+pub(crate) fn get_audit_default_config() -> LogConfig {
+    AUDIT_DEFAULT_LOG_CONFIG_CONTAINER
+        .as_ref()
+        .get(crate::build::PKG_NAME)
+}
@@ -0,0 +1,298 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use ip_network::IpNetwork;
+use yaml_rust::Yaml;
+
+use g3_types::limit::RateLimitQuota;
+
+use crate::protocol::KeylessAction;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum KeyOperation {
+    RsaDecrypt,
+    RsaSign,
+    RsaPssSign,
+    EcdsaSign,
+    Ed25519Sign,
+}
+
+impl KeyOperation {
+    pub(crate) fn from_action(action: KeylessAction) -> Option<Self> {
+        match action {
+            KeylessAction::RsaDecrypt(_) => Some(KeyOperation::RsaDecrypt),
+            KeylessAction::RsaSign(_) => Some(KeyOperation::RsaSign),
+            KeylessAction::RsaPssSign(_) => Some(KeyOperation::RsaPssSign),
+            KeylessAction::EcdsaSign(_) => Some(KeyOperation::EcdsaSign),
+            KeylessAction::Ed25519Sign => Some(KeyOperation::Ed25519Sign),
+            KeylessAction::NotSet | KeylessAction::Ping => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            KeyOperation::RsaDecrypt => "rsa_decrypt",
+            KeyOperation::RsaSign => "rsa_sign",
+            KeyOperation::RsaPssSign => "rsa_pss_sign",
+            KeyOperation::EcdsaSign => "ecdsa_sign",
+            KeyOperation::Ed25519Sign => "ed25519_sign",
+        }
+    }
+}
+
+impl FromStr for KeyOperation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match g3_yaml::key::normalize(s).as_str() {
+            "rsa_decrypt" => Ok(KeyOperation::RsaDecrypt),
+            "rsa_sign" => Ok(KeyOperation::RsaSign),
+            "rsa_pss_sign" => Ok(KeyOperation::RsaPssSign),
+            "ecdsa_sign" => Ok(KeyOperation::EcdsaSign),
+            "ed25519_sign" => Ok(KeyOperation::Ed25519Sign),
+            _ => Err(()),
+        }
+    }
+}
+
+fn as_key_operation(v: &Yaml) -> anyhow::Result<KeyOperation> {
+    let s = g3_yaml::value::as_string(v)?;
+    KeyOperation::from_str(&s).map_err(|_| anyhow!("invalid key operation {s}"))
+}
+
+fn as_ski(v: &Yaml) -> anyhow::Result<Vec<u8>> {
+    let s = g3_yaml::value::as_string(v)?;
+    let s = s.replace(':', "");
+    hex::decode(&s).map_err(|e| anyhow!("invalid hex string {s}: {e}"))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct KeyAuthorizationRuleConfig {
+    pub(crate) name: String,
+    pub(crate) subject_cn: Vec<String>,
+    pub(crate) subject_alt_name: Vec<String>,
+    pub(crate) network: Vec<IpNetwork>,
+    /// all keys are allowed if not set
+    pub(crate) keys: Option<Vec<Vec<u8>>>,
+    /// all operations are allowed if not set
+    pub(crate) operations: Option<Vec<KeyOperation>>,
+    /// rate limit for each key used by each client
+    pub(crate) rate_limit: Option<RateLimitQuota>,
+}
+
+impl KeyAuthorizationRuleConfig {
+    fn new(index: usize) -> Self {
+        KeyAuthorizationRuleConfig {
+            name: format!("#{index}"),
+            subject_cn: Vec::new(),
+            subject_alt_name: Vec::new(),
+            network: Vec::new(),
+            keys: None,
+            operations: None,
+            rate_limit: None,
+        }
+    }
+
+    fn parse_yaml(value: &Yaml, index: usize) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for `key authorization rule` should be `map`"
+            ));
+        };
+
+        let mut rule = KeyAuthorizationRuleConfig::new(index);
+        g3_yaml::foreach_kv(map, |k, v| rule.set(k, v))?;
+        Ok(rule)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "name" => {
+                self.name = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "subject_cn" | "cert_subject_cn" => {
+                self.subject_cn = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                    .context(format!("invalid string list value for key {k}"))?;
+                Ok(())
+            }
+            "subject_alt_name" | "cert_subject_alt_name" | "san" => {
+                self.subject_alt_name = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                    .context(format!("invalid string list value for key {k}"))?;
+                Ok(())
+            }
+            "network" | "networks" => {
+                self.network = g3_yaml::value::as_list(v, g3_yaml::value::as_ip_network)
+                    .context(format!("invalid ip network list value for key {k}"))?;
+                Ok(())
+            }
+            "keys" | "ski" => {
+                let keys = g3_yaml::value::as_list(v, as_ski)
+                    .context(format!("invalid key SKI list value for key {k}"))?;
+                self.keys = Some(keys);
+                Ok(())
+            }
+            "operations" | "actions" => {
+                let operations = g3_yaml::value::as_list(v, as_key_operation)
+                    .context(format!("invalid key operation list value for key {k}"))?;
+                self.operations = Some(operations);
+                Ok(())
+            }
+            "rate_limit" => {
+                let quota = g3_yaml::value::as_rate_limit_quota(v)
+                    .context(format!("invalid request rate limit value for key {k}"))?;
+                self.rate_limit = Some(quota);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct KeyAuthorizationConfig {
+    pub(crate) rules: Vec<KeyAuthorizationRuleConfig>,
+    /// whether to permit requests that match none of the rules
+    pub(crate) default_permit: bool,
+}
+
+impl KeyAuthorizationConfig {
+    pub(super) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let mut config = KeyAuthorizationConfig::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "rules" => {
+                        config.rules = Self::parse_rules(v)?;
+                        Ok(())
+                    }
+                    "default_action" => {
+                        let action = g3_yaml::value::as_string(v)?;
+                        config.default_permit = match action.to_lowercase().as_str() {
+                            "permit" | "allow" => true,
+                            "forbid" | "deny" => false,
+                            _ => return Err(anyhow!("invalid default action {action}")),
+                        };
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Array(_) => {
+                config.rules = Self::parse_rules(value)?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for `key authorization` should be `map` or `seq`"
+                ));
+            }
+        }
+        Ok(config)
+    }
+
+    fn parse_rules(value: &Yaml) -> anyhow::Result<Vec<KeyAuthorizationRuleConfig>> {
+        let Yaml::Array(seq) = value else {
+            return Err(anyhow!("yaml value type for rules should be `seq`"));
+        };
+
+        let mut rules = Vec::with_capacity(seq.len());
+        for (i, v) in seq.iter().enumerate() {
+            let rule = KeyAuthorizationRuleConfig::parse_yaml(v, i)
+                .context(format!("invalid key authorization rule #{i}"))?;
+            rules.push(rule);
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_map() {
+        let yaml = yaml_doc!(
+            r#"
+                rules:
+                  - name: web
+                    subject_cn: [client-a]
+                    san: [a.example.net]
+                    network: 10.0.0.0/8
+                    ski: "01:02:0a"
+                    operations: [rsa_sign, ecdsa-sign]
+                    rate_limit: 10/s
+                  - network: 192.168.0.0/16
+                default_action: permit
+            "#
+        );
+        let config = KeyAuthorizationConfig::parse_yaml(&yaml).unwrap();
+        assert!(config.default_permit);
+        assert_eq!(config.rules.len(), 2);
+
+        let rule = &config.rules[0];
+        assert_eq!(rule.name, "web");
+        assert_eq!(rule.subject_cn, vec!["client-a".to_string()]);
+        assert_eq!(rule.subject_alt_name, vec!["a.example.net".to_string()]);
+        assert_eq!(rule.network, vec![IpNetwork::from_str("10.0.0.0/8").unwrap()]);
+        assert_eq!(rule.keys, Some(vec![vec![0x01, 0x02, 0x0a]]));
+        assert_eq!(
+            rule.operations,
+            Some(vec![KeyOperation::RsaSign, KeyOperation::EcdsaSign])
+        );
+        assert_eq!(rule.rate_limit, Some(RateLimitQuota::from_str("10/s").unwrap()));
+
+        let rule = &config.rules[1];
+        assert_eq!(rule.name, "#1");
+        assert!(rule.keys.is_none());
+        assert!(rule.operations.is_none());
+        assert!(rule.rate_limit.is_none());
+    }
+
+    #[test]
+    fn parse_seq() {
+        let yaml = yaml_doc!(
+            r#"
+                - subject_cn: client-a
+                - subject_cn: client-b
+            "#
+        );
+        let config = KeyAuthorizationConfig::parse_yaml(&yaml).unwrap();
+        assert!(!config.default_permit);
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].name, "#0");
+        assert_eq!(config.rules[1].subject_cn, vec!["client-b".to_string()]);
+
+        let yaml = yaml_doc!("default_action: deny");
+        let config = KeyAuthorizationConfig::parse_yaml(&yaml).unwrap();
+        assert!(!config.default_permit);
+        assert!(config.rules.is_empty());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "default_action: drop",
+            "rules: {name: a}",
+            "invalid_key: 1",
+            "- ski: xyz",
+            "- operations: [rsa_encrypt]",
+            "- network: 10.0.0.0/33",
+            "- invalid_key: 1",
+            "- client-a",
+            "1",
+        ] {
+            let docs = YamlLoader::load_from_str(s).unwrap();
+            assert!(
+                KeyAuthorizationConfig::parse_yaml(&docs[0]).is_err(),
+                "{s} should be invalid"
+            );
+        }
+    }
+}
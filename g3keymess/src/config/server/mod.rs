@@ -15,11 +15,16 @@ use yaml_rust::{Yaml, yaml};
 use g3_histogram::HistogramMetricsConfig;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{OpensslServerConfigBuilder, TcpListenConfig};
+#[cfg(feature = "quic")]
+use g3_types::net::{RustlsServerConfigBuilder, UdpListenConfig};
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 mod registry;
 pub(crate) use registry::{clear, get_all};
 
+mod authorization;
+pub(crate) use authorization::{KeyAuthorizationConfig, KeyAuthorizationRuleConfig, KeyOperation};
+
 #[derive(Clone)]
 pub(crate) struct KeyServerConfig {
     name: NodeName,
@@ -28,12 +33,19 @@ pub(crate) struct KeyServerConfig {
     pub(crate) shared_logger: Option<AsciiString>,
     pub(crate) listen: TcpListenConfig,
     pub(crate) tls_server: Option<OpensslServerConfigBuilder>,
+    #[cfg(feature = "quic")]
+    pub(crate) quic_listen: Option<UdpListenConfig>,
+    #[cfg(feature = "quic")]
+    pub(crate) quic_server: Option<RustlsServerConfigBuilder>,
+    #[cfg(feature = "quic")]
+    pub(crate) quic_concurrency_limit: usize,
     pub(crate) multiplex_queue_depth: usize,
     pub(crate) request_read_timeout: Duration,
     pub(crate) duration_stats: HistogramMetricsConfig,
     #[cfg(feature = "openssl-async-job")]
     pub(crate) async_op_timeout: Duration,
     pub(crate) concurrency_limit: usize,
+    pub(crate) authorization: Option<KeyAuthorizationConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
 
@@ -45,12 +57,19 @@ impl KeyServerConfig {
             shared_logger: None,
             listen: TcpListenConfig::default(),
             tls_server: None,
+            #[cfg(feature = "quic")]
+            quic_listen: None,
+            #[cfg(feature = "quic")]
+            quic_server: None,
+            #[cfg(feature = "quic")]
+            quic_concurrency_limit: 0,
             multiplex_queue_depth: 0,
             request_read_timeout: Duration::from_millis(100),
             duration_stats: HistogramMetricsConfig::default(),
             #[cfg(feature = "openssl-async-job")]
             async_op_timeout: Duration::from_secs(1),
             concurrency_limit: 0,
+            authorization: None,
             extra_metrics_tags: None,
         }
     }
@@ -74,6 +93,23 @@ impl KeyServerConfig {
             return Err(anyhow!("name is not set"));
         }
         self.listen.check().context("invalid listen address")?;
+        #[cfg(feature = "quic")]
+        if let Some(listen) = &self.quic_listen {
+            listen.check().context("invalid quic listen address")?;
+            let Some(tls_server) = &self.quic_server else {
+                return Err(anyhow!(
+                    "quic server tls config is required if quic listen is set"
+                ));
+            };
+            tls_server
+                .check()
+                .context("invalid quic server tls config")?;
+            if self.multiplex_queue_depth <= 1 {
+                return Err(anyhow!(
+                    "multiplex queue depth should be greater than 1 if quic listen is set"
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -107,6 +143,27 @@ impl KeyServerConfig {
                 self.tls_server = Some(tls_server);
                 Ok(())
             }
+            #[cfg(feature = "quic")]
+            "quic_listen" => {
+                let listen = g3_yaml::value::as_udp_listen_config(v)
+                    .context(format!("invalid udp listen config value for key {k}"))?;
+                self.quic_listen = Some(listen);
+                Ok(())
+            }
+            #[cfg(feature = "quic")]
+            "quic_server" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let tls_server =
+                    g3_yaml::value::as_rustls_server_config_builder(v, Some(lookup_dir))
+                        .context(format!("invalid quic server tls config value for key {k}"))?;
+                self.quic_server = Some(tls_server);
+                Ok(())
+            }
+            #[cfg(feature = "quic")]
+            "quic_concurrency_limit" => {
+                self.quic_concurrency_limit = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
             "multiplex_queue_depth" => {
                 self.multiplex_queue_depth = g3_yaml::value::as_usize(v)?;
                 Ok(())
@@ -130,6 +187,13 @@ impl KeyServerConfig {
                 self.concurrency_limit = g3_yaml::value::as_usize(v)?;
                 Ok(())
             }
+            "authorization" | "key_authorization" => {
+                let authorization = KeyAuthorizationConfig::parse_yaml(v).context(format!(
+                    "invalid key authorization config value for key {k}"
+                ))?;
+                self.authorization = Some(authorization);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -149,6 +213,14 @@ impl KeyServerConfig {
             crate::log::request::get_logger(self.name())
         }
     }
+
+    pub(crate) fn get_audit_logger(&self) -> Option<Logger> {
+        if let Some(shared_logger) = &self.shared_logger {
+            crate::log::audit::get_shared_logger(shared_logger.as_str(), self.name())
+        } else {
+            crate::log::audit::get_logger(self.name())
+        }
+    }
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -16,6 +16,8 @@ use g3_yaml::{HybridParser, YamlDocPosition};
 mod local;
 mod redis;
 
+mod secret;
+
 mod registry;
 pub(crate) use registry::{clear, get_all};
 
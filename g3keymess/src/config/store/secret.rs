@@ -0,0 +1,83 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+const SYSTEMD_CREDENTIALS_DIRECTORY_ENV: &str = "CREDENTIALS_DIRECTORY";
+
+/// Where to get the secret passphrase for encrypted key files
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum SecretSource {
+    File(PathBuf),
+    Env(String),
+    SystemdCredential(String),
+}
+
+impl SecretSource {
+    pub(super) fn parse_yaml(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        match value {
+            Yaml::String(_) => {
+                let path = g3_yaml::value::as_file_path(value, lookup_dir, false)?;
+                Ok(SecretSource::File(path))
+            }
+            Yaml::Hash(map) => {
+                let mut source = None;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "file" | "path" => {
+                        let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                            .context(format!("invalid file path value for key {k}"))?;
+                        source = Some(SecretSource::File(path));
+                        Ok(())
+                    }
+                    "env" | "env_var" => {
+                        let name = g3_yaml::value::as_string(v)?;
+                        source = Some(SecretSource::Env(name));
+                        Ok(())
+                    }
+                    "systemd_credential" | "credential" => {
+                        let name = g3_yaml::value::as_string(v)?;
+                        source = Some(SecretSource::SystemdCredential(name));
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                source.ok_or_else(|| anyhow!("no secret source set"))
+            }
+            _ => Err(anyhow!(
+                "yaml value type for `secret source` should be `string` or `map`"
+            )),
+        }
+    }
+
+    /// Load the secret, with the trailing line ending stripped
+    pub(super) fn load(&self) -> anyhow::Result<Vec<u8>> {
+        let mut secret = match self {
+            SecretSource::File(path) => std::fs::read(path)
+                .map_err(|e| anyhow!("failed to read secret file {}: {e}", path.display()))?,
+            SecretSource::Env(name) => std::env::var(name)
+                .map_err(|e| anyhow!("failed to read secret env var {name}: {e}"))?
+                .into_bytes(),
+            SecretSource::SystemdCredential(name) => {
+                let dir = std::env::var_os(SYSTEMD_CREDENTIALS_DIRECTORY_ENV).ok_or_else(|| {
+                    anyhow!("env var {SYSTEMD_CREDENTIALS_DIRECTORY_ENV} is not set")
+                })?;
+                let path = PathBuf::from(dir).join(name);
+                std::fs::read(&path).map_err(|e| {
+                    anyhow!("failed to read systemd credential {}: {e}", path.display())
+                })?
+            }
+        };
+        while matches!(secret.last(), Some(b'\n' | b'\r')) {
+            secret.pop();
+        }
+        if secret.is_empty() {
+            return Err(anyhow!("empty secret"));
+        }
+        Ok(secret)
+    }
+}
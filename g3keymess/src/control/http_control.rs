@@ -0,0 +1,73 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::{Method, StatusCode};
+use serde_json::json;
+
+use g3_daemon::control::http::{HttpControlHandler, HttpControlRequest, HttpControlResponse};
+
+pub struct HttpControlImpl;
+
+impl HttpControlImpl {
+    pub fn new_arc() -> Arc<Self> {
+        Arc::new(HttpControlImpl)
+    }
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse {
+        let segments = req.path_segments();
+        match (&req.method, segments.as_slice()) {
+            (&Method::GET, ["version"]) => HttpControlResponse::ok(json!(crate::build::VERSION)),
+            (&Method::GET, ["server"]) => {
+                let mut names = crate::serve::get_names().into_iter().collect::<Vec<_>>();
+                names.sort();
+                HttpControlResponse::from_names(names)
+            }
+            (&Method::GET, ["key"]) => list_keys().await,
+            (&Method::POST, ["key"]) => publish_key(req).await,
+            (&Method::GET, ["key", ski]) => check_key(ski).await,
+            (_, ["version" | "server" | "key"]) => HttpControlResponse::method_not_allowed(),
+            _ => HttpControlResponse::not_found(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        !crate::serve::get_names().is_empty()
+    }
+}
+
+async fn list_keys() -> HttpControlResponse {
+    match super::bridge::list_keys().await {
+        Ok(list) => {
+            let mut list = list.into_iter().map(hex::encode).collect::<Vec<_>>();
+            list.sort();
+            HttpControlResponse::from_names(list)
+        }
+        Err(e) => HttpControlResponse::error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+}
+
+async fn publish_key(req: &HttpControlRequest) -> HttpControlResponse {
+    match req.body_str() {
+        Ok(pem) => HttpControlResponse::from_result(super::bridge::add_key(pem).await),
+        Err(e) => HttpControlResponse::bad_request(e),
+    }
+}
+
+async fn check_key(ski: &str) -> HttpControlResponse {
+    let ski = match hex::decode(ski) {
+        Ok(v) => v,
+        Err(e) => return HttpControlResponse::bad_request(format!("invalid hex ski {ski}: {e}")),
+    };
+    match super::bridge::check_key(ski).await {
+        Ok(_) => HttpControlResponse::success(),
+        Err(e) => HttpControlResponse::error(StatusCode::NOT_FOUND, e),
+    }
+}
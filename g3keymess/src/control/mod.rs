@@ -14,4 +14,7 @@ pub use upgrade::UpgradeActor;
 mod local;
 pub use local::{DaemonController, UniqueController};
 
+mod http_control;
+pub use http_control::HttpControlImpl;
+
 pub mod capnp;
@@ -13,6 +13,7 @@ pub mod signal;
 pub mod stat;
 pub mod store;
 
+mod auth;
 mod build;
 mod log;
 mod protocol;
@@ -0,0 +1,88 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+
+use slog::{Logger, Value};
+use uuid::Uuid;
+
+use g3_slog_types::{LtDateTime, LtDuration, LtUuid};
+use g3_types::metrics::NodeName;
+
+use super::shared::SharedLoggerType;
+use crate::auth::ClientIdentity;
+use crate::config::server::KeyOperation;
+use crate::protocol::KeylessResponse;
+use crate::serve::RequestProcessContext;
+
+pub(crate) fn get_logger(server_name: &NodeName) -> Option<Logger> {
+    let config = crate::config::log::get_audit_default_config();
+    let logger_name = format!("la-{server_name}");
+    let common_values = slog::o!(
+        "daemon_name" => crate::opts::daemon_group(),
+        "log_type" => super::LOG_TYPE_AUDIT,
+        "pid" => std::process::id(),
+        "server_name" => server_name.to_string(),
+    );
+    config.build_logger(logger_name, super::LOG_TYPE_AUDIT, common_values)
+}
+
+pub(crate) fn get_shared_logger(name: &str, server_name: &NodeName) -> Option<Logger> {
+    let logger_name = format!("la-{name}");
+    super::shared::get_shared_logger(SharedLoggerType::Audit, logger_name, |logger| {
+        logger.new(slog::o!(
+            "server_name" => server_name.to_string(),
+        ))
+    })
+}
+
+struct LtSki<'a>(&'a [u8]);
+
+impl Value for LtSki<'_> {
+    fn serialize(
+        &self,
+        _record: &slog::Record,
+        key: slog::Key,
+        serializer: &mut dyn slog::Serializer,
+    ) -> slog::Result {
+        serializer.emit_str(key, &hex::encode(self.0))
+    }
+}
+
+pub(crate) struct AuditLogContext<'a> {
+    pub(crate) task_id: &'a Uuid,
+    pub(crate) client_addr: SocketAddr,
+    pub(crate) client_identity: Option<&'a ClientIdentity>,
+}
+
+impl AuditLogContext<'_> {
+    pub(crate) fn log(&self, logger: &Logger, ctx: &RequestProcessContext, rsp: &KeylessResponse) {
+        let Some(operation) = KeyOperation::from_action(ctx.action) else {
+            return;
+        };
+
+        let result = if let Some(e) = ctx.auth_error {
+            e.to_string()
+        } else {
+            match rsp {
+                KeylessResponse::Error(r) => r.error_code().to_string(),
+                _ => "ok".to_string(),
+            }
+        };
+        let client_cn = self.client_identity.and_then(|id| id.subject_cn.as_deref());
+
+        slog::info!(logger, "{}", result;
+            "task_id" => LtUuid(self.task_id),
+            "msg_id" => ctx.msg_id,
+            "client_addr" => self.client_addr,
+            "client_cn" => client_cn,
+            "key_ski" => LtSki(&ctx.ski),
+            "operation" => operation.as_str(),
+            "auth_rule" => ctx.auth_rule.as_deref(),
+            "create_at" => LtDateTime(&ctx.create_datetime),
+            "process_time" => LtDuration(ctx.duration()),
+        );
+    }
+}
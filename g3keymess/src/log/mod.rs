@@ -5,8 +5,10 @@
 
 mod shared;
 
+pub(crate) mod audit;
 pub(crate) mod request;
 pub(crate) mod task;
 
 const LOG_TYPE_TASK: &str = "Task";
 const LOG_TYPE_REQUEST: &str = "Request";
+const LOG_TYPE_AUDIT: &str = "Audit";
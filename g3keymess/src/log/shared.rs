@@ -15,6 +15,7 @@ static SHARED_LOGGER: Mutex<HashMap<String, Option<Logger>, FixedState>> =
 pub(super) enum SharedLoggerType {
     Task,
     Request,
+    Audit,
 }
 
 pub(super) fn get_shared_logger<F>(
@@ -34,6 +35,10 @@ where
             crate::config::log::get_request_default_config(),
             super::LOG_TYPE_REQUEST,
         ),
+        SharedLoggerType::Audit => (
+            crate::config::log::get_audit_default_config(),
+            super::LOG_TYPE_AUDIT,
+        ),
     };
     let mut container = SHARED_LOGGER.lock().unwrap();
     let logger = container
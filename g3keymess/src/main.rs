@@ -118,6 +118,10 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
             });
         }
         g3keymess::control::QuitActor::tokio_spawn_run();
+        g3_daemon::control::http::HttpController::start(
+            g3keymess::control::HttpControlImpl::new_arc(),
+        )
+        .context("failed to start http controller")?;
 
         g3keymess::signal::register().context("failed to setup signal handler")?;
         g3_daemon::control::panic::set_hook(&args.daemon_config);
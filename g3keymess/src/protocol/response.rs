@@ -176,6 +176,11 @@ impl KeylessErrorResponse {
     pub(crate) fn format_error(self) -> Self {
         self.set_error_code(KeylessResponseErrorCode::FormatError)
     }
+
+    #[inline]
+    pub(crate) fn internal_error(self) -> Self {
+        self.set_error_code(KeylessResponseErrorCode::InternalError)
+    }
 }
 
 pub(crate) enum KeylessResponse {
@@ -22,6 +22,11 @@ pub(crate) use task::{RequestProcessContext, WrappedKeylessRequest, WrappedKeyle
 mod runtime;
 use runtime::KeyServerRuntime;
 
+#[cfg(feature = "quic")]
+mod quic_runtime;
+#[cfg(feature = "quic")]
+use quic_runtime::KeyServerQuicRuntime;
+
 mod registry;
 pub(crate) use registry::{foreach_online as foreach_server, get_names};
 
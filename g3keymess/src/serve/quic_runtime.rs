@@ -0,0 +1,128 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use log::{info, warn};
+use quinn::{Endpoint, Incoming, TokioRuntime};
+use tokio::sync::broadcast;
+
+use g3_daemon::listen::{ListenAliveGuard, ListenStats};
+use g3_daemon::server::ClientConnectionInfo;
+use g3_std_ext::net::SocketAddrExt;
+use g3_types::net::UdpListenConfig;
+
+use super::{KeyServer, ServerReloadCommand};
+
+pub(super) struct KeyServerQuicRuntime {
+    server: Arc<KeyServer>,
+    listen_stats: Arc<ListenStats>,
+    _alive_guard: Option<ListenAliveGuard>,
+}
+
+impl KeyServerQuicRuntime {
+    pub(crate) fn new(server: &Arc<KeyServer>) -> Self {
+        KeyServerQuicRuntime {
+            server: Arc::clone(server),
+            listen_stats: server.get_listen_stats(),
+            _alive_guard: None,
+        }
+    }
+
+    fn pre_start(&mut self) {
+        info!("started QUIC SRT {}", self.server.name());
+        self._alive_guard = Some(self.listen_stats.add_running_runtime());
+    }
+
+    fn pre_stop(&self) {
+        info!("stopping QUIC SRT {}", self.server.name());
+    }
+
+    fn post_stop(&self) {
+        info!("stopped QUIC SRT {}", self.server.name());
+    }
+
+    async fn run(
+        self,
+        endpoint: Endpoint,
+        listen_addr: SocketAddr,
+        mut server_reload_channel: broadcast::Receiver<ServerReloadCommand>,
+    ) {
+        use broadcast::error::RecvError;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                ev = server_reload_channel.recv() => {
+                    match ev {
+                        Ok(ServerReloadCommand::QuitRuntime) => {},
+                        Err(RecvError::Closed) => {},
+                        Err(RecvError::Lagged(dropped)) => {
+                            warn!("QUIC SRT {} reload notify channel overflowed, {dropped} msg dropped",
+                                self.server.name());
+                            continue
+                        },
+                    }
+
+                    info!("QUIC SRT {} will go offline", self.server.name());
+                    self.pre_stop();
+                    // refuse new connections, and wait for the established ones to finish
+                    endpoint.set_server_config(None);
+                    tokio::spawn(async move { endpoint.wait_idle().await });
+                    break;
+                }
+                result = endpoint.accept() => {
+                    let Some(incoming) = result else {
+                        info!("QUIC SRT {} offline", self.server.name());
+                        break;
+                    };
+                    self.listen_stats.add_accepted();
+                    self.run_task(incoming, listen_addr);
+                }
+            }
+        }
+        self.post_stop();
+    }
+
+    fn run_task(&self, incoming: Incoming, listen_addr: SocketAddr) {
+        let peer_addr = incoming.remote_address();
+        let local_addr = incoming
+            .local_ip()
+            .map(|ip| SocketAddr::new(ip, listen_addr.port()))
+            .unwrap_or(listen_addr);
+        let cc_info =
+            ClientConnectionInfo::new(peer_addr.to_canonical(), local_addr.to_canonical());
+
+        let server = Arc::clone(&self.server);
+        tokio::spawn(async move {
+            server.run_quic_task(incoming, cc_info).await;
+        });
+    }
+
+    pub(super) fn into_running(
+        mut self,
+        listen_config: &UdpListenConfig,
+        quinn_config: quinn::ServerConfig,
+        server_reload_sender: &broadcast::Sender<ServerReloadCommand>,
+    ) -> anyhow::Result<()> {
+        let socket = g3_socket::udp::new_std_bind_listen(listen_config)?;
+        let listen_addr = socket.local_addr()?;
+        let endpoint = Endpoint::new(
+            Default::default(),
+            Some(quinn_config),
+            socket,
+            Arc::new(TokioRuntime),
+        )?;
+        let server_reload_receiver = server_reload_sender.subscribe();
+        tokio::spawn(async move {
+            self.pre_start();
+            self.run(endpoint, listen_addr, server_reload_receiver)
+                .await
+        });
+        Ok(())
+    }
+}
@@ -10,6 +10,8 @@ use anyhow::Context;
 use arc_swap::ArcSwap;
 use log::debug;
 use openssl::ssl::Ssl;
+#[cfg(feature = "quic")]
+use quinn::Incoming;
 use slog::Logger;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
@@ -23,10 +25,13 @@ use g3_openssl::SslAcceptor;
 use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue, NodeName};
 use g3_types::net::OpensslServerConfig;
 
+#[cfg(feature = "quic")]
+use super::KeyServerQuicRuntime;
 use super::{
     KeyServerDurationRecorder, KeyServerDurationStats, KeyServerRuntime, KeyServerStats,
     KeylessTask, KeylessTaskContext, ServerReloadCommand,
 };
+use crate::auth::{ClientIdentity, KeyAuthorization};
 use crate::config::server::KeyServerConfig;
 
 pub(crate) struct KeyServer {
@@ -34,6 +39,8 @@ pub(crate) struct KeyServer {
     server_stats: Arc<KeyServerStats>,
     listen_stats: Arc<ListenStats>,
     tls_server_config: Option<OpensslServerConfig>,
+    #[cfg(feature = "quic")]
+    quinn_config: Option<quinn::ServerConfig>,
     duration_recorder: KeyServerDurationRecorder,
     duration_stats: Arc<KeyServerDurationStats>,
     quit_policy: Arc<ServerQuitPolicy>,
@@ -41,6 +48,8 @@ pub(crate) struct KeyServer {
     concurrency_limit: Option<Arc<Semaphore>>,
     task_logger: Option<Logger>,
     request_logger: Option<Logger>,
+    audit_logger: Option<Logger>,
+    authorization: Option<Arc<KeyAuthorization>>,
     dynamic_metrics_tags: Arc<ArcSwap<MetricTagMap>>,
 }
 
@@ -65,8 +74,31 @@ impl KeyServer {
             None
         };
 
+        #[cfg(feature = "quic")]
+        let quinn_config = if config.quic_listen.is_some()
+            && let Some(builder) = &config.quic_server
+        {
+            let quic_server = builder
+                .build_quic()
+                .context("failed to build quic server tls config")?;
+            let mut quinn_config = quinn::ServerConfig::with_crypto(quic_server.driver);
+            // keyless requests are only carried on bidirectional streams
+            let mut transport = quinn::TransportConfig::default();
+            transport.max_concurrent_uni_streams(quinn::VarInt::from_u32(0));
+            quinn_config.transport_config(Arc::new(transport));
+            Some(quinn_config)
+        } else {
+            None
+        };
+
         let task_logger = config.get_task_logger();
         let request_logger = config.get_request_logger();
+        let audit_logger = config.get_audit_logger();
+
+        let authorization = config
+            .authorization
+            .as_ref()
+            .map(|c| Arc::new(KeyAuthorization::new(c)));
 
         // always update extra metrics tags
         let dynamic_tags = dynamic_metrics_tags.load();
@@ -88,6 +120,8 @@ impl KeyServer {
             server_stats,
             listen_stats,
             tls_server_config,
+            #[cfg(feature = "quic")]
+            quinn_config,
             duration_recorder,
             duration_stats,
             quit_policy: Arc::new(ServerQuitPolicy::default()),
@@ -95,6 +129,8 @@ impl KeyServer {
             concurrency_limit,
             task_logger,
             request_logger,
+            audit_logger,
+            authorization,
             dynamic_metrics_tags,
         })
     }
@@ -208,12 +244,23 @@ impl KeyServer {
     }
 
     pub(super) fn start_runtime(&self, server: &Arc<KeyServer>) -> anyhow::Result<()> {
-        KeyServerRuntime::new(server)
-            .into_running(&self.config.listen, &self.reload_sender)
-            .map(|_| {
-                server.server_stats.set_online();
-                server.duration_stats.set_online()
-            })
+        KeyServerRuntime::new(server).into_running(&self.config.listen, &self.reload_sender)?;
+        #[cfg(feature = "quic")]
+        if let Some(listen) = &self.config.quic_listen
+            && let Some(quinn_config) = &self.quinn_config
+            && let Err(e) = KeyServerQuicRuntime::new(server).into_running(
+                listen,
+                quinn_config.clone(),
+                &self.reload_sender,
+            )
+        {
+            // stop the tcp runtime that has already been started
+            let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+            return Err(e.context("failed to start quic runtime"));
+        }
+        server.server_stats.set_online();
+        server.duration_stats.set_online();
+        Ok(())
     }
 
     pub(super) fn abort_runtime(&self) {
@@ -222,11 +269,12 @@ impl KeyServer {
         self.duration_stats.set_offline();
     }
 
-    async fn run_task<R, W>(&self, cc_info: ClientConnectionInfo, clt_r: R, clt_w: W)
-    where
-        R: AsyncRead + Send + Unpin + 'static,
-        W: AsyncWrite + Send + Unpin + 'static,
-    {
+    fn new_task(
+        &self,
+        cc_info: ClientConnectionInfo,
+        client_identity: Option<Arc<ClientIdentity>>,
+        conn_concurrency_limit: Option<Arc<Semaphore>>,
+    ) -> KeylessTask {
         let ctx = KeylessTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
@@ -236,6 +284,10 @@ impl KeyServer {
             request_logger: self.request_logger.clone(),
             reload_notifier: self.reload_sender.subscribe(),
             concurrency_limit: self.concurrency_limit.clone(),
+            conn_concurrency_limit,
+            authorization: self.authorization.clone(),
+            client_identity,
+            audit_logger: self.audit_logger.clone(),
         };
 
         let mut task = KeylessTask::new(ctx);
@@ -252,6 +304,20 @@ impl KeyServer {
             task.set_allow_openssl_async_job();
         }
 
+        task
+    }
+
+    async fn run_task<R, W>(
+        &self,
+        cc_info: ClientConnectionInfo,
+        client_identity: Option<Arc<ClientIdentity>>,
+        clt_r: R,
+        clt_w: W,
+    ) where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let task = self.new_task(cc_info, client_identity, None);
         if self.config.multiplex_queue_depth > 1 {
             task.into_multiplex_running(clt_r, clt_w).await
         } else {
@@ -281,8 +347,12 @@ impl KeyServer {
                     // Quick ACK is needed with session resumption
                     cc_info.tcp_sock_try_quick_ack();
                 }
+                let client_identity = ssl_stream
+                    .ssl()
+                    .peer_certificate()
+                    .map(|cert| Arc::new(ClientIdentity::from_x509(&cert)));
                 let (r, w) = tokio::io::split(ssl_stream);
-                self.run_task(cc_info, r, w).await
+                self.run_task(cc_info, client_identity, r, w).await
             }
             Err(e) => {
                 self.listen_stats.add_failed();
@@ -301,7 +371,89 @@ impl KeyServer {
             self.run_tls_task(tls_server, stream, cc_info).await
         } else {
             let (r, w) = stream.into_split();
-            self.run_task(cc_info, r, w).await
+            self.run_task(cc_info, None, r, w).await
+        }
+    }
+
+    #[cfg(feature = "quic")]
+    pub(super) async fn run_quic_task(
+        self: &Arc<Self>,
+        incoming: Incoming,
+        cc_info: ClientConnectionInfo,
+    ) {
+        let Some(tls_server) = &self.config.quic_server else {
+            self.listen_stats.add_dropped();
+            return;
+        };
+
+        let connecting = match incoming.accept() {
+            Ok(c) => c,
+            Err(e) => {
+                self.listen_stats.add_failed();
+                debug!(
+                    "{} - {} quic accept error: {e}",
+                    cc_info.sock_local_addr(),
+                    cc_info.sock_peer_addr()
+                );
+                return;
+            }
+        };
+        let connection = match tokio::time::timeout(tls_server.accept_timeout(), connecting).await {
+            Ok(Ok(c)) => c,
+            Ok(Err(e)) => {
+                self.listen_stats.add_failed();
+                debug!(
+                    "{} - {} quic handshake error: {e}",
+                    cc_info.sock_local_addr(),
+                    cc_info.sock_peer_addr()
+                );
+                // TODO record tls failure and add some sec policy
+                return;
+            }
+            Err(_) => {
+                self.listen_stats.add_failed();
+                debug!(
+                    "{} - {} quic handshake timeout",
+                    cc_info.sock_local_addr(),
+                    cc_info.sock_peer_addr()
+                );
+                return;
+            }
+        };
+
+        let client_identity = ClientIdentity::from_quic_connection(&connection).map(Arc::new);
+        let conn_concurrency_limit = if self.config.quic_concurrency_limit > 0 {
+            Some(Arc::new(Semaphore::new(self.config.quic_concurrency_limit)))
+        } else {
+            None
+        };
+        let mut reload_notifier = self.reload_sender.subscribe();
+        loop {
+            tokio::select! {
+                biased;
+
+                r = reload_notifier.recv() => {
+                    match r {
+                        Ok(ServerReloadCommand::QuitRuntime) => break,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                        Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    }
+                }
+                r = connection.accept_bi() => {
+                    let Ok((send_stream, recv_stream)) = r else {
+                        // the connection has been closed
+                        break;
+                    };
+                    let task = self.new_task(
+                        cc_info.clone(),
+                        client_identity.clone(),
+                        conn_concurrency_limit.clone(),
+                    );
+                    tokio::spawn(async move {
+                        task.into_multiplex_running(recv_stream, send_stream).await
+                    });
+                }
+            }
         }
     }
 }
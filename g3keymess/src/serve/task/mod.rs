@@ -19,7 +19,9 @@ use g3_histogram::HistogramRecorder;
 use g3_slog_types::{LtDateTime, LtUuid};
 use g3_std_ext::time::DurationExt;
 
-use crate::config::server::KeyServerConfig;
+use crate::auth::{ClientIdentity, KeyAuthorization, KeyAuthorizationError};
+use crate::config::server::{KeyOperation, KeyServerConfig};
+use crate::log::audit::AuditLogContext;
 use crate::protocol::{KeylessAction, KeylessErrorResponse, KeylessRequest, KeylessResponse};
 use crate::serve::{
     KeyServerAliveTaskGuard, KeyServerDurationRecorder, KeyServerRequestStats, KeyServerStats,
@@ -32,15 +34,23 @@ mod simplex;
 #[derive(Clone)]
 pub(crate) struct RequestProcessContext {
     pub(crate) msg_id: u32,
+    pub(crate) action: KeylessAction,
+    pub(crate) ski: Arc<[u8]>,
+    pub(crate) auth_rule: Option<Arc<str>>,
+    pub(crate) auth_error: Option<KeyAuthorizationError>,
     create_time: Instant,
     pub(crate) create_datetime: DateTime<Utc>,
     duration_recorder: Arc<HistogramRecorder<u64>>,
 }
 
 impl RequestProcessContext {
-    fn new(msg_id: u32, duration_recorder: Arc<HistogramRecorder<u64>>) -> Self {
+    fn new(req: &KeylessRequest, duration_recorder: Arc<HistogramRecorder<u64>>) -> Self {
         RequestProcessContext {
-            msg_id,
+            msg_id: req.id,
+            action: req.action,
+            ski: Arc::from(req.ski.as_slice()),
+            auth_rule: None,
+            auth_error: None,
             create_time: Instant::now(),
             create_datetime: Utc::now(),
             duration_recorder,
@@ -75,6 +85,7 @@ pub(crate) struct WrappedKeylessRequest {
     ctx: RequestProcessContext,
     err_rsp: Option<KeylessErrorResponse>,
     server_sem_permit: Option<OwnedSemaphorePermit>,
+    conn_sem_permit: Option<OwnedSemaphorePermit>,
 }
 
 impl WrappedKeylessRequest {
@@ -113,13 +124,14 @@ impl WrappedKeylessRequest {
         };
         stats.add_total();
         stats.inc_alive();
-        let ctx = RequestProcessContext::new(req.id, duration_recorder);
+        let ctx = RequestProcessContext::new(&req, duration_recorder);
         WrappedKeylessRequest {
             inner: req,
             stats,
             ctx,
             err_rsp,
             server_sem_permit: None,
+            conn_sem_permit: None,
         }
     }
 
@@ -160,6 +172,11 @@ pub(crate) struct KeylessTaskContext {
     pub(crate) request_logger: Option<Logger>,
     pub(crate) reload_notifier: broadcast::Receiver<ServerReloadCommand>,
     pub(crate) concurrency_limit: Option<Arc<Semaphore>>,
+    /// request concurrency limit shared by all streams on the same quic connection
+    pub(crate) conn_concurrency_limit: Option<Arc<Semaphore>>,
+    pub(crate) authorization: Option<Arc<KeyAuthorization>>,
+    pub(crate) client_identity: Option<Arc<ClientIdentity>>,
+    pub(crate) audit_logger: Option<Logger>,
 }
 
 pub(crate) struct KeylessTask {
@@ -222,6 +239,47 @@ impl KeylessTask {
         }
     }
 
+    fn check_authorization(
+        &self,
+        req: &mut WrappedKeylessRequest,
+    ) -> Result<(), KeylessErrorResponse> {
+        let Some(authorization) = &self.ctx.authorization else {
+            return Ok(());
+        };
+        let Some(operation) = KeyOperation::from_action(req.inner.action) else {
+            return Ok(());
+        };
+
+        match authorization.check(
+            self.ctx.cc_info.client_ip(),
+            self.ctx.client_identity.as_deref(),
+            &req.inner.ski,
+            operation,
+        ) {
+            Ok(rule) => {
+                req.ctx.auth_rule = rule;
+                Ok(())
+            }
+            Err(e) => {
+                req.ctx.auth_error = Some(e);
+                let rsp = KeylessErrorResponse::new(req.inner.id);
+                match e {
+                    // do not leak the existence of the key to unauthorized clients
+                    KeyAuthorizationError::Forbidden => Err(rsp.key_not_found()),
+                    KeyAuthorizationError::RateLimited => Err(rsp.internal_error()),
+                }
+            }
+        }
+    }
+
+    fn audit_log_context(&self) -> AuditLogContext<'_> {
+        AuditLogContext {
+            task_id: &self.id,
+            client_addr: self.ctx.cc_info.client_addr(),
+            client_identity: self.ctx.client_identity.as_deref(),
+        }
+    }
+
     fn log_task_err(&self, e: ServerTaskError) {
         if e.ignore_log() {
             return;
@@ -11,6 +11,7 @@ use g3_io_ext::LimitedBufReadExt;
 
 use super::{KeylessTask, WrappedKeylessRequest, WrappedKeylessResponse};
 use crate::backend::DispatchedKeylessRequest;
+use crate::log::audit::AuditLogContext;
 use crate::log::request::RequestErrorLogContext;
 use crate::protocol::KeylessResponse;
 use crate::serve::{ServerReloadCommand, ServerTaskError};
@@ -26,14 +27,26 @@ impl KeylessTask {
 
         let task_id = self.id;
         let request_logger = self.ctx.request_logger.clone();
+        let audit_logger = self.ctx.audit_logger.clone();
+        let client_addr = self.ctx.cc_info.client_addr();
+        let client_identity = self.ctx.client_identity.clone();
         let write_handle = tokio::spawn(async move {
             let mut write_error: Result<(), ServerTaskError> = Ok(());
 
+            let audit_log_ctx = AuditLogContext {
+                task_id: &task_id,
+                client_addr,
+                client_identity: client_identity.as_deref(),
+            };
+
             'outer: while let Some(rsp) = msg_receiver.recv().await {
                 rsp.ctx.record_duration_stats();
                 if let Some(logger) = &request_logger {
                     RequestErrorLogContext { task_id: &task_id }.log(logger, &rsp.ctx, &rsp.inner);
                 }
+                if let Some(logger) = &audit_logger {
+                    audit_log_ctx.log(logger, &rsp.ctx, &rsp.inner);
+                }
                 if let Err(e) = writer.write_all(rsp.inner.message()).await {
                     write_error = Err(ServerTaskError::WriteFailed(e));
                     break;
@@ -45,6 +58,9 @@ impl KeylessTask {
                         RequestErrorLogContext { task_id: &task_id }
                             .log(logger, &rsp.ctx, &rsp.inner);
                     }
+                    if let Some(logger) = &audit_logger {
+                        audit_log_ctx.log(logger, &rsp.ctx, &rsp.inner);
+                    }
                     if let Err(e) = writer.write_all(rsp.inner.message()).await {
                         write_error = Err(ServerTaskError::WriteFailed(e));
                         break 'outer;
@@ -154,6 +170,14 @@ impl KeylessTask {
             return Ok(());
         }
 
+        if let Err(rsp) = self.check_authorization(&mut req) {
+            req.stats.add_by_error_code(rsp.error_code());
+            let _ = msg_sender
+                .send(req.build_response(KeylessResponse::Error(rsp)))
+                .await;
+            return Ok(());
+        }
+
         let key = match req.inner.find_key() {
             Ok(key) => key,
             Err(rsp) => {
@@ -165,6 +189,12 @@ impl KeylessTask {
             }
         };
 
+        if let Some(sem) = self.ctx.conn_concurrency_limit.clone()
+            && let Ok(permit) = sem.acquire_owned().await
+        {
+            req.conn_sem_permit = Some(permit);
+        }
+
         if self.allow_dispatch {
             self.async_process_by_dispatch(req, key, msg_sender).await;
             return Ok(());
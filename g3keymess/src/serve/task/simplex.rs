@@ -93,6 +93,13 @@ impl KeylessTask {
                 .await;
         }
 
+        if let Err(rsp) = self.check_authorization(&mut req) {
+            req.stats.add_by_error_code(rsp.error_code());
+            return self
+                .send_response(writer, &req.ctx, KeylessResponse::Error(rsp))
+                .await;
+        }
+
         let key = match req.inner.find_key() {
             Ok(key) => key,
             Err(rsp) => {
@@ -129,6 +136,9 @@ impl KeylessTask {
         if let Some(logger) = &self.ctx.request_logger {
             RequestErrorLogContext { task_id: &self.id }.log(logger, ctx, &rsp);
         }
+        if let Some(logger) = &self.ctx.audit_logger {
+            self.audit_log_context().log(logger, ctx, &rsp);
+        }
 
         writer
             .write_all_flush(rsp.message())
@@ -5,20 +5,33 @@
 
 use std::collections::HashMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use clap::ArgMatches;
 use openssl::hash::DigestBytes;
 use openssl::pkey::PKey;
+use openssl::symm::Cipher;
 
-use g3_ctl::CommandResult;
+use g3_ctl::{CommandError, CommandResult};
+use g3_tls_cert::envelope::DataKey;
 use g3_tls_cert::ext::PublicKeyExt;
 
 pub const COMMAND_CHECK_DUP: &str = "check-dup";
+pub const COMMAND_GEN_DATA_KEY: &str = "gen-data-key";
+pub const COMMAND_REWRAP_DATA_KEY: &str = "rewrap-data-key";
+pub const COMMAND_RE_ENCRYPT: &str = "re-encrypt";
 
 const COMMAND_ARG_DIR: &str = "dir";
 const COMMAND_ARG_EXT: &str = "ext";
+const COMMAND_ARG_FILE: &str = "file";
+const COMMAND_ARG_PASSPHRASE_FILE: &str = "passphrase-file";
+const COMMAND_ARG_PASSPHRASE_ENV: &str = "passphrase-env";
+const COMMAND_ARG_DATA_KEY: &str = "data-key";
+const COMMAND_ARG_NEW_PASSPHRASE_FILE: &str = "new-passphrase-file";
+const COMMAND_ARG_NEW_PASSPHRASE_ENV: &str = "new-passphrase-env";
+const COMMAND_ARG_NEW_DATA_KEY: &str = "new-data-key";
 
 pub mod commands {
     use super::*;
@@ -41,6 +54,104 @@ pub mod commands {
                     .num_args(1),
             )
     }
+
+    fn passphrase_args(cmd: Command, file_arg: &'static str, env_arg: &'static str) -> Command {
+        cmd.arg(
+            Arg::new(file_arg)
+                .help("File that contains the passphrase")
+                .long(file_arg)
+                .num_args(1)
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with(env_arg),
+        )
+        .arg(
+            Arg::new(env_arg)
+                .help("Environment variable that contains the passphrase")
+                .long(env_arg)
+                .num_args(1),
+        )
+    }
+
+    fn data_key_arg(cmd: Command, arg: &'static str, help: &'static str) -> Command {
+        cmd.arg(
+            Arg::new(arg)
+                .help(help)
+                .long(arg)
+                .num_args(1)
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
+    }
+
+    pub fn gen_data_key() -> Command {
+        let cmd = Command::new(COMMAND_GEN_DATA_KEY)
+            .about("Generate a new data key and wrap it with the passphrase")
+            .arg(
+                Arg::new(COMMAND_ARG_FILE)
+                    .help("Output file for the wrapped data key")
+                    .required(true)
+                    .num_args(1)
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            );
+        passphrase_args(cmd, COMMAND_ARG_PASSPHRASE_FILE, COMMAND_ARG_PASSPHRASE_ENV)
+    }
+
+    pub fn rewrap_data_key() -> Command {
+        let cmd = Command::new(COMMAND_REWRAP_DATA_KEY)
+            .about("Wrap the data key with a new passphrase")
+            .arg(
+                Arg::new(COMMAND_ARG_FILE)
+                    .help("Wrapped data key file")
+                    .required(true)
+                    .num_args(1)
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            );
+        let cmd = passphrase_args(cmd, COMMAND_ARG_PASSPHRASE_FILE, COMMAND_ARG_PASSPHRASE_ENV);
+        passphrase_args(
+            cmd,
+            COMMAND_ARG_NEW_PASSPHRASE_FILE,
+            COMMAND_ARG_NEW_PASSPHRASE_ENV,
+        )
+    }
+
+    pub fn re_encrypt() -> Command {
+        let cmd = Command::new(COMMAND_RE_ENCRYPT)
+            .about("Encrypt or re-encrypt the private key files in place")
+            .arg(
+                Arg::new(COMMAND_ARG_DIR)
+                    .help("Directory that contains Private key files")
+                    .required(true)
+                    .num_args(1)
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::DirPath),
+            )
+            .arg(
+                Arg::new(COMMAND_ARG_EXT)
+                    .help("File extension to match")
+                    .long(COMMAND_ARG_EXT)
+                    .num_args(1)
+                    .default_value("key"),
+            );
+        let cmd = passphrase_args(cmd, COMMAND_ARG_PASSPHRASE_FILE, COMMAND_ARG_PASSPHRASE_ENV);
+        let cmd = data_key_arg(
+            cmd,
+            COMMAND_ARG_DATA_KEY,
+            "Wrapped data key file used by the current key files",
+        );
+        let cmd = passphrase_args(
+            cmd,
+            COMMAND_ARG_NEW_PASSPHRASE_FILE,
+            COMMAND_ARG_NEW_PASSPHRASE_ENV,
+        );
+        data_key_arg(
+            cmd,
+            COMMAND_ARG_NEW_DATA_KEY,
+            "Wrapped data key file to be used by the new key files",
+        )
+    }
 }
 
 pub fn check_dup(args: &ArgMatches) -> CommandResult<()> {
@@ -101,3 +212,194 @@ fn get_ski(path: &Path) -> anyhow::Result<DigestBytes> {
     key.ski()
         .map_err(|e| anyhow!("failed to get SKI for key file {}: {e}", path.display()))
 }
+
+fn load_secret(
+    args: &ArgMatches,
+    file_arg: &str,
+    env_arg: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut secret = if let Some(path) = args.get_one::<PathBuf>(file_arg) {
+        fs::read(path).map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?
+    } else if let Some(name) = args.get_one::<String>(env_arg) {
+        std::env::var(name)
+            .map_err(|e| anyhow!("failed to read env var {name}: {e}"))?
+            .into_bytes()
+    } else {
+        return Ok(None);
+    };
+    while matches!(secret.last(), Some(b'\n' | b'\r')) {
+        secret.pop();
+    }
+    if secret.is_empty() {
+        return Err(anyhow!("empty passphrase"));
+    }
+    Ok(Some(secret))
+}
+
+fn load_data_key(path: &Path, secret: &[u8]) -> anyhow::Result<DataKey> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("failed to read data key file {}: {e}", path.display()))?;
+    DataKey::unwrap_pem(&content, secret)
+        .context(format!("failed to unwrap data key file {}", path.display()))
+}
+
+/// Get the passphrase for the key files, which is either the secret itself or the unwrapped data key
+fn load_passphrase(
+    args: &ArgMatches,
+    file_arg: &str,
+    env_arg: &str,
+    data_key_arg: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let secret = load_secret(args, file_arg, env_arg)?;
+    match (secret, args.get_one::<PathBuf>(data_key_arg)) {
+        (Some(secret), Some(path)) => {
+            let data_key = load_data_key(path, &secret)?;
+            Ok(Some(data_key.passphrase().into_bytes()))
+        }
+        (Some(secret), None) => Ok(Some(secret)),
+        (None, Some(_)) => Err(anyhow!("passphrase is required to unwrap the data key")),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Write to a temp file in the same dir and then rename to the target path
+fn replace_file(path: &Path, content: &[u8]) -> anyhow::Result<()> {
+    let Some(file_name) = path.file_name() else {
+        return Err(anyhow!("invalid file path {}", path.display()));
+    };
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let meta = fs::metadata(path)
+        .map_err(|e| anyhow!("failed to get metadata of file {}: {e}", path.display()))?;
+    fs::write(&tmp_path, content)
+        .map_err(|e| anyhow!("failed to write file {}: {e}", tmp_path.display()))?;
+    fs::set_permissions(&tmp_path, meta.permissions())
+        .map_err(|e| anyhow!("failed to set permissions of {}: {e}", tmp_path.display()))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        anyhow!(
+            "failed to rename {} to {}: {e}",
+            tmp_path.display(),
+            path.display()
+        )
+    })
+}
+
+pub fn gen_data_key(args: &ArgMatches) -> CommandResult<()> {
+    let path = args.get_one::<PathBuf>(COMMAND_ARG_FILE).unwrap();
+    let secret = load_secret(
+        args,
+        COMMAND_ARG_PASSPHRASE_FILE,
+        COMMAND_ARG_PASSPHRASE_ENV,
+    )?
+    .ok_or_else(|| anyhow!("no passphrase set"))?;
+
+    let data_key = DataKey::generate()?;
+    let pem = data_key.wrap_pem(&secret)?;
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| anyhow!("failed to create file {}: {e}", path.display()))?;
+    file.write_all(pem.as_bytes())
+        .map_err(|e| anyhow!("failed to write file {}: {e}", path.display()))?;
+    Ok(())
+}
+
+pub fn rewrap_data_key(args: &ArgMatches) -> CommandResult<()> {
+    let path = args.get_one::<PathBuf>(COMMAND_ARG_FILE).unwrap();
+    let secret = load_secret(
+        args,
+        COMMAND_ARG_PASSPHRASE_FILE,
+        COMMAND_ARG_PASSPHRASE_ENV,
+    )?
+    .ok_or_else(|| anyhow!("no passphrase set"))?;
+    let new_secret = load_secret(
+        args,
+        COMMAND_ARG_NEW_PASSPHRASE_FILE,
+        COMMAND_ARG_NEW_PASSPHRASE_ENV,
+    )?
+    .ok_or_else(|| anyhow!("no new passphrase set"))?;
+
+    let data_key = load_data_key(path, &secret)?;
+    let pem = data_key.wrap_pem(&new_secret)?;
+    replace_file(path, pem.as_bytes())?;
+    Ok(())
+}
+
+pub fn re_encrypt(args: &ArgMatches) -> CommandResult<()> {
+    let dir_path = args.get_one::<PathBuf>(COMMAND_ARG_DIR).unwrap();
+    let ext = args.get_one::<String>(COMMAND_ARG_EXT).unwrap();
+
+    let passphrase = load_passphrase(
+        args,
+        COMMAND_ARG_PASSPHRASE_FILE,
+        COMMAND_ARG_PASSPHRASE_ENV,
+        COMMAND_ARG_DATA_KEY,
+    )?;
+    let new_passphrase = load_passphrase(
+        args,
+        COMMAND_ARG_NEW_PASSPHRASE_FILE,
+        COMMAND_ARG_NEW_PASSPHRASE_ENV,
+        COMMAND_ARG_NEW_DATA_KEY,
+    )?
+    .ok_or_else(|| anyhow!("no new passphrase set"))?;
+
+    let dir = fs::read_dir(dir_path)
+        .map_err(|e| anyhow!("failed to open {}: {e}", dir_path.display()))?;
+    let mut failed = 0usize;
+    for entry in dir {
+        let entry = entry
+            .map_err(|e| anyhow!("failed to read entry of dir {}: {e}", dir_path.display()))?;
+        let path = entry.path();
+        let ft = entry
+            .file_type()
+            .map_err(|e| anyhow!("failed to get file type of {}: {e}", path.display()))?;
+        if !ft.is_file() {
+            continue;
+        }
+        let Some(e) = path.extension() else {
+            continue;
+        };
+        if e.to_str() != Some(ext.as_str()) {
+            continue;
+        }
+
+        match re_encrypt_file(&path, passphrase.as_deref(), &new_passphrase) {
+            Ok(_) => println!("re-encrypted {}", path.display()),
+            Err(e) => {
+                eprintln!("{e:?}");
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        Err(CommandError::Cli(anyhow!(
+            "failed to re-encrypt {failed} file(s)"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn re_encrypt_file(
+    path: &Path,
+    passphrase: Option<&[u8]>,
+    new_passphrase: &[u8],
+) -> anyhow::Result<()> {
+    let content = fs::read(path)
+        .map_err(|e| anyhow!("failed to read content of file {}: {e}", path.display()))?;
+    let key = if let Some(passphrase) = passphrase {
+        PKey::private_key_from_pem_passphrase(&content, passphrase)
+    } else {
+        PKey::private_key_from_pem(&content)
+    }
+    .map_err(|e| anyhow!("failed to load private key file {}: {e}", path.display()))?;
+
+    let pem = key
+        .private_key_to_pem_pkcs8_passphrase(Cipher::aes_256_cbc(), new_passphrase)
+        .map_err(|e| anyhow!("failed to encrypt private key {}: {e}", path.display()))?;
+    replace_file(path, &pem)
+}
@@ -28,6 +28,9 @@ fn build_cli_args() -> Command {
         .subcommand(proc::commands::check_key())
         .subcommand(server::command())
         .subcommand(local::commands::check_dup())
+        .subcommand(local::commands::gen_data_key())
+        .subcommand(local::commands::rewrap_data_key())
+        .subcommand(local::commands::re_encrypt())
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -61,6 +64,9 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_CHECK_KEY => proc::check_key(&proc_control, args).await,
                 server::COMMAND => server::run(&proc_control, args).await,
                 local::COMMAND_CHECK_DUP => local::check_dup(args),
+                local::COMMAND_GEN_DATA_KEY => local::gen_data_key(args),
+                local::COMMAND_REWRAP_DATA_KEY => local::rewrap_data_key(args),
+                local::COMMAND_RE_ENCRYPT => local::re_encrypt(args),
                 _ => Err(CommandError::Cli(anyhow!(
                     "unsupported command {subcommand}"
                 ))),
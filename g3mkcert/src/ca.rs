@@ -0,0 +1,189 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use clap::ArgMatches;
+use openssl::x509::extension::SubjectAlternativeName;
+use openssl::x509::{X509, X509Req, X509VerifyResult};
+
+use g3_tls_cert::builder::{CrlBuilder, CrlReason, CsrCertBuilder};
+use g3_types::net::Host;
+
+use super::index::{CertIndex, normalize_serial, serial_to_hex};
+use super::{
+    ARG_CSR_USAGE, ARG_DAYS, ARG_HOST, ARG_INDEX, ARG_OUTPUT_CRL, ARG_REASON, ARG_REVOKE,
+    ARG_SIGN_CSR, get_ca_cert, get_ca_cert_and_key, get_output_cert_file, record_issued,
+    write_certificate_file, write_output_file,
+};
+
+const DEFAULT_CRL_DAYS: u32 = 30;
+
+fn get_index_file(args: &ArgMatches) -> anyhow::Result<&PathBuf> {
+    args.get_one::<PathBuf>(ARG_INDEX)
+        .ok_or_else(|| anyhow!("no index file set"))
+}
+
+fn load_csr(path: &Path) -> anyhow::Result<X509Req> {
+    let content = std::fs::read(path)
+        .map_err(|e| anyhow!("failed to read csr file {}: {e:?}", path.display()))?;
+    X509Req::from_pem(&content)
+        .or_else(|_| X509Req::from_der(&content))
+        .map_err(|e| anyhow!("invalid csr in file {}: {e}", path.display()))
+}
+
+fn get_subject_alt_name(args: &ArgMatches) -> Option<SubjectAlternativeName> {
+    let hosts = args.get_many::<Host>(ARG_HOST)?;
+    let mut san = SubjectAlternativeName::new();
+    for host in hosts {
+        match host {
+            Host::Domain(domain) => {
+                san.dns(domain);
+            }
+            Host::Ip(ip) => {
+                san.ip(&ip.to_string());
+            }
+        }
+    }
+    Some(san)
+}
+
+pub(super) fn sign_csr(args: ArgMatches) -> anyhow::Result<()> {
+    let csr_file = args
+        .get_one::<PathBuf>(ARG_SIGN_CSR)
+        .ok_or_else(|| anyhow!("no csr file set"))?;
+    let req = load_csr(csr_file)?;
+
+    let mut builder = match args.get_one::<String>(ARG_CSR_USAGE).map(|s| s.as_str()) {
+        Some("client") => CsrCertBuilder::new_tls_client()?,
+        _ => CsrCertBuilder::new_tls_server()?,
+    };
+    if let Some(days) = args.get_one::<u32>(ARG_DAYS) {
+        builder.set_valid_days(*days)?;
+    }
+
+    let (ca_cert, ca_key) = get_ca_cert_and_key(&args)?;
+    let subject_alt_name = get_subject_alt_name(&args);
+    let cert = builder
+        .build(&req, subject_alt_name, &ca_cert, &ca_key, None)
+        .context("failed to sign the csr")?;
+    record_issued(&args, &cert)?;
+
+    let cert_output = get_output_cert_file(&args).unwrap_or_else(|| csr_file.with_extension("crt"));
+    write_certificate_file(&cert, cert_output)?;
+
+    Ok(())
+}
+
+/// Make sure the cert is issued by the CA, so we won't record foreign certs in the index
+fn verify_issued(ca_cert: &X509, cert: &X509) -> anyhow::Result<()> {
+    let r = ca_cert.issued(cert);
+    if r != X509VerifyResult::OK {
+        return Err(anyhow!("not issued by the ca: {}", r.error_string()));
+    }
+    let ca_pkey = ca_cert
+        .public_key()
+        .map_err(|e| anyhow!("failed to get ca public key: {e}"))?;
+    let verified = cert
+        .verify(&ca_pkey)
+        .map_err(|e| anyhow!("failed to verify the signature: {e}"))?;
+    if !verified {
+        return Err(anyhow!("the signature is not signed by the ca"));
+    }
+    Ok(())
+}
+
+pub(super) fn revoke(args: ArgMatches) -> anyhow::Result<()> {
+    let target = args
+        .get_one::<String>(ARG_REVOKE)
+        .ok_or_else(|| anyhow!("no certificate to revoke"))?;
+    let reason = match args.get_one::<String>(ARG_REASON) {
+        Some(s) => {
+            Some(CrlReason::from_str(s).map_err(|_| anyhow!("invalid revocation reason {s}"))?)
+        }
+        None => None,
+    };
+
+    let ca_cert = get_ca_cert(&args)?;
+    let index_file = get_index_file(&args)?;
+    let mut index = CertIndex::load(index_file)?;
+
+    let cert_file = Path::new(target);
+    let serial = if cert_file.is_file() {
+        let content = std::fs::read_to_string(cert_file)
+            .map_err(|e| anyhow!("failed to read cert file {}: {e:?}", cert_file.display()))?;
+        let cert = X509::from_pem(content.as_bytes())
+            .map_err(|e| anyhow!("invalid cert in file {}: {e}", cert_file.display()))?;
+        verify_issued(&ca_cert, &cert)
+            .context(format!("invalid cert in file {}", cert_file.display()))?;
+        let serial = serial_to_hex(cert.serial_number())?;
+        if !index.contains(&serial) {
+            index.add_issued(&cert)?;
+        }
+        serial
+    } else {
+        normalize_serial(target)?
+    };
+
+    index.revoke(&serial, reason)?;
+    index.save()?;
+    println!("certificate with serial {serial} revoked");
+    Ok(())
+}
+
+pub(super) fn generate_crl(args: ArgMatches) -> anyhow::Result<()> {
+    let (ca_cert, ca_key) = get_ca_cert_and_key(&args)?;
+    let index_file = get_index_file(&args)?;
+    let index = CertIndex::load(index_file)?;
+
+    let days = args
+        .get_one::<u32>(ARG_DAYS)
+        .copied()
+        .unwrap_or(DEFAULT_CRL_DAYS);
+    let mut builder = CrlBuilder::new(days)?;
+    index.add_revoked_to_crl(&mut builder)?;
+    builder.set_crl_number(index.next_crl_number()?);
+    let crl = builder
+        .build(&ca_cert, &ca_key, None)
+        .context("failed to build crl")?;
+
+    let content = crl
+        .to_pem()
+        .map_err(|e| anyhow!("failed to encode crl: {e}"))?;
+    let crl_output = args
+        .get_one::<PathBuf>(ARG_OUTPUT_CRL)
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("crl.pem"));
+    write_output_file(&content, crl_output, "crl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_tls_cert::builder::RootCertBuilder;
+
+    fn root_ca(common_name: &str) -> X509 {
+        let mut builder = RootCertBuilder::new_ec256().unwrap();
+        builder
+            .subject_builder_mut()
+            .set_common_name(common_name.to_string());
+        builder.build(None).unwrap()
+    }
+
+    #[test]
+    fn verify_issued_cert() {
+        let ca_cert = root_ca("Test CA");
+        verify_issued(&ca_cert, &ca_cert).unwrap();
+
+        let other = root_ca("Other CA");
+        assert!(verify_issued(&ca_cert, &other).is_err());
+
+        // same subject name, but signed by another key
+        let fake = root_ca("Test CA");
+        assert!(verify_issued(&ca_cert, &fake).is_err());
+    }
+}
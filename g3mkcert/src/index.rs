@@ -0,0 +1,477 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! A simple issuance index, in the same format as the OpenSSL CA `index.txt` file.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, Timelike};
+use openssl::asn1::{Asn1Integer, Asn1IntegerRef, Asn1Time, Asn1TimeRef};
+use openssl::bn::BigNum;
+use openssl::x509::{X509NameRef, X509Ref};
+
+use g3_tls_cert::builder::{CertRevocationStatus, CrlBuilder, CrlReason};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EntryStatus {
+    Valid,
+    Revoked,
+    Expired,
+}
+
+struct IndexEntry {
+    status: EntryStatus,
+    expire_time: String,
+    revoke_time: String,
+    revoke_reason: Option<CrlReason>,
+    serial: String,
+    file: String,
+    subject: String,
+}
+
+impl IndexEntry {
+    fn parse(line: &str) -> anyhow::Result<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 6 {
+            return Err(anyhow!("invalid field count {}", fields.len()));
+        }
+
+        let status = match fields[0] {
+            "V" => EntryStatus::Valid,
+            "R" => EntryStatus::Revoked,
+            "E" => EntryStatus::Expired,
+            s => return Err(anyhow!("invalid status {s}")),
+        };
+        let (revoke_time, revoke_reason) = match fields[2].split_once(',') {
+            Some((time, reason)) => {
+                let reason = CrlReason::from_str(reason)
+                    .map_err(|_| anyhow!("invalid revocation reason {reason}"))?;
+                (time.to_string(), Some(reason))
+            }
+            None => (fields[2].to_string(), None),
+        };
+        if status == EntryStatus::Revoked && revoke_time.is_empty() {
+            return Err(anyhow!("no revocation time set for revoked entry"));
+        }
+
+        Ok(IndexEntry {
+            status,
+            expire_time: fields[1].to_string(),
+            revoke_time,
+            revoke_reason,
+            serial: fields[3].to_uppercase(),
+            file: fields[4].to_string(),
+            subject: fields[5].to_string(),
+        })
+    }
+
+    fn write_line(&self, buf: &mut String) {
+        let status = match self.status {
+            EntryStatus::Valid => 'V',
+            EntryStatus::Revoked => 'R',
+            EntryStatus::Expired => 'E',
+        };
+        let _ = write!(buf, "{status}\t{}\t{}", self.expire_time, self.revoke_time);
+        if let Some(reason) = self.revoke_reason {
+            let _ = write!(buf, ",{}", reason.as_str());
+        }
+        let _ = writeln!(buf, "\t{}\t{}\t{}", self.serial, self.file, self.subject);
+    }
+}
+
+pub(crate) struct CertIndex {
+    path: PathBuf,
+    entries: Vec<IndexEntry>,
+}
+
+impl CertIndex {
+    /// Load the index file, an empty index will be returned if the file does not exist
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let mut entries = Vec::new();
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                for (i, line) in content.lines().enumerate() {
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let entry = IndexEntry::parse(line).map_err(|e| {
+                        anyhow!(
+                            "invalid line {} in index file {}: {e}",
+                            i + 1,
+                            path.display()
+                        )
+                    })?;
+                    entries.push(entry);
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(anyhow!(
+                    "failed to read index file {}: {e:?}",
+                    path.display()
+                ));
+            }
+        }
+        Ok(CertIndex {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub(crate) fn save(&self) -> anyhow::Result<()> {
+        let mut content = String::new();
+        for entry in &self.entries {
+            entry.write_line(&mut content);
+        }
+        write_file_atomic(&self.path, content.as_bytes())
+            .map_err(|e| anyhow!("failed to write index file {}: {e:?}", self.path.display()))
+    }
+
+    fn find(&self, serial: &str) -> Option<&IndexEntry> {
+        self.entries.iter().find(|e| e.serial == serial)
+    }
+
+    pub(crate) fn contains(&self, serial: &str) -> bool {
+        self.find(serial).is_some()
+    }
+
+    fn find_mut(&mut self, serial: &str) -> Option<&mut IndexEntry> {
+        self.entries.iter_mut().find(|e| e.serial == serial)
+    }
+
+    pub(crate) fn add_issued(&mut self, cert: &X509Ref) -> anyhow::Result<()> {
+        let serial = serial_to_hex(cert.serial_number())?;
+        if self.contains(&serial) {
+            return Err(anyhow!("serial {serial} already exists in the index"));
+        }
+
+        let expire_time = asn1_time_to_unix(cert.not_after())?;
+        self.entries.push(IndexEntry {
+            status: EntryStatus::Valid,
+            expire_time: format_index_time(expire_time)?,
+            revoke_time: String::new(),
+            revoke_reason: None,
+            serial,
+            file: "unknown".to_string(),
+            subject: format_subject(cert.subject_name()),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn revoke(&mut self, serial: &str, reason: Option<CrlReason>) -> anyhow::Result<()> {
+        let Some(entry) = self.find_mut(serial) else {
+            return Err(anyhow!("serial {serial} is not found in the index"));
+        };
+        if entry.status == EntryStatus::Revoked {
+            return Err(anyhow!(
+                "certificate with serial {serial} is already revoked"
+            ));
+        }
+
+        let revoke_time = format_index_time(unix_now())?;
+        entry.status = EntryStatus::Revoked;
+        entry.revoke_time = revoke_time;
+        entry.revoke_reason = reason;
+        Ok(())
+    }
+
+    pub(crate) fn add_revoked_to_crl(&self, builder: &mut CrlBuilder) -> anyhow::Result<()> {
+        for entry in &self.entries {
+            if entry.status != EntryStatus::Revoked {
+                continue;
+            }
+            let serial = serial_from_hex(&entry.serial)?;
+            let time = Asn1Time::from_str(&entry.revoke_time)
+                .map_err(|e| anyhow!("invalid revocation time {}: {e}", entry.revoke_time))?;
+            builder.add_revoked(serial, time, entry.revoke_reason);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn revocation_status(&self, serial: &Asn1IntegerRef) -> CertRevocationStatus {
+        let Ok(serial) = serial_to_hex(serial) else {
+            return CertRevocationStatus::Unknown;
+        };
+        let Some(entry) = self.find(&serial) else {
+            return CertRevocationStatus::Unknown;
+        };
+        match entry.status {
+            EntryStatus::Valid => CertRevocationStatus::Good,
+            EntryStatus::Revoked => match Asn1Time::from_str(&entry.revoke_time) {
+                Ok(revocation_time) => CertRevocationStatus::Revoked {
+                    revocation_time,
+                    reason: entry.revoke_reason,
+                },
+                Err(_) => CertRevocationStatus::Unknown,
+            },
+            EntryStatus::Expired => CertRevocationStatus::Unknown,
+        }
+    }
+
+    /// Get the next CRL number, which is stored in the `<index>.crlnumber` file
+    pub(crate) fn next_crl_number(&self) -> anyhow::Result<Asn1Integer> {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".crlnumber");
+        let path = PathBuf::from(path);
+
+        let mut number = match std::fs::read_to_string(&path) {
+            Ok(s) => BigNum::from_hex_str(s.trim())
+                .map_err(|e| anyhow!("invalid crl number in file {}: {e}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                BigNum::from_u32(1).map_err(|e| anyhow!("failed to create bignum: {e}"))?
+            }
+            Err(e) => {
+                return Err(anyhow!(
+                    "failed to read crl number file {}: {e:?}",
+                    path.display()
+                ));
+            }
+        };
+        let current = number
+            .to_asn1_integer()
+            .map_err(|e| anyhow!("failed to convert crl number: {e}"))?;
+
+        number
+            .add_word(1)
+            .map_err(|e| anyhow!("failed to increase crl number: {e}"))?;
+        let next = number
+            .to_hex_str()
+            .map_err(|e| anyhow!("failed to encode crl number: {e}"))?;
+        write_file_atomic(&path, format!("{next}\n").as_bytes())
+            .map_err(|e| anyhow!("failed to write crl number file {}: {e:?}", path.display()))?;
+        Ok(current)
+    }
+}
+
+pub(crate) fn serial_to_hex(serial: &Asn1IntegerRef) -> anyhow::Result<String> {
+    let bn = serial
+        .to_bn()
+        .map_err(|e| anyhow!("failed to convert serial number: {e}"))?;
+    let s = bn
+        .to_hex_str()
+        .map_err(|e| anyhow!("failed to encode serial number: {e}"))?;
+    Ok(s.to_uppercase())
+}
+
+/// Normalize the user input hex serial number
+pub(crate) fn normalize_serial(s: &str) -> anyhow::Result<String> {
+    let s = s.trim().trim_start_matches("0x").replace(':', "");
+    let bn = BigNum::from_hex_str(&s).map_err(|e| anyhow!("invalid hex serial {s}: {e}"))?;
+    let s = bn
+        .to_hex_str()
+        .map_err(|e| anyhow!("failed to encode serial number: {e}"))?;
+    Ok(s.to_uppercase())
+}
+
+fn serial_from_hex(s: &str) -> anyhow::Result<Asn1Integer> {
+    let bn = BigNum::from_hex_str(s).map_err(|e| anyhow!("invalid hex serial {s}: {e}"))?;
+    bn.to_asn1_integer()
+        .map_err(|e| anyhow!("failed to convert serial {s}: {e}"))
+}
+
+fn format_subject(name: &X509NameRef) -> String {
+    let mut s = String::new();
+    for entry in name.entries() {
+        let key = entry.object().nid().short_name().unwrap_or("UNDEF");
+        let _ = write!(s, "/{key}=");
+        match entry.data().as_utf8() {
+            Ok(v) => push_escaped(&mut s, &v),
+            Err(_) => push_escaped(&mut s, &String::from_utf8_lossy(entry.data().as_slice())),
+        }
+    }
+    s
+}
+
+/// Escape the control chars as `\xHH`, like what OpenSSL does, to keep the line format valid
+fn push_escaped(buf: &mut String, v: &str) {
+    for c in v.chars() {
+        if c.is_ascii_control() {
+            let _ = write!(buf, "\\x{:02X}", c as u32);
+        } else {
+            buf.push(c);
+        }
+    }
+}
+
+/// Write to a temp file and then rename it, so the file will never be left partially written
+fn write_file_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut tmp_path = path.to_path_buf().into_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp_path, path)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+fn asn1_time_to_unix(time: &Asn1TimeRef) -> anyhow::Result<i64> {
+    let epoch = Asn1Time::from_unix(0).map_err(|e| anyhow!("failed to get epoch time: {e}"))?;
+    let diff = epoch
+        .diff(time)
+        .map_err(|e| anyhow!("failed to get time diff: {e}"))?;
+    Ok(diff.days as i64 * 86400 + diff.secs as i64)
+}
+
+/// Format as UTCTime for year 1950-2049, or GeneralizedTime otherwise
+fn format_index_time(unix: i64) -> anyhow::Result<String> {
+    let time =
+        DateTime::from_timestamp(unix, 0).ok_or_else(|| anyhow!("out of range time {unix}"))?;
+    let year = time.year();
+    if !(0..10000).contains(&year) {
+        return Err(anyhow!("out of range time {unix}"));
+    }
+    let s = if (1950..2050).contains(&year) {
+        format!(
+            "{:02}{:02}{:02}{:02}{:02}{:02}Z",
+            year % 100,
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minute(),
+            time.second()
+        )
+    } else {
+        format!(
+            "{year:04}{:02}{:02}{:02}{:02}{:02}Z",
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minute(),
+            time.second()
+        )
+    };
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::nid::Nid;
+    use openssl::x509::X509NameBuilder;
+
+    #[test]
+    fn entry_round_trip() {
+        let lines = [
+            "V\t491231235959Z\t\t0A1B\tunknown\t/CN=a.example.net",
+            "R\t20500101000000Z\t250301123456Z,keyCompromise\t0C\tunknown\t/O=Test/CN=b",
+            "R\t491231235959Z\t250301123456Z\t0D\tunknown\t/CN=c",
+            "E\t250301123456Z\t\tFF\tcert.pem\t/CN=d",
+        ];
+        for line in lines {
+            let entry = IndexEntry::parse(line).unwrap();
+            let mut buf = String::new();
+            entry.write_line(&mut buf);
+            assert_eq!(buf, format!("{line}\n"));
+        }
+
+        let entry = IndexEntry::parse(lines[1]).unwrap();
+        assert!(entry.status == EntryStatus::Revoked);
+        assert_eq!(entry.revoke_time, "250301123456Z");
+        assert_eq!(entry.revoke_reason, Some(CrlReason::KeyCompromise));
+
+        // serial will be normalized to uppercase
+        let entry = IndexEntry::parse("V\t491231235959Z\t\t0a1b\tunknown\t/CN=a").unwrap();
+        assert_eq!(entry.serial, "0A1B");
+    }
+
+    #[test]
+    fn entry_invalid() {
+        for line in [
+            "V\t491231235959Z\t\t0A\tunknown",
+            "V\t491231235959Z\t\t0A\tunknown\t/CN=a\textra",
+            "X\t491231235959Z\t\t0A\tunknown\t/CN=a",
+            "R\t491231235959Z\t\t0A\tunknown\t/CN=a",
+            "R\t491231235959Z\t250301123456Z,badReason\t0A\tunknown\t/CN=a",
+        ] {
+            assert!(IndexEntry::parse(line).is_err(), "{line} should be invalid");
+        }
+    }
+
+    #[test]
+    fn index_time() {
+        assert_eq!(format_index_time(0).unwrap(), "700101000000Z");
+        assert_eq!(format_index_time(1740832496).unwrap(), "250301123456Z");
+        assert_eq!(format_index_time(-631152000).unwrap(), "500101000000Z");
+        assert_eq!(format_index_time(2524607999).unwrap(), "491231235959Z");
+        assert_eq!(format_index_time(-631152001).unwrap(), "19491231235959Z");
+        assert_eq!(format_index_time(2524608000).unwrap(), "20500101000000Z");
+        assert!(format_index_time(253402300800).is_err());
+        assert!(format_index_time(i64::MAX).is_err());
+
+        // should be parsable by openssl
+        for unix in [0, 2524607999, 2524608000] {
+            let s = format_index_time(unix).unwrap();
+            let time = Asn1Time::from_str(&s).unwrap();
+            let expected = Asn1Time::from_unix(unix).unwrap();
+            assert_eq!(time.to_string(), expected.to_string());
+        }
+    }
+
+    #[test]
+    fn subject_escape() {
+        let mut builder = X509NameBuilder::new().unwrap();
+        builder
+            .append_entry_by_nid(Nid::ORGANIZATIONNAME, "Test")
+            .unwrap();
+        builder
+            .append_entry_by_nid(Nid::COMMONNAME, "a\tb\nc")
+            .unwrap();
+        let name = builder.build();
+
+        let subject = format_subject(&name);
+        assert_eq!(subject, "/O=Test/CN=a\\x09b\\x0Ac");
+
+        let line = format!("V\t491231235959Z\t\t0A\tunknown\t{subject}");
+        let entry = IndexEntry::parse(&line).unwrap();
+        assert_eq!(entry.subject, subject);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = std::env::temp_dir().join(format!("g3mkcert-index-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.txt");
+
+        let mut index = CertIndex::load(&path).unwrap();
+        assert!(index.entries.is_empty());
+        index
+            .entries
+            .push(IndexEntry::parse("V\t491231235959Z\t\t0A\tunknown\t/CN=a").unwrap());
+        index.revoke("0A", Some(CrlReason::Superseded)).unwrap();
+        assert!(index.revoke("0A", None).is_err());
+        assert!(index.revoke("0B", None).is_err());
+        index.save().unwrap();
+
+        let index = CertIndex::load(&path).unwrap();
+        assert_eq!(index.entries.len(), 1);
+        let entry = index.find("0A").unwrap();
+        assert!(entry.status == EntryStatus::Revoked);
+        assert_eq!(entry.revoke_reason, Some(CrlReason::Superseded));
+
+        let n1 = index.next_crl_number().unwrap();
+        let n2 = index.next_crl_number().unwrap();
+        assert_eq!(n1.to_bn().unwrap(), BigNum::from_u32(1).unwrap());
+        assert_eq!(n2.to_bn().unwrap(), BigNum::from_u32(2).unwrap());
+
+        assert!(!dir.join("index.txt.tmp").exists());
+        assert!(!dir.join("index.txt.crlnumber.tmp").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,6 +4,7 @@
  */
 
 use std::io::Write;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, anyhow};
@@ -12,7 +13,7 @@ use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command, ValueHint, value_parse
 use clap_complete::Shell;
 use openssl::pkey::{PKey, Private};
 use openssl::x509::extension::SubjectAlternativeName;
-use openssl::x509::{X509, X509Name};
+use openssl::x509::{X509, X509Name, X509Ref};
 
 use g3_tls_cert::builder::{
     ClientCertBuilder, IntermediateCertBuilder, MimicCertBuilder, RootCertBuilder,
@@ -24,6 +25,13 @@ use g3_types::net::Host;
 
 mod build;
 
+mod ca;
+mod index;
+mod ocsp;
+mod pkcs12;
+
+use index::CertIndex;
+
 const ARG_VERSION: &str = "version";
 const ARG_COMPLETION: &str = "completion";
 
@@ -35,6 +43,11 @@ const ARG_TLCP_SERVER_SIGN: &str = "tlcp-server-sign";
 const ARG_TLCP_SERVER_ENC: &str = "tlcp-server-enc";
 const ARG_TLCP_CLIENT_SIGN: &str = "tlcp-client-sign";
 const ARG_TLCP_CLIENT_ENC: &str = "tlcp-client-enc";
+const ARG_SIGN_CSR: &str = "sign-csr";
+const ARG_REVOKE: &str = "revoke";
+const ARG_GEN_CRL: &str = "gen-crl";
+const ARG_PKCS12: &str = "pkcs12";
+const ARG_OCSP_RESPONDER: &str = "ocsp-responder";
 
 const ARG_RSA: &str = "rsa";
 const ARG_EC224: &str = "ec224";
@@ -58,9 +71,21 @@ const ARG_COMMON_NAME: &str = "common-name";
 
 const ARG_PATH_LENGTH: &str = "path-length";
 const ARG_HOST: &str = "host";
+const ARG_DAYS: &str = "days";
+
+const ARG_CSR_USAGE: &str = "csr-usage";
+const ARG_INDEX: &str = "index";
+const ARG_REASON: &str = "reason";
+const ARG_OCSP_VALID_HOURS: &str = "ocsp-valid-hours";
+
+const ARG_CERT: &str = "cert";
+const ARG_KEY: &str = "key";
+const ARG_PKCS12_PASSWORD: &str = "pkcs12-password";
 
 const ARG_OUTPUT_CERT: &str = "output-cert";
 const ARG_OUTPUT_KEY: &str = "output-key";
+const ARG_OUTPUT_CRL: &str = "output-crl";
+const ARG_OUTPUT_PKCS12: &str = "output-pkcs12";
 
 const ARG_GROUP_SUBJECT: &str = "subject";
 const ARG_GROUP_TYPE: &str = "type";
@@ -99,6 +124,16 @@ fn main() -> anyhow::Result<()> {
         generate_tlcp_client_sign(args)
     } else if args.get_flag(ARG_TLCP_CLIENT_ENC) {
         generate_tlcp_client_enc(args)
+    } else if args.contains_id(ARG_SIGN_CSR) {
+        ca::sign_csr(args)
+    } else if args.contains_id(ARG_REVOKE) {
+        ca::revoke(args)
+    } else if args.get_flag(ARG_GEN_CRL) {
+        ca::generate_crl(args)
+    } else if args.get_flag(ARG_PKCS12) {
+        pkcs12::generate_pkcs12(args)
+    } else if args.contains_id(ARG_OCSP_RESPONDER) {
+        ocsp::run_responder(args)
     } else {
         unreachable!()
     }
@@ -199,6 +234,56 @@ fn build_cli_args() -> Command {
                 .requires(ARG_CA_KEY)
                 .requires(ARG_HOST),
         )
+        .arg(
+            Arg::new(ARG_SIGN_CSR)
+                .help("Sign the certificate signing request with the CA")
+                .num_args(1)
+                .long(ARG_SIGN_CSR)
+                .value_name("CSR FILE")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .requires(ARG_CA_CERT)
+                .requires(ARG_CA_KEY),
+        )
+        .arg(
+            Arg::new(ARG_REVOKE)
+                .help("Revoke the certificate with the serial number in hex, or in the cert file")
+                .num_args(1)
+                .long(ARG_REVOKE)
+                .value_name("SERIAL|CERT FILE")
+                .requires(ARG_CA_CERT)
+                .requires(ARG_INDEX),
+        )
+        .arg(
+            Arg::new(ARG_GEN_CRL)
+                .help("Generate CRL for the revoked certificates in the index")
+                .num_args(0)
+                .long(ARG_GEN_CRL)
+                .action(ArgAction::SetTrue)
+                .requires(ARG_CA_CERT)
+                .requires(ARG_CA_KEY)
+                .requires(ARG_INDEX),
+        )
+        .arg(
+            Arg::new(ARG_PKCS12)
+                .help("Generate PKCS#12 bundle for the certificate and private key")
+                .num_args(0)
+                .long(ARG_PKCS12)
+                .action(ArgAction::SetTrue)
+                .requires(ARG_CERT)
+                .requires(ARG_KEY),
+        )
+        .arg(
+            Arg::new(ARG_OCSP_RESPONDER)
+                .help("Run OCSP responder for the certificates in the index")
+                .num_args(1)
+                .long(ARG_OCSP_RESPONDER)
+                .value_name("ADDR")
+                .value_parser(value_parser!(SocketAddr))
+                .requires(ARG_CA_CERT)
+                .requires(ARG_CA_KEY)
+                .requires(ARG_INDEX),
+        )
         .group(
             ArgGroup::new(ARG_GROUP_TYPE)
                 .args([
@@ -211,6 +296,11 @@ fn build_cli_args() -> Command {
                     ARG_TLCP_SERVER_ENC,
                     ARG_TLCP_CLIENT_SIGN,
                     ARG_TLCP_CLIENT_ENC,
+                    ARG_SIGN_CSR,
+                    ARG_REVOKE,
+                    ARG_GEN_CRL,
+                    ARG_PKCS12,
+                    ARG_OCSP_RESPONDER,
                     ARG_VERSION,
                     ARG_COMPLETION,
                 ])
@@ -379,6 +469,70 @@ fn build_cli_args() -> Command {
                 .value_parser(value_parser!(u32))
                 .default_value_if(ARG_INTERMEDIATE, ArgPredicate::IsPresent, "0"),
         )
+        .arg(
+            Arg::new(ARG_DAYS)
+                .help("Set valid days for the signed certificate, or days to the next update of the CRL")
+                .long(ARG_DAYS)
+                .num_args(1)
+                .value_parser(value_parser!(u32)),
+        )
+        .arg(
+            Arg::new(ARG_CSR_USAGE)
+                .help("Set the usage of the certificate signed from CSR")
+                .long(ARG_CSR_USAGE)
+                .num_args(1)
+                .value_parser(["server", "client"])
+                .default_value("server"),
+        )
+        .arg(
+            Arg::new(ARG_INDEX)
+                .help("The index file to record issued and revoked certificates")
+                .long(ARG_INDEX)
+                .num_args(1)
+                .value_name("INDEX FILE")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+            Arg::new(ARG_REASON)
+                .help("Set the revocation reason, such as keyCompromise or superseded")
+                .long(ARG_REASON)
+                .num_args(1)
+                .requires(ARG_REVOKE),
+        )
+        .arg(
+            Arg::new(ARG_OCSP_VALID_HOURS)
+                .help("Set the hours to the next update of the OCSP response")
+                .long(ARG_OCSP_VALID_HOURS)
+                .num_args(1)
+                .value_parser(value_parser!(u32))
+                .requires(ARG_OCSP_RESPONDER),
+        )
+        .arg(
+            Arg::new(ARG_CERT)
+                .help("Certificate file")
+                .long(ARG_CERT)
+                .num_args(1)
+                .value_name("CERT FILE")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+            Arg::new(ARG_KEY)
+                .help("Private Key file")
+                .long(ARG_KEY)
+                .num_args(1)
+                .value_name("KEY FILE")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+            Arg::new(ARG_PKCS12_PASSWORD)
+                .help("Set the password of the PKCS#12 bundle file")
+                .long(ARG_PKCS12_PASSWORD)
+                .num_args(1)
+                .value_name("PASSWORD"),
+        )
         .arg(
             Arg::new(ARG_OUTPUT_CERT)
                 .help("Output path for the certificate file")
@@ -395,15 +549,28 @@ fn build_cli_args() -> Command {
                 .value_parser(value_parser!(PathBuf))
                 .value_hint(ValueHint::FilePath),
         )
+        .arg(
+            Arg::new(ARG_OUTPUT_CRL)
+                .help("Output path for the CRL file")
+                .long(ARG_OUTPUT_CRL)
+                .num_args(1)
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+            Arg::new(ARG_OUTPUT_PKCS12)
+                .help("Output path for the PKCS#12 bundle file")
+                .long(ARG_OUTPUT_PKCS12)
+                .num_args(1)
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
 }
 
-fn get_ca_cert_and_key(args: &ArgMatches) -> anyhow::Result<(X509, PKey<Private>)> {
+fn get_ca_cert(args: &ArgMatches) -> anyhow::Result<X509> {
     let ca_cert_file = args
         .get_one::<PathBuf>(ARG_CA_CERT)
         .ok_or_else(|| anyhow!("no ca certificate set"))?;
-    let ca_key_file = args
-        .get_one::<PathBuf>(ARG_CA_KEY)
-        .ok_or_else(|| anyhow!("no ca private key set"))?;
 
     let ca_cert_content = std::fs::read_to_string(ca_cert_file).map_err(|e| {
         anyhow!(
@@ -411,8 +578,15 @@ fn get_ca_cert_and_key(args: &ArgMatches) -> anyhow::Result<(X509, PKey<Private>
             ca_cert_file.display()
         )
     })?;
-    let ca_cert = X509::from_pem(ca_cert_content.as_bytes())
-        .map_err(|e| anyhow!("invalid ca cert in file {}: {e}", ca_cert_file.display()))?;
+    X509::from_pem(ca_cert_content.as_bytes())
+        .map_err(|e| anyhow!("invalid ca cert in file {}: {e}", ca_cert_file.display()))
+}
+
+fn get_ca_cert_and_key(args: &ArgMatches) -> anyhow::Result<(X509, PKey<Private>)> {
+    let ca_cert = get_ca_cert(args)?;
+    let ca_key_file = args
+        .get_one::<PathBuf>(ARG_CA_KEY)
+        .ok_or_else(|| anyhow!("no ca private key set"))?;
 
     let ca_key_content = std::fs::read_to_string(ca_key_file).map_err(|e| {
         anyhow!(
@@ -446,6 +620,15 @@ fn get_output_key_file(args: &ArgMatches) -> Option<PathBuf> {
     args.get_one::<PathBuf>(ARG_OUTPUT_KEY).cloned()
 }
 
+fn record_issued(args: &ArgMatches, cert: &X509Ref) -> anyhow::Result<()> {
+    if let Some(path) = args.get_one::<PathBuf>(ARG_INDEX) {
+        let mut index = CertIndex::load(path)?;
+        index.add_issued(cert)?;
+        index.save()?;
+    }
+    Ok(())
+}
+
 fn set_subject_name(
     args: &ArgMatches,
     subject_builder: &mut SubjectNameBuilder,
@@ -572,6 +755,7 @@ fn generate_intermediate(args: ArgMatches) -> anyhow::Result<()> {
         .ok_or_else(|| anyhow!("no common name set"))?;
 
     let cert = builder.build(path_len, &ca_cert, &ca_key, None)?;
+    record_issued(&args, &cert)?;
     let cert_output = get_output_cert_file(&args).unwrap_or_else(|| cn2fn(format!("{cn}.crt")));
     write_certificate_file(&cert, cert_output)?;
     let key_output = get_output_key_file(&args).unwrap_or_else(|| cn2fn(format!("{cn}.key")));
@@ -660,10 +844,14 @@ fn generate_server(mut builder: ServerCertBuilder, args: ArgMatches) -> anyhow::
     let cert = builder
         .build_with_subject(&subject_name, subject_alt_name, &ca_cert, &ca_key, None)
         .context("failed to build tls server certificate")?;
+    record_issued(&args, &cert)?;
     let cert_output = get_output_cert_file(&args).unwrap_or_else(|| cn2fn(format!("{cn}.crt")));
     write_certificate_file(&cert, cert_output)?;
     let key_output = get_output_key_file(&args).unwrap_or_else(|| cn2fn(format!("{cn}.key")));
     write_private_key_file(builder.pkey(), key_output)?;
+    if let Some(p12_output) = args.get_one::<PathBuf>(ARG_OUTPUT_PKCS12) {
+        pkcs12::write_pkcs12_file(&args, &cert, builder.pkey(), &[ca_cert], p12_output)?;
+    }
 
     Ok(())
 }
@@ -748,12 +936,16 @@ fn generate_client(mut builder: ClientCertBuilder, args: ArgMatches) -> anyhow::
     let cert = builder
         .build_with_subject(&subject_name, subject_alt_name, &ca_cert, &ca_key, None)
         .context("failed to build tls client certificate")?;
+    record_issued(&args, &cert)?;
     let cert_output =
         get_output_cert_file(&args).unwrap_or_else(|| cn2fn(format!("{cn}-client.crt")));
     write_certificate_file(&cert, cert_output)?;
     let key_output =
         get_output_key_file(&args).unwrap_or_else(|| cn2fn(format!("{cn}-client.key")));
     write_private_key_file(builder.pkey(), key_output)?;
+    if let Some(p12_output) = args.get_one::<PathBuf>(ARG_OUTPUT_PKCS12) {
+        pkcs12::write_pkcs12_file(&args, &cert, builder.pkey(), &[ca_cert], p12_output)?;
+    }
     Ok(())
 }
 
@@ -804,7 +996,7 @@ fn cn2fn(s: String) -> PathBuf {
     PathBuf::from(n)
 }
 
-fn write_certificate_file<P: AsRef<Path>>(cert: &X509, path: P) -> anyhow::Result<()> {
+fn write_certificate_file<P: AsRef<Path>>(cert: &X509Ref, path: P) -> anyhow::Result<()> {
     let content = cert
         .to_pem()
         .map_err(|e| anyhow!("failed to encode certificate: {e}"))?;
@@ -853,3 +1045,25 @@ fn write_private_key_file<P: AsRef<Path>>(key: &PKey<Private>, path: P) -> anyho
     println!("private key saved to {}", path.as_ref().display());
     Ok(())
 }
+
+fn write_output_file<P: AsRef<Path>>(content: &[u8], path: P, kind: &str) -> anyhow::Result<()> {
+    let mut file = std::fs::File::options()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path.as_ref())
+        .map_err(|e| {
+            anyhow!(
+                "failed to open {kind} output file {}: {e:?}",
+                path.as_ref().display()
+            )
+        })?;
+    file.write_all(content).map_err(|e| {
+        anyhow!(
+            "failed to write {kind} to file {}: {e:?}",
+            path.as_ref().display()
+        )
+    })?;
+    println!("{kind} saved to {}", path.as_ref().display());
+    Ok(())
+}
@@ -0,0 +1,319 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! A minimal OCSP responder over HTTP/1.0, which answers requests using the issuance index.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use openssl::pkey::{PKey, Private};
+use openssl::x509::X509Ref;
+
+use g3_tls_cert::builder::OcspResponseBuilder;
+
+use super::index::CertIndex;
+use super::{ARG_INDEX, ARG_OCSP_RESPONDER, ARG_OCSP_VALID_HOURS, get_ca_cert_and_key};
+
+const MAX_HEADER_SIZE: usize = 8192;
+const MAX_BODY_SIZE: usize = 65536;
+/// the max time to receive the whole request, as connections are served one by one
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct OcspResponder<'a> {
+    builder: OcspResponseBuilder,
+    index_file: &'a Path,
+    ca_cert: &'a X509Ref,
+    ca_key: &'a PKey<Private>,
+}
+
+pub(super) fn run_responder(args: ArgMatches) -> anyhow::Result<()> {
+    let addr = args
+        .get_one::<SocketAddr>(ARG_OCSP_RESPONDER)
+        .ok_or_else(|| anyhow!("no listen address set"))?;
+    let index_file = args
+        .get_one::<PathBuf>(ARG_INDEX)
+        .ok_or_else(|| anyhow!("no index file set"))?;
+    let (ca_cert, ca_key) = get_ca_cert_and_key(&args)?;
+    // make sure the index file is valid before serving
+    CertIndex::load(index_file)?;
+
+    let mut builder = OcspResponseBuilder::new();
+    if let Some(hours) = args.get_one::<u32>(ARG_OCSP_VALID_HOURS) {
+        builder.set_valid_hours(*hours);
+    }
+    let responder = OcspResponder {
+        builder,
+        index_file,
+        ca_cert: &ca_cert,
+        ca_key: &ca_key,
+    };
+
+    let listener =
+        TcpListener::bind(addr).map_err(|e| anyhow!("failed to listen on {addr}: {e:?}"))?;
+    let local_addr = listener.local_addr().unwrap_or(*addr);
+    println!("ocsp responder listening on {local_addr}");
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("failed to accept connection: {e:?}");
+                continue;
+            }
+        };
+        let peer = stream
+            .peer_addr()
+            .map(|a| a.to_string())
+            .unwrap_or_default();
+        if let Err(e) = responder.serve(stream) {
+            eprintln!("failed to serve {peer}: {e:?}");
+        }
+    }
+
+    Ok(())
+}
+
+impl OcspResponder<'_> {
+    fn serve(&self, mut stream: TcpStream) -> anyhow::Result<()> {
+        stream
+            .set_write_timeout(Some(WRITE_TIMEOUT))
+            .map_err(|e| anyhow!("failed to set write timeout: {e:?}"))?;
+
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        let req = match read_ocsp_request(&mut stream, deadline) {
+            Ok(req) => req,
+            Err(e) => {
+                let _ = write_http_response(&mut stream, "400 Bad Request", None);
+                return Err(e);
+            }
+        };
+
+        // reload the index for each request, so new issued or revoked certificates can be found
+        let r = CertIndex::load(self.index_file).and_then(|index| {
+            self.builder
+                .build(&req, self.ca_cert, self.ca_key, None, |serial| {
+                    index.revocation_status(serial)
+                })
+        });
+        let rsp = match r {
+            Ok(rsp) => rsp,
+            Err(e) => {
+                let _ = match self.builder.build_internal_error() {
+                    Ok(rsp) => write_http_response(&mut stream, "200 OK", Some(&rsp)),
+                    Err(_) => write_http_response(&mut stream, "500 Internal Server Error", None),
+                };
+                return Err(e);
+            }
+        };
+        write_http_response(&mut stream, "200 OK", Some(&rsp))
+    }
+}
+
+/// Read from the stream, the read timeout will be limited by the request deadline
+fn read_with_deadline(
+    stream: &mut TcpStream,
+    buf: &mut [u8],
+    deadline: Instant,
+) -> anyhow::Result<usize> {
+    let left = deadline.saturating_duration_since(Instant::now());
+    if left.is_zero() {
+        return Err(anyhow!("timeout to read the http request"));
+    }
+    stream
+        .set_read_timeout(Some(left))
+        .map_err(|e| anyhow!("failed to set read timeout: {e:?}"))?;
+    stream
+        .read(buf)
+        .map_err(|e| anyhow!("failed to read http request: {e:?}"))
+}
+
+fn read_ocsp_request(stream: &mut TcpStream, deadline: Instant) -> anyhow::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(1024);
+    let header_end = loop {
+        if let Some(p) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break p;
+        }
+        if buf.len() > MAX_HEADER_SIZE {
+            return Err(anyhow!("too large http header"));
+        }
+        let mut tmp = [0u8; 1024];
+        let nr = read_with_deadline(stream, &mut tmp, deadline)?;
+        if nr == 0 {
+            return Err(anyhow!("connection closed before the end of http header"));
+        }
+        buf.extend_from_slice(&tmp[..nr]);
+    };
+
+    let header = std::str::from_utf8(&buf[..header_end])
+        .map_err(|_| anyhow!("invalid http header encoding"))?;
+    let mut lines = header.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+
+    match method {
+        "GET" => decode_get_target(target),
+        "POST" => {
+            let mut content_length = None;
+            for line in lines {
+                if let Some((name, value)) = line.split_once(':')
+                    && name.trim().eq_ignore_ascii_case("content-length")
+                {
+                    let len = value
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| anyhow!("invalid content-length value {value}"))?;
+                    content_length = Some(len);
+                }
+            }
+            let content_length =
+                content_length.ok_or_else(|| anyhow!("no content-length header found"))?;
+            if content_length > MAX_BODY_SIZE {
+                return Err(anyhow!("too large ocsp request body"));
+            }
+
+            let mut body = buf.split_off(header_end + 4);
+            body.truncate(content_length);
+            let mut offset = body.len();
+            body.resize(content_length, 0);
+            while offset < content_length {
+                let nr = read_with_deadline(stream, &mut body[offset..], deadline)?;
+                if nr == 0 {
+                    return Err(anyhow!("connection closed before the end of http body"));
+                }
+                offset += nr;
+            }
+            Ok(body)
+        }
+        _ => Err(anyhow!("unsupported http method {method}")),
+    }
+}
+
+/// Get the DER encoded request from the GET request target, which is in the form of
+/// `{url}/{url-encoding of base-64 encoding of the DER encoding of the OCSPRequest}`.
+/// The url may contain a path, and the base64 string may also contain `/`,
+/// so the longest suffix that can be decoded as a DER SEQUENCE will be used.
+fn decode_get_target(target: &str) -> anyhow::Result<Vec<u8>> {
+    let path = target.split_once('?').map(|(p, _)| p).unwrap_or(target);
+    let mut candidate = path;
+    while let Some((_, encoded)) = candidate.split_once('/') {
+        if let Ok(encoded) = percent_decode(encoded)
+            && let Ok(der) = openssl::base64::decode_block(&encoded)
+            && is_der_sequence(&der)
+        {
+            return Ok(der);
+        }
+        candidate = encoded;
+    }
+    Err(anyhow!(
+        "no valid base64 encoded ocsp request found in {path}"
+    ))
+}
+
+/// Check if the data is a single DER encoded SEQUENCE
+fn is_der_sequence(data: &[u8]) -> bool {
+    let [0x30, first, ref left @ ..] = *data else {
+        return false;
+    };
+    let (len, left) = if first & 0x80 == 0 {
+        (first as usize, left)
+    } else {
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > size_of::<usize>() || left.len() < n {
+            return false;
+        }
+        let len = left[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, &left[n..])
+    };
+    len == left.len()
+}
+
+fn percent_decode(s: &str) -> anyhow::Result<String> {
+    let b = s.as_bytes();
+    let mut out = Vec::with_capacity(b.len());
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] == b'%' {
+            let hex = s
+                .get(i + 1..i + 3)
+                .ok_or_else(|| anyhow!("incomplete percent encoding"))?;
+            let v = u8::from_str_radix(hex, 16)
+                .map_err(|_| anyhow!("invalid percent encoding %{hex}"))?;
+            out.push(v);
+            i += 3;
+        } else {
+            out.push(b[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| anyhow!("invalid percent decoded string"))
+}
+
+fn write_http_response(
+    stream: &mut TcpStream,
+    status: &str,
+    body: Option<&[u8]>,
+) -> anyhow::Result<()> {
+    let mut header = format!("HTTP/1.0 {status}\r\nConnection: close\r\n");
+    match body {
+        Some(body) => {
+            header.push_str("Content-Type: application/ocsp-response\r\n");
+            header.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+        }
+        None => header.push_str("Content-Length: 0\r\n\r\n"),
+    }
+    stream
+        .write_all(header.as_bytes())
+        .map_err(|e| anyhow!("failed to write http response header: {e:?}"))?;
+    if let Some(body) = body {
+        stream
+            .write_all(body)
+            .map_err(|e| anyhow!("failed to write http response body: {e:?}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn der_sequence() {
+        assert!(is_der_sequence(&[0x30, 0x00]));
+        assert!(is_der_sequence(&[0x30, 0x02, 0x05, 0x00]));
+        assert!(!is_der_sequence(&[0x30, 0x03, 0x05, 0x00]));
+        assert!(!is_der_sequence(&[0x31, 0x00]));
+        let mut long = vec![0x30, 0x81, 0x80];
+        long.resize(3 + 0x80, 0);
+        assert!(is_der_sequence(&long));
+        assert!(!is_der_sequence(&long[..long.len() - 1]));
+    }
+
+    #[test]
+    fn get_target() {
+        let der = [0x30, 0x04, 0x04, 0x02, 0xff, 0xf0];
+        let encoded = openssl::base64::encode_block(&der);
+        // the base64 string contains '/'
+        assert_eq!(encoded, "MAQEAv/w");
+
+        assert_eq!(decode_get_target(&format!("/{encoded}")).unwrap(), der);
+        assert_eq!(decode_get_target(&format!("/ocsp/{encoded}")).unwrap(), der);
+        let url_encoded = encoded.replace('/', "%2F").replace('=', "%3D");
+        assert_eq!(
+            decode_get_target(&format!("/ocsp/ca/{url_encoded}")).unwrap(),
+            der
+        );
+        assert!(decode_get_target("/ocsp/").is_err());
+        assert!(decode_get_target("/ocsp/invalid").is_err());
+    }
+}
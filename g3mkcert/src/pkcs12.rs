@@ -0,0 +1,94 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use openssl::nid::Nid;
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::{PKey, Private};
+use openssl::stack::Stack;
+use openssl::x509::{X509, X509Ref};
+
+use super::{
+    ARG_CA_CERT, ARG_CERT, ARG_KEY, ARG_OUTPUT_PKCS12, ARG_PKCS12_PASSWORD, write_output_file,
+};
+
+pub(super) fn generate_pkcs12(args: ArgMatches) -> anyhow::Result<()> {
+    let cert_file = args
+        .get_one::<PathBuf>(ARG_CERT)
+        .ok_or_else(|| anyhow!("no certificate set"))?;
+    let key_file = args
+        .get_one::<PathBuf>(ARG_KEY)
+        .ok_or_else(|| anyhow!("no private key set"))?;
+
+    let cert_content = std::fs::read_to_string(cert_file)
+        .map_err(|e| anyhow!("failed to read cert file {}: {e:?}", cert_file.display()))?;
+    let cert = X509::from_pem(cert_content.as_bytes())
+        .map_err(|e| anyhow!("invalid cert in file {}: {e}", cert_file.display()))?;
+
+    let key_content = std::fs::read_to_string(key_file)
+        .map_err(|e| anyhow!("failed to read pkey file {}: {e:?}", key_file.display()))?;
+    let key = PKey::private_key_from_pem(key_content.as_bytes())
+        .map_err(|e| anyhow!("invalid pkey in file {}: {e}", key_file.display()))?;
+
+    let ca_certs = match args.get_one::<PathBuf>(ARG_CA_CERT) {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("failed to read ca cert file {}: {e:?}", path.display()))?;
+            X509::stack_from_pem(content.as_bytes())
+                .map_err(|e| anyhow!("invalid ca cert in file {}: {e}", path.display()))?
+        }
+        None => Vec::new(),
+    };
+
+    let output = args
+        .get_one::<PathBuf>(ARG_OUTPUT_PKCS12)
+        .cloned()
+        .unwrap_or_else(|| cert_file.with_extension("p12"));
+    write_pkcs12_file(&args, &cert, &key, &ca_certs, output)
+}
+
+pub(super) fn write_pkcs12_file<P: AsRef<Path>>(
+    args: &ArgMatches,
+    cert: &X509Ref,
+    key: &PKey<Private>,
+    ca_certs: &[X509],
+    path: P,
+) -> anyhow::Result<()> {
+    let password = args
+        .get_one::<String>(ARG_PKCS12_PASSWORD)
+        .map(|s| s.as_str())
+        .unwrap_or_default();
+    let name = cert
+        .subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .next()
+        .and_then(|e| e.data().as_utf8().ok())
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+
+    let mut builder = Pkcs12::builder();
+    builder.name(&name).pkey(key).cert(cert);
+    if !ca_certs.is_empty() {
+        let mut stack =
+            Stack::new().map_err(|e| anyhow!("failed to create certificate stack: {e}"))?;
+        for ca_cert in ca_certs {
+            stack
+                .push(ca_cert.clone())
+                .map_err(|e| anyhow!("failed to push ca certificate to stack: {e}"))?;
+        }
+        builder.ca(stack);
+    }
+    let pkcs12 = builder
+        .build2(password)
+        .map_err(|e| anyhow!("failed to build pkcs12: {e}"))?;
+
+    let content = pkcs12
+        .to_der()
+        .map_err(|e| anyhow!("failed to encode pkcs12: {e}"))?;
+    write_output_file(&content, path, "pkcs12 bundle")
+}
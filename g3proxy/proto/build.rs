@@ -24,6 +24,7 @@ fn main() {
             .file("schema/proc.capnp")
             .file("schema/user_group.capnp")
             .file("schema/resolver.capnp")
+            .file("schema/auditor.capnp")
             .file("schema/escaper.capnp")
             .file("schema/server.capnp")
             .run()
@@ -26,6 +26,10 @@ pub mod resolver_capnp {
     include!(concat!(env!("G3_CAPNP_GENERATE_DIR"), "/resolver_capnp.rs"));
 }
 
+pub mod auditor_capnp {
+    include!(concat!(env!("G3_CAPNP_GENERATE_DIR"), "/auditor_capnp.rs"));
+}
+
 pub mod escaper_capnp {
     include!(concat!(env!("G3_CAPNP_GENERATE_DIR"), "/escaper_capnp.rs"));
 }
@@ -0,0 +1,400 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use arc_swap::ArcSwap;
+use foldhash::fast::FixedState;
+
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+use crate::config::audit::AuditTlsAutoBypassConfig;
+
+const LEARN_TABLE_SHARDS: usize = 16;
+
+#[derive(Clone, Copy)]
+struct LearnedEntry {
+    failures: usize,
+    first_failure: Instant,
+    bypass_expire: Option<Instant>,
+}
+
+type LearnTable = HashMap<String, LearnedEntry, FixedState>;
+type BypassTable = HashMap<String, Instant, FixedState>;
+
+#[derive(Default)]
+pub(crate) struct TlsAutoBypassSnapshot {
+    pub(crate) added: u64,
+    pub(crate) hit: u64,
+}
+
+/// The table of TLS server names which are learned to be not interceptable,
+/// mostly because the client apps pin the upstream certificates.
+///
+/// The learning table is sharded and is only locked on handshake failures, or on successes if
+/// there are pending failure records. The bypassed server names are published to a read-only
+/// table for the fast check path.
+pub(crate) struct TlsAutoBypass {
+    name: NodeName,
+    stat_id: StatId,
+    config: AuditTlsAutoBypassConfig,
+    shard_hasher: FixedState,
+    shard_max_entries: usize,
+    shards: Box<[Mutex<LearnTable>]>,
+    /// count of the entries which have failure records but are not bypassed yet
+    pending: AtomicUsize,
+    bypass: ArcSwap<BypassTable>,
+    added: AtomicU64,
+    hit: AtomicU64,
+}
+
+fn to_lowercase(sni: &str) -> Cow<'_, str> {
+    if sni.bytes().any(|b| b.is_ascii_uppercase()) {
+        Cow::Owned(sni.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(sni)
+    }
+}
+
+impl TlsAutoBypass {
+    pub(super) fn new(name: &NodeName, config: &AuditTlsAutoBypassConfig) -> Self {
+        TlsAutoBypass {
+            name: name.clone(),
+            stat_id: StatId::new_unique(),
+            config: config.clone(),
+            shard_hasher: FixedState::with_seed(1),
+            shard_max_entries: config.max_entries.div_ceil(LEARN_TABLE_SHARDS),
+            shards: (0..LEARN_TABLE_SHARDS)
+                .map(|_| Mutex::new(HashMap::with_hasher(FixedState::with_seed(0))))
+                .collect(),
+            pending: AtomicUsize::new(0),
+            bypass: ArcSwap::from_pointee(HashMap::with_hasher(FixedState::with_seed(0))),
+            added: AtomicU64::new(0),
+            hit: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a new table with the config changed, the learned entries will be kept
+    pub(super) fn reload(&self, config: &AuditTlsAutoBypassConfig) -> Self {
+        let new = TlsAutoBypass::new(&self.name, config);
+        for shard in &self.shards {
+            let old_table = shard.lock().unwrap();
+            for (k, v) in old_table.iter() {
+                let mut new_table = new.shard(k).lock().unwrap();
+                new_table.insert(k.clone(), *v);
+                if v.bypass_expire.is_none() {
+                    new.pending.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        new.bypass.store(self.bypass.load_full());
+        new
+    }
+
+    fn shard(&self, sni: &str) -> &Mutex<LearnTable> {
+        let i = (self.shard_hasher.hash_one(sni) as usize) % LEARN_TABLE_SHARDS;
+        &self.shards[i]
+    }
+
+    /// Update the read-only bypass table, the expired entries will be dropped
+    fn update_bypass<F>(&self, f: F)
+    where
+        F: Fn(&mut BypassTable),
+    {
+        let now = Instant::now();
+        self.bypass.rcu(|old| {
+            let mut new = HashMap::with_hasher(FixedState::with_seed(0));
+            new.extend(
+                old.iter()
+                    .filter(|(_, expire)| **expire > now)
+                    .map(|(k, v)| (k.clone(), *v)),
+            );
+            f(&mut new);
+            new
+        });
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.stat_id
+    }
+
+    #[inline]
+    pub(crate) fn bypass_ttl(&self) -> Duration {
+        self.config.bypass_ttl
+    }
+
+    /// Check if the interception should be bypassed for this server name
+    pub(crate) fn check(&self, sni: &str) -> bool {
+        let bypass = self.bypass.load();
+        if bypass.is_empty() {
+            return false;
+        }
+        let Some(expire) = bypass.get(to_lowercase(sni).as_ref()) else {
+            return false;
+        };
+        if *expire > Instant::now() {
+            self.hit.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Record a client side handshake failure after forging,
+    /// return true if bypass is newly enabled for this server name
+    pub(crate) fn add_failure(&self, sni: &str) -> bool {
+        let now = Instant::now();
+        let sni = sni.to_ascii_lowercase();
+        let mut table = self.shard(&sni).lock().unwrap();
+        if table.len() >= self.shard_max_entries && !table.contains_key(&sni) {
+            let mut removed_pending = 0;
+            let mut removed_bypass = false;
+            table.retain(|_, v| {
+                let keep = match v.bypass_expire {
+                    Some(expire) => expire > now,
+                    None => now.duration_since(v.first_failure) < self.config.failure_window,
+                };
+                if !keep {
+                    if v.bypass_expire.is_none() {
+                        removed_pending += 1;
+                    } else {
+                        removed_bypass = true;
+                    }
+                }
+                keep
+            });
+            self.pending.fetch_sub(removed_pending, Ordering::Relaxed);
+            if removed_bypass {
+                // the expired entries will be dropped
+                self.update_bypass(|_| {});
+            }
+            if table.len() >= self.shard_max_entries {
+                return false;
+            }
+        }
+
+        let entry = table.entry(sni.clone()).or_insert_with(|| {
+            self.pending.fetch_add(1, Ordering::Relaxed);
+            LearnedEntry {
+                failures: 0,
+                first_failure: now,
+                bypass_expire: None,
+            }
+        });
+        if let Some(expire) = entry.bypass_expire {
+            if expire > now {
+                return false;
+            }
+            entry.bypass_expire = None;
+            entry.failures = 0;
+            self.pending.fetch_add(1, Ordering::Relaxed);
+        }
+        if now.duration_since(entry.first_failure) >= self.config.failure_window {
+            entry.failures = 0;
+        }
+        if entry.failures == 0 {
+            entry.first_failure = now;
+        }
+        entry.failures += 1;
+        if entry.failures >= self.config.failure_threshold {
+            let expire = now + self.config.bypass_ttl;
+            entry.bypass_expire = Some(expire);
+            self.pending.fetch_sub(1, Ordering::Relaxed);
+            drop(table);
+            self.update_bypass(|bypass| {
+                bypass.insert(sni.clone(), expire);
+            });
+            self.added.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Clear the failure records for this server name after a successful handshake
+    pub(crate) fn add_success(&self, sni: &str) {
+        if self.pending.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let sni = to_lowercase(sni);
+        let mut table = self.shard(&sni).lock().unwrap();
+        if let Some(entry) = table.get(sni.as_ref())
+            && entry.bypass_expire.is_none()
+        {
+            table.remove(sni.as_ref());
+            self.pending.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Get all server names that are bypassed currently, with the remaining ttl
+    pub(crate) fn bypassed(&self) -> Vec<(String, Duration)> {
+        let now = Instant::now();
+        let bypass = self.bypass.load();
+        bypass
+            .iter()
+            .filter_map(|(k, expire)| {
+                let left = expire.checked_duration_since(now)?;
+                Some((k.clone(), left))
+            })
+            .collect()
+    }
+
+    pub(crate) fn bypassed_count(&self) -> usize {
+        let now = Instant::now();
+        let bypass = self.bypass.load();
+        bypass.values().filter(|expire| **expire > now).count()
+    }
+
+    /// Remove the learned entry for the server name, or all entries if no name is given
+    pub(crate) fn clear(&self, sni: Option<&str>) {
+        match sni {
+            Some(sni) => {
+                let sni = to_lowercase(sni);
+                let mut table = self.shard(&sni).lock().unwrap();
+                if let Some(entry) = table.remove(sni.as_ref())
+                    && entry.bypass_expire.is_none()
+                {
+                    self.pending.fetch_sub(1, Ordering::Relaxed);
+                }
+                drop(table);
+                self.update_bypass(|bypass| {
+                    bypass.remove(sni.as_ref());
+                });
+            }
+            None => {
+                for shard in &self.shards {
+                    let mut table = shard.lock().unwrap();
+                    let pending = table.values().filter(|v| v.bypass_expire.is_none()).count();
+                    table.clear();
+                    self.pending.fetch_sub(pending, Ordering::Relaxed);
+                }
+                self.bypass
+                    .store(HashMap::with_hasher(FixedState::with_seed(0)).into());
+            }
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> TlsAutoBypassSnapshot {
+        TlsAutoBypassSnapshot {
+            added: self.added.load(Ordering::Relaxed),
+            hit: self.hit.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn learn_and_clear() {
+        let config = AuditTlsAutoBypassConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        };
+        let bypass = TlsAutoBypass::new(&NodeName::default(), &config);
+
+        assert!(!bypass.add_failure("www.Example.net"));
+        assert!(!bypass.check("www.example.net"));
+        assert!(bypass.add_failure("www.example.net"));
+        assert!(bypass.check("WWW.example.net"));
+        assert_eq!(bypass.bypassed_count(), 1);
+        assert!(!bypass.add_failure("www.example.net"));
+
+        bypass.add_success("www.example.net");
+        assert!(bypass.check("www.example.net"));
+
+        assert!(!bypass.add_failure("www.example.org"));
+        bypass.add_success("www.example.org");
+        assert!(!bypass.add_failure("www.example.org"));
+
+        bypass.clear(Some("www.example.net"));
+        assert!(!bypass.check("www.example.net"));
+        assert_eq!(bypass.snapshot().added, 1);
+        assert_eq!(bypass.snapshot().hit, 2);
+    }
+
+    #[test]
+    fn pending_count() {
+        let config = AuditTlsAutoBypassConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        };
+        let bypass = TlsAutoBypass::new(&NodeName::default(), &config);
+        assert_eq!(bypass.pending.load(Ordering::Relaxed), 0);
+        bypass.add_success("www.example.net");
+
+        for i in 0..100 {
+            assert!(!bypass.add_failure(&format!("www{i}.example.net")));
+        }
+        assert_eq!(bypass.pending.load(Ordering::Relaxed), 100);
+        assert!(bypass.add_failure("www0.example.net"));
+        assert_eq!(bypass.pending.load(Ordering::Relaxed), 99);
+        bypass.add_success("www1.example.net");
+        bypass.add_success("www0.example.net");
+        assert_eq!(bypass.pending.load(Ordering::Relaxed), 98);
+        assert!(bypass.check("www0.example.net"));
+
+        bypass.clear(Some("www2.example.net"));
+        assert_eq!(bypass.pending.load(Ordering::Relaxed), 97);
+        let new = bypass.reload(&config);
+        assert_eq!(new.pending.load(Ordering::Relaxed), 97);
+        assert!(new.check("www0.example.net"));
+        new.clear(None);
+        assert_eq!(new.pending.load(Ordering::Relaxed), 0);
+        assert!(!new.check("www0.example.net"));
+    }
+
+    #[test]
+    fn max_entries() {
+        let config = AuditTlsAutoBypassConfig {
+            failure_threshold: 2,
+            max_entries: LEARN_TABLE_SHARDS,
+            ..Default::default()
+        };
+        let bypass = TlsAutoBypass::new(&NodeName::default(), &config);
+        for i in 0..100 {
+            bypass.add_failure(&format!("www{i}.example.net"));
+        }
+        let total: usize = bypass.shards.iter().map(|s| s.lock().unwrap().len()).sum();
+        assert!(total <= LEARN_TABLE_SHARDS);
+        assert_eq!(bypass.pending.load(Ordering::Relaxed), total);
+    }
+
+    #[test]
+    fn reload_keep_learned() {
+        let config = AuditTlsAutoBypassConfig {
+            failure_threshold: 1,
+            ..Default::default()
+        };
+        let bypass = TlsAutoBypass::new(&NodeName::default(), &config);
+        assert!(bypass.add_failure("www.example.net"));
+        assert!(bypass.add_failure("www.example.org"));
+
+        let config = AuditTlsAutoBypassConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        };
+        let new = bypass.reload(&config);
+        assert!(new.check("www.example.net"));
+        assert_eq!(new.bypassed_count(), 2);
+
+        new.clear(None);
+        assert!(!new.check("www.example.net"));
+        assert!(new.bypassed().is_empty());
+    }
+}
@@ -17,7 +17,7 @@ use g3_icap_client::respmod::IcapRespmodClient;
 use super::Auditor;
 #[cfg(feature = "quic")]
 use super::StreamDetourClient;
-use crate::config::audit::AuditorConfig;
+use crate::config::audit::{AuditBlockPageConfig, AuditTlsCertErrorAction, AuditorConfig};
 use crate::inspect::tls::TlsInterceptionContext;
 
 pub(crate) struct AuditHandle {
@@ -35,6 +35,8 @@ pub(crate) struct AuditHandle {
     pub(crate) websocket_inspect_policy: ProtocolInspectPolicy,
     pub(crate) smtp_inspect_policy: ProtocolInspectPolicy,
     pub(crate) imap_inspect_policy: ProtocolInspectPolicy,
+    #[cfg(feature = "quic")]
+    pub(crate) quic_inspect_policy: ProtocolInspectPolicy,
 }
 
 impl AuditHandle {
@@ -62,6 +64,8 @@ impl AuditHandle {
             websocket_inspect_policy: auditor.config.websocket_inspect_policy.build(),
             smtp_inspect_policy: auditor.config.smtp_inspect_policy.build(),
             imap_inspect_policy: auditor.config.imap_inspect_policy.build(),
+            #[cfg(feature = "quic")]
+            quic_inspect_policy: auditor.config.quic_inspect_policy.build(),
         }
     }
 
@@ -99,6 +103,16 @@ impl AuditHandle {
         self.tls_interception.clone()
     }
 
+    #[inline]
+    pub(crate) fn tls_cert_error_action(&self) -> AuditTlsCertErrorAction {
+        self.auditor_config.tls_cert_error_action
+    }
+
+    #[inline]
+    pub(crate) fn block_page(&self) -> Option<&AuditBlockPageConfig> {
+        self.auditor_config.block_page.as_deref()
+    }
+
     #[inline]
     pub(crate) fn log_uri_max_chars(&self) -> usize {
         self.auditor_config.log_uri_max_chars
@@ -10,7 +10,8 @@ use anyhow::Context;
 use g3_dpi::ProtocolPortMap;
 use g3_icap_client::IcapServiceClient;
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslTicketKey, RollingTicketer};
+use g3_types::net::{OpensslKeylogCallback, OpensslTicketKey, RollingTicketer};
+use g3_udpdump::StreamDumpKeylog;
 
 use crate::config::audit::AuditorConfig;
 use crate::inspect::tls::TlsInterceptionContext;
@@ -20,11 +21,16 @@ pub use ops::load_all;
 pub(crate) use ops::reload;
 
 mod registry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{
+    foreach as foreach_auditor, get as get_auditor, get_names, get_or_insert_default,
+};
 
 mod handle;
 pub(crate) use handle::AuditHandle;
 
+mod auto_bypass;
+pub(crate) use auto_bypass::{TlsAutoBypass, TlsAutoBypassSnapshot};
+
 #[cfg(feature = "quic")]
 mod detour;
 #[cfg(feature = "quic")]
@@ -37,6 +43,7 @@ pub(crate) struct Auditor {
     server_tcp_portmap: Arc<ProtocolPortMap>,
     client_tcp_portmap: Arc<ProtocolPortMap>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    tls_auto_bypass: Option<Arc<TlsAutoBypass>>,
     icap_reqmod_service: Option<Arc<IcapServiceClient>>,
     icap_respmod_service: Option<Arc<IcapServiceClient>>,
     #[cfg(feature = "quic")]
@@ -53,6 +60,7 @@ impl Auditor {
             server_tcp_portmap,
             client_tcp_portmap,
             tls_rolling_ticketer: None,
+            tls_auto_bypass: None,
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
@@ -72,11 +80,16 @@ impl Auditor {
         } else {
             None
         };
+        let tls_auto_bypass = config
+            .tls_auto_bypass
+            .as_ref()
+            .map(|c| Arc::new(TlsAutoBypass::new(config.name(), c)));
         let mut auditor = Auditor {
             config: Arc::new(config),
             server_tcp_portmap,
             client_tcp_portmap,
             tls_rolling_ticketer,
+            tls_auto_bypass,
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
@@ -99,11 +112,23 @@ impl Auditor {
         } else {
             None
         };
+        let tls_auto_bypass = match (&self.tls_auto_bypass, &config.tls_auto_bypass) {
+            (Some(old), Some(c)) => {
+                if self.config.tls_auto_bypass.as_ref() == Some(c) {
+                    Some(old.clone())
+                } else {
+                    Some(Arc::new(old.reload(c)))
+                }
+            }
+            (None, Some(c)) => Some(Arc::new(TlsAutoBypass::new(config.name(), c))),
+            (_, None) => None,
+        };
         let mut auditor = Auditor {
             config: Arc::new(config),
             server_tcp_portmap,
             client_tcp_portmap,
             tls_rolling_ticketer,
+            tls_auto_bypass,
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
@@ -113,6 +138,11 @@ impl Auditor {
         Ok(Arc::new(auditor))
     }
 
+    #[inline]
+    pub(crate) fn tls_auto_bypass(&self) -> Option<&Arc<TlsAutoBypass>> {
+        self.tls_auto_bypass.as_ref()
+    }
+
     fn set_agent_clients(&mut self) -> anyhow::Result<()> {
         if let Some(c) = self.config.icap_reqmod_service.clone() {
             self.icap_reqmod_service = Some(Arc::new(
@@ -132,6 +162,24 @@ impl Auditor {
         Ok(())
     }
 
+    fn build_tls_keylog(&self) -> anyhow::Result<Option<OpensslKeylogCallback>> {
+        let Some(path) = self
+            .config
+            .tls_stream_dump
+            .as_ref()
+            .and_then(|dump| dump.pcapng.as_ref())
+            .and_then(|pcapng| pcapng.keylog_file())
+        else {
+            return Ok(None);
+        };
+        let keylog = StreamDumpKeylog::open(&path).context(format!(
+            "failed to open tls key log file {}",
+            path.display()
+        ))?;
+        let callback: OpensslKeylogCallback = Arc::new(move |line: &str| keylog.write_line(line));
+        Ok(Some(callback))
+    }
+
     pub(crate) fn build_handle(&self) -> anyhow::Result<Arc<AuditHandle>> {
         let mut handle = AuditHandle::new(self);
 
@@ -144,16 +192,18 @@ impl Auditor {
                 .tls_interception_client
                 .build()
                 .context("failed to build tls client config")?;
+            let keylog = self.build_tls_keylog()?;
             let server_config = self
                 .config
                 .tls_interception_server
-                .build_with_ticketer(self.tls_rolling_ticketer.as_ref())
+                .build_with_ticketer(self.tls_rolling_ticketer.as_ref(), keylog.as_ref())
                 .context("failed to build tls server config")?;
             let ctx = TlsInterceptionContext::new(
                 cert_agent,
                 client_config,
                 server_config,
-                self.config.tls_stream_dump,
+                self.config.tls_stream_dump.clone(),
+                self.tls_auto_bypass.clone(),
             )?;
             handle.set_tls_interception(ctx);
         }
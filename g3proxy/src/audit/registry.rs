@@ -21,7 +21,7 @@ pub(super) fn add(name: NodeName, auditor: Arc<Auditor>) {
     if let Some(_old_group) = ht.insert(name, auditor) {}
 }
 
-pub(super) fn get(name: &NodeName) -> Option<Arc<Auditor>> {
+pub(crate) fn get(name: &NodeName) -> Option<Arc<Auditor>> {
     let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
     ht.get(name).cloned()
 }
@@ -31,6 +31,16 @@ pub(super) fn del(name: &NodeName) {
     if let Some(_old_auditor) = ht.remove(name) {}
 }
 
+pub(crate) fn foreach<F>(mut f: F)
+where
+    F: FnMut(&NodeName, &Arc<Auditor>),
+{
+    let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
+    for (name, auditor) in ht.iter() {
+        f(name, auditor)
+    }
+}
+
 pub(crate) fn get_names() -> HashSet<NodeName> {
     let mut names = HashSet::new();
     let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
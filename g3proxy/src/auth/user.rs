@@ -27,7 +27,9 @@ use super::{
     UserForbiddenStats, UserRequestStats, UserSite, UserSiteDurationRecorder, UserSiteStats,
     UserSites, UserTrafficStats, UserType, UserUpstreamTrafficStats,
 };
+use crate::category::CategoryFilter;
 use crate::config::auth::{UserAuditConfig, UserConfig};
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 
 pub(crate) struct User {
     config: Arc<UserConfig>,
@@ -43,6 +45,7 @@ pub(crate) struct User {
     udp_all_download_speed_limit: Option<Arc<GlobalDatagramLimiter>>,
     ingress_net_filter: Option<Arc<AclNetworkRule>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    dst_category_filter: Option<Arc<CategoryFilter>>,
     resolve_redirection: Option<ResolveRedirection>,
     log_rate_limit: Option<Arc<RateLimiter<GlobalRateLimitState>>>,
     forbid_stats: Arc<Mutex<HashMap<NodeName, Arc<UserForbiddenStats>>>>,
@@ -75,6 +78,14 @@ impl User {
             .map(|builder| Arc::new(builder.build()));
     }
 
+    fn update_dst_category_filter(&mut self) {
+        self.dst_category_filter = self
+            .config
+            .dst_category_filter
+            .as_ref()
+            .map(|config| Arc::new(CategoryFilter::new(config)));
+    }
+
     fn update_resolve_redirection(&mut self) {
         self.resolve_redirection = self
             .config
@@ -149,6 +160,7 @@ impl User {
             udp_all_download_speed_limit,
             ingress_net_filter: None,
             dst_host_filter: None,
+            dst_category_filter: None,
             resolve_redirection: None,
             log_rate_limit,
             forbid_stats: Arc::new(Mutex::new(HashMap::default())),
@@ -160,6 +172,7 @@ impl User {
         };
         user.update_ingress_net_filter();
         user.update_dst_host_filter();
+        user.update_dst_category_filter();
         user.update_resolve_redirection();
         Ok(user)
     }
@@ -307,6 +320,7 @@ impl User {
             udp_all_download_speed_limit,
             ingress_net_filter: None,
             dst_host_filter: None,
+            dst_category_filter: None,
             resolve_redirection: None,
             log_rate_limit,
             forbid_stats: Arc::clone(&self.forbid_stats),
@@ -330,6 +344,16 @@ impl User {
         } else {
             user.dst_host_filter.clone_from(&self.dst_host_filter);
         }
+        if self
+            .config
+            .dst_category_filter
+            .ne(&config.dst_category_filter)
+        {
+            user.update_dst_category_filter();
+        } else {
+            user.dst_category_filter
+                .clone_from(&self.dst_category_filter);
+        }
         user.update_resolve_redirection();
         Ok(user)
     }
@@ -607,6 +631,23 @@ impl User {
         default_action
     }
 
+    fn check_dst_category(
+        &self,
+        upstream: &UpstreamAddr,
+        path: Option<&str>,
+        forbid_stats: &Arc<UserForbiddenStats>,
+    ) -> (Option<NodeName>, AclAction) {
+        if let Some(filter) = &self.dst_category_filter {
+            let (category, action) = filter.check(upstream, path);
+            if action.forbid_early() {
+                forbid_stats.add_dest_denied();
+            }
+            (category, action)
+        } else {
+            (None, AclAction::Permit)
+        }
+    }
+
     fn check_http_user_agent(
         &self,
         headers: &HttpHeaderMap,
@@ -647,6 +688,16 @@ impl User {
         self.config.log_uri_max_chars
     }
 
+    #[inline]
+    pub(crate) fn name(&self) -> &Arc<str> {
+        self.config.name()
+    }
+
+    #[inline]
+    pub(crate) fn http_header_rewrite(&self) -> &HttpHeaderRewriteConfig {
+        &self.config.http_header_rewrite
+    }
+
     #[inline]
     pub(crate) fn tcp_all_upload_speed_limit(&self) -> Option<&Arc<GlobalStreamLimiter>> {
         self.tcp_all_upload_speed_limit.as_ref()
@@ -883,6 +934,16 @@ impl UserContext {
         self.user.check_upstream(upstream, &self.forbid_stats)
     }
 
+    #[inline]
+    pub(crate) fn check_dst_category(
+        &self,
+        upstream: &UpstreamAddr,
+        path: Option<&str>,
+    ) -> (Option<NodeName>, AclAction) {
+        self.user
+            .check_dst_category(upstream, path, &self.forbid_stats)
+    }
+
     #[inline]
     pub(crate) fn check_http_user_agent(&self, headers: &HttpHeaderMap) -> Option<AclAction> {
         self.user.check_http_user_agent(headers, &self.forbid_stats)
@@ -0,0 +1,54 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use g3_types::acl::AclAction;
+use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
+
+use super::Category;
+use crate::config::category::CategoryFilterConfig;
+
+pub(crate) struct CategoryFilter {
+    rules: Vec<(Arc<Category>, AclAction)>,
+    missed_action: AclAction,
+}
+
+impl CategoryFilter {
+    pub(crate) fn new(config: &CategoryFilterConfig) -> Self {
+        let mut rules: Vec<_> = config
+            .rules
+            .iter()
+            .map(|(name, action)| (super::get_or_insert_default(name), *action))
+            .collect();
+        // permit rules are checked first, so they can be used as exceptions
+        rules.sort_by_key(|(_, action)| action.forbid_early());
+        CategoryFilter {
+            rules,
+            missed_action: config.missed_action,
+        }
+    }
+
+    /// Get the first matched category and the action to take.
+    /// The path should be set if the full url is known.
+    pub(crate) fn check(
+        &self,
+        upstream: &UpstreamAddr,
+        path: Option<&str>,
+    ) -> (Option<NodeName>, AclAction) {
+        for (category, action) in &self.rules {
+            if category.is_match(upstream.host(), path) {
+                let stats = category.stats();
+                stats.add_matched();
+                if action.forbid_early() {
+                    stats.add_forbidden();
+                }
+                return (Some(category.name().clone()), *action);
+            }
+        }
+        (None, self.missed_action)
+    }
+}
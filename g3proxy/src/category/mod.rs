@@ -0,0 +1,151 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use log::{info, warn};
+use tokio::task::AbortHandle;
+use tokio::time::Instant;
+
+use g3_types::metrics::NodeName;
+use g3_types::net::Host;
+
+use crate::config::category::{CategoryConfig, CategorySourceConfig};
+
+mod ops;
+pub use ops::load_all;
+
+mod registry;
+pub(crate) use registry::{foreach as foreach_category, get_or_insert_default};
+
+mod table;
+use table::CategoryTable;
+
+mod filter;
+pub(crate) use filter::CategoryFilter;
+
+mod stats;
+pub(crate) use stats::{CategoryStats, CategoryStatsSnapshot};
+
+#[derive(Default)]
+struct CategoryState {
+    config: Option<Arc<CategoryConfig>>,
+    loader: Option<AbortHandle>,
+}
+
+/// A named domain / url list, the object is kept across reloads,
+/// and a deleted category will match nothing
+pub(crate) struct Category {
+    name: NodeName,
+    table: Arc<ArcSwap<CategoryTable>>,
+    stats: Arc<CategoryStats>,
+    state: Mutex<CategoryState>,
+}
+
+async fn sources_modified(sources: &[CategorySourceConfig]) -> Vec<Option<SystemTime>> {
+    let mut modified = Vec::with_capacity(sources.len());
+    for source in sources {
+        let time = tokio::fs::metadata(&source.path)
+            .await
+            .and_then(|m| m.modified())
+            .ok();
+        modified.push(time);
+    }
+    modified
+}
+
+async fn load_table(config: &Arc<CategoryConfig>) -> anyhow::Result<CategoryTable> {
+    let config = config.clone();
+    // the lists may be very large, so load them in a blocking thread
+    tokio::task::spawn_blocking(move || CategoryTable::load(&config.sources))
+        .await
+        .map_err(|e| anyhow!("failed to join category load task: {e}"))?
+}
+
+async fn run_loader(
+    config: Arc<CategoryConfig>,
+    table: Arc<ArcSwap<CategoryTable>>,
+    mut modified: Vec<Option<SystemTime>>,
+) {
+    let name = config.name();
+    let mut interval = tokio::time::interval_at(
+        Instant::now() + config.check_interval,
+        config.check_interval,
+    );
+    loop {
+        interval.tick().await;
+
+        let new_modified = sources_modified(&config.sources).await;
+        if new_modified.iter().all(|v| v.is_some()) && new_modified == modified {
+            continue;
+        }
+        match load_table(&config).await {
+            Ok(new_table) => {
+                info!("reloaded category {name} with {} entries", new_table.len());
+                table.store(Arc::new(new_table));
+                modified = new_modified;
+            }
+            Err(e) => {
+                warn!("failed to reload category {name}, keep using the old one: {e:?}");
+            }
+        }
+    }
+}
+
+impl Category {
+    fn new(name: &NodeName) -> Self {
+        Category {
+            name: name.clone(),
+            table: Arc::new(ArcSwap::new(Arc::new(CategoryTable::default()))),
+            stats: Arc::new(CategoryStats::new(name)),
+            state: Mutex::new(CategoryState::default()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    pub(crate) fn stats(&self) -> &Arc<CategoryStats> {
+        &self.stats
+    }
+
+    fn is_match(&self, host: &Host, path: Option<&str>) -> bool {
+        self.table.load().is_match(host, path)
+    }
+
+    fn config(&self) -> Option<Arc<CategoryConfig>> {
+        self.state.lock().unwrap().config.clone()
+    }
+
+    async fn update(&self, config: Arc<CategoryConfig>) -> anyhow::Result<()> {
+        let modified = sources_modified(&config.sources).await;
+        let table = load_table(&config).await?;
+        info!("loaded category {} with {} entries", self.name, table.len());
+        self.table.store(Arc::new(table));
+
+        let loader = tokio::spawn(run_loader(config.clone(), self.table.clone(), modified));
+        let mut state = self.state.lock().unwrap();
+        if let Some(old) = state.loader.replace(loader.abort_handle()) {
+            old.abort();
+        }
+        state.config = Some(config);
+        Ok(())
+    }
+
+    fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(old) = state.loader.take() {
+            old.abort();
+        }
+        state.config = None;
+        self.table.store(Arc::new(CategoryTable::default()));
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+
+use anyhow::Context;
+use log::debug;
+use tokio::sync::Mutex;
+
+use g3_types::metrics::NodeName;
+
+use super::registry;
+
+static CATEGORY_OPS_LOCK: Mutex<()> = Mutex::const_new(());
+
+pub async fn load_all() -> anyhow::Result<()> {
+    let _guard = CATEGORY_OPS_LOCK.lock().await;
+
+    let mut new_names = HashSet::<NodeName>::new();
+
+    let all_config = crate::config::category::get_all();
+    for config in all_config {
+        let name = config.name();
+        new_names.insert(name.clone());
+        let category = registry::get_or_insert_default(name);
+        if category.config().as_ref() == Some(&config) {
+            continue;
+        }
+        debug!("loading category {name}");
+        category
+            .update(config.clone())
+            .await
+            .context(format!("failed to load category {name}"))?;
+        debug!("category {name} load OK");
+    }
+
+    for name in &registry::get_names() {
+        if !new_names.contains(name)
+            && let Some(category) = registry::get(name)
+        {
+            debug!("clearing deleted category {name}");
+            category.clear();
+        }
+    }
+
+    Ok(())
+}
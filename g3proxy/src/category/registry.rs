@@ -0,0 +1,47 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use foldhash::fast::FixedState;
+
+use g3_types::metrics::NodeName;
+
+use super::Category;
+
+static RUNTIME_CATEGORY_REGISTRY: Mutex<HashMap<NodeName, Arc<Category>, FixedState>> =
+    Mutex::new(HashMap::with_hasher(FixedState::with_seed(0)));
+
+pub(super) fn get(name: &NodeName) -> Option<Arc<Category>> {
+    let ht = RUNTIME_CATEGORY_REGISTRY.lock().unwrap();
+    ht.get(name).cloned()
+}
+
+pub(super) fn get_names() -> HashSet<NodeName> {
+    let mut names = HashSet::new();
+    let ht = RUNTIME_CATEGORY_REGISTRY.lock().unwrap();
+    for key in ht.keys() {
+        names.insert(key.clone());
+    }
+    names
+}
+
+pub(crate) fn foreach<F>(mut f: F)
+where
+    F: FnMut(&NodeName, &Arc<Category>),
+{
+    let ht = RUNTIME_CATEGORY_REGISTRY.lock().unwrap();
+    for (name, category) in ht.iter() {
+        f(name, category)
+    }
+}
+
+pub(crate) fn get_or_insert_default(name: &NodeName) -> Arc<Category> {
+    let mut ht = RUNTIME_CATEGORY_REGISTRY.lock().unwrap();
+    ht.entry(name.clone())
+        .or_insert_with(|| Arc::new(Category::new(name)))
+        .clone()
+}
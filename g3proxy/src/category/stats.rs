@@ -0,0 +1,58 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+#[derive(Default)]
+pub(crate) struct CategoryStatsSnapshot {
+    pub(crate) matched: u64,
+    pub(crate) forbidden: u64,
+}
+
+pub(crate) struct CategoryStats {
+    name: NodeName,
+    id: StatId,
+    matched: AtomicU64,
+    forbidden: AtomicU64,
+}
+
+impl CategoryStats {
+    pub(super) fn new(name: &NodeName) -> Self {
+        CategoryStats {
+            name: name.clone(),
+            id: StatId::new_unique(),
+            matched: AtomicU64::new(0),
+            forbidden: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    pub(super) fn add_matched(&self) {
+        self.matched.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_forbidden(&self) {
+        self.forbidden.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> CategoryStatsSnapshot {
+        CategoryStatsSnapshot {
+            matched: self.matched.load(Ordering::Relaxed),
+            forbidden: self.forbidden.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+
+use anyhow::{Context, anyhow};
+use foldhash::fast::FixedState;
+
+use g3_types::net::Host;
+
+use crate::config::category::{CategoryListFormat, CategorySourceConfig};
+
+/// the names that are commonly seen in hosts files but should never be blocked
+const HOSTS_IGNORED_NAMES: &[&str] = &[
+    "localhost",
+    "localhost.localdomain",
+    "local",
+    "broadcasthost",
+    "ip6-localhost",
+    "ip6-loopback",
+    "ip6-localnet",
+    "ip6-mcastprefix",
+    "ip6-allnodes",
+    "ip6-allrouters",
+    "ip6-allhosts",
+    "0.0.0.0",
+];
+
+pub(super) struct CategoryTable {
+    domains: HashSet<Box<str>, FixedState>,
+    /// path prefixes keyed by the host
+    url_prefixes: HashMap<Box<str>, Vec<Box<str>>, FixedState>,
+}
+
+impl Default for CategoryTable {
+    fn default() -> Self {
+        CategoryTable {
+            domains: HashSet::with_hasher(FixedState::with_seed(0)),
+            url_prefixes: HashMap::with_hasher(FixedState::with_seed(0)),
+        }
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let line = line.trim();
+    if line.starts_with('#') || line.starts_with('!') {
+        return "";
+    }
+    match line.find([' ', '\t']) {
+        Some(p) => match line[p..].find('#') {
+            Some(c) => line[..p + c].trim_end(),
+            None => line,
+        },
+        None => line,
+    }
+}
+
+fn normalize_domain(s: &str) -> Option<String> {
+    let s = s
+        .strip_prefix("*.")
+        .or_else(|| s.strip_prefix('.'))
+        .unwrap_or(s);
+    let s = s.strip_suffix('.').unwrap_or(s);
+    if s.is_empty() || s.contains(['/', ' ', '\t', '*']) {
+        return None;
+    }
+    Some(s.to_ascii_lowercase())
+}
+
+impl CategoryTable {
+    pub(super) fn load(sources: &[CategorySourceConfig]) -> anyhow::Result<Self> {
+        let mut table = CategoryTable::default();
+        for source in sources {
+            let file = File::open(&source.path)
+                .map_err(|e| anyhow!("failed to open file {}: {e}", source.path.display()))?;
+            table
+                .add_list(BufReader::new(file), source.format)
+                .context(format!("failed to load file {}", source.path.display()))?;
+        }
+        Ok(table)
+    }
+
+    fn add_list<R: BufRead>(
+        &mut self,
+        reader: R,
+        format: CategoryListFormat,
+    ) -> anyhow::Result<()> {
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| anyhow!("failed to read line {}: {e}", i + 1))?;
+            let line = strip_comment(&line);
+            if line.is_empty() {
+                continue;
+            }
+            // invalid lines are skipped, as the lists are usually maintained by others
+            match format {
+                CategoryListFormat::Hosts => self.add_hosts_line(line),
+                CategoryListFormat::Domain => self.add_domain(line),
+                CategoryListFormat::UrlPrefix => self.add_url_prefix(line),
+            }
+        }
+        Ok(())
+    }
+
+    fn add_hosts_line(&mut self, line: &str) {
+        let mut iter = line.split_whitespace();
+        let Some(ip) = iter.next() else {
+            return;
+        };
+        if ip.parse::<IpAddr>().is_err() {
+            return;
+        }
+        for name in iter {
+            if HOSTS_IGNORED_NAMES.contains(&name) {
+                continue;
+            }
+            self.add_domain(name);
+        }
+    }
+
+    fn add_domain(&mut self, s: &str) {
+        if let Some(domain) = normalize_domain(s) {
+            self.domains.insert(domain.into_boxed_str());
+        }
+    }
+
+    fn add_url_prefix(&mut self, s: &str) {
+        let s = match s.find("://") {
+            Some(p) => &s[p + 3..],
+            None => s,
+        };
+        let (host, path) = match s.find(['/', '?']) {
+            Some(p) => (&s[..p], &s[p..]),
+            None => (s, "/"),
+        };
+        let host = if host.starts_with('[') {
+            match host.find(']') {
+                Some(p) => &host[1..p],
+                None => return,
+            }
+        } else {
+            host.split_once(':').map(|(h, _)| h).unwrap_or(host)
+        };
+        let Some(host) = normalize_domain(host) else {
+            return;
+        };
+        let path = if path.starts_with('?') {
+            format!("/{path}")
+        } else {
+            path.to_string()
+        };
+        self.url_prefixes
+            .entry(host.into_boxed_str())
+            .or_default()
+            .push(path.into_boxed_str());
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.domains.len() + self.url_prefixes.values().map(|v| v.len()).sum::<usize>()
+    }
+
+    fn match_domain(&self, domain: &str) -> bool {
+        let mut d = domain;
+        loop {
+            if self.domains.contains(d) {
+                return true;
+            }
+            match d.find('.') {
+                Some(p) => d = &d[p + 1..],
+                None => return false,
+            }
+        }
+    }
+
+    fn match_url(&self, host: &str, path: Option<&str>) -> bool {
+        let Some(prefixes) = self.url_prefixes.get(host) else {
+            return false;
+        };
+        let path = path.unwrap_or_default();
+        prefixes
+            .iter()
+            .any(|p| p.as_ref() == "/" || path.starts_with(p.as_ref()))
+    }
+
+    /// Check if the host, or the url if path is given, is in this category
+    pub(super) fn is_match(&self, host: &Host, path: Option<&str>) -> bool {
+        match host {
+            Host::Domain(d) => {
+                let d = if d.bytes().any(|b| b.is_ascii_uppercase()) {
+                    Cow::Owned(d.to_ascii_lowercase())
+                } else {
+                    Cow::Borrowed(d.as_ref())
+                };
+                let d = d.strip_suffix('.').unwrap_or(&d);
+                self.match_domain(d) || self.match_url(d, path)
+            }
+            Host::Ip(ip) => {
+                let s = ip.to_string();
+                self.domains.contains(s.as_str()) || self.match_url(&s, path)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn domain(s: &str) -> Host {
+        Host::Domain(Arc::from(s))
+    }
+
+    #[test]
+    fn hosts_list() {
+        let content = "# comment\n\
+                       127.0.0.1 localhost\n\
+                       0.0.0.0 ads.example.net tracker.example.org # inline\n\
+                       invalid line\n";
+        let mut table = CategoryTable::default();
+        table
+            .add_list(content.as_bytes(), CategoryListFormat::Hosts)
+            .unwrap();
+        assert_eq!(table.len(), 2);
+        assert!(table.is_match(&domain("ads.example.net"), None));
+        assert!(table.is_match(&domain("a.tracker.example.org"), None));
+        assert!(!table.is_match(&domain("localhost"), None));
+        assert!(!table.is_match(&domain("example.net"), None));
+    }
+
+    #[test]
+    fn domain_list() {
+        let content = "! adblock style comment\n*.Example.COM\n.example.org\n192.168.1.1\n";
+        let mut table = CategoryTable::default();
+        table
+            .add_list(content.as_bytes(), CategoryListFormat::Domain)
+            .unwrap();
+        assert!(table.is_match(&domain("www.example.com"), None));
+        assert!(table.is_match(&domain("EXAMPLE.com."), None));
+        assert!(table.is_match(&domain("example.org"), None));
+        assert!(!table.is_match(&domain("example.net"), None));
+        assert!(table.is_match(&Host::Ip("192.168.1.1".parse().unwrap()), None));
+    }
+
+    #[test]
+    fn url_prefix_list() {
+        let content = "https://www.example.com/bad/\nexample.net:8080\nexample.org/a?b=c\n";
+        let mut table = CategoryTable::default();
+        table
+            .add_list(content.as_bytes(), CategoryListFormat::UrlPrefix)
+            .unwrap();
+        assert!(table.is_match(&domain("www.example.com"), Some("/bad/x.html")));
+        assert!(!table.is_match(&domain("www.example.com"), Some("/good/x.html")));
+        assert!(!table.is_match(&domain("www.example.com"), None));
+        assert!(!table.is_match(&domain("a.www.example.com"), Some("/bad/")));
+        assert!(table.is_match(&domain("example.net"), None));
+        assert!(table.is_match(&domain("example.org"), Some("/a?b=c&d=e")));
+        assert!(!table.is_match(&domain("example.org"), Some("/a")));
+    }
+}
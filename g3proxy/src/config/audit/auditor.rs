@@ -10,6 +10,8 @@ use rand::distr::Bernoulli;
 use yaml_rust::{Yaml, yaml};
 
 use g3_cert_agent::CertAgentConfig;
+#[cfg(feature = "quic")]
+use g3_dpi::ProtocolInspectAction;
 use g3_dpi::{
     H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig,
     ProtocolInspectPolicyBuilder, ProtocolInspectionConfig, ProtocolPortMap,
@@ -26,6 +28,7 @@ use g3_yaml::YamlDocPosition;
 
 #[cfg(feature = "quic")]
 use super::AuditStreamDetourConfig;
+use super::{AuditBlockPageConfig, AuditTlsAutoBypassConfig, AuditTlsCertErrorAction};
 
 #[derive(Clone)]
 pub(crate) struct AuditorConfig {
@@ -39,6 +42,9 @@ pub(crate) struct AuditorConfig {
     pub(crate) tls_interception_client: OpensslInterceptionClientConfigBuilder,
     pub(crate) tls_interception_server: OpensslInterceptionServerConfigBuilder,
     pub(crate) tls_stream_dump: Option<StreamDumpConfig>,
+    pub(crate) tls_auto_bypass: Option<AuditTlsAutoBypassConfig>,
+    pub(crate) tls_cert_error_action: AuditTlsCertErrorAction,
+    pub(crate) block_page: Option<Arc<AuditBlockPageConfig>>,
     pub(crate) log_uri_max_chars: usize,
     pub(crate) h1_interception: H1InterceptionConfig,
     pub(crate) h2_inspect_policy: ProtocolInspectPolicyBuilder,
@@ -48,6 +54,8 @@ pub(crate) struct AuditorConfig {
     pub(crate) smtp_interception: SmtpInterceptionConfig,
     pub(crate) imap_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) imap_interception: ImapInterceptionConfig,
+    #[cfg(feature = "quic")]
+    pub(crate) quic_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) icap_reqmod_service: Option<Arc<IcapServiceConfig>>,
     pub(crate) icap_respmod_service: Option<Arc<IcapServiceConfig>>,
     #[cfg(feature = "quic")]
@@ -76,6 +84,9 @@ impl AuditorConfig {
             tls_interception_client: Default::default(),
             tls_interception_server: Default::default(),
             tls_stream_dump: None,
+            tls_auto_bypass: None,
+            tls_cert_error_action: AuditTlsCertErrorAction::default(),
+            block_page: None,
             log_uri_max_chars: 1024,
             h1_interception: Default::default(),
             h2_inspect_policy: Default::default(),
@@ -85,6 +96,8 @@ impl AuditorConfig {
             smtp_interception: Default::default(),
             imap_inspect_policy: Default::default(),
             imap_interception: Default::default(),
+            #[cfg(feature = "quic")]
+            quic_inspect_policy: ProtocolInspectPolicyBuilder::new(ProtocolInspectAction::Bypass),
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
@@ -111,6 +124,17 @@ impl AuditorConfig {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        #[cfg(feature = "quic")]
+        for action in self.quic_inspect_policy.iter_actions() {
+            match action {
+                ProtocolInspectAction::Bypass | ProtocolInspectAction::Block => {}
+                _ => {
+                    return Err(anyhow!(
+                        "action {action} is not supported in quic_inspect_policy, only bypass and block are supported"
+                    ));
+                }
+            }
+        }
 
         Ok(())
     }
@@ -175,6 +199,23 @@ impl AuditorConfig {
                 self.tls_stream_dump = Some(dump);
                 Ok(())
             }
+            "tls_auto_bypass" => {
+                self.tls_auto_bypass = AuditTlsAutoBypassConfig::parse(v)
+                    .context(format!("invalid tls auto bypass config value for key {k}"))?;
+                Ok(())
+            }
+            "tls_cert_error_action" => {
+                self.tls_cert_error_action = AuditTlsCertErrorAction::parse(v)
+                    .context(format!("invalid tls cert error action value for key {k}"))?;
+                Ok(())
+            }
+            "block_page" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let block_page = AuditBlockPageConfig::parse(v, lookup_dir)
+                    .context(format!("invalid block page config value for key {k}"))?;
+                self.block_page = Some(Arc::new(block_page));
+                Ok(())
+            }
             "log_uri_max_chars" | "uri_log_max_chars" => {
                 self.log_uri_max_chars = g3_yaml::value::as_usize(v)
                     .context(format!("invalid usize value for key {k}"))?;
@@ -221,6 +262,12 @@ impl AuditorConfig {
                     .context(format!("invalid imap interception value for key {k}"))?;
                 Ok(())
             }
+            #[cfg(feature = "quic")]
+            "quic_inspect_policy" => {
+                self.quic_inspect_policy = g3_yaml::value::as_protocol_inspect_policy_builder(v)
+                    .context(format!("invalid protocol inspect policy value for key {k}"))?;
+                Ok(())
+            }
             "icap_reqmod_service" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 let service = IcapServiceConfig::parse_reqmod_service_yaml(v, Some(lookup_dir))
@@ -0,0 +1,78 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct AuditTlsAutoBypassConfig {
+    pub(crate) failure_threshold: usize,
+    pub(crate) failure_window: Duration,
+    pub(crate) bypass_ttl: Duration,
+    pub(crate) max_entries: usize,
+}
+
+impl Default for AuditTlsAutoBypassConfig {
+    fn default() -> Self {
+        AuditTlsAutoBypassConfig {
+            failure_threshold: 3,
+            failure_window: Duration::from_secs(300),
+            bypass_ttl: Duration::from_secs(3600),
+            max_entries: 4096,
+        }
+    }
+}
+
+impl AuditTlsAutoBypassConfig {
+    pub(super) fn parse(value: &Yaml) -> anyhow::Result<Option<Self>> {
+        let mut config = AuditTlsAutoBypassConfig::default();
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "failure_threshold" | "threshold" => {
+                        config.failure_threshold = g3_yaml::value::as_usize(v)?;
+                        Ok(())
+                    }
+                    "failure_window" => {
+                        config.failure_window = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "bypass_ttl" | "ttl" => {
+                        config.bypass_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_entries" => {
+                        config.max_entries = g3_yaml::value::as_usize(v)?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(enable) => {
+                if !enable {
+                    return Ok(None);
+                }
+            }
+            _ => {
+                return Err(anyhow!(
+                    "invalid yaml value type for audit tls auto bypass config"
+                ));
+            }
+        }
+
+        if config.failure_threshold == 0 {
+            return Err(anyhow!("failure threshold should not be 0"));
+        }
+        if config.bypass_ttl.is_zero() {
+            return Err(anyhow!("bypass ttl should not be 0"));
+        }
+        Ok(Some(config))
+    }
+}
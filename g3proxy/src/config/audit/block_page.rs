@@ -0,0 +1,113 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum AuditTlsCertErrorAction {
+    /// Fail the interception task
+    #[default]
+    Fail,
+    /// Forge a certificate signed by the untrusted CA, so the client will show its own warning
+    Mirror,
+    /// Finish the client handshake and reply a block page
+    BlockPage,
+}
+
+impl FromStr for AuditTlsCertErrorAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match g3_yaml::key::normalize(s).as_str() {
+            "fail" | "error" => Ok(AuditTlsCertErrorAction::Fail),
+            "mirror" | "untrusted" => Ok(AuditTlsCertErrorAction::Mirror),
+            "block_page" | "block" => Ok(AuditTlsCertErrorAction::BlockPage),
+            _ => Err(()),
+        }
+    }
+}
+
+impl AuditTlsCertErrorAction {
+    pub(super) fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::String(s) = value {
+            AuditTlsCertErrorAction::from_str(s)
+                .map_err(|_| anyhow!("invalid tls cert error action {s}"))
+        } else {
+            Err(anyhow!(
+                "yaml value type for tls cert error action should be 'string'"
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum AuditBlockPageFormat {
+    #[default]
+    Html,
+    Json,
+}
+
+impl FromStr for AuditBlockPageFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(AuditBlockPageFormat::Html),
+            "json" => Ok(AuditBlockPageFormat::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AuditBlockPageConfig {
+    pub(crate) format: AuditBlockPageFormat,
+    pub(crate) template: Option<Arc<str>>,
+}
+
+impl AuditBlockPageConfig {
+    pub(super) fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let mut config = AuditBlockPageConfig::default();
+
+        match value {
+            Yaml::String(s) => {
+                config.format = AuditBlockPageFormat::from_str(s)
+                    .map_err(|_| anyhow!("invalid block page format {s}"))?;
+            }
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "format" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        config.format = AuditBlockPageFormat::from_str(&s)
+                            .map_err(|_| anyhow!("invalid block page format {s}"))?;
+                        Ok(())
+                    }
+                    "template" | "template_file" => {
+                        let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                            .context(format!("invalid file path value for key {k}"))?;
+                        let contents = std::fs::read_to_string(&path).map_err(|e| {
+                            anyhow!("failed to read template file {}: {e}", path.display())
+                        })?;
+                        config.template = Some(Arc::from(contents));
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "invalid yaml value type for audit block page config"
+                ));
+            }
+        }
+
+        Ok(config)
+    }
+}
@@ -16,6 +16,12 @@ pub(crate) use registry::{clear, get_all};
 mod auditor;
 pub(crate) use auditor::AuditorConfig;
 
+mod auto_bypass;
+pub(crate) use auto_bypass::AuditTlsAutoBypassConfig;
+
+mod block_page;
+pub(crate) use block_page::{AuditBlockPageConfig, AuditBlockPageFormat, AuditTlsCertErrorAction};
+
 #[cfg(feature = "quic")]
 mod detour;
 #[cfg(feature = "quic")]
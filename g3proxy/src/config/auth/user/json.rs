@@ -12,6 +12,8 @@ use serde_json::{Map, Value};
 use g3_types::metrics::NodeName;
 
 use super::{PasswordToken, UserConfig, UserSiteConfig};
+use crate::config::category::CategoryFilterConfig;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 
 impl UserConfig {
     pub(crate) fn parse_json(map: &Map<String, Value>) -> anyhow::Result<Self> {
@@ -180,6 +182,18 @@ impl UserConfig {
                 self.dst_host_filter = Some(builder);
                 Ok(())
             }
+            "dst_category_filter" => {
+                let filter = CategoryFilterConfig::parse_json(v)
+                    .context(format!("invalid category filter value for key {k}"))?;
+                self.dst_category_filter = Some(filter);
+                Ok(())
+            }
+            "http_header_rewrite" => {
+                self.http_header_rewrite = HttpHeaderRewriteConfig::parse_json(v).context(
+                    format!("invalid http header rewrite config value for key {k}"),
+                )?;
+                Ok(())
+            }
             "dst_port_filter" => {
                 let filter = g3_json::value::acl::as_exact_port_rule(v)
                     .context(format!("invalid dst port acl rule value for key {k}"))?;
@@ -227,6 +241,11 @@ impl UserConfig {
                     .context(format!("invalid bool value for key {k}"))?;
                 Ok(())
             }
+            "http_cache_bypass" | "bypass_http_cache" => {
+                self.http_cache_bypass = g3_json::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
             "explicit_sites" => {
                 if let Value::Array(seq) = v {
                     for (i, v) in seq.iter().enumerate() {
@@ -25,6 +25,8 @@ use g3_types::net::{
 };
 use g3_types::resolve::{ResolveRedirectionBuilder, ResolveStrategy};
 
+use crate::config::category::CategoryFilterConfig;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 use crate::escape::EgressPathSelection;
 
 mod token;
@@ -71,12 +73,15 @@ pub(crate) struct UserConfig {
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) proxy_request_filter: Option<AclProxyRequestRule>,
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
+    pub(crate) dst_category_filter: Option<CategoryFilterConfig>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) http_user_agent_filter: Option<AclUserAgentRule>,
     pub(crate) resolve_strategy: Option<ResolveStrategy>,
     pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
     pub(crate) task_idle_max_count: Option<usize>,
     pub(crate) socks_use_udp_associate: bool,
+    pub(crate) http_cache_bypass: bool,
+    pub(crate) http_header_rewrite: HttpHeaderRewriteConfig,
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
     pub(crate) explicit_sites: BTreeMap<NodeName, Arc<UserSiteConfig>>,
 }
@@ -111,12 +116,15 @@ impl Default for UserConfig {
             ingress_net_filter: None,
             proxy_request_filter: None,
             dst_host_filter: None,
+            dst_category_filter: None,
             dst_port_filter: None,
             http_user_agent_filter: None,
             resolve_strategy: None,
             resolve_redirection: None,
             task_idle_max_count: None,
             socks_use_udp_associate: false,
+            http_cache_bypass: false,
+            http_header_rewrite: HttpHeaderRewriteConfig::default(),
             egress_path_selection: None,
             explicit_sites: BTreeMap::new(),
         }
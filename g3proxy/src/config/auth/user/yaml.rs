@@ -12,6 +12,8 @@ use yaml_rust::{Yaml, yaml};
 use g3_yaml::YamlDocPosition;
 
 use super::{PasswordToken, UserConfig, UserSiteConfig};
+use crate::config::category::CategoryFilterConfig;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 
 impl UserConfig {
     pub(crate) fn parse_yaml(
@@ -186,6 +188,18 @@ impl UserConfig {
                 self.dst_host_filter = Some(builder);
                 Ok(())
             }
+            "dst_category_filter" => {
+                let filter = CategoryFilterConfig::parse_yaml(v)
+                    .context(format!("invalid category filter value for key {k}"))?;
+                self.dst_category_filter = Some(filter);
+                Ok(())
+            }
+            "http_header_rewrite" => {
+                self.http_header_rewrite = HttpHeaderRewriteConfig::parse_yaml(v).context(
+                    format!("invalid http header rewrite config value for key {k}"),
+                )?;
+                Ok(())
+            }
             "dst_port_filter" => {
                 let filter = g3_yaml::value::acl::as_exact_port_rule(v)
                     .context(format!("invalid dst port acl rule value for key {k}"))?;
@@ -233,6 +247,11 @@ impl UserConfig {
                     .context(format!("invalid bool value for key {k}"))?;
                 Ok(())
             }
+            "http_cache_bypass" | "bypass_http_cache" => {
+                self.http_cache_bypass = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
             "explicit_sites" => {
                 if let Yaml::Array(seq) = v {
                     for (i, v) in seq.iter().enumerate() {
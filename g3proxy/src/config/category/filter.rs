@@ -0,0 +1,127 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use serde_json::Value;
+use yaml_rust::Yaml;
+
+use g3_types::acl::AclAction;
+use g3_types::metrics::NodeName;
+
+/// Allow or deny by the names of the categories
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct CategoryFilterConfig {
+    pub(crate) rules: Vec<(NodeName, AclAction)>,
+    pub(crate) missed_action: AclAction,
+}
+
+impl Default for CategoryFilterConfig {
+    fn default() -> Self {
+        CategoryFilterConfig {
+            rules: Vec::new(),
+            missed_action: AclAction::Permit,
+        }
+    }
+}
+
+impl CategoryFilterConfig {
+    fn add_rule(&mut self, name: NodeName, action: AclAction) {
+        self.rules.retain(|(n, _)| *n != name);
+        self.rules.push((name, action));
+    }
+
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let mut config = CategoryFilterConfig::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "default" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        config.missed_action = AclAction::from_str(&s)
+                            .map_err(|_| anyhow!("invalid AclAction value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => {
+                        let action = AclAction::from_str(k)
+                            .map_err(|_| anyhow!("the key {k} is not a valid AclAction"))?;
+                        if let Yaml::Array(seq) = v {
+                            for (i, v) in seq.iter().enumerate() {
+                                let name = g3_yaml::value::as_metric_node_name(v)
+                                    .context(format!("invalid category name value for {k}#{i}"))?;
+                                config.add_rule(name, action);
+                            }
+                        } else {
+                            let name = g3_yaml::value::as_metric_node_name(v)
+                                .context(format!("invalid category name value for key {k}"))?;
+                            config.add_rule(name, action);
+                        }
+                        Ok(())
+                    }
+                })?;
+            }
+            Yaml::Array(seq) => {
+                for (i, v) in seq.iter().enumerate() {
+                    let name = g3_yaml::value::as_metric_node_name(v)
+                        .context(format!("invalid category name value for element #{i}"))?;
+                    config.add_rule(name, AclAction::Forbid);
+                }
+            }
+            _ => {
+                let name = g3_yaml::value::as_metric_node_name(value)
+                    .context("invalid category name value")?;
+                config.add_rule(name, AclAction::Forbid);
+            }
+        }
+        Ok(config)
+    }
+
+    pub(crate) fn parse_json(value: &Value) -> anyhow::Result<Self> {
+        let mut config = CategoryFilterConfig::default();
+        match value {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    match g3_json::key::normalize(k).as_str() {
+                        "default" => {
+                            let s = g3_json::value::as_string(v)?;
+                            config.missed_action = AclAction::from_str(&s)
+                                .map_err(|_| anyhow!("invalid AclAction value for key {k}"))?;
+                        }
+                        _ => {
+                            let action = AclAction::from_str(k)
+                                .map_err(|_| anyhow!("the key {k} is not a valid AclAction"))?;
+                            if let Value::Array(seq) = v {
+                                for (i, v) in seq.iter().enumerate() {
+                                    let name = g3_json::value::as_metric_node_name(v).context(
+                                        format!("invalid category name value for {k}#{i}"),
+                                    )?;
+                                    config.add_rule(name, action);
+                                }
+                            } else {
+                                let name = g3_json::value::as_metric_node_name(v)
+                                    .context(format!("invalid category name value for key {k}"))?;
+                                config.add_rule(name, action);
+                            }
+                        }
+                    }
+                }
+            }
+            Value::Array(seq) => {
+                for (i, v) in seq.iter().enumerate() {
+                    let name = g3_json::value::as_metric_node_name(v)
+                        .context(format!("invalid category name value for element #{i}"))?;
+                    config.add_rule(name, AclAction::Forbid);
+                }
+            }
+            _ => {
+                let name = g3_json::value::as_metric_node_name(value)
+                    .context("invalid category name value")?;
+                config.add_rule(name, AclAction::Forbid);
+            }
+        }
+        Ok(config)
+    }
+}
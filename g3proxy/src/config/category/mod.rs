@@ -0,0 +1,168 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::NodeName;
+use g3_yaml::{HybridParser, YamlDocPosition};
+
+mod registry;
+pub(crate) use registry::{clear, get_all};
+
+mod filter;
+pub(crate) use filter::CategoryFilterConfig;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CategoryListFormat {
+    /// hosts file, the domains after the ip address will be used
+    Hosts,
+    /// one domain per line, all the child domains will also match
+    Domain,
+    /// one url prefix per line, with optional scheme
+    UrlPrefix,
+}
+
+impl FromStr for CategoryListFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match g3_yaml::key::normalize(s).as_str() {
+            "hosts" => Ok(CategoryListFormat::Hosts),
+            "domain" | "domains" | "domain_list" => Ok(CategoryListFormat::Domain),
+            "url" | "url_prefix" | "url_list" => Ok(CategoryListFormat::UrlPrefix),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct CategorySourceConfig {
+    pub(crate) path: PathBuf,
+    pub(crate) format: CategoryListFormat,
+}
+
+impl CategorySourceConfig {
+    fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut path = None;
+                let mut format = CategoryListFormat::Domain;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "path" | "file" => {
+                        let p = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                            .context(format!("invalid file path value for key {k}"))?;
+                        path = Some(p);
+                        Ok(())
+                    }
+                    "format" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        format = CategoryListFormat::from_str(&s)
+                            .map_err(|_| anyhow!("invalid category list format {s}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                let Some(path) = path else {
+                    return Err(anyhow!("no file path set"));
+                };
+                Ok(CategorySourceConfig { path, format })
+            }
+            Yaml::String(_) => {
+                let path = g3_yaml::value::as_file_path(value, lookup_dir, false)?;
+                Ok(CategorySourceConfig {
+                    path,
+                    format: CategoryListFormat::Domain,
+                })
+            }
+            _ => Err(anyhow!("invalid yaml value type for category source")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct CategoryConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) sources: Vec<CategorySourceConfig>,
+    pub(crate) check_interval: Duration,
+}
+
+impl CategoryConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        CategoryConfig {
+            name: NodeName::default(),
+            position,
+            sources: Vec::new(),
+            check_interval: Duration::from_secs(60),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn parse(&mut self, map: &yaml::Hash) -> anyhow::Result<()> {
+        g3_yaml::foreach_kv(map, |k, v| self.set(k, v))?;
+        self.check()
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.sources.is_empty() {
+            return Err(anyhow!("no source list file set"));
+        }
+        if self.check_interval.is_zero() {
+            return Err(anyhow!("check interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "name" => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "source" | "sources" | "file" | "files" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                if let Yaml::Array(seq) = v {
+                    for (i, v) in seq.iter().enumerate() {
+                        let source = CategorySourceConfig::parse(v, lookup_dir)
+                            .context(format!("invalid category source value for {k}#{i}"))?;
+                        self.sources.push(source);
+                    }
+                } else {
+                    let source = CategorySourceConfig::parse(v, lookup_dir)
+                        .context(format!("invalid category source value for key {k}"))?;
+                    self.sources.push(source);
+                }
+                Ok(())
+            }
+            "check_interval" | "reload_interval" => {
+                self.check_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+}
+
+pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    parser.foreach_map(v, |map, position| {
+        let mut category = CategoryConfig::new(position);
+        category.parse(map)?;
+        registry::add(category)?;
+        Ok(())
+    })
+}
@@ -0,0 +1,43 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use foldhash::fast::FixedState;
+
+use super::CategoryConfig;
+
+static INITIAL_CATEGORY_CONFIG_REGISTRY: Mutex<HashMap<String, Arc<CategoryConfig>, FixedState>> =
+    Mutex::new(HashMap::with_hasher(FixedState::with_seed(0)));
+
+pub(crate) fn clear() {
+    let mut ht = INITIAL_CATEGORY_CONFIG_REGISTRY.lock().unwrap();
+    ht.clear();
+}
+
+pub(super) fn add(category: CategoryConfig) -> anyhow::Result<()> {
+    let name = category.name().to_string();
+    let category = Arc::new(category);
+    let mut ht = INITIAL_CATEGORY_CONFIG_REGISTRY.lock().unwrap();
+    if let Some(old) = ht.insert(name, category) {
+        Err(anyhow!(
+            "category with the same name {} is already existed",
+            old.name()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn get_all() -> Vec<Arc<CategoryConfig>> {
+    let mut vec = Vec::new();
+    let ht = INITIAL_CATEGORY_CONFIG_REGISTRY.lock().unwrap();
+    for v in ht.values() {
+        vec.push(Arc::clone(v));
+    }
+    vec
+}
@@ -27,6 +27,7 @@ pub(crate) mod proxy_http;
 pub(crate) mod proxy_https;
 pub(crate) mod proxy_socks5;
 pub(crate) mod proxy_socks5s;
+pub(crate) mod proxy_ssh;
 pub(crate) mod route_client;
 pub(crate) mod route_failover;
 pub(crate) mod route_geoip;
@@ -99,6 +100,7 @@ pub(crate) enum AnyEscaperConfig {
     ProxyHttps(proxy_https::ProxyHttpsEscaperConfig),
     ProxySocks5(proxy_socks5::ProxySocks5EscaperConfig),
     ProxySocks5s(proxy_socks5s::ProxySocks5sEscaperConfig),
+    ProxySsh(proxy_ssh::ProxySshEscaperConfig),
     RouteFailover(route_failover::RouteFailoverEscaperConfig),
     RouteResolved(route_resolved::RouteResolvedEscaperConfig),
     RouteGeoIp(route_geoip::RouteGeoIpEscaperConfig),
@@ -191,6 +193,10 @@ fn load_escaper(
             let config = proxy_socks5s::ProxySocks5sEscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::ProxySocks5s(config))
         }
+        "proxy_ssh" | "proxyssh" => {
+            let config = proxy_ssh::ProxySshEscaperConfig::parse(map, position)?;
+            Ok(AnyEscaperConfig::ProxySsh(config))
+        }
         "proxy_float" | "proxyfloat" | "proxy_dynamic" | "proxydynamic" => {
             let config = proxy_float::ProxyFloatEscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::ProxyFloat(config))
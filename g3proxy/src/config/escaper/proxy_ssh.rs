@@ -0,0 +1,485 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use ascii::AsciiString;
+use russh::keys::ssh_key::{Fingerprint, PrivateKey};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::auth::{Password, Username};
+use g3_types::metrics::{MetricTagMap, NodeName};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "illumos",
+    target_os = "solaris"
+))]
+use g3_types::net::Interface;
+use g3_types::net::{HappyEyeballsConfig, Host, TcpKeepAliveConfig, TcpMiscSockOpts, UpstreamAddr};
+use g3_types::resolve::{QueryStrategy, ResolveStrategy};
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
+
+const ESCAPER_CONFIG_TYPE: &str = "ProxySsh";
+
+const DEFAULT_SESSION_POOL_SIZE: usize = 4;
+const DEFAULT_SESSION_MAX_CHANNELS: usize = 64;
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct ProxySshEscaperConfig {
+    pub(crate) name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) shared_logger: Option<AsciiString>,
+    pub(crate) proxy_addr: UpstreamAddr,
+    pub(crate) proxy_username: Username,
+    pub(crate) proxy_password: Option<Password>,
+    pub(crate) proxy_private_key: Option<Arc<PrivateKey>>,
+    pub(crate) host_key_fingerprints: Vec<Fingerprint>,
+    pub(crate) known_hosts_file: Option<PathBuf>,
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "illumos",
+        target_os = "solaris"
+    ))]
+    pub(crate) bind_interface: Option<Interface>,
+    pub(crate) bind_v4: Option<Ipv4Addr>,
+    pub(crate) bind_v6: Option<Ipv6Addr>,
+    pub(crate) no_ipv4: bool,
+    pub(crate) no_ipv6: bool,
+    pub(crate) resolver: NodeName,
+    pub(crate) resolve_strategy: ResolveStrategy,
+    pub(crate) general: GeneralEscaperConfig,
+    pub(crate) happy_eyeballs: HappyEyeballsConfig,
+    pub(crate) tcp_keepalive: TcpKeepAliveConfig,
+    pub(crate) tcp_misc_opts: TcpMiscSockOpts,
+    pub(crate) peer_negotiation_timeout: Duration,
+    pub(crate) session_pool_size: usize,
+    pub(crate) session_max_channels: usize,
+    pub(crate) session_keepalive_interval: Duration,
+    pub(crate) session_keepalive_max: usize,
+    pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
+}
+
+impl ProxySshEscaperConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        ProxySshEscaperConfig {
+            name: NodeName::default(),
+            position,
+            shared_logger: None,
+            proxy_addr: UpstreamAddr::empty(),
+            proxy_username: Username::empty(),
+            proxy_password: None,
+            proxy_private_key: None,
+            host_key_fingerprints: Vec::new(),
+            known_hosts_file: None,
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "illumos",
+                target_os = "solaris"
+            ))]
+            bind_interface: None,
+            bind_v4: None,
+            bind_v6: None,
+            no_ipv4: false,
+            no_ipv6: false,
+            resolver: NodeName::default(),
+            resolve_strategy: Default::default(),
+            general: Default::default(),
+            happy_eyeballs: Default::default(),
+            tcp_keepalive: TcpKeepAliveConfig::default_enabled(),
+            tcp_misc_opts: Default::default(),
+            peer_negotiation_timeout: Duration::from_secs(10),
+            session_pool_size: DEFAULT_SESSION_POOL_SIZE,
+            session_max_channels: DEFAULT_SESSION_MAX_CHANNELS,
+            session_keepalive_interval: Duration::from_secs(30),
+            session_keepalive_max: 3,
+            extra_metrics_tags: None,
+        }
+    }
+
+    pub(super) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut config = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| config.set(k, v))?;
+
+        config.check()?;
+        Ok(config)
+    }
+
+    fn set_private_key(&mut self, v: &Yaml) -> anyhow::Result<()> {
+        let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+        let (path, passphrase) = match v {
+            Yaml::Hash(map) => {
+                let mut path = None;
+                let mut passphrase = None;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "path" | "file" => {
+                        let p = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                            .context(format!("invalid file path value for key {k}"))?;
+                        path = Some(p);
+                        Ok(())
+                    }
+                    "passphrase" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        passphrase = Some(s);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                let path = path.ok_or_else(|| anyhow!("no private key file path set"))?;
+                (path, passphrase)
+            }
+            _ => {
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context("invalid file path value")?;
+                (path, None)
+            }
+        };
+        let key = russh::keys::load_secret_key(&path, passphrase.as_deref()).map_err(|e| {
+            anyhow!(
+                "failed to load ssh private key from file {}: {e}",
+                path.display()
+            )
+        })?;
+        self.proxy_private_key = Some(Arc::new(key));
+        Ok(())
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_ESCAPER_TYPE => Ok(()),
+            super::CONFIG_KEY_ESCAPER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "shared_logger" => {
+                let name = g3_yaml::value::as_ascii(v)?;
+                self.shared_logger = Some(name);
+                Ok(())
+            }
+            "extra_metrics_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                self.extra_metrics_tags = Some(Arc::new(tags));
+                Ok(())
+            }
+            "proxy_addr" => {
+                self.proxy_addr = g3_yaml::value::as_upstream_addr(v, 22)
+                    .context(format!("invalid upstream address value for key {k}"))?;
+                Ok(())
+            }
+            "proxy_username" | "proxy_user" => {
+                self.proxy_username = g3_yaml::value::as_username(v)
+                    .context(format!("invalid username value for key {k}"))?;
+                Ok(())
+            }
+            "proxy_password" | "proxy_passwd" => {
+                let password = g3_yaml::value::as_password(v)
+                    .context(format!("invalid password value for key {k}"))?;
+                self.proxy_password = Some(password);
+                Ok(())
+            }
+            "proxy_private_key" => self
+                .set_private_key(v)
+                .context(format!("invalid ssh private key value for key {k}")),
+            "host_key_fingerprint" | "host_key_fingerprints" => {
+                self.host_key_fingerprints = g3_yaml::value::as_list(v, |v| {
+                    let s = g3_yaml::value::as_string(v)?;
+                    Fingerprint::from_str(&s)
+                        .map_err(|e| anyhow!("invalid ssh key fingerprint {s}: {e}"))
+                })
+                .context(format!(
+                    "invalid ssh host key fingerprint value for key {k}"
+                ))?;
+                Ok(())
+            }
+            "known_hosts" | "known_hosts_file" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                self.known_hosts_file = Some(path);
+                Ok(())
+            }
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "illumos",
+                target_os = "solaris"
+            ))]
+            "bind_interface" => {
+                let interface = g3_yaml::value::as_interface(v)
+                    .context(format!("invalid interface name value for key {k}"))?;
+                self.bind_interface = Some(interface);
+                Ok(())
+            }
+            "bind_ipv4" => {
+                let ip4 = g3_yaml::value::as_ipv4addr(v)?;
+                self.bind_v4 = Some(ip4);
+                Ok(())
+            }
+            "bind_ipv6" => {
+                let ip6 = g3_yaml::value::as_ipv6addr(v)?;
+                self.bind_v6 = Some(ip6);
+                Ok(())
+            }
+            "resolver" => {
+                self.resolver = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "resolve_strategy" => {
+                self.resolve_strategy = g3_yaml::value::as_resolve_strategy(v)?;
+                Ok(())
+            }
+            "tcp_sock_speed_limit" => {
+                self.general.tcp_sock_speed_limit = g3_yaml::value::as_tcp_sock_speed_limit(v)
+                    .context(format!("invalid tcp socket speed limit value for key {k}"))?;
+                Ok(())
+            }
+            "tcp_keepalive" => {
+                self.tcp_keepalive = g3_yaml::value::as_tcp_keepalive_config(v)
+                    .context(format!("invalid tcp keepalive config value for key {k}"))?;
+                Ok(())
+            }
+            "tcp_misc_opts" => {
+                self.tcp_misc_opts = g3_yaml::value::as_tcp_misc_sock_opts(v)
+                    .context(format!("invalid tcp misc sock opts value for key {k}"))?;
+                Ok(())
+            }
+            "no_ipv4" => {
+                self.no_ipv4 = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "no_ipv6" => {
+                self.no_ipv6 = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "tcp_connect" => {
+                self.general.tcp_connect = g3_yaml::value::as_tcp_connect_config(v)
+                    .context(format!("invalid tcp connect value for key {k}"))?;
+                Ok(())
+            }
+            "happy_eyeballs" => {
+                self.happy_eyeballs = g3_yaml::value::as_happy_eyeballs_config(v)
+                    .context(format!("invalid happy eyeballs config value for key {k}"))?;
+                Ok(())
+            }
+            "peer_negotiation_timeout" => {
+                self.peer_negotiation_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "session_pool_size" => {
+                self.session_pool_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "session_max_channels" => {
+                self.session_max_channels = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "session_keepalive_interval" => {
+                self.session_keepalive_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "session_keepalive_max" => {
+                self.session_keepalive_max = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.proxy_addr.is_empty() {
+            return Err(anyhow!("proxy addr is not set"));
+        }
+        if self.proxy_username.is_empty() {
+            return Err(anyhow!("proxy username is not set"));
+        }
+        if self.proxy_password.is_none() && self.proxy_private_key.is_none() {
+            return Err(anyhow!(
+                "neither proxy password nor proxy private key is set"
+            ));
+        }
+        if self.host_key_fingerprints.is_empty() && self.known_hosts_file.is_none() {
+            return Err(anyhow!(
+                "either host key fingerprint or known hosts file should be set"
+            ));
+        }
+        if self.session_pool_size == 0 {
+            self.session_pool_size = DEFAULT_SESSION_POOL_SIZE;
+        }
+        if self.session_max_channels == 0 {
+            self.session_max_channels = DEFAULT_SESSION_MAX_CHANNELS;
+        }
+        if self.no_ipv4 && self.no_ipv6 {
+            return Err(anyhow!("both ipv4 and ipv6 are disabled"));
+        }
+
+        match self.proxy_addr.host() {
+            Host::Domain(_) => {
+                if self.resolver.is_empty() {
+                    return Err(anyhow!("resolver is not set"));
+                }
+                self.resolve_strategy
+                    .update_query_strategy(self.no_ipv4, self.no_ipv6)
+                    .context("found incompatible resolver strategy")?;
+                if !self.no_ipv4 && !self.no_ipv6 {
+                    match self.resolve_strategy.query {
+                        QueryStrategy::Ipv4Only => self.no_ipv6 = true,
+                        QueryStrategy::Ipv6Only => self.no_ipv4 = true,
+                        _ => {}
+                    }
+                }
+            }
+            Host::Ip(IpAddr::V4(_)) => {
+                if self.no_ipv4 {
+                    return Err(anyhow!("ipv4 is disable but the proxy addr is also ipv4"));
+                }
+                self.no_ipv6 = true;
+            }
+            Host::Ip(IpAddr::V6(_)) => {
+                if self.no_ipv6 {
+                    return Err(anyhow!("ipv6 is disable but the proxy addr is also ipv6"));
+                }
+                self.no_ipv4 = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl EscaperConfig for ProxySshEscaperConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &str {
+        ESCAPER_CONFIG_TYPE
+    }
+
+    fn resolver(&self) -> &NodeName {
+        &self.resolver
+    }
+
+    fn diff_action(&self, new: &AnyEscaperConfig) -> EscaperConfigDiffAction {
+        let AnyEscaperConfig::ProxySsh(new) = new else {
+            return EscaperConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return EscaperConfigDiffAction::NoAction;
+        }
+
+        EscaperConfigDiffAction::Reload
+    }
+
+    fn shared_logger(&self) -> Option<&str> {
+        self.shared_logger.as_ref().map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    const FINGERPRINT: &str = "SHA256:LXEWQrcmsEQBYnyp+6wy9chTD7GQPMTbAiWHF5IaSIE";
+
+    fn parse(s: &str) -> anyhow::Result<ProxySshEscaperConfig> {
+        let doc = YamlLoader::load_from_str(s).unwrap();
+        let Yaml::Hash(map) = &doc[0] else {
+            panic!("invalid yaml doc");
+        };
+        ProxySshEscaperConfig::parse(map, None)
+    }
+
+    #[test]
+    fn parse_ok() {
+        let config = parse(&format!(
+            r#"
+                name: ssh
+                type: proxy_ssh
+                proxy_addr: 192.0.2.1
+                proxy_username: root
+                proxy_password: secret
+                host_key_fingerprint: "{FINGERPRINT}"
+                session_pool_size: 0
+                session_max_channels: 8
+            "#
+        ))
+        .unwrap();
+        assert_eq!(config.proxy_addr.port(), 22);
+        assert_eq!(config.host_key_fingerprints.len(), 1);
+        assert_eq!(config.session_pool_size, DEFAULT_SESSION_POOL_SIZE);
+        assert_eq!(config.session_max_channels, 8);
+        assert!(config.no_ipv6);
+    }
+
+    #[test]
+    fn reject_missing_host_key() {
+        let r = parse(
+            r#"
+                name: ssh
+                proxy_addr: 192.0.2.1:2222
+                proxy_username: root
+                proxy_password: secret
+            "#,
+        );
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn reject_invalid() {
+        // no auth method
+        assert!(
+            parse(&format!(
+                "name: ssh\nproxy_addr: 192.0.2.1\nproxy_username: root\nhost_key_fingerprint: \"{FINGERPRINT}\"\n"
+            ))
+            .is_err()
+        );
+        // invalid fingerprint
+        assert!(
+            parse(
+                "name: ssh\nproxy_addr: 192.0.2.1\nproxy_username: root\nproxy_password: secret\nhost_key_fingerprint: abc\n"
+            )
+            .is_err()
+        );
+        // domain proxy addr without resolver
+        assert!(
+            parse(&format!(
+                "name: ssh\nproxy_addr: ssh.example.net\nproxy_username: root\nproxy_password: secret\nhost_key_fingerprint: \"{FINGERPRINT}\"\n"
+            ))
+            .is_err()
+        );
+    }
+}
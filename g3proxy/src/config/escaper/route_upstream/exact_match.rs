@@ -38,6 +38,14 @@ impl ExactMatchBuilder {
         set.extend(self.ipaddr.keys().cloned());
     }
 
+    pub(crate) fn iter_domain(&self) -> impl Iterator<Item = (&NodeName, &BTreeSet<Arc<str>>)> {
+        self.domain.iter()
+    }
+
+    pub(crate) fn iter_ipaddr(&self) -> impl Iterator<Item = (&NodeName, &BTreeSet<IpAddr>)> {
+        self.ipaddr.iter()
+    }
+
     pub(super) fn set_by_yaml(&mut self, value: &Yaml) -> anyhow::Result<()> {
         match value {
             Yaml::Hash(map) => g3_yaml::foreach_kv(map, |k, v| {
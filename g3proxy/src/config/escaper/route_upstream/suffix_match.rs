@@ -30,6 +30,10 @@ impl SuffixMatchBuilder {
         set.extend(self.inner.keys().cloned())
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&NodeName, &BTreeSet<String>)> {
+        self.inner.iter()
+    }
+
     pub(super) fn set_by_yaml(&mut self, value: &Yaml) -> anyhow::Result<()> {
         match value {
             Yaml::Hash(map) => g3_yaml::foreach_kv(map, |k, v| {
@@ -0,0 +1,139 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpCacheDiskConfig {
+    pub(crate) path: PathBuf,
+    pub(crate) size: u64,
+    pub(crate) max_object_size: u64,
+}
+
+impl HttpCacheDiskConfig {
+    fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let mut config = HttpCacheDiskConfig {
+            path: PathBuf::new(),
+            size: 1 << 30,             // 1GiB
+            max_object_size: 64 << 20, // 64MiB
+        };
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "path" | "dir" | "directory" => {
+                        config.path = g3_yaml::value::as_dir_path(v, lookup_dir, true)
+                            .context(format!("invalid directory path value for key {k}"))?;
+                        Ok(())
+                    }
+                    "size" | "max_size" => {
+                        config.size = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_object_size" => {
+                        config.max_object_size = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::String(_) => {
+                config.path = g3_yaml::value::as_dir_path(value, lookup_dir, true)
+                    .context("invalid directory path value")?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "invalid yaml value type for http cache disk config"
+                ));
+            }
+        }
+
+        if config.path.as_os_str().is_empty() {
+            return Err(anyhow!("no disk cache path set"));
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpCacheConfig {
+    pub(crate) memory_size: usize,
+    pub(crate) memory_max_object_size: usize,
+    pub(crate) disk: Option<HttpCacheDiskConfig>,
+    /// the max freshness lifetime when calculated from Last-Modified
+    pub(crate) heuristic_max_age: Duration,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        HttpCacheConfig {
+            memory_size: 64 << 20,           // 64MiB
+            memory_max_object_size: 4 << 20, // 4MiB
+            disk: None,
+            heuristic_max_age: Duration::from_secs(86400),
+        }
+    }
+}
+
+impl HttpCacheConfig {
+    pub(crate) fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let mut config = HttpCacheConfig::default();
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| config.set(k, v, lookup_dir))?;
+            }
+            Yaml::Boolean(true) => {}
+            _ => {
+                return Err(anyhow!("invalid yaml value type for http cache config"));
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml, lookup_dir: &Path) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "memory_size" | "memory" => {
+                self.memory_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "memory_max_object_size" | "max_object_size" => {
+                self.memory_max_object_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "disk" => {
+                let disk = HttpCacheDiskConfig::parse(v, lookup_dir)
+                    .context(format!("invalid http cache disk config value for key {k}"))?;
+                self.disk = Some(disk);
+                Ok(())
+            }
+            "heuristic_max_age" => {
+                self.heuristic_max_age = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    /// the max size of the response body that may be stored
+    pub(crate) fn max_object_size(&self) -> usize {
+        match &self.disk {
+            Some(disk) => usize::try_from(disk.max_object_size)
+                .unwrap_or(usize::MAX)
+                .max(self.memory_max_object_size),
+            None => self.memory_max_object_size,
+        }
+    }
+}
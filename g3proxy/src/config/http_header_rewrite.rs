@@ -0,0 +1,422 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use http::{HeaderName, header};
+use regex::Regex;
+use serde_json::Value;
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum HttpHeaderTemplatePart {
+    Literal(String),
+    Username,
+    ClientIp,
+    EgressIp,
+    TaskId,
+}
+
+/// A header value with `${name}` variables
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpHeaderValueTemplate {
+    parts: Vec<HttpHeaderTemplatePart>,
+}
+
+impl HttpHeaderValueTemplate {
+    #[inline]
+    pub(crate) fn parts(&self) -> &[HttpHeaderTemplatePart] {
+        &self.parts
+    }
+}
+
+impl FromStr for HttpHeaderValueTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut left = s;
+        while let Some(p) = left.find("${") {
+            if p > 0 {
+                parts.push(HttpHeaderTemplatePart::Literal(left[..p].to_string()));
+            }
+            let var = &left[p + 2..];
+            let Some(end) = var.find('}') else {
+                return Err(anyhow!("no closing '}}' found for variable at offset {p}"));
+            };
+            let part = match var[..end].trim() {
+                "username" | "user" => HttpHeaderTemplatePart::Username,
+                "client_ip" => HttpHeaderTemplatePart::ClientIp,
+                "egress_ip" | "outgoing_ip" => HttpHeaderTemplatePart::EgressIp,
+                "task_id" => HttpHeaderTemplatePart::TaskId,
+                name => return Err(anyhow!("unsupported variable {name}")),
+            };
+            parts.push(part);
+            left = &var[end + 1..];
+        }
+        if !left.is_empty() {
+            parts.push(HttpHeaderTemplatePart::Literal(left.to_string()));
+        }
+        Ok(HttpHeaderValueTemplate { parts })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum HttpHeaderRewriteAction {
+    /// append a new value
+    Add(HttpHeaderValueTemplate),
+    /// replace all existing values
+    Set(HttpHeaderValueTemplate),
+    Remove,
+    /// regex replace in each of the existing values
+    Replace(Regex, String),
+}
+
+impl PartialEq for HttpHeaderRewriteAction {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HttpHeaderRewriteAction::Add(a), HttpHeaderRewriteAction::Add(b)) => a == b,
+            (HttpHeaderRewriteAction::Set(a), HttpHeaderRewriteAction::Set(b)) => a == b,
+            (HttpHeaderRewriteAction::Remove, HttpHeaderRewriteAction::Remove) => true,
+            (
+                HttpHeaderRewriteAction::Replace(r1, s1),
+                HttpHeaderRewriteAction::Replace(r2, s2),
+            ) => r1.as_str() == r2.as_str() && s1 == s2,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for HttpHeaderRewriteAction {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpHeaderRewriteRule {
+    pub(crate) name: HeaderName,
+    /// the name as written in config, used when adding new headers
+    pub(crate) original_name: String,
+    pub(crate) action: HttpHeaderRewriteAction,
+}
+
+/// The headers that control the message framing or the connection, which should never be rewritten
+const PROTECTED_HEADERS: &[HeaderName] = &[
+    header::CONTENT_LENGTH,
+    header::TRANSFER_ENCODING,
+    header::CONNECTION,
+    header::UPGRADE,
+    header::TE,
+    header::TRAILER,
+    header::HOST,
+    HeaderName::from_static("keep-alive"),
+    HeaderName::from_static("proxy-connection"),
+];
+
+#[derive(Default)]
+struct HttpHeaderRewriteRuleBuilder {
+    action: String,
+    name: String,
+    value: Option<String>,
+    regex: Option<Regex>,
+}
+
+impl HttpHeaderRewriteRuleBuilder {
+    fn build(self) -> anyhow::Result<HttpHeaderRewriteRule> {
+        if self.name.is_empty() {
+            return Err(anyhow!("no header name set"));
+        }
+        let name = HeaderName::from_str(&self.name)
+            .map_err(|e| anyhow!("invalid http header name {}: {e}", self.name))?;
+        if PROTECTED_HEADERS.contains(&name) {
+            return Err(anyhow!("rewrite of http header {name} is not allowed"));
+        }
+
+        let action = match self.action.as_str() {
+            "add" | "append" => {
+                let value = self.value.ok_or_else(|| anyhow!("no value set"))?;
+                let template = HttpHeaderValueTemplate::from_str(&value)
+                    .context(format!("invalid header value template {value}"))?;
+                HttpHeaderRewriteAction::Add(template)
+            }
+            "set" | "insert" => {
+                let value = self.value.ok_or_else(|| anyhow!("no value set"))?;
+                let template = HttpHeaderValueTemplate::from_str(&value)
+                    .context(format!("invalid header value template {value}"))?;
+                HttpHeaderRewriteAction::Set(template)
+            }
+            "remove" | "delete" => HttpHeaderRewriteAction::Remove,
+            "replace" => {
+                let regex = self.regex.ok_or_else(|| anyhow!("no regex set"))?;
+                HttpHeaderRewriteAction::Replace(regex, self.value.unwrap_or_default())
+            }
+            "" => return Err(anyhow!("no action set")),
+            s => return Err(anyhow!("unsupported action {s}")),
+        };
+
+        Ok(HttpHeaderRewriteRule {
+            name,
+            original_name: self.name,
+            action,
+        })
+    }
+}
+
+impl HttpHeaderRewriteRule {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for http header rewrite rule should be 'map'"
+            ));
+        };
+
+        let mut builder = HttpHeaderRewriteRuleBuilder::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "action" => {
+                let action = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                builder.action = action.to_lowercase();
+                Ok(())
+            }
+            "name" | "header" => {
+                builder.name = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            "value" | "replacement" => {
+                let value = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                builder.value = Some(value);
+                Ok(())
+            }
+            "regex" | "pattern" => {
+                let regex = g3_yaml::value::as_regex(v)
+                    .context(format!("invalid regex value for key {k}"))?;
+                builder.regex = Some(regex);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        builder.build()
+    }
+
+    fn parse_json(value: &Value) -> anyhow::Result<Self> {
+        let Value::Object(map) = value else {
+            return Err(anyhow!(
+                "json value type for http header rewrite rule should be 'map'"
+            ));
+        };
+
+        let mut builder = HttpHeaderRewriteRuleBuilder::default();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "action" => {
+                    let action = g3_json::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    builder.action = action.to_lowercase();
+                }
+                "name" | "header" => {
+                    builder.name = g3_json::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                }
+                "value" | "replacement" => {
+                    let value = g3_json::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    builder.value = Some(value);
+                }
+                "regex" | "pattern" => {
+                    let regex = g3_json::value::as_regex(v)
+                        .context(format!("invalid regex value for key {k}"))?;
+                    builder.regex = Some(regex);
+                }
+                _ => return Err(anyhow!("invalid key {k}")),
+            }
+        }
+        builder.build()
+    }
+}
+
+/// Header rewrite rules for the forwarded request and the returned response
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct HttpHeaderRewriteConfig {
+    pub(crate) request: Vec<HttpHeaderRewriteRule>,
+    pub(crate) response: Vec<HttpHeaderRewriteRule>,
+}
+
+impl HttpHeaderRewriteConfig {
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.request.is_empty() && self.response.is_empty()
+    }
+
+    fn parse_yaml_rules(value: &Yaml) -> anyhow::Result<Vec<HttpHeaderRewriteRule>> {
+        match value {
+            Yaml::Array(seq) => {
+                let mut rules = Vec::with_capacity(seq.len());
+                for (i, v) in seq.iter().enumerate() {
+                    let rule = HttpHeaderRewriteRule::parse_yaml(v)
+                        .context(format!("invalid http header rewrite rule #{i}"))?;
+                    rules.push(rule);
+                }
+                Ok(rules)
+            }
+            Yaml::Hash(_) => {
+                let rule = HttpHeaderRewriteRule::parse_yaml(value)?;
+                Ok(vec![rule])
+            }
+            _ => Err(anyhow!("invalid yaml value type")),
+        }
+    }
+
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for http header rewrite config should be 'map'"
+            ));
+        };
+
+        let mut config = HttpHeaderRewriteConfig::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "request" | "req" => {
+                config.request = Self::parse_yaml_rules(v)
+                    .context(format!("invalid http header rewrite rules for key {k}"))?;
+                Ok(())
+            }
+            "response" | "rsp" => {
+                config.response = Self::parse_yaml_rules(v)
+                    .context(format!("invalid http header rewrite rules for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        Ok(config)
+    }
+
+    fn parse_json_rules(value: &Value) -> anyhow::Result<Vec<HttpHeaderRewriteRule>> {
+        match value {
+            Value::Array(seq) => {
+                let mut rules = Vec::with_capacity(seq.len());
+                for (i, v) in seq.iter().enumerate() {
+                    let rule = HttpHeaderRewriteRule::parse_json(v)
+                        .context(format!("invalid http header rewrite rule #{i}"))?;
+                    rules.push(rule);
+                }
+                Ok(rules)
+            }
+            Value::Object(_) => {
+                let rule = HttpHeaderRewriteRule::parse_json(value)?;
+                Ok(vec![rule])
+            }
+            _ => Err(anyhow!("invalid json value type")),
+        }
+    }
+
+    pub(crate) fn parse_json(value: &Value) -> anyhow::Result<Self> {
+        let Value::Object(map) = value else {
+            return Err(anyhow!(
+                "json value type for http header rewrite config should be 'map'"
+            ));
+        };
+
+        let mut config = HttpHeaderRewriteConfig::default();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "request" | "req" => {
+                    config.request = Self::parse_json_rules(v)
+                        .context(format!("invalid http header rewrite rules for key {k}"))?;
+                }
+                "response" | "rsp" => {
+                    config.response = Self::parse_json_rules(v)
+                        .context(format!("invalid http header rewrite rules for key {k}"))?;
+                }
+                _ => return Err(anyhow!("invalid key {k}")),
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use serde_json::json;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_yaml_rules() {
+        let value = yaml_doc!(
+            r#"
+                request:
+                  - action: set
+                    name: X-Client-IP
+                    value: "${client_ip}"
+                  - action: remove
+                    name: x-debug
+                response:
+                  action: replace
+                  name: Server
+                  regex: "^(.*)$"
+                  replacement: "g3 $1"
+            "#
+        );
+        let config = HttpHeaderRewriteConfig::parse_yaml(&value).unwrap();
+        assert_eq!(config.request.len(), 2);
+        assert_eq!(config.request[0].original_name, "X-Client-IP");
+        assert_eq!(
+            config.request[0].action,
+            HttpHeaderRewriteAction::Set(HttpHeaderValueTemplate {
+                parts: vec![HttpHeaderTemplatePart::ClientIp]
+            })
+        );
+        assert_eq!(config.request[1].action, HttpHeaderRewriteAction::Remove);
+        assert_eq!(config.response.len(), 1);
+        assert!(matches!(
+            config.response[0].action,
+            HttpHeaderRewriteAction::Replace(_, _)
+        ));
+    }
+
+    #[test]
+    fn reject_protected_headers() {
+        for name in [
+            "Content-Length",
+            "transfer-encoding",
+            "Connection",
+            "Upgrade",
+            "TE",
+            "Trailer",
+            "Keep-Alive",
+            "Proxy-Connection",
+            "Host",
+        ] {
+            for action in ["add", "set", "remove"] {
+                let doc = format!("request:\n  action: {action}\n  name: {name}\n  value: abc\n");
+                let value = YamlLoader::load_from_str(&doc).unwrap().remove(0);
+                assert!(
+                    HttpHeaderRewriteConfig::parse_yaml(&value).is_err(),
+                    "{action} {name}"
+                );
+            }
+
+            let value = json!({"response": {"action": "add", "name": name, "value": "abc"}});
+            assert!(HttpHeaderRewriteConfig::parse_json(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_template() {
+        assert!(HttpHeaderValueTemplate::from_str("${client_ip").is_err());
+        assert!(HttpHeaderValueTemplate::from_str("${unknown}").is_err());
+
+        let template = HttpHeaderValueTemplate::from_str("a-${user}-b").unwrap();
+        assert_eq!(
+            template.parts(),
+            &[
+                HttpHeaderTemplatePart::Literal("a-".to_string()),
+                HttpHeaderTemplatePart::Username,
+                HttpHeaderTemplatePart::Literal("-b".to_string()),
+            ]
+        );
+    }
+}
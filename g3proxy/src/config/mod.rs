@@ -19,10 +19,15 @@ pub use plantuml::plantuml_graph;
 
 pub(crate) mod audit;
 pub(crate) mod auth;
+pub(crate) mod category;
 pub(crate) mod escaper;
+pub(crate) mod http_cache;
+pub(crate) mod http_header_rewrite;
 pub(crate) mod log;
+pub(crate) mod pac;
 pub(crate) mod resolver;
 pub(crate) mod server;
+pub(crate) mod trace;
 
 pub fn load() -> anyhow::Result<&'static Path> {
     let config_file =
@@ -43,6 +48,7 @@ fn clear_all() {
     auth::clear();
     server::clear();
     resolver::clear();
+    category::clear();
 }
 
 pub(crate) async fn reload() -> anyhow::Result<()> {
@@ -67,12 +73,13 @@ fn reload_doc(map: &yaml::Hash) -> anyhow::Result<()> {
     let conf_dir =
         g3_daemon::opts::config_dir().ok_or_else(|| anyhow!("no valid config dir has been set"))?;
     g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
-        "runtime" | "worker" | "log" | "stat" | "controller" => Ok(()),
+        "runtime" | "worker" | "log" | "stat" | "trace" | "controller" => Ok(()),
         "escaper" => escaper::load_all(v, conf_dir),
         "server" => server::load_all(v, conf_dir),
         "resolver" => resolver::load_all(v, conf_dir),
         "user" | "user_group" => auth::load_all(v, conf_dir),
         "auditor" => audit::load_all(v, conf_dir),
+        "category" => category::load_all(v, conf_dir),
         _ => Ok(()),
     })?;
     Ok(())
@@ -86,12 +93,14 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "worker" => g3_daemon::runtime::config::load_worker(v),
         "log" => log::load(v, conf_dir),
         "stat" => g3_daemon::stat::config::load(v, crate::build::PKG_NAME),
+        "trace" => trace::load(v, conf_dir),
         "controller" => g3_daemon::control::config::load(v),
         "escaper" => escaper::load_all(v, conf_dir),
         "server" => server::load_all(v, conf_dir),
         "resolver" => resolver::load_all(v, conf_dir),
         "user" | "user_group" => auth::load_all(v, conf_dir),
         "auditor" => audit::load_all(v, conf_dir),
+        "category" => category::load_all(v, conf_dir),
         _ => Err(anyhow!("invalid key {k} in main conf")),
     })?;
     Ok(())
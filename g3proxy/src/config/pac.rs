@@ -0,0 +1,246 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use ip_network::IpNetwork;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::NodeName;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PacClientNetworkConfig {
+    pub(crate) networks: Vec<IpNetwork>,
+    pub(crate) proxy: String,
+}
+
+impl PacClientNetworkConfig {
+    fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for pac client network config should be 'map'"
+            ));
+        };
+
+        let mut networks = Vec::new();
+        let mut proxy = String::new();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "network" | "networks" => {
+                networks = g3_yaml::value::as_list(v, g3_yaml::value::as_ip_network)
+                    .context(format!("invalid ip network list value for key {k}"))?;
+                Ok(())
+            }
+            "proxy" => {
+                proxy = as_pac_proxy(v).context(format!("invalid pac proxy value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if networks.is_empty() {
+            return Err(anyhow!("no client network set"));
+        }
+        if proxy.is_empty() {
+            return Err(anyhow!("no proxy set"));
+        }
+        Ok(PacClientNetworkConfig { networks, proxy })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|net| net.contains(ip))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PacFileConfig {
+    paths: BTreeSet<String>,
+    pub(crate) template: Option<Arc<str>>,
+    /// the default return value of FindProxyForURL
+    pub(crate) proxy: Option<String>,
+    pub(crate) client_networks: Vec<PacClientNetworkConfig>,
+    /// use DIRECT for hosts explicitly forbidden by dst_host_filter_set
+    pub(crate) direct_forbidden_hosts: bool,
+    /// the proxy values to use for next escapers of route_upstream escaper
+    pub(crate) escaper_proxies: BTreeMap<NodeName, String>,
+    pub(crate) max_age: Duration,
+}
+
+impl Default for PacFileConfig {
+    fn default() -> Self {
+        PacFileConfig {
+            paths: BTreeSet::from(["/proxy.pac".to_string(), "/wpad.dat".to_string()]),
+            template: None,
+            proxy: None,
+            client_networks: Vec::new(),
+            direct_forbidden_hosts: true,
+            escaper_proxies: BTreeMap::new(),
+            max_age: Duration::from_secs(300),
+        }
+    }
+}
+
+impl PacFileConfig {
+    pub(crate) fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let mut config = PacFileConfig::default();
+
+        match value {
+            Yaml::Boolean(true) => {}
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| config.set(k, v, lookup_dir))?;
+            }
+            _ => {
+                return Err(anyhow!("invalid yaml value type for pac file config"));
+            }
+        }
+
+        if config.paths.is_empty() {
+            return Err(anyhow!("no pac file path set"));
+        }
+        Ok(config)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml, lookup_dir: &Path) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "path" | "paths" => {
+                let paths = g3_yaml::value::as_list(v, |v| {
+                    let s = g3_yaml::value::as_string(v)?;
+                    if s.starts_with('/') {
+                        Ok(s)
+                    } else {
+                        Err(anyhow!("the path should start with '/'"))
+                    }
+                })
+                .context(format!("invalid url path list value for key {k}"))?;
+                self.paths = BTreeSet::from_iter(paths);
+                Ok(())
+            }
+            "template" | "template_file" => {
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|e| anyhow!("failed to read template file {}: {e}", path.display()))?;
+                self.template = Some(Arc::from(contents));
+                Ok(())
+            }
+            "proxy" | "default_proxy" => {
+                let proxy =
+                    as_pac_proxy(v).context(format!("invalid pac proxy value for key {k}"))?;
+                self.proxy = Some(proxy);
+                Ok(())
+            }
+            "client_networks" | "client_network" => {
+                self.client_networks = g3_yaml::value::as_list(v, PacClientNetworkConfig::parse)
+                    .context(format!("invalid pac client network list value for key {k}"))?;
+                Ok(())
+            }
+            "direct_forbidden_hosts" => {
+                self.direct_forbidden_hosts = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
+            "escaper_proxies" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                self.escaper_proxies.clear();
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let escaper = NodeName::from_str(k)
+                        .map_err(|e| anyhow!("the map key is not valid escaper name: {e}"))?;
+                    let proxy =
+                        as_pac_proxy(v).context(format!("invalid pac proxy value for key {k}"))?;
+                    self.escaper_proxies.insert(escaper, proxy);
+                    Ok(())
+                })
+            }
+            "max_age" | "cache_max_age" => {
+                self.max_age = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    pub(crate) fn match_path(&self, path: &str) -> bool {
+        self.paths.contains(path)
+    }
+
+    /// Get the proxy value for the client network, or the configured default one
+    pub(crate) fn select_proxy(&self, client_ip: IpAddr) -> Option<&str> {
+        self.client_networks
+            .iter()
+            .find(|c| c.contains(client_ip))
+            .map(|c| c.proxy.as_str())
+            .or(self.proxy.as_deref())
+    }
+}
+
+/// The proxy value will be used as a JavaScript string, so only printable ascii chars
+/// other than quotes and backslash are allowed
+fn as_pac_proxy(v: &Yaml) -> anyhow::Result<String> {
+    let s = g3_yaml::value::as_string(v)?;
+    if s.is_empty() {
+        return Err(anyhow!("empty string"));
+    }
+    if let Some(c) = s
+        .chars()
+        .find(|c| !(c.is_ascii_graphic() || *c == ' ') || matches!(c, '"' | '\'' | '\\'))
+    {
+        return Err(anyhow!("invalid char {c:?} found"));
+    }
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_map() {
+        let conf = r#"
+        path: /proxy.pac
+        proxy: "PROXY proxy.example.net:3128; DIRECT"
+        client_networks:
+          - networks: 10.1.0.0/16
+            proxy: "PROXY 10.1.0.1:3128"
+        escaper_proxies:
+          overseas: "PROXY overseas.example.net:3128"
+        max_age: 1m
+        "#;
+
+        let v = YamlLoader::load_from_str(conf).unwrap();
+        let config = PacFileConfig::parse(&v[0], Path::new("/")).unwrap();
+        assert!(config.match_path("/proxy.pac"));
+        assert!(!config.match_path("/wpad.dat"));
+        assert_eq!(config.max_age, Duration::from_secs(60));
+        assert_eq!(config.escaper_proxies.len(), 1);
+
+        assert_eq!(
+            config.select_proxy(IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))),
+            Some("PROXY 10.1.0.1:3128")
+        );
+        assert_eq!(
+            config.select_proxy(IpAddr::V4(Ipv4Addr::new(10, 2, 2, 3))),
+            Some("PROXY proxy.example.net:3128; DIRECT")
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let v = YamlLoader::load_from_str("proxy: \"PROXY a\\\" + b\"").unwrap();
+        assert!(PacFileConfig::parse(&v[0], Path::new("/")).is_err());
+
+        let v = YamlLoader::load_from_str("path: proxy.pac").unwrap();
+        assert!(PacFileConfig::parse(&v[0], Path::new("/")).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use yaml_rust::{Yaml, yaml};
+
+use g3_resolver::driver::hosts::HostsDriverConfig;
+use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyResolverConfig, ResolverConfigDiffAction};
+
+const RESOLVER_CONFIG_TYPE: &str = "hosts";
+
+#[derive(Clone, Eq, PartialEq)]
+pub(crate) struct HostsResolverConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    runtime: ResolverRuntimeConfig,
+    driver: HostsDriverConfig,
+}
+
+impl From<&HostsResolverConfig> for g3_resolver::ResolverConfig {
+    fn from(c: &HostsResolverConfig) -> Self {
+        g3_resolver::ResolverConfig {
+            name: c.name.to_string(),
+            runtime: c.runtime.clone(),
+            driver: AnyResolveDriverConfig::Hosts(c.driver.clone()),
+        }
+    }
+}
+
+impl HostsResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        HostsResolverConfig {
+            name: NodeName::default(),
+            position,
+            runtime: Default::default(),
+            driver: HostsDriverConfig::new(PathBuf::new()),
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    #[inline]
+    pub(crate) fn hosts_file(&self) -> String {
+        self.driver.path().display().to_string()
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "path" | "file" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)?;
+                self.driver.set_path(path);
+                Ok(())
+            }
+            "ttl" => {
+                let ttl = g3_yaml::value::as_u32(v)?;
+                self.driver.set_ttl(ttl);
+                Ok(())
+            }
+            "negative_ttl" | "protective_cache_ttl" => {
+                let ttl = g3_yaml::value::as_u32(v)?;
+                self.driver.set_negative_ttl(ttl);
+                Ok(())
+            }
+            "check_interval" => {
+                let interval = g3_yaml::humanize::as_duration(v)?;
+                self.driver.set_check_interval(interval);
+                Ok(())
+            }
+            "graceful_stop_wait" => {
+                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "protective_query_timeout" => {
+                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.driver.path().as_os_str().is_empty() {
+            return Err(anyhow!("no hosts file path set"));
+        }
+        Ok(())
+    }
+}
+
+impl super::ResolverConfig for HostsResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::Hosts(new) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ResolverConfigDiffAction::NoAction;
+        }
+
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        None
+    }
+}
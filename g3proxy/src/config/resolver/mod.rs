@@ -21,6 +21,8 @@ pub(crate) mod hickory;
 
 pub(crate) mod deny_all;
 pub(crate) mod fail_over;
+pub(crate) mod hosts;
+pub(crate) mod split;
 
 mod registry;
 pub(crate) use registry::clear;
@@ -54,6 +56,8 @@ pub(crate) enum AnyResolverConfig {
     Hickory(Box<hickory::HickoryResolverConfig>),
     DenyAll(deny_all::DenyAllResolverConfig),
     FailOver(fail_over::FailOverResolverConfig),
+    Hosts(hosts::HostsResolverConfig),
+    Split(split::SplitResolverConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -121,6 +125,16 @@ fn load_resolver(
                 .context("failed to load this FailOver resolver")?;
             Ok(AnyResolverConfig::FailOver(resolver))
         }
+        "hosts" => {
+            let resolver = hosts::HostsResolverConfig::parse(map, position)
+                .context("failed to load this Hosts resolver")?;
+            Ok(AnyResolverConfig::Hosts(resolver))
+        }
+        "split" | "split_horizon" => {
+            let resolver = split::SplitResolverConfig::parse(map, position)
+                .context("failed to load this Split resolver")?;
+            Ok(AnyResolverConfig::Split(resolver))
+        }
         _ => Err(anyhow!("unsupported resolver type {resolver_type}")),
     }
 }
@@ -0,0 +1,145 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_resolver::ResolverRuntimeConfig;
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
+
+const RESOLVER_CONFIG_TYPE: &str = "split";
+
+#[derive(Clone, Eq, PartialEq)]
+pub(crate) struct SplitResolverConfig {
+    position: Option<YamlDocPosition>,
+    name: NodeName,
+    pub(crate) runtime: ResolverRuntimeConfig,
+    pub(crate) rules: BTreeMap<String, NodeName>,
+    pub(crate) default: Option<NodeName>,
+    pub(crate) negative_ttl: Option<u32>,
+}
+
+impl SplitResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        SplitResolverConfig {
+            name: NodeName::default(),
+            position,
+            runtime: Default::default(),
+            rules: BTreeMap::new(),
+            default: None,
+            negative_ttl: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "rules" | "rule" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                g3_yaml::foreach_kv(map, |suffix, v| {
+                    let resolver = g3_yaml::value::as_metric_node_name(v)
+                        .context(format!("invalid resolver name value for suffix {suffix}"))?;
+                    let suffix = suffix.trim_matches('.').to_ascii_lowercase();
+                    if suffix.is_empty() {
+                        return Err(anyhow!("empty domain suffix is not allowed"));
+                    }
+                    self.rules.insert(suffix, resolver);
+                    Ok(())
+                })
+            }
+            "default" | "fallback" => {
+                self.default = Some(g3_yaml::value::as_metric_node_name(v)?);
+                Ok(())
+            }
+            "negative_ttl" | "protective_cache_ttl" => {
+                self.negative_ttl = Some(g3_yaml::value::as_u32(v)?);
+                Ok(())
+            }
+            "graceful_stop_wait" => {
+                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "protective_query_timeout" => {
+                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.rules.is_empty() && self.default.is_none() {
+            return Err(anyhow!("neither rules nor default next resolver is set"));
+        }
+        if self
+            .dependent_resolver()
+            .is_some_and(|set| set.contains(&self.name))
+        {
+            return Err(anyhow!("the next resolver should not be itself"));
+        }
+
+        Ok(())
+    }
+}
+
+impl ResolverConfig for SplitResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::Split(new) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ResolverConfigDiffAction::NoAction;
+        }
+
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        let mut set: BTreeSet<NodeName> = self.rules.values().cloned().collect();
+        if let Some(name) = &self.default {
+            set.insert(name.clone());
+        }
+        Some(set)
+    }
+}
@@ -32,6 +32,10 @@ use super::{
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
 };
 use crate::config::auth::UsernameParamsConfig;
+use crate::config::category::CategoryFilterConfig;
+use crate::config::http_cache::HttpCacheConfig;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+use crate::config::pac::PacFileConfig;
 
 const SERVER_CONFIG_TYPE: &str = "HttpProxy";
 
@@ -69,6 +73,7 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) ftp_client_config: Arc<FtpClientConfig>,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
+    pub(crate) dst_category_filter: Option<CategoryFilterConfig>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) local_server_names: HashSet<Host>,
     pub(crate) server_id: Option<HttpServerId>,
@@ -97,6 +102,9 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) egress_path_selection_header: Option<HeaderName>,
     pub(crate) steal_forwarded_for: bool,
+    pub(crate) http_cache: Option<HttpCacheConfig>,
+    pub(crate) http_header_rewrite: Arc<HttpHeaderRewriteConfig>,
+    pub(crate) pac_file: Option<PacFileConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
     // Optional: derive next-hop escaper addr from username params
     pub(crate) username_params: Option<UsernameParamsConfig>,
@@ -119,6 +127,7 @@ impl HttpProxyServerConfig {
             ftp_client_config: Arc::new(Default::default()),
             ingress_net_filter: None,
             dst_host_filter: None,
+            dst_category_filter: None,
             dst_port_filter: None,
             local_server_names: HashSet::new(),
             server_id: None,
@@ -147,6 +156,9 @@ impl HttpProxyServerConfig {
             untrusted_read_limit: None,
             egress_path_selection_header: None,
             steal_forwarded_for: false,
+            http_cache: None,
+            http_header_rewrite: Arc::new(HttpHeaderRewriteConfig::default()),
+            pac_file: None,
             extra_metrics_tags: None,
             username_params: None,
         }
@@ -256,6 +268,12 @@ impl HttpProxyServerConfig {
                 self.dst_host_filter = Some(filter_set);
                 Ok(())
             }
+            "dst_category_filter" => {
+                let filter = CategoryFilterConfig::parse_yaml(v)
+                    .context(format!("invalid category filter value for key {k}"))?;
+                self.dst_category_filter = Some(filter);
+                Ok(())
+            }
             "dst_port_filter" => {
                 let filter = g3_yaml::value::acl::as_exact_port_rule(v)
                     .context(format!("invalid dst port acl rule value for key {k}"))?;
@@ -426,6 +444,35 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid boolean value for key {k}"))?;
                 Ok(())
             }
+            "http_cache" => {
+                if let Yaml::Boolean(false) = v {
+                    self.http_cache = None;
+                    return Ok(());
+                }
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = HttpCacheConfig::parse(v, lookup_dir)
+                    .context(format!("invalid http cache config value for key {k}"))?;
+                self.http_cache = Some(config);
+                Ok(())
+            }
+            "http_header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                self.http_header_rewrite = Arc::new(config);
+                Ok(())
+            }
+            "pac_file" | "pac" => {
+                if let Yaml::Boolean(false) = v {
+                    self.pac_file = None;
+                    return Ok(());
+                }
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = PacFileConfig::parse(v, lookup_dir)
+                    .context(format!("invalid pac file config value for key {k}"))?;
+                self.pac_file = Some(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -512,4 +559,9 @@ impl ServerConfig for HttpProxyServerConfig {
     fn task_max_idle_count(&self) -> usize {
         self.task_idle_max_count
     }
+
+    #[inline]
+    fn http_header_rewrite(&self) -> Option<&HttpHeaderRewriteConfig> {
+        Some(&self.http_header_rewrite)
+    }
 }
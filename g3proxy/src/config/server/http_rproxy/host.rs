@@ -3,18 +3,24 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::BTreeMap;
+
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
 use g3_types::net::{Host, OpensslClientConfigBuilder, RustlsServerConfigBuilder, UpstreamAddr};
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
+use super::{HttpRouteConfig, HttpUpstreamPoolConfig};
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct HttpHostConfig {
     upstream: UpstreamAddr,
     pub(crate) tls_server_builder: Option<RustlsServerConfigBuilder>,
     pub(crate) tls_client_builder: Option<OpensslClientConfigBuilder>,
     pub(crate) tls_name: Host,
+    pub(crate) pools: BTreeMap<String, HttpUpstreamPoolConfig>,
+    pub(crate) routes: Vec<HttpRouteConfig>,
 }
 
 impl Default for HttpHostConfig {
@@ -24,6 +30,8 @@ impl Default for HttpHostConfig {
             tls_server_builder: None,
             tls_client_builder: None,
             tls_name: Host::empty(),
+            pools: BTreeMap::new(),
+            routes: Vec::new(),
         }
     }
 }
@@ -78,14 +86,37 @@ impl YamlMapCallback for HttpHostConfig {
                     .context(format!("invalid tls name value for key {key}"))?;
                 Ok(())
             }
+            "pools" | "upstream_pools" => {
+                let Yaml::Hash(map) = value else {
+                    return Err(anyhow!("yaml value type for key {key} should be 'map'"));
+                };
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                self.pools.clear();
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let pool = HttpUpstreamPoolConfig::parse(v, lookup_dir)
+                        .context(format!("invalid http upstream pool config value for {k}"))?;
+                    self.pools.insert(k.to_string(), pool);
+                    Ok(())
+                })
+            }
+            "routes" => {
+                self.routes = g3_yaml::value::as_list(value, HttpRouteConfig::parse)
+                    .context(format!("invalid http route list value for key {key}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {key}")),
         }
     }
 
     fn check(&mut self) -> anyhow::Result<()> {
-        if self.upstream.is_empty() {
+        if self.upstream.is_empty() && self.routes.is_empty() {
             return Err(anyhow!("upstream is empty"));
         }
+        for route in &self.routes {
+            if !self.pools.contains_key(&route.pool) {
+                return Err(anyhow!("no upstream pool named {} found", route.pool));
+            }
+        }
         if self.tls_name.is_empty() {
             self.upstream.host().clone_into(&mut self.tls_name);
         }
@@ -23,6 +23,8 @@ use g3_types::net::{
 use g3_types::route::HostMatch;
 use g3_yaml::YamlDocPosition;
 
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
@@ -31,6 +33,12 @@ use super::{
 mod host;
 pub(crate) use host::HttpHostConfig;
 
+mod pool;
+pub(crate) use pool::HttpUpstreamPoolConfig;
+
+mod route;
+pub(crate) use route::HttpRouteConfig;
+
 const SERVER_CONFIG_TYPE: &str = "HttpRProxy";
 
 /// collection of timeout config
@@ -82,6 +90,7 @@ pub(crate) struct HttpRProxyServerConfig {
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) append_forwarded_for: HttpForwardedHeaderType,
+    pub(crate) http_header_rewrite: Arc<HttpHeaderRewriteConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
     pub(crate) hosts: HostMatch<Arc<HttpHostConfig>>,
     pub(crate) enable_tls_server: bool,
@@ -122,6 +131,7 @@ impl HttpRProxyServerConfig {
             http_forward_upstream_keepalive: Default::default(),
             untrusted_read_limit: None,
             append_forwarded_for: HttpForwardedHeaderType::default(),
+            http_header_rewrite: Arc::new(HttpHeaderRewriteConfig::default()),
             extra_metrics_tags: None,
             hosts: Default::default(),
             enable_tls_server: false,
@@ -320,6 +330,13 @@ impl HttpRProxyServerConfig {
                     ))?;
                 Ok(())
             }
+            "http_header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                self.http_header_rewrite = Arc::new(config);
+                Ok(())
+            }
             "hosts" | "sites" => {
                 self.hosts = g3_yaml::value::as_host_matched_obj(v, self.position.as_ref())
                     .context(format!(
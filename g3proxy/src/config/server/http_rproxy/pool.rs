@@ -0,0 +1,135 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::net::{Host, OpensslClientConfigBuilder, WeightedUpstreamAddr};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HttpUpstreamPoolConfig {
+    pub(crate) upstream: Vec<WeightedUpstreamAddr>,
+    pub(crate) pick_policy: SelectivePickPolicy,
+    pub(crate) tls_client_builder: Option<OpensslClientConfigBuilder>,
+    pub(crate) tls_name: Option<Host>,
+    /// the member will be ejected if failed this many times within `fail_window`, 0 to disable
+    pub(crate) max_fails: usize,
+    pub(crate) fail_window: Duration,
+    pub(crate) eject_duration: Duration,
+    /// max retries to other members for idempotent requests
+    pub(crate) max_retries: usize,
+}
+
+impl Default for HttpUpstreamPoolConfig {
+    fn default() -> Self {
+        HttpUpstreamPoolConfig {
+            upstream: Vec::new(),
+            pick_policy: SelectivePickPolicy::RoundRobin,
+            tls_client_builder: None,
+            tls_name: None,
+            max_fails: 3,
+            fail_window: Duration::from_secs(10),
+            eject_duration: Duration::from_secs(30),
+            max_retries: 1,
+        }
+    }
+}
+
+impl HttpUpstreamPoolConfig {
+    pub(super) fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let mut config = HttpUpstreamPoolConfig::default();
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| config.set(k, v, lookup_dir))?;
+            }
+            Yaml::Array(_) | Yaml::String(_) => {
+                config.set_upstream(value)?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "invalid yaml value type for http upstream pool config"
+                ));
+            }
+        }
+
+        config.check()?;
+        Ok(config)
+    }
+
+    fn set_upstream(&mut self, value: &Yaml) -> anyhow::Result<()> {
+        self.upstream =
+            g3_yaml::value::as_list(value, |v| g3_yaml::value::as_weighted_upstream_addr(v, 80))?;
+        Ok(())
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml, lookup_dir: &Path) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "upstream" | "members" => self.set_upstream(v).context(format!(
+                "invalid weighted upstream address value for key {k}"
+            )),
+            "pick_policy" | "upstream_pick_policy" => {
+                self.pick_policy = g3_yaml::value::as_selective_pick_policy(v)
+                    .context(format!("invalid selective pick policy value for key {k}"))?;
+                Ok(())
+            }
+            "tls_client" => {
+                let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                    v,
+                    Some(lookup_dir),
+                )
+                .context(format!(
+                    "invalid openssl tls client config value for key {k}"
+                ))?;
+                self.tls_client_builder = Some(builder);
+                Ok(())
+            }
+            "tls_name" => {
+                let tls_name = g3_yaml::value::as_host(v)
+                    .context(format!("invalid tls name value for key {k}"))?;
+                self.tls_name = Some(tls_name);
+                Ok(())
+            }
+            "max_fails" => {
+                self.max_fails = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "fail_window" | "fail_timeout" => {
+                self.fail_window = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "eject_duration" | "eject_time" => {
+                self.eject_duration = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "max_retries" | "retries" => {
+                self.max_retries = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.upstream.is_empty() {
+            return Err(anyhow!("no upstream address set"));
+        }
+        if self.tls_client_builder.is_some()
+            && self.tls_name.is_none()
+            && let Some(upstream) = self.upstream.first()
+        {
+            self.tls_name = Some(upstream.inner().host().to_owned());
+        }
+        Ok(())
+    }
+}
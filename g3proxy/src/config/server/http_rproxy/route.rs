@@ -0,0 +1,191 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use http::{HeaderName, Method};
+use regex::Regex;
+use yaml_rust::Yaml;
+
+use g3_types::net::HttpHeaderMap;
+
+#[derive(Clone, Debug)]
+pub(crate) enum HttpRoutePathMatch {
+    Prefix(String),
+    Regex(Regex),
+}
+
+impl PartialEq for HttpRoutePathMatch {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HttpRoutePathMatch::Prefix(a), HttpRoutePathMatch::Prefix(b)) => a == b,
+            (HttpRoutePathMatch::Regex(a), HttpRoutePathMatch::Regex(b)) => {
+                a.as_str() == b.as_str()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl HttpRoutePathMatch {
+    fn is_match(&self, path: &str) -> bool {
+        match self {
+            HttpRoutePathMatch::Prefix(prefix) => path.starts_with(prefix.as_str()),
+            HttpRoutePathMatch::Regex(regex) => regex.is_match(path),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct HttpRouteHeaderMatch {
+    name: HeaderName,
+    value: Option<Regex>,
+}
+
+impl PartialEq for HttpRouteHeaderMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.value.as_ref().map(|r| r.as_str()) == other.value.as_ref().map(|r| r.as_str())
+    }
+}
+
+impl HttpRouteHeaderMatch {
+    fn parse(k: &str, v: &Yaml) -> anyhow::Result<Self> {
+        let name = HeaderName::from_str(k).map_err(|e| anyhow!("invalid header name {k}: {e}"))?;
+        let value = match v {
+            Yaml::Null => None,
+            Yaml::Boolean(true) => None,
+            _ => Some(g3_yaml::value::as_regex(v)?),
+        };
+        Ok(HttpRouteHeaderMatch { name, value })
+    }
+
+    fn is_match(&self, headers: &HttpHeaderMap) -> bool {
+        match &self.value {
+            Some(regex) => headers
+                .get_all(&self.name)
+                .iter()
+                .any(|v| regex.is_match(v.to_str())),
+            None => headers.contains_key(&self.name),
+        }
+    }
+}
+
+/// A route entry in the host, the first matched one will be used
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HttpRouteConfig {
+    path: Option<HttpRoutePathMatch>,
+    methods: Vec<Method>,
+    headers: Vec<HttpRouteHeaderMatch>,
+    pub(crate) pool: String,
+}
+
+impl HttpRouteConfig {
+    pub(super) fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!("yaml value type for http route should be 'map'"));
+        };
+
+        let mut route = HttpRouteConfig {
+            path: None,
+            methods: Vec::new(),
+            headers: Vec::new(),
+            pool: String::new(),
+        };
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "path" | "path_prefix" | "prefix" => {
+                let prefix = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                route.path = Some(HttpRoutePathMatch::Prefix(prefix));
+                Ok(())
+            }
+            "path_regex" | "regex" => {
+                let regex = g3_yaml::value::as_regex(v)
+                    .context(format!("invalid regex value for key {k}"))?;
+                route.path = Some(HttpRoutePathMatch::Regex(regex));
+                Ok(())
+            }
+            "method" | "methods" => {
+                route.methods = g3_yaml::value::as_list(v, |v| {
+                    let s = g3_yaml::value::as_string(v)?;
+                    Method::from_str(&s.to_uppercase())
+                        .map_err(|e| anyhow!("invalid http method {s}: {e}"))
+                })
+                .context(format!("invalid http method list value for key {k}"))?;
+                Ok(())
+            }
+            "header" | "headers" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("yaml value type for key {k} should be 'map'"));
+                };
+                route.headers.clear();
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let m = HttpRouteHeaderMatch::parse(k, v)
+                        .context(format!("invalid header match value for header {k}"))?;
+                    route.headers.push(m);
+                    Ok(())
+                })?;
+                Ok(())
+            }
+            "pool" | "upstream_pool" => {
+                route.pool = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if route.pool.is_empty() {
+            return Err(anyhow!("no upstream pool set"));
+        }
+        Ok(route)
+    }
+
+    pub(crate) fn is_match(&self, method: &Method, path: &str, headers: &HttpHeaderMap) -> bool {
+        if !self.methods.is_empty() && !self.methods.contains(method) {
+            return false;
+        }
+        if let Some(path_match) = &self.path
+            && !path_match.is_match(path)
+        {
+            return false;
+        }
+        self.headers.iter().all(|m| m.is_match(headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn route_match() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+                path: /api/
+                methods: [get, post]
+                headers:
+                  x-env: "^(dev|test)$"
+                pool: api
+            "#,
+        )
+        .unwrap();
+        let route = HttpRouteConfig::parse(&doc[0]).unwrap();
+        assert_eq!(route.pool, "api");
+
+        let mut headers = HttpHeaderMap::default();
+        assert!(!route.is_match(&Method::GET, "/api/v1", &headers));
+        headers.insert(
+            HeaderName::from_static("x-env"),
+            HttpHeaderValue::from_static("dev"),
+        );
+        assert!(route.is_match(&Method::GET, "/api/v1", &headers));
+        assert!(!route.is_match(&Method::PUT, "/api/v1", &headers));
+        assert!(!route.is_match(&Method::GET, "/static/a.js", &headers));
+    }
+}
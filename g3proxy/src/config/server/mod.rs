@@ -20,6 +20,7 @@ use g3_yaml::{HybridParser, YamlDocPosition};
 
 use crate::audit::AuditHandle;
 use crate::auth::UserGroup;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 
 pub(crate) mod dummy_close;
 pub(crate) mod intelli_proxy;
@@ -42,6 +43,8 @@ pub(crate) mod tcp_stream;
 ))]
 pub(crate) mod tcp_tproxy;
 pub(crate) mod tls_stream;
+#[cfg(target_os = "linux")]
+pub(crate) mod udp_tproxy;
 
 mod registry;
 pub(crate) use registry::clear;
@@ -98,6 +101,11 @@ pub(crate) trait ServerConfig {
         1
     }
 
+    /// The server level http header rewrite rules, which will be used in protocol inspection
+    fn http_header_rewrite(&self) -> Option<&HttpHeaderRewriteConfig> {
+        None
+    }
+
     fn get_user_group(&self) -> Option<Arc<UserGroup>> {
         if self.user_group().is_empty() {
             None
@@ -146,6 +154,8 @@ pub(crate) enum AnyServerConfig {
     ))]
     TcpTProxy(tcp_tproxy::TcpTProxyServerConfig),
     TlsStream(tls_stream::TlsStreamServerConfig),
+    #[cfg(target_os = "linux")]
+    UdpTProxy(udp_tproxy::UdpTProxyServerConfig),
     SniProxy(sni_proxy::SniProxyServerConfig),
     SocksProxy(socks_proxy::SocksProxyServerConfig),
     HttpProxy(http_proxy::HttpProxyServerConfig),
@@ -245,6 +255,12 @@ fn load_server(
                 .context("failed to load this TcpTProxy server")?;
             Ok(AnyServerConfig::TcpTProxy(server))
         }
+        #[cfg(target_os = "linux")]
+        "udp_tproxy" | "udptproxy" => {
+            let server = udp_tproxy::UdpTProxyServerConfig::parse(map, position)
+                .context("failed to load this UdpTProxy server")?;
+            Ok(AnyServerConfig::UdpTProxy(server))
+        }
         "tls_stream" | "tlsstream" => {
             let server = tls_stream::TlsStreamServerConfig::parse(map, position)
                 .context("failed to load this TLsStream server")?;
@@ -19,6 +19,8 @@ use g3_types::net::{TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig};
 use g3_types::route::HostMatch;
 use g3_yaml::YamlDocPosition;
 
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
@@ -45,6 +47,7 @@ pub(crate) struct SniProxyServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) http_header_rewrite: Arc<HttpHeaderRewriteConfig>,
     pub(crate) tcp_copy: StreamCopyConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) tls_max_client_hello_size: u32,
@@ -74,6 +77,7 @@ impl SniProxyServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            http_header_rewrite: Arc::new(HttpHeaderRewriteConfig::default()),
             tcp_copy: Default::default(),
             tcp_misc_opts: Default::default(),
             tls_max_client_hello_size: 1 << 16,
@@ -200,6 +204,13 @@ impl SniProxyServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "http_header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                self.http_header_rewrite = Arc::new(config);
+                Ok(())
+            }
             "request_wait_timeout" => {
                 self.request_wait_timeout = g3_yaml::humanize::as_duration(v)
                     .context(format!("invalid humanize duration value for key {k}"))?;
@@ -311,4 +322,9 @@ impl ServerConfig for SniProxyServerConfig {
     fn task_max_idle_count(&self) -> usize {
         self.task_idle_max_count
     }
+
+    #[inline]
+    fn http_header_rewrite(&self) -> Option<&HttpHeaderRewriteConfig> {
+        Some(&self.http_header_rewrite)
+    }
 }
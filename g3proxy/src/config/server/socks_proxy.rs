@@ -28,6 +28,8 @@ use super::{
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
 };
 use crate::config::auth::UsernameParamsConfig;
+use crate::config::category::CategoryFilterConfig;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 
 const SERVER_CONFIG_TYPE: &str = "SocksProxy";
 
@@ -67,6 +69,7 @@ pub(crate) struct SocksProxyServerConfig {
     pub(crate) udp_socket_buffer: SocketBufferConfig,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
+    pub(crate) dst_category_filter: Option<CategoryFilterConfig>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
     pub(crate) udp_sock_speed_limit: UdpSockSpeedLimitConfig,
@@ -76,6 +79,7 @@ pub(crate) struct SocksProxyServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) http_header_rewrite: Arc<HttpHeaderRewriteConfig>,
     pub(crate) tcp_copy: StreamCopyConfig,
     pub(crate) udp_relay: LimitedUdpRelayConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
@@ -104,6 +108,7 @@ impl SocksProxyServerConfig {
             udp_socket_buffer: SocketBufferConfig::default(),
             ingress_net_filter: None,
             dst_host_filter: None,
+            dst_category_filter: None,
             dst_port_filter: None,
             tcp_sock_speed_limit: TcpSockSpeedLimitConfig::default(),
             udp_sock_speed_limit: UdpSockSpeedLimitConfig::default(),
@@ -113,6 +118,7 @@ impl SocksProxyServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            http_header_rewrite: Arc::new(HttpHeaderRewriteConfig::default()),
             tcp_copy: Default::default(),
             udp_relay: Default::default(),
             tcp_misc_opts: Default::default(),
@@ -224,6 +230,12 @@ impl SocksProxyServerConfig {
                 self.dst_host_filter = Some(filter_set);
                 Ok(())
             }
+            "dst_category_filter" => {
+                let filter = CategoryFilterConfig::parse_yaml(v)
+                    .context(format!("invalid category filter value for key {k}"))?;
+                self.dst_category_filter = Some(filter);
+                Ok(())
+            }
             "dst_port_filter" => {
                 let filter = g3_yaml::value::acl::as_exact_port_rule(v)
                     .context(format!("invalid dst port acl rule for key {k}"))?;
@@ -325,6 +337,13 @@ impl SocksProxyServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "http_header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                self.http_header_rewrite = Arc::new(config);
+                Ok(())
+            }
             "transmute_udp_echo_ip" => {
                 if let Yaml::Hash(_) = v {
                     let map = g3_yaml::value::as_hashmap(
@@ -437,4 +456,9 @@ impl ServerConfig for SocksProxyServerConfig {
     fn task_max_idle_count(&self) -> usize {
         self.task_idle_max_count
     }
+
+    #[inline]
+    fn http_header_rewrite(&self) -> Option<&HttpHeaderRewriteConfig> {
+        Some(&self.http_header_rewrite)
+    }
 }
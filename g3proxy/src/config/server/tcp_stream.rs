@@ -21,6 +21,8 @@ use g3_types::net::{
 };
 use g3_yaml::YamlDocPosition;
 
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
@@ -48,6 +50,7 @@ pub(crate) struct TcpStreamServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) http_header_rewrite: Arc<HttpHeaderRewriteConfig>,
     pub(crate) tcp_copy: StreamCopyConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
@@ -74,6 +77,7 @@ impl TcpStreamServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            http_header_rewrite: Arc::new(HttpHeaderRewriteConfig::default()),
             tcp_copy: Default::default(),
             tcp_misc_opts: Default::default(),
             extra_metrics_tags: None,
@@ -226,6 +230,13 @@ impl TcpStreamServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "http_header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                self.http_header_rewrite = Arc::new(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -312,4 +323,9 @@ impl ServerConfig for TcpStreamServerConfig {
     fn task_max_idle_count(&self) -> usize {
         self.task_idle_max_count
     }
+
+    #[inline]
+    fn http_header_rewrite(&self) -> Option<&HttpHeaderRewriteConfig> {
+        Some(&self.http_header_rewrite)
+    }
 }
@@ -17,6 +17,8 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig};
 use g3_yaml::YamlDocPosition;
 
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
@@ -40,6 +42,7 @@ pub(crate) struct TcpTProxyServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) http_header_rewrite: Arc<HttpHeaderRewriteConfig>,
     pub(crate) tcp_copy: StreamCopyConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
@@ -62,6 +65,7 @@ impl TcpTProxyServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            http_header_rewrite: Arc::new(HttpHeaderRewriteConfig::default()),
             tcp_copy: Default::default(),
             tcp_misc_opts: Default::default(),
             extra_metrics_tags: None,
@@ -176,6 +180,13 @@ impl TcpTProxyServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "http_header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                self.http_header_rewrite = Arc::new(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -257,4 +268,9 @@ impl ServerConfig for TcpTProxyServerConfig {
     fn task_max_idle_count(&self) -> usize {
         self.task_idle_max_count
     }
+
+    #[inline]
+    fn http_header_rewrite(&self) -> Option<&HttpHeaderRewriteConfig> {
+        Some(&self.http_header_rewrite)
+    }
 }
@@ -0,0 +1,387 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use ascii::AsciiString;
+use yaml_rust::{Yaml, yaml};
+
+use g3_io_ext::LimitedUdpRelayConfig;
+use g3_types::acl::{AclExactPortRule, AclNetworkRuleBuilder};
+use g3_types::acl_set::AclDstHostRuleSetBuilder;
+use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::net::{
+    SocketBufferConfig, UdpListenConfig, UdpMiscSockOpts, UdpSockSpeedLimitConfig,
+};
+use g3_yaml::YamlDocPosition;
+
+use super::{
+    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
+    IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
+};
+
+const SERVER_CONFIG_TYPE: &str = "UdpTProxy";
+
+const DEFAULT_MAX_FLOW_COUNT: usize = 65536;
+const DEFAULT_FLOW_QUEUE_SIZE: usize = 64;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UdpTProxyServerConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) escaper: NodeName,
+    pub(crate) auditor: NodeName,
+    pub(crate) shared_logger: Option<AsciiString>,
+    pub(crate) listen: UdpListenConfig,
+    pub(crate) listen_in_worker: bool,
+    pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
+    pub(crate) dst_port_filter: Option<AclExactPortRule>,
+    pub(crate) udp_sock_speed_limit: UdpSockSpeedLimitConfig,
+    pub(crate) udp_socket_buffer: SocketBufferConfig,
+    pub(crate) udp_relay: LimitedUdpRelayConfig,
+    pub(crate) udp_misc_opts: UdpMiscSockOpts,
+    pub(crate) max_flow_count: usize,
+    pub(crate) flow_queue_size: usize,
+    pub(crate) inspect_first_packet: bool,
+    pub(crate) task_idle_check_interval: Duration,
+    pub(crate) task_idle_max_count: usize,
+    pub(crate) flush_task_log_on_created: bool,
+    pub(crate) flush_task_log_on_connected: bool,
+    pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
+}
+
+impl UdpTProxyServerConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        UdpTProxyServerConfig {
+            name: NodeName::default(),
+            position,
+            escaper: NodeName::default(),
+            auditor: NodeName::default(),
+            shared_logger: None,
+            listen: UdpListenConfig::default(),
+            listen_in_worker: false,
+            ingress_net_filter: None,
+            dst_host_filter: None,
+            dst_port_filter: None,
+            udp_sock_speed_limit: UdpSockSpeedLimitConfig::default(),
+            udp_socket_buffer: SocketBufferConfig::default(),
+            udp_relay: Default::default(),
+            udp_misc_opts: Default::default(),
+            max_flow_count: DEFAULT_MAX_FLOW_COUNT,
+            flow_queue_size: DEFAULT_FLOW_QUEUE_SIZE,
+            inspect_first_packet: false,
+            task_idle_check_interval: IDLE_CHECK_DEFAULT_DURATION,
+            task_idle_max_count: IDLE_CHECK_DEFAULT_MAX_COUNT,
+            flush_task_log_on_created: false,
+            flush_task_log_on_connected: false,
+            task_log_flush_interval: None,
+            extra_metrics_tags: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut server = UdpTProxyServerConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| server.set(k, v))?;
+
+        server.check()?;
+        Ok(server)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_SERVER_TYPE => Ok(()),
+            super::CONFIG_KEY_SERVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "escaper" => {
+                self.escaper = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "auditor" => {
+                self.auditor = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "shared_logger" => {
+                let name = g3_yaml::value::as_ascii(v)?;
+                self.shared_logger = Some(name);
+                Ok(())
+            }
+            "extra_metrics_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                self.extra_metrics_tags = Some(Arc::new(tags));
+                Ok(())
+            }
+            "listen" => {
+                self.listen = g3_yaml::value::as_udp_listen_config(v)
+                    .context(format!("invalid udp listen config value for key {k}"))?;
+                Ok(())
+            }
+            "listen_in_worker" => {
+                self.listen_in_worker = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "ingress_network_filter" | "ingress_net_filter" => {
+                let filter = g3_yaml::value::acl::as_ingress_network_rule_builder(v).context(
+                    format!("invalid ingress network acl rule value for key {k}"),
+                )?;
+                self.ingress_net_filter = Some(filter);
+                Ok(())
+            }
+            "dst_host_filter_set" => {
+                let filter_set = g3_yaml::value::acl_set::as_dst_host_rule_set_builder(v)
+                    .context(format!("invalid dst host acl rule set value for key {k}"))?;
+                self.dst_host_filter = Some(filter_set);
+                Ok(())
+            }
+            "dst_port_filter" => {
+                let filter = g3_yaml::value::acl::as_exact_port_rule(v)
+                    .context(format!("invalid dst port acl rule for key {k}"))?;
+                self.dst_port_filter = Some(filter);
+                Ok(())
+            }
+            "udp_sock_speed_limit" => {
+                self.udp_sock_speed_limit = g3_yaml::value::as_udp_sock_speed_limit(v)
+                    .context(format!("invalid udp socket speed limit value for key {k}"))?;
+                Ok(())
+            }
+            "udp_socket_buffer" => {
+                self.udp_socket_buffer = g3_yaml::value::as_socket_buffer_config(v)
+                    .context(format!("invalid socket buffer config value for key {k}"))?;
+                Ok(())
+            }
+            "udp_relay_packet_size" => {
+                let packet_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.udp_relay.set_packet_size(packet_size);
+                Ok(())
+            }
+            "udp_relay_yield_size" => {
+                let yield_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.udp_relay.set_yield_size(yield_size);
+                Ok(())
+            }
+            "udp_relay_batch_size" => {
+                let batch_size = g3_yaml::value::as_usize(v)?;
+                self.udp_relay.set_batch_size(batch_size);
+                Ok(())
+            }
+            "udp_misc_opts" => {
+                self.udp_misc_opts = g3_yaml::value::as_udp_misc_sock_opts(v)
+                    .context(format!("invalid udp misc sock opts value for key {k}"))?;
+                Ok(())
+            }
+            "max_flow_count" => {
+                self.max_flow_count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "flow_queue_size" => {
+                self.flow_queue_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "inspect_first_packet" => {
+                self.inspect_first_packet = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "task_idle_check_interval" => {
+                self.task_idle_check_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "task_idle_max_count" => {
+                self.task_idle_max_count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "flush_task_log_on_created" => {
+                self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "flush_task_log_on_connected" => {
+                self.flush_task_log_on_connected = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "task_log_flush_interval" => {
+                let interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.task_log_flush_interval = Some(interval);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.escaper.is_empty() {
+            return Err(anyhow!("escaper is not set"));
+        }
+        if self.task_idle_check_interval > IDLE_CHECK_MAXIMUM_DURATION {
+            self.task_idle_check_interval = IDLE_CHECK_MAXIMUM_DURATION;
+        }
+        if self.max_flow_count == 0 {
+            self.max_flow_count = DEFAULT_MAX_FLOW_COUNT;
+        }
+        if self.flow_queue_size == 0 {
+            self.flow_queue_size = DEFAULT_FLOW_QUEUE_SIZE;
+        }
+
+        self.listen.set_transparent();
+        self.listen.check()?;
+
+        Ok(())
+    }
+}
+
+impl ServerConfig for UdpTProxyServerConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        SERVER_CONFIG_TYPE
+    }
+
+    fn escaper(&self) -> &NodeName {
+        &self.escaper
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        &self.auditor
+    }
+
+    fn diff_action(&self, new: &AnyServerConfig) -> ServerConfigDiffAction {
+        let AnyServerConfig::UdpTProxy(new) = new else {
+            return ServerConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ServerConfigDiffAction::NoAction;
+        }
+
+        if self.listen != new.listen {
+            return ServerConfigDiffAction::ReloadAndRespawn;
+        }
+
+        ServerConfigDiffAction::ReloadNoRespawn
+    }
+
+    fn shared_logger(&self) -> Option<&str> {
+        self.shared_logger.as_ref().map(|s| s.as_str())
+    }
+
+    fn task_log_flush_interval(&self) -> Option<Duration> {
+        self.task_log_flush_interval
+    }
+
+    #[inline]
+    fn task_max_idle_count(&self) -> usize {
+        self.task_idle_max_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn parse(s: &str) -> anyhow::Result<UdpTProxyServerConfig> {
+        let doc = YamlLoader::load_from_str(s).unwrap();
+        let Yaml::Hash(map) = &doc[0] else {
+            panic!("invalid yaml doc");
+        };
+        UdpTProxyServerConfig::parse(map, None)
+    }
+
+    #[test]
+    fn parse_minimal() {
+        let config = parse(
+            r#"
+                name: udp-tproxy
+                type: udp_tproxy
+                escaper: default
+                listen: "[::]:10053"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.name().as_str(), "udp-tproxy");
+        assert_eq!(config.escaper.as_str(), "default");
+        assert_eq!(config.max_flow_count, DEFAULT_MAX_FLOW_COUNT);
+        assert_eq!(config.flow_queue_size, DEFAULT_FLOW_QUEUE_SIZE);
+        assert!(!config.inspect_first_packet);
+    }
+
+    #[test]
+    fn parse_flow_limits() {
+        let config = parse(
+            r#"
+                name: udp-tproxy
+                escaper: default
+                listen: 10053
+                max_flow_count: 1024
+                flow_queue_size: 0
+                inspect_first_packet: true
+                task_idle_check_interval: 1h
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.max_flow_count, 1024);
+        assert_eq!(config.flow_queue_size, DEFAULT_FLOW_QUEUE_SIZE);
+        assert!(config.inspect_first_packet);
+        assert_eq!(config.task_idle_check_interval, IDLE_CHECK_MAXIMUM_DURATION);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        // no escaper
+        assert!(parse("name: udp-tproxy\nlisten: 10053\n").is_err());
+        // no listen port
+        assert!(parse("name: udp-tproxy\nescaper: default\n").is_err());
+        // unknown key
+        assert!(parse("name: udp-tproxy\nescaper: default\nlisten: 10053\nfoo: 1\n").is_err());
+    }
+
+    #[test]
+    fn diff_action() {
+        let config = parse("name: udp-tproxy\nescaper: default\nlisten: 10053\n").unwrap();
+        let new = parse("name: udp-tproxy\nescaper: default\nlisten: 10053\nmax_flow_count: 10\n")
+            .unwrap();
+        assert!(matches!(
+            config.diff_action(&AnyServerConfig::UdpTProxy(config.clone())),
+            ServerConfigDiffAction::NoAction
+        ));
+        assert!(matches!(
+            config.diff_action(&AnyServerConfig::UdpTProxy(new)),
+            ServerConfigDiffAction::ReloadNoRespawn
+        ));
+        let new = parse("name: udp-tproxy\nescaper: default\nlisten: 10054\n").unwrap();
+        assert!(matches!(
+            config.diff_action(&AnyServerConfig::UdpTProxy(new)),
+            ServerConfigDiffAction::ReloadAndRespawn
+        ));
+    }
+}
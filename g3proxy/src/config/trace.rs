@@ -0,0 +1,165 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use log::warn;
+use url::Url;
+use yaml_rust::Yaml;
+
+use g3_types::net::{Host, OpensslClientConfig, OpensslClientConfigBuilder, UpstreamAddr};
+
+static GLOBAL_TRACE_CONFIG: OnceLock<TraceConfig> = OnceLock::new();
+
+const DEFAULT_COLLECTOR_URL: &str = "http://127.0.0.1:4318/v1/traces";
+const DEFAULT_TRACES_PATH: &str = "/v1/traces";
+
+pub(crate) fn get_global_trace_config() -> Option<TraceConfig> {
+    GLOBAL_TRACE_CONFIG.get().cloned()
+}
+
+fn set_global_trace_config(config: TraceConfig) {
+    if GLOBAL_TRACE_CONFIG.set(config).is_err() {
+        warn!("Global trace config has already been set");
+    }
+}
+
+/// Config for the OTLP/HTTP (JSON encoding) trace exporter
+#[derive(Clone)]
+pub(crate) struct TraceConfig {
+    pub(crate) service_name: String,
+    pub(crate) collector: Url,
+    pub(crate) tls_client: Option<OpensslClientConfig>,
+    pub(crate) tls_name: Option<Host>,
+    pub(crate) channel_size: usize,
+    pub(crate) batch_size: usize,
+    pub(crate) flush_interval: Duration,
+    pub(crate) timeout: Duration,
+}
+
+impl Default for TraceConfig {
+    fn default() -> Self {
+        TraceConfig {
+            service_name: crate::build::PKG_NAME.to_string(),
+            collector: Url::from_str(DEFAULT_COLLECTOR_URL).unwrap(),
+            tls_client: None,
+            tls_name: None,
+            channel_size: 4096,
+            batch_size: 256,
+            flush_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(4),
+        }
+    }
+}
+
+impl TraceConfig {
+    fn set(&mut self, k: &str, v: &Yaml, lookup_dir: &Path) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "service_name" => {
+                self.service_name = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            "collector" | "url" => {
+                self.collector =
+                    g3_yaml::value::as_url(v).context(format!("invalid url value for key {k}"))?;
+                Ok(())
+            }
+            "tls_client" => {
+                let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                    v,
+                    Some(lookup_dir),
+                )
+                .context(format!(
+                    "invalid openssl tls client config value for key {k}"
+                ))?;
+                let tls_client = builder
+                    .build()
+                    .context("failed to build tls client config")?;
+                self.tls_client = Some(tls_client);
+                Ok(())
+            }
+            "tls_name" => {
+                let tls_name = g3_yaml::value::as_host(v)
+                    .context(format!("invalid tls server name value for key {k}"))?;
+                self.tls_name = Some(tls_name);
+                Ok(())
+            }
+            "channel_size" => {
+                self.channel_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "batch_size" => {
+                let size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                self.batch_size = size.max(1);
+                Ok(())
+            }
+            "flush_interval" => {
+                self.flush_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "timeout" => {
+                self.timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        match self.collector.scheme() {
+            "http" => {
+                if self.tls_client.is_some() {
+                    return Err(anyhow!("tls client config is set for http collector url"));
+                }
+            }
+            "https" => {
+                if self.tls_client.is_none() {
+                    let tls_client = OpensslClientConfigBuilder::with_cache_for_one_site()
+                        .build()
+                        .context("failed to build default tls client config")?;
+                    self.tls_client = Some(tls_client);
+                }
+            }
+            s => return Err(anyhow!("unsupported collector url scheme {s}")),
+        }
+        UpstreamAddr::try_from(&self.collector).context("invalid collector url")?;
+        if self.collector.path() == "/" {
+            self.collector.set_path(DEFAULT_TRACES_PATH);
+        }
+        if self.flush_interval.is_zero() {
+            return Err(anyhow!("flush interval should not be zero"));
+        }
+        if self.timeout.is_zero() {
+            return Err(anyhow!("timeout should not be zero"));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn load(v: &Yaml, lookup_dir: &Path) -> anyhow::Result<()> {
+    let mut config = TraceConfig::default();
+    match v {
+        Yaml::Hash(map) => {
+            g3_yaml::foreach_kv(map, |k, v| config.set(k, v, lookup_dir))?;
+        }
+        Yaml::String(_) => {
+            config.collector = g3_yaml::value::as_url(v).context("invalid collector url value")?;
+        }
+        Yaml::Null => return Ok(()),
+        _ => return Err(anyhow!("invalid value type")),
+    }
+    config.check()?;
+    set_global_trace_config(config);
+    Ok(())
+}
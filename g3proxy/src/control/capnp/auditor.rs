@@ -0,0 +1,69 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use g3_types::metrics::NodeName;
+
+use g3proxy_proto::auditor_capnp::auditor_control;
+
+use super::set_operation_result;
+use crate::audit::{Auditor, TlsAutoBypass};
+
+pub(super) struct AuditorControlImpl {
+    auditor: Arc<Auditor>,
+}
+
+impl AuditorControlImpl {
+    pub(super) fn new_client(name: &str) -> anyhow::Result<auditor_control::Client> {
+        let name = unsafe { NodeName::new_unchecked(name) };
+        let auditor =
+            crate::audit::get_auditor(&name).ok_or_else(|| anyhow!("no auditor named {name}"))?;
+        Ok(capnp_rpc::new_client(AuditorControlImpl { auditor }))
+    }
+
+    fn tls_auto_bypass(&self) -> capnp::Result<&Arc<TlsAutoBypass>> {
+        self.auditor.tls_auto_bypass().ok_or_else(|| {
+            capnp::Error::failed("tls auto bypass is not enabled on this auditor".to_string())
+        })
+    }
+}
+
+impl auditor_control::Server for AuditorControlImpl {
+    async fn list_tls_auto_bypass(
+        self: Rc<Self>,
+        _params: auditor_control::ListTlsAutoBypassParams,
+        mut results: auditor_control::ListTlsAutoBypassResults,
+    ) -> capnp::Result<()> {
+        let mut list = self.tls_auto_bypass()?.bypassed();
+        list.sort();
+        let mut builder = results.get().init_result(list.len() as u32);
+        for (i, (sni, ttl)) in list.iter().enumerate() {
+            let mut entry = builder.reborrow().get(i as u32);
+            entry.set_server_name(sni.as_str());
+            entry.set_ttl(ttl.as_secs());
+        }
+        Ok(())
+    }
+
+    async fn clear_tls_auto_bypass(
+        self: Rc<Self>,
+        params: auditor_control::ClearTlsAutoBypassParams,
+        mut results: auditor_control::ClearTlsAutoBypassResults,
+    ) -> capnp::Result<()> {
+        let server_name = params.get()?.get_server_name()?.to_str()?;
+        let auto_bypass = self.tls_auto_bypass()?;
+        if server_name.is_empty() {
+            auto_bypass.clear(None);
+        } else {
+            auto_bypass.clear(Some(server_name));
+        }
+        set_operation_result(results.get().init_result(), Ok(()));
+        Ok(())
+    }
+}
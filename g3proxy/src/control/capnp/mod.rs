@@ -9,6 +9,7 @@ mod common;
 use common::set_operation_result;
 mod proc;
 
+mod auditor;
 mod escaper;
 mod resolver;
 mod server;
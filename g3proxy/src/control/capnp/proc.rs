@@ -7,6 +7,7 @@ use std::rc::Rc;
 
 use g3_types::metrics::NodeName;
 
+use g3proxy_proto::auditor_capnp::auditor_control;
 use g3proxy_proto::escaper_capnp::escaper_control;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::resolver_capnp::resolver_control;
@@ -224,6 +225,18 @@ impl proc_control::Server for ProcControlImpl {
         )
     }
 
+    async fn get_auditor(
+        self: Rc<Self>,
+        params: proc_control::GetAuditorParams,
+        mut results: proc_control::GetAuditorResults,
+    ) -> capnp::Result<()> {
+        let auditor = params.get()?.get_name()?.to_str()?;
+        set_fetch_result::<auditor_control::Owned>(
+            results.get().init_auditor(),
+            super::auditor::AuditorControlImpl::new_client(auditor),
+        )
+    }
+
     async fn force_quit_offline_servers(
         self: Rc<Self>,
         _params: proc_control::ForceQuitOfflineServersParams,
@@ -9,6 +9,7 @@ use g3_types::metrics::NodeName;
 
 use g3proxy_proto::server_capnp::server_control;
 
+use super::set_operation_result;
 use crate::serve::ArcServer;
 
 pub(super) struct ServerControlImpl {
@@ -42,4 +43,16 @@ impl server_control::Server for ServerControlImpl {
             ))
         }
     }
+
+    async fn purge_http_cache(
+        self: Rc<Self>,
+        params: server_control::PurgeHttpCacheParams,
+        mut results: server_control::PurgeHttpCacheResults,
+    ) -> capnp::Result<()> {
+        let url = params.get()?.get_url()?.to_str()?;
+        let url = if url.is_empty() { None } else { Some(url) };
+        let r = self.server.purge_http_cache(url);
+        set_operation_result(results.get().init_result(), r);
+        Ok(())
+    }
 }
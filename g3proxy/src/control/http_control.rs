@@ -0,0 +1,241 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use http::{Method, StatusCode};
+use serde_json::json;
+
+use g3_daemon::control::http::{HttpControlHandler, HttpControlRequest, HttpControlResponse};
+use g3_types::metrics::NodeName;
+use g3_types::resolve::{QueryStrategy, ResolveStrategy};
+
+use crate::audit::TlsAutoBypass;
+use crate::resolve::HappyEyeballsResolveJob;
+
+const DEFAULT_RESOLUTION_DELAY: Duration = Duration::from_millis(50);
+
+pub struct HttpControlImpl;
+
+impl HttpControlImpl {
+    pub fn new_arc() -> Arc<Self> {
+        Arc::new(HttpControlImpl)
+    }
+}
+
+fn sorted_names(set: HashSet<NodeName>) -> HttpControlResponse {
+    let mut names = set.into_iter().collect::<Vec<_>>();
+    names.sort();
+    HttpControlResponse::from_names(names)
+}
+
+fn parse_name(name: &str) -> Result<NodeName, HttpControlResponse> {
+    NodeName::from_str(name)
+        .map_err(|e| HttpControlResponse::bad_request(format!("invalid name {name}: {e}")))
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse {
+        let segments = req.path_segments();
+        match (&req.method, segments.as_slice()) {
+            (&Method::GET, ["version"]) => HttpControlResponse::ok(json!(crate::build::VERSION)),
+            (&Method::GET, ["user-group"]) => sorted_names(crate::auth::get_names()),
+            (&Method::GET, ["resolver"]) => sorted_names(crate::resolve::get_names()),
+            (&Method::GET, ["auditor"]) => sorted_names(crate::audit::get_names()),
+            (&Method::GET, ["escaper"]) => sorted_names(crate::escape::get_names()),
+            (&Method::GET, ["server"]) => sorted_names(crate::serve::get_names()),
+            (&Method::POST, [r#type, name, "reload"]) => reload(r#type, name).await,
+            (&Method::POST, ["user-group", name, "publish"]) => publish_user_group(name, req).await,
+            (&Method::POST, ["escaper", name, "publish"]) => publish_escaper(name, req).await,
+            (&Method::GET, ["resolver", name, "query"]) => query_resolver(name, req).await,
+            (&Method::GET, ["server", name, "status"]) => server_status(name),
+            (&Method::GET, ["auditor", name, "tls-auto-bypass"]) => list_tls_auto_bypass(name),
+            (&Method::DELETE, ["auditor", name, "tls-auto-bypass"]) => {
+                clear_tls_auto_bypass(name, req)
+            }
+            (&Method::POST, ["offline-server", "force-quit"]) => {
+                crate::serve::force_quit_offline_servers();
+                HttpControlResponse::success()
+            }
+            (&Method::POST, ["offline-server", name, "force-quit"]) => match parse_name(name) {
+                Ok(name) => {
+                    crate::serve::force_quit_offline_server(&name);
+                    HttpControlResponse::success()
+                }
+                Err(rsp) => rsp,
+            },
+            (_, ["version" | "user-group" | "resolver" | "auditor" | "escaper" | "server"]) => {
+                HttpControlResponse::method_not_allowed()
+            }
+            _ => HttpControlResponse::not_found(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        !crate::serve::get_names().is_empty()
+    }
+}
+
+async fn reload(r#type: &str, name: &str) -> HttpControlResponse {
+    if let Err(rsp) = parse_name(name) {
+        return rsp;
+    }
+    let name = name.to_string();
+    let r = match r#type {
+        "user-group" => super::bridge::reload_user_group(name, None).await,
+        "resolver" => super::bridge::reload_resolver(name, None).await,
+        "auditor" => super::bridge::reload_auditor(name, None).await,
+        "escaper" => super::bridge::reload_escaper(name, None).await,
+        "server" => super::bridge::reload_server(name, None).await,
+        _ => return HttpControlResponse::not_found(),
+    };
+    HttpControlResponse::from_result(r)
+}
+
+async fn publish_user_group(name: &str, req: &HttpControlRequest) -> HttpControlResponse {
+    let name = match parse_name(name) {
+        Ok(name) => name,
+        Err(rsp) => return rsp,
+    };
+    let contents = match req.body_str() {
+        Ok(s) => s,
+        Err(e) => return HttpControlResponse::bad_request(e),
+    };
+    let user_group = crate::auth::get_or_insert_default(&name);
+    HttpControlResponse::from_result(user_group.publish_dynamic_users(contents).await)
+}
+
+async fn publish_escaper(name: &str, req: &HttpControlRequest) -> HttpControlResponse {
+    let name = match parse_name(name) {
+        Ok(name) => name,
+        Err(rsp) => return rsp,
+    };
+    let data = match req.body_str() {
+        Ok(s) => s,
+        Err(e) => return HttpControlResponse::bad_request(e),
+    };
+    let escaper = match crate::escape::get_escaper(&name) {
+        Ok(escaper) => escaper,
+        Err(e) => return HttpControlResponse::error(StatusCode::NOT_FOUND, e),
+    };
+    HttpControlResponse::from_result(escaper.publish(data).await)
+}
+
+async fn query_resolver(name: &str, req: &HttpControlRequest) -> HttpControlResponse {
+    let name = match parse_name(name) {
+        Ok(name) => name,
+        Err(rsp) => return rsp,
+    };
+    let Some(domain) = req.query_param("domain") else {
+        return HttpControlResponse::bad_request("no domain query parameter set");
+    };
+    let query = match req.query_param("strategy") {
+        Some(s) => match QueryStrategy::from_str(&s) {
+            Ok(q) => q,
+            Err(_) => return HttpControlResponse::bad_request(format!("invalid strategy {s}")),
+        },
+        None => QueryStrategy::default(),
+    };
+    let resolution_delay = match req.query_param("resolution_delay") {
+        Some(s) => match u64::from_str(&s) {
+            Ok(ms) => Duration::from_millis(ms),
+            Err(e) => {
+                return HttpControlResponse::bad_request(format!(
+                    "invalid resolution_delay {s}: {e}"
+                ));
+            }
+        },
+        None => DEFAULT_RESOLUTION_DELAY,
+    };
+
+    let handle = match crate::resolve::get_handle(&name) {
+        Ok(handle) => handle,
+        Err(e) => return HttpControlResponse::error(StatusCode::NOT_FOUND, e),
+    };
+    let strategy = ResolveStrategy {
+        query,
+        pick: Default::default(),
+    };
+    let mut job = match HappyEyeballsResolveJob::new_dyn(strategy, &handle, Arc::from(domain)) {
+        Ok(job) => job,
+        Err(e) => {
+            return HttpControlResponse::error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to create resolve job: {e:?}"),
+            );
+        }
+    };
+    match job.get_r1_or_first_many(resolution_delay, usize::MAX).await {
+        Ok(ips) => HttpControlResponse::from_names(ips.into_iter().map(|ip| ip.to_string())),
+        Err(e) => HttpControlResponse::error(StatusCode::BAD_GATEWAY, format!("{e:?}")),
+    }
+}
+
+fn get_tls_auto_bypass(name: &str) -> Result<Arc<TlsAutoBypass>, HttpControlResponse> {
+    let name = parse_name(name)?;
+    let Some(auditor) = crate::audit::get_auditor(&name) else {
+        return Err(HttpControlResponse::error(
+            StatusCode::NOT_FOUND,
+            format!("no auditor named {name} found"),
+        ));
+    };
+    auditor.tls_auto_bypass().cloned().ok_or_else(|| {
+        HttpControlResponse::error(
+            StatusCode::NOT_IMPLEMENTED,
+            "tls auto bypass is not enabled on this auditor",
+        )
+    })
+}
+
+fn list_tls_auto_bypass(name: &str) -> HttpControlResponse {
+    let auto_bypass = match get_tls_auto_bypass(name) {
+        Ok(v) => v,
+        Err(rsp) => return rsp,
+    };
+    let mut list = auto_bypass.bypassed();
+    list.sort();
+    let list = list
+        .into_iter()
+        .map(|(sni, ttl)| json!({"server_name": sni, "ttl": ttl.as_secs()}))
+        .collect::<Vec<_>>();
+    HttpControlResponse::ok(json!(list))
+}
+
+fn clear_tls_auto_bypass(name: &str, req: &HttpControlRequest) -> HttpControlResponse {
+    let auto_bypass = match get_tls_auto_bypass(name) {
+        Ok(v) => v,
+        Err(rsp) => return rsp,
+    };
+    auto_bypass.clear(req.query_param("server_name").as_deref());
+    HttpControlResponse::success()
+}
+
+fn server_status(name: &str) -> HttpControlResponse {
+    let name = match parse_name(name) {
+        Ok(name) => name,
+        Err(rsp) => return rsp,
+    };
+    let server = match crate::serve::get_server(&name) {
+        Ok(server) => server,
+        Err(e) => return HttpControlResponse::error(StatusCode::NOT_FOUND, e),
+    };
+    match server.get_server_stats() {
+        Some(stats) => HttpControlResponse::ok(json!({
+            "online": stats.is_online(),
+            "alive_task_count": stats.get_alive_count(),
+            "total_conn_count": stats.get_conn_total(),
+            "total_task_count": stats.get_task_total(),
+        })),
+        None => HttpControlResponse::error(
+            StatusCode::NOT_IMPLEMENTED,
+            "server status is not supported on this server",
+        ),
+    }
+}
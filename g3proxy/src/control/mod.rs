@@ -18,6 +18,9 @@ pub use local::{DaemonController, UniqueController};
 
 pub mod capnp;
 
+mod http_control;
+pub use http_control::HttpControlImpl;
+
 static IO_MUTEX: Mutex<Option<Mutex<()>>> = Mutex::const_new(Some(Mutex::const_new(())));
 
 pub(crate) async fn run_protected_io<F: Future>(future: F) -> Option<F::Output> {
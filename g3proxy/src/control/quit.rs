@@ -3,6 +3,7 @@
  * Copyright 2024-2025 ByteDance and/or its affiliates.
  */
 
+use g3_daemon::control::http::HttpController;
 use g3_daemon::control::quit::QuitAction;
 
 use super::local::{DaemonController, UniqueController};
@@ -12,6 +13,7 @@ pub struct QuitActor {}
 
 impl QuitAction for QuitActor {
     async fn do_release_controller(&self) {
+        HttpController::abort();
         DaemonController::abort().await;
     }
 
@@ -20,7 +22,7 @@ impl QuitAction for QuitActor {
         tokio::spawn(async move {
             daemon_ctl.await;
         });
-        Ok(())
+        HttpController::resume()
     }
 
     async fn do_graceful_shutdown(&self) {
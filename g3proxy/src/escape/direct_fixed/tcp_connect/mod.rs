@@ -124,7 +124,7 @@ impl DirectFixedEscaper {
         match tokio::time::timeout(config.connect.each_timeout(), sock.connect(peer)).await {
             Ok(Ok(ups_stream)) => {
                 self.stats.tcp.connect.add_success();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let local_addr = ups_stream
                     .local_addr()
@@ -137,7 +137,7 @@ impl DirectFixedEscaper {
             }
             Ok(Err(e)) => {
                 self.stats.tcp.connect.add_error();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::ConnectFailed(ConnectError::from(e));
                 if let Some(logger) = &self.escape_logger {
@@ -152,7 +152,7 @@ impl DirectFixedEscaper {
             }
             Err(_) => {
                 self.stats.tcp.connect.add_timeout();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::TimeoutByRule;
                 if let Some(logger) = &self.escape_logger {
@@ -181,12 +181,15 @@ impl DirectFixedEscaper {
         task_notes: &ServerTaskNotes,
     ) -> Result<TcpStream, TcpConnectError> {
         let max_tries_each_family = config.connect.max_tries();
-        let mut ips = resolver_job
+        let resolve_start = Instant::now();
+        let resolve_result = resolver_job
             .get_r1_or_first_many(
                 self.config.happy_eyeballs.resolution_delay(),
                 max_tries_each_family,
             )
-            .await?;
+            .await;
+        tcp_notes.set_resolve_time(resolve_start);
+        let mut ips = resolve_result?;
         let port = task_conf.upstream.port();
 
         let mut c_set = JoinSet::new();
@@ -244,7 +247,7 @@ impl DirectFixedEscaper {
                     biased;
 
                     r = c_set.join_next() => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         match r {
                             Some(Ok(r)) => {
                                 running_connection -= 1;
@@ -302,7 +305,7 @@ impl DirectFixedEscaper {
                     }
                 }
             } else if resolver_r2_done {
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
                 return Err(returned_err);
             } else {
                 match tokio::time::timeout(
@@ -317,11 +320,11 @@ impl DirectFixedEscaper {
                         spawn_new_connection = true;
                     }
                     Ok(Err(_e)) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(returned_err);
                     }
                     Err(_) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(TcpConnectError::TimeoutByRule);
                     }
                 }
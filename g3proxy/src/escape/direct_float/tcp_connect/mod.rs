@@ -123,7 +123,7 @@ impl DirectFloatEscaper {
         match tokio::time::timeout(config.connect.each_timeout(), sock.connect(peer)).await {
             Ok(Ok(ups_stream)) => {
                 self.stats.tcp.connect.add_success();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let local_addr = ups_stream
                     .local_addr()
@@ -136,7 +136,7 @@ impl DirectFloatEscaper {
             }
             Ok(Err(e)) => {
                 self.stats.tcp.connect.add_error();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::ConnectFailed(ConnectError::from(e));
                 if let Some(logger) = &self.escape_logger {
@@ -151,7 +151,7 @@ impl DirectFloatEscaper {
             }
             Err(_) => {
                 self.stats.tcp.connect.add_timeout();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::TimeoutByRule;
                 if let Some(logger) = &self.escape_logger {
@@ -180,12 +180,15 @@ impl DirectFloatEscaper {
         task_notes: &ServerTaskNotes,
     ) -> Result<(TcpStream, DirectFloatBindIp), TcpConnectError> {
         let max_tries_each_family = config.connect.max_tries();
-        let mut ips = resolver_job
+        let resolve_start = Instant::now();
+        let resolve_result = resolver_job
             .get_r1_or_first_many(
                 self.config.happy_eyeballs.resolution_delay(),
                 max_tries_each_family,
             )
-            .await?;
+            .await;
+        tcp_notes.set_resolve_time(resolve_start);
+        let mut ips = resolve_result?;
 
         let mut c_set = JoinSet::new();
 
@@ -242,7 +245,7 @@ impl DirectFloatEscaper {
                     biased;
 
                     r = c_set.join_next() => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         match r {
                             Some(Ok(r)) => {
                                 running_connection -= 1;
@@ -303,7 +306,7 @@ impl DirectFloatEscaper {
                     }
                 }
             } else if resolver_r2_done {
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
                 return Err(returned_err);
             } else {
                 match tokio::time::timeout(
@@ -318,11 +321,11 @@ impl DirectFloatEscaper {
                         spawn_new_connection = true;
                     }
                     Ok(Err(_e)) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(returned_err);
                     }
                     Err(_) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(TcpConnectError::TimeoutByRule);
                     }
                 }
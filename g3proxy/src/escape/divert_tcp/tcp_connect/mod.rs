@@ -99,7 +99,7 @@ impl DivertTcpEscaper {
         {
             Ok(Ok(ups_stream)) => {
                 self.stats.tcp.connect.add_success();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let local_addr = ups_stream
                     .local_addr()
@@ -111,7 +111,7 @@ impl DivertTcpEscaper {
             }
             Ok(Err(e)) => {
                 self.stats.tcp.connect.add_error();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::ConnectFailed(ConnectError::from(e));
                 if let Some(logger) = &self.escape_logger {
@@ -126,7 +126,7 @@ impl DivertTcpEscaper {
             }
             Err(_) => {
                 self.stats.tcp.connect.add_timeout();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::TimeoutByRule;
                 if let Some(logger) = &self.escape_logger {
@@ -155,12 +155,15 @@ impl DivertTcpEscaper {
         task_notes: &ServerTaskNotes,
     ) -> Result<TcpStream, TcpConnectError> {
         let max_tries_each_family = self.config.general.tcp_connect.max_tries();
-        let mut ips = resolver_job
+        let resolve_start = Instant::now();
+        let resolve_result = resolver_job
             .get_r1_or_first_many(
                 self.config.happy_eyeballs.resolution_delay(),
                 max_tries_each_family,
             )
-            .await?;
+            .await;
+        tcp_notes.set_resolve_time(resolve_start);
+        let mut ips = resolve_result?;
 
         let mut c_set = JoinSet::new();
 
@@ -216,7 +219,7 @@ impl DivertTcpEscaper {
                     biased;
 
                     r = c_set.join_next() => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         match r {
                             Some(Ok(r)) => {
                                 running_connection -= 1;
@@ -273,7 +276,7 @@ impl DivertTcpEscaper {
                     }
                 }
             } else if resolver_r2_done {
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
                 return Err(returned_err);
             } else {
                 match tokio::time::timeout(
@@ -288,11 +291,11 @@ impl DivertTcpEscaper {
                         spawn_new_connection = true;
                     }
                     Ok(Err(_e)) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(returned_err);
                     }
                     Err(_) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(TcpConnectError::TimeoutByRule);
                     }
                 }
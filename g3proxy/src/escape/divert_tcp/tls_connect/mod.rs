@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::Instant;
 
 use g3_daemon::stat::remote::{
     ArcTcpConnectionTaskRemoteStats, TcpConnectionTaskRemoteStatsWrapper,
@@ -55,7 +56,11 @@ impl DivertTcpEscaper {
         let connector = SslConnector::new(ssl, stream)
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
-        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
+        let handshake_start = Instant::now();
+        let handshake_result =
+            tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await;
+        tcp_notes.set_tls_time(handshake_start);
+        match handshake_result {
             Ok(Ok(stream)) => Ok(stream),
             Ok(Err(e)) => {
                 let e = anyhow::Error::new(e);
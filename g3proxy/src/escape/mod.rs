@@ -35,7 +35,9 @@ use crate::serve::ServerTaskNotes;
 
 mod registry;
 use registry::EscaperRegistry;
-pub(crate) use registry::{foreach as foreach_escaper, get_names, get_or_insert_default};
+pub(crate) use registry::{
+    foreach as foreach_escaper, get_config as get_escaper_config, get_names, get_or_insert_default,
+};
 
 mod stats;
 pub(crate) use stats::{
@@ -58,6 +60,7 @@ mod proxy_http;
 mod proxy_https;
 mod proxy_socks5;
 mod proxy_socks5s;
+mod proxy_ssh;
 mod route_client;
 mod route_failover;
 mod route_geoip;
@@ -83,7 +83,7 @@ impl ProxyFloatEscaper {
             self.try_connect_tcp(peer_addr, &tcp_notes.bind),
         )
         .await;
-        tcp_notes.duration = instant_now.elapsed();
+        tcp_notes.set_connect_time(instant_now);
         match ret {
             Ok(Ok(ups_stream)) => {
                 self.stats.tcp.connect.add_success();
@@ -8,6 +8,7 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
+use tokio::time::Instant;
 
 use g3_daemon::stat::remote::{
     ArcTcpConnectionTaskRemoteStats, TcpConnectionTaskRemoteStatsWrapper,
@@ -119,7 +120,11 @@ impl ProxyHttpEscaper {
         let connector = SslConnector::new(ssl, buf_stream.into_inner())
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
-        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
+        let handshake_start = Instant::now();
+        let handshake_result =
+            tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await;
+        tcp_notes.set_tls_time(handshake_start);
+        match handshake_result {
             Ok(Ok(stream)) => Ok(stream),
             Ok(Err(e)) => {
                 let e = anyhow::Error::new(e);
@@ -95,7 +95,7 @@ impl ProxyHttpsEscaper {
         {
             Ok(Ok(ups_stream)) => {
                 self.stats.tcp.connect.add_success();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let local_addr = ups_stream
                     .local_addr()
@@ -107,7 +107,7 @@ impl ProxyHttpsEscaper {
             }
             Ok(Err(e)) => {
                 self.stats.tcp.connect.add_error();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::ConnectFailed(ConnectError::from(e));
                 if let Some(logger) = &self.escape_logger {
@@ -122,7 +122,7 @@ impl ProxyHttpsEscaper {
             }
             Err(_) => {
                 self.stats.tcp.connect.add_timeout();
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
 
                 let e = TcpConnectError::TimeoutByRule;
                 if let Some(logger) = &self.escape_logger {
@@ -151,12 +151,15 @@ impl ProxyHttpsEscaper {
         task_notes: &ServerTaskNotes,
     ) -> Result<TcpStream, TcpConnectError> {
         let max_tries_each_family = self.config.general.tcp_connect.max_tries();
-        let mut ips = resolver_job
+        let resolve_start = Instant::now();
+        let resolve_result = resolver_job
             .get_r1_or_first_many(
                 self.config.happy_eyeballs.resolution_delay(),
                 max_tries_each_family,
             )
-            .await?;
+            .await;
+        tcp_notes.set_resolve_time(resolve_start);
+        let mut ips = resolve_result?;
 
         let mut c_set = JoinSet::new();
 
@@ -212,7 +215,7 @@ impl ProxyHttpsEscaper {
                     biased;
 
                     r = c_set.join_next() => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         match r {
                             Some(Ok(r)) => {
                                 running_connection -= 1;
@@ -269,7 +272,7 @@ impl ProxyHttpsEscaper {
                     }
                 }
             } else if resolver_r2_done {
-                tcp_notes.duration = instant_now.elapsed();
+                tcp_notes.set_connect_time(instant_now);
                 return Err(returned_err);
             } else {
                 match tokio::time::timeout(
@@ -284,11 +287,11 @@ impl ProxyHttpsEscaper {
                         spawn_new_connection = true;
                     }
                     Ok(Err(_e)) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(returned_err);
                     }
                     Err(_) => {
-                        tcp_notes.duration = instant_now.elapsed();
+                        tcp_notes.set_connect_time(instant_now);
                         return Err(TcpConnectError::TimeoutByRule);
                     }
                 }
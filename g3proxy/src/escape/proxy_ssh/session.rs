@@ -0,0 +1,268 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use log::warn;
+use russh::ChannelStream;
+use russh::client::{Handle, Handler, Msg};
+use russh::keys::ssh_key::{HashAlg, PublicKey};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use g3_types::net::UpstreamAddr;
+
+use crate::config::escaper::proxy_ssh::ProxySshEscaperConfig;
+use crate::module::tcp_connect::TcpConnectError;
+
+pub(super) struct ProxySshClientHandler {
+    config: Arc<ProxySshEscaperConfig>,
+}
+
+impl ProxySshClientHandler {
+    pub(super) fn new(config: Arc<ProxySshEscaperConfig>) -> Self {
+        ProxySshClientHandler { config }
+    }
+
+    fn verify_host_key(&self, key: &PublicKey) -> bool {
+        if !self.config.host_key_fingerprints.is_empty() {
+            let fingerprint = key.fingerprint(HashAlg::Sha256);
+            if self.config.host_key_fingerprints.contains(&fingerprint) {
+                return true;
+            }
+        }
+
+        if let Some(path) = &self.config.known_hosts_file {
+            let host = self.config.proxy_addr.host().to_string();
+            let port = self.config.proxy_addr.port();
+            match russh::keys::check_known_hosts_path(&host, port, key, path) {
+                Ok(found) => return found,
+                Err(e) => {
+                    warn!(
+                        "failed to check host key of ssh server {} with known hosts file {}: {e}",
+                        self.config.proxy_addr,
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl Handler for ProxySshClientHandler {
+    type Error = russh::Error;
+
+    async fn check_server_key(
+        &mut self,
+        server_public_key: &PublicKey,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.verify_host_key(server_public_key))
+    }
+}
+
+pub(super) fn map_ssh_error(e: russh::Error) -> TcpConnectError {
+    match e {
+        russh::Error::IO(e) => TcpConnectError::NegotiationReadFailed(e),
+        russh::Error::UnknownKey => {
+            TcpConnectError::NegotiationRejected("untrusted ssh host key".to_string())
+        }
+        russh::Error::ChannelOpenFailure(reason) => {
+            TcpConnectError::NegotiationRejected(format!("ssh channel open failure: {reason:?}"))
+        }
+        e => TcpConnectError::NegotiationRejected(format!("ssh error: {e}")),
+    }
+}
+
+/// An authenticated ssh session to the bastion server
+pub(super) struct ProxySshSession {
+    handle: Handle<ProxySshClientHandler>,
+    pub(super) peer_addr: Option<SocketAddr>,
+    pub(super) local_addr: Option<SocketAddr>,
+    alive_channels: AtomicUsize,
+}
+
+impl ProxySshSession {
+    pub(super) fn new(
+        handle: Handle<ProxySshClientHandler>,
+        peer_addr: Option<SocketAddr>,
+        local_addr: Option<SocketAddr>,
+    ) -> Self {
+        ProxySshSession {
+            handle,
+            peer_addr,
+            local_addr,
+            alive_channels: AtomicUsize::new(0),
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.handle.is_closed()
+    }
+
+    fn alive_channels(&self) -> usize {
+        self.alive_channels.load(Ordering::Relaxed)
+    }
+
+    /// Open a direct-tcpip channel to the upstream through this session
+    pub(super) async fn open_direct_tcpip(
+        self: &Arc<Self>,
+        upstream: &UpstreamAddr,
+        originator: SocketAddr,
+    ) -> Result<ProxySshChannelStream, TcpConnectError> {
+        let guard = ProxySshChannelGuard::new(self.clone());
+        let channel = self
+            .handle
+            .channel_open_direct_tcpip(
+                upstream.host().to_string(),
+                upstream.port() as u32,
+                originator.ip().to_string(),
+                originator.port() as u32,
+            )
+            .await
+            .map_err(map_ssh_error)?;
+        Ok(ProxySshChannelStream {
+            inner: Box::pin(channel.into_stream()),
+            _guard: guard,
+        })
+    }
+}
+
+/// Select the least loaded one, None will be returned if a new one should be created
+fn select_least_loaded<T>(
+    items: &[T],
+    load: impl Fn(&T) -> usize,
+    pool_size: usize,
+    max_load: usize,
+) -> Option<&T> {
+    let selected = items
+        .iter()
+        .filter(|v| load(v) < max_load)
+        .min_by_key(|v| load(v))?;
+    if load(selected) > 0 && items.len() < pool_size {
+        // grow the pool before sharing sessions
+        return None;
+    }
+    Some(selected)
+}
+
+/// All the reusable ssh sessions of an escaper
+#[derive(Default)]
+pub(super) struct ProxySshSessionPool {
+    sessions: Mutex<Vec<Arc<ProxySshSession>>>,
+    create_lock: tokio::sync::Mutex<()>,
+}
+
+impl ProxySshSessionPool {
+    /// Select the least loaded session, None will be returned if a new session should be created
+    pub(super) fn select(
+        &self,
+        pool_size: usize,
+        max_channels: usize,
+    ) -> Option<Arc<ProxySshSession>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|s| !s.is_closed());
+
+        select_least_loaded(
+            sessions.as_slice(),
+            |s| s.alive_channels(),
+            pool_size,
+            max_channels,
+        )
+        .cloned()
+    }
+
+    /// Wait until no other new session is being created, the returned guard should be held
+    /// when creating the new session
+    pub(super) async fn lock_create(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.create_lock.lock().await
+    }
+
+    /// Add a new session to the pool, it won't be added if the pool is already full
+    pub(super) fn add(&self, session: Arc<ProxySshSession>, pool_size: usize) {
+        let mut sessions = self.sessions.lock().unwrap();
+        if sessions.len() < pool_size {
+            sessions.push(session);
+        }
+    }
+}
+
+struct ProxySshChannelGuard {
+    session: Arc<ProxySshSession>,
+}
+
+impl ProxySshChannelGuard {
+    fn new(session: Arc<ProxySshSession>) -> Self {
+        session.alive_channels.fetch_add(1, Ordering::Relaxed);
+        ProxySshChannelGuard { session }
+    }
+}
+
+impl Drop for ProxySshChannelGuard {
+    fn drop(&mut self) {
+        self.session.alive_channels.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// The direct-tcpip channel stream, which will keep the ssh session alive
+pub(super) struct ProxySshChannelStream {
+    inner: Pin<Box<ChannelStream<Msg>>>,
+    _guard: ProxySshChannelGuard,
+}
+
+impl AsyncRead for ProxySshChannelStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.get_mut().inner.as_mut().poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ProxySshChannelStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().inner.as_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().inner.as_mut().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().inner.as_mut().poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_session() {
+        let load = |v: &usize| *v;
+
+        // empty pool
+        assert_eq!(select_least_loaded(&[], load, 4, 2), None);
+        // idle session should be reused
+        assert_eq!(select_least_loaded(&[1, 0], load, 4, 2), Some(&0));
+        // grow the pool before sharing
+        assert_eq!(select_least_loaded(&[1], load, 4, 2), None);
+        // share the least loaded one if the pool is full
+        assert_eq!(select_least_loaded(&[1, 0, 1, 1], load, 4, 2), Some(&0));
+        assert_eq!(select_least_loaded(&[2, 1, 1, 2], load, 4, 2), Some(&1));
+        // all sessions reach the max channels
+        assert_eq!(select_least_loaded(&[2, 2, 2, 2], load, 4, 2), None);
+    }
+}
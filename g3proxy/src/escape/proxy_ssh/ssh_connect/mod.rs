@@ -0,0 +1,245 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use russh::client::Handle;
+use russh::keys::PrivateKeyWithHashAlg;
+use tokio::time::Instant;
+
+use g3_daemon::stat::remote::{
+    ArcTcpConnectionTaskRemoteStats, TcpConnectionTaskRemoteStatsWrapper,
+};
+use g3_io_ext::{AsyncStream, LimitedReader, LimitedWriter};
+use g3_openssl::{SslConnector, SslStream};
+
+use super::{
+    ProxySshChannelStream, ProxySshClientHandler, ProxySshEscaper, ProxySshSession, map_ssh_error,
+};
+use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::serve::ServerTaskNotes;
+
+impl ProxySshEscaper {
+    async fn ssh_authenticate(
+        &self,
+        handle: &mut Handle<ProxySshClientHandler>,
+    ) -> Result<(), TcpConnectError> {
+        let username = self.config.proxy_username.as_original();
+
+        if let Some(key) = &self.config.proxy_private_key {
+            let hash_alg = handle
+                .best_supported_rsa_hash()
+                .await
+                .map_err(map_ssh_error)?
+                .flatten();
+            let r = handle
+                .authenticate_publickey(username, PrivateKeyWithHashAlg::new(key.clone(), hash_alg))
+                .await
+                .map_err(map_ssh_error)?;
+            if r.success() {
+                return Ok(());
+            }
+        }
+
+        if let Some(password) = &self.config.proxy_password {
+            let r = handle
+                .authenticate_password(username, password.as_original())
+                .await
+                .map_err(map_ssh_error)?;
+            if r.success() {
+                return Ok(());
+            }
+        }
+
+        Err(TcpConnectError::NegotiationRejected(
+            "ssh authentication failed".to_string(),
+        ))
+    }
+
+    async fn ssh_new_session(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<Arc<ProxySshSession>, TcpConnectError> {
+        let stream = self
+            .tcp_new_connection(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let handler = ProxySshClientHandler::new(self.config.clone());
+        let mut handle = russh::client::connect_stream(self.ssh_config.clone(), stream, handler)
+            .await
+            .map_err(map_ssh_error)?;
+        self.ssh_authenticate(&mut handle).await?;
+
+        let session = Arc::new(ProxySshSession::new(
+            handle,
+            tcp_notes.next,
+            tcp_notes.local,
+        ));
+        self.session_pool
+            .add(session.clone(), self.config.session_pool_size);
+        Ok(session)
+    }
+
+    fn ssh_select_session(
+        &self,
+        tcp_notes: &mut TcpConnectTaskNotes,
+    ) -> Option<Arc<ProxySshSession>> {
+        let session = self.session_pool.select(
+            self.config.session_pool_size,
+            self.config.session_max_channels,
+        )?;
+        tcp_notes.next = session.peer_addr;
+        tcp_notes.local = session.local_addr;
+        Some(session)
+    }
+
+    async fn ssh_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<ProxySshChannelStream, TcpConnectError> {
+        let session = match self.ssh_select_session(tcp_notes) {
+            Some(session) => session,
+            None => {
+                let _create_guard = self.session_pool.lock_create().await;
+                // the session may have been created by another task while waiting
+                match self.ssh_select_session(tcp_notes) {
+                    Some(session) => session,
+                    None => {
+                        self.ssh_new_session(task_conf, tcp_notes, task_notes)
+                            .await?
+                    }
+                }
+            }
+        };
+
+        // we can not determine the real upstream addr that the ssh server choose to connect to
+        session
+            .open_direct_tcpip(task_conf.upstream, task_notes.client_addr())
+            .await
+    }
+
+    pub(super) async fn timed_ssh_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<ProxySshChannelStream, TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.ssh_connect_tcp_connect_to(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    pub(super) async fn ssh_new_tcp_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let ups_s = self
+            .timed_ssh_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .await?;
+        let (ups_r, ups_w) = tokio::io::split(ups_s);
+
+        // add task and user stats
+        let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+        wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+        let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+
+        Ok((Box::new(ups_r), Box::new(ups_w)))
+    }
+
+    pub(super) async fn ssh_connect_tls_connect_to(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        tls_application: TlsApplication,
+    ) -> Result<SslStream<ProxySshChannelStream>, TcpConnectError> {
+        let ups_s = self
+            .timed_ssh_connect_tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
+            .await?;
+
+        let ssl = task_conf.build_ssl()?;
+        let connector = SslConnector::new(ssl, ups_s)
+            .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
+
+        let handshake_start = Instant::now();
+        let handshake_result =
+            tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await;
+        tcp_notes.set_tls_time(handshake_start);
+        match handshake_result {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(e)) => {
+                let e = anyhow::Error::new(e);
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeFailed(e))
+            }
+            Err(_) => {
+                let e = anyhow!("upstream tls handshake timed out");
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeTimeout)
+            }
+        }
+    }
+
+    pub(super) async fn ssh_new_tls_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let tls_stream = self
+            .ssh_connect_tls_connect_to(task_conf, tcp_notes, task_notes, TlsApplication::TcpStream)
+            .await?;
+
+        let (ups_r, ups_w) = tls_stream.into_split();
+
+        // add task and user stats
+        let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+        wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+        let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+
+        Ok((Box::new(ups_r), Box::new(ups_w)))
+    }
+}
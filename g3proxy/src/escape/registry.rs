@@ -113,7 +113,7 @@ pub(super) fn get_escaper(name: &NodeName) -> Option<ArcEscaper> {
     r.get_escaper(name)
 }
 
-pub(super) fn get_config(name: &NodeName) -> Option<AnyEscaperConfig> {
+pub(crate) fn get_config(name: &NodeName) -> Option<AnyEscaperConfig> {
     let r = RUNTIME_ESCAPER_REGISTRY.lock().unwrap();
     r.get_config(name)
 }
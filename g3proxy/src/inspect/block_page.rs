@@ -0,0 +1,204 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+
+use bytes::Bytes;
+use http::{Response, StatusCode, Version, header};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::config::audit::{AuditBlockPageConfig, AuditBlockPageFormat};
+
+const DEFAULT_HTML_TEMPLATE: &str = "<html>\n\
+     <head><title>Access Blocked</title></head>\n\
+     <body>\n\
+     <div style=\"text-align: center;\">\n\
+     <h1>Access to {{host}} is blocked</h1>\n\
+     <p>{{reason}}</p>\n\
+     <p>{{detail}}</p>\n\
+     <p><small>Task ID: {{task_id}}</small></p>\n\
+     </div>\n\
+     </body>\n\
+     </html>\n";
+
+/// The block page that will be sent to the client in place of the real response
+pub(crate) struct BlockPage<'a> {
+    config: &'a AuditBlockPageConfig,
+    status: StatusCode,
+    reason: &'a str,
+    host: &'a str,
+    detail: String,
+    task_id: &'a Uuid,
+}
+
+impl<'a> BlockPage<'a> {
+    pub(crate) fn new(
+        config: &'a AuditBlockPageConfig,
+        status: StatusCode,
+        reason: &'a str,
+        host: &'a str,
+        task_id: &'a Uuid,
+    ) -> Self {
+        BlockPage {
+            config,
+            status,
+            reason,
+            host,
+            detail: String::new(),
+            task_id,
+        }
+    }
+
+    pub(crate) fn set_detail(&mut self, detail: String) {
+        self.detail = detail;
+    }
+
+    #[inline]
+    pub(crate) fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn escape(&self, s: &str) -> String {
+        match self.config.format {
+            AuditBlockPageFormat::Html => {
+                let mut r = String::with_capacity(s.len());
+                for c in s.chars() {
+                    match c {
+                        '<' => r.push_str("&lt;"),
+                        '>' => r.push_str("&gt;"),
+                        '&' => r.push_str("&amp;"),
+                        '"' => r.push_str("&quot;"),
+                        '\'' => r.push_str("&#39;"),
+                        _ => r.push(c),
+                    }
+                }
+                r
+            }
+            AuditBlockPageFormat::Json => {
+                let quoted = json!(s).to_string();
+                quoted[1..quoted.len() - 1].to_string()
+            }
+        }
+    }
+
+    fn render_template(&self, template: &str) -> String {
+        template
+            .replace("{{reason}}", &self.escape(self.reason))
+            .replace("{{host}}", &self.escape(self.host))
+            .replace("{{detail}}", &self.escape(&self.detail))
+            .replace("{{task_id}}", &self.task_id.to_string())
+    }
+
+    pub(crate) fn render_body(&self) -> String {
+        match (&self.config.template, self.config.format) {
+            (Some(template), _) => self.render_template(template),
+            (None, AuditBlockPageFormat::Html) => self.render_template(DEFAULT_HTML_TEMPLATE),
+            (None, AuditBlockPageFormat::Json) => json!({
+                "status": self.status.as_u16(),
+                "reason": self.reason,
+                "host": self.host,
+                "detail": self.detail,
+                "task_id": self.task_id.to_string(),
+            })
+            .to_string(),
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self.config.format {
+            AuditBlockPageFormat::Html => "text/html; charset=utf-8",
+            AuditBlockPageFormat::Json => "application/json",
+        }
+    }
+
+    /// Serialize as a complete HTTP/1.x response, the connection should be closed after sent
+    pub(crate) fn serialize(&self, version: Version) -> Vec<u8> {
+        let body = self.render_body();
+        let mut s = String::with_capacity(256 + body.len());
+        let _ = write!(
+            s,
+            "{version:?} {} {}\r\n",
+            self.status.as_str(),
+            self.status.canonical_reason().unwrap_or("Blocked")
+        );
+        let _ = write!(s, "Content-Type: {}\r\n", self.content_type());
+        let _ = write!(s, "Content-Length: {}\r\n", body.len());
+        s.push_str("Cache-Control: no-store\r\n");
+        s.push_str("Connection: close\r\n\r\n");
+        s.push_str(&body);
+        s.into_bytes()
+    }
+
+    /// Build the HTTP/2 response header and body
+    pub(crate) fn to_h2_response(&self) -> Option<(Response<()>, Bytes)> {
+        let body = Bytes::from(self.render_body());
+        let rsp = Response::builder()
+            .version(Version::HTTP_2)
+            .status(self.status)
+            .header(header::CONTENT_TYPE, self.content_type())
+            .header(header::CONTENT_LENGTH, body.len())
+            .header(header::CACHE_CONTROL, "no-store")
+            .body(())
+            .ok()?;
+        Some((rsp, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn render_html() {
+        let config = AuditBlockPageConfig::default();
+        let task_id = Uuid::nil();
+        let mut page = BlockPage::new(
+            &config,
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "<a>.example.net",
+            &task_id,
+        );
+        page.set_detail("a & b".to_string());
+        let body = page.render_body();
+        assert!(body.contains("&lt;a&gt;.example.net"));
+        assert!(body.contains("a &amp; b"));
+        assert!(body.contains(&task_id.to_string()));
+    }
+
+    #[test]
+    fn render_json() {
+        let config = AuditBlockPageConfig {
+            format: AuditBlockPageFormat::Json,
+            template: Some(Arc::from("{\"msg\":\"{{reason}}\"}")),
+        };
+        let task_id = Uuid::nil();
+        let page = BlockPage::new(
+            &config,
+            StatusCode::FORBIDDEN,
+            "bad \"cert\"",
+            "www.example.net",
+            &task_id,
+        );
+        let v: serde_json::Value = serde_json::from_str(&page.render_body()).unwrap();
+        assert_eq!(v["msg"].as_str(), Some("bad \"cert\""));
+
+        let rsp = page.serialize(Version::HTTP_11);
+        assert!(rsp.starts_with(b"HTTP/1.1 403 Forbidden\r\n"));
+
+        let (rsp, body) = page.to_h2_response().unwrap();
+        assert_eq!(rsp.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            rsp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            rsp.headers().get(header::CONTENT_LENGTH).unwrap(),
+            body.len().to_string().as_str()
+        );
+    }
+}
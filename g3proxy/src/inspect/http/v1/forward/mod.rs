@@ -3,18 +3,19 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use futures_util::FutureExt;
-use http::header;
+use http::{Method, StatusCode, header};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::time::Instant;
 
 use g3_http::client::HttpTransparentResponse;
-use g3_http::server::HttpTransparentRequest;
+use g3_http::server::{HttpAdaptedRequest, HttpTransparentRequest};
 use g3_http::{HttpBodyReader, HttpBodyType};
 use g3_icap_client::reqmod::IcapReqmodClient;
 use g3_icap_client::reqmod::h1::{
@@ -26,13 +27,18 @@ use g3_icap_client::respmod::h1::{
 };
 use g3_io_ext::{LimitedBufReadExt, LimitedWriteExt, StreamCopy, StreamCopyError};
 use g3_slog_types::{LtDateTime, LtDuration, LtHttpHeaderValue, LtHttpMethod, LtHttpUri, LtUuid};
-use g3_types::net::HttpHeaderMap;
+use g3_types::net::{HttpHeaderMap, UpstreamAddr};
 
 use super::{HttpRequest, HttpRequestIo, HttpResponseIo};
 use crate::config::server::ServerConfig;
-use crate::inspect::StreamInspectContext;
+use crate::inspect::{BlockPage, StreamInspectContext};
+use crate::module::http_cache::{
+    CacheControl, HttpCacheBody, HttpCacheEntry, HttpCacheLookup, HttpCacheRecordReader,
+};
 use crate::module::http_forward::HttpProxyClientResponse;
 use crate::serve::{ServerIdleChecker, ServerTaskError, ServerTaskResult};
+use crate::trace::TraceContext;
+use crate::trace::inspect::InspectSpanForHttpForward;
 
 mod adaptation;
 pub(crate) use adaptation::HttpRequestWriterForAdaptation;
@@ -121,12 +127,18 @@ pub(super) struct H1ForwardTask<'a, SC: ServerConfig> {
     send_error_response: bool,
     should_close: bool,
     http_notes: HttpForwardTaskNotes,
+    http_cache_key: Option<String>,
+    http_cache_stale: Option<Arc<HttpCacheEntry>>,
+    http_cache_cond_req: Option<HttpTransparentRequest>,
+    http_cache_req_time: u64,
+    trace_ctx: Option<TraceContext>,
 }
 
 impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
     pub(super) fn new(ctx: StreamInspectContext<SC>, req: &'a HttpRequest, req_id: usize) -> Self {
         let http_notes = HttpForwardTaskNotes::new(req.datetime_received, req.time_received);
         let should_close = !req.inner.keep_alive();
+        let trace_ctx = ctx.task_notes.child_trace_ctx();
         H1ForwardTask {
             ctx,
             req: &req.inner,
@@ -134,18 +146,248 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
             send_error_response: true,
             should_close,
             http_notes,
+            http_cache_key: None,
+            http_cache_stale: None,
+            http_cache_cond_req: None,
+            http_cache_req_time: 0,
+            trace_ctx,
+        }
+    }
+
+    fn emit_trace_span(&self, error: Option<&str>) {
+        if let Some(trace_ctx) = &self.trace_ctx {
+            InspectSpanForHttpForward {
+                trace_ctx,
+                task_id: self.ctx.server_task_id(),
+                depth: self.ctx.inspection_depth,
+                protocol_version: "1.1",
+                method: &self.req.method,
+                uri: &self.req.uri,
+                started_at: &self.http_notes.receive_datetime,
+                rsp_status: self.http_notes.rsp_status,
+                dur_req_send_all: self.http_notes.dur_req_send_all,
+                dur_rsp_recv_hdr: self.http_notes.dur_rsp_recv_hdr,
+                dur_rsp_recv_all: self.http_notes.dur_rsp_recv_all,
+            }
+            .emit(self.http_notes.receive_ins.elapsed(), error);
+        }
+    }
+
+    pub(super) fn prepare_http_cache(&mut self, upstream: &UpstreamAddr, is_https: bool) {
+        if self.ctx.http_cache().is_none() {
+            return;
+        }
+        // use the host in request, as the upstream may be an ip address
+        let mut upstream = upstream.clone();
+        if let Some(host) = &self.req.host {
+            upstream.set_host(host.host().clone());
+        }
+        let path_and_query = self
+            .req
+            .uri
+            .path_and_query()
+            .map(|v| v.as_str())
+            .unwrap_or("/");
+        self.http_cache_key = Some(crate::module::http_cache::cache_key(
+            is_https,
+            &upstream,
+            path_and_query,
+        ));
+    }
+
+    /// Returns true if the response has been sent to the client
+    async fn lookup_http_cache<CW>(&mut self, clt_w: &mut CW) -> ServerTaskResult<bool>
+    where
+        CW: AsyncWrite + Unpin,
+    {
+        let Some((cache, stats)) = self.ctx.http_cache().cloned() else {
+            return Ok(false);
+        };
+        let Some(key) = &self.http_cache_key else {
+            return Ok(false);
+        };
+        if !crate::module::http_cache::request_cacheable(
+            &self.req.method,
+            &self.req.end_to_end_headers,
+            self.req.body_type().is_some(),
+        ) {
+            return Ok(false);
+        }
+
+        let req_cc = CacheControl::parse(&self.req.end_to_end_headers);
+        match cache
+            .lookup(key, &self.req.end_to_end_headers, &req_cc)
+            .await
+        {
+            HttpCacheLookup::Fresh(entry) => {
+                stats.add_hit();
+                self.send_cached_response(clt_w, &entry).await?;
+                return Ok(true);
+            }
+            HttpCacheLookup::Stale(entry) => {
+                if req_cc.only_if_cached {
+                    self.reply_gateway_timeout(clt_w).await;
+                    return Ok(true);
+                }
+                stats.add_miss();
+                // only validate if the client is not doing it itself
+                if self.req.method == Method::GET
+                    && !self
+                        .req
+                        .end_to_end_headers
+                        .contains_key(header::IF_NONE_MATCH)
+                    && !self
+                        .req
+                        .end_to_end_headers
+                        .contains_key(header::IF_MODIFIED_SINCE)
+                {
+                    let mut headers = self.req.end_to_end_headers.clone();
+                    for (name, value) in entry.validation_headers() {
+                        headers.insert(name, value);
+                    }
+                    let mut cond_req = self.req.adapt_without_body(HttpAdaptedRequest {
+                        method: self.req.method.clone(),
+                        uri: self.req.uri.clone(),
+                        version: self.req.version,
+                        headers,
+                        content_length: None,
+                    });
+                    cond_req.host.clone_from(&self.req.host);
+                    self.http_cache_cond_req = Some(cond_req);
+                    self.http_cache_stale = Some(entry);
+                }
+            }
+            HttpCacheLookup::Miss => {
+                if req_cc.only_if_cached {
+                    self.reply_gateway_timeout(clt_w).await;
+                    return Ok(true);
+                }
+                stats.add_miss();
+            }
+        }
+
+        self.http_cache_req_time = crate::module::http_cache::unix_now();
+        Ok(false)
+    }
+
+    async fn reply_gateway_timeout<CW>(&mut self, clt_w: &mut CW)
+    where
+        CW: AsyncWrite + Unpin,
+    {
+        let rsp = HttpProxyClientResponse::from_standard(
+            StatusCode::GATEWAY_TIMEOUT,
+            self.req.version,
+            self.should_close,
+        );
+        if rsp.reply_err_to_request(clt_w).await.is_ok() {
+            self.http_notes.rsp_status = rsp.status();
+        } else {
+            self.should_close = true;
         }
     }
 
+    async fn send_cached_response<CW>(
+        &mut self,
+        clt_w: &mut CW,
+        entry: &HttpCacheEntry,
+    ) -> ServerTaskResult<()>
+    where
+        CW: AsyncWrite + Unpin,
+    {
+        let not_modified = entry.not_modified_for(&self.req.end_to_end_headers);
+        let header = entry.serialize_header(
+            self.req.version,
+            not_modified,
+            self.should_close,
+            crate::module::http_cache::unix_now(),
+        );
+        self.send_error_response = false;
+        clt_w
+            .write_all(&header)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        if !not_modified && self.req.method != Method::HEAD {
+            clt_w
+                .write_all(entry.body())
+                .await
+                .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        }
+        clt_w
+            .flush()
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.http_notes.rsp_status = if not_modified { 304 } else { entry.code() };
+        Ok(())
+    }
+
+    fn store_http_cache(&self, rsp: &HttpTransparentResponse, body: Bytes) {
+        let (Some((cache, stats)), Some(key)) = (self.ctx.http_cache(), &self.http_cache_key)
+        else {
+            return;
+        };
+        let entry = HttpCacheEntry::new(
+            key.clone(),
+            &self.req.end_to_end_headers,
+            rsp.code,
+            &rsp.reason,
+            &rsp.end_to_end_headers,
+            body,
+            self.http_cache_req_time,
+            crate::module::http_cache::unix_now(),
+            cache.heuristic_max_age(),
+        );
+        cache.store(Arc::new(entry));
+        stats.add_stored();
+    }
+
     #[inline]
     pub(super) fn should_close(&self) -> bool {
         self.should_close
     }
 
+    async fn reply_block_page<CW>(&mut self, reason: &str, clt_w: &mut CW) -> bool
+    where
+        CW: AsyncWrite + Unpin,
+    {
+        let Some(config) = self.ctx.block_page() else {
+            return false;
+        };
+        let host = self
+            .req
+            .host
+            .as_ref()
+            .map(|v| v.host().to_string())
+            .unwrap_or_default();
+        let block_page = BlockPage::new(
+            config,
+            StatusCode::FORBIDDEN,
+            reason,
+            &host,
+            self.ctx.server_task_id(),
+        );
+        let buf = block_page.serialize(self.req.version);
+        self.should_close = true;
+        if clt_w.write_all_flush(&buf).await.is_ok() {
+            self.http_notes.rsp_status = block_page.status().as_u16();
+        }
+        true
+    }
+
     async fn reply_task_err<CW>(&mut self, e: &ServerTaskError, clt_w: &mut CW)
     where
         CW: AsyncWrite + Unpin,
     {
+        let block_reason = match e {
+            ServerTaskError::ForbiddenByRule(_) => Some("forbidden by rule"),
+            ServerTaskError::CanceledAsUserBlocked => Some("user blocked"),
+            _ => None,
+        };
+        if let Some(reason) = block_reason
+            && self.reply_block_page(reason, clt_w).await
+        {
+            return;
+        }
+
         let body_pending = self.req.body_type().is_some();
         let rsp = HttpProxyClientResponse::from_task_err(
             e,
@@ -180,8 +422,10 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
             if self.send_error_response {
                 self.reply_task_err(&e, &mut rsp_io.clt_w).await;
             }
+            self.emit_trace_span(Some(e.brief()));
             intercept_log!(self, "{e}");
         } else {
+            self.emit_trace_span(None);
             intercept_log!(self, "ok");
         }
     }
@@ -219,6 +463,7 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                 } else {
                     let e = ServerTaskError::InternalAdapterError(e);
                     self.reply_task_err(&e, &mut rsp_io.clt_w).await;
+                    self.emit_trace_span(Some(e.brief()));
                     intercept_log!(self, "{e:?}");
                 }
                 return;
@@ -242,12 +487,14 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
 
         match r {
             Ok(_) => {
+                self.emit_trace_span(None);
                 intercept_log!(self, "ok");
             }
             Err(e) => {
                 if self.send_error_response {
                     self.reply_task_err(&e, &mut rsp_io.clt_w).await;
                 }
+                self.emit_trace_span(Some(e.brief()));
                 intercept_log!(self, "{e}");
             }
         }
@@ -270,12 +517,14 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         };
         match r {
             Ok(_) => {
+                self.emit_trace_span(None);
                 intercept_log!(self, "ok");
             }
             Err(e) => {
                 if self.send_error_response {
                     self.reply_task_err(&e, &mut rsp_io.clt_w).await;
                 }
+                self.emit_trace_span(Some(e.brief()));
                 intercept_log!(self, "{e}");
             }
         }
@@ -294,13 +543,15 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         UR: AsyncRead + Unpin,
         UW: AsyncWrite + Send + Unpin,
     {
+        // the rewritten request will be sent to the ICAP server for adaptation
+        let rewritten_req = self.rewrite_request();
         let mut ups_w_adaptation = HttpRequestWriterForAdaptation {
             inner: &mut rsp_io.ups_w,
         };
         let mut adaptation_fut = icap_adapter
             .xfer(
                 adaptation_state,
-                self.req,
+                rewritten_req.as_ref().unwrap_or(self.req),
                 Some(&mut req_io.clt_r),
                 &mut ups_w_adaptation,
             )
@@ -397,9 +648,16 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         W: AsyncWrite + Unpin,
     {
         self.should_close = true;
+        self.send_error_response = false;
+
+        if rsp.status == StatusCode::FORBIDDEN
+            && rsp_recv_body.is_none()
+            && self.reply_block_page("blocked by icap server", clt_w).await
+        {
+            return Ok(());
+        }
 
         let buf = rsp.serialize(self.should_close);
-        self.send_error_response = false;
         clt_w
             .write_all(buf.as_ref())
             .await
@@ -430,11 +688,27 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         Ok(())
     }
 
+    /// Get the request with the header rewrite rules applied, None if no rules are set
+    fn rewrite_request(&self) -> Option<HttpTransparentRequest> {
+        if !self.ctx.has_http_request_header_rewrite() {
+            return None;
+        }
+        let req = self.http_cache_cond_req.as_ref().unwrap_or(self.req);
+        let mut headers = req.end_to_end_headers.clone();
+        self.ctx.rewrite_http_request_header(&mut headers);
+        Some(req.clone_with_headers(headers))
+    }
+
     async fn send_request_header<UW>(&mut self, ups_w: &mut UW) -> ServerTaskResult<()>
     where
         UW: AsyncWrite + Unpin,
     {
-        let head_bytes = self.req.serialize_for_origin();
+        let rewritten_req = self.rewrite_request();
+        let head_bytes = rewritten_req
+            .as_ref()
+            .or(self.http_cache_cond_req.as_ref())
+            .unwrap_or(self.req)
+            .serialize_for_origin();
         ups_w
             .write_all_flush(&head_bytes)
             .await
@@ -453,6 +727,10 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         CW: AsyncWrite + Send + Unpin,
         UW: AsyncWrite + Unpin,
     {
+        if self.lookup_http_cache(&mut rsp_io.clt_w).await? {
+            return Ok(());
+        }
+
         self.send_request_header(&mut rsp_io.ups_w).await?;
         self.http_notes.mark_req_no_body();
 
@@ -467,7 +745,30 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         )
         .await
         {
-            Ok(Ok((rsp, head_bytes))) => self.send_response(rsp, head_bytes, rsp_io, None).await,
+            Ok(Ok((rsp, head_bytes))) => {
+                if rsp.code == 304
+                    && let Some(entry) = self.http_cache_stale.take()
+                    && let Some((cache, stats)) = self.ctx.http_cache().cloned()
+                {
+                    // the stored response is validated
+                    if !rsp.keep_alive() {
+                        self.should_close = true;
+                    }
+                    self.http_notes.origin_status = rsp.code;
+                    self.http_notes.mark_rsp_recv_hdr();
+                    self.http_notes.mark_rsp_no_body();
+                    let entry = Arc::new(entry.freshen(
+                        &rsp.end_to_end_headers,
+                        self.http_cache_req_time,
+                        crate::module::http_cache::unix_now(),
+                        cache.heuristic_max_age(),
+                    ));
+                    cache.store(entry.clone());
+                    stats.add_revalidated();
+                    return self.send_cached_response(&mut rsp_io.clt_w, &entry).await;
+                }
+                self.send_response(rsp, head_bytes, rsp_io, None).await
+            }
             Ok(Err(e)) => Err(e.into()),
             Err(_) => Err(ServerTaskError::UpstreamAppTimeout(
                 "timeout to receive response header",
@@ -668,6 +969,23 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         self.http_notes.rsp_status = 0;
         self.http_notes.mark_rsp_recv_hdr();
 
+        // unsafe methods invalidate the stored response, see rfc9111 Section 4.4
+        if !self.req.method.is_safe()
+            && rsp.code < 400
+            && let Some((cache, _)) = self.ctx.http_cache()
+            && let Some(key) = &self.http_cache_key
+        {
+            cache.invalidate(key);
+        }
+
+        let rsp_head = if self.ctx.has_http_response_header_rewrite() {
+            self.ctx
+                .rewrite_http_response_header(&mut rsp.end_to_end_headers);
+            Bytes::from(rsp.serialize())
+        } else {
+            rsp_head
+        };
+
         if let Some(respmod) = self.ctx.audit_handle.icap_respmod_client() {
             match respmod
                 .h1_adapter(
@@ -758,20 +1076,46 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
     {
         self.send_error_response = false;
 
+        let store_cache = self.req.method == Method::GET
+            && self.http_cache_key.is_some()
+            && crate::module::http_cache::response_storable(
+                &self.req.end_to_end_headers,
+                rsp.code,
+                &rsp.end_to_end_headers,
+            );
+
         if let Some(body_type) = rsp.body_type(&self.req.method) {
             self.http_notes.rsp_status = self.http_notes.origin_status; // the following function must send rsp header out
+            let mut cache_body = match self.ctx.http_cache() {
+                Some((cache, _)) if store_cache => {
+                    Some(HttpCacheBody::new(body_type, cache.max_object_size()))
+                }
+                _ => None,
+            };
             self.send_response_body(
                 rsp_head.into(),
                 &mut rsp_io.ups_r,
                 &mut rsp_io.clt_w,
                 body_type,
+                cache_body.as_mut(),
             )
-            .await
+            .await?;
+            if let Some(cache_body) = cache_body
+                && let Some(body) = cache_body
+                    .finish(self.ctx.h1_interception().body_line_max_len)
+                    .await
+            {
+                self.store_http_cache(&rsp, body);
+            }
+            Ok(())
         } else {
             self.send_response_header(&mut rsp_io.clt_w, rsp_head)
                 .await?;
             self.http_notes.rsp_status = self.http_notes.origin_status;
             self.http_notes.mark_rsp_no_body();
+            if store_cache {
+                self.store_http_cache(&rsp, Bytes::new());
+            }
             Ok(())
         }
     }
@@ -796,16 +1140,18 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         ups_r: &mut UR,
         clt_w: &mut CW,
         body_type: HttpBodyType,
+        cache_body: Option<&mut HttpCacheBody>,
     ) -> ServerTaskResult<()>
     where
         UR: AsyncBufRead + Unpin,
         CW: AsyncWrite + Unpin,
     {
-        let mut body_reader = HttpBodyReader::new(
+        let body_reader = HttpBodyReader::new(
             ups_r,
             body_type,
             self.ctx.h1_interception().body_line_max_len,
         );
+        let mut body_reader = HttpCacheRecordReader::new(body_reader, cache_body);
 
         let mut ups_to_clt = StreamCopy::with_data(
             &mut body_reader,
@@ -12,6 +12,7 @@ use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use g3_dpi::Protocol;
 use g3_io_ext::{FlexBufReader, LimitedBufReadExt};
 use g3_slog_types::LtUuid;
+use g3_types::net::UpstreamAddr;
 
 use crate::config::server::ServerConfig;
 use crate::inspect::{
@@ -55,14 +56,22 @@ struct H1InterceptIo {
 pub(crate) struct H1InterceptObject<SC: ServerConfig> {
     io: Option<H1InterceptIo>,
     ctx: StreamInspectContext<SC>,
+    upstream: UpstreamAddr,
+    is_https: bool,
     req_id: usize,
 }
 
 impl<SC: ServerConfig> H1InterceptObject<SC> {
-    pub(crate) fn new(ctx: StreamInspectContext<SC>) -> Self {
+    pub(crate) fn new(
+        ctx: StreamInspectContext<SC>,
+        upstream: UpstreamAddr,
+        is_https: bool,
+    ) -> Self {
         H1InterceptObject {
             io: None,
             ctx,
+            upstream,
+            is_https,
             req_id: 0,
         }
     }
@@ -173,6 +182,7 @@ where
                 }
                 HttpRecvRequest::RequestWithoutIo(r) => {
                     let mut forward_task = H1ForwardTask::new(self.ctx.clone(), &r, self.req_id);
+                    forward_task.prepare_http_cache(&self.upstream, self.is_https);
                     // not ICAP in this case
                     forward_task.forward_without_body(&mut rsp_io).await;
                     pipeline_stats.del_task();
@@ -227,6 +237,7 @@ where
                     } else {
                         let mut forward_task =
                             H1ForwardTask::new(self.ctx.clone(), &r, self.req_id);
+                        forward_task.prepare_http_cache(&self.upstream, self.is_https);
                         if let Some(reqmod_client) = self.ctx.audit_handle.icap_reqmod_client() {
                             forward_task
                                 .adapt_with_io(&mut req_io, &mut rsp_io, reqmod_client)
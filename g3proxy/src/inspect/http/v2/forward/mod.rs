@@ -27,8 +27,10 @@ use g3_types::net::HttpHeaderMap;
 
 use super::{H2BodyTransfer, H2StreamTransferError};
 use crate::config::server::ServerConfig;
-use crate::inspect::StreamInspectContext;
+use crate::inspect::{BlockPage, StreamInspectContext};
 use crate::serve::ServerIdleChecker;
+use crate::trace::TraceContext;
+use crate::trace::inspect::InspectSpanForHttpForward;
 
 macro_rules! intercept_log {
     ($obj:tt, $($args:tt)+) => {
@@ -120,6 +122,7 @@ pub(crate) struct H2ForwardTask<SC: ServerConfig> {
     ups_stream_id: Option<StreamId>,
     send_error_response: bool,
     http_notes: HttpForwardTaskNotes,
+    trace_ctx: Option<TraceContext>,
 }
 
 impl<SC> H2ForwardTask<SC>
@@ -132,16 +135,67 @@ where
         req: &Request<RecvStream>,
     ) -> Self {
         let http_notes = HttpForwardTaskNotes::new(req.method().clone(), req.uri().clone());
+        let trace_ctx = ctx.task_notes.child_trace_ctx();
         H2ForwardTask {
             ctx,
             clt_stream_id,
             ups_stream_id: None,
             send_error_response: false,
             http_notes,
+            trace_ctx,
         }
     }
 
+    fn emit_trace_span(&self, error: Option<&str>) {
+        if let Some(trace_ctx) = &self.trace_ctx {
+            InspectSpanForHttpForward {
+                trace_ctx,
+                task_id: self.ctx.server_task_id(),
+                depth: self.ctx.inspection_depth,
+                protocol_version: "2",
+                method: &self.http_notes.method,
+                uri: &self.http_notes.uri,
+                started_at: &self.http_notes.started_datetime,
+                rsp_status: self.http_notes.rsp_status,
+                dur_req_send_all: self.http_notes.dur_req_send_all,
+                dur_rsp_recv_hdr: self.http_notes.dur_rsp_recv_hdr,
+                dur_rsp_recv_all: self.http_notes.dur_rsp_recv_all,
+            }
+            .emit(self.http_notes.started_ins.elapsed(), error);
+        }
+    }
+
+    fn reply_block_page(&mut self, reason: &str, clt_send_rsp: &mut SendResponse<Bytes>) -> bool {
+        let Some(config) = self.ctx.block_page() else {
+            return false;
+        };
+        let host = self.http_notes.uri.host().unwrap_or_default();
+        let block_page = BlockPage::new(
+            config,
+            StatusCode::FORBIDDEN,
+            reason,
+            host,
+            self.ctx.server_task_id(),
+        );
+        let Some((rsp, body)) = block_page.to_h2_response() else {
+            return false;
+        };
+        self.send_error_response = false;
+        let rsp_status = rsp.status().as_u16();
+        if let Ok(mut clt_send_stream) = clt_send_rsp.send_response(rsp, false) {
+            self.http_notes.rsp_status = rsp_status;
+            let _ = clt_send_stream.send_data(body, true);
+        }
+        true
+    }
+
     fn reply_task_err(&mut self, mut clt_send_rsp: SendResponse<Bytes>, e: &H2StreamTransferError) {
+        if matches!(e, H2StreamTransferError::CanceledAsUserBlocked)
+            && self.reply_block_page("user blocked", &mut clt_send_rsp)
+        {
+            return;
+        }
+
         if let Some(rsp) = e.build_reply() {
             let rsp_status = rsp.status().as_u16();
             if clt_send_rsp.send_response(rsp, true).is_ok() {
@@ -181,8 +235,10 @@ where
             if self.send_error_response {
                 self.reply_task_err(clt_send_rsp, &e);
             }
+            self.emit_trace_span(Some(&e.to_string()));
             intercept_log!(self, "{e}");
         } else {
+            self.emit_trace_span(None);
             intercept_log!(self, "finished");
         }
     }
@@ -200,6 +256,7 @@ where
         } else if parts.headers.contains_key(http::header::EXPECT) {
             return self.reply_expectation_failed(clt_send_rsp);
         }
+        self.ctx.rewrite_http_request_header(&mut parts.headers);
 
         let ups_send_req = match tokio::time::timeout(
             self.ctx.h2_interception().upstream_stream_open_timeout,
@@ -324,6 +381,13 @@ where
         rsp: HttpAdapterErrorResponse,
         rsp_recv_body: Option<ReqmodRecvHttpResponseBody>,
     ) -> Result<(), H2StreamTransferError> {
+        if rsp.status == StatusCode::FORBIDDEN
+            && rsp_recv_body.is_none()
+            && self.reply_block_page("blocked by icap server", clt_send_rsp)
+        {
+            return Ok(());
+        }
+
         let response = Response::new(());
         let (mut parts, _) = response.into_parts();
         parts.version = Version::HTTP_2;
@@ -523,7 +587,8 @@ where
         clt_send_rsp: &mut SendResponse<Bytes>,
         adaptation_respond_shared_headers: Option<HttpHeaderMap>,
     ) -> Result<(), H2StreamTransferError> {
-        let (parts, ups_body) = ups_rsp.into_parts();
+        let (mut parts, ups_body) = ups_rsp.into_parts();
+        self.ctx.rewrite_http_response_header(&mut parts.headers);
         let clt_rsp = Response::from_parts(parts, ());
 
         self.http_notes.origin_status = clt_rsp.status().as_u16();
@@ -21,13 +21,22 @@ use g3_types::net::{Host, OpensslClientConfig};
 
 use crate::audit::AuditHandle;
 use crate::auth::{User, UserForbiddenStats, UserSite};
+use crate::config::audit::{AuditBlockPageConfig, AuditTlsCertErrorAction};
 use crate::config::server::ServerConfig;
+use crate::module::http_cache::{HttpCache, HttpCacheStats};
+use crate::module::http_header::{
+    HttpHeaderRewriteContext, HttpHeaderRewriteRules, HttpHeaderRewriteTarget,
+};
 use crate::module::tcp_connect::TcpConnectTaskNotes;
 use crate::serve::{ArcServerStats, ServerIdleChecker, ServerTaskNotes};
+use crate::trace::TraceContext;
 
 mod error;
 pub(crate) use error::InterceptionError;
 
+mod block_page;
+use block_page::BlockPage;
+
 pub(crate) mod stream;
 pub(crate) use stream::StreamTransitTask;
 
@@ -43,6 +52,9 @@ mod websocket;
 pub(crate) mod imap;
 pub(crate) mod smtp;
 
+#[cfg(feature = "quic")]
+pub(crate) mod quic;
+
 #[derive(Clone)]
 pub(super) struct StreamInspectUserContext {
     raw_user_name: Option<Arc<str>>,
@@ -67,6 +79,7 @@ pub(crate) struct StreamInspectTaskNotes {
     pub(crate) server_addr: SocketAddr,
     worker_id: Option<usize>,
     user_ctx: Option<StreamInspectUserContext>,
+    trace_ctx: Option<TraceContext>,
 }
 
 impl StreamInspectTaskNotes {
@@ -84,6 +97,11 @@ impl StreamInspectTaskNotes {
     pub(crate) fn task_id(&self) -> &Uuid {
         &self.task_id
     }
+
+    /// Create a trace context for the span of an inspected request, as a child of the task span
+    pub(crate) fn child_trace_ctx(&self) -> Option<TraceContext> {
+        self.trace_ctx.as_ref().map(|ctx| ctx.new_child())
+    }
 }
 
 impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
@@ -99,6 +117,7 @@ impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
                 user_site: ctx.user_site().cloned(),
                 forbidden_stats: ctx.forbidden_stats().clone(),
             }),
+            trace_ctx: task_notes.trace_ctx,
         }
     }
 }
@@ -127,6 +146,7 @@ pub(crate) struct StreamInspectContext<SC: ServerConfig> {
     task_notes: StreamInspectTaskNotes,
     connect_notes: StreamInspectConnectNotes,
     inspection_depth: usize,
+    http_cache: Option<(Arc<HttpCache>, Arc<HttpCacheStats>)>,
 
     max_idle_count: usize,
 }
@@ -142,6 +162,7 @@ impl<SC: ServerConfig> Clone for StreamInspectContext<SC> {
             task_notes: self.task_notes.clone(),
             connect_notes: self.connect_notes,
             inspection_depth: self.inspection_depth,
+            http_cache: self.http_cache.clone(),
             max_idle_count: self.max_idle_count,
         }
     }
@@ -171,10 +192,60 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
             task_notes: StreamInspectTaskNotes::from(task_notes),
             connect_notes: StreamInspectConnectNotes::from(tcp_notes),
             inspection_depth: 0,
+            http_cache: None,
             max_idle_count,
         }
     }
 
+    pub(crate) fn set_http_cache(&mut self, cache: Arc<HttpCache>, stats: Arc<HttpCacheStats>) {
+        self.http_cache = Some((cache, stats));
+    }
+
+    /// Get the http cache, which is not used if ICAP adaptation is enabled
+    fn http_cache(&self) -> Option<&(Arc<HttpCache>, Arc<HttpCacheStats>)> {
+        if self.audit_handle.icap_reqmod_client().is_some()
+            || self.audit_handle.icap_respmod_client().is_some()
+        {
+            return None;
+        }
+        self.http_cache.as_ref()
+    }
+
+    fn http_header_rewrite_rules(&self) -> HttpHeaderRewriteRules<'_> {
+        HttpHeaderRewriteRules::new(self.server_config.http_header_rewrite(), self.user())
+    }
+
+    #[inline]
+    fn has_http_request_header_rewrite(&self) -> bool {
+        self.http_header_rewrite_rules().has_request()
+    }
+
+    #[inline]
+    fn has_http_response_header_rewrite(&self) -> bool {
+        self.http_header_rewrite_rules().has_response()
+    }
+
+    fn http_header_rewrite_context(&self) -> HttpHeaderRewriteContext<'_> {
+        HttpHeaderRewriteContext {
+            username: self.user().map(|u| u.name().as_ref()),
+            client_ip: self.task_notes.client_addr.ip(),
+            egress_ip: Some(self.connect_notes.client_addr.ip()),
+            task_id: self.server_task_id(),
+        }
+    }
+
+    /// Apply the server and then the user request header rewrite rules
+    fn rewrite_http_request_header<M: HttpHeaderRewriteTarget>(&self, headers: &mut M) {
+        self.http_header_rewrite_rules()
+            .rewrite_request(&self.http_header_rewrite_context(), headers);
+    }
+
+    /// Apply the server and then the user response header rewrite rules
+    fn rewrite_http_response_header<M: HttpHeaderRewriteTarget>(&self, headers: &mut M) {
+        self.http_header_rewrite_rules()
+            .rewrite_response(&self.http_header_rewrite_context(), headers);
+    }
+
     #[inline]
     fn user(&self) -> Option<&User> {
         self.task_notes.user().map(|u| u.as_ref())
@@ -252,6 +323,16 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.audit_handle.tls_interception()
     }
 
+    #[inline]
+    fn tls_cert_error_action(&self) -> AuditTlsCertErrorAction {
+        self.audit_handle.tls_cert_error_action()
+    }
+
+    #[inline]
+    fn block_page(&self) -> Option<&AuditBlockPageConfig> {
+        self.audit_handle.block_page()
+    }
+
     pub(crate) fn user_site_tls_client(&self) -> Option<&OpensslClientConfig> {
         self.task_notes
             .user_ctx
@@ -349,6 +430,7 @@ pub(crate) enum StreamInspection<SC: ServerConfig> {
     End,
     StreamUnknown(stream::StreamInspectObject<SC>),
     StreamInspect(stream::StreamInspectObject<SC>),
+    StreamBypass(stream::StreamInspectObject<SC>),
     TlsModern(tls::TlsInterceptObject<SC>),
     #[cfg(feature = "vendored-tongsuo")]
     TlsTlcp(tls::TlsInterceptObject<SC>),
@@ -362,3 +444,34 @@ pub(crate) enum StreamInspection<SC: ServerConfig> {
 
 type BoxAsyncRead = Box<dyn AsyncRead + Send + Sync + Unpin + 'static>;
 type BoxAsyncWrite = Box<dyn AsyncWrite + Send + Sync + Unpin + 'static>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_daemon::server::ClientConnectionInfo;
+
+    #[test]
+    fn child_trace_ctx() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 1080));
+        let cc_info = ClientConnectionInfo::new(addr, addr);
+        let mut task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+        task_notes.trace_ctx = None;
+        let inspect_notes = StreamInspectTaskNotes::from(&task_notes);
+        assert!(inspect_notes.child_trace_ctx().is_none());
+
+        let task_ctx = TraceContext::new_root();
+        task_notes.trace_ctx = Some(task_ctx);
+        let inspect_notes = StreamInspectTaskNotes::from(&task_notes);
+
+        let ctx1 = inspect_notes.child_trace_ctx().unwrap();
+        assert_eq!(ctx1.trace_id, task_ctx.trace_id);
+        assert_eq!(ctx1.parent_span_id, Some(task_ctx.span_id));
+        assert_ne!(ctx1.span_id, task_ctx.span_id);
+
+        // nested inspection shares the same task span as parent
+        let ctx2 = inspect_notes.clone().child_trace_ctx().unwrap();
+        assert_eq!(ctx2.trace_id, task_ctx.trace_id);
+        assert_eq!(ctx2.parent_span_id, Some(task_ctx.span_id));
+        assert_ne!(ctx2.span_id, ctx1.span_id);
+    }
+}
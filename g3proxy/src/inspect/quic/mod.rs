@@ -0,0 +1,253 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::num::NonZero;
+use std::sync::Arc;
+
+use lru::LruCache;
+use uuid::Uuid;
+
+use g3_dpi::ProtocolInspectAction;
+use g3_dpi::parser::quic::{HandshakeCoalescer, InitialPacket};
+use g3_dpi::parser::tls::ExtensionType;
+use g3_types::net::{Host, TlsServerName, UpstreamAddr};
+
+use crate::audit::AuditHandle;
+use crate::log::inspect::quic::QuicInspectLog;
+use crate::serve::ServerTaskNotes;
+
+mod send;
+pub(crate) use send::{QuicInspectCopyRemoteSend, QuicInspectRelayRemoteSend};
+
+const FLOW_CACHE_SIZE: NonZero<usize> = NonZero::new(64).unwrap();
+const CLIENT_HELLO_MAX_SIZE: u32 = 1 << 16;
+
+enum QuicFlowState {
+    Detecting(Box<HandshakeCoalescer>),
+    Decided(ProtocolInspectAction),
+}
+
+enum FlowCheckResult {
+    Forward,
+    Drop,
+    Decide(Option<TlsServerName>),
+}
+
+enum DetectResult {
+    NeedMore,
+    Done(Option<TlsServerName>),
+}
+
+/// Inspect the QUIC Initial packets sent by the client and decide what to do with each flow.
+///
+/// Only the server name based policy is supported here. QUIC termination and HTTP/3 interception
+/// are not supported, so the policy is only allowed to contain `bypass` and `block` actions, which
+/// is checked at config load. Blocked flows will make the client fall back to TCP, where TLS
+/// interception is available.
+///
+/// Packets other than the Initial ones will be dropped until the ClientHello message has been
+/// fully received and the decision has been made.
+pub(crate) struct QuicFlowInspector {
+    audit_handle: Arc<AuditHandle>,
+    task_id: Uuid,
+    flows: LruCache<UpstreamAddr, QuicFlowState>,
+}
+
+impl QuicFlowInspector {
+    pub(crate) fn new(audit_handle: Arc<AuditHandle>, task_id: Uuid) -> Self {
+        QuicFlowInspector {
+            audit_handle,
+            task_id,
+            flows: LruCache::new(FLOW_CACHE_SIZE),
+        }
+    }
+
+    /// Create a new inspector if the task should be audited
+    pub(crate) fn new_for_task(
+        audit_handle: &Arc<AuditHandle>,
+        task_notes: &ServerTaskNotes,
+    ) -> Option<Self> {
+        let audit_task = task_notes
+            .user_ctx()
+            .map(|ctx| {
+                let user_config = &ctx.user_config().audit;
+                user_config.enable_protocol_inspection
+                    && user_config
+                        .do_task_audit()
+                        .unwrap_or_else(|| audit_handle.do_task_audit())
+            })
+            .unwrap_or_else(|| audit_handle.do_task_audit());
+        if audit_task {
+            Some(QuicFlowInspector::new(audit_handle.clone(), task_notes.id))
+        } else {
+            None
+        }
+    }
+
+    /// Check the packet that will be sent to `upstream`, return false if it should be dropped
+    pub(crate) fn check_packet(&mut self, upstream: &UpstreamAddr, data: &[u8]) -> bool {
+        match Self::check_flow(&mut self.flows, upstream, data) {
+            FlowCheckResult::Forward => true,
+            FlowCheckResult::Drop => false,
+            FlowCheckResult::Decide(sni) => {
+                let action = self.decide(upstream, sni);
+                Self::allow_forward(action)
+            }
+        }
+    }
+
+    fn check_flow(
+        flows: &mut LruCache<UpstreamAddr, QuicFlowState>,
+        upstream: &UpstreamAddr,
+        data: &[u8],
+    ) -> FlowCheckResult {
+        let result = match flows.get_mut(upstream) {
+            Some(QuicFlowState::Decided(action)) => {
+                return if Self::allow_forward(*action) {
+                    FlowCheckResult::Forward
+                } else {
+                    FlowCheckResult::Drop
+                };
+            }
+            Some(QuicFlowState::Detecting(coalescer)) => {
+                let Ok(packet) = InitialPacket::parse_client(data) else {
+                    // hold back all other packets until the decision has been made
+                    return FlowCheckResult::Drop;
+                };
+                Self::detect(&packet, coalescer)
+            }
+            None => {
+                let Ok(packet) = InitialPacket::parse_client(data) else {
+                    // not a QUIC Initial packet, leave it alone
+                    return FlowCheckResult::Forward;
+                };
+                let mut coalescer = Box::new(HandshakeCoalescer::new(CLIENT_HELLO_MAX_SIZE));
+                let result = Self::detect(&packet, &mut coalescer);
+                if matches!(result, DetectResult::NeedMore) {
+                    flows.put(upstream.clone(), QuicFlowState::Detecting(coalescer));
+                }
+                result
+            }
+        };
+
+        match result {
+            DetectResult::NeedMore => FlowCheckResult::Forward,
+            DetectResult::Done(sni) => FlowCheckResult::Decide(sni),
+        }
+    }
+
+    fn detect(packet: &InitialPacket, coalescer: &mut HandshakeCoalescer) -> DetectResult {
+        if packet.consume_frames(coalescer).is_err() {
+            return DetectResult::Done(None);
+        }
+        match coalescer.parse_client_hello() {
+            Ok(Some(ch)) => {
+                let sni = match ch.get_ext(ExtensionType::ServerName) {
+                    Ok(Some(data)) => TlsServerName::from_extension_value(data).ok(),
+                    _ => None,
+                };
+                DetectResult::Done(sni)
+            }
+            Ok(None) => DetectResult::NeedMore,
+            Err(_) => DetectResult::Done(None),
+        }
+    }
+
+    fn decide(
+        &mut self,
+        upstream: &UpstreamAddr,
+        sni: Option<TlsServerName>,
+    ) -> ProtocolInspectAction {
+        let (_, action) = match &sni {
+            Some(name) => self
+                .audit_handle
+                .quic_inspect_policy
+                .check(&Host::from(name)),
+            None => self.audit_handle.quic_inspect_policy.check(upstream.host()),
+        };
+        self.flows
+            .put(upstream.clone(), QuicFlowState::Decided(action));
+
+        if let Some(logger) = self.audit_handle.inspect_logger() {
+            QuicInspectLog {
+                task_id: &self.task_id,
+                upstream,
+                sni: sni.as_ref(),
+                action,
+            }
+            .log(logger);
+        }
+        action
+    }
+
+    fn allow_forward(action: ProtocolInspectAction) -> bool {
+        matches!(action, ProtocolInspectAction::Bypass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    const SHORT_HEADER_PACKET: &[u8] = &[0x40, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+
+    fn upstream() -> UpstreamAddr {
+        UpstreamAddr::from_ip_and_port(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 443)
+    }
+
+    #[test]
+    fn forward_unknown_flow() {
+        let mut flows = LruCache::new(FLOW_CACHE_SIZE);
+        let upstream = upstream();
+        let r = QuicFlowInspector::check_flow(&mut flows, &upstream, SHORT_HEADER_PACKET);
+        assert!(matches!(r, FlowCheckResult::Forward));
+        assert!(flows.is_empty());
+    }
+
+    #[test]
+    fn drop_while_detecting() {
+        let mut flows = LruCache::new(FLOW_CACHE_SIZE);
+        let upstream = upstream();
+        flows.put(
+            upstream.clone(),
+            QuicFlowState::Detecting(Box::new(HandshakeCoalescer::new(CLIENT_HELLO_MAX_SIZE))),
+        );
+        let r = QuicFlowInspector::check_flow(&mut flows, &upstream, SHORT_HEADER_PACKET);
+        assert!(matches!(r, FlowCheckResult::Drop));
+        assert!(matches!(
+            flows.get(&upstream),
+            Some(QuicFlowState::Detecting(_))
+        ));
+    }
+
+    #[test]
+    fn decided_flow() {
+        let mut flows = LruCache::new(FLOW_CACHE_SIZE);
+        let upstream = upstream();
+
+        flows.put(
+            upstream.clone(),
+            QuicFlowState::Decided(ProtocolInspectAction::Bypass),
+        );
+        let r = QuicFlowInspector::check_flow(&mut flows, &upstream, SHORT_HEADER_PACKET);
+        assert!(matches!(r, FlowCheckResult::Forward));
+
+        flows.put(
+            upstream.clone(),
+            QuicFlowState::Decided(ProtocolInspectAction::Block),
+        );
+        let r = QuicFlowInspector::check_flow(&mut flows, &upstream, SHORT_HEADER_PACKET);
+        assert!(matches!(r, FlowCheckResult::Drop));
+
+        flows.put(
+            upstream.clone(),
+            QuicFlowState::Decided(ProtocolInspectAction::Intercept),
+        );
+        let r = QuicFlowInspector::check_flow(&mut flows, &upstream, SHORT_HEADER_PACKET);
+        assert!(matches!(r, FlowCheckResult::Drop));
+    }
+}
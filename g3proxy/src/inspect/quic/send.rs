@@ -0,0 +1,243 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::task::{Context, Poll, ready};
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::UdpCopyPacket;
+use g3_io_ext::{
+    UdpCopyRemoteError, UdpCopyRemoteSend, UdpRelayPacket, UdpRelayRemoteError, UdpRelayRemoteSend,
+};
+use g3_types::net::UpstreamAddr;
+
+use super::QuicFlowInspector;
+
+/// Track the inspect result of the packets in the pending batch.
+///
+/// The relay buffer will always retry with the same unsent packets, so we only need to save
+/// how many leading packets have been checked and allowed, and whether the next one should
+/// be dropped.
+#[derive(Default)]
+struct BatchCheckState {
+    allowed: usize,
+    drop_next: bool,
+}
+
+impl BatchCheckState {
+    fn check<F>(&mut self, count: usize, mut check_one: F) -> Option<usize>
+    where
+        F: FnMut(usize) -> bool,
+    {
+        if self.allowed == 0 && self.drop_next {
+            self.drop_next = false;
+            return None;
+        }
+
+        while !self.drop_next && self.allowed < count {
+            if check_one(self.allowed) {
+                self.allowed += 1;
+            } else {
+                self.drop_next = true;
+            }
+        }
+
+        if self.allowed == 0 {
+            self.drop_next = false;
+            None
+        } else {
+            Some(self.allowed)
+        }
+    }
+
+    fn mark_sent(&mut self, count: usize) {
+        self.allowed -= count;
+    }
+}
+
+pub(crate) struct QuicInspectRelayRemoteSend {
+    inner: Box<dyn UdpRelayRemoteSend + Unpin + Send>,
+    inspector: QuicFlowInspector,
+    batch_state: BatchCheckState,
+}
+
+impl QuicInspectRelayRemoteSend {
+    pub(crate) fn new(
+        inner: Box<dyn UdpRelayRemoteSend + Unpin + Send>,
+        inspector: QuicFlowInspector,
+    ) -> Self {
+        QuicInspectRelayRemoteSend {
+            inner,
+            inspector,
+            batch_state: BatchCheckState::default(),
+        }
+    }
+}
+
+impl UdpRelayRemoteSend for QuicInspectRelayRemoteSend {
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        to: &UpstreamAddr,
+    ) -> Poll<Result<usize, UdpRelayRemoteError>> {
+        let inspector = &mut self.inspector;
+        if self
+            .batch_state
+            .check(1, |_| inspector.check_packet(to, buf))
+            .is_none()
+        {
+            // silently drop the packet
+            return Poll::Ready(Ok(buf.len()));
+        }
+        let nw = ready!(self.inner.poll_send_packet(cx, buf, to))?;
+        self.batch_state.mark_sent(1);
+        Poll::Ready(Ok(nw))
+    }
+
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpRelayPacket],
+    ) -> Poll<Result<usize, UdpRelayRemoteError>> {
+        let inspector = &mut self.inspector;
+        let Some(allowed) = self.batch_state.check(packets.len(), |i| {
+            let p = &packets[i];
+            inspector.check_packet(p.upstream(), p.payload())
+        }) else {
+            // silently drop the packet
+            return Poll::Ready(Ok(1));
+        };
+        let count = ready!(self.inner.poll_send_packets(cx, &packets[..allowed]))?;
+        self.batch_state.mark_sent(count);
+        Poll::Ready(Ok(count))
+    }
+}
+
+pub(crate) struct QuicInspectCopyRemoteSend {
+    inner: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+    upstream: UpstreamAddr,
+    inspector: QuicFlowInspector,
+    batch_state: BatchCheckState,
+}
+
+impl QuicInspectCopyRemoteSend {
+    pub(crate) fn new(
+        inner: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+        upstream: UpstreamAddr,
+        inspector: QuicFlowInspector,
+    ) -> Self {
+        QuicInspectCopyRemoteSend {
+            inner,
+            upstream,
+            inspector,
+            batch_state: BatchCheckState::default(),
+        }
+    }
+}
+
+impl UdpCopyRemoteSend for QuicInspectCopyRemoteSend {
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        let inspector = &mut self.inspector;
+        let upstream = &self.upstream;
+        if self
+            .batch_state
+            .check(1, |_| inspector.check_packet(upstream, buf))
+            .is_none()
+        {
+            // silently drop the packet
+            return Poll::Ready(Ok(buf.len()));
+        }
+        let nw = ready!(self.inner.poll_send_packet(cx, buf))?;
+        self.batch_state.mark_sent(1);
+        Poll::Ready(Ok(nw))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        let inspector = &mut self.inspector;
+        let upstream = &self.upstream;
+        let Some(allowed) = self.batch_state.check(packets.len(), |i| {
+            inspector.check_packet(upstream, packets[i].payload())
+        }) else {
+            // silently drop the packet
+            return Poll::Ready(Ok(1));
+        };
+        let count = ready!(self.inner.poll_send_packets(cx, &packets[..allowed]))?;
+        self.batch_state.mark_sent(count);
+        Poll::Ready(Ok(count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_check() {
+        let allow = [true, true, false, true];
+        let mut state = BatchCheckState::default();
+        let mut offset = 0;
+
+        assert_eq!(state.check(4, |i| allow[offset + i]), Some(2));
+        state.mark_sent(2);
+        offset += 2;
+
+        assert_eq!(state.check(2, |i| allow[offset + i]), None);
+        offset += 1;
+
+        assert_eq!(state.check(1, |i| allow[offset + i]), Some(1));
+        state.mark_sent(1);
+        assert_eq!(state.allowed, 0);
+        assert!(!state.drop_next);
+    }
+
+    #[test]
+    fn batch_partial_sent() {
+        let mut state = BatchCheckState::default();
+        let mut checked = 0;
+
+        assert_eq!(
+            state.check(3, |_| {
+                checked += 1;
+                true
+            }),
+            Some(3)
+        );
+        state.mark_sent(1);
+        // the remaining allowed packets should not be checked again
+        assert_eq!(
+            state.check(2, |_| {
+                checked += 1;
+                true
+            }),
+            Some(2)
+        );
+        assert_eq!(checked, 3);
+    }
+}
@@ -472,6 +472,9 @@ where
                 StreamInspection::StreamUnknown(stream) => {
                     return stream.transit_inspect_unknown().await;
                 }
+                StreamInspection::StreamBypass(stream) => {
+                    return stream.transit_inspect_bypass().await;
+                }
                 StreamInspection::StreamInspect(stream) => {
                     if stream.ctx.skip_next_inspection() {
                         return stream.transit_inspect_unknown().await;
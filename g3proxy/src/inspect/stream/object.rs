@@ -75,6 +75,19 @@ where
             .await
     }
 
+    pub(super) async fn transit_inspect_bypass(mut self) -> ServerTaskResult<()> {
+        let StreamInspectIo {
+            clt_r,
+            clt_w,
+            ups_r,
+            ups_w,
+        } = self.io.take().unwrap();
+
+        self.ctx
+            .transit_inspect_bypass(clt_r, clt_w, ups_r, ups_w)
+            .await
+    }
+
     pub(super) async fn transit_with_inspection(
         mut self,
         inspector: &mut ProtocolInspector,
@@ -172,7 +185,11 @@ where
                 }
             }
             Protocol::Http1 => {
-                let mut h1_obj = crate::inspect::http::H1InterceptObject::new(self.ctx);
+                let mut h1_obj = crate::inspect::http::H1InterceptObject::new(
+                    self.ctx,
+                    self.upstream.clone(),
+                    false,
+                );
                 h1_obj.set_io(
                     FlexBufReader::with_bytes(clt_r_buf, clt_r),
                     clt_w,
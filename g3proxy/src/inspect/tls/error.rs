@@ -21,4 +21,6 @@ pub(crate) enum TlsInterceptionError {
     UpstreamHandshakeFailed(anyhow::Error),
     #[error("no fake cert generated: {0:?}")]
     NoFakeCertGenerated(anyhow::Error),
+    #[error("upstream cert verify failed, block page sent: {0}")]
+    UpstreamCertBlocked(String),
 }
@@ -3,10 +3,14 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::io;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use anyhow::anyhow;
 use bytes::BytesMut;
+use openssl::ssl::Ssl;
 use openssl::x509::X509VerifyResult;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
 use tokio::runtime::Handle;
@@ -17,10 +21,11 @@ use g3_dpi::parser::tls::{
 };
 use g3_dpi::{Protocol, ProtocolInspector};
 use g3_io_ext::{AsyncStream, FlexBufReader, OnceBufReader};
-use g3_slog_types::{LtUpstreamAddr, LtUuid, LtX509VerifyResult};
+use g3_openssl::SslInfoCallbackWhere;
+use g3_slog_types::{LtDuration, LtUpstreamAddr, LtUuid, LtX509VerifyResult};
 use g3_types::net::{
-    AlpnProtocol, OpensslInterceptionClientConfig, OpensslInterceptionServerConfig, TlsAlpn,
-    TlsServerName, UpstreamAddr,
+    AlpnProtocol, OpensslInterceptionClientConfig, OpensslInterceptionServerConfig, TlsAlert,
+    TlsAlertType, TlsAlpn, TlsServerName, UpstreamAddr,
 };
 use g3_udpdump::{
     ExportedPduDissectorHint, StreamDumpConfig, StreamDumpProxyAddresses, StreamDumper,
@@ -29,6 +34,7 @@ use g3_udpdump::{
 use super::{
     BoxAsyncRead, BoxAsyncWrite, InterceptionError, StreamInspectContext, StreamInspection,
 };
+use crate::audit::TlsAutoBypass;
 use crate::config::server::ServerConfig;
 use crate::log::inspect::{InspectSource, stream::StreamInspectLog};
 use crate::serve::ServerTaskResult;
@@ -86,6 +92,7 @@ pub(crate) struct TlsInterceptionContext {
     pub(super) client_config: Arc<OpensslInterceptionClientConfig>,
     pub(super) server_config: Arc<OpensslInterceptionServerConfig>,
     stream_dumper: Arc<Vec<StreamDumper>>,
+    auto_bypass: Option<Arc<TlsAutoBypass>>,
 }
 
 impl TlsInterceptionContext {
@@ -94,11 +101,12 @@ impl TlsInterceptionContext {
         client_config: OpensslInterceptionClientConfig,
         server_config: OpensslInterceptionServerConfig,
         dump_config: Option<StreamDumpConfig>,
+        auto_bypass: Option<Arc<TlsAutoBypass>>,
     ) -> anyhow::Result<Self> {
         let mut stream_dumper = Vec::new();
         if let Some(dump) = dump_config {
             g3_daemon::runtime::worker::foreach(|h| {
-                let dumper = StreamDumper::new(dump, &h.handle).map_err(|e| {
+                let dumper = StreamDumper::new(dump.clone(), &h.handle).map_err(|e| {
                     anyhow!("failed to create tls stream dumper in worker {}: {e}", h.id)
                 })?;
                 stream_dumper.push(dumper);
@@ -110,7 +118,7 @@ impl TlsInterceptionContext {
                     g3_daemon::runtime::config::get_runtime_config().intended_thread_number();
                 let handle = Handle::current();
                 for i in 0..dump_count {
-                    let dumper = StreamDumper::new(dump, &handle).map_err(|e| {
+                    let dumper = StreamDumper::new(dump.clone(), &handle).map_err(|e| {
                         anyhow!("failed to create tls stream dumper #{i} in main runtime: {e}")
                     })?;
                     stream_dumper.push(dumper);
@@ -123,6 +131,7 @@ impl TlsInterceptionContext {
             client_config: Arc::new(client_config),
             server_config: Arc::new(server_config),
             stream_dumper: Arc::new(stream_dumper),
+            auto_bypass,
         })
     }
 
@@ -285,6 +294,92 @@ impl<SC: ServerConfig> TlsInterceptObject<SC> {
         intercept_log!(self, "{e}");
     }
 
+    fn log_auto_bypass(&self, sni: &TlsServerName, bypass_ttl: Option<Duration>) {
+        if let Some(logger) = self.ctx.intercept_logger() {
+            let msg = if bypass_ttl.is_some() {
+                "auto bypass enabled"
+            } else {
+                "auto bypassed"
+            };
+            slog::info!(logger, "{msg}";
+                "intercept_type" => "TlsHandshake",
+                "task_id" => LtUuid(self.ctx.server_task_id()),
+                "depth" => self.ctx.inspection_depth,
+                "upstream" => LtUpstreamAddr(&self.upstream),
+                "tls_server_name" => sni.as_ref(),
+                "auto_bypass_ttl" => bypass_ttl.map(LtDuration),
+            );
+        }
+    }
+
+    /// Check if the interception should be skipped as the client is known to reject forged certs
+    fn check_auto_bypass(&self, sni: Option<&TlsServerName>) -> bool {
+        let Some(auto_bypass) = &self.tls_interception.auto_bypass else {
+            return false;
+        };
+        let Some(sni) = sni else {
+            return false;
+        };
+        if auto_bypass.check(sni.as_ref()) {
+            self.log_auto_bypass(sni, None);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn add_auto_bypass_failure(&self, sni: &TlsServerName) {
+        if let Some(auto_bypass) = &self.tls_interception.auto_bypass
+            && auto_bypass.add_failure(sni.as_ref())
+        {
+            self.log_auto_bypass(sni, Some(auto_bypass.bypass_ttl()));
+        }
+    }
+
+    fn add_auto_bypass_success(&self, sni: &TlsServerName) {
+        if let Some(auto_bypass) = &self.tls_interception.auto_bypass {
+            auto_bypass.add_success(sni.as_ref());
+        }
+    }
+
+    /// Record fatal alerts sent by the client, which may reject our forged cert
+    fn watch_client_alert(
+        &self,
+        clt_ssl: &mut Ssl,
+        sni: Option<&TlsServerName>,
+    ) -> Option<Arc<AtomicBool>> {
+        if self.tls_interception.auto_bypass.is_none() || sni.is_none() {
+            return None;
+        }
+        let alert_flag = Arc::new(AtomicBool::new(false));
+        let alert_flag2 = alert_flag.clone();
+        clt_ssl.set_info_callback(move |_ssl, r#where, ret| {
+            let mask = SslInfoCallbackWhere::from_bits_retain(r#where);
+            if mask.contains(SslInfoCallbackWhere::ALERT | SslInfoCallbackWhere::READ)
+                && matches!(TlsAlert::new(ret & 0xFF).r#type(), TlsAlertType::Error)
+            {
+                alert_flag2.store(true, Ordering::Relaxed);
+            }
+        });
+        Some(alert_flag)
+    }
+
+    /// Learn from the client handshake failure after sending the forged cert
+    fn check_client_handshake_error(
+        &self,
+        sni: Option<&TlsServerName>,
+        client_alert: Option<Arc<AtomicBool>>,
+        e: &io::Error,
+    ) {
+        // a fatal alert or an immediate close after receiving the forged cert
+        if let Some(alert) = client_alert
+            && let Some(sni) = sni
+            && (alert.load(Ordering::Relaxed) || e.kind() != io::ErrorKind::TimedOut)
+        {
+            self.add_auto_bypass_failure(sni);
+        }
+    }
+
     fn retain_alpn_protocol(&self, p: &[u8]) -> bool {
         if p == AlpnProtocol::Http2.identification_sequence() {
             return !self.ctx.h2_inspect_action(self.upstream.host()).is_block();
@@ -313,7 +408,9 @@ where
     ) -> ServerTaskResult<StreamInspection<SC>> {
         match self.do_intercept(inspector).await {
             Ok(obj) => {
-                self.log_ok();
+                if !matches!(obj, StreamInspection::StreamBypass(_)) {
+                    self.log_ok();
+                }
                 Ok(obj)
             }
             Err(e) => {
@@ -340,6 +437,20 @@ where
             .read_client_hello(&mut clt_r, &mut clt_r_buf)
             .await?;
 
+        if self.check_auto_bypass(client_hello.sni.as_ref()) {
+            let mut stream_obj = crate::inspect::stream::StreamInspectObject::new(
+                self.ctx.clone(),
+                self.upstream.clone(),
+            );
+            stream_obj.set_io(
+                Box::new(OnceBufReader::new(clt_r, clt_r_buf)),
+                Box::new(clt_w),
+                Box::new(ups_r),
+                Box::new(ups_w),
+            );
+            return Ok(StreamInspection::StreamBypass(stream_obj));
+        }
+
         self.set_io(clt_r_buf, clt_r, clt_w, ups_r, ups_w);
 
         if client_hello.version.is_tlcp() {
@@ -445,7 +556,8 @@ where
         StreamInspectLog::new(&ctx).log(InspectSource::TlsAlpn, protocol);
         match protocol {
             Protocol::Http1 => {
-                let mut h1_obj = crate::inspect::http::H1InterceptObject::new(ctx);
+                let mut h1_obj =
+                    crate::inspect::http::H1InterceptObject::new(ctx, self.upstream.clone(), true);
                 h1_obj.set_io(
                     FlexBufReader::new(Box::new(clt_r)),
                     Box::new(clt_w),
@@ -3,10 +3,15 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::future::poll_fn;
 use std::sync::Arc;
 
 use anyhow::anyhow;
-use openssl::ssl::Ssl;
+use bytes::{Bytes, BytesMut};
+use http::{StatusCode, Version};
+use openssl::ssl::{Ssl, SslVerifyMode};
+use openssl::x509::X509VerifyResult;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use g3_dpi::{Protocol, ProtocolInspector};
 use g3_io_ext::OnceBufReader;
@@ -14,8 +19,9 @@ use g3_openssl::{SslAcceptor, SslConnector};
 use g3_types::net::{AlpnProtocol, Host, TlsCertUsage, TlsServiceType};
 
 use super::{ParsedClientHello, TlsInterceptIo, TlsInterceptObject, TlsInterceptionError};
+use crate::config::audit::{AuditBlockPageConfig, AuditTlsCertErrorAction};
 use crate::config::server::ServerConfig;
-use crate::inspect::StreamInspection;
+use crate::inspect::{BlockPage, StreamInspection};
 
 #[cfg(not(feature = "vendored-tongsuo"))]
 const CERT_USAGE: TlsCertUsage = TlsCertUsage::TlsServer;
@@ -62,7 +68,7 @@ where
                 new_ext
             }
         });
-        let ups_ssl = match self.ctx.user_site_tls_client() {
+        let mut ups_ssl = match self.ctx.user_site_tls_client() {
             Some(c) => c
                 .build_mimic_ssl(sni_hostname, &self.upstream, alpn_ext.as_ref())
                 .map_err(|e| {
@@ -81,6 +87,14 @@ where
                 })?,
         };
 
+        // let the handshake continue on cert error, and handle it after handshake
+        let cert_error_action = self.ctx.tls_cert_error_action();
+        if cert_error_action != AuditTlsCertErrorAction::Fail
+            && ups_ssl.verify_mode().contains(SslVerifyMode::PEER)
+        {
+            ups_ssl.set_verify_callback(SslVerifyMode::PEER, |_, _| true);
+        }
+
         // fetch fake server cert early in the background
         let cert_domain = sni_hostname
             .map(|v| v.to_string())
@@ -111,6 +125,14 @@ where
             TlsInterceptionError::UpstreamHandshakeFailed(anyhow!("upstream handshake error: {e}"))
         })?;
 
+        let verify_result = ups_tls_stream.ssl().verify_result();
+        self.server_verify_result = Some(verify_result);
+        let cert_error_action = if verify_result == X509VerifyResult::OK {
+            AuditTlsCertErrorAction::Fail
+        } else {
+            cert_error_action
+        };
+
         let pre_fetch_pair = pre_fetch_handle.await.map_err(|e| {
             TlsInterceptionError::NoFakeCertGenerated(anyhow!(
                 "join client cert handle failed: {e}"
@@ -118,37 +140,78 @@ where
         })?;
 
         let cert_pair = match pre_fetch_pair {
-            Some(pair) => pair,
-            None => {
+            Some(pair) if cert_error_action != AuditTlsCertErrorAction::Mirror => pair,
+            _ => {
                 let upstream_cert = ups_tls_stream.ssl().peer_certificate().ok_or_else(|| {
                     TlsInterceptionError::NoFakeCertGenerated(anyhow!(
                         "failed to get upstream certificate"
                     ))
                 })?;
-                self.tls_interception
-                    .cert_agent
-                    .fetch(TlsServiceType::Http, CERT_USAGE, cert_domain, upstream_cert)
-                    .await
-                    .ok_or_else(|| {
-                        TlsInterceptionError::NoFakeCertGenerated(anyhow!(
-                            "failed to get fake upstream certificate"
-                        ))
-                    })?
+                let cert_agent = &self.tls_interception.cert_agent;
+                if cert_error_action == AuditTlsCertErrorAction::Mirror {
+                    cert_agent
+                        .fetch_untrusted(
+                            TlsServiceType::Http,
+                            CERT_USAGE,
+                            cert_domain.clone(),
+                            upstream_cert,
+                        )
+                        .await
+                        .ok_or_else(|| {
+                            TlsInterceptionError::NoFakeCertGenerated(anyhow!(
+                                "failed to get untrusted fake upstream certificate"
+                            ))
+                        })?
+                } else {
+                    cert_agent
+                        .fetch(
+                            TlsServiceType::Http,
+                            CERT_USAGE,
+                            cert_domain.clone(),
+                            upstream_cert,
+                        )
+                        .await
+                        .ok_or_else(|| {
+                            TlsInterceptionError::NoFakeCertGenerated(anyhow!(
+                                "failed to get fake upstream certificate"
+                            ))
+                        })?
+                }
             }
         };
-        self.server_verify_result = Some(ups_tls_stream.ssl().verify_result());
 
         // set certificate and private key
         cert_pair
             .add_to_ssl(&mut clt_ssl)
             .map_err(TlsInterceptionError::InternalOpensslServerError)?;
         // set alpn
-        if let Some(alpn_protocol) = ups_tls_stream.ssl().selected_alpn_protocol() {
+        if cert_error_action == AuditTlsCertErrorAction::BlockPage {
+            // the block page will be sent in http/1.1, or in h2 if the client doesn't support http/1.1
+            if let Some(ext) = &client_hello.alpn {
+                let offered = |protocol: AlpnProtocol| {
+                    !ext.retain_clone(|p| p == protocol.identification_sequence())
+                        .is_empty()
+                };
+                if offered(AlpnProtocol::Http11) {
+                    self.tls_interception.server_config.set_selected_alpn(
+                        &mut clt_ssl,
+                        AlpnProtocol::Http11.to_identification_sequence(),
+                    );
+                } else if offered(AlpnProtocol::Http2) {
+                    self.tls_interception.server_config.set_selected_alpn(
+                        &mut clt_ssl,
+                        AlpnProtocol::Http2.to_identification_sequence(),
+                    );
+                }
+            }
+        } else if let Some(alpn_protocol) = ups_tls_stream.ssl().selected_alpn_protocol() {
             self.tls_interception
                 .server_config
                 .set_selected_alpn(&mut clt_ssl, alpn_protocol.to_vec());
         }
 
+        let client_alert = self.watch_client_alert(&mut clt_ssl, sni_hostname);
+
         let clt_acceptor = SslAcceptor::new(
             clt_ssl,
             tokio::io::join(OnceBufReader::new(clt_r, clt_r_buf), clt_w),
@@ -159,9 +222,29 @@ where
                 "failed to convert acceptor: {e}"
             ))
         })?;
-        let clt_tls_stream = clt_acceptor.accept().await.map_err(|e| {
-            TlsInterceptionError::ClientHandshakeFailed(anyhow!("client handshake error: {e:?}"))
-        })?;
+        let clt_tls_stream = match clt_acceptor.accept().await {
+            Ok(s) => {
+                if let Some(sni) = sni_hostname {
+                    self.add_auto_bypass_success(sni);
+                }
+                s
+            }
+            Err(e) => {
+                self.check_client_handshake_error(sni_hostname, client_alert, &e);
+                return Err(TlsInterceptionError::ClientHandshakeFailed(anyhow!(
+                    "client handshake error: {e:?}"
+                )));
+            }
+        };
+
+        if cert_error_action == AuditTlsCertErrorAction::BlockPage {
+            let detail = verify_result.error_string().to_string();
+            let is_h2 = clt_tls_stream.ssl().selected_alpn_protocol()
+                == Some(AlpnProtocol::Http2.identification_sequence());
+            self.send_cert_error_block_page(clt_tls_stream, is_h2, &cert_domain, detail.clone())
+                .await;
+            return Err(TlsInterceptionError::UpstreamCertBlocked(detail));
+        }
 
         let mut protocol = Protocol::Unknown;
         let has_alpn = if let Some(alpn_protocol) = clt_tls_stream.ssl().selected_alpn_protocol() {
@@ -177,3 +260,118 @@ where
         Ok(self.transfer_connected(protocol, has_alpn, clt_tls_stream, ups_tls_stream))
     }
 }
+
+impl<SC> TlsInterceptObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    async fn send_cert_error_block_page<S>(
+        &self,
+        mut clt_stream: S,
+        is_h2: bool,
+        host: &str,
+        detail: String,
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let default_config = AuditBlockPageConfig::default();
+        let config = self.ctx.block_page().unwrap_or(&default_config);
+        let mut block_page = BlockPage::new(
+            config,
+            StatusCode::FORBIDDEN,
+            "upstream certificate verification failed",
+            host,
+            self.ctx.server_task_id(),
+        );
+        block_page.set_detail(detail);
+
+        if is_h2 {
+            self.send_h2_block_page(clt_stream, &block_page).await;
+            return;
+        }
+
+        let h1_config = self.ctx.h1_interception();
+        let mut buf = Vec::with_capacity(1024);
+        let read_req_head = async {
+            // read and drop the request header, the body will be ignored as we will close it
+            while !buf.windows(4).any(|w| w == b"\r\n\r\n")
+                && buf.len() < h1_config.req_head_max_size
+            {
+                match clt_stream.read_buf(&mut buf).await {
+                    Ok(0) | Err(_) => return false,
+                    Ok(_) => {}
+                }
+            }
+            true
+        };
+        match tokio::time::timeout(h1_config.req_head_recv_timeout, read_req_head).await {
+            Ok(true) => {}
+            Ok(false) | Err(_) => return,
+        }
+        let version = if buf.starts_with(b"PRI * HTTP/2") {
+            // h2 with prior knowledge, push back the preface and continue as h2 server
+            let (clt_r, clt_w) = tokio::io::split(clt_stream);
+            let clt_r = OnceBufReader::new(clt_r, BytesMut::from(buf.as_slice()));
+            self.send_h2_block_page(tokio::io::join(clt_r, clt_w), &block_page)
+                .await;
+            return;
+        } else if buf
+            .split(|c| *c == b'\n')
+            .next()
+            .map(|line| line.trim_ascii_end().ends_with(b"HTTP/1.0"))
+            .unwrap_or(false)
+        {
+            Version::HTTP_10
+        } else {
+            Version::HTTP_11
+        };
+
+        let rsp = block_page.serialize(version);
+        if clt_stream.write_all(&rsp).await.is_ok() {
+            let _ = clt_stream.shutdown().await;
+        }
+    }
+
+    async fn send_h2_block_page<S>(&self, clt_stream: S, block_page: &BlockPage<'_>)
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let Some((rsp, body)) = block_page.to_h2_response() else {
+            return;
+        };
+
+        let http_config = self.ctx.h2_interception();
+        let mut server_builder = h2::server::Builder::new();
+        server_builder
+            .max_header_list_size(http_config.max_header_list_size)
+            .max_concurrent_streams(1)
+            .max_frame_size(http_config.max_frame_size())
+            .max_send_buffer_size(http_config.max_send_buffer_size);
+        let mut h2c = match tokio::time::timeout(
+            http_config.client_handshake_timeout,
+            server_builder.handshake::<_, Bytes>(clt_stream),
+        )
+        .await
+        {
+            Ok(Ok(h2c)) => h2c,
+            Ok(Err(_)) | Err(_) => return,
+        };
+
+        let send_block_page = async {
+            // reply the block page to the first request, and then close the connection
+            let Some(Ok((_req, mut clt_send_rsp))) = h2c.accept().await else {
+                return;
+            };
+            if let Ok(mut clt_send_stream) = clt_send_rsp.send_response(rsp, false) {
+                let _ = clt_send_stream.send_data(body, true);
+            }
+            h2c.graceful_shutdown();
+            let _ = poll_fn(|cx| h2c.poll_closed(cx)).await;
+        };
+        let _ = tokio::time::timeout(
+            self.ctx.h1_interception().req_head_recv_timeout,
+            send_block_page,
+        )
+        .await;
+    }
+}
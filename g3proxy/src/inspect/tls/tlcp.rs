@@ -188,6 +188,8 @@ where
                 .set_selected_alpn(&mut clt_ssl, alpn_protocol.to_vec());
         }
 
+        let client_alert = self.watch_client_alert(&mut clt_ssl, sni_hostname);
+
         let clt_acceptor = SslAcceptor::new(
             clt_ssl,
             tokio::io::join(OnceBufReader::new(clt_r, clt_r_buf), clt_w),
@@ -198,9 +200,20 @@ where
                 "failed to convert acceptor: {e}"
             ))
         })?;
-        let clt_tls_stream = clt_acceptor.accept().await.map_err(|e| {
-            TlsInterceptionError::ClientHandshakeFailed(anyhow!("client handshake error: {e:?}"))
-        })?;
+        let clt_tls_stream = match clt_acceptor.accept().await {
+            Ok(s) => {
+                if let Some(sni) = sni_hostname {
+                    self.add_auto_bypass_success(sni);
+                }
+                s
+            }
+            Err(e) => {
+                self.check_client_handshake_error(sni_hostname, client_alert, &e);
+                return Err(TlsInterceptionError::ClientHandshakeFailed(anyhow!(
+                    "client handshake error: {e:?}"
+                )));
+            }
+        };
 
         let mut protocol = Protocol::Unknown;
         let has_alpn = if let Some(alpn_protocol) = clt_tls_stream.ssl().selected_alpn_protocol() {
@@ -5,6 +5,7 @@
 
 pub mod audit;
 pub mod auth;
+pub mod category;
 pub mod config;
 pub mod control;
 pub mod escape;
@@ -13,6 +14,7 @@ pub mod resolve;
 pub mod serve;
 pub mod signal;
 pub mod stat;
+pub mod trace;
 
 mod build;
 mod inspect;
@@ -7,6 +7,8 @@ use slog::Logger;
 
 use g3_types::metrics::NodeName;
 
+#[cfg(feature = "quic")]
+pub(crate) mod quic;
 pub(crate) mod stream;
 
 pub(crate) enum InspectSource {
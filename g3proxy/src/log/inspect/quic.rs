@@ -0,0 +1,32 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use slog::Logger;
+use uuid::Uuid;
+
+use g3_dpi::ProtocolInspectAction;
+use g3_slog_types::{LtUpstreamAddr, LtUuid};
+use g3_types::net::{TlsServerName, UpstreamAddr};
+
+pub(crate) struct QuicInspectLog<'a> {
+    pub(crate) task_id: &'a Uuid,
+    pub(crate) upstream: &'a UpstreamAddr,
+    pub(crate) sni: Option<&'a TlsServerName>,
+    pub(crate) action: ProtocolInspectAction,
+}
+
+impl QuicInspectLog<'_> {
+    pub(crate) fn log(&self, logger: &Logger) {
+        slog::info!(logger, "";
+            "task_id" => LtUuid(self.task_id),
+            "depth" => 0,
+            "source" => "quic initial",
+            "protocol" => "quic",
+            "upstream" => LtUpstreamAddr(self.upstream),
+            "tls_server_name" => self.sni.map(|v| v.as_ref()),
+            "inspect_action" => self.action.to_string(),
+        );
+    }
+}
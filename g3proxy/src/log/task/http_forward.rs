@@ -46,6 +46,7 @@ impl TaskLogForHttpForward<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
             "method" => LtHttpMethod(&self.http_notes.method),
             "uri" => LtHttpUri::new(&self.http_notes.uri, self.http_notes.uri_log_max_chars),
@@ -71,6 +72,7 @@ impl TaskLogForHttpForward<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
@@ -105,6 +107,7 @@ impl TaskLogForHttpForward<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
@@ -150,6 +153,7 @@ impl TaskLogForHttpForward<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
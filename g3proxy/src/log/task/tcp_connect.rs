@@ -41,6 +41,7 @@ impl TaskLogForTcpConnect<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "wait_time" => LtDuration(self.task_notes.wait_time),
         )
     }
@@ -62,6 +63,7 @@ impl TaskLogForTcpConnect<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
@@ -91,6 +93,7 @@ impl TaskLogForTcpConnect<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
@@ -119,6 +122,7 @@ impl TaskLogForTcpConnect<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bound_addr" => self.tcp_notes.local,
             "next_peer_addr" => self.tcp_notes.next,
@@ -158,6 +162,7 @@ impl TaskLogForTcpConnect<'_> {
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
+            "category" => self.task_notes.category.as_ref().map(|v| v.as_str()),
             "escaper" => self.tcp_notes.escaper.as_str(),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
@@ -86,6 +86,9 @@ fn main() -> anyhow::Result<()> {
         None
     };
 
+    let trace_join =
+        g3proxy::trace::spawn_working_thread().context("failed to start trace export thread")?;
+
     let _workers_guard =
         g3_daemon::runtime::worker::spawn_workers().context("failed to spawn workers")?;
     let ret = tokio_run(&proc_args);
@@ -96,6 +99,10 @@ fn main() -> anyhow::Result<()> {
             let _ = handle.join();
         }
     }
+    if let Some(handle) = trace_join {
+        g3proxy::trace::stop_working_thread();
+        let _ = handle.join();
+    }
 
     match ret {
         Ok(_) => Ok(()),
@@ -126,6 +133,10 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
             });
         }
         g3proxy::control::QuitActor::tokio_spawn_run();
+        g3_daemon::control::http::HttpController::start(
+            g3proxy::control::HttpControlImpl::new_arc(),
+        )
+        .context("failed to start http controller")?;
 
         g3proxy::signal::register().context("failed to setup signal handler")?;
         g3_daemon::control::panic::set_hook(&args.daemon_config);
@@ -164,6 +175,9 @@ async fn load_and_spawn() -> anyhow::Result<()> {
     g3proxy::resolve::spawn_all()
         .await
         .context("failed to spawn all resolvers")?;
+    g3proxy::category::load_all()
+        .await
+        .context("failed to load all categories")?;
     g3proxy::escape::load_all()
         .await
         .context("failed to load all escapers")?;
@@ -0,0 +1,485 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::Write;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use http::{HeaderName, Version, header};
+use serde_json::{Value, json};
+
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+use super::policy::{self, CacheControl};
+
+const DISK_FILE_MAGIC: &[u8] = b"G3HTTPCACHE1\n";
+
+/// A stored response, with all times in unix seconds
+pub(crate) struct HttpCacheEntry {
+    pub(super) key: String,
+    code: u16,
+    reason: String,
+    headers: Vec<(HeaderName, HttpHeaderValue)>,
+    /// the selecting header values of the original request
+    vary: Vec<(HeaderName, Option<String>)>,
+    body: Bytes,
+    response_time: u64,
+    corrected_initial_age: u64,
+    freshness_lifetime: u64,
+    must_revalidate: bool,
+    no_cache: bool,
+}
+
+fn vary_headers(
+    rsp_headers: &HttpHeaderMap,
+    req_headers: &HttpHeaderMap,
+) -> Vec<(HeaderName, Option<String>)> {
+    let mut vary = Vec::new();
+    for v in rsp_headers.get_all(header::VARY) {
+        for name in v.to_str().split(',') {
+            let Ok(name) = HeaderName::from_str(name.trim()) else {
+                continue;
+            };
+            let value = join_header_values(req_headers, &name);
+            vary.push((name, value));
+        }
+    }
+    vary
+}
+
+fn join_header_values(headers: &HttpHeaderMap, name: &HeaderName) -> Option<String> {
+    let mut values = headers.get_all(name).iter().map(|v| v.to_str());
+    let first = values.next()?;
+    let mut s = first.to_string();
+    for v in values {
+        s.push_str(", ");
+        s.push_str(v);
+    }
+    Some(s)
+}
+
+impl HttpCacheEntry {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        key: String,
+        req_headers: &HttpHeaderMap,
+        code: u16,
+        reason: &str,
+        rsp_headers: &HttpHeaderMap,
+        body: Bytes,
+        request_time: u64,
+        response_time: u64,
+        heuristic_max_age: u64,
+    ) -> Self {
+        let mut headers = Vec::new();
+        rsp_headers.for_each(|name, value| {
+            if *name != header::CONTENT_LENGTH {
+                headers.push((name.clone(), value.clone()));
+            }
+        });
+        let mut entry = HttpCacheEntry {
+            key,
+            code,
+            reason: reason.to_string(),
+            headers,
+            vary: vary_headers(rsp_headers, req_headers),
+            body,
+            response_time,
+            corrected_initial_age: 0,
+            freshness_lifetime: 0,
+            must_revalidate: false,
+            no_cache: false,
+        };
+        entry.update_times(rsp_headers, request_time, response_time, heuristic_max_age);
+        entry
+    }
+
+    fn update_times(
+        &mut self,
+        rsp_headers: &HttpHeaderMap,
+        request_time: u64,
+        response_time: u64,
+        heuristic_max_age: u64,
+    ) {
+        let rsp_cc = CacheControl::parse(rsp_headers);
+        let date = policy::header_date(rsp_headers, header::DATE).unwrap_or(response_time);
+        let age_value = rsp_headers
+            .get(header::AGE)
+            .and_then(|v| u64::from_str(v.to_str().trim()).ok())
+            .unwrap_or(0);
+
+        // rfc9111 Section 4.2.3
+        let apparent_age = response_time.saturating_sub(date);
+        let response_delay = response_time.saturating_sub(request_time);
+        let corrected_age_value = age_value + response_delay;
+
+        self.response_time = response_time;
+        self.corrected_initial_age = apparent_age.max(corrected_age_value);
+        self.freshness_lifetime =
+            policy::freshness_lifetime(self.code, rsp_headers, &rsp_cc, date, heuristic_max_age);
+        self.must_revalidate = rsp_cc.must_revalidate;
+        self.no_cache = rsp_cc.no_cache;
+    }
+
+    /// Create a new entry with the headers updated by a 304 response, see rfc9111 Section 4.3.4
+    pub(crate) fn freshen(
+        &self,
+        rsp_headers: &HttpHeaderMap,
+        request_time: u64,
+        response_time: u64,
+        heuristic_max_age: u64,
+    ) -> Self {
+        let mut headers: Vec<(HeaderName, HttpHeaderValue)> = self
+            .headers
+            .iter()
+            .filter(|(name, _)| !rsp_headers.contains_key(name))
+            .cloned()
+            .collect();
+        rsp_headers.for_each(|name, value| {
+            if *name != header::CONTENT_LENGTH {
+                headers.push((name.clone(), value.clone()));
+            }
+        });
+        let mut merged = HttpHeaderMap::default();
+        for (name, value) in &headers {
+            merged.append(name.clone(), value.clone());
+        }
+
+        let mut entry = HttpCacheEntry {
+            key: self.key.clone(),
+            code: self.code,
+            reason: self.reason.clone(),
+            headers,
+            vary: self.vary.clone(),
+            body: self.body.clone(),
+            response_time,
+            corrected_initial_age: 0,
+            freshness_lifetime: 0,
+            must_revalidate: false,
+            no_cache: false,
+        };
+        entry.update_times(&merged, request_time, response_time, heuristic_max_age);
+        entry
+    }
+
+    #[inline]
+    pub(crate) fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// The memory size used by this entry
+    pub(super) fn size(&self) -> usize {
+        let headers_size: usize = self
+            .headers
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.as_bytes().len() + 4)
+            .sum();
+        self.key.len() + self.reason.len() + headers_size + self.body.len() + 128
+    }
+
+    pub(super) fn current_age(&self, now: u64) -> u64 {
+        self.corrected_initial_age + now.saturating_sub(self.response_time)
+    }
+
+    /// Check if the stored response can be used without validation, see rfc9111 Section 4.2
+    pub(super) fn is_fresh(&self, req_cc: &CacheControl, now: u64) -> bool {
+        if self.no_cache || req_cc.no_cache {
+            return false;
+        }
+        let age = self.current_age(now);
+        if let Some(max_age) = req_cc.max_age
+            && age > max_age
+        {
+            return false;
+        }
+        if let Some(min_fresh) = req_cc.min_fresh
+            && self.freshness_lifetime < age + min_fresh
+        {
+            return false;
+        }
+        if self.freshness_lifetime > age {
+            return true;
+        }
+        if self.must_revalidate {
+            return false;
+        }
+        match req_cc.max_stale {
+            Some(None) => true,
+            Some(Some(max_stale)) => age - self.freshness_lifetime <= max_stale,
+            None => false,
+        }
+    }
+
+    /// Check if the selecting header fields match, see rfc9111 Section 4.1
+    pub(super) fn vary_match(&self, req_headers: &HttpHeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| join_header_values(req_headers, name).eq(value))
+    }
+
+    fn header_value(&self, name: HeaderName) -> Option<&HttpHeaderValue> {
+        self.headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v)
+    }
+
+    pub(super) fn has_validator(&self) -> bool {
+        self.header_value(header::ETAG).is_some()
+            || self.header_value(header::LAST_MODIFIED).is_some()
+    }
+
+    /// Get the conditional headers to validate this entry
+    pub(crate) fn validation_headers(&self) -> Vec<(HeaderName, HttpHeaderValue)> {
+        let mut headers = Vec::with_capacity(2);
+        if let Some(v) = self.header_value(header::ETAG) {
+            headers.push((header::IF_NONE_MATCH, v.clone()));
+        }
+        if let Some(v) = self.header_value(header::LAST_MODIFIED) {
+            headers.push((header::IF_MODIFIED_SINCE, v.clone()));
+        }
+        headers
+    }
+
+    /// Check if the conditional request from the client can be replied with 304,
+    /// see rfc9110 Section 13.2.2
+    pub(crate) fn not_modified_for(&self, req_headers: &HttpHeaderMap) -> bool {
+        if self.code != 200 {
+            return false;
+        }
+        if req_headers.contains_key(header::IF_NONE_MATCH) {
+            let Some(etag) = self.header_value(header::ETAG) else {
+                return false;
+            };
+            let etag = etag.to_str().trim().trim_start_matches("W/");
+            return req_headers.get_all(header::IF_NONE_MATCH).iter().any(|v| {
+                v.to_str().split(',').any(|t| {
+                    let t = t.trim();
+                    t == "*" || t.trim_start_matches("W/") == etag
+                })
+            });
+        }
+        if let Some(since) = req_headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|v| policy::parse_http_date(v.to_str()))
+        {
+            let last_modified = self
+                .header_value(header::LAST_MODIFIED)
+                .and_then(|v| policy::parse_http_date(v.to_str()));
+            return last_modified.map(|t| t <= since).unwrap_or(false);
+        }
+        false
+    }
+
+    /// Serialize the response header to be sent to the client
+    pub(crate) fn serialize_header(
+        &self,
+        version: Version,
+        not_modified: bool,
+        close: bool,
+        now: u64,
+    ) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(512);
+        if not_modified {
+            let _ = write!(buf, "{version:?} 304 Not Modified\r\n");
+        } else {
+            let _ = write!(buf, "{version:?} {} {}\r\n", self.code, self.reason);
+        }
+        for (name, value) in &self.headers {
+            if *name == header::AGE {
+                continue;
+            }
+            value.write_to_buf(name, &mut buf);
+        }
+        let _ = write!(buf, "Age: {}\r\n", self.current_age(now));
+        if !not_modified {
+            let _ = write!(buf, "Content-Length: {}\r\n", self.body.len());
+        }
+        if close {
+            buf.extend_from_slice(b"Connection: Close\r\n");
+        } else if version == Version::HTTP_10 {
+            buf.extend_from_slice(b"Connection: Keep-Alive\r\n");
+        }
+        buf.extend_from_slice(b"\r\n");
+        buf
+    }
+
+    #[inline]
+    pub(crate) fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let headers: Vec<Value> = self
+            .headers
+            .iter()
+            .map(|(name, value)| json!([name.as_str(), value.to_str()]))
+            .collect();
+        let vary: Vec<Value> = self
+            .vary
+            .iter()
+            .map(|(name, value)| json!([name.as_str(), value]))
+            .collect();
+        let meta = json!({
+            "key": self.key,
+            "code": self.code,
+            "reason": self.reason,
+            "headers": headers,
+            "vary": vary,
+            "response_time": self.response_time,
+            "corrected_initial_age": self.corrected_initial_age,
+            "freshness_lifetime": self.freshness_lifetime,
+            "must_revalidate": self.must_revalidate,
+            "no_cache": self.no_cache,
+        })
+        .to_string();
+
+        let mut buf = Vec::with_capacity(DISK_FILE_MAGIC.len() + meta.len() + 1 + self.body.len());
+        buf.extend_from_slice(DISK_FILE_MAGIC);
+        buf.extend_from_slice(meta.as_bytes());
+        buf.push(b'\n');
+        buf.extend_from_slice(&self.body);
+        buf
+    }
+
+    /// Get the key from the encoded data, only the leading part is required
+    pub(super) fn decode_key(data: &[u8]) -> Option<String> {
+        let meta = decode_meta(data).ok()?.0;
+        meta.get("key")?.as_str().map(|s| s.to_string())
+    }
+
+    pub(super) fn decode(data: Bytes) -> anyhow::Result<Self> {
+        let (meta, offset) = decode_meta(&data)?;
+        let get_u64 = |k: &str| {
+            meta.get(k)
+                .and_then(|v| v.as_u64())
+                .ok_or_else(|| anyhow!("no valid {k} found"))
+        };
+        let get_bool = |k: &str| meta.get(k).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let key = meta
+            .get("key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("no valid key found"))?
+            .to_string();
+        let code = u16::try_from(get_u64("code")?)?;
+        let reason = meta
+            .get("reason")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut headers = Vec::new();
+        if let Some(Value::Array(list)) = meta.get("headers") {
+            for v in list {
+                let (Some(name), Some(value)) = (
+                    v.get(0).and_then(|v| v.as_str()),
+                    v.get(1).and_then(|v| v.as_str()),
+                ) else {
+                    return Err(anyhow!("invalid header entry"));
+                };
+                let name = HeaderName::from_str(name)?;
+                let value = HttpHeaderValue::from_str(value)?;
+                headers.push((name, value));
+            }
+        }
+        let mut vary = Vec::new();
+        if let Some(Value::Array(list)) = meta.get("vary") {
+            for v in list {
+                let Some(name) = v.get(0).and_then(|v| v.as_str()) else {
+                    return Err(anyhow!("invalid vary entry"));
+                };
+                let name = HeaderName::from_str(name)?;
+                let value = v.get(1).and_then(|v| v.as_str()).map(|s| s.to_string());
+                vary.push((name, value));
+            }
+        }
+
+        Ok(HttpCacheEntry {
+            key,
+            code,
+            reason,
+            headers,
+            vary,
+            body: data.slice(offset..),
+            response_time: get_u64("response_time")?,
+            corrected_initial_age: get_u64("corrected_initial_age")?,
+            freshness_lifetime: get_u64("freshness_lifetime")?,
+            must_revalidate: get_bool("must_revalidate"),
+            no_cache: get_bool("no_cache"),
+        })
+    }
+}
+
+fn decode_meta(data: &[u8]) -> anyhow::Result<(Value, usize)> {
+    let Some(left) = data.strip_prefix(DISK_FILE_MAGIC) else {
+        return Err(anyhow!("invalid file magic"));
+    };
+    let Some(p) = memchr::memchr(b'\n', left) else {
+        return Err(anyhow!("no meta line found"));
+    };
+    let meta = serde_json::from_slice(&left[..p])?;
+    Ok((meta, DISK_FILE_MAGIC.len() + p + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let mut rsp_headers = HttpHeaderMap::default();
+        rsp_headers.append(
+            header::CACHE_CONTROL,
+            HttpHeaderValue::from_static("max-age=60"),
+        );
+        rsp_headers.append(header::ETAG, HttpHeaderValue::from_static("\"abc\""));
+        rsp_headers.append(
+            header::VARY,
+            HttpHeaderValue::from_static("Accept-Encoding"),
+        );
+        rsp_headers.append(header::CONTENT_LENGTH, HttpHeaderValue::from_static("4"));
+        let mut req_headers = HttpHeaderMap::default();
+        req_headers.append(
+            header::ACCEPT_ENCODING,
+            HttpHeaderValue::from_static("gzip"),
+        );
+
+        let entry = HttpCacheEntry::new(
+            "http://www.example.net/".to_string(),
+            &req_headers,
+            200,
+            "OK",
+            &rsp_headers,
+            Bytes::from_static(b"test"),
+            1000,
+            1001,
+            86400,
+        );
+        assert_eq!(entry.current_age(1001), 1);
+        assert!(entry.is_fresh(&CacheControl::default(), 1050));
+        assert!(!entry.is_fresh(&CacheControl::default(), 1100));
+        assert!(entry.vary_match(&req_headers));
+        assert!(!entry.vary_match(&HttpHeaderMap::default()));
+
+        let data = entry.encode();
+        assert_eq!(
+            HttpCacheEntry::decode_key(&data).as_deref(),
+            Some("http://www.example.net/")
+        );
+        let decoded = HttpCacheEntry::decode(Bytes::from(data)).unwrap();
+        assert_eq!(decoded.body().as_ref(), b"test");
+        assert_eq!(decoded.freshness_lifetime, 60);
+        assert!(decoded.has_validator());
+
+        let mut cond = HttpHeaderMap::default();
+        cond.append(
+            header::IF_NONE_MATCH,
+            HttpHeaderValue::from_static("W/\"abc\""),
+        );
+        assert!(decoded.not_modified_for(&cond));
+    }
+}
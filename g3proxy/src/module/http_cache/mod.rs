@@ -0,0 +1,217 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use log::debug;
+use url::Url;
+
+use g3_types::net::{HttpHeaderMap, UpstreamAddr};
+
+use crate::config::http_cache::HttpCacheConfig;
+
+mod policy;
+pub(crate) use policy::{CacheControl, request_cacheable, response_storable};
+
+mod entry;
+pub(crate) use entry::HttpCacheEntry;
+
+mod recorder;
+pub(crate) use recorder::{HttpCacheBody, HttpCacheRecordReader};
+
+mod stats;
+pub(crate) use stats::{HttpCacheStats, HttpCacheStatsSnapshot};
+
+mod store;
+use store::{DiskStore, MemoryStore};
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Get the cache key for the request, which is the absolute url with explicit port
+pub(crate) fn cache_key(is_https: bool, upstream: &UpstreamAddr, path_and_query: &str) -> String {
+    let scheme = if is_https { "https" } else { "http" };
+    format!("{scheme}://{upstream}{path_and_query}")
+}
+
+fn url_cache_key(url: &str) -> anyhow::Result<String> {
+    let url = Url::parse(url).map_err(|e| anyhow!("invalid url: {e}"))?;
+    let is_https = match url.scheme() {
+        "http" => false,
+        "https" => true,
+        s => return Err(anyhow!("unsupported url scheme {s}")),
+    };
+    let upstream = UpstreamAddr::try_from(&url)?;
+    let path_and_query = match url.query() {
+        Some(q) => format!("{}?{q}", url.path()),
+        None => url.path().to_string(),
+    };
+    Ok(cache_key(is_https, &upstream, &path_and_query))
+}
+
+pub(crate) enum HttpCacheLookup {
+    Fresh(Arc<HttpCacheEntry>),
+    /// a stored response that should be validated before use
+    Stale(Arc<HttpCacheEntry>),
+    Miss,
+}
+
+/// The shared http response cache, with an in memory tier and an optional disk tier
+pub(crate) struct HttpCache {
+    config: HttpCacheConfig,
+    memory: Mutex<MemoryStore>,
+    disk: Option<Arc<Mutex<DiskStore>>>,
+}
+
+impl HttpCache {
+    pub(crate) fn try_build(config: &HttpCacheConfig) -> anyhow::Result<Arc<Self>> {
+        let disk = match &config.disk {
+            Some(disk_config) => Some(DiskStore::spawn(disk_config)?),
+            None => None,
+        };
+        Ok(Arc::new(HttpCache {
+            config: config.clone(),
+            memory: Mutex::new(MemoryStore::new(config.memory_size)),
+            disk,
+        }))
+    }
+
+    #[inline]
+    pub(crate) fn max_object_size(&self) -> usize {
+        self.config.max_object_size()
+    }
+
+    #[inline]
+    pub(crate) fn heuristic_max_age(&self) -> u64 {
+        self.config.heuristic_max_age.as_secs()
+    }
+
+    async fn get(&self, key: &str) -> Option<Arc<HttpCacheEntry>> {
+        if let Some(entry) = self.memory.lock().unwrap().get(key) {
+            return Some(entry);
+        }
+
+        let disk = self.disk.as_ref()?;
+        let path = disk.lock().unwrap().get(key)?;
+        match store::read_file(path).await {
+            Ok(entry) if entry.key == key => {
+                let entry = Arc::new(entry);
+                if entry.size() <= self.config.memory_max_object_size {
+                    // promote to memory
+                    let evicted = self.memory.lock().unwrap().insert(entry.clone());
+                    self.save_to_disk(disk, evicted);
+                }
+                Some(entry)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                debug!("failed to load http cache entry for {key}: {e:?}");
+                disk.lock().unwrap().remove(key);
+                None
+            }
+        }
+    }
+
+    pub(crate) async fn lookup(
+        &self,
+        key: &str,
+        req_headers: &HttpHeaderMap,
+        req_cc: &CacheControl,
+    ) -> HttpCacheLookup {
+        let Some(entry) = self.get(key).await else {
+            return HttpCacheLookup::Miss;
+        };
+        if !entry.vary_match(req_headers) {
+            return HttpCacheLookup::Miss;
+        }
+        if entry.is_fresh(req_cc, unix_now()) {
+            HttpCacheLookup::Fresh(entry)
+        } else if entry.has_validator() {
+            HttpCacheLookup::Stale(entry)
+        } else {
+            HttpCacheLookup::Miss
+        }
+    }
+
+    pub(crate) fn store(&self, entry: Arc<HttpCacheEntry>) {
+        let size = entry.size();
+        let mut evicted = Vec::new();
+        if size <= self.config.memory_max_object_size {
+            evicted = self.memory.lock().unwrap().insert(entry.clone());
+        } else {
+            self.memory.lock().unwrap().remove(&entry.key);
+        }
+
+        let Some(disk) = &self.disk else {
+            return;
+        };
+        if size > self.config.memory_max_object_size {
+            // write through for large objects
+            evicted.push(entry);
+        }
+        self.save_to_disk(disk, evicted);
+    }
+
+    fn save_to_disk(&self, disk: &Mutex<DiskStore>, entries: Vec<Arc<HttpCacheEntry>>) {
+        for entry in entries {
+            let data = entry.encode();
+            let mut disk = disk.lock().unwrap();
+            if data.len() as u64 > disk.max_object_size() {
+                disk.remove(&entry.key);
+            } else {
+                disk.insert(&entry.key, data);
+            }
+        }
+    }
+
+    pub(crate) fn invalidate(&self, key: &str) {
+        self.memory.lock().unwrap().remove(key);
+        if let Some(disk) = &self.disk {
+            disk.lock().unwrap().remove(key);
+        }
+    }
+
+    /// Remove the entry of the url, or all the entries if no url is given
+    pub(crate) fn purge(&self, url: Option<&str>) -> anyhow::Result<()> {
+        match url {
+            Some(url) => {
+                let key = url_cache_key(url)?;
+                self.invalidate(&key);
+            }
+            None => {
+                let count = self.memory.lock().unwrap().clear();
+                debug!("purged {count} http cache entries in memory");
+                if let Some(disk) = &self.disk {
+                    disk.lock().unwrap().clear();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_key() {
+        assert_eq!(
+            url_cache_key("http://www.example.net/a?b=c").unwrap(),
+            "http://www.example.net:80/a?b=c"
+        );
+        assert_eq!(
+            url_cache_key("https://www.example.net:8443").unwrap(),
+            "https://www.example.net:8443/"
+        );
+        assert!(url_cache_key("ftp://www.example.net/").is_err());
+    }
+}
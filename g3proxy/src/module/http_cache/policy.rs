@@ -0,0 +1,248 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use chrono::DateTime;
+use http::{Method, header};
+
+use g3_types::net::HttpHeaderMap;
+
+/// Cache-Control directives, see rfc9111 Section 5.2
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CacheControl {
+    pub(crate) no_store: bool,
+    pub(crate) no_cache: bool,
+    pub(crate) private: bool,
+    pub(crate) public: bool,
+    pub(crate) must_revalidate: bool,
+    pub(crate) only_if_cached: bool,
+    pub(crate) max_age: Option<u64>,
+    pub(crate) s_maxage: Option<u64>,
+    pub(crate) min_fresh: Option<u64>,
+    /// `Some(None)` means any stale response is acceptable
+    pub(crate) max_stale: Option<Option<u64>>,
+}
+
+impl CacheControl {
+    pub(crate) fn parse(headers: &HttpHeaderMap) -> Self {
+        let mut cc = CacheControl::default();
+        for v in headers.get_all(header::CACHE_CONTROL) {
+            for directive in v.to_str().split(',') {
+                let directive = directive.trim();
+                if directive.is_empty() {
+                    continue;
+                }
+                let (name, value) = match directive.split_once('=') {
+                    Some((n, v)) => (n.trim(), Some(v.trim().trim_matches('"'))),
+                    None => (directive, None),
+                };
+                let seconds = value.and_then(|v| u64::from_str(v).ok());
+                match name.to_ascii_lowercase().as_str() {
+                    "no-store" => cc.no_store = true,
+                    // the field-name form is treated the same as the unqualified one
+                    "no-cache" => cc.no_cache = true,
+                    "private" => cc.private = true,
+                    "public" => cc.public = true,
+                    "must-revalidate" | "proxy-revalidate" => cc.must_revalidate = true,
+                    "only-if-cached" => cc.only_if_cached = true,
+                    "max-age" => cc.max_age = seconds.or(Some(0)),
+                    "s-maxage" => {
+                        cc.s_maxage = seconds.or(Some(0));
+                        // s-maxage also implies proxy-revalidate
+                        cc.must_revalidate = true;
+                    }
+                    "min-fresh" => cc.min_fresh = seconds,
+                    "max-stale" => cc.max_stale = Some(seconds),
+                    _ => {}
+                }
+            }
+        }
+
+        // Pragma: no-cache is only used if there is no Cache-Control header
+        if !headers.contains_key(header::CACHE_CONTROL)
+            && headers
+                .get_all(header::PRAGMA)
+                .iter()
+                .any(|v| v.to_str().eq_ignore_ascii_case("no-cache"))
+        {
+            cc.no_cache = true;
+        }
+        cc
+    }
+}
+
+/// Parse a HTTP-date value to unix timestamp
+pub(crate) fn parse_http_date(value: &str) -> Option<u64> {
+    let dt = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    u64::try_from(dt.timestamp()).ok()
+}
+
+pub(super) fn header_date(headers: &HttpHeaderMap, name: header::HeaderName) -> Option<u64> {
+    headers.get(name).and_then(|v| parse_http_date(v.to_str()))
+}
+
+/// Status codes that are defined as heuristically cacheable, see rfc9110 Section 15.1
+fn is_heuristically_cacheable(code: u16) -> bool {
+    matches!(
+        code,
+        200 | 203 | 204 | 206 | 300 | 301 | 308 | 404 | 405 | 410 | 414 | 501
+    )
+}
+
+/// Check if the request may be served from or stored in a shared cache
+pub(crate) fn request_cacheable(method: &Method, headers: &HttpHeaderMap, has_body: bool) -> bool {
+    if !matches!(*method, Method::GET | Method::HEAD) || has_body {
+        return false;
+    }
+    // range requests are not handled
+    if headers.contains_key(header::RANGE) {
+        return false;
+    }
+    !CacheControl::parse(headers).no_store
+}
+
+/// Check if the response to a GET request can be stored in a shared cache, see rfc9111 Section 3
+pub(crate) fn response_storable(
+    req_headers: &HttpHeaderMap,
+    code: u16,
+    rsp_headers: &HttpHeaderMap,
+) -> bool {
+    // only complete final responses are stored
+    if code < 200 || code == 206 || code == 304 {
+        return false;
+    }
+
+    let rsp_cc = CacheControl::parse(rsp_headers);
+    if rsp_cc.no_store || rsp_cc.private {
+        return false;
+    }
+    if req_headers.contains_key(header::AUTHORIZATION)
+        && !(rsp_cc.public || rsp_cc.must_revalidate || rsp_cc.s_maxage.is_some())
+    {
+        return false;
+    }
+    // responses that set cookies are private to the client
+    if rsp_headers.contains_key(header::SET_COOKIE) {
+        return false;
+    }
+    if rsp_headers
+        .get_all(header::VARY)
+        .iter()
+        .any(|v| v.to_str().split(',').any(|s| s.trim() == "*"))
+    {
+        return false;
+    }
+
+    rsp_cc.public
+        || rsp_cc.max_age.is_some()
+        || rsp_cc.s_maxage.is_some()
+        || rsp_headers.contains_key(header::EXPIRES)
+        || is_heuristically_cacheable(code)
+}
+
+/// Calculate the freshness lifetime in seconds, see rfc9111 Section 4.2.1
+pub(super) fn freshness_lifetime(
+    code: u16,
+    rsp_headers: &HttpHeaderMap,
+    rsp_cc: &CacheControl,
+    date: u64,
+    heuristic_max_age: u64,
+) -> u64 {
+    if let Some(v) = rsp_cc.s_maxage {
+        return v;
+    }
+    if let Some(v) = rsp_cc.max_age {
+        return v;
+    }
+    if let Some(value) = rsp_headers.get(header::EXPIRES) {
+        // invalid Expires value means already expired
+        return parse_http_date(value.to_str())
+            .map(|expires| expires.saturating_sub(date))
+            .unwrap_or(0);
+    }
+    if is_heuristically_cacheable(code)
+        && let Some(last_modified) = header_date(rsp_headers, header::LAST_MODIFIED)
+    {
+        return (date.saturating_sub(last_modified) / 10).min(heuristic_max_age);
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+
+    fn headers(list: &[(&'static str, &'static str)]) -> HttpHeaderMap {
+        let mut map = HttpHeaderMap::default();
+        for (name, value) in list {
+            map.append(
+                header::HeaderName::from_static(name),
+                HttpHeaderValue::from_static(value),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn parse_cache_control() {
+        let cc = CacheControl::parse(&headers(&[
+            ("cache-control", "public, max-age=60"),
+            ("cache-control", "s-maxage=\"120\", max-stale"),
+        ]));
+        assert!(cc.public);
+        assert_eq!(cc.max_age, Some(60));
+        assert_eq!(cc.s_maxage, Some(120));
+        assert_eq!(cc.max_stale, Some(None));
+        assert!(cc.must_revalidate);
+
+        let cc = CacheControl::parse(&headers(&[("pragma", "no-cache")]));
+        assert!(cc.no_cache);
+    }
+
+    #[test]
+    fn storable() {
+        let req = HttpHeaderMap::default();
+        assert!(response_storable(
+            &req,
+            200,
+            &headers(&[("cache-control", "max-age=10")])
+        ));
+        assert!(!response_storable(
+            &req,
+            200,
+            &headers(&[("cache-control", "private, max-age=10")])
+        ));
+        assert!(!response_storable(&req, 200, &headers(&[("vary", "*")])));
+        assert!(!response_storable(&req, 302, &HttpHeaderMap::default()));
+
+        let req = headers(&[("authorization", "Basic dGVzdDp0ZXN0")]);
+        assert!(!response_storable(
+            &req,
+            200,
+            &headers(&[("cache-control", "max-age=10")])
+        ));
+        assert!(response_storable(
+            &req,
+            200,
+            &headers(&[("cache-control", "s-maxage=10")])
+        ));
+    }
+
+    #[test]
+    fn lifetime() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        let rsp = headers(&[("expires", "Sun, 06 Nov 1994 08:59:37 GMT")]);
+        let cc = CacheControl::parse(&rsp);
+        assert_eq!(freshness_lifetime(200, &rsp, &cc, date, 86400), 600);
+
+        let rsp = headers(&[("last-modified", "Sun, 06 Nov 1994 06:49:37 GMT")]);
+        let cc = CacheControl::parse(&rsp);
+        assert_eq!(freshness_lifetime(200, &rsp, &cc, date, 86400), 720);
+        assert_eq!(freshness_lifetime(200, &rsp, &cc, date, 300), 300);
+        assert_eq!(freshness_lifetime(302, &rsp, &cc, date, 86400), 0);
+    }
+}
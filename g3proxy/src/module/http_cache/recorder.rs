@@ -0,0 +1,115 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+
+use g3_http::{HttpBodyDecodeReader, HttpBodyType};
+
+/// The response body data recorded for the cache, in wire format
+pub(crate) struct HttpCacheBody {
+    body_type: HttpBodyType,
+    data: Vec<u8>,
+    max_size: usize,
+    overflow: bool,
+}
+
+impl HttpCacheBody {
+    pub(crate) fn new(body_type: HttpBodyType, max_size: usize) -> Self {
+        let overflow = matches!(body_type, HttpBodyType::ContentLength(n) if n > max_size as u64);
+        HttpCacheBody {
+            body_type,
+            data: Vec::new(),
+            max_size,
+            overflow,
+        }
+    }
+
+    fn record(&mut self, data: &[u8]) {
+        if self.overflow {
+            return;
+        }
+        // the chunked encoding overhead is also counted in
+        if self.data.len() + data.len() > self.max_size {
+            self.overflow = true;
+            self.data = Vec::new();
+        } else {
+            self.data.extend_from_slice(data);
+        }
+    }
+
+    /// Get the decoded body, or None if it's too large to be stored
+    pub(crate) async fn finish(self, body_line_max_len: usize) -> Option<Bytes> {
+        if self.overflow {
+            return None;
+        }
+        match self.body_type {
+            HttpBodyType::Chunked => {
+                let mut encoded = self.data.as_slice();
+                let mut reader = HttpBodyDecodeReader::new_chunked(&mut encoded, body_line_max_len);
+                let mut decoded = Vec::with_capacity(self.data.len());
+                reader.read_to_end(&mut decoded).await.ok()?;
+                Some(Bytes::from(decoded))
+            }
+            _ => Some(Bytes::from(self.data)),
+        }
+    }
+}
+
+/// A reader wrapper that records the data for the cache if needed
+pub(crate) struct HttpCacheRecordReader<'a, R> {
+    inner: R,
+    body: Option<&'a mut HttpCacheBody>,
+}
+
+impl<'a, R> HttpCacheRecordReader<'a, R> {
+    pub(crate) fn new(inner: R, body: Option<&'a mut HttpCacheBody>) -> Self {
+        HttpCacheRecordReader { inner, body }
+    }
+}
+
+impl<R> AsyncRead for HttpCacheRecordReader<'_, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        if let Some(body) = &mut self.body {
+            body.record(&buf.filled()[filled..]);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn record_chunked() {
+        let mut body = HttpCacheBody::new(HttpBodyType::Chunked, 1024);
+        let data = b"4\r\ntest\r\n3\r\nabc\r\n0\r\n\r\n";
+        let mut reader = HttpCacheRecordReader::new(data.as_slice(), Some(&mut body));
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), data);
+        let decoded = body.finish(1024).await.unwrap();
+        assert_eq!(decoded.as_ref(), b"testabc");
+
+        let mut body = HttpCacheBody::new(HttpBodyType::ReadUntilEnd, 4);
+        let mut reader = HttpCacheRecordReader::new(b"12345".as_slice(), Some(&mut body));
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert!(body.finish(1024).await.is_none());
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Default)]
+pub(crate) struct HttpCacheStatsSnapshot {
+    pub(crate) hit: u64,
+    pub(crate) miss: u64,
+    pub(crate) revalidated: u64,
+    pub(crate) stored: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct HttpCacheStats {
+    hit: AtomicU64,
+    miss: AtomicU64,
+    revalidated: AtomicU64,
+    stored: AtomicU64,
+}
+
+impl HttpCacheStats {
+    pub(crate) fn add_hit(&self) {
+        self.hit.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_miss(&self) {
+        self.miss.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_revalidated(&self) {
+        self.revalidated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_stored(&self) {
+        self.stored.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> HttpCacheStatsSnapshot {
+        HttpCacheStatsSnapshot {
+            hit: self.hit.load(Ordering::Relaxed),
+            miss: self.miss.load(Ordering::Relaxed),
+            revalidated: self.revalidated.load(Ordering::Relaxed),
+            stored: self.stored.load(Ordering::Relaxed),
+        }
+    }
+}
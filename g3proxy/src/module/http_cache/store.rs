@@ -0,0 +1,464 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use log::warn;
+use lru::LruCache;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+use super::HttpCacheEntry;
+use crate::config::http_cache::HttpCacheDiskConfig;
+
+/// the max size to read when loading the key from the meta line
+const META_READ_SIZE: u64 = 64 * 1024;
+/// the max number of pending disk io operations
+const DISK_IO_QUEUE_SIZE: usize = 1024;
+
+pub(super) struct MemoryStore {
+    entries: LruCache<String, Arc<HttpCacheEntry>>,
+    size: usize,
+    max_size: usize,
+}
+
+impl MemoryStore {
+    pub(super) fn new(max_size: usize) -> Self {
+        MemoryStore {
+            entries: LruCache::unbounded(),
+            size: 0,
+            max_size,
+        }
+    }
+
+    pub(super) fn get(&mut self, key: &str) -> Option<Arc<HttpCacheEntry>> {
+        self.entries.get(key).cloned()
+    }
+
+    /// Insert the entry and return the evicted ones
+    pub(super) fn insert(&mut self, entry: Arc<HttpCacheEntry>) -> Vec<Arc<HttpCacheEntry>> {
+        let size = entry.size();
+        if let Some(old) = self.entries.put(entry.key.clone(), entry) {
+            self.size -= old.size();
+        }
+        self.size += size;
+
+        let mut evicted = Vec::new();
+        while self.size > self.max_size {
+            let Some((_, old)) = self.entries.pop_lru() else {
+                break;
+            };
+            self.size -= old.size();
+            evicted.push(old);
+        }
+        evicted
+    }
+
+    pub(super) fn remove(&mut self, key: &str) -> bool {
+        match self.entries.pop(key) {
+            Some(old) => {
+                self.size -= old.size();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(super) fn clear(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.clear();
+        self.size = 0;
+        count
+    }
+}
+
+/// The on-disk store, the index will be rebuilt from the existing files in the background.
+///
+/// All file operations are done in order in a single io task, so a remove will never race with
+/// a previous write to the same file. The queue is bounded, a write will be skipped if it's full,
+/// and the removals will be kept and sent later.
+pub(super) struct DiskStore {
+    dir: PathBuf,
+    index: LruCache<String, u64>,
+    size: u64,
+    max_size: u64,
+    max_object_size: u64,
+    loading: Option<DiskIndexLoading>,
+    io_sender: mpsc::Sender<DiskIoOp>,
+    pending_removal: Vec<PathBuf>,
+}
+
+/// The changes that should be applied to the loaded index
+#[derive(Default)]
+struct DiskIndexLoading {
+    cleared: bool,
+    removed: HashSet<String>,
+}
+
+enum DiskIoOp {
+    Write(PathBuf, Vec<u8>),
+    Remove(Vec<PathBuf>),
+}
+
+fn file_name(key: &str) -> String {
+    let digest = openssl::sha::sha256(key.as_bytes());
+    let mut s = String::with_capacity(digest.len() * 2);
+    for b in digest {
+        let _ = write!(s, "{b:02x}");
+    }
+    s
+}
+
+impl DiskStore {
+    pub(super) fn spawn(config: &HttpCacheDiskConfig) -> anyhow::Result<Arc<Mutex<Self>>> {
+        fs::create_dir_all(&config.path).map_err(|e| {
+            anyhow!(
+                "failed to create cache directory {}: {e}",
+                config.path.display()
+            )
+        })?;
+
+        let (io_sender, io_receiver) = mpsc::channel(DISK_IO_QUEUE_SIZE);
+        let store = Arc::new(Mutex::new(DiskStore {
+            dir: config.path.clone(),
+            index: LruCache::unbounded(),
+            size: 0,
+            max_size: config.size,
+            max_object_size: config.max_object_size,
+            loading: Some(DiskIndexLoading::default()),
+            io_sender,
+            pending_removal: Vec::new(),
+        }));
+        tokio::spawn(run_io(
+            config.path.clone(),
+            Arc::downgrade(&store),
+            io_receiver,
+        ));
+        Ok(store)
+    }
+
+    /// Merge the index loaded from disk, the entries added after start will be more recent
+    fn merge_index(&mut self, loaded: Vec<(String, u64)>) {
+        let Some(loading) = self.loading.take() else {
+            return;
+        };
+
+        let mut index = LruCache::unbounded();
+        let mut size = 0;
+        let mut removed = Vec::new();
+        for (key, len) in loaded {
+            if loading.cleared || loading.removed.contains(&key) {
+                removed.push(self.dir.join(file_name(&key)));
+            } else if !self.index.contains(&key) {
+                size += len;
+                index.put(key, len);
+            }
+        }
+        for (key, len) in self.index.iter().rev() {
+            size += *len;
+            index.put(key.clone(), *len);
+        }
+        self.index = index;
+        self.size = size;
+
+        removed.extend(self.evict());
+        self.remove_files(removed);
+    }
+
+    fn evict(&mut self) -> Vec<PathBuf> {
+        let mut removed = Vec::new();
+        while self.size > self.max_size {
+            let Some((key, size)) = self.index.pop_lru() else {
+                break;
+            };
+            self.size -= size;
+            removed.push(self.dir.join(file_name(&key)));
+        }
+        removed
+    }
+
+    fn remove_files(&mut self, paths: Vec<PathBuf>) {
+        let mut all = std::mem::take(&mut self.pending_removal);
+        all.extend(paths);
+        if all.is_empty() {
+            return;
+        }
+        if let Err(TrySendError::Full(DiskIoOp::Remove(all))) =
+            self.io_sender.try_send(DiskIoOp::Remove(all))
+        {
+            self.pending_removal = all;
+        }
+    }
+
+    #[inline]
+    pub(super) fn max_object_size(&self) -> u64 {
+        self.max_object_size
+    }
+
+    /// Get the file path if the key exists
+    pub(super) fn get(&mut self, key: &str) -> Option<PathBuf> {
+        self.index.get(key).map(|_| self.dir.join(file_name(key)))
+    }
+
+    /// Add the key to index and write the data to file in the background
+    pub(super) fn insert(&mut self, key: &str, data: Vec<u8>) {
+        let size = data.len() as u64;
+        if let Some(old) = self.index.put(key.to_string(), size) {
+            self.size -= old;
+        }
+        self.size += size;
+        if let Some(loading) = &mut self.loading {
+            loading.removed.remove(key);
+        }
+        let path = self.dir.join(file_name(key));
+        let removed = self.evict();
+        // skip the write if the new entry itself is evicted
+        let evicted = removed.contains(&path);
+        self.remove_files(removed);
+        if evicted {
+            return;
+        }
+        // the write should not be cancelled by a previous removal that is still pending
+        self.pending_removal.retain(|p| *p != path);
+        if let Err(TrySendError::Full(_)) = self.io_sender.try_send(DiskIoOp::Write(path, data)) {
+            // roll back the index entry, and remove the old file if there is one
+            self.remove(key);
+        }
+    }
+
+    /// Remove the key from index and remove the file in the background
+    pub(super) fn remove(&mut self, key: &str) {
+        if let Some(loading) = &mut self.loading {
+            loading.removed.insert(key.to_string());
+        }
+        if let Some(size) = self.index.pop(key) {
+            self.size -= size;
+            let path = self.dir.join(file_name(key));
+            self.remove_files(vec![path]);
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        if let Some(loading) = &mut self.loading {
+            loading.cleared = true;
+            loading.removed.clear();
+        }
+        let removed = self
+            .index
+            .iter()
+            .map(|(key, _)| self.dir.join(file_name(key)))
+            .collect();
+        self.index.clear();
+        self.size = 0;
+        self.remove_files(removed);
+    }
+}
+
+/// Scan the cache directory, the invalid files will be removed
+fn load_index(dir: &Path) -> anyhow::Result<Vec<(String, u64)>> {
+    let read_dir =
+        fs::read_dir(dir).map_err(|e| anyhow!("failed to read dir {}: {e}", dir.display()))?;
+    let mut loaded = Vec::new();
+    let mut buf = Vec::with_capacity(META_READ_SIZE as usize);
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        buf.clear();
+        let key = fs::File::open(&path).ok().and_then(|f| {
+            f.take(META_READ_SIZE).read_to_end(&mut buf).ok()?;
+            HttpCacheEntry::decode_key(&buf)
+        });
+        match key {
+            Some(key)
+                if path.file_name().and_then(|s| s.to_str()) == Some(file_name(&key).as_str()) =>
+            {
+                loaded.push((key, meta.len()));
+            }
+            _ => {
+                warn!("removing invalid http cache file {}", path.display());
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+async fn run_io(
+    dir: PathBuf,
+    store: Weak<Mutex<DiskStore>>,
+    mut receiver: mpsc::Receiver<DiskIoOp>,
+) {
+    // load the index before any other file operations
+    let loaded = match tokio::task::spawn_blocking(move || load_index(&dir)).await {
+        Ok(Ok(loaded)) => loaded,
+        Ok(Err(e)) => {
+            warn!("failed to load http cache index: {e:?}");
+            Vec::new()
+        }
+        Err(e) => {
+            warn!("failed to join http cache index loading task: {e}");
+            Vec::new()
+        }
+    };
+    let Some(store) = store.upgrade() else {
+        return;
+    };
+    store.lock().unwrap().merge_index(loaded);
+    drop(store);
+
+    while let Some(op) = receiver.recv().await {
+        match op {
+            DiskIoOp::Write(path, data) => write_file(&path, data).await,
+            DiskIoOp::Remove(paths) => {
+                for path in paths {
+                    let _ = tokio::fs::remove_file(&path).await;
+                }
+            }
+        }
+    }
+}
+
+/// Write to a temp file and then rename, so the readers will never see a partial file
+async fn write_file(path: &Path, data: Vec<u8>) {
+    let tmp_path = path.with_extension("tmp");
+    if let Err(e) = tokio::fs::write(&tmp_path, data).await {
+        warn!("failed to write http cache file {}: {e}", tmp_path.display());
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return;
+    }
+    if let Err(e) = tokio::fs::rename(&tmp_path, path).await {
+        warn!("failed to rename http cache file {}: {e}", path.display());
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+    }
+}
+
+pub(super) async fn read_file(path: PathBuf) -> anyhow::Result<HttpCacheEntry> {
+    let data = tokio::fs::read(&path)
+        .await
+        .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+    HttpCacheEntry::decode(Bytes::from(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use g3_types::net::HttpHeaderMap;
+
+    fn test_entry(key: &str) -> HttpCacheEntry {
+        HttpCacheEntry::new(
+            key.to_string(),
+            &HttpHeaderMap::default(),
+            200,
+            "OK",
+            &HttpHeaderMap::default(),
+            Bytes::from_static(b"test"),
+            1000,
+            1001,
+            86400,
+        )
+    }
+
+    async fn wait_loaded(store: &Mutex<DiskStore>) {
+        for _ in 0..100 {
+            if store.lock().unwrap().loading.is_none() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("http cache index not loaded");
+    }
+
+    #[tokio::test]
+    async fn disk_reload() {
+        let dir =
+            std::env::temp_dir().join(format!("g3proxy-http-cache-{}", std::process::id()));
+        let config = HttpCacheDiskConfig {
+            path: dir.clone(),
+            size: 1024 * 1024,
+            max_object_size: 64 * 1024,
+        };
+
+        let store = DiskStore::spawn(&config).unwrap();
+        wait_loaded(&store).await;
+        store
+            .lock()
+            .unwrap()
+            .insert("http://a.example.net:80/", test_entry("http://a.example.net:80/").encode());
+        store
+            .lock()
+            .unwrap()
+            .insert("http://b.example.net:80/", test_entry("http://b.example.net:80/").encode());
+        let b_path = store.lock().unwrap().get("http://b.example.net:80/").unwrap();
+        // the remove will be done after the write
+        store.lock().unwrap().remove("http://b.example.net:80/");
+        let a_path = store.lock().unwrap().get("http://a.example.net:80/").unwrap();
+        drop(store);
+
+        for _ in 0..100 {
+            if a_path.exists() && !b_path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let entry = read_file(a_path).await.unwrap();
+        assert_eq!(entry.body().as_ref(), b"test");
+        assert!(!b_path.exists());
+
+        fs::write(dir.join("invalid"), b"invalid").unwrap();
+        let store = DiskStore::spawn(&config).unwrap();
+        wait_loaded(&store).await;
+        let mut store = store.lock().unwrap();
+        assert!(store.get("http://a.example.net:80/").is_some());
+        assert!(store.get("http://b.example.net:80/").is_none());
+        assert!(!dir.join("invalid").exists());
+        drop(store);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn disk_queue_full() {
+        let dir = std::env::temp_dir().join(format!(
+            "g3proxy-http-cache-queue-{}",
+            std::process::id()
+        ));
+        let config = HttpCacheDiskConfig {
+            path: dir.clone(),
+            size: 64 * 1024 * 1024,
+            max_object_size: 64 * 1024,
+        };
+
+        let store = DiskStore::spawn(&config).unwrap();
+        wait_loaded(&store).await;
+        let mut guard = store.lock().unwrap();
+        // the io task won't run before we yield
+        for i in 0..=DISK_IO_QUEUE_SIZE {
+            let key = format!("http://{i}.example.net:80/");
+            guard.insert(&key, test_entry(&key).encode());
+        }
+        assert!(guard.get("http://0.example.net:80/").is_some());
+        let last = format!("http://{DISK_IO_QUEUE_SIZE}.example.net:80/");
+        assert!(guard.get(&last).is_none());
+        assert_eq!(guard.pending_removal.len(), 1);
+        drop(guard);
+        drop(store);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
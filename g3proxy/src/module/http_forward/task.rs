@@ -15,10 +15,13 @@ pub(crate) struct HttpForwardTaskNotes {
     pub(crate) pipeline_wait: Duration,
     pub(crate) reused_connection: bool,
     create_ins: Instant,
+    pub(crate) dur_ups_connect_start: Option<Duration>,
     pub(crate) dur_req_send_hdr: Duration,
     pub(crate) dur_req_send_all: Duration,
     pub(crate) dur_rsp_recv_hdr: Duration,
     pub(crate) dur_rsp_recv_all: Duration,
+    pub(crate) dur_icap_reqmod: Option<(Duration, Duration)>,
+    pub(crate) dur_icap_respmod: Option<(Duration, Duration)>,
     pub(crate) retry_new_connection: bool,
 }
 
@@ -39,14 +42,21 @@ impl HttpForwardTaskNotes {
             pipeline_wait: req_received.elapsed(),
             reused_connection: false,
             create_ins: task_created,
+            dur_ups_connect_start: None,
             dur_req_send_hdr: Duration::default(),
             dur_req_send_all: Duration::default(),
             dur_rsp_recv_hdr: Duration::default(),
             dur_rsp_recv_all: Duration::default(),
+            dur_icap_reqmod: None,
+            dur_icap_respmod: None,
             retry_new_connection: false,
         }
     }
 
+    pub(crate) fn mark_ups_connect_start(&mut self) {
+        self.dur_ups_connect_start = Some(self.create_ins.elapsed());
+    }
+
     pub(crate) fn mark_req_send_hdr(&mut self) {
         self.dur_req_send_hdr = self.create_ins.elapsed();
     }
@@ -70,4 +80,16 @@ impl HttpForwardTaskNotes {
     pub(crate) fn mark_rsp_recv_all(&mut self) {
         self.dur_rsp_recv_all = self.create_ins.elapsed();
     }
+
+    pub(crate) fn time_elapsed(&self) -> Duration {
+        self.create_ins.elapsed()
+    }
+
+    pub(crate) fn mark_icap_reqmod(&mut self, start: Duration) {
+        self.dur_icap_reqmod = Some((start, self.create_ins.elapsed()));
+    }
+
+    pub(crate) fn mark_icap_respmod(&mut self, start: Duration) {
+        self.dur_icap_respmod = Some((start, self.create_ins.elapsed()));
+    }
 }
@@ -0,0 +1,334 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use uuid::Uuid;
+
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+use crate::auth::User;
+use crate::config::http_header_rewrite::{
+    HttpHeaderRewriteAction, HttpHeaderRewriteConfig, HttpHeaderRewriteRule,
+    HttpHeaderTemplatePart, HttpHeaderValueTemplate,
+};
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+use crate::serve::ServerTaskNotes;
+
+/// The server level and the user level rewrite rules, the server rules will be applied first
+#[derive(Clone, Copy, Default)]
+pub(crate) struct HttpHeaderRewriteRules<'a> {
+    server: Option<&'a HttpHeaderRewriteConfig>,
+    user: Option<&'a HttpHeaderRewriteConfig>,
+}
+
+impl<'a> HttpHeaderRewriteRules<'a> {
+    pub(crate) fn new(server: Option<&'a HttpHeaderRewriteConfig>, user: Option<&'a User>) -> Self {
+        HttpHeaderRewriteRules {
+            server,
+            user: user.map(|u| u.http_header_rewrite()),
+        }
+    }
+
+    fn select<F>(&self, select: F) -> [&'a [HttpHeaderRewriteRule]; 2]
+    where
+        F: Fn(&'a HttpHeaderRewriteConfig) -> &'a [HttpHeaderRewriteRule],
+    {
+        [
+            self.server.map(&select).unwrap_or_default(),
+            self.user.map(&select).unwrap_or_default(),
+        ]
+    }
+
+    pub(crate) fn has_request(&self) -> bool {
+        self.select(|c| c.request.as_slice())
+            .iter()
+            .any(|rules| !rules.is_empty())
+    }
+
+    pub(crate) fn has_response(&self) -> bool {
+        self.select(|c| c.response.as_slice())
+            .iter()
+            .any(|rules| !rules.is_empty())
+    }
+
+    pub(crate) fn rewrite_request<M: HttpHeaderRewriteTarget>(
+        &self,
+        ctx: &HttpHeaderRewriteContext<'_>,
+        headers: &mut M,
+    ) {
+        for rules in self.select(|c| c.request.as_slice()) {
+            ctx.rewrite(rules, headers);
+        }
+    }
+
+    pub(crate) fn rewrite_response<M: HttpHeaderRewriteTarget>(
+        &self,
+        ctx: &HttpHeaderRewriteContext<'_>,
+        headers: &mut M,
+    ) {
+        for rules in self.select(|c| c.response.as_slice()) {
+            ctx.rewrite(rules, headers);
+        }
+    }
+}
+
+/// The variables that can be used in header value templates
+pub(crate) struct HttpHeaderRewriteContext<'a> {
+    pub(crate) username: Option<&'a str>,
+    pub(crate) client_ip: IpAddr,
+    pub(crate) egress_ip: Option<IpAddr>,
+    pub(crate) task_id: &'a Uuid,
+}
+
+impl<'a> HttpHeaderRewriteContext<'a> {
+    /// Build the context for forward tasks, the egress ip will be taken from the connect notes
+    pub(crate) fn new(task_notes: &'a ServerTaskNotes, tcp_notes: &TcpConnectTaskNotes) -> Self {
+        HttpHeaderRewriteContext {
+            username: task_notes.user_ctx().map(|ctx| ctx.user_name().as_ref()),
+            client_ip: task_notes.client_ip(),
+            egress_ip: tcp_notes
+                .chained
+                .outgoing_addr
+                .or(tcp_notes.local)
+                .map(|addr| addr.ip()),
+            task_id: &task_notes.id,
+        }
+    }
+
+    /// Render the template, None will be returned if any variable is not available
+    fn render(&self, template: &HttpHeaderValueTemplate) -> Option<HttpHeaderValue> {
+        let mut s = String::new();
+        for part in template.parts() {
+            match part {
+                HttpHeaderTemplatePart::Literal(v) => s.push_str(v),
+                HttpHeaderTemplatePart::Username => s.push_str(self.username?),
+                HttpHeaderTemplatePart::ClientIp => {
+                    let _ = write!(s, "{}", self.client_ip);
+                }
+                HttpHeaderTemplatePart::EgressIp => {
+                    let _ = write!(s, "{}", self.egress_ip?);
+                }
+                HttpHeaderTemplatePart::TaskId => {
+                    let _ = write!(s, "{}", self.task_id);
+                }
+            }
+        }
+        HttpHeaderValue::from_str(&s).ok()
+    }
+
+    /// Apply the rules in order, values that are not valid after rewriting will be skipped
+    pub(crate) fn rewrite<M: HttpHeaderRewriteTarget>(
+        &self,
+        rules: &[HttpHeaderRewriteRule],
+        headers: &mut M,
+    ) {
+        for rule in rules {
+            match &rule.action {
+                HttpHeaderRewriteAction::Add(template) => {
+                    if let Some(mut value) = self.render(template) {
+                        value.set_original_name(&rule.original_name);
+                        headers.add_value(&rule.name, value);
+                    }
+                }
+                HttpHeaderRewriteAction::Set(template) => {
+                    if let Some(mut value) = self.render(template) {
+                        value.set_original_name(&rule.original_name);
+                        headers.set_value(&rule.name, value);
+                    }
+                }
+                HttpHeaderRewriteAction::Remove => headers.remove_all(&rule.name),
+                HttpHeaderRewriteAction::Replace(regex, replacement) => {
+                    headers.replace_each(&rule.name, |v| {
+                        let new = regex.replace_all(v, replacement.as_str());
+                        HeaderValue::from_str(&new).ok()
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// The header maps that the rewrite rules can be applied to
+pub(crate) trait HttpHeaderRewriteTarget {
+    fn add_value(&mut self, name: &HeaderName, value: HttpHeaderValue);
+    fn set_value(&mut self, name: &HeaderName, value: HttpHeaderValue);
+    fn remove_all(&mut self, name: &HeaderName);
+    /// Replace each of the values, the value will be dropped if None is returned
+    fn replace_each<F>(&mut self, name: &HeaderName, replace: F)
+    where
+        F: Fn(&str) -> Option<HeaderValue>;
+}
+
+impl HttpHeaderRewriteTarget for HttpHeaderMap {
+    fn add_value(&mut self, name: &HeaderName, value: HttpHeaderValue) {
+        self.append(name.clone(), value);
+    }
+
+    fn set_value(&mut self, name: &HeaderName, value: HttpHeaderValue) {
+        self.insert(name.clone(), value);
+    }
+
+    fn remove_all(&mut self, name: &HeaderName) {
+        self.remove(name);
+    }
+
+    fn replace_each<F>(&mut self, name: &HeaderName, replace: F)
+    where
+        F: Fn(&str) -> Option<HeaderValue>,
+    {
+        let values: Vec<HttpHeaderValue> = self.get_all(name).iter().cloned().collect();
+        if values.is_empty() {
+            return;
+        }
+        self.remove(name);
+        for mut value in values {
+            if let Some(new) = replace(value.to_str()) {
+                value.set_inner(new);
+                self.append(name.clone(), value);
+            }
+        }
+    }
+}
+
+impl HttpHeaderRewriteTarget for HeaderMap {
+    fn add_value(&mut self, name: &HeaderName, value: HttpHeaderValue) {
+        self.append(name.clone(), value.into_inner());
+    }
+
+    fn set_value(&mut self, name: &HeaderName, value: HttpHeaderValue) {
+        self.insert(name.clone(), value.into_inner());
+    }
+
+    fn remove_all(&mut self, name: &HeaderName) {
+        self.remove(name);
+    }
+
+    fn replace_each<F>(&mut self, name: &HeaderName, replace: F)
+    where
+        F: Fn(&str) -> Option<HeaderValue>,
+    {
+        let values: Vec<HeaderValue> = self.get_all(name).iter().cloned().collect();
+        if values.is_empty() {
+            return;
+        }
+        self.remove(name);
+        for value in values {
+            // values that are not valid utf-8 are dropped
+            if let Some(new) = value.to_str().ok().and_then(&replace) {
+                self.append(name.clone(), new);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn rewrite_request() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+            request:
+              - action: set
+                name: X-Forwarded-User
+                value: "${username}@${client_ip}"
+              - action: add
+                name: X-Egress
+                value: "${egress_ip}"
+              - action: remove
+                name: Cookie
+              - action: replace
+                name: User-Agent
+                regex: "curl/([0-9.]+)"
+                value: "client/$1"
+            "#,
+        )
+        .unwrap();
+        let config = HttpHeaderRewriteConfig::parse_yaml(&yaml[0]).unwrap();
+        assert_eq!(config.request.len(), 4);
+        assert!(config.response.is_empty());
+
+        let mut headers = HttpHeaderMap::default();
+        headers.insert(http::header::COOKIE, HttpHeaderValue::from_static("a=b"));
+        headers.insert(
+            http::header::USER_AGENT,
+            HttpHeaderValue::from_static("curl/8.0.1"),
+        );
+        headers.insert(
+            http::HeaderName::from_static("x-forwarded-user"),
+            HttpHeaderValue::from_static("fake"),
+        );
+
+        let task_id = Uuid::nil();
+        let ctx = HttpHeaderRewriteContext {
+            username: Some("alice"),
+            client_ip: IpAddr::from([192, 168, 1, 1]),
+            egress_ip: None,
+            task_id: &task_id,
+        };
+        ctx.rewrite(&config.request, &mut headers);
+
+        assert_eq!(
+            headers.get("x-forwarded-user").unwrap().to_str(),
+            "alice@192.168.1.1"
+        );
+        assert!(!headers.contains_key("x-egress"));
+        assert!(!headers.contains_key(http::header::COOKIE));
+        assert_eq!(
+            headers.get(http::header::USER_AGENT).unwrap().to_str(),
+            "client/8.0.1"
+        );
+    }
+
+    #[test]
+    fn server_rules() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+            response:
+              - action: set
+                name: X-Task-Id
+                value: "${task_id}"
+            "#,
+        )
+        .unwrap();
+        let config = HttpHeaderRewriteConfig::parse_yaml(&yaml[0]).unwrap();
+
+        assert!(!HttpHeaderRewriteRules::default().has_response());
+        let rules = HttpHeaderRewriteRules::new(Some(&config), None);
+        assert!(!rules.has_request());
+        assert!(rules.has_response());
+
+        let task_id = Uuid::nil();
+        let ctx = HttpHeaderRewriteContext {
+            username: None,
+            client_ip: IpAddr::from([192, 168, 1, 1]),
+            egress_ip: None,
+            task_id: &task_id,
+        };
+        let mut headers = HttpHeaderMap::default();
+        rules.rewrite_request(&ctx, &mut headers);
+        assert!(headers.is_empty());
+        rules.rewrite_response(&ctx, &mut headers);
+        assert_eq!(
+            headers.get("x-task-id").unwrap().to_str(),
+            task_id.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_template() {
+        assert!(HttpHeaderValueTemplate::from_str("${task_id}-${unknown}").is_err());
+        assert!(HttpHeaderValueTemplate::from_str("${task_id").is_err());
+        let t = HttpHeaderValueTemplate::from_str("id=${task_id};").unwrap();
+        assert_eq!(t.parts().len(), 3);
+    }
+}
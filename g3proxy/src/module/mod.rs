@@ -4,8 +4,10 @@
  */
 
 pub(crate) mod ftp_over_http;
+pub(crate) mod http_cache;
 pub(crate) mod http_forward;
 pub(crate) mod http_header;
+pub(crate) mod pac;
 pub(crate) mod tcp_connect;
 pub(crate) mod udp_connect;
 pub(crate) mod udp_relay;
@@ -0,0 +1,345 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+use std::net::{IpAddr, Ipv4Addr};
+
+use http::{Method, StatusCode, Version, header};
+use ip_network::IpNetwork;
+
+use g3_http::server::HttpProxyClientRequest;
+use g3_types::acl::AclAction;
+use g3_types::acl_set::AclDstHostRuleSetBuilder;
+
+use crate::config::escaper::AnyEscaperConfig;
+use crate::config::escaper::route_upstream::RouteUpstreamEscaperConfig;
+use crate::config::pac::PacFileConfig;
+
+const PAC_CONTENT_TYPE: &str = "application/x-ns-proxy-autoconfig";
+const PAC_DIRECT: &str = "DIRECT";
+
+const DEFAULT_TEMPLATE: &str = "function FindProxyForURL(url, host) {\n\
+     {{rules}}\
+     \x20   return \"{{proxy}}\";\n\
+     }\n";
+
+/// Collect rules from the server and escaper config to generate the PAC file
+pub(crate) struct PacFileBuilder<'a> {
+    config: &'a PacFileConfig,
+    rules: String,
+    has_ipv4_rule: bool,
+}
+
+impl<'a> PacFileBuilder<'a> {
+    pub(crate) fn new(config: &'a PacFileConfig) -> Self {
+        PacFileBuilder {
+            config,
+            rules: String::new(),
+            has_ipv4_rule: false,
+        }
+    }
+
+    fn add_exact_domain(&mut self, domain: &str, proxy: &str) {
+        let _ = writeln!(
+            self.rules,
+            "    if (host == \"{domain}\") return \"{proxy}\";"
+        );
+    }
+
+    fn add_exact_ip(&mut self, ip: IpAddr, proxy: &str) {
+        let _ = writeln!(self.rules, "    if (host == \"{ip}\") return \"{proxy}\";");
+    }
+
+    fn add_child_domain(&mut self, domain: &str, proxy: &str) {
+        let _ = writeln!(
+            self.rules,
+            "    if (host == \"{domain}\" || dnsDomainIs(host, \".{domain}\")) return \"{proxy}\";"
+        );
+    }
+
+    fn add_suffix_domain(&mut self, suffix: &str, proxy: &str) {
+        let _ = writeln!(
+            self.rules,
+            "    if (dnsDomainIs(host, \"{suffix}\")) return \"{proxy}\";"
+        );
+    }
+
+    fn add_subnet(&mut self, net: &IpNetwork, proxy: &str) {
+        // isInNet only works with IPv4, and we should avoid dns resolution in PAC
+        let IpNetwork::V4(net) = net else {
+            return;
+        };
+        let mask = u32::MAX.checked_shl(32 - net.netmask() as u32).unwrap_or(0);
+        let _ = writeln!(
+            self.rules,
+            "    if (isIpv4 && isInNet(host, \"{}\", \"{}\")) return \"{proxy}\";",
+            net.network_address(),
+            Ipv4Addr::from(mask)
+        );
+        self.has_ipv4_rule = true;
+    }
+
+    /// Use DIRECT for all hosts that are explicitly forbidden by the server
+    pub(crate) fn add_dst_host_filter(&mut self, filter: &AclDstHostRuleSetBuilder) {
+        fn forbidden<T: Ord>(iter: impl Iterator<Item = (T, AclAction)>) -> Vec<T> {
+            let mut all: Vec<T> = iter
+                .filter(|(_, action)| action.forbid_early())
+                .map(|(v, _)| v)
+                .collect();
+            // keep the output stable, so we can use the hash as etag
+            all.sort();
+            all
+        }
+
+        if let Some(rule) = &filter.exact {
+            for domain in forbidden(rule.iter_domain()) {
+                self.add_exact_domain(domain, PAC_DIRECT);
+            }
+            for ip in forbidden(rule.iter_ip()) {
+                self.add_exact_ip(*ip, PAC_DIRECT);
+            }
+        }
+        if let Some(rule) = &filter.child {
+            for domain in forbidden(rule.iter()) {
+                self.add_child_domain(&domain, PAC_DIRECT);
+            }
+        }
+        if let Some(rule) = &filter.subnet {
+            for net in forbidden(rule.iter()) {
+                self.add_subnet(net, PAC_DIRECT);
+            }
+        }
+    }
+
+    /// Use the mapped proxy for hosts that will be routed to specific next escapers
+    pub(crate) fn add_escaper(&mut self, escaper: &AnyEscaperConfig) {
+        if let AnyEscaperConfig::RouteUpstream(config) = escaper {
+            self.add_route_upstream(config);
+        }
+    }
+
+    fn add_route_upstream(&mut self, config: &RouteUpstreamEscaperConfig) {
+        let escaper_proxies = &self.config.escaper_proxies;
+
+        for (escaper, ips) in config.exact_match.iter_ipaddr() {
+            let Some(proxy) = escaper_proxies.get(escaper) else {
+                continue;
+            };
+            for ip in ips {
+                self.add_exact_ip(*ip, proxy);
+            }
+        }
+        for (escaper, nets) in config.subnet_match.iter() {
+            let Some(proxy) = escaper_proxies.get(escaper) else {
+                continue;
+            };
+            for net in nets {
+                self.add_subnet(net, proxy);
+            }
+        }
+        for (escaper, domains) in config.exact_match.iter_domain() {
+            let Some(proxy) = escaper_proxies.get(escaper) else {
+                continue;
+            };
+            for domain in domains {
+                self.add_exact_domain(domain, proxy);
+            }
+        }
+        for (escaper, domains) in config.child_match.iter() {
+            let Some(proxy) = escaper_proxies.get(escaper) else {
+                continue;
+            };
+            for domain in domains {
+                self.add_child_domain(domain, proxy);
+            }
+        }
+        for (escaper, suffixes) in config.suffix_match.iter() {
+            let Some(proxy) = escaper_proxies.get(escaper) else {
+                continue;
+            };
+            for suffix in suffixes {
+                self.add_suffix_domain(suffix, proxy);
+            }
+        }
+    }
+
+    pub(crate) fn build(self, default_proxy: &str) -> PacFile {
+        let mut rules = String::with_capacity(self.rules.len() + 64);
+        if self.has_ipv4_rule {
+            rules.push_str("    var isIpv4 = /^\\d+\\.\\d+\\.\\d+\\.\\d+$/.test(host);\n");
+        }
+        rules.push_str(&self.rules);
+
+        let template = self.config.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+        let body = template
+            .replace("{{rules}}", &rules)
+            .replace("{{proxy}}", default_proxy);
+
+        let digest = openssl::sha::sha256(body.as_bytes());
+        let mut etag = String::with_capacity(34);
+        etag.push('"');
+        for b in &digest[..16] {
+            let _ = write!(etag, "{b:02x}");
+        }
+        etag.push('"');
+
+        let cache_control = if self.config.client_networks.is_empty() {
+            format!("max-age={}", self.config.max_age.as_secs())
+        } else {
+            // the content varies by client network
+            format!("private, max-age={}", self.config.max_age.as_secs())
+        };
+
+        PacFile {
+            body,
+            etag,
+            cache_control,
+        }
+    }
+}
+
+pub(crate) struct PacFile {
+    body: String,
+    etag: String,
+    cache_control: String,
+}
+
+impl PacFile {
+    #[cfg(test)]
+    fn body(&self) -> &str {
+        &self.body
+    }
+
+    fn match_etag(&self, req: &HttpProxyClientRequest) -> bool {
+        let Some(v) = req.end_to_end_headers.get(header::IF_NONE_MATCH) else {
+            return false;
+        };
+        v.to_str().split(',').any(|tag| {
+            let tag = tag.trim();
+            let tag = tag.strip_prefix("W/").unwrap_or(tag);
+            tag == "*" || tag == self.etag
+        })
+    }
+
+    /// Serialize as a complete HTTP/1.x response to the PAC file request
+    pub(crate) fn serialize_response(&self, req: &HttpProxyClientRequest, close: bool) -> Vec<u8> {
+        let status = if self.match_etag(req) {
+            StatusCode::NOT_MODIFIED
+        } else {
+            StatusCode::OK
+        };
+        let version = match req.version {
+            Version::HTTP_10 => Version::HTTP_10,
+            _ => Version::HTTP_11,
+        };
+
+        let mut s = String::with_capacity(256 + self.body.len());
+        let _ = write!(
+            s,
+            "{version:?} {} {}\r\n",
+            status.as_str(),
+            status.canonical_reason().unwrap_or_default()
+        );
+        let _ = write!(s, "ETag: {}\r\n", self.etag);
+        let _ = write!(s, "Cache-Control: {}\r\n", self.cache_control);
+        if status == StatusCode::OK {
+            let _ = write!(s, "Content-Type: {PAC_CONTENT_TYPE}\r\n");
+            let _ = write!(s, "Content-Length: {}\r\n", self.body.len());
+        }
+        if close {
+            s.push_str("Connection: close\r\n\r\n");
+        } else {
+            s.push_str("Connection: keep-alive\r\n\r\n");
+        }
+        if status == StatusCode::OK && req.method != Method::HEAD {
+            s.push_str(&self.body);
+        }
+        s.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use g3_types::acl::{AclChildDomainRuleBuilder, AclExactHostRule, AclNetworkRuleBuilder};
+    use yaml_rust::YamlLoader;
+
+    fn build_config() -> PacFileConfig {
+        let conf = r#"
+        escaper_proxies:
+          overseas: "PROXY overseas.example.net:3128"
+        "#;
+        let v = YamlLoader::load_from_str(conf).unwrap();
+        PacFileConfig::parse(&v[0], Path::new("/")).unwrap()
+    }
+
+    #[test]
+    fn forbidden_hosts() {
+        let config = build_config();
+
+        let mut exact = AclExactHostRule::new(AclAction::Permit);
+        exact.add_domain(Arc::from("b.example.net"), AclAction::Forbid);
+        exact.add_domain(Arc::from("a.example.net"), AclAction::ForbidAndLog);
+        exact.add_domain(Arc::from("c.example.net"), AclAction::Permit);
+        let mut child = AclChildDomainRuleBuilder::new(AclAction::Permit);
+        child.add_node("intranet.example.net", AclAction::Forbid);
+        let mut subnet = AclNetworkRuleBuilder::new(AclAction::Permit);
+        subnet.add_network(
+            IpNetwork::from_str("10.0.0.0/8").unwrap(),
+            AclAction::Forbid,
+        );
+        subnet.add_network(IpNetwork::from_str("fd00::/8").unwrap(), AclAction::Forbid);
+        let filter = AclDstHostRuleSetBuilder {
+            exact: Some(exact),
+            child: Some(child),
+            regex: None,
+            subnet: Some(subnet),
+        };
+
+        let mut builder = PacFileBuilder::new(&config);
+        builder.add_dst_host_filter(&filter);
+        let pac = builder.build("PROXY 192.168.1.1:3128");
+        let body = pac.body();
+
+        let a = body
+            .find("if (host == \"a.example.net\") return \"DIRECT\";")
+            .unwrap();
+        let b = body
+            .find("if (host == \"b.example.net\") return \"DIRECT\";")
+            .unwrap();
+        assert!(a < b);
+        assert!(!body.contains("c.example.net"));
+        assert!(body.contains(
+            "if (host == \"intranet.example.net\" || dnsDomainIs(host, \".intranet.example.net\")) return \"DIRECT\";"
+        ));
+        assert!(body.contains("var isIpv4 = "));
+        assert!(body.contains(
+            "if (isIpv4 && isInNet(host, \"10.0.0.0\", \"255.0.0.0\")) return \"DIRECT\";"
+        ));
+        assert!(!body.contains("fd00"));
+        assert!(body.ends_with("    return \"PROXY 192.168.1.1:3128\";\n}\n"));
+    }
+
+    #[test]
+    fn stable_etag() {
+        let config = build_config();
+
+        let pac1 = PacFileBuilder::new(&config).build("DIRECT");
+        let pac2 = PacFileBuilder::new(&config).build("DIRECT");
+        assert_eq!(pac1.etag, pac2.etag);
+
+        let pac3 = PacFileBuilder::new(&config).build("PROXY 127.0.0.1:3128");
+        assert_ne!(pac1.etag, pac3.etag);
+
+        let mut builder = PacFileBuilder::new(&config);
+        builder.add_exact_ip(IpAddr::from_str("192.168.1.1").unwrap(), PAC_DIRECT);
+        let pac4 = builder.build("DIRECT");
+        assert_ne!(pac1.etag, pac4.etag);
+    }
+}
@@ -8,6 +8,7 @@ use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use openssl::ssl::Ssl;
+use tokio::time::Instant;
 
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
@@ -51,6 +52,22 @@ impl TcpConnectChainedNotes {
     }
 }
 
+/// The start time and time spent of a connection setup stage
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TcpConnectStageTime {
+    pub(crate) start: Instant,
+    pub(crate) spend: Duration,
+}
+
+impl TcpConnectStageTime {
+    fn since(start: Instant) -> Self {
+        TcpConnectStageTime {
+            start,
+            spend: start.elapsed(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct TcpConnectTaskNotes {
     pub(crate) escaper: NodeName,
@@ -63,6 +80,9 @@ pub(crate) struct TcpConnectTaskNotes {
     pub(crate) chained: TcpConnectChainedNotes,
     pub(crate) duration: Duration,
     pub(crate) override_peer: Option<UpstreamAddr>,
+    pub(crate) resolve_time: Option<TcpConnectStageTime>,
+    pub(crate) connect_time: Option<TcpConnectStageTime>,
+    pub(crate) tls_time: Option<TcpConnectStageTime>,
 }
 
 impl TcpConnectTaskNotes {
@@ -77,5 +97,22 @@ impl TcpConnectTaskNotes {
         self.chained.reset();
         self.duration = Duration::ZERO;
         self.override_peer = None;
+        self.resolve_time = None;
+        self.connect_time = None;
+        self.tls_time = None;
+    }
+
+    pub(crate) fn set_resolve_time(&mut self, start: Instant) {
+        self.resolve_time = Some(TcpConnectStageTime::since(start));
+    }
+
+    pub(crate) fn set_connect_time(&mut self, start: Instant) {
+        let time = TcpConnectStageTime::since(start);
+        self.duration = time.spend;
+        self.connect_time = Some(time);
+    }
+
+    pub(crate) fn set_tls_time(&mut self, start: Instant) {
+        self.tls_time = Some(TcpConnectStageTime::since(start));
     }
 }
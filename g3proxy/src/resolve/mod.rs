@@ -32,6 +32,8 @@ mod hickory;
 
 mod deny_all;
 mod fail_over;
+mod hosts;
+mod split;
 
 mod ops;
 pub use ops::spawn_all;
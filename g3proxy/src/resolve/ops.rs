@@ -21,6 +21,8 @@ use super::hickory::HickoryResolver;
 
 use super::deny_all::DenyAllResolver;
 use super::fail_over::FailOverResolver;
+use super::hosts::HostsResolver;
+use super::split::SplitResolver;
 
 use super::{Resolver, registry};
 
@@ -178,6 +180,8 @@ async fn spawn_new_unlocked(config: AnyResolverConfig) -> anyhow::Result<()> {
         AnyResolverConfig::Hickory(c) => HickoryResolver::new_obj(*c)?,
         AnyResolverConfig::DenyAll(c) => DenyAllResolver::new_obj(c)?,
         AnyResolverConfig::FailOver(c) => FailOverResolver::new_obj(c)?,
+        AnyResolverConfig::Hosts(c) => HostsResolver::new_obj(c)?,
+        AnyResolverConfig::Split(c) => SplitResolver::new_obj(c)?,
     };
     let old_resolver = registry::add(name.clone(), resolver);
     update_dependency_to_resolver_unlocked(&name, STATUS).await;
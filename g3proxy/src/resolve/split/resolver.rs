@@ -0,0 +1,157 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use async_trait::async_trait;
+use slog::Logger;
+
+use g3_resolver::driver::split::SplitDriverConfig;
+use g3_types::metrics::NodeName;
+
+use crate::config::resolver::split::SplitResolverConfig;
+use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
+use crate::resolve::{
+    ArcIntegratedResolverHandle, BoxResolverInternal, Resolver, ResolverInternal, ResolverStats,
+};
+
+type DependentHandles = BTreeMap<NodeName, Option<g3_resolver::ResolverHandle>>;
+
+pub(crate) struct SplitResolver {
+    config: Arc<SplitResolverConfig>,
+    dep_handles: DependentHandles,
+    inner: g3_resolver::Resolver,
+    stats: Arc<ResolverStats>,
+    logger: Option<Logger>,
+}
+
+fn build_inner_config(
+    config: &SplitResolverConfig,
+    dep_handles: &DependentHandles,
+) -> g3_resolver::ResolverConfig {
+    let mut driver_config = SplitDriverConfig::default();
+    for (suffix, name) in &config.rules {
+        let handle = dep_handles.get(name).cloned().flatten();
+        driver_config.add_rule(suffix, handle);
+    }
+    if let Some(name) = &config.default {
+        driver_config.set_default_handle(dep_handles.get(name).cloned().flatten());
+    }
+    if let Some(ttl) = config.negative_ttl {
+        driver_config.set_negative_ttl(ttl);
+    }
+
+    g3_resolver::ResolverConfig {
+        name: config.name().to_string(),
+        runtime: config.runtime.clone(),
+        driver: g3_resolver::AnyResolveDriverConfig::Split(driver_config),
+    }
+}
+
+impl SplitResolver {
+    pub(crate) fn new_obj(config: SplitResolverConfig) -> anyhow::Result<BoxResolverInternal> {
+        let mut dep_handles = BTreeMap::new();
+        for name in config.dependent_resolver().unwrap_or_default() {
+            let handle = crate::resolve::get_handle(&name)
+                .context(format!("failed to get resolver handle for {name}"))?;
+            dep_handles.insert(name, handle.clone_inner());
+        }
+
+        let inner_config = build_inner_config(&config, &dep_handles);
+        let mut builder = g3_resolver::ResolverBuilder::new(inner_config);
+        builder.thread_name(format!("res-{}", config.name()));
+        let resolver = builder.build()?;
+
+        let logger = crate::log::resolve::get_logger(config.r#type(), config.name());
+        let stats = ResolverStats::new(config.name(), resolver.get_stats());
+
+        Ok(Box::new(SplitResolver {
+            config: Arc::new(config),
+            dep_handles,
+            inner: resolver,
+            stats: Arc::new(stats),
+            logger,
+        }))
+    }
+}
+
+#[async_trait]
+impl ResolverInternal for SplitResolver {
+    fn _dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        self.config.dependent_resolver()
+    }
+
+    fn _clone_config(&self) -> AnyResolverConfig {
+        AnyResolverConfig::Split(self.config.as_ref().clone())
+    }
+
+    fn _update_config(
+        &mut self,
+        config: AnyResolverConfig,
+        dep_table: BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+    ) -> anyhow::Result<()> {
+        if let AnyResolverConfig::Split(config) = config {
+            let mut dep_handles = BTreeMap::new();
+            for name in config.dependent_resolver().unwrap_or_default() {
+                let handle = dep_table.get(&name).unwrap();
+                dep_handles.insert(name, handle.clone_inner());
+            }
+
+            let inner_config = build_inner_config(&config, &dep_handles);
+            self.inner
+                .update_config(inner_config)
+                .context("failed to update inner split resolver config")?;
+            self.dep_handles = dep_handles;
+            self.config = Arc::new(config);
+            Ok(())
+        } else {
+            Err(anyhow!("invalid config type for SplitResolver"))
+        }
+    }
+
+    fn _update_dependent_handle(
+        &mut self,
+        target: &NodeName,
+        handle: ArcIntegratedResolverHandle,
+    ) -> anyhow::Result<()> {
+        let mut dep_handles = self.dep_handles.clone();
+        let Some(v) = dep_handles.get_mut(target) else {
+            return Err(anyhow!(
+                "resolver {} doesn't depend on resolver {}",
+                self.config.name(),
+                target
+            ));
+        };
+        *v = handle.clone_inner();
+
+        let inner_config = build_inner_config(&self.config, &dep_handles);
+        self.inner
+            .update_config(inner_config)
+            .context("failed to update inner split resolver config")?;
+        self.dep_handles = dep_handles;
+        Ok(())
+    }
+
+    async fn _shutdown(&mut self) {
+        self.inner.shutdown().await;
+    }
+}
+
+impl Resolver for SplitResolver {
+    fn get_handle(&self) -> ArcIntegratedResolverHandle {
+        let inner_context = self.inner.get_handle();
+        Arc::new(super::SplitResolverHandle::new(
+            &self.config,
+            inner_context,
+            self.logger.clone(),
+        ))
+    }
+
+    fn get_stats(&self) -> Arc<ResolverStats> {
+        Arc::clone(&self.stats)
+    }
+}
@@ -37,9 +37,11 @@ use super::task::{
 };
 use crate::audit::{AuditContext, AuditHandle};
 use crate::auth::UserGroup;
+use crate::category::CategoryFilter;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::http_cache::HttpCache;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
     ServerRegistry, ServerStats, WrapArcServer,
@@ -50,11 +52,13 @@ pub(crate) struct HttpProxyServer {
     server_stats: Arc<HttpProxyServerStats>,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    http_cache: Option<Arc<HttpCache>>,
     tls_acceptor: Option<TlsAcceptor>,
     tls_accept_timeout: Duration,
     tls_client_config: Arc<OpensslClientConfig>,
     ingress_net_filter: Option<AclNetworkRule>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    dst_category_filter: Option<Arc<CategoryFilter>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Option<Logger>,
 
@@ -72,6 +76,7 @@ impl HttpProxyServer {
         server_stats: Arc<HttpProxyServerStats>,
         listen_stats: Arc<ListenStats>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        http_cache: Option<Arc<HttpCache>>,
         version: usize,
     ) -> anyhow::Result<HttpProxyServer> {
         let reload_sender = crate::serve::new_reload_notify_channel();
@@ -104,6 +109,10 @@ impl HttpProxyServer {
             .dst_host_filter
             .as_ref()
             .map(|builder| Arc::new(builder.build()));
+        let dst_category_filter = config
+            .dst_category_filter
+            .as_ref()
+            .map(|config| Arc::new(CategoryFilter::new(config)));
 
         let task_logger = config.get_task_logger();
         let idle_wheel = IdleWheel::spawn(config.task_idle_check_interval);
@@ -120,11 +129,13 @@ impl HttpProxyServer {
             server_stats,
             listen_stats,
             tls_rolling_ticketer,
+            http_cache,
             tls_acceptor,
             tls_accept_timeout,
             tls_client_config: Arc::new(tls_client_config),
             ingress_net_filter,
             dst_host_filter,
+            dst_category_filter,
             reload_sender,
             task_logger,
             escaper: ArcSwap::new(escaper),
@@ -154,8 +165,21 @@ impl HttpProxyServer {
             None
         };
 
-        let server =
-            HttpProxyServer::new(config, server_stats, listen_stats, tls_rolling_ticketer, 1)?;
+        let http_cache = if let Some(c) = &config.http_cache {
+            let cache = HttpCache::try_build(c).context("failed to create http cache")?;
+            Some(cache)
+        } else {
+            None
+        };
+
+        let server = HttpProxyServer::new(
+            config,
+            server_stats,
+            listen_stats,
+            tls_rolling_ticketer,
+            http_cache,
+            1,
+        )?;
         Ok(Arc::new(server))
     }
 
@@ -176,11 +200,21 @@ impl HttpProxyServer {
                 None
             };
 
+            let http_cache = if self.config.http_cache.eq(&config.http_cache) {
+                self.http_cache.clone()
+            } else if let Some(c) = &config.http_cache {
+                let cache = HttpCache::try_build(c).context("failed to create http cache")?;
+                Some(cache)
+            } else {
+                None
+            };
+
             let server = HttpProxyServer::new(
                 config,
                 server_stats,
                 listen_stats,
                 tls_rolling_ticketer,
+                http_cache,
                 self.reload_version + 1,
             )?;
             Ok(server)
@@ -204,6 +238,8 @@ impl HttpProxyServer {
             tls_client_config: self.tls_client_config.clone(),
             task_logger: self.task_logger.clone(),
             dst_host_filter: self.dst_host_filter.clone(),
+            dst_category_filter: self.dst_category_filter.clone(),
+            http_cache: self.http_cache.clone(),
         })
     }
 
@@ -473,6 +509,13 @@ impl Server for HttpProxyServer {
         &self.quit_policy
     }
 
+    fn purge_http_cache(&self, url: Option<&str>) -> anyhow::Result<()> {
+        match &self.http_cache {
+            Some(cache) => cache.purge(url),
+            None => Err(anyhow!("no http cache enabled on this server")),
+        }
+    }
+
     async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo) {
         let client_addr = cc_info.client_addr();
         self.server_stats.add_conn(client_addr);
@@ -12,6 +12,7 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
+use crate::module::http_cache::{HttpCacheStats, HttpCacheStatsSnapshot};
 use crate::serve::{
     ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
 };
@@ -36,6 +37,8 @@ pub(crate) struct HttpProxyServerStats {
     pub io_http: TcpIoStats,
     pub io_connect: TcpIoStats,
     pub io_untrusted: TcpIoStats,
+
+    pub http_cache: Arc<HttpCacheStats>,
 }
 
 impl HttpProxyServerStats {
@@ -54,6 +57,7 @@ impl HttpProxyServerStats {
             io_http: Default::default(),
             io_connect: Default::default(),
             io_untrusted: Default::default(),
+            http_cache: Default::default(),
         }
     }
 
@@ -135,4 +139,8 @@ impl ServerStats for HttpProxyServerStats {
             in_bytes: self.io_untrusted.get_in_bytes(),
         })
     }
+
+    fn http_cache_snapshot(&self) -> Option<HttpCacheStatsSnapshot> {
+        Some(self.http_cache.snapshot())
+    }
 }
@@ -18,7 +18,9 @@ use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::net::{OpensslClientConfig, UpstreamAddr};
 
 use super::{HttpProxyServerConfig, HttpProxyServerStats};
+use crate::category::CategoryFilter;
 use crate::escape::ArcEscaper;
+use crate::module::http_cache::HttpCache;
 use crate::module::http_forward::HttpProxyClientResponse;
 use crate::module::http_header;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
@@ -36,6 +38,8 @@ pub(crate) struct CommonTaskContext {
     pub(crate) task_logger: Option<Logger>,
 
     pub(crate) dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    pub(crate) dst_category_filter: Option<Arc<CategoryFilter>>,
+    pub(crate) http_cache: Option<Arc<HttpCache>>,
 }
 
 impl CommonTaskContext {
@@ -31,6 +31,7 @@ use crate::serve::{
     ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
     ServerTaskStage,
 };
+use crate::trace::tcp_connect::TaskSpanForTcpConnect;
 
 pub(crate) struct HttpProxyConnectTask {
     ctx: Arc<CommonTaskContext>,
@@ -155,6 +156,7 @@ impl HttpProxyConnectTask {
                 self.back_to_http = false;
             }
             Err(e) => {
+                self.emit_trace_span(&e);
                 if let Some(log_ctx) = self.get_log_context() {
                     log_ctx.log(e);
                 }
@@ -198,6 +200,29 @@ impl HttpProxyConnectTask {
         }
     }
 
+    /// Check the category of the upstream host
+    async fn check_dst_category<W>(&mut self, clt_w: &mut W) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let (category, action) = user_ctx.check_dst_category(&self.upstream, None);
+            if category.is_some() {
+                self.task_notes.category = category;
+            }
+            self.handle_user_upstream_acl_action(action, clt_w).await?;
+        }
+        if let Some(filter) = &self.ctx.dst_category_filter {
+            let (category, action) = filter.check(&self.upstream, None);
+            if category.is_some() {
+                self.task_notes.category = category;
+            }
+            self.handle_server_upstream_acl_action(action, clt_w)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn handle_user_upstream_acl_action<W>(
         &mut self,
         action: AclAction,
@@ -306,6 +331,9 @@ impl HttpProxyConnectTask {
             tcp_client_misc_opts = Cow::Borrowed(&self.ctx.server_config.tcp_misc_opts);
         }
 
+        // user and server level dst category acl rules
+        self.check_dst_category(clt_w).await?;
+
         // set client side socket options
         self.ctx
             .cc_info
@@ -397,6 +425,16 @@ impl HttpProxyConnectTask {
             })
     }
 
+    fn emit_trace_span(&self, e: &ServerTaskError) {
+        TaskSpanForTcpConnect {
+            server_type: "HTTP CONNECT",
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            tcp_notes: &self.tcp_notes,
+        }
+        .emit(e);
+    }
+
     pub(crate) fn into_running<CDR, CDW>(mut self, clt_r: CDR, clt_w: HttpClientWriter<CDW>)
     where
         CDR: AsyncRead + Send + Sync + Unpin + 'static,
@@ -413,6 +451,7 @@ impl HttpProxyConnectTask {
                         Ok(_) => ServerTaskError::Finished,
                         Err(e) => e,
                     };
+                    self.emit_trace_span(&e);
                     if let Some(log_ctx) = self.get_log_context() {
                         log_ctx.log(e);
                     }
@@ -483,7 +522,7 @@ impl HttpProxyConnectTask {
                 .unwrap_or_else(|| audit_handle.do_task_audit());
 
             if audit_task {
-                let ctx = StreamInspectContext::new(
+                let mut ctx = StreamInspectContext::new(
                     audit_handle.clone(),
                     self.ctx.server_config.clone(),
                     self.ctx.server_stats.clone(),
@@ -492,6 +531,16 @@ impl HttpProxyConnectTask {
                     &self.task_notes,
                     &self.tcp_notes,
                 );
+                let cache_bypass = self
+                    .task_notes
+                    .user_ctx()
+                    .map(|ctx| ctx.user_config().http_cache_bypass)
+                    .unwrap_or(false);
+                if let Some(cache) = &self.ctx.http_cache
+                    && !cache_bypass
+                {
+                    ctx.set_http_cache(cache.clone(), self.ctx.server_stats.http_cache.clone());
+                }
                 return crate::inspect::stream::transit_with_inspection(
                     clt_r,
                     clt_w,
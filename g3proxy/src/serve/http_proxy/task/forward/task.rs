@@ -8,12 +8,13 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
+use bytes::Bytes;
 use futures_util::FutureExt;
-use http::header;
+use http::{Method, StatusCode, header};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use g3_http::client::HttpForwardRemoteResponse;
-use g3_http::server::HttpProxyClientRequest;
+use g3_http::server::{HttpAdaptedRequest, HttpProxyClientRequest};
 use g3_http::{HttpBodyReader, HttpBodyType};
 use g3_icap_client::reqmod::h1::{
     H1ReqmodAdaptationError, HttpAdapterErrorResponse, HttpRequestAdapter,
@@ -37,11 +38,14 @@ use super::{
 use crate::audit::AuditContext;
 use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
+use crate::module::http_cache::{
+    CacheControl, HttpCacheBody, HttpCacheEntry, HttpCacheLookup, HttpCacheRecordReader,
+};
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
     HttpForwardTaskNotes, HttpProxyClientResponse,
 };
-use crate::module::http_header;
+use crate::module::http_header::{self, HttpHeaderRewriteContext, HttpHeaderRewriteRules};
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
@@ -49,6 +53,8 @@ use crate::serve::{
     ServerIdleChecker, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
     ServerTaskResult, ServerTaskStage,
 };
+use crate::trace::TraceContext;
+use crate::trace::http_forward::TaskSpanForHttpForward;
 
 pub(crate) struct HttpProxyForwardTask<'a> {
     ctx: Arc<CommonTaskContext>,
@@ -61,9 +67,15 @@ pub(crate) struct HttpProxyForwardTask<'a> {
     task_notes: ServerTaskNotes,
     http_notes: HttpForwardTaskNotes,
     tcp_notes: TcpConnectTaskNotes,
+    trace_ctx: Option<TraceContext>,
     task_stats: Arc<HttpForwardTaskStats>,
     max_idle_count: usize,
     started: bool,
+    http_cache_key: Option<String>,
+    http_cache_stale: Option<Arc<HttpCacheEntry>>,
+    http_cache_cond_req: Option<HttpProxyClientRequest>,
+    http_cache_req_time: u64,
+    http_rewrite_req: Option<HttpProxyClientRequest>,
 }
 
 impl Drop for HttpProxyForwardTask<'_> {
@@ -82,6 +94,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         req: &'a HttpProxyRequest<impl AsyncRead>,
         is_https: bool,
         task_notes: ServerTaskNotes,
+        trace_ctx: Option<TraceContext>,
     ) -> Self {
         let uri_log_max_chars = task_notes
             .user_ctx()
@@ -109,9 +122,15 @@ impl<'a> HttpProxyForwardTask<'a> {
             task_notes,
             http_notes,
             tcp_notes: TcpConnectTaskNotes::default(),
+            trace_ctx,
             task_stats: Arc::new(HttpForwardTaskStats::default()),
             max_idle_count,
             started: false,
+            http_cache_key: None,
+            http_cache_stale: None,
+            http_cache_cond_req: None,
+            http_cache_req_time: 0,
+            http_rewrite_req: None,
         }
     }
 
@@ -250,6 +269,18 @@ impl<'a> HttpProxyForwardTask<'a> {
         })
     }
 
+    fn get_span_context(&self) -> Option<TaskSpanForHttpForward<'_>> {
+        let trace_ctx = self.trace_ctx.as_ref()?;
+        Some(TaskSpanForHttpForward {
+            trace_ctx,
+            upstream: &self.upstream,
+            is_https: self.is_https,
+            task_notes: &self.task_notes,
+            http_notes: &self.http_notes,
+            tcp_notes: &self.tcp_notes,
+        })
+    }
+
     pub(crate) async fn run<CDR, CDW>(
         &mut self,
         clt_r: &mut Option<HttpClientReader<CDR>>,
@@ -267,6 +298,9 @@ impl<'a> HttpProxyForwardTask<'a> {
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(&e);
         }
+        if let Some(span_ctx) = self.get_span_context() {
+            span_ctx.emit(&e);
+        }
     }
 
     fn pre_start(&mut self) {
@@ -337,6 +371,31 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    /// Check the category of the upstream host and the request url
+    async fn check_dst_category<W>(&mut self, clt_w: &mut W) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let uri = self.req.uri.clone();
+        let path = uri.path_and_query().map(|v| v.as_str());
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let (category, action) = user_ctx.check_dst_category(&self.upstream, path);
+            if category.is_some() {
+                self.task_notes.category = category;
+            }
+            self.handle_user_upstream_acl_action(action, clt_w).await?;
+        }
+        if let Some(filter) = &self.ctx.dst_category_filter {
+            let (category, action) = filter.check(&self.upstream, path);
+            if category.is_some() {
+                self.task_notes.category = category;
+            }
+            self.handle_server_upstream_acl_action(action, clt_w)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn handle_user_upstream_acl_action<W>(
         &mut self,
         action: AclAction,
@@ -618,6 +677,9 @@ impl<'a> HttpProxyForwardTask<'a> {
             }
         }
 
+        // user and server level dst category acl rules
+        self.check_dst_category(clt_w).await?;
+
         // set client side socket options
         self.ctx
             .cc_info
@@ -628,6 +690,10 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
+        if !audit_task && self.lookup_http_cache(clt_w).await? {
+            return Ok(());
+        }
+
         fwd_ctx.prepare_connection(&self.upstream, self.is_https);
 
         if let Some(mut connection) = fwd_ctx
@@ -641,6 +707,7 @@ impl<'a> HttpProxyForwardTask<'a> {
             self.task_notes.stage = ServerTaskStage::Connected;
             self.http_notes.reused_connection = true;
             fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+            self.rewrite_request_header();
             self.http_notes.retry_new_connection = false;
             if let Some(user_ctx) = self.task_notes.user_ctx() {
                 user_ctx.foreach_req_stats(|s| s.req_reuse.add_http_forward(self.is_https));
@@ -704,6 +771,148 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    async fn lookup_http_cache<W>(&mut self, clt_w: &mut W) -> ServerTaskResult<bool>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let Some(cache) = self.ctx.http_cache.clone() else {
+            return Ok(false);
+        };
+        if let Some(user_ctx) = self.task_notes.user_ctx()
+            && user_ctx.user_config().http_cache_bypass
+        {
+            return Ok(false);
+        }
+
+        let path_and_query = self
+            .req
+            .uri
+            .path_and_query()
+            .map(|v| v.as_str())
+            .unwrap_or("/");
+        let key =
+            crate::module::http_cache::cache_key(self.is_https, &self.upstream, path_and_query);
+        if !self.req.method.is_safe() {
+            // the key is kept for invalidation
+            self.http_cache_key = Some(key);
+            return Ok(false);
+        }
+        if !crate::module::http_cache::request_cacheable(
+            &self.req.method,
+            &self.req.end_to_end_headers,
+            self.req.body_type().is_some(),
+        ) {
+            return Ok(false);
+        }
+
+        let req_cc = CacheControl::parse(&self.req.end_to_end_headers);
+        match cache
+            .lookup(&key, &self.req.end_to_end_headers, &req_cc)
+            .await
+        {
+            HttpCacheLookup::Fresh(entry) => {
+                self.ctx.server_stats.http_cache.add_hit();
+                self.send_cached_response(clt_w, &entry).await?;
+                return Ok(true);
+            }
+            HttpCacheLookup::Stale(entry) => {
+                if req_cc.only_if_cached {
+                    self.reply_gateway_timeout(clt_w).await;
+                    return Ok(true);
+                }
+                self.ctx.server_stats.http_cache.add_miss();
+                // only validate if the client is not doing it itself
+                if self.req.method == Method::GET
+                    && !self
+                        .req
+                        .end_to_end_headers
+                        .contains_key(header::IF_NONE_MATCH)
+                    && !self
+                        .req
+                        .end_to_end_headers
+                        .contains_key(header::IF_MODIFIED_SINCE)
+                {
+                    let mut headers = self.req.end_to_end_headers.clone();
+                    for (name, value) in entry.validation_headers() {
+                        headers.insert(name, value);
+                    }
+                    let mut cond_req = self.req.adapt_without_body(HttpAdaptedRequest {
+                        method: self.req.method.clone(),
+                        uri: self.req.uri.clone(),
+                        version: self.req.version,
+                        headers,
+                        content_length: None,
+                    });
+                    cond_req.host.clone_from(&self.req.host);
+                    self.http_cache_cond_req = Some(cond_req);
+                    self.http_cache_stale = Some(entry);
+                }
+            }
+            HttpCacheLookup::Miss => {
+                if req_cc.only_if_cached {
+                    self.reply_gateway_timeout(clt_w).await;
+                    return Ok(true);
+                }
+                self.ctx.server_stats.http_cache.add_miss();
+            }
+        }
+
+        self.http_cache_key = Some(key);
+        self.http_cache_req_time = crate::module::http_cache::unix_now();
+        Ok(false)
+    }
+
+    async fn reply_gateway_timeout<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let rsp = HttpProxyClientResponse::from_standard(
+            StatusCode::GATEWAY_TIMEOUT,
+            self.req.version,
+            self.should_close,
+        );
+        // no custom header is set
+        if rsp.reply_err_to_request(clt_w).await.is_ok() {
+            self.http_notes.rsp_status = rsp.status();
+        }
+        self.task_notes.stage = ServerTaskStage::Finished;
+    }
+
+    async fn send_cached_response<W>(
+        &mut self,
+        clt_w: &mut W,
+        entry: &HttpCacheEntry,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let not_modified = entry.not_modified_for(&self.req.end_to_end_headers);
+        let header = entry.serialize_header(
+            self.req.version,
+            not_modified,
+            self.should_close,
+            crate::module::http_cache::unix_now(),
+        );
+        self.send_error_response = false;
+        clt_w
+            .write_all(&header)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        if !not_modified && self.req.method != Method::HEAD {
+            clt_w
+                .write_all(entry.body())
+                .await
+                .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        }
+        clt_w
+            .flush()
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.http_notes.rsp_status = if not_modified { 304 } else { entry.code() };
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
+    }
+
     async fn save_or_close<CDW>(
         &self,
         fwd_ctx: &mut BoxHttpForwardContext,
@@ -732,11 +941,13 @@ impl<'a> HttpProxyForwardTask<'a> {
     {
         self.task_notes.stage = ServerTaskStage::Connecting;
         self.http_notes.reused_connection = false;
+        self.http_notes.mark_ups_connect_start();
 
         match self.make_new_connection(fwd_ctx).await {
             Ok(mut connection) => {
                 self.task_notes.stage = ServerTaskStage::Connected;
                 fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+                self.rewrite_request_header();
 
                 if self.ctx.server_config.flush_task_log_on_connected
                     && let Some(log_ctx) = self.get_log_context()
@@ -791,6 +1002,36 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    fn header_rewrite_rules(&self) -> HttpHeaderRewriteRules<'_> {
+        HttpHeaderRewriteRules::new(
+            Some(&self.ctx.server_config.http_header_rewrite),
+            self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref()),
+        )
+    }
+
+    /// Apply the server and then the user request header rewrite rules,
+    /// this should be called for each connection as the egress ip may change
+    fn rewrite_request_header(&mut self) {
+        let rules = self.header_rewrite_rules();
+        if !rules.has_request() {
+            return;
+        }
+
+        let req = self.http_cache_cond_req.as_ref().unwrap_or(self.req);
+        let mut headers = req.end_to_end_headers.clone();
+        let rewrite_ctx = HttpHeaderRewriteContext::new(&self.task_notes, &self.tcp_notes);
+        rules.rewrite_request(&rewrite_ctx, &mut headers);
+        self.http_rewrite_req = Some(req.clone_with_headers(headers));
+    }
+
+    /// Get the request that should be sent to upstream
+    fn upstream_req(&self) -> &HttpProxyClientRequest {
+        self.http_rewrite_req
+            .as_ref()
+            .or(self.http_cache_cond_req.as_ref())
+            .unwrap_or(self.req)
+    }
+
     fn mark_relaying(&mut self) {
         self.task_notes.mark_relaying();
         if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -843,9 +1084,11 @@ impl<'a> HttpProxyForwardTask<'a> {
                     if let Some(name) = self.task_notes.raw_user_name() {
                         adapter.set_client_username(name.clone());
                     }
+                    let icap_start = self.http_notes.time_elapsed();
                     let r = self
                         .run_with_adaptation(clt_r, clt_w, ups_c, adapter, &mut adaptation_state)
                         .await;
+                    self.http_notes.mark_icap_reqmod(icap_start);
                     if let Some(dur) = adaptation_state.dur_ups_send_header {
                         self.http_notes.dur_req_send_hdr = dur;
                     }
@@ -891,11 +1134,13 @@ impl<'a> HttpProxyForwardTask<'a> {
         let ups_w = &mut ups_c.0;
         let ups_r = &mut ups_c.1;
 
+        // the rewritten request will be sent to the ICAP server for adaptation
+        let rewritten_req = self.http_rewrite_req.take();
         let mut ups_w_adaptation = HttpForwardWriterForAdaptation { inner: ups_w };
         let mut adaptation_fut = icap_adapter
             .xfer(
                 adaptation_state,
-                self.req,
+                rewritten_req.as_ref().unwrap_or(self.req),
                 clt_r.as_mut(),
                 &mut ups_w_adaptation,
             )
@@ -1159,7 +1404,7 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.http_notes.retry_new_connection = true;
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(self.upstream_req(), None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -1199,6 +1444,32 @@ impl<'a> HttpProxyForwardTask<'a> {
         };
         self.http_notes.mark_rsp_recv_hdr();
 
+        if rsp_header.code == 304
+            && let Some(entry) = self.http_cache_stale.take()
+            && let Some(cache) = self.ctx.http_cache.clone()
+        {
+            // the stored response is validated
+            if !rsp_header.keep_alive() {
+                self.should_close = true;
+            }
+            self.http_notes.origin_status = rsp_header.code;
+            let entry = Arc::new(entry.freshen(
+                &rsp_header.end_to_end_headers,
+                self.http_cache_req_time,
+                crate::module::http_cache::unix_now(),
+                cache.heuristic_max_age(),
+            ));
+            cache.store(entry.clone());
+            self.ctx.server_stats.http_cache.add_revalidated();
+            self.send_error_response = false;
+            self.send_cached_response(clt_w, &entry).await?;
+            return if self.should_close {
+                Ok(None)
+            } else {
+                Ok(Some(ups_c))
+            };
+        }
+
         self.send_response(clt_w, ups_r, &mut rsp_header, false, None)
             .await?;
 
@@ -1215,7 +1486,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.http_notes.retry_new_connection = true;
 
         ups_w
-            .send_request_header(self.req, Some(body))
+            .send_request_header(self.upstream_req(), Some(body))
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -1315,7 +1586,7 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.http_notes.retry_new_connection = true;
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(self.upstream_req(), None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -1534,6 +1805,15 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.http_notes.rsp_status = 0;
         self.update_response_header(rsp_header);
 
+        // unsafe methods invalidate the stored response, see rfc9111 Section 4.4
+        if !self.req.method.is_safe()
+            && rsp_header.code < 400
+            && let Some(key) = &self.http_cache_key
+            && let Some(cache) = &self.ctx.http_cache
+        {
+            cache.invalidate(key);
+        }
+
         if audit_task
             && let Some(audit_handle) = self.audit_ctx.handle()
             && let Some(respmod) = audit_handle.icap_respmod_client()
@@ -1556,6 +1836,7 @@ impl<'a> HttpProxyForwardTask<'a> {
                         adapter.set_client_username(name.clone());
                     }
                     adapter.set_respond_shared_headers(adaptation_respond_shared_headers);
+                    let icap_start = self.http_notes.time_elapsed();
                     let r = self
                         .send_response_with_adaptation(
                             clt_w,
@@ -1565,6 +1846,7 @@ impl<'a> HttpProxyForwardTask<'a> {
                             &mut adaptation_state,
                         )
                         .await;
+                    self.http_notes.mark_icap_respmod(icap_start);
                     if !adaptation_state.clt_write_finished || !adaptation_state.ups_read_finished {
                         self.should_close = true;
                     }
@@ -1640,33 +1922,80 @@ impl<'a> HttpProxyForwardTask<'a> {
     {
         self.send_error_response = false;
 
+        let store_cache = self.req.method == Method::GET
+            && self.http_cache_key.is_some()
+            && crate::module::http_cache::response_storable(
+                &self.req.end_to_end_headers,
+                rsp_header.code,
+                &rsp_header.end_to_end_headers,
+            );
+
         if let Some(body_type) = rsp_header.body_type(&self.req.method) {
             let mut buf = Vec::with_capacity(self.ctx.server_config.tcp_copy.buffer_size());
             rsp_header.serialize_to(&mut buf);
             self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
-            self.send_response_body(buf, clt_w, ups_r, body_type).await
+            let mut cache_body = match &self.ctx.http_cache {
+                Some(cache) if store_cache => {
+                    Some(HttpCacheBody::new(body_type, cache.max_object_size()))
+                }
+                _ => None,
+            };
+            self.send_response_body(buf, clt_w, ups_r, body_type, cache_body.as_mut())
+                .await?;
+            if let Some(cache_body) = cache_body
+                && let Some(body) = cache_body
+                    .finish(self.ctx.server_config.body_line_max_len)
+                    .await
+            {
+                self.store_http_cache(rsp_header, body);
+            }
+            Ok(())
         } else {
             self.send_response_header(clt_w, rsp_header).await?;
             self.http_notes.rsp_status = rsp_header.code;
             self.http_notes.mark_rsp_no_body();
+            if store_cache {
+                self.store_http_cache(rsp_header, Bytes::new());
+            }
             Ok(())
         }
     }
 
+    fn store_http_cache(&self, rsp_header: &HttpForwardRemoteResponse, body: Bytes) {
+        let (Some(cache), Some(key)) = (&self.ctx.http_cache, &self.http_cache_key) else {
+            return;
+        };
+        let entry = HttpCacheEntry::new(
+            key.clone(),
+            &self.req.end_to_end_headers,
+            rsp_header.code,
+            &rsp_header.reason,
+            &rsp_header.end_to_end_headers,
+            body,
+            self.http_cache_req_time,
+            crate::module::http_cache::unix_now(),
+            cache.heuristic_max_age(),
+        );
+        cache.store(Arc::new(entry));
+        self.ctx.server_stats.http_cache.add_stored();
+    }
+
     async fn send_response_body<R, W>(
         &mut self,
         header: Vec<u8>,
         clt_w: &mut W,
         ups_r: &mut R,
         body_type: HttpBodyType,
+        cache_body: Option<&mut HttpCacheBody>,
     ) -> ServerTaskResult<()>
     where
         R: AsyncBufRead + Unpin,
         W: AsyncWrite + Unpin,
     {
         let header_len = header.len() as u64;
-        let mut body_reader =
+        let body_reader =
             HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        let mut body_reader = HttpCacheRecordReader::new(body_reader, cache_body);
 
         let mut ups_to_clt = StreamCopy::with_data(
             &mut body_reader,
@@ -1784,6 +2113,12 @@ impl<'a> HttpProxyForwardTask<'a> {
                 http_header::set_outgoing_ip(&mut rsp.hop_by_hop_headers, addr);
             }
         }
+
+        let rules = self.header_rewrite_rules();
+        if rules.has_response() {
+            let rewrite_ctx = HttpHeaderRewriteContext::new(&self.task_notes, &self.tcp_notes);
+            rules.rewrite_response(&rewrite_ctx, &mut rsp.end_to_end_headers);
+        }
     }
 
     async fn send_response_header<W>(
@@ -8,13 +8,14 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use ahash::AHashMap;
+use http::{HeaderName, Method};
 use log::debug;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::mpsc;
 
-use g3_io_ext::{ArcLimitedWriterStats, LimitedWriter};
+use g3_io_ext::{ArcLimitedWriterStats, LimitedWriteExt, LimitedWriter};
 use g3_types::auth::UserAuthError;
-use g3_types::net::{HttpAuth, HttpProxySubProtocol};
+use g3_types::net::{HttpAuth, HttpHeaderValue, HttpProxySubProtocol, UpstreamAddr};
 
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpProxyRequest};
 use super::{
@@ -26,7 +27,9 @@ use crate::auth::{UserContext, UserGroup, UserRequestStats};
 use crate::config::server::ServerConfig;
 use crate::escape::EgressPathSelection;
 use crate::module::http_forward::{BoxHttpForwardContext, HttpProxyClientResponse};
+use crate::module::pac::{PacFile, PacFileBuilder};
 use crate::serve::{ServerStats, ServerTaskNotes};
+use crate::trace::{TRACEPARENT, TraceContext};
 
 struct UserData {
     req_stats: Arc<UserRequestStats>,
@@ -194,15 +197,20 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
-                    let res = match self.do_auth(&req) {
-                        Ok(user_ctx) => {
-                            self.req_count.consequent_auth_failed = 0;
-                            self.run(req, user_ctx).await
-                        }
-                        Err(e) => {
-                            self.req_count.consequent_auth_failed += 1;
-                            self.req_count.auth_failed += 1;
-                            self.run_untrusted(req, e.blocked_delay()).await
+                    let res = if req.local_pac {
+                        // no auth is required for the PAC file
+                        self.run_local_pac(req).await
+                    } else {
+                        match self.do_auth(&req) {
+                            Ok(user_ctx) => {
+                                self.req_count.consequent_auth_failed = 0;
+                                self.run(req, user_ctx).await
+                            }
+                            Err(e) => {
+                                self.req_count.consequent_auth_failed += 1;
+                                self.req_count.auth_failed += 1;
+                                self.run_untrusted(req, e.blocked_delay()).await
+                            }
                         }
                     };
                     self.pipeline_stats.del_task();
@@ -524,12 +532,25 @@ where
             _ => unreachable!(),
         };
 
+        let trace_ctx = if crate::trace::enabled() {
+            let ctx = TraceContext::from_http_headers(&req.inner.end_to_end_headers);
+            req.inner
+                .end_to_end_headers
+                .insert(HeaderName::from_static(TRACEPARENT), unsafe {
+                    HttpHeaderValue::from_string_unchecked(ctx.traceparent())
+                });
+            Some(ctx)
+        } else {
+            None
+        };
+
         match req.body_reader.take() {
             Some(stream_r) => {
                 // we have a body, or we need to close the connection
                 // we may need to send stream_r back if we have a body
-                let mut forward_task =
-                    HttpProxyForwardTask::new(&self.ctx, audit_ctx, &req, is_https, task_notes);
+                let mut forward_task = HttpProxyForwardTask::new(
+                    &self.ctx, audit_ctx, &req, is_https, task_notes, trace_ctx,
+                );
                 let mut clt_r = Some(stream_r);
                 forward_task
                     .run(&mut clt_r, clt_w, &mut self.forward_context)
@@ -550,8 +571,9 @@ where
             }
             None => {
                 // no http body, and the connection is expected to keep alive from the client side
-                let mut forward_task =
-                    HttpProxyForwardTask::new(&self.ctx, audit_ctx, &req, is_https, task_notes);
+                let mut forward_task = HttpProxyForwardTask::new(
+                    &self.ctx, audit_ctx, &req, is_https, task_notes, trace_ctx,
+                );
                 let mut clt_r = None;
                 forward_task
                     .run::<CDR, CDW>(&mut clt_r, clt_w, &mut self.forward_context)
@@ -567,6 +589,71 @@ where
         }
     }
 
+    fn build_pac_file(&self, req: &HttpProxyRequest<CDR>) -> Option<PacFile> {
+        let config = self.ctx.server_config.pac_file.as_ref()?;
+
+        let mut builder = PacFileBuilder::new(config);
+        if config.direct_forbidden_hosts
+            && let Some(filter) = &self.ctx.server_config.dst_host_filter
+        {
+            builder.add_dst_host_filter(filter);
+        }
+        if let Some(escaper_config) = crate::escape::get_escaper_config(self.ctx.escaper.name()) {
+            builder.add_escaper(&escaper_config);
+        }
+
+        let pac_file = match config.select_proxy(self.ctx.cc_info.client_ip()) {
+            Some(proxy) => builder.build(proxy),
+            None => {
+                // use the address that the client used to reach us
+                let server_addr = self.ctx.cc_info.server_addr();
+                let proxy = match &req.inner.host {
+                    Some(host) if host.port() != 0 => format!("PROXY {host}"),
+                    Some(host) => {
+                        let addr = UpstreamAddr::new(host.host().clone(), server_addr.port());
+                        format!("PROXY {addr}")
+                    }
+                    None => format!("PROXY {server_addr}"),
+                };
+                builder.build(&proxy)
+            }
+        };
+        Some(pac_file)
+    }
+
+    async fn run_local_pac(&mut self, req: HttpProxyRequest<CDR>) -> LoopAction {
+        let pac_file = if matches!(req.inner.method, Method::GET | Method::HEAD) {
+            self.build_pac_file(&req)
+        } else {
+            None
+        };
+
+        let Some(clt_w) = &mut self.stream_writer else {
+            // should be impossible
+            self.notify_reader_to_close();
+            return LoopAction::Break;
+        };
+
+        let Some(pac_file) = pac_file else {
+            let rsp = HttpProxyClientResponse::method_not_allowed(req.inner.version);
+            let _ = rsp.reply_err_to_request(clt_w).await;
+            self.notify_reader_to_close();
+            return LoopAction::Break;
+        };
+
+        // the connection should be closed if the reader has been sent to us
+        let close = !req.inner.keep_alive() || req.body_reader.is_some();
+        let rsp = pac_file.serialize_response(&req.inner, close);
+        if clt_w.write_all_flush(&rsp).await.is_err() || close {
+            // close read end
+            let _ = req.stream_sender.try_send(None);
+            self.notify_reader_to_close();
+            LoopAction::Break
+        } else {
+            LoopAction::Continue
+        }
+    }
+
     async fn run_ftp_over_http(
         &mut self,
         clt_w: &mut HttpClientWriter<CDW>,
@@ -19,6 +19,8 @@ pub(crate) struct HttpProxyRequest<CDR> {
     pub(crate) client_protocol: HttpProxySubProtocol,
     pub(crate) inner: HttpProxyClientRequest,
     pub(crate) upstream: UpstreamAddr,
+    /// the request is for the local PAC file
+    pub(crate) local_pac: bool,
     pub(crate) time_accepted: Instant,
     pub(crate) time_received: Instant,
     pub(crate) body_reader: Option<HttpClientReader<CDR>>,
@@ -62,9 +64,16 @@ where
         .await?;
         let time_received = Instant::now();
 
+        let mut local_pac = false;
         let (upstream, sub_protocol) = if matches!(&req.method, &Method::CONNECT) {
             let addr = req.uri.get_upstream_with_default_port(443)?;
             (addr, HttpProxySubProtocol::TcpConnect)
+        } else if let Some(pac_file) = &config.pac_file
+            && pac_file.match_path(req.uri.path())
+            && req.is_local_request(&config.local_server_names)
+        {
+            local_pac = true;
+            (UpstreamAddr::empty(), HttpProxySubProtocol::HttpForward)
         } else if req.is_local_request(&config.local_server_names) {
             match WellKnownUri::parse(&req.uri).map_err(|e| {
                 HttpRequestParseError::UnsupportedRequest(format!("invalid well-known uri: {e}",))
@@ -96,7 +105,8 @@ where
             req.uri.get_upstream_and_protocol()?
         };
 
-        if !config.allow_custom_host
+        if !local_pac
+            && !config.allow_custom_host
             && let Some(host) = &req.host
             && !host.host_eq(&upstream)
         {
@@ -107,6 +117,7 @@ where
             client_protocol: sub_protocol,
             inner: req,
             upstream,
+            local_pac,
             time_accepted,
             time_received,
             body_reader: None,
@@ -3,18 +3,23 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 
+use g3_http::server::HttpProxyClientRequest;
 use g3_types::net::{OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConfig};
 
+use super::HttpUpstreamPool;
 use crate::config::server::http_rproxy::HttpHostConfig;
 
 pub(crate) struct HttpHost {
     pub(super) config: Arc<HttpHostConfig>,
     pub(super) tls_server: Option<RustlsServerConfig>,
     pub(super) tls_client: Option<OpensslClientConfig>,
+    /// the pools for each route in config, in the same order
+    routes: Vec<Arc<HttpUpstreamPool>>,
 }
 
 impl HttpHost {
@@ -38,10 +43,40 @@ impl HttpHost {
             None
         };
 
+        let mut pools = BTreeMap::new();
+        for (name, pool_config) in &config.pools {
+            let pool = HttpUpstreamPool::try_build(pool_config)
+                .context(format!("failed to build upstream pool {name}"))?;
+            pools.insert(name.as_str(), Arc::new(pool));
+        }
+        let mut routes = Vec::with_capacity(config.routes.len());
+        for route in &config.routes {
+            let pool = pools
+                .get(route.pool.as_str())
+                .ok_or_else(|| anyhow!("no upstream pool named {} found", route.pool))?;
+            routes.push(pool.clone());
+        }
+
         Ok(HttpHost {
             config: Arc::clone(config),
             tls_server,
             tls_client,
+            routes,
         })
     }
+
+    /// Get the upstream pool of the first matched route
+    pub(super) fn match_pool(&self, req: &HttpProxyClientRequest) -> Option<Arc<HttpUpstreamPool>> {
+        self.config
+            .routes
+            .iter()
+            .zip(self.routes.iter())
+            .find(|(route, _)| route.is_match(&req.method, req.uri.path(), &req.end_to_end_headers))
+            .map(|(_, pool)| pool.clone())
+    }
+
+    #[inline]
+    pub(super) fn has_default_upstream(&self) -> bool {
+        !self.config.upstream().is_empty()
+    }
 }
@@ -13,3 +13,6 @@ pub(super) use server::HttpRProxyServer;
 
 mod host;
 use host::HttpHost;
+
+mod pool;
+use pool::HttpUpstreamPool;
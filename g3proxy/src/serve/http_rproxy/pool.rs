@@ -0,0 +1,219 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
+
+use g3_types::collection::{SelectivePickPolicy, SelectiveVec, SelectiveVecBuilder};
+use g3_types::net::{Host, OpensslClientConfig, UpstreamAddr, WeightedUpstreamAddr};
+
+use crate::config::server::http_rproxy::HttpUpstreamPoolConfig;
+
+#[derive(Default)]
+struct MemberHealth {
+    fails: usize,
+    first_fail: Option<Instant>,
+    eject_until: Option<Instant>,
+}
+
+struct PoolMember {
+    addr: UpstreamAddr,
+    health: Mutex<MemberHealth>,
+}
+
+impl PoolMember {
+    fn is_ejected(&self, now: Instant) -> bool {
+        let health = self.health.lock().unwrap();
+        health.eject_until.map(|t| t > now).unwrap_or(false)
+    }
+}
+
+pub(crate) struct HttpUpstreamPool {
+    nodes: SelectiveVec<WeightedUpstreamAddr>,
+    members: Vec<PoolMember>,
+    pick_policy: SelectivePickPolicy,
+    max_fails: usize,
+    fail_window: Duration,
+    eject_duration: Duration,
+    max_retries: usize,
+    pub(super) tls_client: Option<OpensslClientConfig>,
+    pub(super) tls_name: Host,
+}
+
+impl HttpUpstreamPool {
+    pub(super) fn try_build(config: &HttpUpstreamPoolConfig) -> anyhow::Result<Self> {
+        let mut nodes_builder = SelectiveVecBuilder::new();
+        let mut members = Vec::with_capacity(config.upstream.len());
+        for node in &config.upstream {
+            nodes_builder.insert(node.clone());
+            if !members.iter().any(|m: &PoolMember| m.addr.eq(node.inner())) {
+                members.push(PoolMember {
+                    addr: node.inner().clone(),
+                    health: Mutex::new(MemberHealth::default()),
+                });
+            }
+        }
+        let nodes = nodes_builder
+            .build()
+            .ok_or_else(|| anyhow!("no upstream addr set"))?;
+
+        let tls_client = if let Some(builder) = &config.tls_client_builder {
+            let client = builder.build().context("failed to build tls client")?;
+            Some(client)
+        } else {
+            None
+        };
+
+        Ok(HttpUpstreamPool {
+            nodes,
+            members,
+            pick_policy: config.pick_policy,
+            max_fails: config.max_fails,
+            fail_window: config.fail_window,
+            eject_duration: config.eject_duration,
+            max_retries: config.max_retries,
+            tls_client,
+            tls_name: config.tls_name.clone().unwrap_or_else(Host::empty),
+        })
+    }
+
+    #[inline]
+    pub(crate) fn max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    fn select(&self, client_ip: IpAddr, path: &str) -> &UpstreamAddr {
+        #[derive(Hash)]
+        struct ConsistentKey<'a> {
+            client_ip: IpAddr,
+            path: &'a str,
+        }
+
+        let node = match self.pick_policy {
+            SelectivePickPolicy::Random => self.nodes.pick_random(),
+            SelectivePickPolicy::Serial => self.nodes.pick_serial(),
+            SelectivePickPolicy::RoundRobin => self.nodes.pick_round_robin(),
+            SelectivePickPolicy::Ketama => {
+                self.nodes.pick_ketama(&ConsistentKey { client_ip, path })
+            }
+            SelectivePickPolicy::Rendezvous => self
+                .nodes
+                .pick_rendezvous(&ConsistentKey { client_ip, path }),
+            SelectivePickPolicy::JumpHash => {
+                self.nodes.pick_jump(&ConsistentKey { client_ip, path })
+            }
+        };
+        node.inner()
+    }
+
+    /// Pick a member that is not ejected and not tried before.
+    /// Fallback to the ejected ones if all members are ejected.
+    pub(crate) fn pick(
+        &self,
+        client_ip: IpAddr,
+        path: &str,
+        tried: &[UpstreamAddr],
+    ) -> Option<UpstreamAddr> {
+        let now = Instant::now();
+        let selected = self.select(client_ip, path);
+        let start = self
+            .members
+            .iter()
+            .position(|m| m.addr.eq(selected))
+            .unwrap_or_default();
+
+        let mut fallback: Option<&PoolMember> = None;
+        for i in 0..self.members.len() {
+            let member = &self.members[(start + i) % self.members.len()];
+            if tried.contains(&member.addr) {
+                continue;
+            }
+            if !member.is_ejected(now) {
+                return Some(member.addr.clone());
+            }
+            if fallback.is_none() {
+                fallback = Some(member);
+            }
+        }
+        fallback.map(|m| m.addr.clone())
+    }
+
+    /// Record a failure of the member, it will be ejected if failed too many times
+    pub(crate) fn add_failure(&self, addr: &UpstreamAddr) {
+        if self.max_fails == 0 {
+            return;
+        }
+        let Some(member) = self.members.iter().find(|m| m.addr.eq(addr)) else {
+            return;
+        };
+
+        let now = Instant::now();
+        let mut health = member.health.lock().unwrap();
+        match health.first_fail {
+            Some(t) if now.duration_since(t) < self.fail_window => health.fails += 1,
+            _ => {
+                health.first_fail = Some(now);
+                health.fails = 1;
+            }
+        }
+        if health.fails >= self.max_fails {
+            health.eject_until = Some(now + self.eject_duration);
+            health.fails = 0;
+            health.first_fail = None;
+        }
+    }
+
+    pub(crate) fn add_success(&self, addr: &UpstreamAddr) {
+        if self.max_fails == 0 {
+            return;
+        }
+        if let Some(member) = self.members.iter().find(|m| m.addr.eq(addr)) {
+            let mut health = member.health.lock().unwrap();
+            if health.fails > 0 || health.eject_until.is_some() {
+                *health = MemberHealth::default();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn eject_and_fallback() {
+        let config = HttpUpstreamPoolConfig {
+            upstream: vec![
+                WeightedUpstreamAddr::new(UpstreamAddr::from_str("127.0.0.1:8080").unwrap()),
+                WeightedUpstreamAddr::new(UpstreamAddr::from_str("127.0.0.2:8080").unwrap()),
+            ],
+            pick_policy: SelectivePickPolicy::Serial,
+            max_fails: 2,
+            ..Default::default()
+        };
+        let pool = HttpUpstreamPool::try_build(&config).unwrap();
+        let ip = IpAddr::from([127, 0, 0, 1]);
+        let a1 = UpstreamAddr::from_str("127.0.0.1:8080").unwrap();
+        let a2 = UpstreamAddr::from_str("127.0.0.2:8080").unwrap();
+
+        assert_eq!(pool.pick(ip, "/", &[]), Some(a1.clone()));
+        assert_eq!(pool.pick(ip, "/", &[a1.clone()]), Some(a2.clone()));
+
+        pool.add_failure(&a1);
+        assert_eq!(pool.pick(ip, "/", &[]), Some(a1.clone()));
+        pool.add_failure(&a1);
+        assert_eq!(pool.pick(ip, "/", &[]), Some(a2.clone()));
+        // all ejected or tried
+        assert_eq!(pool.pick(ip, "/", &[a2.clone()]), Some(a1.clone()));
+        assert_eq!(pool.pick(ip, "/", &[a1.clone(), a2.clone()]), None);
+
+        pool.add_success(&a1);
+        assert_eq!(pool.pick(ip, "/", &[]), Some(a1));
+    }
+}
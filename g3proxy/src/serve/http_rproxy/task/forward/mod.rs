@@ -8,6 +8,9 @@ use super::{CommonTaskContext, HttpRProxyServerStats, protocol};
 mod task;
 pub(super) use task::HttpRProxyForwardTask;
 
+mod upstream;
+pub(super) use upstream::HttpRProxyUpstream;
+
 mod stats;
 use stats::{
     HttpForwardTaskCltWrapperStats, HttpForwardTaskStats, HttpsForwardTaskCltWrapperStats,
@@ -22,19 +22,20 @@ use g3_types::acl::AclAction;
 
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpRProxyRequest};
 use super::{
-    CommonTaskContext, HttpForwardTaskCltWrapperStats, HttpForwardTaskStats,
+    CommonTaskContext, HttpForwardTaskCltWrapperStats, HttpForwardTaskStats, HttpRProxyUpstream,
     HttpsForwardTaskCltWrapperStats,
 };
+use crate::audit::AuditContext;
 use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
     HttpForwardTaskNotes, HttpProxyClientResponse,
 };
+use crate::module::http_header::{HttpHeaderRewriteContext, HttpHeaderRewriteRules};
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
-use crate::serve::http_rproxy::host::HttpHost;
 use crate::serve::{
     ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
     ServerTaskStage,
@@ -42,17 +43,19 @@ use crate::serve::{
 
 pub(crate) struct HttpRProxyForwardTask<'a> {
     ctx: Arc<CommonTaskContext>,
-    host: Arc<HttpHost>,
+    upstream: HttpRProxyUpstream,
     req: &'a HttpProxyClientRequest,
     is_https: bool,
     should_close: bool,
     send_error_response: bool,
     task_notes: ServerTaskNotes,
+    audit_ctx: AuditContext,
     http_notes: HttpForwardTaskNotes,
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<HttpForwardTaskStats>,
     max_idle_count: usize,
     started: bool,
+    http_rewrite_req: Option<HttpProxyClientRequest>,
 }
 
 impl Drop for HttpRProxyForwardTask<'_> {
@@ -68,8 +71,9 @@ impl<'a> HttpRProxyForwardTask<'a> {
     pub(crate) fn new(
         ctx: &Arc<CommonTaskContext>,
         req: &'a HttpRProxyRequest<impl AsyncRead>,
-        host: Arc<HttpHost>,
+        upstream: HttpRProxyUpstream,
         task_notes: ServerTaskNotes,
+        audit_ctx: AuditContext,
     ) -> Self {
         let uri_log_max_chars = task_notes
             .user_ctx()
@@ -82,24 +86,26 @@ impl<'a> HttpRProxyForwardTask<'a> {
             req.inner.uri.clone(),
             uri_log_max_chars,
         );
-        let is_https = host.tls_client.is_some();
+        let is_https = upstream.tls_client().is_some();
         let max_idle_count = task_notes
             .user_ctx()
             .and_then(|c| c.user().task_max_idle_count())
             .unwrap_or(ctx.server_config.task_idle_max_count);
         HttpRProxyForwardTask {
             ctx: Arc::clone(ctx),
-            host,
+            upstream,
             req: &req.inner,
             is_https,
             should_close: !req.inner.keep_alive(),
             send_error_response: true,
             task_notes,
+            audit_ctx,
             http_notes,
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(HttpForwardTaskStats::default()),
             max_idle_count,
             started: false,
+            http_rewrite_req: None,
         }
     }
 
@@ -201,7 +207,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             .map(|v| v.to_str());
         Some(TaskLogForHttpForward {
             logger,
-            upstream: self.host.config.upstream(),
+            upstream: self.upstream.addr(),
             task_notes: &self.task_notes,
             http_notes: &self.http_notes,
             http_user_agent,
@@ -469,7 +475,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 }
             }
 
-            let action = user_ctx.check_upstream(self.host.config.upstream());
+            let action = user_ctx.check_upstream(self.upstream.addr());
             self.handle_user_upstream_acl_action(action, clt_w).await?;
 
             if let Some(action) = user_ctx.check_http_user_agent(&self.req.end_to_end_headers) {
@@ -495,7 +501,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
-        fwd_ctx.prepare_connection(self.host.config.upstream(), self.is_https);
+        fwd_ctx.prepare_connection(self.upstream.addr(), self.is_https);
 
         if let Some(mut connection) = fwd_ctx
             .get_alive_connection(
@@ -508,6 +514,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             self.task_notes.stage = ServerTaskStage::Connected;
             self.http_notes.reused_connection = true;
             fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+            self.rewrite_request_header();
             self.http_notes.retry_new_connection = false;
             if let Some(user_ctx) = self.task_notes.user_ctx() {
                 user_ctx.foreach_req_stats(|s| s.req_reuse.add_http_forward(self.is_https));
@@ -521,7 +528,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
             connection
                 .0
-                .prepare_new(&self.task_notes, self.host.config.upstream());
+                .prepare_new(&self.task_notes, self.upstream.addr());
             self.mark_relaying();
 
             let r = self
@@ -529,6 +536,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 .await;
             match r {
                 Ok(ups_s) => {
+                    self.upstream.add_success();
                     self.save_or_close(fwd_ctx, clt_w, ups_s).await;
                     return Ok(());
                 }
@@ -543,6 +551,17 @@ impl<'a> HttpRProxyForwardTask<'a> {
                             user_ctx
                                 .foreach_req_stats(|s| s.req_renew.add_http_forward(self.is_https));
                         }
+                    } else if self.upstream_retryable(&e) {
+                        self.upstream.add_failure();
+                        if !self.switch_next_upstream(fwd_ctx).await {
+                            self.should_close = true;
+                            self.reply_task_err(&e, clt_w).await;
+                            return Err(e);
+                        }
+                        if let Some(log_ctx) = self.get_log_context() {
+                            log_ctx.log(&e);
+                        }
+                        // continue to make new connection to the next pool member
                     } else {
                         self.should_close = true;
                         if self.send_error_response {
@@ -554,23 +573,114 @@ impl<'a> HttpRProxyForwardTask<'a> {
             }
         }
 
-        let connection = self.get_new_connection(fwd_ctx, clt_w).await?;
-        match self
-            .run_with_connection(fwd_ctx, clt_r, clt_w, connection)
-            .await
-        {
-            Ok(ups_s) => {
-                self.save_or_close(fwd_ctx, clt_w, ups_s).await;
-                Ok(())
-            }
-            Err(e) => {
-                self.should_close = true;
-                if self.send_error_response {
-                    self.reply_task_err(&e, clt_w).await;
+        loop {
+            let connection = match self.try_new_connection(fwd_ctx).await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    if Self::connect_retryable(&e) {
+                        self.upstream.add_failure();
+                        if self.switch_next_upstream(fwd_ctx).await {
+                            if let Some(log_ctx) = self.get_log_context() {
+                                log_ctx.log(&ServerTaskError::from(e));
+                            }
+                            continue;
+                        }
+                    }
+                    self.reply_connect_err(&e, clt_w).await;
+                    return Err(e.into());
                 }
-                Err(e)
+            };
+            match self
+                .run_with_connection(fwd_ctx, clt_r, clt_w, connection)
+                .await
+            {
+                Ok(ups_s) => {
+                    self.upstream.add_success();
+                    self.save_or_close(fwd_ctx, clt_w, ups_s).await;
+                    return Ok(());
+                }
+                Err(e) => {
+                    if self.upstream_retryable(&e) {
+                        self.upstream.add_failure();
+                        if self.switch_next_upstream(fwd_ctx).await {
+                            if let Some(log_ctx) = self.get_log_context() {
+                                log_ctx.log(&e);
+                            }
+                            continue;
+                        }
+                    }
+                    self.should_close = true;
+                    if self.send_error_response {
+                        self.reply_task_err(&e, clt_w).await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Check if we can retry with another pool member after the upstream error
+    fn upstream_retryable(&self, e: &ServerTaskError) -> bool {
+        matches!(
+            e,
+            ServerTaskError::UpstreamReadFailed(_)
+                | ServerTaskError::UpstreamWriteFailed(_)
+                | ServerTaskError::UpstreamAppTimeout(_)
+                | ServerTaskError::ClosedByUpstream
+        ) && self.send_error_response
+            && self.task_stats.clt.write.get_bytes() == 0
+    }
+
+    /// Check if we can retry with another pool member after the connect error.
+    /// Errors caused by the escaper or its rules are not upstream failures.
+    fn connect_retryable(e: &TcpConnectError) -> bool {
+        !matches!(
+            e,
+            TcpConnectError::MethodUnavailable
+                | TcpConnectError::EscaperNotUsable(_)
+                | TcpConnectError::ForbiddenAddressFamily
+                | TcpConnectError::ForbiddenRemoteAddress
+                | TcpConnectError::InternalServerError(_)
+                | TcpConnectError::InternalTlsClientError(_)
+        )
+    }
+
+    /// Switch to the next pool member, only for idempotent requests without body.
+    /// The members that are forbidden by the user upstream ACL will be skipped.
+    async fn switch_next_upstream(&mut self, fwd_ctx: &mut BoxHttpForwardContext) -> bool {
+        if !self.req.pipeline_safe() {
+            return false;
+        }
+        loop {
+            if !self
+                .upstream
+                .switch_next(self.ctx.client_ip(), self.req.uri.path())
+            {
+                return false;
             }
+            if let Some(user_ctx) = self.task_notes.user_ctx()
+                && user_ctx
+                    .check_upstream(self.upstream.addr())
+                    .forbid_early()
+            {
+                continue;
+            }
+            break;
         }
+
+        // check in final escaper again as the route escapers may be different, the forward
+        // capability is not used as the upstream protocol is set by the host config.
+        // The escaper side forbidden errors will be returned when connecting, which will fail
+        // the task without further retry.
+        fwd_ctx
+            .check_in_final_escaper(&self.task_notes, self.upstream.addr(), &mut self.audit_ctx)
+            .await;
+
+        self.should_close = !self.req.keep_alive();
+        self.tcp_notes = TcpConnectTaskNotes::default();
+        self.task_stats.ups.reset();
+        fwd_ctx.prepare_connection(self.upstream.addr(), self.is_https);
+        true
     }
 
     async fn save_or_close<CDW>(
@@ -599,6 +709,19 @@ impl<'a> HttpRProxyForwardTask<'a> {
     where
         CDW: AsyncWrite + Unpin,
     {
+        match self.try_new_connection(fwd_ctx).await {
+            Ok(connection) => Ok(connection),
+            Err(e) => {
+                self.reply_connect_err(&e, clt_w).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn try_new_connection(
+        &mut self,
+        fwd_ctx: &mut BoxHttpForwardContext,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.task_notes.stage = ServerTaskStage::Connecting;
         self.http_notes.reused_connection = false;
 
@@ -606,6 +729,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             Ok(mut connection) => {
                 self.task_notes.stage = ServerTaskStage::Connected;
                 fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+                self.rewrite_request_header();
 
                 if self.ctx.server_config.flush_task_log_on_connected
                     && let Some(log_ctx) = self.get_log_context()
@@ -615,37 +739,62 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
                 connection
                     .0
-                    .prepare_new(&self.task_notes, self.host.config.upstream());
+                    .prepare_new(&self.task_notes, self.upstream.addr());
                 self.mark_relaying();
                 Ok(connection)
             }
             Err(e) => {
                 fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
                 self.should_close = true;
-                self.reply_connect_err(&e, clt_w).await;
-                Err(e.into())
+                Err(e)
             }
         }
     }
 
+    fn header_rewrite_rules(&self) -> HttpHeaderRewriteRules<'_> {
+        HttpHeaderRewriteRules::new(
+            Some(&self.ctx.server_config.http_header_rewrite),
+            self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref()),
+        )
+    }
+
+    /// Apply the server and then the user request header rewrite rules,
+    /// this should be called for each connection as the egress ip may change
+    fn rewrite_request_header(&mut self) {
+        let rules = self.header_rewrite_rules();
+        if !rules.has_request() {
+            return;
+        }
+
+        let mut headers = self.req.end_to_end_headers.clone();
+        let rewrite_ctx = HttpHeaderRewriteContext::new(&self.task_notes, &self.tcp_notes);
+        rules.rewrite_request(&rewrite_ctx, &mut headers);
+        self.http_rewrite_req = Some(self.req.clone_with_headers(headers));
+    }
+
+    /// Get the request that should be sent to upstream
+    fn upstream_req(&self) -> &HttpProxyClientRequest {
+        self.http_rewrite_req.as_ref().unwrap_or(self.req)
+    }
+
     async fn make_new_connection(
         &self,
         fwd_ctx: &mut BoxHttpForwardContext,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        if let Some(tls_client) = &self.host.tls_client {
+        if let Some(tls_client) = self.upstream.tls_client() {
             let task_conf = TlsConnectTaskConf {
                 tcp: TcpConnectTaskConf {
-                    upstream: self.host.config.upstream(),
+                    upstream: self.upstream.addr(),
                 },
                 tls_config: tls_client,
-                tls_name: &self.host.config.tls_name,
+                tls_name: self.upstream.tls_name(),
             };
             fwd_ctx
                 .make_new_https_connection(&task_conf, &self.task_notes, self.task_stats.clone())
                 .await
         } else {
             let task_conf = TcpConnectTaskConf {
-                upstream: self.host.config.upstream(),
+                upstream: self.upstream.addr(),
             };
             fwd_ctx
                 .make_new_http_connection(&task_conf, &self.task_notes, self.task_stats.clone())
@@ -765,7 +914,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
         self.http_notes.retry_new_connection = true;
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(self.upstream_req(), None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -821,7 +970,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         self.http_notes.retry_new_connection = true;
 
         ups_w
-            .send_request_header(self.req, Some(body))
+            .send_request_header(self.upstream_req(), Some(body))
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -921,7 +1070,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
         self.http_notes.retry_new_connection = true;
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(self.upstream_req(), None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -1253,6 +1402,12 @@ impl<'a> HttpRProxyForwardTask<'a> {
         if let Some(_server_id) = &self.ctx.server_config.server_id {
             // TODO custom header
         }
+
+        let rules = self.header_rewrite_rules();
+        if rules.has_response() {
+            let rewrite_ctx = HttpHeaderRewriteContext::new(&self.task_notes, &self.tcp_notes);
+            rules.rewrite_response(&rewrite_ctx, &mut rsp.end_to_end_headers);
+        }
     }
 
     async fn send_response_header<W>(
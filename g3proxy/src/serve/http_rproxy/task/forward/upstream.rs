@@ -0,0 +1,84 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use g3_types::net::{Host, OpensslClientConfig, UpstreamAddr};
+
+use crate::serve::http_rproxy::HttpUpstreamPool;
+use crate::serve::http_rproxy::host::HttpHost;
+
+/// The selected upstream for a forward task, which may be a member of a routed pool
+pub(crate) struct HttpRProxyUpstream {
+    host: Arc<HttpHost>,
+    pool: Option<Arc<HttpUpstreamPool>>,
+    addr: UpstreamAddr,
+    tried: Vec<UpstreamAddr>,
+}
+
+impl HttpRProxyUpstream {
+    pub(crate) fn new(
+        host: Arc<HttpHost>,
+        pool: Option<Arc<HttpUpstreamPool>>,
+        addr: UpstreamAddr,
+    ) -> Self {
+        HttpRProxyUpstream {
+            host,
+            pool,
+            addr,
+            tried: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub(super) fn addr(&self) -> &UpstreamAddr {
+        &self.addr
+    }
+
+    pub(super) fn tls_client(&self) -> Option<&OpensslClientConfig> {
+        match &self.pool {
+            Some(pool) => pool.tls_client.as_ref(),
+            None => self.host.tls_client.as_ref(),
+        }
+    }
+
+    pub(super) fn tls_name(&self) -> &Host {
+        match &self.pool {
+            Some(pool) => &pool.tls_name,
+            None => &self.host.config.tls_name,
+        }
+    }
+
+    pub(super) fn add_success(&self) {
+        if let Some(pool) = &self.pool {
+            pool.add_success(&self.addr);
+        }
+    }
+
+    pub(super) fn add_failure(&self) {
+        if let Some(pool) = &self.pool {
+            pool.add_failure(&self.addr);
+        }
+    }
+
+    /// Switch to another pool member if retry is allowed
+    pub(super) fn switch_next(&mut self, client_ip: IpAddr, path: &str) -> bool {
+        let Some(pool) = &self.pool else {
+            return false;
+        };
+        if self.tried.len() >= pool.max_retries() {
+            return false;
+        }
+        self.tried.push(self.addr.clone());
+        match pool.pick(client_ip, path, &self.tried) {
+            Some(addr) => {
+                self.addr = addr;
+                true
+            }
+            None => false,
+        }
+    }
+}
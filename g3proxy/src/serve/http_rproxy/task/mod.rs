@@ -15,7 +15,7 @@ mod forward;
 mod pipeline;
 mod untrusted;
 
-use forward::HttpRProxyForwardTask;
+use forward::{HttpRProxyForwardTask, HttpRProxyUpstream};
 pub(super) use pipeline::{
     HttpRProxyPipelineReaderTask, HttpRProxyPipelineStats, HttpRProxyPipelineWriterTask,
 };
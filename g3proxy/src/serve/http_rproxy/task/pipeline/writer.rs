@@ -18,7 +18,7 @@ use g3_types::route::HostMatch;
 use super::protocol::{HttpClientWriter, HttpRProxyRequest};
 use super::{
     CommonTaskContext, HttpRProxyCltWrapperStats, HttpRProxyForwardTask, HttpRProxyPipelineStats,
-    HttpRProxyUntrustedTask,
+    HttpRProxyUntrustedTask, HttpRProxyUpstream,
 };
 use crate::audit::AuditContext;
 use crate::auth::{UserContext, UserGroup, UserRequestStats};
@@ -248,15 +248,35 @@ where
             req.time_accepted.elapsed(),
         );
 
+        let pool = host.match_pool(&req.inner);
+        let upstream = match &pool {
+            Some(pool) => pool.pick(self.ctx.client_ip(), req.inner.uri.path(), &[]),
+            None if host.has_default_upstream() => Some(host.config.upstream().clone()),
+            None => None,
+        };
+        let Some(upstream) = upstream else {
+            // no route matched and no default upstream set
+            if let Some(stream_w) = &mut self.stream_writer {
+                let rsp = HttpProxyClientResponse::resource_not_found(req.inner.version, true);
+                let _ = rsp.reply_err_to_request(stream_w).await;
+            }
+            self.notify_reader_to_close();
+            return LoopAction::Break;
+        };
+
         if let Some(mut stream_w) = self.stream_writer.take() {
             let mut audit_ctx = AuditContext::default();
             // check in final escaper so we can use route escapers
             let _ = self
                 .forward_context
-                .check_in_final_escaper(&task_notes, host.config.upstream(), &mut audit_ctx)
+                .check_in_final_escaper(&task_notes, &upstream, &mut audit_ctx)
                 .await;
 
-            match self.run_forward(&mut stream_w, req, host, task_notes).await {
+            let upstream = HttpRProxyUpstream::new(host, pool, upstream);
+            match self
+                .run_forward(&mut stream_w, req, upstream, task_notes, audit_ctx)
+                .await
+            {
                 LoopAction::Continue => {
                     self.reset_client_writer(stream_w);
                     LoopAction::Continue
@@ -372,15 +392,16 @@ where
         &mut self,
         clt_w: &mut HttpClientWriter<CDW>,
         mut req: HttpRProxyRequest<CDR>,
-        host: Arc<HttpHost>,
+        upstream: HttpRProxyUpstream,
         task_notes: ServerTaskNotes,
+        audit_ctx: AuditContext,
     ) -> LoopAction {
         match req.body_reader.take() {
             Some(stream_r) => {
                 // we have a body, or we need to close the connection
                 // we may need to send stream_r back if we have a body
                 let mut forward_task =
-                    HttpRProxyForwardTask::new(&self.ctx, &req, host, task_notes);
+                    HttpRProxyForwardTask::new(&self.ctx, &req, upstream, task_notes, audit_ctx);
                 let mut clt_r = Some(stream_r);
                 forward_task
                     .run(&mut clt_r, clt_w, &mut self.forward_context)
@@ -402,7 +423,7 @@ where
             None => {
                 // no body, and the connection is expected to keep alive from the client side
                 let mut forward_task =
-                    HttpRProxyForwardTask::new(&self.ctx, &req, host, task_notes);
+                    HttpRProxyForwardTask::new(&self.ctx, &req, upstream, task_notes, audit_ctx);
                 let mut clt_r = None;
                 forward_task
                     .run::<CDR, CDW>(&mut clt_r, clt_w, &mut self.forward_context)
@@ -3,8 +3,10 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 #[cfg(feature = "quic")]
 use quinn::Connection;
@@ -12,7 +14,7 @@ use tokio::net::TcpStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats};
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ReceiveUdpServer};
 use g3_daemon::server::{
     BaseServer, ClientConnectionInfo, ReloadServer, ServerQuitPolicy, ServerReloadCommand,
 };
@@ -49,6 +51,8 @@ mod tcp_stream;
 ))]
 mod tcp_tproxy;
 mod tls_stream;
+#[cfg(target_os = "linux")]
+mod udp_tproxy;
 
 mod error;
 mod task;
@@ -83,6 +87,20 @@ pub(crate) trait Server: BaseServer + AcceptTcpServer + AcceptQuicServer {
     fn alive_count(&self) -> i32;
     fn quit_policy(&self) -> &Arc<ServerQuitPolicy>;
 
+    fn purge_http_cache(&self, _url: Option<&str>) -> anyhow::Result<()> {
+        Err(anyhow!("http cache is not supported on this server"))
+    }
+
+    /// Handle a packet received by the udp listen socket, only used by udp based servers
+    fn receive_udp_packet(
+        &self,
+        _packet: &[u8],
+        _client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        _worker_id: Option<usize>,
+    ) {
+    }
+
     async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo);
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo);
@@ -157,6 +175,19 @@ impl AcceptQuicServer for WrapArcServer {
     }
 }
 
+impl ReceiveUdpServer for WrapArcServer {
+    fn receive_udp_packet(
+        &self,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        self.0
+            .receive_udp_packet(packet, client_addr, server_addr, worker_id)
+    }
+}
+
 fn new_reload_notify_channel() -> broadcast::Sender<ServerReloadCommand> {
     broadcast::Sender::new(16)
 }
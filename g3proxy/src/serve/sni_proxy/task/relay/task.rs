@@ -25,6 +25,7 @@ use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
 use crate::serve::tcp_stream::{TcpStreamServerAliveTaskGuard, TcpStreamTaskCltWrapperStats};
 use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::trace::tcp_connect::TaskSpanForTcpConnect;
 
 pub(crate) struct TcpStreamTask {
     ctx: CommonTaskContext,
@@ -75,6 +76,16 @@ impl TcpStreamTask {
             })
     }
 
+    fn emit_trace_span(&self, e: &ServerTaskError) {
+        TaskSpanForTcpConnect {
+            server_type: "SNI PROXY",
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            tcp_notes: &self.tcp_notes,
+        }
+        .emit(e);
+    }
+
     pub(crate) async fn into_running<R, W>(
         mut self,
         clt_r: LimitedReader<R>,
@@ -89,6 +100,7 @@ impl TcpStreamTask {
             Ok(_) => ServerTaskError::Finished,
             Err(e) => e,
         };
+        self.emit_trace_span(&e);
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(e);
         }
@@ -240,7 +252,11 @@ impl TcpStreamTask {
                     }
                 }
                 Protocol::Http1 => {
-                    let mut h1_obj = crate::inspect::http::H1InterceptObject::new(ctx);
+                    let mut h1_obj = crate::inspect::http::H1InterceptObject::new(
+                        ctx,
+                        self.upstream.clone(),
+                        false,
+                    );
                     h1_obj.set_io(
                         FlexBufReader::with_bytes(clt_r_buf, Box::new(clt_r)),
                         Box::new(clt_w),
@@ -29,6 +29,7 @@ use super::SocksProxyServerStats;
 use super::task::{CommonTaskContext, SocksProxyNegotiationTask};
 use crate::audit::{AuditContext, AuditHandle};
 use crate::auth::UserGroup;
+use crate::category::CategoryFilter;
 use crate::config::server::socks_proxy::SocksProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
@@ -43,6 +44,7 @@ pub(crate) struct SocksProxyServer {
     listen_stats: Arc<ListenStats>,
     ingress_net_filter: Option<Arc<AclNetworkRule>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    dst_category_filter: Option<Arc<CategoryFilter>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Option<Logger>,
 
@@ -72,6 +74,10 @@ impl SocksProxyServer {
             .dst_host_filter
             .as_ref()
             .map(|builder| Arc::new(builder.build()));
+        let dst_category_filter = config
+            .dst_category_filter
+            .as_ref()
+            .map(|config| Arc::new(CategoryFilter::new(config)));
 
         let task_logger = config.get_task_logger();
         let idle_wheel = IdleWheel::spawn(config.task_idle_check_interval);
@@ -88,6 +94,7 @@ impl SocksProxyServer {
             listen_stats,
             ingress_net_filter,
             dst_host_filter,
+            dst_category_filter,
             reload_sender,
             task_logger,
             escaper: ArcSwap::new(escaper),
@@ -171,6 +178,7 @@ impl SocksProxyServer {
             escaper: self.escaper.load().as_ref().clone(),
             ingress_net_filter: self.ingress_net_filter.clone(),
             dst_host_filter: self.dst_host_filter.clone(),
+            dst_category_filter: self.dst_category_filter.clone(),
             cc_info,
             task_logger: self.task_logger.clone(),
         };
@@ -18,6 +18,7 @@ use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::net::UpstreamAddr;
 
 use super::{SocksProxyServerConfig, SocksProxyServerStats};
+use crate::category::CategoryFilter;
 use crate::escape::ArcEscaper;
 use crate::serve::{ServerQuitPolicy, ServerTaskError, ServerTaskNotes, ServerTaskResult};
 
@@ -30,6 +31,7 @@ pub(crate) struct CommonTaskContext {
     pub(crate) escaper: ArcEscaper,
     pub(crate) ingress_net_filter: Option<Arc<AclNetworkRule>>,
     pub(crate) dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    pub(crate) dst_category_filter: Option<Arc<CategoryFilter>>,
     pub(crate) cc_info: ClientConnectionInfo,
     pub(crate) task_logger: Option<Logger>,
 }
@@ -333,10 +333,14 @@ impl SocksProxyNegotiationTask {
                 if use_udp_associate {
                     let task =
                         SocksProxyUdpAssociateTask::new(self.ctx, task_notes, udp_check_addr);
+                    #[cfg(feature = "quic")]
+                    let task = task.with_audit_context(self.audit_ctx);
                     task.into_running(clt_r.into_inner(), clt_w);
                     Ok(())
                 } else {
                     let task = SocksProxyUdpConnectTask::new(self.ctx, task_notes, udp_check_addr);
+                    #[cfg(feature = "quic")]
+                    let task = task.with_audit_context(self.audit_ctx);
                     task.into_running(clt_r.into_inner(), clt_w);
                     Ok(())
                 }
@@ -28,6 +28,7 @@ use crate::serve::{
     ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
     ServerTaskStage,
 };
+use crate::trace::tcp_connect::TaskSpanForTcpConnect;
 
 pub(crate) struct SocksProxyTcpConnectTask {
     socks_version: SocksVersion,
@@ -95,6 +96,16 @@ impl SocksProxyTcpConnectTask {
             })
     }
 
+    fn emit_trace_span(&self, e: &ServerTaskError) {
+        TaskSpanForTcpConnect {
+            server_type: "SOCKS CONNECT",
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            tcp_notes: &self.tcp_notes,
+        }
+        .emit(e);
+    }
+
     pub(crate) fn into_running<R, W>(mut self, clt_r: LimitedReader<R>, clt_w: LimitedWriter<W>)
     where
         R: AsyncRead + Send + Sync + Unpin + 'static,
@@ -106,6 +117,7 @@ impl SocksProxyTcpConnectTask {
                 Ok(_) => ServerTaskError::Finished,
                 Err(e) => e,
             };
+            self.emit_trace_span(&e);
             if let Some(log_ctx) = self.get_log_context() {
                 log_ctx.log(e);
             }
@@ -195,6 +207,30 @@ impl SocksProxyTcpConnectTask {
         }
     }
 
+    /// Check the category of the upstream host
+    async fn check_dst_category<W>(&mut self, clt_w: &mut W) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let (category, action) = user_ctx.check_dst_category(&self.upstream, None);
+            if category.is_some() {
+                self.task_notes.category = category;
+            }
+            self.handle_user_acl_action(action, clt_w, ServerTaskForbiddenError::DestDenied)
+                .await?;
+        }
+        if let Some(filter) = &self.ctx.dst_category_filter {
+            let (category, action) = filter.check(&self.upstream, None);
+            if category.is_some() {
+                self.task_notes.category = category;
+            }
+            self.handle_server_upstream_acl_action(action, clt_w)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn handle_user_acl_action<W>(
         &self,
         action: AclAction,
@@ -280,6 +316,9 @@ impl SocksProxyTcpConnectTask {
             tcp_client_misc_opts = Cow::Borrowed(&self.ctx.server_config.tcp_misc_opts);
         }
 
+        // user and server level dst category acl rules
+        self.check_dst_category(&mut clt_w).await?;
+
         // set client side socket options
         self.ctx
             .cc_info
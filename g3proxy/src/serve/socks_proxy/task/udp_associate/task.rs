@@ -24,7 +24,11 @@ use super::{
     CommonTaskContext, Socks5UdpAssociateClientRecv, Socks5UdpAssociateClientSend,
     UdpAssociateTaskCltWrapperStats, UdpAssociateTaskStats,
 };
+#[cfg(feature = "quic")]
+use crate::audit::AuditContext;
 use crate::config::server::ServerConfig;
+#[cfg(feature = "quic")]
+use crate::inspect::quic::{QuicFlowInspector, QuicInspectRelayRemoteSend};
 use crate::log::escape::udp_sendto::EscapeLogForUdpRelaySendto;
 use crate::log::task::udp_associate::TaskLogForUdpAssociate;
 use crate::module::udp_relay::{UdpRelayTaskConf, UdpRelayTaskNotes};
@@ -43,6 +47,8 @@ pub(crate) struct SocksProxyUdpAssociateTask {
     udp_client_addr: Option<SocketAddr>,
     max_idle_count: usize,
     started: bool,
+    #[cfg(feature = "quic")]
+    audit_ctx: AuditContext,
 }
 
 impl Drop for SocksProxyUdpAssociateTask {
@@ -74,9 +80,17 @@ impl SocksProxyUdpAssociateTask {
             udp_client_addr,
             max_idle_count,
             started: false,
+            #[cfg(feature = "quic")]
+            audit_ctx: AuditContext::default(),
         }
     }
 
+    #[cfg(feature = "quic")]
+    pub(crate) fn with_audit_context(mut self, audit_ctx: AuditContext) -> Self {
+        self.audit_ctx = audit_ctx;
+        self
+    }
+
     fn get_log_context(&self) -> Option<TaskLogForUdpAssociate<'_>> {
         self.ctx
             .task_logger
@@ -495,7 +509,7 @@ impl SocksProxyUdpAssociateTask {
             initial_peer: &self.initial_peer,
             sock_buf: self.ctx.server_config.udp_socket_buffer,
         };
-        let (ups_r, mut ups_w, logger) = self
+        let (ups_r, ups_w, logger) = self
             .ctx
             .escaper
             .udp_setup_relay(
@@ -505,6 +519,7 @@ impl SocksProxyUdpAssociateTask {
                 self.task_stats.clone(),
             )
             .await?;
+        let mut ups_w = self.wrap_ups_send(ups_w);
         self.task_notes.stage = ServerTaskStage::Connected;
 
         if self.ctx.server_config.flush_task_log_on_connected
@@ -520,6 +535,19 @@ impl SocksProxyUdpAssociateTask {
         Ok((clt_r, clt_w, ups_r, ups_w, logger))
     }
 
+    fn wrap_ups_send(
+        &self,
+        ups_w: Box<dyn UdpRelayRemoteSend + Unpin + Send>,
+    ) -> Box<dyn UdpRelayRemoteSend + Unpin + Send> {
+        #[cfg(feature = "quic")]
+        if let Some(audit_handle) = self.audit_ctx.handle()
+            && let Some(inspector) = QuicFlowInspector::new_for_task(audit_handle, &self.task_notes)
+        {
+            return Box::new(QuicInspectRelayRemoteSend::new(ups_w, inspector));
+        }
+        ups_w
+    }
+
     async fn recv_first_packet<R>(
         &mut self,
         clt_tcp_r: &mut R,
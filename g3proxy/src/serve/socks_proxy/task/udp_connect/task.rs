@@ -24,7 +24,11 @@ use super::{
     CommonTaskContext, Socks5UdpConnectClientRecv, Socks5UdpConnectClientSend,
     UdpConnectTaskCltWrapperStats, UdpConnectTaskStats,
 };
+#[cfg(feature = "quic")]
+use crate::audit::AuditContext;
 use crate::config::server::ServerConfig;
+#[cfg(feature = "quic")]
+use crate::inspect::quic::{QuicFlowInspector, QuicInspectCopyRemoteSend};
 use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
 use crate::log::task::udp_connect::TaskLogForUdpConnect;
 use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
@@ -43,6 +47,8 @@ pub(crate) struct SocksProxyUdpConnectTask {
     udp_client_addr: Option<SocketAddr>,
     max_idle_count: usize,
     started: bool,
+    #[cfg(feature = "quic")]
+    audit_ctx: AuditContext,
 }
 
 impl Drop for SocksProxyUdpConnectTask {
@@ -73,9 +79,17 @@ impl SocksProxyUdpConnectTask {
             udp_client_addr,
             max_idle_count,
             started: false,
+            #[cfg(feature = "quic")]
+            audit_ctx: AuditContext::default(),
         }
     }
 
+    #[cfg(feature = "quic")]
+    pub(crate) fn with_audit_context(mut self, audit_ctx: AuditContext) -> Self {
+        self.audit_ctx = audit_ctx;
+        self
+    }
+
     fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
         self.ctx
             .task_logger
@@ -545,7 +559,7 @@ impl SocksProxyUdpConnectTask {
             upstream: &upstream,
             sock_buf: self.ctx.server_config.udp_socket_buffer,
         };
-        let (ups_r, mut ups_w, logger) = self
+        let (ups_r, ups_w, logger) = self
             .ctx
             .escaper
             .udp_setup_connection(
@@ -555,6 +569,7 @@ impl SocksProxyUdpConnectTask {
                 self.task_stats.clone(),
             )
             .await?;
+        let mut ups_w = self.wrap_ups_send(ups_w, &upstream);
         self.task_notes.stage = ServerTaskStage::Connected;
 
         if self.ctx.server_config.flush_task_log_on_connected
@@ -570,6 +585,33 @@ impl SocksProxyUdpConnectTask {
         Ok((clt_r, clt_w, ups_r, ups_w, logger))
     }
 
+    #[cfg(feature = "quic")]
+    fn wrap_ups_send(
+        &self,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+        upstream: &UpstreamAddr,
+    ) -> Box<dyn UdpCopyRemoteSend + Unpin + Send> {
+        if let Some(audit_handle) = self.audit_ctx.handle()
+            && let Some(inspector) = QuicFlowInspector::new_for_task(audit_handle, &self.task_notes)
+        {
+            return Box::new(QuicInspectCopyRemoteSend::new(
+                ups_w,
+                upstream.clone(),
+                inspector,
+            ));
+        }
+        ups_w
+    }
+
+    #[cfg(not(feature = "quic"))]
+    fn wrap_ups_send(
+        &self,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+        _upstream: &UpstreamAddr,
+    ) -> Box<dyn UdpCopyRemoteSend + Unpin + Send> {
+        ups_w
+    }
+
     async fn recv_first_packet<R>(
         &self,
         clt_tcp_r: &mut R,
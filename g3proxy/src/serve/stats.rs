@@ -11,6 +11,7 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
+use crate::module::http_cache::HttpCacheStatsSnapshot;
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
 pub(crate) trait ServerStats {
@@ -40,6 +41,10 @@ pub(crate) trait ServerStats {
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         None
     }
+
+    fn http_cache_snapshot(&self) -> Option<HttpCacheStatsSnapshot> {
+        None
+    }
 }
 
 pub(crate) type ArcServerStats = Arc<dyn ServerStats + Send + Sync>;
@@ -17,6 +17,7 @@ use g3_types::metrics::NodeName;
 
 use crate::auth::UserContext;
 use crate::escape::{EgressPathSelection, EgressUpstream};
+use crate::trace::TraceContext;
 
 #[derive(Clone, Copy)]
 pub(crate) enum ServerTaskStage {
@@ -58,6 +59,10 @@ pub(crate) struct ServerTaskNotes {
     pub(crate) wait_time: Duration,
     pub(crate) ready_time: Duration,
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
+    /// the matched category of the upstream
+    pub(crate) category: Option<NodeName>,
+    /// trace context of the task span, set if the trace exporter is enabled
+    pub(crate) trace_ctx: Option<TraceContext>,
     /// the following fields should not be cloned
     pub(crate) user_req_alive_permit: Option<GaugeSemaphorePermit>,
 }
@@ -89,6 +94,8 @@ impl ServerTaskNotes {
             wait_time,
             ready_time: Duration::default(),
             egress_path_selection,
+            category: None,
+            trace_ctx: crate::trace::enabled().then(TraceContext::new_root),
             user_req_alive_permit: None,
         }
     }
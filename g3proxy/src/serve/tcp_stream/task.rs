@@ -21,6 +21,7 @@ use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
 use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::trace::tcp_connect::TaskSpanForTcpConnect;
 
 pub(super) struct TcpStreamTask {
     ctx: CommonTaskContext,
@@ -66,6 +67,16 @@ impl TcpStreamTask {
             })
     }
 
+    fn emit_trace_span(&self, e: &ServerTaskError) {
+        TaskSpanForTcpConnect {
+            server_type: "TCP STREAM",
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            tcp_notes: &self.tcp_notes,
+        }
+        .emit(e);
+    }
+
     pub(super) async fn into_running<CR, CW>(mut self, clt_r: CR, clt_w: CW)
     where
         CR: AsyncRead + Send + Sync + Unpin + 'static,
@@ -77,6 +88,7 @@ impl TcpStreamTask {
             Ok(_) => ServerTaskError::Finished,
             Err(e) => e,
         };
+        self.emit_trace_span(&e);
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(e);
         }
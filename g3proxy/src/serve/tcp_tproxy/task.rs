@@ -22,6 +22,7 @@ use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
 use crate::serve::tcp_stream::{TcpStreamServerAliveTaskGuard, TcpStreamTaskCltWrapperStats};
 use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::trace::tcp_connect::TaskSpanForTcpConnect;
 
 pub(super) struct TProxyStreamTask {
     ctx: CommonTaskContext,
@@ -64,12 +65,23 @@ impl TProxyStreamTask {
             })
     }
 
+    fn emit_trace_span(&self, e: &ServerTaskError) {
+        TaskSpanForTcpConnect {
+            server_type: "TCP TPROXY",
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            tcp_notes: &self.tcp_notes,
+        }
+        .emit(e);
+    }
+
     pub(super) async fn into_running(mut self, stream: TcpStream) {
         self.pre_start();
         let e = match self.run(stream).await {
             Ok(_) => ServerTaskError::Finished,
             Err(e) => e,
         };
+        self.emit_trace_span(&e);
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(e);
         }
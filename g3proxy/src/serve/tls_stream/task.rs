@@ -23,6 +23,7 @@ use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
 use crate::serve::tcp_stream::{TcpStreamServerAliveTaskGuard, TcpStreamTaskCltWrapperStats};
 use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::trace::tcp_connect::TaskSpanForTcpConnect;
 
 pub(super) struct TlsStreamTask {
     ctx: CommonTaskContext,
@@ -68,12 +69,23 @@ impl TlsStreamTask {
             })
     }
 
+    fn emit_trace_span(&self, e: &ServerTaskError) {
+        TaskSpanForTcpConnect {
+            server_type: "TLS STREAM",
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            tcp_notes: &self.tcp_notes,
+        }
+        .emit(e);
+    }
+
     pub(super) async fn into_running(mut self, stream: TlsStream<TcpStream>) {
         self.pre_start();
         let e = match self.run(stream).await {
             Ok(_) => ServerTaskError::Finished,
             Err(e) => e,
         };
+        self.emit_trace_span(&e);
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(e);
         }
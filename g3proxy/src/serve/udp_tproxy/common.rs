@@ -0,0 +1,72 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use slog::Logger;
+use tokio::time::Instant;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_io_ext::{IdleWheel, OptionalInterval};
+use g3_types::acl::AclAction;
+use g3_types::acl_set::AclDstHostRuleSet;
+use g3_types::net::UpstreamAddr;
+
+use super::UdpTProxyServerStats;
+use crate::config::server::udp_tproxy::UdpTProxyServerConfig;
+use crate::escape::ArcEscaper;
+use crate::serve::ServerQuitPolicy;
+
+pub(super) struct CommonTaskContext {
+    pub(super) server_config: Arc<UdpTProxyServerConfig>,
+    pub(super) server_stats: Arc<UdpTProxyServerStats>,
+    pub(super) server_quit_policy: Arc<ServerQuitPolicy>,
+    pub(super) idle_wheel: Arc<IdleWheel>,
+    pub(super) escaper: ArcEscaper,
+    pub(super) dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    pub(super) cc_info: ClientConnectionInfo,
+    pub(super) task_logger: Option<Logger>,
+}
+
+impl CommonTaskContext {
+    pub(super) fn check_upstream(&self, upstream: &UpstreamAddr) -> AclAction {
+        let mut default_action = AclAction::Permit;
+
+        if let Some(filter) = &self.server_config.dst_port_filter {
+            let port = upstream.port();
+            let (found, action) = filter.check_port(&port);
+            if found && action.forbid_early() {
+                return action;
+            };
+            default_action = default_action.restrict(action);
+        }
+
+        if let Some(filter) = &self.dst_host_filter {
+            let (found, action) = filter.check(upstream.host());
+            if found && action.forbid_early() {
+                return action;
+            }
+            default_action = default_action.restrict(action);
+        }
+
+        default_action
+    }
+
+    fn log_flush_interval(&self) -> Option<Duration> {
+        self.task_logger.as_ref()?;
+        self.server_config.task_log_flush_interval
+    }
+
+    pub(super) fn get_log_interval(&self) -> OptionalInterval {
+        self.log_flush_interval()
+            .map(|log_interval| {
+                let log_interval =
+                    tokio::time::interval_at(Instant::now() + log_interval, log_interval);
+                OptionalInterval::with(log_interval)
+            })
+            .unwrap_or_default()
+    }
+}
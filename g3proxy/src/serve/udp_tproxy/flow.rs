@@ -0,0 +1,238 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::hash::{Hash, Hasher};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+use bytes::Bytes;
+use rustc_hash::{FxHashMap, FxHasher};
+use tokio::sync::mpsc;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(super) struct UdpTProxyFlowKey {
+    pub(super) client: SocketAddr,
+    pub(super) target: SocketAddr,
+}
+
+struct UdpTProxyFlowEntry {
+    id: u64,
+    sender: mpsc::Sender<Bytes>,
+}
+
+pub(super) enum UdpTProxyFlowDispatch {
+    /// the packet has been sent to the running flow task
+    Queued,
+    /// the queue of the flow task is full, and the packet is dropped
+    Dropped,
+    /// no running flow task found
+    NotFound,
+}
+
+/// The shard count of the flow table, should be a power of 2
+const FLOW_TABLE_SHARDS: usize = 16;
+
+type UdpTProxyFlowShard = RwLock<FxHashMap<UdpTProxyFlowKey, UdpTProxyFlowEntry>>;
+
+/// All the running flows of a server, shared by all versions of the server.
+///
+/// The flows are sharded by key, and the dispatch of packets only needs a shared lock.
+pub(crate) struct UdpTProxyFlowTable {
+    next_id: AtomicU64,
+    count: AtomicUsize,
+    shards: Box<[UdpTProxyFlowShard]>,
+}
+
+impl Default for UdpTProxyFlowTable {
+    fn default() -> Self {
+        UdpTProxyFlowTable {
+            next_id: AtomicU64::new(0),
+            count: AtomicUsize::new(0),
+            shards: (0..FLOW_TABLE_SHARDS).map(|_| RwLock::default()).collect(),
+        }
+    }
+}
+
+impl UdpTProxyFlowTable {
+    fn shard(&self, key: &UdpTProxyFlowKey) -> &UdpTProxyFlowShard {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+        &self.shards[(hasher.finish() as usize) & (FLOW_TABLE_SHARDS - 1)]
+    }
+
+    /// Send the packet to the running flow task.
+    ///
+    /// The packet will only be copied if there is room in the queue, and this should only happen
+    /// before the connected reply socket of the flow takes over.
+    pub(super) fn dispatch(&self, key: &UdpTProxyFlowKey, packet: &[u8]) -> UdpTProxyFlowDispatch {
+        let shard = self.shard(key);
+        let flows = shard.read().unwrap();
+        let Some(entry) = flows.get(key) else {
+            return UdpTProxyFlowDispatch::NotFound;
+        };
+        match entry.sender.try_reserve() {
+            Ok(permit) => {
+                permit.send(Bytes::copy_from_slice(packet));
+                UdpTProxyFlowDispatch::Queued
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => UdpTProxyFlowDispatch::Dropped,
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                // the task is quiting, let a new one take over the flow
+                let id = entry.id;
+                drop(flows);
+                self.remove(shard, key, id);
+                UdpTProxyFlowDispatch::NotFound
+            }
+        }
+    }
+
+    /// Register a new flow, None will be returned if there are already too many flows
+    pub(super) fn register(
+        self: &Arc<Self>,
+        key: UdpTProxyFlowKey,
+        sender: mpsc::Sender<Bytes>,
+        max_count: usize,
+    ) -> Option<UdpTProxyFlowGuard> {
+        if self.count.fetch_add(1, Ordering::Relaxed) >= max_count {
+            self.count.fetch_sub(1, Ordering::Relaxed);
+            return None;
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let old = self
+            .shard(&key)
+            .write()
+            .unwrap()
+            .insert(key, UdpTProxyFlowEntry { id, sender });
+        if old.is_some() {
+            // the old one has been replaced
+            self.count.fetch_sub(1, Ordering::Relaxed);
+        }
+        Some(UdpTProxyFlowGuard {
+            table: self.clone(),
+            key,
+            id,
+        })
+    }
+
+    fn remove(&self, shard: &UdpTProxyFlowShard, key: &UdpTProxyFlowKey, id: u64) {
+        let mut flows = shard.write().unwrap();
+        if flows.get(key).map(|e| e.id == id).unwrap_or(false) {
+            flows.remove(key);
+            self.count.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    fn unregister(&self, key: &UdpTProxyFlowKey, id: u64) {
+        self.remove(self.shard(key), key, id);
+    }
+
+    #[cfg(test)]
+    fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+/// Remove the flow from the table when the flow task is finished
+pub(super) struct UdpTProxyFlowGuard {
+    table: Arc<UdpTProxyFlowTable>,
+    key: UdpTProxyFlowKey,
+    id: u64,
+}
+
+impl Drop for UdpTProxyFlowGuard {
+    fn drop(&mut self) {
+        self.table.unregister(&self.key, self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow_key(port: u16) -> UdpTProxyFlowKey {
+        UdpTProxyFlowKey {
+            client: SocketAddr::from(([192, 168, 1, 1], port)),
+            target: SocketAddr::from(([8, 8, 8, 8], 53)),
+        }
+    }
+
+    #[test]
+    fn dispatch() {
+        let table = Arc::new(UdpTProxyFlowTable::default());
+        let key = flow_key(10000);
+        assert!(matches!(
+            table.dispatch(&key, b"a"),
+            UdpTProxyFlowDispatch::NotFound
+        ));
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        let _guard = table.register(key, sender, 16).unwrap();
+        assert!(matches!(
+            table.dispatch(&key, b"a"),
+            UdpTProxyFlowDispatch::Queued
+        ));
+        assert!(matches!(
+            table.dispatch(&key, b"b"),
+            UdpTProxyFlowDispatch::Dropped
+        ));
+        assert_eq!(receiver.try_recv().unwrap().as_ref(), b"a");
+        assert!(receiver.try_recv().is_err());
+
+        assert!(matches!(
+            table.dispatch(&flow_key(10001), b"a"),
+            UdpTProxyFlowDispatch::NotFound
+        ));
+
+        // the closed flow should be removed
+        drop(receiver);
+        assert!(matches!(
+            table.dispatch(&key, b"c"),
+            UdpTProxyFlowDispatch::NotFound
+        ));
+        assert_eq!(table.count(), 0);
+    }
+
+    #[test]
+    fn expire() {
+        let table = Arc::new(UdpTProxyFlowTable::default());
+        let key = flow_key(10000);
+
+        let (sender, _receiver) = mpsc::channel(1);
+        let guard = table.register(key, sender, 2).unwrap();
+        assert_eq!(table.count(), 1);
+        drop(guard);
+        assert_eq!(table.count(), 0);
+        assert!(matches!(
+            table.dispatch(&key, b"a"),
+            UdpTProxyFlowDispatch::NotFound
+        ));
+
+        // the guard of the replaced flow should not remove the new one
+        let (sender, _receiver1) = mpsc::channel(1);
+        let old_guard = table.register(key, sender, 2).unwrap();
+        let (sender, _receiver2) = mpsc::channel(1);
+        let _new_guard = table.register(key, sender, 2).unwrap();
+        assert_eq!(table.count(), 1);
+        drop(old_guard);
+        assert_eq!(table.count(), 1);
+        assert!(matches!(
+            table.dispatch(&key, b"a"),
+            UdpTProxyFlowDispatch::Queued
+        ));
+    }
+
+    #[test]
+    fn max_count() {
+        let table = Arc::new(UdpTProxyFlowTable::default());
+        let (sender, _receiver) = mpsc::channel(1);
+        let _guard1 = table.register(flow_key(10000), sender.clone(), 2).unwrap();
+        let guard2 = table.register(flow_key(10001), sender.clone(), 2).unwrap();
+        assert!(table.register(flow_key(10002), sender.clone(), 2).is_none());
+        assert_eq!(table.count(), 2);
+        drop(guard2);
+        assert!(table.register(flow_key(10002), sender, 2).is_some());
+    }
+}
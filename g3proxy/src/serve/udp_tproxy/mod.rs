@@ -0,0 +1,28 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod common;
+use common::CommonTaskContext;
+
+mod flow;
+use flow::{UdpTProxyFlowDispatch, UdpTProxyFlowGuard, UdpTProxyFlowKey, UdpTProxyFlowTable};
+
+mod recv;
+use recv::UdpTProxyClientRecv;
+
+mod send;
+use send::UdpTProxyClientSend;
+
+mod stats;
+use stats::{
+    UdpTProxyServerAliveTaskGuard, UdpTProxyServerStats, UdpTProxyTaskCltWrapperStats,
+    UdpTProxyTaskStats,
+};
+
+mod task;
+use task::UdpTProxyFlowTask;
+
+mod server;
+pub(crate) use server::UdpTProxyServer;
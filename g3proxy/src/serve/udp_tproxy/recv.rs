@@ -0,0 +1,114 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::IoSliceMut;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+
+use g3_io_ext::{
+    AsyncUdpRecv, LimitedRecvStats, UdpCopyClientError, UdpCopyClientRecv, UdpCopyPacket,
+    UdpCopyPacketMeta,
+};
+use g3_io_sys::udp::RecvMsgHdr;
+
+use super::UdpTProxyTaskCltWrapperStats;
+
+/// Receive client packets from both the connected reply socket and the listen socket.
+///
+/// The kernel will deliver packets to the connected reply socket once it's created,
+/// the packets received by the listen socket before that will be queued by the server.
+pub(super) struct UdpTProxyClientRecv<T> {
+    inner: T,
+    queued: mpsc::Receiver<Bytes>,
+    queued_stats: UdpTProxyTaskCltWrapperStats,
+}
+
+impl<T> UdpTProxyClientRecv<T>
+where
+    T: AsyncUdpRecv,
+{
+    pub(super) fn new(
+        inner: T,
+        queued: mpsc::Receiver<Bytes>,
+        queued_stats: UdpTProxyTaskCltWrapperStats,
+    ) -> Self {
+        UdpTProxyClientRecv {
+            inner,
+            queued,
+            queued_stats,
+        }
+    }
+
+    fn poll_queued(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Option<usize> {
+        match self.queued.poll_recv(cx) {
+            Poll::Ready(Some(packet)) => {
+                let len = packet.len().min(buf.len());
+                buf[..len].copy_from_slice(&packet[..len]);
+                self.queued_stats.add_recv_packets(1);
+                self.queued_stats.add_recv_bytes(len);
+                Some(len)
+            }
+            Poll::Ready(None) | Poll::Pending => None,
+        }
+    }
+}
+
+impl<T> UdpCopyClientRecv for UdpTProxyClientRecv<T>
+where
+    T: AsyncUdpRecv + Send,
+{
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyClientError>> {
+        if let Some(len) = self.poll_queued(cx, buf) {
+            return Poll::Ready(Ok((0, len)));
+        }
+
+        let nr = ready!(self.inner.poll_recv(cx, buf)).map_err(UdpCopyClientError::RecvFailed)?;
+        Poll::Ready(Ok((0, nr)))
+    }
+
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        if let Some(p) = packets.first_mut() {
+            let buf = p.buf_mut();
+            if let Some(len) = self.poll_queued(cx, buf) {
+                let meta = UdpCopyPacketMeta::new(&IoSliceMut::new(buf), 0, len);
+                meta.set_packet(p);
+                return Poll::Ready(Ok(1));
+            }
+        }
+
+        let mut hdr_v: Vec<RecvMsgHdr<1>> = packets
+            .iter_mut()
+            .map(|p| RecvMsgHdr::new([IoSliceMut::new(p.buf_mut())]))
+            .collect();
+
+        let count = ready!(self.inner.poll_batch_recvmsg(cx, &mut hdr_v))
+            .map_err(UdpCopyClientError::RecvFailed)?;
+
+        let r: Vec<UdpCopyPacketMeta> = hdr_v
+            .iter()
+            .take(count)
+            .map(|h| UdpCopyPacketMeta::new(&h.iov[0], 0, h.n_recv))
+            .collect();
+        for (m, p) in r.into_iter().zip(packets.iter_mut()) {
+            m.set_packet(p);
+        }
+
+        Poll::Ready(Ok(count))
+    }
+}
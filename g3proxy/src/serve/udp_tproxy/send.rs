@@ -0,0 +1,68 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, IoSlice};
+use std::task::{Context, Poll, ready};
+
+use g3_io_ext::{AsyncUdpSend, UdpCopyClientError, UdpCopyClientSend, UdpCopyPacket};
+use g3_io_sys::udp::SendMsgHdr;
+
+/// Send packets to the client through the connected reply socket,
+/// which is bound to the original destination address
+pub(super) struct UdpTProxyClientSend<T> {
+    inner: T,
+}
+
+impl<T> UdpTProxyClientSend<T>
+where
+    T: AsyncUdpSend,
+{
+    pub(super) fn new(inner: T) -> Self {
+        UdpTProxyClientSend { inner }
+    }
+}
+
+impl<T> UdpCopyClientSend for UdpTProxyClientSend<T>
+where
+    T: AsyncUdpSend + Send,
+{
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let nw = ready!(self.inner.poll_send(cx, buf)).map_err(UdpCopyClientError::SendFailed)?;
+        if nw == 0 {
+            Poll::Ready(Err(UdpCopyClientError::SendFailed(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero byte into sender",
+            ))))
+        } else {
+            Poll::Ready(Ok(nw))
+        }
+    }
+
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut msgs: Vec<SendMsgHdr<1>> = packets
+            .iter()
+            .map(|p| SendMsgHdr::new([IoSlice::new(p.payload())], None))
+            .collect();
+
+        let count = ready!(self.inner.poll_batch_sendmsg(cx, &mut msgs))
+            .map_err(UdpCopyClientError::SendFailed)?;
+        if count == 0 {
+            Poll::Ready(Err(UdpCopyClientError::SendFailed(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero packet into sender",
+            ))))
+        } else {
+            Poll::Ready(Ok(count))
+        }
+    }
+}
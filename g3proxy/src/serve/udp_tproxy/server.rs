@@ -0,0 +1,343 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use arc_swap::{ArcSwap, ArcSwapOption};
+use async_trait::async_trait;
+use bytes::Bytes;
+#[cfg(feature = "quic")]
+use quinn::Connection;
+use slog::Logger;
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc};
+use tokio_rustls::server::TlsStream;
+
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ReceiveUdpRuntime};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
+use g3_io_ext::IdleWheel;
+use g3_openssl::SslStream;
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::acl_set::AclDstHostRuleSet;
+use g3_types::metrics::NodeName;
+
+use super::{
+    CommonTaskContext, UdpTProxyFlowDispatch, UdpTProxyFlowKey, UdpTProxyFlowTable,
+    UdpTProxyFlowTask, UdpTProxyServerStats,
+};
+use crate::audit::{AuditContext, AuditHandle};
+use crate::config::server::udp_tproxy::UdpTProxyServerConfig;
+use crate::config::server::{AnyServerConfig, ServerConfig};
+use crate::escape::ArcEscaper;
+use crate::serve::{
+    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
+    ServerRegistry, ServerStats, WrapArcServer,
+};
+
+pub(crate) struct UdpTProxyServer {
+    config: Arc<UdpTProxyServerConfig>,
+    server_stats: Arc<UdpTProxyServerStats>,
+    listen_stats: Arc<ListenStats>,
+    flow_table: Arc<UdpTProxyFlowTable>,
+    ingress_net_filter: Option<AclNetworkRule>,
+    dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+    task_logger: Option<Logger>,
+
+    escaper: ArcSwap<ArcEscaper>,
+    audit_handle: ArcSwapOption<AuditHandle>,
+    quit_policy: Arc<ServerQuitPolicy>,
+    idle_wheel: Arc<IdleWheel>,
+    reload_version: usize,
+}
+
+impl UdpTProxyServer {
+    fn new(
+        config: Arc<UdpTProxyServerConfig>,
+        server_stats: Arc<UdpTProxyServerStats>,
+        listen_stats: Arc<ListenStats>,
+        flow_table: Arc<UdpTProxyFlowTable>,
+        version: usize,
+    ) -> anyhow::Result<Self> {
+        let reload_sender = crate::serve::new_reload_notify_channel();
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+        let dst_host_filter = config
+            .dst_host_filter
+            .as_ref()
+            .map(|builder| Arc::new(builder.build()));
+
+        let task_logger = config.get_task_logger();
+        let idle_wheel = IdleWheel::spawn(config.task_idle_check_interval);
+
+        server_stats.set_extra_tags(config.extra_metrics_tags.clone());
+
+        let escaper = Arc::new(crate::escape::get_or_insert_default(config.escaper()));
+        let audit_handle = config.get_audit_handle()?;
+
+        let server = UdpTProxyServer {
+            config,
+            server_stats,
+            listen_stats,
+            flow_table,
+            ingress_net_filter,
+            dst_host_filter,
+            reload_sender,
+            task_logger,
+            escaper: ArcSwap::new(escaper),
+            audit_handle: ArcSwapOption::new(audit_handle),
+            quit_policy: Arc::new(ServerQuitPolicy::default()),
+            idle_wheel,
+            reload_version: version,
+        };
+
+        Ok(server)
+    }
+
+    pub(crate) fn prepare_initial(
+        config: UdpTProxyServerConfig,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let config = Arc::new(config);
+        let server_stats = Arc::new(UdpTProxyServerStats::new(config.name()));
+        let listen_stats = Arc::new(ListenStats::new(config.name()));
+        let flow_table = Arc::new(UdpTProxyFlowTable::default());
+
+        let server = UdpTProxyServer::new(config, server_stats, listen_stats, flow_table, 1)?;
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyServerConfig) -> anyhow::Result<Self> {
+        if let AnyServerConfig::UdpTProxy(config) = config {
+            let config = Arc::new(config);
+            let server_stats = Arc::clone(&self.server_stats);
+            let listen_stats = Arc::clone(&self.listen_stats);
+            // running flows should be kept across reloads
+            let flow_table = Arc::clone(&self.flow_table);
+
+            let server = UdpTProxyServer::new(
+                config,
+                server_stats,
+                listen_stats,
+                flow_table,
+                self.reload_version + 1,
+            )?;
+            Ok(server)
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.r#type(),
+                config.r#type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    self.listen_stats.add_dropped();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn audit_context(&self) -> AuditContext {
+        AuditContext::new(self.audit_handle.load_full())
+    }
+
+    fn spawn_flow_task(&self, key: UdpTProxyFlowKey, packet: &[u8], worker_id: Option<usize>) {
+        let (sender, receiver) = mpsc::channel(self.config.flow_queue_size);
+        let Some(flow_guard) = self
+            .flow_table
+            .register(key, sender, self.config.max_flow_count)
+        else {
+            self.listen_stats.add_dropped();
+            return;
+        };
+        self.server_stats.add_flow();
+
+        let mut cc_info = ClientConnectionInfo::new(key.client, key.target);
+        cc_info.set_worker_id(worker_id);
+        let ctx = CommonTaskContext {
+            server_config: self.config.clone(),
+            server_stats: self.server_stats.clone(),
+            server_quit_policy: self.quit_policy.clone(),
+            idle_wheel: self.idle_wheel.clone(),
+            escaper: self.escaper.load().as_ref().clone(),
+            dst_host_filter: self.dst_host_filter.clone(),
+            cc_info,
+            task_logger: self.task_logger.clone(),
+        };
+
+        let task = UdpTProxyFlowTask::new(ctx, self.audit_context());
+        let first_packet = Bytes::copy_from_slice(packet);
+        tokio::spawn(task.into_running(first_packet, receiver, flow_guard));
+    }
+}
+
+impl ServerInternal for UdpTProxyServer {
+    fn _clone_config(&self) -> AnyServerConfig {
+        AnyServerConfig::UdpTProxy(self.config.as_ref().clone())
+    }
+
+    fn _depend_on_server(&self, _name: &NodeName) -> bool {
+        false
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_next_servers_in_place(&self) {}
+
+    fn _update_escaper_in_place(&self) {
+        let escaper = crate::escape::get_or_insert_default(self.config.escaper());
+        self.escaper.store(Arc::new(escaper));
+    }
+
+    fn _update_user_group_in_place(&self) {}
+
+    fn _update_audit_handle_in_place(&self) -> anyhow::Result<()> {
+        let audit_handle = self.config.get_audit_handle()?;
+        self.audit_handle.store(audit_handle);
+        Ok(())
+    }
+
+    fn _reload_with_old_notifier(
+        &self,
+        config: AnyServerConfig,
+        _registry: &mut ServerRegistry,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(
+        &self,
+        config: AnyServerConfig,
+        _registry: &mut ServerRegistry,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, server: ArcServer) -> anyhow::Result<()> {
+        let runtime = ReceiveUdpRuntime::new(WrapArcServer(server), self.config.listen.clone());
+        runtime
+            .run_all_instances(self.config.listen_in_worker, &self.reload_sender)
+            .map(|_| self.server_stats.set_online())
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+        self.server_stats.set_offline();
+    }
+}
+
+impl BaseServer for UdpTProxyServer {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.r#type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+#[async_trait]
+impl AcceptTcpServer for UdpTProxyServer {
+    async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {}
+}
+
+#[async_trait]
+impl AcceptQuicServer for UdpTProxyServer {
+    #[cfg(feature = "quic")]
+    async fn run_quic_task(&self, _connection: Connection, _cc_info: ClientConnectionInfo) {}
+}
+
+#[async_trait]
+impl Server for UdpTProxyServer {
+    fn escaper(&self) -> &NodeName {
+        self.config.escaper()
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        self.config.auditor()
+    }
+
+    fn get_server_stats(&self) -> Option<ArcServerStats> {
+        Some(self.server_stats.clone())
+    }
+
+    fn get_listen_stats(&self) -> Arc<ListenStats> {
+        Arc::clone(&self.listen_stats)
+    }
+
+    fn alive_count(&self) -> i32 {
+        self.server_stats.get_alive_count()
+    }
+
+    #[inline]
+    fn quit_policy(&self) -> &Arc<ServerQuitPolicy> {
+        &self.quit_policy
+    }
+
+    async fn run_rustls_task(&self, _stream: TlsStream<TcpStream>, _cc_info: ClientConnectionInfo) {
+    }
+
+    async fn run_openssl_task(
+        &self,
+        _stream: SslStream<TcpStream>,
+        _cc_info: ClientConnectionInfo,
+    ) {
+    }
+
+    fn receive_udp_packet(
+        &self,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        // dual stack listen sockets will report ipv4 clients as ipv4-mapped ipv6 addresses
+        let client_addr = SocketAddr::new(client_addr.ip().to_canonical(), client_addr.port());
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        let key = UdpTProxyFlowKey {
+            client: client_addr,
+            target: server_addr,
+        };
+        match self.flow_table.dispatch(&key, packet) {
+            UdpTProxyFlowDispatch::Queued => {}
+            UdpTProxyFlowDispatch::Dropped => self.listen_stats.add_dropped(),
+            UdpTProxyFlowDispatch::NotFound => self.spawn_flow_task(key, packet, worker_id),
+        }
+    }
+}
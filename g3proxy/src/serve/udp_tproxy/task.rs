@@ -0,0 +1,349 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::poll_fn;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use slog::Logger;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+
+use g3_io_ext::{
+    LimitedRecvStats, LimitedUdpRecv, LimitedUdpSend, UdpCopyClientToRemote, UdpCopyError,
+    UdpCopyRemoteRecv, UdpCopyRemoteSend, UdpCopyRemoteToClient, UdpRecvHalf, UdpSendHalf,
+};
+use g3_types::acl::AclAction;
+use g3_types::net::UpstreamAddr;
+
+use super::{
+    CommonTaskContext, UdpTProxyClientRecv, UdpTProxyClientSend, UdpTProxyFlowGuard,
+    UdpTProxyServerAliveTaskGuard, UdpTProxyTaskCltWrapperStats, UdpTProxyTaskStats,
+};
+use crate::audit::AuditContext;
+#[cfg(feature = "quic")]
+use crate::inspect::quic::{QuicFlowInspector, QuicInspectCopyRemoteSend};
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{
+    ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult, ServerTaskStage,
+};
+
+pub(super) struct UdpTProxyFlowTask {
+    ctx: CommonTaskContext,
+    upstream: UpstreamAddr,
+    udp_notes: UdpConnectTaskNotes,
+    task_notes: ServerTaskNotes,
+    task_stats: Arc<UdpTProxyTaskStats>,
+    #[cfg_attr(not(feature = "quic"), allow(unused))]
+    audit_ctx: AuditContext,
+    max_idle_count: usize,
+    _alive_guard: Option<UdpTProxyServerAliveTaskGuard>,
+}
+
+impl UdpTProxyFlowTask {
+    pub(super) fn new(ctx: CommonTaskContext, audit_ctx: AuditContext) -> Self {
+        let target = ctx.cc_info.server_addr();
+        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, Duration::ZERO);
+        let max_idle_count = ctx.server_config.task_idle_max_count;
+        UdpTProxyFlowTask {
+            ctx,
+            upstream: UpstreamAddr::from(target),
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_notes,
+            task_stats: Arc::new(UdpTProxyTaskStats::default()),
+            audit_ctx,
+            max_idle_count,
+            _alive_guard: None,
+        }
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
+        self.ctx
+            .task_logger
+            .as_ref()
+            .map(|logger| TaskLogForUdpConnect {
+                logger,
+                task_notes: &self.task_notes,
+                tcp_server_addr: self.ctx.cc_info.server_addr(),
+                tcp_client_addr: self.ctx.cc_info.client_addr(),
+                udp_listen_addr: Some(self.ctx.cc_info.server_addr()),
+                udp_client_addr: Some(self.ctx.cc_info.client_addr()),
+                upstream: Some(&self.upstream),
+                udp_notes: &self.udp_notes,
+                client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+                client_rd_packets: self.task_stats.clt.recv.get_packets(),
+                client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+                client_wr_packets: self.task_stats.clt.send.get_packets(),
+                remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+                remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+                remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+                remote_wr_packets: self.task_stats.ups.send.get_packets(),
+            })
+    }
+
+    pub(super) async fn into_running(
+        mut self,
+        first_packet: Bytes,
+        queued: mpsc::Receiver<Bytes>,
+        flow_guard: UdpTProxyFlowGuard,
+    ) {
+        self.pre_start();
+        let e = match self.run(first_packet, queued).await {
+            Ok(_) => ServerTaskError::Finished,
+            Err(e) => e,
+        };
+        // stop receiving new packets before logging
+        drop(flow_guard);
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+    }
+
+    fn pre_start(&mut self) {
+        self._alive_guard = Some(self.ctx.server_stats.add_task());
+
+        if self.ctx.server_config.flush_task_log_on_created
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_created();
+        }
+    }
+
+    fn handle_upstream_acl_action(&self, action: AclAction) -> ServerTaskResult<()> {
+        let forbid = match action {
+            AclAction::Permit => false,
+            AclAction::PermitAndLog => {
+                // TODO log permit
+                false
+            }
+            AclAction::Forbid => true,
+            AclAction::ForbidAndLog => {
+                // TODO log forbid
+                true
+            }
+        };
+        if forbid {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::DestDenied,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn run(
+        &mut self,
+        first_packet: Bytes,
+        queued: mpsc::Receiver<Bytes>,
+    ) -> ServerTaskResult<()> {
+        let action = self.ctx.check_upstream(&self.upstream);
+        self.handle_upstream_acl_action(action)?;
+
+        if self.ctx.server_config.inspect_first_packet
+            && g3_dpi::is_dns_udp_request_message(&first_packet)
+        {
+            // DNS flows are short-lived, close them once idle
+            self.max_idle_count = 1;
+        }
+
+        self.task_notes.stage = ServerTaskStage::Preparing;
+        let clt_socket = self.setup_reply_socket()?;
+        let (clt_r, clt_w) = self.split_clt(clt_socket, queued, first_packet.len());
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        let (ups_r, ups_w, escape_logger) = self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await?;
+        let mut ups_w = self.wrap_ups_send(ups_w);
+        self.task_notes.stage = ServerTaskStage::Connected;
+
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        poll_fn(|cx| ups_w.poll_send_packet(cx, &first_packet[..])).await?;
+
+        self.task_notes.mark_relaying();
+        self.run_relay(clt_r, clt_w, ups_r, ups_w, escape_logger)
+            .await
+    }
+
+    fn setup_reply_socket(&self) -> ServerTaskResult<UdpSocket> {
+        let socket = g3_socket::udp::new_std_transparent_bind_connect(
+            self.ctx.cc_info.server_addr(),
+            self.ctx.cc_info.client_addr(),
+            self.ctx.server_config.udp_socket_buffer,
+            self.ctx.server_config.udp_misc_opts,
+        )
+        .map_err(ServerTaskError::ClientUdpSendFailed)?;
+        UdpSocket::from_std(socket).map_err(|_| {
+            ServerTaskError::InternalServerError("failed to setup the client side reply socket")
+        })
+    }
+
+    fn split_clt(
+        &self,
+        clt_socket: UdpSocket,
+        queued: mpsc::Receiver<Bytes>,
+        first_packet_len: usize,
+    ) -> (
+        UdpTProxyClientRecv<LimitedUdpRecv<UdpRecvHalf>>,
+        UdpTProxyClientSend<LimitedUdpSend<UdpSendHalf>>,
+    ) {
+        let (clt_r, clt_w) = g3_io_ext::split_udp(clt_socket);
+
+        let limit_config = &self.ctx.server_config.udp_sock_speed_limit;
+        let wrapper_stats =
+            UdpTProxyTaskCltWrapperStats::new(&self.ctx.server_stats, &self.task_stats);
+        // the first packet has already been received by the listen socket
+        wrapper_stats.add_recv_packets(1);
+        wrapper_stats.add_recv_bytes(first_packet_len);
+
+        let clt_r = LimitedUdpRecv::local_limited(
+            clt_r,
+            limit_config.shift_millis,
+            limit_config.max_north_packets,
+            limit_config.max_north_bytes,
+            Arc::new(wrapper_stats.clone()),
+        );
+        let clt_w = LimitedUdpSend::local_limited(
+            clt_w,
+            limit_config.shift_millis,
+            limit_config.max_south_packets,
+            limit_config.max_south_bytes,
+            Arc::new(wrapper_stats.clone()),
+        );
+
+        (
+            UdpTProxyClientRecv::new(clt_r, queued, wrapper_stats),
+            UdpTProxyClientSend::new(clt_w),
+        )
+    }
+
+    #[cfg(feature = "quic")]
+    fn wrap_ups_send(
+        &self,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+    ) -> Box<dyn UdpCopyRemoteSend + Unpin + Send> {
+        if let Some(audit_handle) = self.audit_ctx.handle()
+            && let Some(inspector) = QuicFlowInspector::new_for_task(audit_handle, &self.task_notes)
+        {
+            return Box::new(QuicInspectCopyRemoteSend::new(
+                ups_w,
+                self.upstream.clone(),
+                inspector,
+            ));
+        }
+        ups_w
+    }
+
+    #[cfg(not(feature = "quic"))]
+    fn wrap_ups_send(
+        &self,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+    ) -> Box<dyn UdpCopyRemoteSend + Unpin + Send> {
+        ups_w
+    }
+
+    async fn run_relay(
+        &mut self,
+        mut clt_r: UdpTProxyClientRecv<LimitedUdpRecv<UdpRecvHalf>>,
+        mut clt_w: UdpTProxyClientSend<LimitedUdpSend<UdpSendHalf>>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+        escape_logger: Option<Logger>,
+    ) -> ServerTaskResult<()> {
+        let task_id = &self.task_notes.id;
+
+        let mut c_to_r =
+            UdpCopyClientToRemote::new(&mut clt_r, &mut *ups_w, self.ctx.server_config.udp_relay);
+        let mut r_to_c =
+            UdpCopyRemoteToClient::new(&mut clt_w, &mut *ups_r, self.ctx.server_config.udp_relay);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut c_to_r => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = escape_logger {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(&logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                r = &mut r_to_c => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = escape_logger {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(&logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if c_to_r.is_idle() && r_to_c.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        c_to_r.reset_active();
+                        r_to_c.reset_active();
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -22,6 +22,9 @@ async fn do_reload() {
     if let Err(e) = crate::resolve::spawn_all().await {
         error!("failed to reload all resolvers: {e:?}");
     }
+    if let Err(e) = crate::category::load_all().await {
+        error!("failed to reload all categories: {e:?}");
+    }
     if let Err(e) = crate::escape::load_all().await {
         error!("failed to reload all escapers: {e:?}");
     }
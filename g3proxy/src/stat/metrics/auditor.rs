@@ -0,0 +1,81 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use g3_daemon::metrics::TAG_KEY_STAT_ID;
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::stats::GlobalStatsMap;
+
+use crate::audit::{TlsAutoBypass, TlsAutoBypassSnapshot};
+
+const TAG_KEY_AUDITOR: &str = "auditor";
+
+const METRIC_NAME_TLS_AUTO_BYPASS_ADDED: &str = "auditor.tls.auto_bypass.added";
+const METRIC_NAME_TLS_AUTO_BYPASS_HIT: &str = "auditor.tls.auto_bypass.hit";
+const METRIC_NAME_TLS_AUTO_BYPASS_ACTIVE: &str = "auditor.tls.auto_bypass.active";
+
+type TlsAutoBypassStatsValue = (Arc<TlsAutoBypass>, TlsAutoBypassSnapshot);
+
+static TLS_AUTO_BYPASS_STATS_MAP: Mutex<GlobalStatsMap<TlsAutoBypassStatsValue>> =
+    Mutex::new(GlobalStatsMap::new());
+
+pub(in crate::stat) fn sync_stats() {
+    let mut stats_map = TLS_AUTO_BYPASS_STATS_MAP.lock().unwrap();
+    crate::audit::foreach_auditor(|_, auditor| {
+        if let Some(auto_bypass) = auditor.tls_auto_bypass() {
+            stats_map.get_or_insert_with(auto_bypass.stat_id(), || {
+                (auto_bypass.clone(), TlsAutoBypassSnapshot::default())
+            });
+        }
+    });
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut stats_map = TLS_AUTO_BYPASS_STATS_MAP.lock().unwrap();
+    stats_map.retain(|(auto_bypass, snap)| {
+        emit_tls_auto_bypass_stats(client, auto_bypass, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(auto_bypass) > 1
+    });
+}
+
+fn emit_tls_auto_bypass_stats(
+    client: &mut StatsdClient,
+    auto_bypass: &TlsAutoBypass,
+    snap: &mut TlsAutoBypassSnapshot,
+) {
+    let mut common_tags = StatsdTagGroup::default();
+    let mut buffer = itoa::Buffer::new();
+    let stat_id = buffer.format(auto_bypass.stat_id().as_u64());
+    common_tags.add_tag(TAG_KEY_AUDITOR, auto_bypass.name());
+    common_tags.add_tag(TAG_KEY_STAT_ID, stat_id);
+
+    let new_snap = auto_bypass.snapshot();
+
+    macro_rules! emit_count {
+        ($id:ident, $name:expr) => {
+            let new_value = new_snap.$id;
+            if new_value != 0 || snap.$id != 0 {
+                let diff_value = new_value.wrapping_sub(snap.$id);
+                client
+                    .count_with_tags($name, diff_value, &common_tags)
+                    .send();
+                snap.$id = new_value;
+            }
+        };
+    }
+
+    emit_count!(added, METRIC_NAME_TLS_AUTO_BYPASS_ADDED);
+    emit_count!(hit, METRIC_NAME_TLS_AUTO_BYPASS_HIT);
+
+    client
+        .gauge_with_tags(
+            METRIC_NAME_TLS_AUTO_BYPASS_ACTIVE,
+            auto_bypass.bypassed_count(),
+            &common_tags,
+        )
+        .send();
+}
@@ -0,0 +1,71 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use g3_daemon::metrics::TAG_KEY_STAT_ID;
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::stats::GlobalStatsMap;
+
+use crate::category::{CategoryStats, CategoryStatsSnapshot};
+
+const TAG_KEY_CATEGORY: &str = "category";
+
+const METRIC_NAME_CATEGORY_MATCHED: &str = "category.matched";
+const METRIC_NAME_CATEGORY_FORBIDDEN: &str = "category.forbidden";
+
+type CategoryStatsValue = (Arc<CategoryStats>, CategoryStatsSnapshot);
+
+static CATEGORY_STATS_MAP: Mutex<GlobalStatsMap<CategoryStatsValue>> =
+    Mutex::new(GlobalStatsMap::new());
+
+pub(in crate::stat) fn sync_stats() {
+    let mut stats_map = CATEGORY_STATS_MAP.lock().unwrap();
+    crate::category::foreach_category(|_, category| {
+        let stats = category.stats();
+        stats_map.get_or_insert_with(stats.stat_id(), || {
+            (stats.clone(), CategoryStatsSnapshot::default())
+        });
+    });
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut stats_map = CATEGORY_STATS_MAP.lock().unwrap();
+    stats_map.retain(|(stats, snap)| {
+        emit_category_stats(client, stats, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(stats) > 1
+    });
+}
+
+fn emit_category_stats(
+    client: &mut StatsdClient,
+    stats: &CategoryStats,
+    snap: &mut CategoryStatsSnapshot,
+) {
+    let mut common_tags = StatsdTagGroup::default();
+    let mut buffer = itoa::Buffer::new();
+    let stat_id = buffer.format(stats.stat_id().as_u64());
+    common_tags.add_tag(TAG_KEY_CATEGORY, stats.name());
+    common_tags.add_tag(TAG_KEY_STAT_ID, stat_id);
+
+    let new_snap = stats.snapshot();
+
+    macro_rules! emit_count {
+        ($id:ident, $name:expr) => {
+            let new_value = new_snap.$id;
+            if new_value != 0 || snap.$id != 0 {
+                let diff_value = new_value.wrapping_sub(snap.$id);
+                client
+                    .count_with_tags($name, diff_value, &common_tags)
+                    .send();
+                snap.$id = new_value;
+            }
+        };
+    }
+
+    emit_count!(matched, METRIC_NAME_CATEGORY_MATCHED);
+    emit_count!(forbidden, METRIC_NAME_CATEGORY_FORBIDDEN);
+}
@@ -3,6 +3,8 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+pub(super) mod auditor;
+pub(super) mod category;
 pub(super) mod escaper;
 pub(super) mod resolver;
 pub(super) mod server;
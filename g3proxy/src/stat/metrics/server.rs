@@ -12,6 +12,7 @@ use g3_daemon::metrics::{
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::{GlobalStatsMap, TcpIoSnapshot, UdpIoSnapshot};
 
+use crate::module::http_cache::HttpCacheStatsSnapshot;
 use crate::serve::{ArcServerStats, ServerForbiddenSnapshot};
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -28,6 +29,10 @@ const METRIC_NAME_SERVER_IO_OUT_PACKETS: &str = "server.traffic.out.packets";
 const METRIC_NAME_SERVER_UNTRUSTED_TASK_TOTAL: &str = "server.task.untrusted_total";
 const METRIC_NAME_SERVER_UNTRUSTED_TASK_ALIVE: &str = "server.task.untrusted_alive";
 const METRIC_NAME_SERVER_IO_UNTRUSTED_IN_BYTES: &str = "server.traffic.untrusted_in.bytes";
+const METRIC_NAME_SERVER_HTTP_CACHE_HIT: &str = "server.http_cache.hit";
+const METRIC_NAME_SERVER_HTTP_CACHE_MISS: &str = "server.http_cache.miss";
+const METRIC_NAME_SERVER_HTTP_CACHE_REVALIDATED: &str = "server.http_cache.revalidated";
+const METRIC_NAME_SERVER_HTTP_CACHE_STORED: &str = "server.http_cache.stored";
 
 type ServerStatsValue = (ArcServerStats, ServerSnapshot);
 type ListenStatsValue = (Arc<ListenStats>, ListenSnapshot);
@@ -45,6 +50,7 @@ struct ServerSnapshot {
     tcp: TcpIoSnapshot,
     udp: UdpIoSnapshot,
     untrusted: UntrustedTaskStatsSnapshot,
+    http_cache: HttpCacheStatsSnapshot,
 }
 
 pub(in crate::stat) fn sync_stats() {
@@ -129,6 +135,10 @@ fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &m
     if let Some(untrusted_stats) = stats.untrusted_snapshot() {
         emit_untrusted_stats(client, untrusted_stats, &mut snap.untrusted, &common_tags);
     }
+
+    if let Some(http_cache_stats) = stats.http_cache_snapshot() {
+        emit_http_cache_stats(client, http_cache_stats, &mut snap.http_cache, &common_tags);
+    }
 }
 
 fn emit_forbidden_stats(
@@ -249,3 +259,30 @@ fn emit_untrusted_stats(
         .send();
     snap.in_bytes = new_value;
 }
+
+fn emit_http_cache_stats(
+    client: &mut StatsdClient,
+    stats: HttpCacheStatsSnapshot,
+    snap: &mut HttpCacheStatsSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    if stats.hit == 0 && stats.miss == 0 && snap.hit == 0 && snap.miss == 0 {
+        return;
+    }
+
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            let new_value = stats.$field;
+            let diff_value = new_value.wrapping_sub(snap.$field);
+            client
+                .count_with_tags($name, diff_value, common_tags)
+                .send();
+            snap.$field = new_value;
+        };
+    }
+
+    emit_field!(hit, METRIC_NAME_SERVER_HTTP_CACHE_HIT);
+    emit_field!(miss, METRIC_NAME_SERVER_HTTP_CACHE_MISS);
+    emit_field!(revalidated, METRIC_NAME_SERVER_HTTP_CACHE_REVALIDATED);
+    emit_field!(stored, METRIC_NAME_SERVER_HTTP_CACHE_STORED);
+}
@@ -41,12 +41,16 @@ fn spawn_main_thread(config: &StatsdClientConfig) -> anyhow::Result<JoinHandle<(
                 metrics::server::sync_stats();
                 metrics::escaper::sync_stats();
                 metrics::resolver::sync_stats();
+                metrics::auditor::sync_stats();
+                metrics::category::sync_stats();
                 metrics::user::sync_stats();
                 g3_daemon::log::metrics::sync_stats();
 
                 metrics::server::emit_stats(&mut client);
                 metrics::escaper::emit_stats(&mut client);
                 metrics::resolver::emit_stats(&mut client);
+                metrics::auditor::emit_stats(&mut client);
+                metrics::category::emit_stats(&mut client);
                 metrics::user::emit_stats(&mut client);
                 g3_daemon::runtime::metrics::emit_stats(&mut client);
                 g3_daemon::log::metrics::emit_stats(&mut client);
@@ -0,0 +1,44 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use super::{SpanData, SpanKind, TraceContext};
+use crate::module::tcp_connect::{TcpConnectStageTime, TcpConnectTaskNotes};
+use crate::serve::ServerTaskNotes;
+
+/// Emit the DNS resolution, TCP connect and TLS handshake spans of the upstream connection
+pub(super) fn emit_stage_spans(
+    parent: &TraceContext,
+    task_notes: &ServerTaskNotes,
+    tcp_notes: &TcpConnectTaskNotes,
+) {
+    let base = task_notes.task_created_instant();
+    let new_stage_span = |name: &str, time: &TcpConnectStageTime| -> SpanData {
+        let start = time.start.saturating_duration_since(base);
+        let ctx = parent.new_child();
+        SpanData::new(
+            &ctx,
+            name.to_string(),
+            SpanKind::Client,
+            &task_notes.start_at,
+            start,
+            start + time.spend,
+        )
+    };
+
+    if let Some(time) = &tcp_notes.resolve_time {
+        super::emit(new_stage_span("dns resolve", time));
+    }
+    if let Some(time) = &tcp_notes.connect_time {
+        let mut span = new_stage_span("tcp connect", time);
+        if let Some(next) = tcp_notes.next {
+            span.add_attr("network.peer.address", next.to_string());
+        }
+        span.add_attr("connect.tries", tcp_notes.tries);
+        super::emit(span);
+    }
+    if let Some(time) = &tcp_notes.tls_time {
+        super::emit(new_stage_span("tls handshake", time));
+    }
+}
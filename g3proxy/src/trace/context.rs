@@ -0,0 +1,160 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use g3_types::net::HttpHeaderMap;
+
+pub(crate) const TRACEPARENT: &str = "traceparent";
+
+const TRACE_FLAG_SAMPLED: u8 = 0x01;
+
+/// W3C trace context of the span created for a task
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TraceContext {
+    pub(crate) trace_id: u128,
+    pub(crate) span_id: u64,
+    pub(crate) parent_span_id: Option<u64>,
+    pub(crate) flags: u8,
+}
+
+fn new_trace_id() -> u128 {
+    fastrand::u128(1..)
+}
+
+fn new_span_id() -> u64 {
+    fastrand::u64(1..)
+}
+
+impl TraceContext {
+    pub(crate) fn new_root() -> Self {
+        TraceContext {
+            trace_id: new_trace_id(),
+            span_id: new_span_id(),
+            parent_span_id: None,
+            flags: TRACE_FLAG_SAMPLED,
+        }
+    }
+
+    /// Continue the trace from the `traceparent` header in the request if present,
+    /// or start a new trace
+    pub(crate) fn from_http_headers(headers: &HttpHeaderMap) -> Self {
+        headers
+            .get(TRACEPARENT)
+            .and_then(|v| Self::parse_traceparent(v.to_str()))
+            .map(|remote| remote.new_child())
+            .unwrap_or_else(Self::new_root)
+    }
+
+    pub(crate) fn new_child(&self) -> Self {
+        TraceContext {
+            trace_id: self.trace_id,
+            span_id: new_span_id(),
+            parent_span_id: Some(self.span_id),
+            flags: self.flags,
+        }
+    }
+
+    pub(crate) fn sampled(&self) -> bool {
+        self.flags & TRACE_FLAG_SAMPLED != 0
+    }
+
+    fn parse_traceparent(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let mut parts = value.splitn(5, '-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        if version.len() != 2 || trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+
+        if !is_lower_hex(version) {
+            return None;
+        }
+        let version = u8::from_str_radix(version, 16).ok()?;
+        match version {
+            0xff => return None,
+            0x00 if parts.next().is_some() => return None,
+            _ => {}
+        }
+
+        if !is_lower_hex(trace_id) || !is_lower_hex(span_id) || !is_lower_hex(flags) {
+            return None;
+        }
+        let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+        let span_id = u64::from_str_radix(span_id, 16).ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        if trace_id == 0 || span_id == 0 {
+            return None;
+        }
+
+        Some(TraceContext {
+            trace_id,
+            span_id,
+            parent_span_id: None,
+            flags,
+        })
+    }
+
+    pub(crate) fn traceparent(&self) -> String {
+        format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.span_id, self.flags
+        )
+    }
+}
+
+fn is_lower_hex(s: &str) -> bool {
+    s.bytes()
+        .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid() {
+        let ctx = TraceContext::parse_traceparent(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .unwrap();
+        assert_eq!(ctx.trace_id, 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert_eq!(ctx.span_id, 0x00f067aa0ba902b7);
+        assert!(ctx.sampled());
+        assert_eq!(
+            ctx.traceparent(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+
+        let child = ctx.new_child();
+        assert_eq!(child.trace_id, ctx.trace_id);
+        assert_eq!(child.parent_span_id, Some(ctx.span_id));
+        assert_ne!(child.span_id, ctx.span_id);
+
+        // future versions may have more fields
+        let ctx = TraceContext::parse_traceparent(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra",
+        )
+        .unwrap();
+        assert!(!ctx.sampled());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+        ] {
+            assert!(TraceContext::parse_traceparent(s).is_none(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use g3_types::net::UpstreamAddr;
+
+use super::{SpanData, SpanKind, TraceContext};
+use crate::module::http_forward::HttpForwardTaskNotes;
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+use crate::serve::{ServerTaskError, ServerTaskNotes};
+
+pub(crate) struct TaskSpanForHttpForward<'a> {
+    pub(crate) trace_ctx: &'a TraceContext,
+    pub(crate) upstream: &'a UpstreamAddr,
+    pub(crate) is_https: bool,
+    pub(crate) task_notes: &'a ServerTaskNotes,
+    pub(crate) http_notes: &'a HttpForwardTaskNotes,
+    pub(crate) tcp_notes: &'a TcpConnectTaskNotes,
+}
+
+impl TaskSpanForHttpForward<'_> {
+    fn new_span(
+        &self,
+        ctx: &TraceContext,
+        name: &str,
+        kind: SpanKind,
+        start: Duration,
+        end: Duration,
+    ) -> SpanData {
+        SpanData::new(
+            ctx,
+            name.to_string(),
+            kind,
+            &self.task_notes.start_at,
+            start,
+            end,
+        )
+    }
+
+    fn emit_child(&self, name: &str, kind: SpanKind, start: Duration, end: Duration) {
+        if end > start {
+            let ctx = self.trace_ctx.new_child();
+            super::emit(self.new_span(&ctx, name, kind, start, end));
+        }
+    }
+
+    pub(crate) fn emit(&self, e: &ServerTaskError) {
+        if !self.trace_ctx.sampled() {
+            return;
+        }
+
+        let time_elapsed = self.task_notes.time_elapsed();
+        let mut span = self.new_span(
+            self.trace_ctx,
+            &format!("HTTP {}", self.http_notes.method),
+            SpanKind::Server,
+            Duration::ZERO,
+            time_elapsed,
+        );
+        span.add_attr("task.id", self.task_notes.id.to_string());
+        span.add_attr("http.request.method", self.http_notes.method.as_str());
+        span.add_attr("url.full", self.http_notes.uri.to_string());
+        span.add_attr("server.address", self.upstream.to_string());
+        span.add_attr("client.address", self.task_notes.client_addr().to_string());
+        if let Some(user) = self.task_notes.raw_user_name() {
+            span.add_attr("user.name", user.as_ref());
+        }
+        if self.http_notes.rsp_status > 0 {
+            span.add_attr("http.response.status_code", self.http_notes.rsp_status);
+        }
+        span.add_attr("connection.reused", self.http_notes.reused_connection);
+        match e {
+            ServerTaskError::Finished | ServerTaskError::ClosedByClient => {}
+            _ => span.set_error(e.brief().to_string()),
+        }
+        super::emit(span);
+
+        if let Some(connect_start) = self.http_notes.dur_ups_connect_start {
+            let connect_end = if self.task_notes.ready_time > connect_start {
+                self.task_notes.ready_time
+            } else {
+                time_elapsed
+            };
+            let ctx = self.trace_ctx.new_child();
+            let mut span = self.new_span(
+                &ctx,
+                "upstream connect",
+                SpanKind::Client,
+                connect_start,
+                connect_end,
+            );
+            span.add_attr("escaper", self.tcp_notes.escaper.as_str());
+            if let Some(next) = self.tcp_notes.next {
+                span.add_attr("network.peer.address", next.to_string());
+            }
+            span.add_attr("connect.tries", self.tcp_notes.tries);
+            span.add_attr("tls", self.is_https);
+            if self.task_notes.ready_time <= connect_start {
+                span.set_error("ConnectFailed".to_string());
+            }
+            super::emit(span);
+
+            if !self.http_notes.reused_connection {
+                super::connect::emit_stage_spans(&ctx, self.task_notes, self.tcp_notes);
+            }
+        }
+
+        if let Some((start, end)) = self.http_notes.dur_icap_reqmod {
+            self.emit_child("icap reqmod", SpanKind::Client, start, end);
+        }
+        if let Some((start, end)) = self.http_notes.dur_icap_respmod {
+            self.emit_child("icap respmod", SpanKind::Client, start, end);
+        }
+
+        let ready_time = self.task_notes.ready_time;
+        if ready_time.is_zero() {
+            return;
+        }
+        self.emit_child(
+            "send request",
+            SpanKind::Internal,
+            ready_time,
+            self.http_notes.dur_req_send_all,
+        );
+        self.emit_child(
+            "wait response",
+            SpanKind::Internal,
+            self.http_notes.dur_req_send_all,
+            self.http_notes.dur_rsp_recv_hdr,
+        );
+        self.emit_child(
+            "recv response",
+            SpanKind::Internal,
+            self.http_notes.dur_rsp_recv_hdr,
+            self.http_notes.dur_rsp_recv_all,
+        );
+    }
+}
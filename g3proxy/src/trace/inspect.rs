@@ -0,0 +1,82 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::{Method, Uri};
+use uuid::Uuid;
+
+use super::{SpanData, SpanKind, TraceContext};
+
+/// Span for HTTP requests forwarded inside the inspected streams
+pub(crate) struct InspectSpanForHttpForward<'a> {
+    pub(crate) trace_ctx: &'a TraceContext,
+    pub(crate) task_id: &'a Uuid,
+    pub(crate) depth: usize,
+    pub(crate) protocol_version: &'static str,
+    pub(crate) method: &'a Method,
+    pub(crate) uri: &'a Uri,
+    pub(crate) started_at: &'a DateTime<Utc>,
+    pub(crate) rsp_status: u16,
+    pub(crate) dur_req_send_all: Duration,
+    pub(crate) dur_rsp_recv_hdr: Duration,
+    pub(crate) dur_rsp_recv_all: Duration,
+}
+
+impl InspectSpanForHttpForward<'_> {
+    fn emit_child(&self, name: &str, start: Duration, end: Duration) {
+        if end > start {
+            let ctx = self.trace_ctx.new_child();
+            super::emit(SpanData::new(
+                &ctx,
+                name.to_string(),
+                SpanKind::Internal,
+                self.started_at,
+                start,
+                end,
+            ));
+        }
+    }
+
+    pub(crate) fn emit(&self, elapsed: Duration, error: Option<&str>) {
+        if !self.trace_ctx.sampled() {
+            return;
+        }
+
+        let mut span = SpanData::new(
+            self.trace_ctx,
+            format!("HTTP {}", self.method),
+            SpanKind::Server,
+            self.started_at,
+            Duration::ZERO,
+            elapsed,
+        );
+        span.add_attr("task.id", self.task_id.to_string());
+        span.add_attr("inspect.depth", self.depth);
+        span.add_attr("network.protocol.name", "http");
+        span.add_attr("network.protocol.version", self.protocol_version);
+        span.add_attr("http.request.method", self.method.as_str());
+        span.add_attr("url.full", self.uri.to_string());
+        if self.rsp_status > 0 {
+            span.add_attr("http.response.status_code", self.rsp_status);
+        }
+        if let Some(e) = error {
+            span.set_error(e.to_string());
+        }
+        super::emit(span);
+
+        self.emit_child(
+            "wait response",
+            self.dur_req_send_all,
+            self.dur_rsp_recv_hdr,
+        );
+        self.emit_child(
+            "recv response",
+            self.dur_rsp_recv_hdr,
+            self.dur_rsp_recv_all,
+        );
+    }
+}
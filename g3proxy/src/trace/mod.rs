@@ -0,0 +1,67 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+
+use anyhow::{Context, anyhow};
+
+mod context;
+pub(crate) use context::{TRACEPARENT, TraceContext};
+
+mod span;
+pub(crate) use span::{SpanData, SpanKind};
+
+mod otlp;
+use otlp::OtlpHttpExporter;
+
+mod connect;
+
+pub(crate) mod http_forward;
+pub(crate) mod inspect;
+pub(crate) mod tcp_connect;
+
+static QUIT_TRACE_THREAD: AtomicBool = AtomicBool::new(false);
+static SPAN_SENDER: OnceLock<kanal::Sender<SpanData>> = OnceLock::new();
+
+/// Check if the trace exporter is running
+pub(crate) fn enabled() -> bool {
+    SPAN_SENDER.get().is_some()
+}
+
+/// Send the finished span to the exporter, it will be dropped if the queue is full
+pub(crate) fn emit(span: SpanData) {
+    if let Some(sender) = SPAN_SENDER.get() {
+        let _ = sender.try_send(span);
+    }
+}
+
+pub fn spawn_working_thread() -> anyhow::Result<Option<JoinHandle<()>>> {
+    let Some(config) = crate::config::trace::get_global_trace_config() else {
+        return Ok(None);
+    };
+
+    let (sender, receiver) = kanal::bounded(config.channel_size);
+    let mut exporter = OtlpHttpExporter::new(config).context("invalid trace config")?;
+    let handle = std::thread::Builder::new()
+        .name("trace-export".to_string())
+        .spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(exporter.run(receiver.clone_async(), &QUIT_TRACE_THREAD));
+        })
+        .map_err(|e| anyhow!("failed to spawn thread: {e:?}"))?;
+    if SPAN_SENDER.set(sender).is_err() {
+        return Err(anyhow!("trace exporter has already been started"));
+    }
+    Ok(Some(handle))
+}
+
+pub fn stop_working_thread() {
+    QUIT_TRACE_THREAD.store(true, Ordering::Relaxed);
+}
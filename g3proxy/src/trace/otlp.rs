@@ -0,0 +1,208 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, anyhow};
+use log::warn;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use g3_openssl::SslConnector;
+use g3_types::net::{Host, UpstreamAddr};
+
+use super::SpanData;
+use crate::config::trace::TraceConfig;
+
+pub(super) struct OtlpHttpExporter {
+    config: TraceConfig,
+    upstream: UpstreamAddr,
+    tls_name: Host,
+    request_header_prefix: String,
+    batch: Vec<SpanData>,
+}
+
+impl OtlpHttpExporter {
+    pub(super) fn new(config: TraceConfig) -> anyhow::Result<Self> {
+        let upstream = UpstreamAddr::try_from(&config.collector)?;
+        let tls_name = config
+            .tls_name
+            .clone()
+            .unwrap_or_else(|| upstream.host().clone());
+        let url = &config.collector;
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let host = url.host_str().unwrap_or_default();
+        let host = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        let request_header_prefix = format!(
+            "POST {path} HTTP/1.1\r\n\
+             Host: {host}\r\n\
+             Content-Type: application/json\r\n\
+             Connection: close\r\n"
+        );
+        let batch = Vec::with_capacity(config.batch_size);
+        Ok(OtlpHttpExporter {
+            config,
+            upstream,
+            tls_name,
+            request_header_prefix,
+            batch,
+        })
+    }
+
+    pub(super) async fn run(
+        &mut self,
+        receiver: kanal::AsyncReceiver<SpanData>,
+        quit: &AtomicBool,
+    ) {
+        loop {
+            match tokio::time::timeout(self.config.flush_interval, receiver.recv()).await {
+                Ok(Ok(span)) => {
+                    self.batch.push(span);
+                    if self.batch.len() >= self.config.batch_size {
+                        self.flush().await;
+                    }
+                }
+                Ok(Err(_)) => break,
+                Err(_) => self.flush().await,
+            }
+
+            if quit.load(Ordering::Relaxed) {
+                while let Ok(Some(span)) = receiver.try_recv() {
+                    self.batch.push(span);
+                    if self.batch.len() >= self.config.batch_size {
+                        self.flush().await;
+                    }
+                }
+                break;
+            }
+        }
+        self.flush().await;
+    }
+
+    async fn flush(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+
+        let body = self.encode_batch().to_string();
+        self.batch.clear();
+        let r = match tokio::time::timeout(self.config.timeout, self.send(body.as_bytes())).await {
+            Ok(r) => r,
+            Err(_) => Err(anyhow!("timed out")),
+        };
+        if let Err(e) = r {
+            warn!("failed to export spans to {}: {e:?}", self.config.collector);
+        }
+    }
+
+    fn encode_batch(&self) -> Value {
+        let spans = self
+            .batch
+            .iter()
+            .map(|span| span.to_otlp_json())
+            .collect::<Vec<_>>();
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [{
+                        "key": "service.name",
+                        "value": {"stringValue": self.config.service_name},
+                    }, {
+                        "key": "service.version",
+                        "value": {"stringValue": crate::build::VERSION},
+                    }],
+                },
+                "scopeSpans": [{
+                    "scope": {
+                        "name": crate::build::PKG_NAME,
+                        "version": crate::build::VERSION,
+                    },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+
+    async fn connect(&self) -> anyhow::Result<TcpStream> {
+        let peers = tokio::net::lookup_host(self.upstream.to_string())
+            .await
+            .map_err(|e| anyhow!("failed to resolve {}: {e}", self.upstream))?
+            .collect::<Vec<SocketAddr>>();
+        let mut last_err = anyhow!("no address resolved for {}", self.upstream);
+        for peer in peers {
+            match TcpStream::connect(peer).await {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_err = anyhow!("failed to connect to {peer}: {e}"),
+            }
+        }
+        Err(last_err)
+    }
+
+    async fn send(&self, body: &[u8]) -> anyhow::Result<()> {
+        let stream = self.connect().await?;
+        match &self.config.tls_client {
+            Some(tls_client) => {
+                let ssl = tls_client
+                    .build_ssl(&self.tls_name, self.upstream.port())
+                    .context("failed to build ssl context")?;
+                let connector = SslConnector::new(ssl, stream)
+                    .map_err(|e| anyhow!("failed to create tls connector: {e}"))?;
+                let stream = connector
+                    .connect()
+                    .await
+                    .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+                self.send_request(stream, body).await
+            }
+            None => self.send_request(stream, body).await,
+        }
+    }
+
+    async fn send_request<S>(&self, mut stream: S, body: &[u8]) -> anyhow::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let header = format!(
+            "{}Content-Length: {}\r\n\r\n",
+            self.request_header_prefix,
+            body.len()
+        );
+        stream
+            .write_all(header.as_bytes())
+            .await
+            .map_err(|e| anyhow!("failed to write request header: {e}"))?;
+        stream
+            .write_all(body)
+            .await
+            .map_err(|e| anyhow!("failed to write request body: {e}"))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| anyhow!("failed to flush request: {e}"))?;
+
+        let mut status_line = String::new();
+        BufReader::new(stream)
+            .read_line(&mut status_line)
+            .await
+            .map_err(|e| anyhow!("failed to read response: {e}"))?;
+        let code = status_line
+            .split_ascii_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse::<u16>().ok())
+            .ok_or_else(|| anyhow!("invalid response status line"))?;
+        if (200..300).contains(&code) {
+            Ok(())
+        } else {
+            Err(anyhow!("unexpected response code {code}"))
+        }
+    }
+}
@@ -0,0 +1,165 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value, json};
+
+use super::TraceContext;
+
+#[derive(Clone, Copy)]
+pub(crate) enum SpanKind {
+    Internal,
+    Server,
+    Client,
+}
+
+impl SpanKind {
+    /// the value defined in the OTLP protocol
+    fn as_otlp_value(&self) -> u8 {
+        match self {
+            SpanKind::Internal => 1,
+            SpanKind::Server => 2,
+            SpanKind::Client => 3,
+        }
+    }
+}
+
+pub(crate) enum SpanAttrValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl From<String> for SpanAttrValue {
+    fn from(value: String) -> Self {
+        SpanAttrValue::Str(value)
+    }
+}
+
+impl From<&str> for SpanAttrValue {
+    fn from(value: &str) -> Self {
+        SpanAttrValue::Str(value.to_string())
+    }
+}
+
+impl From<u16> for SpanAttrValue {
+    fn from(value: u16) -> Self {
+        SpanAttrValue::Int(value as i64)
+    }
+}
+
+impl From<usize> for SpanAttrValue {
+    fn from(value: usize) -> Self {
+        SpanAttrValue::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<bool> for SpanAttrValue {
+    fn from(value: bool) -> Self {
+        SpanAttrValue::Bool(value)
+    }
+}
+
+impl SpanAttrValue {
+    fn to_otlp_json(&self) -> Value {
+        match self {
+            SpanAttrValue::Str(s) => json!({"stringValue": s}),
+            // int64 values are encoded as decimal strings in OTLP/JSON
+            SpanAttrValue::Int(i) => json!({"intValue": i.to_string()}),
+            SpanAttrValue::Bool(b) => json!({"boolValue": b}),
+        }
+    }
+}
+
+pub(crate) struct SpanData {
+    trace_id: u128,
+    span_id: u64,
+    parent_span_id: Option<u64>,
+    name: String,
+    kind: SpanKind,
+    start_unix_nano: u64,
+    end_unix_nano: u64,
+    attributes: Vec<(&'static str, SpanAttrValue)>,
+    error: Option<String>,
+}
+
+fn unix_nano(time: &DateTime<Utc>, offset: Duration) -> u64 {
+    let base = time.timestamp_nanos_opt().unwrap_or_default().max(0) as u64;
+    base.saturating_add(offset.as_nanos().min(u64::MAX as u128) as u64)
+}
+
+impl SpanData {
+    /// Create a new span, the start and end time are set as offsets to the `base` time
+    pub(crate) fn new(
+        ctx: &TraceContext,
+        name: String,
+        kind: SpanKind,
+        base: &DateTime<Utc>,
+        start: Duration,
+        end: Duration,
+    ) -> Self {
+        SpanData {
+            trace_id: ctx.trace_id,
+            span_id: ctx.span_id,
+            parent_span_id: ctx.parent_span_id,
+            name,
+            kind,
+            start_unix_nano: unix_nano(base, start),
+            end_unix_nano: unix_nano(base, end.max(start)),
+            attributes: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub(crate) fn add_attr<V: Into<SpanAttrValue>>(&mut self, key: &'static str, value: V) {
+        self.attributes.push((key, value.into()));
+    }
+
+    pub(crate) fn set_error(&mut self, message: String) {
+        self.error = Some(message);
+    }
+
+    pub(super) fn to_otlp_json(&self) -> Value {
+        let mut map = Map::with_capacity(10);
+        map.insert(
+            "traceId".to_string(),
+            Value::String(format!("{:032x}", self.trace_id)),
+        );
+        map.insert(
+            "spanId".to_string(),
+            Value::String(format!("{:016x}", self.span_id)),
+        );
+        if let Some(parent) = self.parent_span_id {
+            map.insert(
+                "parentSpanId".to_string(),
+                Value::String(format!("{parent:016x}")),
+            );
+        }
+        map.insert("name".to_string(), Value::String(self.name.clone()));
+        map.insert("kind".to_string(), json!(self.kind.as_otlp_value()));
+        map.insert(
+            "startTimeUnixNano".to_string(),
+            Value::String(self.start_unix_nano.to_string()),
+        );
+        map.insert(
+            "endTimeUnixNano".to_string(),
+            Value::String(self.end_unix_nano.to_string()),
+        );
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(k, v)| json!({"key": k, "value": v.to_otlp_json()}))
+            .collect::<Vec<_>>();
+        map.insert("attributes".to_string(), Value::Array(attributes));
+        let status = match &self.error {
+            Some(message) => json!({"code": 2, "message": message}),
+            None => json!({"code": 1}),
+        };
+        map.insert("status".to_string(), status);
+        Value::Object(map)
+    }
+}
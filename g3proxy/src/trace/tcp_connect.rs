@@ -0,0 +1,52 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use g3_types::net::UpstreamAddr;
+
+use super::{SpanData, SpanKind};
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+use crate::serve::{ServerTaskError, ServerTaskNotes};
+
+pub(crate) struct TaskSpanForTcpConnect<'a> {
+    pub(crate) server_type: &'static str,
+    pub(crate) upstream: &'a UpstreamAddr,
+    pub(crate) task_notes: &'a ServerTaskNotes,
+    pub(crate) tcp_notes: &'a TcpConnectTaskNotes,
+}
+
+impl TaskSpanForTcpConnect<'_> {
+    pub(crate) fn emit(&self, e: &ServerTaskError) {
+        let Some(trace_ctx) = &self.task_notes.trace_ctx else {
+            return;
+        };
+
+        let mut span = SpanData::new(
+            trace_ctx,
+            format!("{} {}", self.server_type, self.upstream),
+            SpanKind::Server,
+            &self.task_notes.start_at,
+            Duration::ZERO,
+            self.task_notes.time_elapsed(),
+        );
+        span.add_attr("task.id", self.task_notes.id.to_string());
+        span.add_attr("server.address", self.upstream.to_string());
+        span.add_attr("client.address", self.task_notes.client_addr().to_string());
+        if let Some(user) = self.task_notes.raw_user_name() {
+            span.add_attr("user.name", user.as_ref());
+        }
+        span.add_attr("escaper", self.tcp_notes.escaper.as_str());
+        match e {
+            ServerTaskError::Finished
+            | ServerTaskError::ClosedByClient
+            | ServerTaskError::ClosedByUpstream => {}
+            _ => span.set_error(e.brief().to_string()),
+        }
+        super::emit(span);
+
+        super::connect::emit_stage_spans(trace_ctx, self.task_notes, self.tcp_notes);
+    }
+}
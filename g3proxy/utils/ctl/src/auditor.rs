@@ -0,0 +1,91 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use clap::{Arg, ArgMatches, Command};
+use futures_util::future::TryFutureExt;
+
+use g3_ctl::{CommandError, CommandResult};
+
+use g3proxy_proto::auditor_capnp::auditor_control;
+use g3proxy_proto::proc_capnp::proc_control;
+
+use crate::common::parse_operation_result;
+
+pub const COMMAND: &str = "auditor";
+
+const COMMAND_ARG_NAME: &str = "name";
+
+const SUBCOMMAND_LIST_TLS_AUTO_BYPASS: &str = "list-tls-auto-bypass";
+const SUBCOMMAND_CLEAR_TLS_AUTO_BYPASS: &str = "clear-tls-auto-bypass";
+const SUBCOMMAND_CLEAR_TLS_AUTO_BYPASS_ARG_SERVER_NAME: &str = "server-name";
+
+pub fn command() -> Command {
+    Command::new(COMMAND)
+        .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
+        .subcommand_required(true)
+        .subcommand(
+            Command::new(SUBCOMMAND_LIST_TLS_AUTO_BYPASS)
+                .about("List the TLS server names that are auto bypassed currently"),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_CLEAR_TLS_AUTO_BYPASS)
+                .about("Clear the learned TLS auto bypass entries")
+                .arg(
+                    Arg::new(SUBCOMMAND_CLEAR_TLS_AUTO_BYPASS_ARG_SERVER_NAME)
+                        .help("The server name to clear, all entries will be cleared if not set")
+                        .num_args(1),
+                ),
+        )
+}
+
+async fn list_tls_auto_bypass(client: &auditor_control::Client) -> CommandResult<()> {
+    let req = client.list_tls_auto_bypass_request();
+    let rsp = req.send().promise.await?;
+    let list = rsp.get()?.get_result()?;
+    for entry in list.iter() {
+        let server_name = entry
+            .get_server_name()?
+            .to_str()
+            .map_err(|e| CommandError::Utf8 {
+                field: "server_name",
+                reason: e,
+            })?;
+        println!("{server_name} {}s", entry.get_ttl());
+    }
+    Ok(())
+}
+
+async fn clear_tls_auto_bypass(
+    client: &auditor_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let mut req = client.clear_tls_auto_bypass_request();
+    if let Some(server_name) =
+        args.get_one::<String>(SUBCOMMAND_CLEAR_TLS_AUTO_BYPASS_ARG_SERVER_NAME)
+    {
+        req.get().set_server_name(server_name.as_str());
+    }
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
+
+    let (subcommand, args) = args.subcommand().unwrap();
+    match subcommand {
+        SUBCOMMAND_LIST_TLS_AUTO_BYPASS => {
+            super::proc::get_auditor(client, name)
+                .and_then(|auditor| async move { list_tls_auto_bypass(&auditor).await })
+                .await
+        }
+        SUBCOMMAND_CLEAR_TLS_AUTO_BYPASS => {
+            super::proc::get_auditor(client, name)
+                .and_then(|auditor| async move { clear_tls_auto_bypass(&auditor, args).await })
+                .await
+        }
+        _ => unreachable!(),
+    }
+}
@@ -13,6 +13,7 @@ use g3proxy_proto::proc_capnp::proc_control;
 mod common;
 mod proc;
 
+mod auditor;
 mod escaper;
 mod resolver;
 mod server;
@@ -34,6 +35,7 @@ fn build_cli_args() -> Command {
         .subcommand(proc::commands::reload_server())
         .subcommand(user_group::command())
         .subcommand(resolver::command())
+        .subcommand(auditor::command())
         .subcommand(escaper::command())
         .subcommand(server::command())
 }
@@ -76,6 +78,7 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_RELOAD_SERVER => proc::reload_server(&proc_control, args).await,
                 user_group::COMMAND => user_group::run(&proc_control, args).await,
                 resolver::COMMAND => resolver::run(&proc_control, args).await,
+                auditor::COMMAND => auditor::run(&proc_control, args).await,
                 escaper::COMMAND => escaper::run(&proc_control, args).await,
                 server::COMMAND => server::run(&proc_control, args).await,
                 _ => Err(CommandError::Cli(anyhow!(
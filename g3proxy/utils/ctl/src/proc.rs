@@ -7,6 +7,7 @@ use clap::ArgMatches;
 
 use g3_ctl::CommandResult;
 
+use g3proxy_proto::auditor_capnp::auditor_control;
 use g3proxy_proto::escaper_capnp::escaper_control;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::resolver_capnp::resolver_control;
@@ -251,6 +252,16 @@ pub(crate) async fn get_resolver(
     parse_fetch_result(rsp.get()?.get_resolver()?)
 }
 
+pub(crate) async fn get_auditor(
+    client: &proc_control::Client,
+    name: &str,
+) -> CommandResult<auditor_control::Client> {
+    let mut req = client.get_auditor_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    parse_fetch_result(rsp.get()?.get_auditor()?)
+}
+
 pub(crate) async fn get_escaper(
     client: &proc_control::Client,
     name: &str,
@@ -11,17 +11,28 @@ use g3_ctl::CommandResult;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::server_capnp::server_control;
 
+use crate::common::parse_operation_result;
+
 pub const COMMAND: &str = "server";
 
 const COMMAND_ARG_NAME: &str = "name";
 
 const SUBCOMMAND_STATUS: &str = "status";
+const SUBCOMMAND_PURGE_HTTP_CACHE: &str = "purge-http-cache";
+const SUBCOMMAND_PURGE_HTTP_CACHE_ARG_URL: &str = "url";
 
 pub fn command() -> Command {
     Command::new(COMMAND)
         .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
         .subcommand_required(true)
         .subcommand(Command::new(SUBCOMMAND_STATUS))
+        .subcommand(
+            Command::new(SUBCOMMAND_PURGE_HTTP_CACHE).arg(
+                Arg::new(SUBCOMMAND_PURGE_HTTP_CACHE_ARG_URL)
+                    .help("The url to purge, all entries will be purged if not set")
+                    .num_args(1),
+            ),
+        )
 }
 
 async fn status(client: &server_control::Client) -> CommandResult<()> {
@@ -35,16 +46,30 @@ async fn status(client: &server_control::Client) -> CommandResult<()> {
     Ok(())
 }
 
+async fn purge_http_cache(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let mut req = client.purge_http_cache_request();
+    if let Some(url) = args.get_one::<String>(SUBCOMMAND_PURGE_HTTP_CACHE_ARG_URL) {
+        req.get().set_url(url.as_str());
+    }
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
     let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
 
-    let (subcommand, _) = args.subcommand().unwrap();
+    let (subcommand, args) = args.subcommand().unwrap();
     match subcommand {
         SUBCOMMAND_STATUS => {
             super::proc::get_server(client, name)
                 .and_then(|server| async move { status(&server).await })
                 .await
         }
+        SUBCOMMAND_PURGE_HTTP_CACHE => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { purge_http_cache(&server, args).await })
+                .await
+        }
         _ => unreachable!(),
     }
 }
@@ -0,0 +1,65 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::Method;
+use serde_json::json;
+
+use g3_daemon::control::http::{HttpControlHandler, HttpControlRequest, HttpControlResponse};
+use g3_types::metrics::NodeName;
+
+pub struct HttpControlImpl;
+
+impl HttpControlImpl {
+    pub fn new_arc() -> Arc<Self> {
+        Arc::new(HttpControlImpl)
+    }
+}
+
+fn sorted_names(set: HashSet<NodeName>) -> HttpControlResponse {
+    let mut names = set.into_iter().collect::<Vec<_>>();
+    names.sort();
+    HttpControlResponse::from_names(names)
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse {
+        let segments = req.path_segments();
+        match (&req.method, segments.as_slice()) {
+            (&Method::GET, ["version"]) => HttpControlResponse::ok(json!(crate::build::VERSION)),
+            (&Method::GET, ["importer"]) => sorted_names(crate::import::get_names()),
+            (&Method::GET, ["collector"]) => sorted_names(crate::collect::get_names()),
+            (&Method::GET, ["exporter"]) => sorted_names(crate::export::get_names()),
+            (&Method::POST, [r#type, name, "reload"]) => reload(r#type, name).await,
+            (_, ["version" | "importer" | "collector" | "exporter"]) => {
+                HttpControlResponse::method_not_allowed()
+            }
+            _ => HttpControlResponse::not_found(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        !crate::import::get_names().is_empty()
+    }
+}
+
+async fn reload(r#type: &str, name: &str) -> HttpControlResponse {
+    if let Err(e) = NodeName::from_str(name) {
+        return HttpControlResponse::bad_request(format!("invalid name {name}: {e}"));
+    }
+    let name = name.to_string();
+    let r = match r#type {
+        "importer" => super::bridge::reload_importer(name, None).await,
+        "collector" => super::bridge::reload_collector(name, None).await,
+        "exporter" => super::bridge::reload_exporter(name, None).await,
+        _ => return HttpControlResponse::not_found(),
+    };
+    HttpControlResponse::from_result(r)
+}
@@ -74,6 +74,10 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
             });
         }
         g3statsd::control::QuitActor::tokio_spawn_run();
+        g3_daemon::control::http::HttpController::start(
+            g3statsd::control::HttpControlImpl::new_arc(),
+        )
+        .context("failed to start http controller")?;
 
         g3statsd::signal::register().context("failed to setup signal handler")?;
         g3_daemon::control::panic::set_hook(&args.daemon_config);
@@ -41,6 +41,12 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                     default_log_config = Some(config);
                     Ok(())
                 }
+                "file" => {
+                    let config = LogConfig::parse_file_yaml(v, conf_dir, crate::build::PKG_NAME)
+                        .context(format!("invalid file log config value for key {k}"))?;
+                    default_log_config = Some(config);
+                    Ok(())
+                }
                 "task" => {
                     let config = LogConfig::parse_yaml(v, conf_dir, crate::build::PKG_NAME)
                         .context(format!("invalid value for key {k}"))?;
@@ -0,0 +1,81 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::Method;
+use serde_json::json;
+
+use g3_daemon::control::http::{HttpControlHandler, HttpControlRequest, HttpControlResponse};
+use g3_types::metrics::NodeName;
+
+pub struct HttpControlImpl;
+
+impl HttpControlImpl {
+    pub fn new_arc() -> Arc<Self> {
+        Arc::new(HttpControlImpl)
+    }
+}
+
+fn sorted_names(set: HashSet<NodeName>) -> HttpControlResponse {
+    let mut names = set.into_iter().collect::<Vec<_>>();
+    names.sort();
+    HttpControlResponse::from_names(names)
+}
+
+fn parse_name(name: &str) -> Result<NodeName, HttpControlResponse> {
+    NodeName::from_str(name)
+        .map_err(|e| HttpControlResponse::bad_request(format!("invalid name {name}: {e}")))
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse {
+        let segments = req.path_segments();
+        match (&req.method, segments.as_slice()) {
+            (&Method::GET, ["version"]) => HttpControlResponse::ok(json!(crate::build::VERSION)),
+            (&Method::GET, ["server"]) => sorted_names(crate::serve::get_names()),
+            (&Method::GET, ["discover"]) => sorted_names(crate::discover::get_names()),
+            (&Method::GET, ["backend"]) => sorted_names(crate::backend::get_names()),
+            (&Method::POST, [r#type, name, "reload"]) => reload(r#type, name).await,
+            (&Method::POST, ["offline-server", "force-quit"]) => {
+                crate::serve::force_quit_offline_servers();
+                HttpControlResponse::success()
+            }
+            (&Method::POST, ["offline-server", name, "force-quit"]) => match parse_name(name) {
+                Ok(name) => {
+                    crate::serve::force_quit_offline_server(&name);
+                    HttpControlResponse::success()
+                }
+                Err(rsp) => rsp,
+            },
+            (_, ["version" | "server" | "discover" | "backend"]) => {
+                HttpControlResponse::method_not_allowed()
+            }
+            _ => HttpControlResponse::not_found(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        !crate::serve::get_names().is_empty()
+    }
+}
+
+async fn reload(r#type: &str, name: &str) -> HttpControlResponse {
+    if let Err(rsp) = parse_name(name) {
+        return rsp;
+    }
+    let name = name.to_string();
+    let r = match r#type {
+        "server" => super::bridge::reload_server(name, None).await,
+        "discover" => super::bridge::reload_discover(name, None).await,
+        "backend" => super::bridge::reload_backend(name, None).await,
+        _ => return HttpControlResponse::not_found(),
+    };
+    HttpControlResponse::from_result(r)
+}
@@ -111,6 +111,10 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
             });
         }
         g3tiles::control::QuitActor::tokio_spawn_run();
+        g3_daemon::control::http::HttpController::start(
+            g3tiles::control::HttpControlImpl::new_arc(),
+        )
+        .context("failed to start http controller")?;
 
         g3tiles::signal::register().context("failed to setup signal handler")?;
         g3_daemon::control::panic::set_hook(&args.daemon_config);
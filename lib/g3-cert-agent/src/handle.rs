@@ -56,4 +56,21 @@ impl CertAgentHandle {
             .await
             .and_then(|r| r.inner().cloned())
     }
+
+    /// Fetch a cert signed by the untrusted CA, which is used to mirror upstream cert errors
+    pub async fn fetch_untrusted(
+        &self,
+        service: TlsServiceType,
+        usage: TlsCertUsage,
+        host: Arc<str>,
+        mimic_cert: X509,
+    ) -> Option<FakeCertPair> {
+        let mut query_key = CacheQueryKey::new(service, usage, host);
+        query_key.set_untrusted();
+        query_key.set_mimic_cert(mimic_cert);
+        self.inner
+            .fetch(Arc::new(query_key), self.request_timeout)
+            .await
+            .and_then(|r| r.inner().cloned())
+    }
 }
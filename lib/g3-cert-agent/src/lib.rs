@@ -39,6 +39,7 @@ struct CacheIndexKey {
     service: TlsServiceType,
     usage: TlsCertUsage,
     host: Arc<str>,
+    untrusted: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -54,11 +55,16 @@ impl CacheQueryKey {
                 service,
                 usage,
                 host,
+                untrusted: false,
             },
             mimic_cert: None,
         }
     }
 
+    fn set_untrusted(&mut self) {
+        self.index.untrusted = true;
+    }
+
     fn host(&self) -> &str {
         self.index.host.as_ref()
     }
@@ -70,7 +76,7 @@ impl CacheQueryKey {
     fn encode(&self) -> Result<Vec<u8>, rmpv::encode::Error> {
         use rmpv::ValueRef;
 
-        let mut map = Vec::with_capacity(4);
+        let mut map = Vec::with_capacity(5);
         map.push((
             ValueRef::Integer(request_key_id::HOST.into()),
             ValueRef::String(self.host().into()),
@@ -83,6 +89,12 @@ impl CacheQueryKey {
             ValueRef::Integer(request_key_id::USAGE.into()),
             ValueRef::Integer((self.index.usage as u8).into()),
         ));
+        if self.index.untrusted {
+            map.push((
+                ValueRef::Integer(request_key_id::UNTRUSTED.into()),
+                ValueRef::Boolean(true),
+            ));
+        }
         if let Some(cert) = &self.mimic_cert
             && let Ok(der) = cert.to_der()
         {
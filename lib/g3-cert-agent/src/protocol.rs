@@ -8,6 +8,7 @@ pub mod request_key {
     pub const SERVICE: &str = "service";
     pub const CERT: &str = "cert";
     pub const USAGE: &str = "usage";
+    pub const UNTRUSTED: &str = "untrusted";
 }
 
 pub mod request_key_id {
@@ -15,6 +16,7 @@ pub mod request_key_id {
     pub const SERVICE: u64 = 2;
     pub const CERT: u64 = 3;
     pub const USAGE: u64 = 4;
+    pub const UNTRUSTED: u64 = 5;
 }
 
 pub mod response_key {
@@ -24,6 +26,7 @@ pub mod response_key {
     pub const PRIVATE_KEY: &str = "key";
     pub const TTL: &str = "ttl";
     pub const USAGE: &str = "usage";
+    pub const UNTRUSTED: &str = "untrusted";
 }
 
 pub mod response_key_id {
@@ -33,4 +36,5 @@ pub mod response_key_id {
     pub const PRIVATE_KEY: u64 = 4;
     pub const TTL: u64 = 5;
     pub const USAGE: u64 = 6;
+    pub const UNTRUSTED: u64 = 7;
 }
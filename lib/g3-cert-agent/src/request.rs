@@ -18,6 +18,7 @@ pub struct Request {
     service: TlsServiceType,
     usage: TlsCertUsage,
     pub(crate) cert: Option<X509>,
+    untrusted: bool,
 }
 
 impl Default for Request {
@@ -27,6 +28,7 @@ impl Default for Request {
             service: TlsServiceType::Http,
             usage: TlsCertUsage::TlsServer,
             cert: None,
+            untrusted: false,
         }
     }
 }
@@ -52,6 +54,12 @@ impl Request {
         self.usage
     }
 
+    /// Whether the cert should be signed by the untrusted CA
+    #[inline]
+    pub fn untrusted(&self) -> bool {
+        self.untrusted
+    }
+
     fn check(&self) -> anyhow::Result<()> {
         if self.host.is_empty() {
             return Err(anyhow!("no host value set"));
@@ -85,6 +93,11 @@ impl Request {
                         self.cert = Some(cert);
                         Ok(())
                     }
+                    request_key::UNTRUSTED => {
+                        self.untrusted = g3_msgpack::value::as_bool(&v)
+                            .context(format!("invalid bool value for key {key}"))?;
+                        Ok(())
+                    }
                     _ => Err(anyhow!("invalid key {key}")),
                 }
             }
@@ -111,6 +124,11 @@ impl Request {
                         self.cert = Some(cert);
                         Ok(())
                     }
+                    request_key_id::UNTRUSTED => {
+                        self.untrusted = g3_msgpack::value::as_bool(&v)
+                            .context(format!("invalid bool value for key id {key_id}"))?;
+                        Ok(())
+                    }
                     _ => Err(anyhow!("invalid key id {key_id}")),
                 }
             }
@@ -144,7 +162,7 @@ impl Request {
     }
 
     pub fn encode_rsp(&self, pem_cert: &str, der_key: &[u8], ttl: u32) -> anyhow::Result<Vec<u8>> {
-        let map = vec![
+        let mut map = vec![
             (
                 ValueRef::Integer(response_key_id::HOST.into()),
                 ValueRef::String(self.host.as_ref().into()),
@@ -170,6 +188,12 @@ impl Request {
                 ValueRef::Integer(ttl.into()),
             ),
         ];
+        if self.untrusted {
+            map.push((
+                ValueRef::Integer(response_key_id::UNTRUSTED.into()),
+                ValueRef::Boolean(true),
+            ));
+        }
         let mut buf = Vec::with_capacity(4096);
         let v = ValueRef::Map(map);
         rmpv::encode::write_value_ref(&mut buf, &v)
@@ -21,6 +21,7 @@ pub(super) struct Response {
     certs: Vec<X509>,
     key: Option<PKey<Private>>,
     ttl: u32,
+    untrusted: bool,
 }
 
 impl Response {
@@ -32,6 +33,7 @@ impl Response {
             certs: Vec::new(),
             key: None,
             ttl: protective_ttl,
+            untrusted: false,
         }
     }
 
@@ -67,6 +69,10 @@ impl Response {
                         self.ttl = g3_msgpack::value::as_u32(&v)
                             .context(format!("invalid u32 value for key {key}"))?;
                     }
+                    response_key::UNTRUSTED => {
+                        self.untrusted = g3_msgpack::value::as_bool(&v)
+                            .context(format!("invalid bool value for key {key}"))?;
+                    }
                     _ => {} // ignore unknown keys
                 }
             }
@@ -101,6 +107,10 @@ impl Response {
                         self.ttl = g3_msgpack::value::as_u32(&v)
                             .context(format!("invalid u32 value for key id {key_id}"))?;
                     }
+                    response_key_id::UNTRUSTED => {
+                        self.untrusted = g3_msgpack::value::as_bool(&v)
+                            .context(format!("invalid bool value for key id {key_id}"))?;
+                    }
                     _ => {} // ignore unknown keys
                 }
             }
@@ -126,8 +136,12 @@ impl Response {
             return Err(anyhow!("no cert chain set"));
         }
         let key = self.key.ok_or_else(|| anyhow!("no private key set"))?;
+        let mut query_key = CacheQueryKey::new(self.service, self.usage, Arc::from(self.host));
+        if self.untrusted {
+            query_key.set_untrusted();
+        }
         Ok((
-            CacheQueryKey::new(self.service, self.usage, Arc::from(self.host)),
+            query_key,
             FakeCertPair {
                 certs: self.certs,
                 key,
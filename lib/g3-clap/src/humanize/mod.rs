@@ -7,4 +7,4 @@ mod size;
 pub use size::get_usize;
 
 mod time;
-pub use time::get_duration;
+pub use time::{get_duration, parse_duration};
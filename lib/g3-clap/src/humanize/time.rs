@@ -6,22 +6,26 @@
 use std::str::FromStr;
 use std::time::Duration;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use clap::ArgMatches;
 
+pub fn parse_duration(v: &str) -> anyhow::Result<Duration> {
+    if let Ok(timeout) = humanize_rs::duration::parse(v) {
+        Ok(timeout)
+    } else if let Ok(timeout) = u64::from_str(v) {
+        Ok(Duration::from_secs(timeout))
+    } else if let Ok(timeout) = f64::from_str(v) {
+        Duration::try_from_secs_f64(timeout)
+            .map_err(|e| anyhow!("out of range duration value: {e}"))
+    } else {
+        Err(anyhow!("invalid duration value {v}"))
+    }
+}
+
 pub fn get_duration(args: &ArgMatches, id: &str) -> anyhow::Result<Option<Duration>> {
     if let Some(v) = args.get_one::<String>(id) {
-        if let Ok(timeout) = humanize_rs::duration::parse(v) {
-            Ok(Some(timeout))
-        } else if let Ok(timeout) = u64::from_str(v) {
-            Ok(Some(Duration::from_secs(timeout)))
-        } else if let Ok(timeout) = f64::from_str(v) {
-            let timeout = Duration::try_from_secs_f64(timeout)
-                .map_err(|e| anyhow!("out of range timeout value: {e}"))?;
-            Ok(Some(timeout))
-        } else {
-            Err(anyhow!("invalid {id} value {v}"))
-        }
+        let timeout = parse_duration(v).context(format!("invalid {id} value {v}"))?;
+        Ok(Some(timeout))
     } else {
         Ok(None)
     }
@@ -0,0 +1,123 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::OnceLock;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_types::net::RustlsServerConfigBuilder;
+
+use super::GeneralControllerConfig;
+
+const DEFAULT_LISTEN_PORT: u16 = 2999;
+const DEFAULT_MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+
+static HTTP_CONTROLLER_CONFIG: OnceLock<HttpControllerConfig> = OnceLock::new();
+
+pub(crate) struct HttpControllerConfig {
+    pub(crate) general: GeneralControllerConfig,
+    pub(crate) listen: SocketAddr,
+    pub(crate) auth_token: Option<String>,
+    pub(crate) tls_server: Option<RustlsServerConfigBuilder>,
+    pub(crate) max_body_size: usize,
+}
+
+impl Default for HttpControllerConfig {
+    fn default() -> Self {
+        HttpControllerConfig {
+            general: GeneralControllerConfig::new(),
+            listen: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), DEFAULT_LISTEN_PORT),
+            auth_token: None,
+            tls_server: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
+
+impl HttpControllerConfig {
+    pub(crate) fn get() -> Option<&'static HttpControllerConfig> {
+        HTTP_CONTROLLER_CONFIG.get()
+    }
+
+    pub(crate) fn set_default(v: &Yaml) -> anyhow::Result<()> {
+        let mut config = HttpControllerConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| config.set(k, v))?;
+            }
+            Yaml::String(_) => {
+                config.listen =
+                    g3_yaml::value::as_env_sockaddr(v).context("invalid listen address value")?;
+            }
+            Yaml::Null => {}
+            _ => return Err(anyhow!("invalid value type")),
+        }
+        config.check()?;
+        HTTP_CONTROLLER_CONFIG
+            .set(config)
+            .map_err(|_| anyhow!("http controller config has already been set"))
+    }
+
+    #[inline]
+    pub(crate) fn client_auth_enabled(&self) -> bool {
+        self.tls_server
+            .as_ref()
+            .map(|b| b.client_auth_enabled())
+            .unwrap_or(false)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "recv_timeout" | "send_timeout" => self.general.set(k, v),
+            "listen" => {
+                self.listen = g3_yaml::value::as_env_sockaddr(v)
+                    .context(format!("invalid socket address value for key {k}"))?;
+                Ok(())
+            }
+            "auth_token" | "bearer_token" => {
+                let token = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                if token.is_empty() {
+                    return Err(anyhow!("empty auth token is not allowed"));
+                }
+                self.auth_token = Some(token);
+                Ok(())
+            }
+            "tls_server" | "tls" => {
+                let lookup_dir = crate::opts::config_dir();
+                let builder = g3_yaml::value::as_rustls_server_config_builder(v, lookup_dir)
+                    .context(format!("invalid rustls server config value for key {k}"))?;
+                self.tls_server = Some(builder);
+                Ok(())
+            }
+            "max_body_size" => {
+                self.max_body_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.listen.port() == 0 {
+            return Err(anyhow!("no listen port set"));
+        }
+        if let Some(builder) = &self.tls_server {
+            builder.check()?;
+        }
+        if !self.listen.ip().is_loopback()
+            && self.auth_token.is_none()
+            && !self.client_auth_enabled()
+        {
+            return Err(anyhow!(
+                "either auth token or tls client auth should be set if listen on non-loopback address"
+            ));
+        }
+        Ok(())
+    }
+}
@@ -8,6 +8,11 @@ use yaml_rust::Yaml;
 
 mod local;
 
+#[cfg(feature = "http-control")]
+mod http_control;
+#[cfg(feature = "http-control")]
+pub(crate) use http_control::HttpControllerConfig;
+
 const DEFAULT_RECV_TIMEOUT: u64 = 30;
 const DEFAULT_SEND_TIMEOUT: u64 = 1;
 
@@ -57,6 +62,8 @@ pub fn load(v: &Yaml) -> anyhow::Result<()> {
         Yaml::Hash(map) => {
             g3_yaml::foreach_kv(map, |k, v| match k {
                 "local" => LocalControllerConfig::set_default(v),
+                #[cfg(feature = "http-control")]
+                "http" => HttpControllerConfig::set_default(v),
                 _ => Err(anyhow!("invalid key '{k}'")),
             })?;
             Ok(())
@@ -0,0 +1,374 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use async_trait::async_trait;
+use http::{Method, StatusCode};
+use log::{debug, info, warn};
+use serde_json::json;
+use tokio::io::{AsyncRead, AsyncWrite, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio_rustls::TlsAcceptor;
+
+use g3_io_ext::LimitedWriteExt;
+use g3_types::net::TcpListenConfig;
+
+use super::config::HttpControllerConfig;
+
+mod request;
+pub use request::HttpControlRequest;
+
+mod response;
+pub use response::HttpControlResponse;
+
+/// Application specific handler for the http controller.
+///
+/// The following APIs are handled inside the controller and will not be passed to the handler:
+///
+/// - `GET /healthz`
+/// - `GET /readyz`
+/// - `GET /pid`
+/// - `POST /offline`
+/// - `POST /cancel-shutdown`
+/// - `POST /release-controller`
+#[async_trait]
+pub trait HttpControlHandler: Send + Sync {
+    async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse;
+
+    /// Check if the application is ready to serve new requests
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+pub type ArcHttpControlHandler = Arc<dyn HttpControlHandler>;
+
+static HTTP_CONTROL_HANDLER: Mutex<Option<ArcHttpControlHandler>> = Mutex::new(None);
+static HTTP_CONTROLLER_ABORT_CHANNEL: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
+
+pub struct HttpController {
+    config: &'static HttpControllerConfig,
+    listener: TcpListener,
+    tls_acceptor: Option<(TlsAcceptor, Duration)>,
+    handler: ArcHttpControlHandler,
+}
+
+impl HttpController {
+    /// Start the http controller if it's enabled in config, this should be called in tokio context
+    pub fn start(handler: ArcHttpControlHandler) -> anyhow::Result<()> {
+        HTTP_CONTROL_HANDLER
+            .lock()
+            .unwrap()
+            .replace(handler.clone());
+        HttpController::spawn(handler)
+    }
+
+    /// Start the http controller again with the handler set in [`HttpController::start`],
+    /// this should be called in tokio context after [`HttpController::abort`]
+    pub fn resume() -> anyhow::Result<()> {
+        let handler = HTTP_CONTROL_HANDLER.lock().unwrap().clone();
+        match handler {
+            Some(handler) => HttpController::spawn(handler),
+            None => Ok(()),
+        }
+    }
+
+    /// Stop the http controller and close the listen socket,
+    /// the requests already accepted will still be served
+    pub fn abort() {
+        if let Some(sender) = HTTP_CONTROLLER_ABORT_CHANNEL.lock().unwrap().take() {
+            let _ = sender.send(());
+        }
+    }
+
+    fn spawn(handler: ArcHttpControlHandler) -> anyhow::Result<()> {
+        let Some(config) = HttpControllerConfig::get() else {
+            return Ok(());
+        };
+
+        let tls_acceptor = match &config.tls_server {
+            Some(builder) => {
+                let tls_config = builder
+                    .build()
+                    .context("failed to build tls server config")?;
+                Some((
+                    TlsAcceptor::from(tls_config.driver),
+                    tls_config.accept_timeout,
+                ))
+            }
+            None => None,
+        };
+
+        let listener = g3_socket::tcp::new_std_listener(&TcpListenConfig::new(config.listen))
+            .map_err(|e| anyhow!("failed to listen on {}: {e}", config.listen))?;
+        let listener = TcpListener::from_std(listener)
+            .map_err(|e| anyhow!("failed to convert listen socket: {e}"))?;
+        info!("http controller listen on {}", config.listen);
+
+        let (abort_sender, abort_receiver) = oneshot::channel();
+        {
+            let mut abort_channel = HTTP_CONTROLLER_ABORT_CHANNEL.lock().unwrap();
+            if abort_channel.is_some() {
+                return Err(anyhow!("http controller is already running"));
+            }
+            *abort_channel = Some(abort_sender);
+        }
+
+        let controller = HttpController {
+            config,
+            listener,
+            tls_acceptor,
+            handler,
+        };
+        tokio::spawn(controller.into_running(abort_receiver));
+        Ok(())
+    }
+
+    async fn into_running(self, mut abort_receiver: oneshot::Receiver<()>) {
+        let ctx = Arc::new(HttpControlCtx {
+            config: self.config,
+            handler: self.handler,
+        });
+        loop {
+            let (stream, peer) = tokio::select! {
+                biased;
+
+                _ = &mut abort_receiver => break,
+                r = self.listener.accept() => match r {
+                    Ok(v) => v,
+                    Err(e) => {
+                        warn!("http controller accept error: {e}");
+                        continue;
+                    }
+                },
+            };
+            let ctx = ctx.clone();
+            match &self.tls_acceptor {
+                Some((acceptor, accept_timeout)) => {
+                    let acceptor = acceptor.clone();
+                    let accept_timeout = *accept_timeout;
+                    tokio::spawn(async move {
+                        match tokio::time::timeout(accept_timeout, acceptor.accept(stream)).await {
+                            Ok(Ok(tls_stream)) => ctx.serve(tls_stream, peer).await,
+                            Ok(Err(e)) => debug!("http controller tls handshake failed: {e}"),
+                            Err(_) => debug!("http controller tls handshake timed out"),
+                        }
+                    });
+                }
+                None => {
+                    tokio::spawn(async move { ctx.serve(stream, peer).await });
+                }
+            }
+        }
+        info!("http controller on {} stopped", self.config.listen);
+    }
+}
+
+struct HttpControlCtx {
+    config: &'static HttpControllerConfig,
+    handler: ArcHttpControlHandler,
+}
+
+impl HttpControlCtx {
+    async fn serve<S>(&self, stream: S, peer: SocketAddr)
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (r, mut w) = tokio::io::split(stream);
+        let mut reader = BufReader::new(r);
+
+        let rsp = match tokio::time::timeout(
+            Duration::from_secs(self.config.general.recv_timeout),
+            HttpControlRequest::read(&mut reader, self.config.max_body_size),
+        )
+        .await
+        {
+            Ok(Ok(req)) => self.handle(&req).await,
+            Ok(Err(e)) => HttpControlResponse::bad_request(e),
+            Err(_) => {
+                debug!("http controller timed out to read request from {peer}");
+                return;
+            }
+        };
+
+        let buf = rsp.serialize();
+        match tokio::time::timeout(
+            Duration::from_secs(self.config.general.send_timeout),
+            w.write_all_flush(&buf),
+        )
+        .await
+        {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => debug!("http controller failed to send response to {peer}: {e}"),
+            Err(_) => debug!("http controller timed out to send response to {peer}"),
+        }
+    }
+
+    fn check_auth(&self, req: &HttpControlRequest) -> bool {
+        let Some(token) = &self.config.auth_token else {
+            // either no auth is required, or the client has been verified in tls handshake
+            return true;
+        };
+        let Some(value) = &req.authorization else {
+            return false;
+        };
+        let Some((scheme, credential)) = value.split_once(' ') else {
+            return false;
+        };
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return false;
+        }
+        constant_time_eq(credential.trim().as_bytes(), token.as_bytes())
+    }
+
+    async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse {
+        // probes are always allowed
+        match (&req.method, req.path()) {
+            (&Method::GET, "/healthz") => return HttpControlResponse::ok(json!({"status": "ok"})),
+            (&Method::GET, "/readyz") => {
+                return if crate::control::quit::is_going_offline() {
+                    HttpControlResponse::error(StatusCode::SERVICE_UNAVAILABLE, "offline")
+                } else if !self.handler.is_ready() {
+                    HttpControlResponse::error(StatusCode::SERVICE_UNAVAILABLE, "not ready")
+                } else {
+                    HttpControlResponse::ok(json!({"status": "ready"}))
+                };
+            }
+            _ => {}
+        }
+
+        if !self.check_auth(req) {
+            return HttpControlResponse::error(StatusCode::UNAUTHORIZED, "unauthorized");
+        }
+
+        match (&req.method, req.path()) {
+            (&Method::GET, "/pid") => HttpControlResponse::ok(json!(std::process::id())),
+            (&Method::POST, "/offline") => {
+                crate::control::quit::start_graceful_shutdown().await;
+                HttpControlResponse::success()
+            }
+            (&Method::POST, "/cancel-shutdown") => HttpControlResponse::from_result(
+                crate::control::quit::cancel_graceful_shutdown().await,
+            ),
+            (&Method::POST, "/release-controller") => {
+                HttpControlResponse::from_result(crate::control::quit::release_controller().await)
+            }
+            _ => self.handler.handle(req).await,
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    struct TestHandler;
+
+    #[async_trait]
+    impl HttpControlHandler for TestHandler {
+        async fn handle(&self, req: &HttpControlRequest) -> HttpControlResponse {
+            match (&req.method, req.path_segments().as_slice()) {
+                (&Method::GET, ["echo"]) => match req.query_param("v") {
+                    Some(v) => HttpControlResponse::ok(json!(v)),
+                    None => HttpControlResponse::bad_request("no v"),
+                },
+                _ => HttpControlResponse::not_found(),
+            }
+        }
+
+        fn is_ready(&self) -> bool {
+            false
+        }
+    }
+
+    fn new_ctx(auth_token: Option<&str>) -> HttpControlCtx {
+        let config = HttpControllerConfig {
+            auth_token: auth_token.map(|s| s.to_string()),
+            ..Default::default()
+        };
+        HttpControlCtx {
+            config: Box::leak(Box::new(config)),
+            handler: Arc::new(TestHandler),
+        }
+    }
+
+    async fn request(ctx: &HttpControlCtx, data: &[u8]) -> HttpControlResponse {
+        let mut reader = BufReader::new(data);
+        let req = HttpControlRequest::read(&mut reader, 1024).await.unwrap();
+        ctx.handle(&req).await
+    }
+
+    #[tokio::test]
+    async fn probes() {
+        let ctx = new_ctx(Some("abc"));
+
+        let rsp = request(&ctx, b"GET /healthz HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::OK);
+
+        let rsp = request(&ctx, b"GET /readyz HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn auth() {
+        let ctx = new_ctx(Some("abc"));
+
+        let rsp = request(&ctx, b"GET /pid HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::UNAUTHORIZED);
+
+        let rsp = request(
+            &ctx,
+            b"GET /pid HTTP/1.1\r\nAuthorization: Bearer abd\r\n\r\n",
+        )
+        .await;
+        assert_eq!(rsp.status(), StatusCode::UNAUTHORIZED);
+
+        let rsp = request(
+            &ctx,
+            b"GET /pid HTTP/1.1\r\nAuthorization: Basic abc\r\n\r\n",
+        )
+        .await;
+        assert_eq!(rsp.status(), StatusCode::UNAUTHORIZED);
+
+        let rsp = request(
+            &ctx,
+            b"GET /pid HTTP/1.1\r\nAuthorization: bearer abc\r\n\r\n",
+        )
+        .await;
+        assert_eq!(rsp.status(), StatusCode::OK);
+
+        let ctx = new_ctx(None);
+        let rsp = request(&ctx, b"GET /pid HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn app_handler() {
+        let ctx = new_ctx(None);
+
+        let rsp = request(&ctx, b"GET /echo?v=a%20b HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::OK);
+        assert_eq!(rsp.body(), &json!("a b"));
+
+        let rsp = request(&ctx, b"GET /echo HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::BAD_REQUEST);
+
+        let rsp = request(&ctx, b"POST /echo HTTP/1.1\r\n\r\n").await;
+        assert_eq!(rsp.status(), StatusCode::NOT_FOUND);
+    }
+}
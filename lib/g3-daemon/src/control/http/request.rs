@@ -0,0 +1,199 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use http::Method;
+use percent_encoding::percent_decode_str;
+use tokio::io::{AsyncBufRead, AsyncReadExt};
+
+use g3_io_ext::LimitedBufReadExt;
+
+const MAX_HEADER_LINE_SIZE: usize = 4096;
+const MAX_HEADER_COUNT: usize = 64;
+
+/// The request received by the http controller
+pub struct HttpControlRequest {
+    pub method: Method,
+    path: String,
+    query: Option<String>,
+    pub(super) authorization: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpControlRequest {
+    pub(super) async fn read<R>(reader: &mut R, max_body_size: usize) -> anyhow::Result<Self>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let mut line = Vec::with_capacity(MAX_HEADER_LINE_SIZE);
+        read_line(reader, &mut line).await?;
+        let request_line = std::str::from_utf8(&line)
+            .map_err(|e| anyhow!("invalid request line: {e}"))?
+            .trim_end();
+
+        let mut iter = request_line.split(' ');
+        let method = iter.next().ok_or_else(|| anyhow!("no method found"))?;
+        let method =
+            Method::from_str(method).map_err(|e| anyhow!("invalid method {method}: {e}"))?;
+        let target = iter
+            .next()
+            .ok_or_else(|| anyhow!("no request target found"))?;
+        let version = iter.next().ok_or_else(|| anyhow!("no version found"))?;
+        if !version.starts_with("HTTP/1.") {
+            return Err(anyhow!("unsupported http version {version}"));
+        }
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), Some(query.to_string())),
+            None => (target.to_string(), None),
+        };
+        if !path.starts_with('/') {
+            return Err(anyhow!("invalid request target {target}"));
+        }
+
+        let mut content_length = 0usize;
+        let mut authorization = None;
+        let mut header_count = 0;
+        loop {
+            read_line(reader, &mut line).await?;
+            let header = std::str::from_utf8(&line)
+                .map_err(|e| anyhow!("invalid header line: {e}"))?
+                .trim_end();
+            if header.is_empty() {
+                break;
+            }
+            header_count += 1;
+            if header_count > MAX_HEADER_COUNT {
+                return Err(anyhow!("too many headers"));
+            }
+            let Some((name, value)) = header.split_once(':') else {
+                return Err(anyhow!("invalid header line {header}"));
+            };
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = usize::from_str(value)
+                    .map_err(|e| anyhow!("invalid content-length value {value}: {e}"))?;
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.to_string());
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                return Err(anyhow!("transfer-encoding is not supported"));
+            }
+        }
+
+        if content_length > max_body_size {
+            return Err(anyhow!("too large request body"));
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await?;
+
+        Ok(HttpControlRequest {
+            method,
+            path,
+            query,
+            authorization,
+            body,
+        })
+    }
+
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the non-empty path segments
+    pub fn path_segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+
+    /// Get the percent decoded first value of the query parameter
+    pub fn query_param(&self, name: &str) -> Option<Cow<'_, str>> {
+        let query = self.query.as_ref()?;
+        query.split('&').find_map(|kv| {
+            let (k, v) = kv.split_once('=').unwrap_or((kv, ""));
+            if percent_decode_str(k).decode_utf8_lossy() == name {
+                Some(percent_decode_str(v).decode_utf8_lossy())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn body_str(&self) -> anyhow::Result<&str> {
+        std::str::from_utf8(&self.body).map_err(|e| anyhow!("invalid utf-8 body: {e}"))
+    }
+}
+
+async fn read_line<R>(reader: &mut R, line: &mut Vec<u8>) -> anyhow::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    line.clear();
+    let (found, nr) = reader
+        .limited_read_until(b'\n', MAX_HEADER_LINE_SIZE, line)
+        .await?;
+    if nr == 0 {
+        Err(anyhow!("connection closed by client"))
+    } else if !found {
+        Err(anyhow!("too long header line"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn read_request() {
+        let data = b"POST /reload/server/s1?force&delay=1 HTTP/1.1\r\n\
+Host: localhost\r\n\
+Authorization: Bearer abc\r\n\
+Content-Length: 4\r\n\
+\r\n\
+body";
+        let mut reader = BufReader::new(&data[..]);
+        let req = HttpControlRequest::read(&mut reader, 1024).await.unwrap();
+        assert_eq!(req.method, Method::POST);
+        assert_eq!(req.path(), "/reload/server/s1");
+        assert_eq!(req.path_segments(), vec!["reload", "server", "s1"]);
+        assert_eq!(req.query_param("force").as_deref(), Some(""));
+        assert_eq!(req.query_param("delay").as_deref(), Some("1"));
+        assert_eq!(req.query_param("none"), None);
+        assert_eq!(req.authorization.as_deref(), Some("Bearer abc"));
+        assert_eq!(req.body_str().unwrap(), "body");
+    }
+
+    #[tokio::test]
+    async fn percent_decode_query() {
+        let data = b"GET /resolver/r1/query?domain=%E4%BE%8B.example.net&strategy=Ipv4%4Fnly&a%20b=c%26d HTTP/1.1\r\n\r\n";
+        let mut reader = BufReader::new(&data[..]);
+        let req = HttpControlRequest::read(&mut reader, 1024).await.unwrap();
+        assert_eq!(
+            req.query_param("domain").as_deref(),
+            Some("\u{4f8b}.example.net")
+        );
+        assert_eq!(req.query_param("strategy").as_deref(), Some("Ipv4Only"));
+        assert_eq!(req.query_param("a b").as_deref(), Some("c&d"));
+    }
+
+    #[tokio::test]
+    async fn read_invalid() {
+        let data = b"GET /healthz HTTP/1.1\r\nContent-Length: 10\r\n\r\n";
+        let mut reader = BufReader::new(&data[..]);
+        assert!(HttpControlRequest::read(&mut reader, 4).await.is_err());
+
+        let data = b"GET healthz HTTP/1.1\r\n\r\n";
+        let mut reader = BufReader::new(&data[..]);
+        assert!(HttpControlRequest::read(&mut reader, 4).await.is_err());
+
+        let data = b"GET /healthz HTTP/2\r\n\r\n";
+        let mut reader = BufReader::new(&data[..]);
+        assert!(HttpControlRequest::read(&mut reader, 4).await.is_err());
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Display;
+
+use http::StatusCode;
+use serde_json::{Value, json};
+
+/// The JSON response to be sent by the http controller
+pub struct HttpControlResponse {
+    status: StatusCode,
+    body: Value,
+}
+
+impl HttpControlResponse {
+    pub fn new(status: StatusCode, body: Value) -> Self {
+        HttpControlResponse { status, body }
+    }
+
+    #[inline]
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    #[inline]
+    pub fn body(&self) -> &Value {
+        &self.body
+    }
+
+    /// A success response with JSON body
+    pub fn ok(body: Value) -> Self {
+        HttpControlResponse::new(StatusCode::OK, body)
+    }
+
+    /// A success response for operations with no return data
+    pub fn success() -> Self {
+        HttpControlResponse::ok(json!({"ok": "success"}))
+    }
+
+    pub fn error<T: Display>(status: StatusCode, reason: T) -> Self {
+        HttpControlResponse::new(status, json!({"error": reason.to_string()}))
+    }
+
+    pub fn bad_request<T: Display>(reason: T) -> Self {
+        HttpControlResponse::error(StatusCode::BAD_REQUEST, reason)
+    }
+
+    pub fn not_found() -> Self {
+        HttpControlResponse::error(StatusCode::NOT_FOUND, "no such api")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        HttpControlResponse::error(StatusCode::METHOD_NOT_ALLOWED, "method not allowed")
+    }
+
+    /// Convert the result of an operation, errors will be returned as 500
+    pub fn from_result(r: anyhow::Result<()>) -> Self {
+        match r {
+            Ok(_) => HttpControlResponse::success(),
+            Err(e) => {
+                HttpControlResponse::error(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:?}"))
+            }
+        }
+    }
+
+    /// Convert a list of names, which is the result of all list operations
+    pub fn from_names<I, T>(names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let list = names
+            .into_iter()
+            .map(|n| Value::String(n.as_ref().to_string()))
+            .collect::<Vec<_>>();
+        HttpControlResponse::ok(Value::Array(list))
+    }
+
+    pub(super) fn serialize(&self) -> Vec<u8> {
+        let body = self.body.to_string();
+        let reason = self.status.canonical_reason().unwrap_or("");
+        let mut buf = Vec::with_capacity(128 + body.len());
+        buf.extend_from_slice(
+            format!(
+                "HTTP/1.1 {} {reason}\r\n\
+                 Content-Type: application/json\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\r\n",
+                self.status.as_u16(),
+                body.len()
+            )
+            .as_bytes(),
+        );
+        buf.extend_from_slice(body.as_bytes());
+        buf
+    }
+}
@@ -19,6 +19,9 @@ pub mod panic;
 
 pub mod capnp;
 
+#[cfg(feature = "http-control")]
+pub mod http;
+
 pub mod config;
 use config::{GeneralControllerConfig, LocalControllerConfig};
 
@@ -4,6 +4,7 @@
  */
 
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -11,6 +12,7 @@ use log::{info, warn};
 use tokio::sync::{mpsc, oneshot};
 
 static DAEMON_QUIT_CHANNEL: Mutex<Option<mpsc::Sender<Command>>> = Mutex::new(None);
+static DAEMON_GOING_OFFLINE: AtomicBool = AtomicBool::new(false);
 
 enum Command {
     StartGracefulShutdown,
@@ -49,6 +51,11 @@ pub async fn cancel_graceful_shutdown() -> anyhow::Result<()> {
     }
 }
 
+/// Check if the daemon is going offline, which means a graceful shutdown is pending
+pub fn is_going_offline() -> bool {
+    DAEMON_GOING_OFFLINE.load(Ordering::Relaxed)
+}
+
 pub fn trigger_force_shutdown() {
     let mut channel = DAEMON_QUIT_CHANNEL.lock().unwrap();
     *channel = None;
@@ -95,6 +102,7 @@ impl<T: QuitAction> QuitLoop<T> {
                 Command::StartGracefulShutdown => {
                     // this should be sent by an init, which doesn't know it's a restart or stop
                     info!("received StartGracefulShutdown request");
+                    DAEMON_GOING_OFFLINE.store(true, Ordering::Relaxed);
                     let mut is_restart = false;
                     loop {
                         info!(
@@ -149,6 +157,7 @@ impl<T: QuitAction> QuitLoop<T> {
                 Command::ReleaseController(finish_sender) => {
                     // this should be sent by the new process, which knows it's a restart
                     info!("received ReleaseController request");
+                    DAEMON_GOING_OFFLINE.store(true, Ordering::Relaxed);
                     self.release_controller().await;
                     let _ = finish_sender.send(true);
                     loop {
@@ -218,6 +227,7 @@ impl<T: QuitAction> QuitLoop<T> {
             match self.action.do_resume_controller() {
                 Ok(_) => {
                     self.controller_released = false;
+                    DAEMON_GOING_OFFLINE.store(false, Ordering::Relaxed);
                     info!("daemon controller resumed");
                     true
                 }
@@ -227,6 +237,7 @@ impl<T: QuitAction> QuitLoop<T> {
                 }
             }
         } else {
+            DAEMON_GOING_OFFLINE.store(false, Ordering::Relaxed);
             true
         }
     }
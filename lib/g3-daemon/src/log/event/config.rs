@@ -10,6 +10,7 @@ use anyhow::{Context, anyhow};
 use slog::{Logger, OwnedKV, SendSyncRefUnwindSafeKV};
 use yaml_rust::Yaml;
 
+use g3_filelog::FileLogConfig;
 use g3_fluentd::FluentdClientConfig;
 #[cfg(target_os = "linux")]
 use g3_journal::JournalConfig;
@@ -29,6 +30,7 @@ pub enum LogConfigDriver {
     Journal(JournalConfig),
     Syslog(SyslogBuilder),
     Fluentd(Arc<FluentdClientConfig>),
+    File(Arc<FileLogConfig>),
     Stdout,
 }
 
@@ -59,6 +61,7 @@ impl LogConfig {
             "journal" => Ok(LogConfig::new_journal(program_name)),
             "syslog" => Ok(LogConfig::new_syslog(program_name)),
             "fluentd" => Ok(LogConfig::new_fluentd(program_name)),
+            "file" => Ok(LogConfig::new_file(program_name)),
             "stdout" => Ok(LogConfig::new_stdout(program_name)),
             _ => Err(anyhow!("invalid default log config")),
         }
@@ -90,6 +93,13 @@ impl LogConfig {
         )
     }
 
+    pub fn new_file(program_name: &'static str) -> Self {
+        Self::with_driver(
+            LogConfigDriver::File(Arc::new(FileLogConfig::with_ident(program_name))),
+            program_name,
+        )
+    }
+
     pub fn new_stdout(program_name: &'static str) -> Self {
         Self::with_driver(LogConfigDriver::Stdout, program_name)
     }
@@ -106,6 +116,7 @@ impl LogConfig {
                 "journal" => Ok(LogConfig::new_journal(program_name)),
                 "syslog" => Ok(LogConfig::new_syslog(program_name)),
                 "fluentd" => Ok(LogConfig::new_fluentd(program_name)),
+                "file" => Ok(LogConfig::new_file(program_name)),
                 "stdout" => Ok(LogConfig::new_stdout(program_name)),
                 _ => Err(anyhow!("invalid log config")),
             },
@@ -130,6 +141,12 @@ impl LogConfig {
                         config.driver = LogConfigDriver::Fluentd(Arc::new(client));
                         Ok(())
                     }
+                    "file" => {
+                        let file_conf = FileLogConfig::parse_yaml(v, conf_dir, program_name)
+                            .context("invalid file log config")?;
+                        config.driver = LogConfigDriver::File(Arc::new(file_conf));
+                        Ok(())
+                    }
                     "async_channel_size" | "channel_size" => {
                         let channel_size = g3_yaml::value::as_usize(v)
                             .context(format!("invalid usize value for key {k}"))?;
@@ -183,6 +200,19 @@ impl LogConfig {
         ))
     }
 
+    pub fn parse_file_yaml(
+        v: &Yaml,
+        conf_dir: &Path,
+        program_name: &'static str,
+    ) -> anyhow::Result<LogConfig> {
+        let driver = FileLogConfig::parse_yaml(v, conf_dir, program_name)
+            .context("invalid file log config")?;
+        Ok(LogConfig::with_driver(
+            LogConfigDriver::File(Arc::new(driver)),
+            program_name,
+        ))
+    }
+
     pub fn build_shared_logger(
         self,
         logger_name: String,
@@ -240,6 +270,13 @@ impl LogConfig {
                 let drain = ReportLogIoError::new(drain, &logger_name, self.io_err_sampling_mask);
                 Some(Logger::root(drain, common_values))
             }
+            LogConfigDriver::File(file_conf) => {
+                let drain = g3_filelog::new_async_logger(&async_conf, &file_conf, &logger_name);
+                let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
+                super::registry::add(logger_name.clone(), Arc::new(logger_stats));
+                let drain = ReportLogIoError::new(drain, &logger_name, self.io_err_sampling_mask);
+                Some(Logger::root(drain, common_values))
+            }
             LogConfigDriver::Stdout => {
                 let drain = g3_stdlog::new_async_logger(&async_conf, false, true);
                 let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
@@ -64,6 +64,7 @@ where
                 break;
             }
             info!("got reload signal");
+            g3_filelog::reopen_all();
             call_reload.run().await;
         }
     });
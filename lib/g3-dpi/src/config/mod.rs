@@ -54,6 +54,21 @@ impl ProtocolInspectPolicyBuilder {
         self.missed_action = missed_action;
     }
 
+    /// Iterate over all the actions that may be returned by the built policy
+    pub fn iter_actions(&self) -> impl Iterator<Item = ProtocolInspectAction> + '_ {
+        let exact = self.exact.iter().flat_map(|r| {
+            r.iter_domain()
+                .map(|(_, a)| a)
+                .chain(r.iter_ip().map(|(_, a)| a))
+        });
+        let child = self.child.iter().flat_map(|r| r.iter().map(|(_, a)| a));
+        let subnet = self.subnet.iter().flat_map(|r| r.iter().map(|(_, a)| a));
+        std::iter::once(self.missed_action)
+            .chain(exact)
+            .chain(child)
+            .chain(subnet)
+    }
+
     pub fn build(&self) -> ProtocolInspectPolicy {
         ProtocolInspectPolicy {
             exact: self.exact.clone(),
@@ -229,3 +244,32 @@ impl ProtocolInspectionConfig {
         &mut self.data0_size_limit
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_actions() {
+        let builder = ProtocolInspectPolicyBuilder::new(ProtocolInspectAction::Bypass);
+        let actions: Vec<_> = builder.iter_actions().collect();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], ProtocolInspectAction::Bypass));
+
+        let mut builder = ProtocolInspectPolicyBuilder::new(ProtocolInspectAction::Bypass);
+        let mut exact = AclExactHostRule::new(ProtocolInspectAction::Bypass);
+        exact.add_domain("a.example.net".into(), ProtocolInspectAction::Block);
+        builder.exact = Some(exact);
+        let mut child = AclChildDomainRuleBuilder::new(ProtocolInspectAction::Bypass);
+        child.add_node("example.org", ProtocolInspectAction::Intercept);
+        builder.child = Some(child);
+        let actions: Vec<_> = builder.iter_actions().collect();
+        assert_eq!(actions.len(), 3);
+        assert!(actions.iter().any(|a| a.is_block()));
+        assert!(
+            actions
+                .iter()
+                .any(|a| matches!(a, ProtocolInspectAction::Intercept))
+        );
+    }
+}
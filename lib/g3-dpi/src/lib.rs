@@ -9,7 +9,7 @@ pub use source::Source;
 mod protocol;
 pub use protocol::{
     MaybeProtocol, Protocol, ProtocolInspectError, ProtocolInspector, ProtocolPortMap,
-    ProtocolPortMapValue,
+    ProtocolPortMapValue, is_dns_udp_request_message,
 };
 
 mod config;
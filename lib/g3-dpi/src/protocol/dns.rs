@@ -28,25 +28,56 @@ impl ProtocolInspectState {
             return Ok(None);
         }
 
-        if self.check_dns_request_message_header(&data[2..]).is_err() {
+        if check_dns_request_message_header(&data[2..]).is_err() {
             self.exclude_current();
             return Ok(None);
         }
 
         Ok(Some(Protocol::Dns))
     }
+}
 
-    fn check_dns_request_message_header(&mut self, hdr: &[u8]) -> Result<(), ()> {
-        if hdr[2] & 0b1000_0000 != 0 {
-            // QR bit is not query
-            return Err(());
-        }
+fn check_dns_request_message_header(hdr: &[u8]) -> Result<(), ()> {
+    if hdr[2] & 0b1000_0000 != 0 {
+        // QR bit is not query
+        return Err(());
+    }
 
-        if hdr[6..DNS_MESSAGE_HEADER_LEN] != [0x00, 0x00, 0x00, 0x00, 0x00, 0x00] {
-            // there should be no any an / ns / ar count
-            return Err(());
-        }
+    if hdr[6..DNS_MESSAGE_HEADER_LEN] != [0x00, 0x00, 0x00, 0x00, 0x00, 0x00] {
+        // there should be no any an / ns / ar count
+        return Err(());
+    }
+
+    Ok(())
+}
+
+/// Check if the udp datagram is a DNS request message
+pub fn is_dns_udp_request_message(data: &[u8]) -> bool {
+    if data.len() < DNS_MESSAGE_HEADER_LEN {
+        return false;
+    }
+    if data[4..6] == [0x00, 0x00] {
+        // there should be at least one question
+        return false;
+    }
+    check_dns_request_message_header(data).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn udp_request() {
+        let data = hex!("a0e10100000100000000000003777777076578616d706c6503636f6d0000010001");
+        assert!(is_dns_udp_request_message(&data));
+
+        // response
+        let mut rsp = data;
+        rsp[2] |= 0b1000_0000;
+        assert!(!is_dns_udp_request_message(&rsp));
 
-        Ok(())
+        assert!(!is_dns_udp_request_message(&data[..8]));
     }
 }
@@ -285,6 +285,7 @@ impl fmt::Display for Protocol {
 
 mod bittorrent;
 mod dns;
+pub use dns::is_dns_udp_request_message;
 mod ftp;
 mod http;
 mod imap;
@@ -0,0 +1,140 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::cell::RefCell;
+use std::fmt::{Arguments, Write};
+use std::io;
+
+use chrono::Local;
+use serde::ser::{SerializeMap, Serializer as _};
+use slog::{KV, OwnedKVList, Record, Serializer};
+
+use g3_syslog::SyslogLineFormatter;
+use g3_types::log::AsyncLogFormatter;
+
+thread_local! {
+    static TL_BUF: RefCell<String> = RefCell::new(String::with_capacity(128))
+}
+
+pub enum FileLogFormatter {
+    Json,
+    Syslog(SyslogLineFormatter),
+}
+
+impl AsyncLogFormatter<Vec<u8>> for FileLogFormatter {
+    fn format_slog(
+        &self,
+        record: &Record,
+        logger_values: &OwnedKVList,
+    ) -> Result<Vec<u8>, slog::Error> {
+        let mut buf = Vec::with_capacity(1024);
+        match self {
+            FileLogFormatter::Json => format_json(&mut buf, record, logger_values)?,
+            FileLogFormatter::Syslog(formatter) => {
+                formatter.format_slog(&mut buf, record, logger_values)?
+            }
+        }
+        if buf.last() != Some(&b'\n') {
+            buf.push(b'\n');
+        }
+        Ok(buf)
+    }
+}
+
+fn format_json(
+    buf: &mut Vec<u8>,
+    record: &Record,
+    logger_values: &OwnedKVList,
+) -> Result<(), slog::Error> {
+    let datetime_now = Local::now();
+    let ts = datetime_now
+        .format_with_items(g3_datetime::format::log::RFC5424.iter())
+        .to_string();
+
+    let mut serializer = serde_json::Serializer::new(buf);
+    let ser_map = (&mut serializer)
+        .serialize_map(None)
+        .map_err(|e| io::Error::other(format!("serde serialization error: {e}")))?;
+    let mut kv_formatter = JsonFormatterKV { ser_map };
+    kv_formatter.emit_entry("ts", &ts)?;
+    kv_formatter.emit_entry("level", record.level().as_str())?;
+    logger_values.serialize(record, &mut kv_formatter)?;
+    record.kv().serialize(record, &mut kv_formatter)?;
+    kv_formatter.emit_arguments("msg".into(), record.msg())?;
+    kv_formatter
+        .ser_map
+        .end()
+        .map_err(|e| io::Error::other(format!("serde serialization error: {e}")))?;
+    Ok(())
+}
+
+struct JsonFormatterKV<M: SerializeMap> {
+    ser_map: M,
+}
+
+impl<M: SerializeMap> JsonFormatterKV<M> {
+    fn emit_entry<V: serde::Serialize + ?Sized>(&mut self, key: &str, value: &V) -> slog::Result {
+        self.ser_map
+            .serialize_entry(key, value)
+            .map_err(|e| io::Error::other(format!("serde serialization error: {e}")))?;
+        Ok(())
+    }
+}
+
+macro_rules! impl_emit_by_serde {
+    ($($t:ty => $f:ident),* $(,)?) => {
+        $(
+            fn $f(&mut self, key: slog::Key, value: $t) -> slog::Result {
+                self.emit_entry(key.as_str(), &value)
+            }
+        )*
+    };
+}
+
+impl<M: SerializeMap> Serializer for JsonFormatterKV<M> {
+    impl_emit_by_serde! {
+        bool => emit_bool,
+        char => emit_char,
+        u8 => emit_u8,
+        i8 => emit_i8,
+        u16 => emit_u16,
+        i16 => emit_i16,
+        u32 => emit_u32,
+        i32 => emit_i32,
+        u64 => emit_u64,
+        i64 => emit_i64,
+        usize => emit_usize,
+        isize => emit_isize,
+        f32 => emit_f32,
+        f64 => emit_f64,
+        &str => emit_str,
+    }
+
+    fn emit_unit(&mut self, key: slog::Key) -> slog::Result {
+        self.emit_entry(key.as_str(), &())
+    }
+
+    fn emit_none(&mut self, _key: slog::Key) -> slog::Result {
+        Ok(())
+    }
+
+    fn emit_arguments(&mut self, key: slog::Key, value: &Arguments) -> slog::Result {
+        if let Some(s) = value.as_str() {
+            self.emit_str(key, s)
+        } else {
+            TL_BUF.with_borrow_mut(|buf| {
+                buf.clear();
+
+                buf.write_fmt(*value).unwrap();
+
+                self.emit_str(key, buf.as_str())
+            })
+        }
+    }
+
+    fn emit_serde(&mut self, key: slog::Key, value: &dyn slog::SerdeValue) -> slog::Result {
+        self.emit_entry(key.as_str(), value.as_serde())
+    }
+}
@@ -0,0 +1,172 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use g3_syslog::SyslogBuilder;
+use g3_types::log::{AsyncLogConfig, AsyncLogger, LogStats};
+
+mod format;
+pub use format::FileLogFormatter;
+
+mod rotate;
+use rotate::RotateFile;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+const DEFAULT_ROTATE_SIZE: u64 = 64 * 1024 * 1024;
+const DEFAULT_RETENTION: usize = 7;
+
+static REOPEN_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Notify all file loggers to reopen their log files before the next write
+pub fn reopen_all() {
+    REOPEN_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+fn reopen_generation() -> usize {
+    REOPEN_GENERATION.load(Ordering::Relaxed)
+}
+
+#[derive(Clone, Debug)]
+pub enum FileLogFormat {
+    Json,
+    Syslog(SyslogBuilder),
+}
+
+#[derive(Clone, Debug)]
+pub struct FileLogConfig {
+    dir: PathBuf,
+    format: FileLogFormat,
+    rotate_size: u64,
+    rotate_interval: Option<Duration>,
+    compress: bool,
+    retention: usize,
+}
+
+impl FileLogConfig {
+    pub fn new(dir: PathBuf) -> Self {
+        FileLogConfig {
+            dir,
+            format: FileLogFormat::Json,
+            rotate_size: DEFAULT_ROTATE_SIZE,
+            rotate_interval: None,
+            compress: true,
+            retention: DEFAULT_RETENTION,
+        }
+    }
+
+    pub fn with_ident(ident: &'static str) -> Self {
+        let mut dir = PathBuf::from("/var/log");
+        dir.push(ident);
+        FileLogConfig::new(dir)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn set_dir(&mut self, dir: PathBuf) {
+        self.dir = dir;
+    }
+
+    pub fn set_format(&mut self, format: FileLogFormat) {
+        self.format = format;
+    }
+
+    /// Set the max size of the log file, 0 means no size based rotation
+    pub fn set_rotate_size(&mut self, size: u64) {
+        self.rotate_size = size;
+    }
+
+    pub fn set_rotate_interval(&mut self, interval: Option<Duration>) {
+        self.rotate_interval = interval.filter(|d| !d.is_zero());
+    }
+
+    pub fn set_compress(&mut self, enable: bool) {
+        self.compress = enable;
+    }
+
+    /// Set how many rotated files to keep
+    pub fn set_retention(&mut self, count: usize) {
+        self.retention = count;
+    }
+
+    fn file_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.log"))
+    }
+
+    fn build_formatter(&self) -> FileLogFormatter {
+        match &self.format {
+            FileLogFormat::Json => FileLogFormatter::Json,
+            FileLogFormat::Syslog(builder) => {
+                FileLogFormatter::Syslog(builder.build_line_formatter())
+            }
+        }
+    }
+}
+
+/// Create a new async logger which writes to file `<dir>/<name>.log`.
+///
+/// Only one io thread will be spawned, as the writes to the same file should be in order.
+pub fn new_async_logger(
+    async_conf: &AsyncLogConfig,
+    file_conf: &FileLogConfig,
+    name: &str,
+) -> AsyncLogger<Vec<u8>, FileLogFormatter> {
+    let (sender, receiver) = kanal::bounded::<Vec<u8>>(async_conf.channel_capacity);
+
+    let stats = Arc::new(LogStats::default());
+
+    let io_thread = AsyncIoThread {
+        receiver,
+        stats: Arc::clone(&stats),
+        file: RotateFile::new(file_conf.file_path(name), file_conf),
+    };
+
+    let _detached_thread = std::thread::Builder::new()
+        .name(async_conf.thread_name.clone())
+        .spawn(move || {
+            io_thread.run_to_end();
+        });
+
+    AsyncLogger::new(sender, file_conf.build_formatter(), stats)
+}
+
+struct AsyncIoThread {
+    receiver: kanal::Receiver<Vec<u8>>,
+    stats: Arc<LogStats>,
+    file: RotateFile,
+}
+
+impl AsyncIoThread {
+    fn run_to_end(mut self) {
+        while let Ok(v) = self.receiver.recv() {
+            self.file.check_reopen();
+            self.write_buf(&v);
+
+            while let Ok(Some(v)) = self.receiver.try_recv() {
+                self.write_buf(&v);
+            }
+
+            self.file.flush();
+        }
+        self.file.close();
+    }
+
+    fn write_buf(&mut self, buf: &[u8]) {
+        match self.file.write_all(buf) {
+            Ok(_) => {
+                self.stats.io.add_passed();
+                self.stats.io.add_size(buf.len());
+            }
+            Err(_) => self.stats.drop.add_peer_unreachable(),
+        }
+    }
+}
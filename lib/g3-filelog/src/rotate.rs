@@ -0,0 +1,291 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use log::warn;
+
+use super::FileLogConfig;
+
+/// hard coded 4s for a minimal reopen interval
+const REOPEN_MIN_INTERVAL: Duration = Duration::from_secs(4);
+
+pub(super) struct RotateFile {
+    path: PathBuf,
+    rotate_size: u64,
+    rotate_interval: Option<Duration>,
+    compress: bool,
+    retention: usize,
+    writer: Option<BufWriter<File>>,
+    written_size: u64,
+    opened_instant: Instant,
+    open_failed_instant: Option<Instant>,
+    reopen_generation: usize,
+    compress_job: Option<JoinHandle<()>>,
+}
+
+impl RotateFile {
+    pub(super) fn new(path: PathBuf, config: &FileLogConfig) -> Self {
+        RotateFile {
+            path,
+            rotate_size: config.rotate_size,
+            rotate_interval: config.rotate_interval,
+            compress: config.compress,
+            retention: config.retention,
+            writer: None,
+            written_size: 0,
+            opened_instant: Instant::now(),
+            open_failed_instant: None,
+            reopen_generation: super::reopen_generation(),
+            compress_job: None,
+        }
+    }
+
+    /// Close the current file if a reopen is requested, the file will be opened again on next write
+    pub(super) fn check_reopen(&mut self) {
+        let generation = super::reopen_generation();
+        if generation != self.reopen_generation {
+            self.reopen_generation = generation;
+            self.close();
+            self.open_failed_instant = None;
+        }
+    }
+
+    pub(super) fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        if self.need_rotate(buf.len()) {
+            self.rotate();
+        }
+
+        let writer = self.get_writer()?;
+        match writer.write_all(buf) {
+            Ok(_) => {
+                self.written_size += buf.len() as u64;
+                Ok(())
+            }
+            Err(e) => {
+                warn!("failed to write to log file {}: {e}", self.path.display());
+                self.writer = None;
+                self.open_failed_instant = Some(Instant::now());
+                Err(e)
+            }
+        }
+    }
+
+    pub(super) fn flush(&mut self) {
+        if let Some(writer) = &mut self.writer
+            && let Err(e) = writer.flush()
+        {
+            warn!("failed to flush log file {}: {e}", self.path.display());
+            self.writer = None;
+            self.open_failed_instant = Some(Instant::now());
+        }
+    }
+
+    pub(super) fn close(&mut self) {
+        if let Some(mut writer) = self.writer.take() {
+            let _ = writer.flush();
+        }
+    }
+
+    fn get_writer(&mut self) -> io::Result<&mut BufWriter<File>> {
+        if self.writer.is_none() {
+            if let Some(failed) = self.open_failed_instant
+                && failed.elapsed() < REOPEN_MIN_INTERVAL
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotConnected,
+                    "log file is not available",
+                ));
+            }
+            match self.open() {
+                Ok(file) => {
+                    self.written_size = file.metadata().map(|m| m.len()).unwrap_or_default();
+                    self.opened_instant = Instant::now();
+                    self.open_failed_instant = None;
+                    self.writer = Some(BufWriter::new(file));
+                }
+                Err(e) => {
+                    warn!("failed to open log file {}: {e}", self.path.display());
+                    self.open_failed_instant = Some(Instant::now());
+                    return Err(e);
+                }
+            }
+        }
+        self.writer
+            .as_mut()
+            .ok_or_else(|| io::Error::other("no log file opened"))
+    }
+
+    fn open(&self) -> io::Result<File> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+    }
+
+    fn need_rotate(&self, next_size: usize) -> bool {
+        if self.writer.is_none() || self.written_size == 0 {
+            return false;
+        }
+        if self.rotate_size > 0 && self.written_size + next_size as u64 > self.rotate_size {
+            return true;
+        }
+        self.rotate_interval
+            .map(|interval| self.opened_instant.elapsed() >= interval)
+            .unwrap_or(false)
+    }
+
+    fn rotated_path(&self, index: usize, compressed: bool) -> PathBuf {
+        let mut s = self.path.as_os_str().to_os_string();
+        s.push(format!(".{index}"));
+        if compressed {
+            s.push(".gz");
+        }
+        PathBuf::from(s)
+    }
+
+    fn rotate(&mut self) {
+        self.close();
+
+        // the previous compression should be finished before we shift the rotated files
+        if let Some(job) = self.compress_job.take() {
+            let _ = job.join();
+        }
+
+        if self.retention == 0 {
+            if let Err(e) = fs::remove_file(&self.path) {
+                warn!("failed to remove log file {}: {e}", self.path.display());
+            }
+            return;
+        }
+
+        for compressed in [false, true] {
+            let oldest = self.rotated_path(self.retention, compressed);
+            if oldest.exists() {
+                let _ = fs::remove_file(&oldest);
+            }
+        }
+        for index in (1..self.retention).rev() {
+            for compressed in [false, true] {
+                let from = self.rotated_path(index, compressed);
+                if from.exists() {
+                    let to = self.rotated_path(index + 1, compressed);
+                    if let Err(e) = fs::rename(&from, &to) {
+                        warn!("failed to rename log file {}: {e}", from.display());
+                    }
+                }
+            }
+        }
+
+        let rotated = self.rotated_path(1, false);
+        if let Err(e) = fs::rename(&self.path, &rotated) {
+            warn!("failed to rotate log file {}: {e}", self.path.display());
+            return;
+        }
+
+        if self.compress {
+            let compressed = self.rotated_path(1, true);
+            let spawn_result = std::thread::Builder::new()
+                .name("log-compress".to_string())
+                .spawn(move || {
+                    if let Err(e) = compress_file(&rotated, &compressed) {
+                        warn!("failed to compress log file {}: {e}", rotated.display());
+                        let _ = fs::remove_file(&compressed);
+                    }
+                });
+            match spawn_result {
+                Ok(job) => self.compress_job = Some(job),
+                Err(e) => warn!("failed to spawn log compress thread: {e}"),
+            }
+        }
+    }
+}
+
+fn compress_file(from: &Path, to: &Path) -> io::Result<()> {
+    let mut input = File::open(from)?;
+    let output = File::create(to)?;
+    let mut encoder = GzEncoder::new(BufWriter::new(output), Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.flush()?;
+    fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(dir: &Path) -> FileLogConfig {
+        let mut config = FileLogConfig::new(dir.to_path_buf());
+        config.set_rotate_size(16);
+        config.set_compress(false);
+        config.set_retention(2);
+        config
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("g3-filelog-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn rotate_by_size() {
+        let dir = test_dir("size");
+        let config = test_config(&dir);
+        let path = config.file_path("test");
+        let mut file = RotateFile::new(path.clone(), &config);
+
+        for line in [
+            "0123456789\n",
+            "abcdefghij\n",
+            "ABCDEFGHIJ\n",
+            "9876543210\n",
+        ] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        file.close();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "9876543210\n");
+        let rotated1 = file.rotated_path(1, false);
+        assert_eq!(fs::read_to_string(&rotated1).unwrap(), "ABCDEFGHIJ\n");
+        let rotated2 = file.rotated_path(2, false);
+        assert_eq!(fs::read_to_string(&rotated2).unwrap(), "abcdefghij\n");
+        assert!(!file.rotated_path(3, false).exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reopen() {
+        let dir = test_dir("reopen");
+        let config = test_config(&dir);
+        let path = config.file_path("test");
+        let mut file = RotateFile::new(path.clone(), &config);
+
+        file.write_all(b"1\n").unwrap();
+        file.flush();
+        let moved = dir.join("moved.log");
+        fs::rename(&path, &moved).unwrap();
+
+        crate::reopen_all();
+        file.check_reopen();
+        file.write_all(b"2\n").unwrap();
+        file.close();
+
+        assert_eq!(fs::read_to_string(&moved).unwrap(), "1\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2\n");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
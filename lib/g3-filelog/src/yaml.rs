@@ -0,0 +1,151 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_syslog::{Facility, SyslogBuilder, SyslogFormatterKind};
+
+use super::{FileLogConfig, FileLogFormat};
+
+fn new_syslog_builder(ident: &'static str) -> SyslogBuilder {
+    let mut builder = SyslogBuilder::with_ident(ident);
+    builder.set_facility(Facility::Daemon);
+    builder
+}
+
+impl FileLogConfig {
+    pub fn parse_yaml(
+        value: &Yaml,
+        lookup_dir: &Path,
+        ident: &'static str,
+    ) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut config = FileLogConfig::with_ident(ident);
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "dir" | "directory" => {
+                        let dir = g3_yaml::value::as_dir_path(v, lookup_dir, true)
+                            .context(format!("invalid directory path value for key {k}"))?;
+                        config.set_dir(dir);
+                        Ok(())
+                    }
+                    "format" => {
+                        let format = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        match g3_yaml::key::normalize(&format).as_str() {
+                            "json" => config.set_format(FileLogFormat::Json),
+                            "syslog" | "rfc3164" => {
+                                config.set_format(FileLogFormat::Syslog(new_syslog_builder(ident)))
+                            }
+                            "rfc5424" => {
+                                let mut builder = new_syslog_builder(ident);
+                                builder.set_format(SyslogFormatterKind::Rfc5424(0, None));
+                                config.set_format(FileLogFormat::Syslog(builder));
+                            }
+                            _ => return Err(anyhow!("unsupported file log format {format}")),
+                        }
+                        Ok(())
+                    }
+                    "format_rfc5424" => {
+                        let format = SyslogFormatterKind::parse_rfc5424_yaml(v)
+                            .context(format!("invalid value for key {k}"))?;
+                        let mut builder = new_syslog_builder(ident);
+                        builder.set_format(format);
+                        config.set_format(FileLogFormat::Syslog(builder));
+                        Ok(())
+                    }
+                    "rotate_size" => {
+                        let size = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        config.set_rotate_size(size);
+                        Ok(())
+                    }
+                    "rotate_interval" => {
+                        let interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_rotate_interval(Some(interval));
+                        Ok(())
+                    }
+                    "compress" => {
+                        let enable = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid boolean value for key {k}"))?;
+                        config.set_compress(enable);
+                        Ok(())
+                    }
+                    "retention" | "retention_count" => {
+                        let count = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        config.set_retention(count);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                Ok(config)
+            }
+            Yaml::String(_) => {
+                let dir = g3_yaml::value::as_dir_path(value, lookup_dir, true)
+                    .context("invalid directory path value")?;
+                Ok(FileLogConfig::new(dir))
+            }
+            Yaml::Null => Ok(FileLogConfig::with_ident(ident)),
+            _ => Err(anyhow!(
+                "yaml value type for 'FileLogConfig' should be 'map' or 'string'"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_yaml_null() {
+        let config = FileLogConfig::parse_yaml(&Yaml::Null, Path::new("/"), "test").unwrap();
+        assert_eq!(config.dir(), Path::new("/var/log/test"));
+        assert!(matches!(config.format, FileLogFormat::Json));
+        assert!(config.compress);
+    }
+
+    #[test]
+    fn parse_yaml_map() {
+        let lookup_dir = std::env::temp_dir();
+        let yaml = yaml_doc!(
+            r#"
+            format: rfc5424
+            rotate_size: 1048576
+            rotate_interval: 1h
+            compress: false
+            retention: 3
+            "#
+        );
+        let config = FileLogConfig::parse_yaml(&yaml, &lookup_dir, "test").unwrap();
+        assert!(matches!(config.format, FileLogFormat::Syslog(_)));
+        assert_eq!(config.rotate_size, 1 << 20);
+        assert_eq!(config.rotate_interval, Some(Duration::from_secs(3600)));
+        assert!(!config.compress);
+        assert_eq!(config.retention, 3);
+    }
+
+    #[test]
+    fn parse_yaml_invalid() {
+        let lookup_dir = std::env::temp_dir();
+        let yaml = yaml_doc!(
+            r#"
+            format: xml
+            "#
+        );
+        assert!(FileLogConfig::parse_yaml(&yaml, &lookup_dir, "test").is_err());
+
+        assert!(FileLogConfig::parse_yaml(&Yaml::Integer(1), &lookup_dir, "test").is_err());
+    }
+}
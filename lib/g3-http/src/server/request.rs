@@ -136,6 +136,27 @@ impl HttpProxyClientRequest {
         }
     }
 
+    /// Create a new request with the end-to-end headers replaced, the body info is kept
+    pub fn clone_with_headers(&self, end_to_end_headers: HttpHeaderMap) -> Self {
+        HttpProxyClientRequest {
+            version: self.version,
+            method: self.method.clone(),
+            uri: self.uri.clone(),
+            end_to_end_headers,
+            hop_by_hop_headers: self.hop_by_hop_headers.clone(),
+            auth_info: HttpAuth::None,
+            host: self.host.clone(),
+            original_connection_name: self.original_connection_name.clone(),
+            extra_connection_headers: self.extra_connection_headers.clone(),
+            origin_header_size: self.origin_header_size,
+            keep_alive: self.keep_alive,
+            content_length: self.content_length,
+            chunked_transfer: self.chunked_transfer,
+            has_transfer_encoding: self.has_transfer_encoding,
+            has_content_length: self.has_content_length,
+        }
+    }
+
     #[inline]
     pub fn origin_header_size(&self) -> usize {
         self.origin_header_size
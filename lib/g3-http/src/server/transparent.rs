@@ -146,6 +146,29 @@ impl HttpTransparentRequest {
         }
     }
 
+    /// Create a new request with the end-to-end headers replaced, the body info is kept
+    pub fn clone_with_headers(&self, end_to_end_headers: HttpHeaderMap) -> Self {
+        HttpTransparentRequest {
+            version: self.version,
+            method: self.method.clone(),
+            uri: self.uri.clone(),
+            steal_forwarded_for: self.steal_forwarded_for,
+            end_to_end_headers,
+            hop_by_hop_headers: self.hop_by_hop_headers.clone(),
+            host: self.host.clone(),
+            original_connection_name: self.original_connection_name.clone(),
+            extra_connection_headers: self.extra_connection_headers.clone(),
+            origin_header_size: self.origin_header_size,
+            keep_alive: self.keep_alive,
+            connection_upgrade: self.connection_upgrade,
+            upgrade: self.upgrade,
+            content_length: self.content_length,
+            chunked_transfer: self.chunked_transfer,
+            has_transfer_encoding: self.has_transfer_encoding,
+            has_content_length: self.has_content_length,
+        }
+    }
+
     #[inline]
     pub fn disable_keep_alive(&mut self) {
         self.keep_alive = false;
@@ -3,7 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-mod parse;
+pub mod parse;
 mod reason;
 mod serialize;
 
@@ -16,5 +16,5 @@ pub use options::IcapServiceOptions;
 
 mod service;
 
-use service::{IcapClientConnection, IcapClientReader, IcapClientWriter};
-pub use service::{IcapMethod, IcapServiceClient, IcapServiceConfig};
+use service::{IcapClientReader, IcapClientWriter};
+pub use service::{IcapClientConnection, IcapMethod, IcapServiceClient, IcapServiceConfig};
@@ -57,6 +57,21 @@ impl IcapServiceOptions {
         }
     }
 
+    #[inline]
+    pub fn service_tag(&self) -> &str {
+        &self.service_tag
+    }
+
+    #[inline]
+    pub fn support_204(&self) -> bool {
+        self.support_204
+    }
+
+    #[inline]
+    pub fn preview_size(&self) -> Option<usize> {
+        self.preview_size
+    }
+
     pub(crate) fn expired(&self) -> bool {
         if let Some(expire) = self.expire {
             Instant::now() >= expire
@@ -153,6 +168,10 @@ impl IcapServiceOptions {
 
         match header.name.to_lowercase().as_str() {
             "methods" => {
+                if self.method == IcapMethod::Options {
+                    // no specific method is required
+                    return Ok(());
+                }
                 for v in header.value.split(',') {
                     if self.method.as_str() == v.trim() {
                         return Ok(());
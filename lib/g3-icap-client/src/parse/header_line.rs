@@ -5,13 +5,13 @@
 
 use super::IcapLineParseError;
 
-pub(crate) struct HeaderLine<'a> {
-    pub(crate) name: &'a str,
-    pub(crate) value: &'a str,
+pub struct HeaderLine<'a> {
+    pub name: &'a str,
+    pub value: &'a str,
 }
 
 impl<'a> HeaderLine<'a> {
-    pub(crate) fn parse(buf: &'a [u8]) -> Result<HeaderLine<'a>, IcapLineParseError> {
+    pub fn parse(buf: &'a [u8]) -> Result<HeaderLine<'a>, IcapLineParseError> {
         let line = std::str::from_utf8(buf)?;
 
         let p = memchr::memchr(b':', line.as_bytes())
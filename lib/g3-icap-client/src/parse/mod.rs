@@ -7,7 +7,7 @@ mod error;
 pub use error::IcapLineParseError;
 
 mod header_line;
-pub(crate) use header_line::HeaderLine;
+pub use header_line::HeaderLine;
 
 mod status_line;
-pub(crate) use status_line::StatusLine;
+pub use status_line::StatusLine;
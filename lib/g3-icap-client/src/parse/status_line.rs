@@ -7,13 +7,13 @@ use atoi::FromRadix10;
 
 use super::IcapLineParseError;
 
-pub(crate) struct StatusLine<'a> {
-    pub(crate) code: u16,
-    pub(crate) message: &'a str,
+pub struct StatusLine<'a> {
+    pub code: u16,
+    pub message: &'a str,
 }
 
 impl<'a> StatusLine<'a> {
-    pub(crate) fn parse(buf: &'a [u8]) -> Result<StatusLine<'a>, IcapLineParseError> {
+    pub fn parse(buf: &'a [u8]) -> Result<StatusLine<'a>, IcapLineParseError> {
         const PREFIX: &str = "ICAP/1.0 ";
         const MINIMAL_LENGTH: usize = 13; // ICAP/1.0 XYZ\n
 
@@ -37,6 +37,11 @@ impl IcapServiceClient {
         })
     }
 
+    /// Get the request line and common headers for the configured method
+    pub fn partial_request_header(&self) -> &[u8] {
+        &self.partial_request_header
+    }
+
     async fn fetch_from_pool(&self) -> Option<(IcapClientConnection, Arc<IcapServiceOptions>)> {
         let (rsp_sender, rsp_receiver) = oneshot::channel();
         let cmd = IcapServiceClientCommand::FetchConnection(rsp_sender);
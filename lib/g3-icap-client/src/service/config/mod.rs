@@ -89,6 +89,10 @@ impl IcapServiceConfig {
         self.tcp_keepalive = config;
     }
 
+    pub fn set_connection_pool(&mut self, config: ConnectionPoolConfig) {
+        self.connection_pool = config;
+    }
+
     pub fn set_tls_client(&mut self, config: RustlsClientConfigBuilder) {
         self.tls_client = Some(config);
     }
@@ -7,7 +7,8 @@ mod config;
 pub use config::IcapServiceConfig;
 
 mod connection;
-pub(super) use connection::{IcapClientConnection, IcapClientReader, IcapClientWriter};
+pub use connection::IcapClientConnection;
+pub(super) use connection::{IcapClientReader, IcapClientWriter};
 use connection::{IcapConnectionEofPoller, IcapConnectionPollRequest, IcapConnector};
 
 mod client;
@@ -14,7 +14,7 @@ pub use self::uuid::as_uuid;
 pub use datetime::as_rfc3339_datetime;
 pub use metrics::{as_metrics_name, as_weighted_metrics_name};
 pub use net::*;
-pub use primary::{as_f64, as_string, as_u32, as_weighted_name_string};
+pub use primary::{as_bool, as_f64, as_string, as_u32, as_weighted_name_string};
 pub use tls::{as_tls_cert_usage, as_tls_service_type};
 
 #[cfg(feature = "openssl")]
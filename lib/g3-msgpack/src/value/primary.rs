@@ -52,6 +52,24 @@ pub fn as_u32(v: &ValueRef) -> anyhow::Result<u32> {
     }
 }
 
+pub fn as_bool(v: &ValueRef) -> anyhow::Result<bool> {
+    match v {
+        ValueRef::Boolean(b) => Ok(*b),
+        ValueRef::Integer(i) => match i.as_u64() {
+            Some(i) => Ok(i != 0),
+            None => Err(anyhow!("invalid unsigned integer value")),
+        },
+        ValueRef::String(s) => match s.as_str().map(|s| s.to_lowercase()).as_deref() {
+            Some("on" | "true" | "yes" | "1") => Ok(true),
+            Some("off" | "false" | "no" | "0") => Ok(false),
+            _ => Err(anyhow!("invalid bool string value")),
+        },
+        _ => Err(anyhow!(
+            "msgpack value type for 'bool' should be 'boolean' / 'integer' / 'string'"
+        )),
+    }
+}
+
 pub fn as_f64(v: &ValueRef) -> anyhow::Result<f64> {
     match v {
         ValueRef::Integer(i) => i
@@ -174,6 +192,33 @@ mod tests {
         assert!(as_string(&v).is_err());
     }
 
+    #[test]
+    fn as_bool_ok() {
+        let v = ValueRef::Boolean(true);
+        assert!(as_bool(&v).unwrap());
+
+        let v = ValueRef::Integer(Integer::from(0u32));
+        assert!(!as_bool(&v).unwrap());
+
+        let v = ValueRef::String(Utf8StringRef::from("Yes"));
+        assert!(as_bool(&v).unwrap());
+
+        let v = ValueRef::String(Utf8StringRef::from("off"));
+        assert!(!as_bool(&v).unwrap());
+    }
+
+    #[test]
+    fn as_bool_err() {
+        let v = ValueRef::String(Utf8StringRef::from("abc"));
+        assert!(as_bool(&v).is_err());
+
+        let v = ValueRef::Integer(Integer::from(-1i32));
+        assert!(as_bool(&v).is_err());
+
+        let v = ValueRef::F32(1.0);
+        assert!(as_bool(&v).is_err());
+    }
+
     #[test]
     fn as_f64_ok() {
         let v = ValueRef::String(Utf8StringRef::from("123"));
@@ -0,0 +1,63 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::HostsResolver;
+use crate::BoxResolverDriver;
+
+const DEFAULT_TTL: u32 = 60;
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HostsDriverConfig {
+    path: PathBuf,
+    ttl: u32,
+    negative_ttl: u32,
+    check_interval: Duration,
+}
+
+impl HostsDriverConfig {
+    pub fn new(path: PathBuf) -> Self {
+        HostsDriverConfig {
+            path,
+            ttl: DEFAULT_TTL,
+            negative_ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    pub fn set_ttl(&mut self, ttl: u32) {
+        self.ttl = ttl;
+    }
+
+    pub fn set_negative_ttl(&mut self, ttl: u32) {
+        self.negative_ttl = ttl;
+    }
+
+    pub fn set_check_interval(&mut self, interval: Duration) {
+        self.check_interval = interval;
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<BoxResolverDriver> {
+        let driver = HostsResolver::new(
+            self.path.clone(),
+            self.ttl,
+            self.negative_ttl,
+            self.check_interval,
+        )?;
+        Ok(Box::new(driver))
+    }
+}
@@ -0,0 +1,191 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwap;
+use log::{info, warn};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::HostsTable;
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{ResolveDriver, ResolveServerError, ResolvedRecord};
+
+const MIN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(super) struct HostsResolver {
+    ttl: u32,
+    negative_ttl: u32,
+    table: Arc<ArcSwap<HostsTable>>,
+    reload_handle: JoinHandle<()>,
+}
+
+impl Drop for HostsResolver {
+    fn drop(&mut self) {
+        self.reload_handle.abort();
+    }
+}
+
+fn load_table(path: &Path) -> anyhow::Result<(HostsTable, Option<SystemTime>)> {
+    let modified = std::fs::metadata(path)
+        .map_err(|e| anyhow!("failed to get metadata of file {}: {e}", path.display()))?
+        .modified()
+        .ok();
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+    let table =
+        HostsTable::parse(&content).context(format!("invalid hosts file {}", path.display()))?;
+    Ok((table, modified))
+}
+
+/// Reload the table if the modified time of the file has changed,
+/// return the new modified time
+fn reload_table(
+    path: &Path,
+    modified: Option<SystemTime>,
+    table: &ArcSwap<HostsTable>,
+) -> Option<SystemTime> {
+    let new_modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if new_modified.is_some() && new_modified == modified {
+        return modified;
+    }
+    match load_table(path) {
+        Ok((new_table, new_modified)) => {
+            info!("reloaded hosts file {}", path.display());
+            table.store(Arc::new(new_table));
+            new_modified
+        }
+        Err(e) => {
+            warn!("failed to reload hosts file, keep using the old one: {e:?}");
+            modified
+        }
+    }
+}
+
+async fn reload_loop(
+    path: PathBuf,
+    check_interval: Duration,
+    mut modified: Option<SystemTime>,
+    table: Arc<ArcSwap<HostsTable>>,
+) {
+    let mut interval =
+        tokio::time::interval_at(tokio::time::Instant::now() + check_interval, check_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let path = Arc::new(path);
+    loop {
+        interval.tick().await;
+
+        let path = path.clone();
+        let table = table.clone();
+        match tokio::task::spawn_blocking(move || reload_table(&path, modified, &table)).await {
+            Ok(m) => modified = m,
+            Err(e) => warn!("failed to join hosts file reload task: {e}"),
+        }
+    }
+}
+
+impl HostsResolver {
+    pub(super) fn new(
+        path: PathBuf,
+        ttl: u32,
+        negative_ttl: u32,
+        check_interval: Duration,
+    ) -> anyhow::Result<Self> {
+        let (table, modified) = load_table(&path)?;
+        let check_interval = check_interval.max(MIN_CHECK_INTERVAL);
+        let table = Arc::new(ArcSwap::from_pointee(table));
+        let reload_handle =
+            tokio::spawn(reload_loop(path, check_interval, modified, table.clone()));
+        Ok(HostsResolver {
+            ttl,
+            negative_ttl,
+            table,
+            reload_handle,
+        })
+    }
+
+    #[inline]
+    fn get_table(&self) -> Arc<HostsTable> {
+        self.table.load_full()
+    }
+
+    fn query(&self, domain: Arc<str>, v4: bool) -> ResolvedRecord {
+        let table = self.get_table();
+        match table.get(&domain) {
+            Some(entry) => {
+                let ips: &Vec<IpAddr> = if v4 { &entry.v4 } else { &entry.v6 };
+                if ips.is_empty() {
+                    ResolvedRecord::empty(domain, self.ttl)
+                } else {
+                    ResolvedRecord::resolved(domain, self.ttl, self.ttl, self.ttl, ips.clone())
+                }
+            }
+            None => ResolvedRecord::failed(
+                domain,
+                self.negative_ttl,
+                ResolveServerError::NotFound.into(),
+            ),
+        }
+    }
+}
+
+impl ResolveDriver for HostsResolver {
+    fn query_v4(
+        &self,
+        domain: Arc<str>,
+        _config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let record = self.query(domain, true);
+        let _ = sender.send(ResolveDriverResponse::V4(record));
+    }
+
+    fn query_v6(
+        &self,
+        domain: Arc<str>,
+        _config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let record = self.query(domain, false);
+        let _ = sender.send(ResolveDriverResponse::V6(record));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reload_in_background() {
+        let path = std::env::temp_dir().join(format!("g3-resolver-hosts-{}", std::process::id()));
+        std::fs::write(&path, "127.0.0.1 a.example.net\n").unwrap();
+
+        let resolver = HostsResolver::new(path.clone(), 60, 30, MIN_CHECK_INTERVAL).unwrap();
+        let record = resolver.query(Arc::from("a.example.net"), true);
+        assert!(record.is_usable());
+        let record = resolver.query(Arc::from("b.example.net"), true);
+        assert!(!record.is_usable());
+
+        std::fs::write(&path, "127.0.0.2 b.example.net\n").unwrap();
+        tokio::time::sleep(MIN_CHECK_INTERVAL * 2).await;
+        let record = resolver.query(Arc::from("a.example.net"), true);
+        assert!(!record.is_usable());
+        let record = resolver.query(Arc::from("b.example.net"), true);
+        assert!(record.is_usable());
+
+        std::fs::write(&path, "invalid content").unwrap();
+        tokio::time::sleep(MIN_CHECK_INTERVAL * 2).await;
+        let record = resolver.query(Arc::from("b.example.net"), true);
+        assert!(record.is_usable());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod config;
+pub use config::HostsDriverConfig;
+
+mod table;
+use table::HostsTable;
+
+mod driver;
+use driver::HostsResolver;
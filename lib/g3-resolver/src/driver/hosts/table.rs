@@ -0,0 +1,155 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+
+#[derive(Default)]
+pub(super) struct HostsEntry {
+    pub(super) v4: Vec<IpAddr>,
+    pub(super) v6: Vec<IpAddr>,
+}
+
+impl HostsEntry {
+    fn add(&mut self, ip: IpAddr) {
+        let list = if ip.is_ipv4() {
+            &mut self.v4
+        } else {
+            &mut self.v6
+        };
+        if !list.contains(&ip) {
+            list.push(ip);
+        }
+    }
+}
+
+/// Hosts table parsed from a hosts-style file.
+///
+/// Each line should be an IP address followed by one or more names,
+/// names like `*.example.net` will match all subdomains of `example.net`.
+#[derive(Default)]
+pub(super) struct HostsTable {
+    exact: AHashMap<String, HostsEntry>,
+    wildcard: AHashMap<String, HostsEntry>,
+}
+
+impl HostsTable {
+    pub(super) fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut table = HostsTable::default();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((data, _comment)) => data,
+                None => line,
+            };
+            let mut iter = line.split_ascii_whitespace();
+            let Some(ip) = iter.next() else {
+                continue;
+            };
+            let ip = IpAddr::from_str(ip)
+                .map_err(|e| anyhow!("invalid ip address {ip} at line {}: {e}", i + 1))?;
+
+            let mut has_name = false;
+            for name in iter {
+                let name = name.trim_end_matches('.').to_ascii_lowercase();
+                if let Some(domain) = name.strip_prefix("*.") {
+                    if domain.is_empty() {
+                        return Err(anyhow!("invalid wildcard name at line {}", i + 1));
+                    }
+                    table
+                        .wildcard
+                        .entry(domain.to_string())
+                        .or_default()
+                        .add(ip);
+                } else if name.is_empty() || name.contains('*') {
+                    return Err(anyhow!("invalid name {name} at line {}", i + 1));
+                } else {
+                    table.exact.entry(name).or_default().add(ip);
+                }
+                has_name = true;
+            }
+            if !has_name {
+                return Err(anyhow!("no host name found at line {}", i + 1));
+            }
+        }
+
+        Ok(table)
+    }
+
+    pub(super) fn get(&self, domain: &str) -> Option<&HostsEntry> {
+        let domain = domain.trim_end_matches('.');
+        let lower;
+        let domain = if domain.bytes().any(|b| b.is_ascii_uppercase()) {
+            lower = domain.to_ascii_lowercase();
+            lower.as_str()
+        } else {
+            domain
+        };
+
+        if let Some(entry) = self.exact.get(domain) {
+            return Some(entry);
+        }
+
+        // the longest wildcard suffix will be matched first
+        let mut offset = 0;
+        while let Some(p) = domain[offset..].find('.') {
+            offset += p + 1;
+            if let Some(entry) = self.wildcard.get(&domain[offset..]) {
+                return Some(entry);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn parse_and_get() {
+        let content = r#"
+# comment line
+127.0.0.1   localhost
+::1         localhost ip6-localhost
+10.0.0.1    a.example.net  B.example.net.  # trailing comment
+10.0.0.2    *.example.net
+10.0.0.3    *.sub.example.net
+"#;
+        let table = HostsTable::parse(content).unwrap();
+
+        let entry = table.get("localhost").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]);
+        assert_eq!(entry.v6, vec![IpAddr::V6(Ipv6Addr::LOCALHOST)]);
+
+        let entry = table.get("b.example.net").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+        assert!(entry.v6.is_empty());
+
+        let entry = table.get("A.Example.Net").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+
+        let entry = table.get("c.example.net").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]);
+
+        let entry = table.get("d.sub.example.net").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3))]);
+
+        assert!(table.get("example.net").is_none());
+        assert!(table.get("example.org").is_none());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(HostsTable::parse("10.0.0.300 a.example.net").is_err());
+        assert!(HostsTable::parse("10.0.0.1").is_err());
+        assert!(HostsTable::parse("10.0.0.1 *.").is_err());
+        assert!(HostsTable::parse("10.0.0.1 a.*.example.net").is_err());
+    }
+}
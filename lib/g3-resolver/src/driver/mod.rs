@@ -11,6 +11,8 @@ use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
 
 pub mod fail_over;
+pub mod hosts;
+pub mod split;
 
 #[cfg(feature = "c-ares")]
 pub mod c_ares;
@@ -21,6 +23,8 @@ pub mod hickory;
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyResolveDriverConfig {
     FailOver(fail_over::FailOverDriverConfig),
+    Hosts(hosts::HostsDriverConfig),
+    Split(split::SplitDriverConfig),
     #[cfg(feature = "c-ares")]
     CAres(c_ares::CAresDriverConfig),
     #[cfg(feature = "hickory")]
@@ -31,6 +35,8 @@ impl AnyResolveDriverConfig {
     pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<Box<dyn ResolveDriver>> {
         match self {
             AnyResolveDriverConfig::FailOver(c) => Ok(c.spawn_resolver_driver()),
+            AnyResolveDriverConfig::Hosts(c) => c.spawn_resolver_driver(),
+            AnyResolveDriverConfig::Split(c) => Ok(c.spawn_resolver_driver()),
             #[cfg(feature = "c-ares")]
             AnyResolveDriverConfig::CAres(c) => c.spawn_resolver_driver(),
             #[cfg(feature = "hickory")]
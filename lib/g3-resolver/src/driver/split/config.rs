@@ -0,0 +1,52 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use super::SplitResolver;
+use crate::{BoxResolverDriver, ResolverHandle};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitDriverConfig {
+    rules: Vec<(String, Option<ResolverHandle>)>,
+    default_handle: Option<ResolverHandle>,
+    negative_ttl: u32,
+}
+
+impl Default for SplitDriverConfig {
+    fn default() -> Self {
+        SplitDriverConfig {
+            rules: Vec::new(),
+            default_handle: None,
+            negative_ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+        }
+    }
+}
+
+impl SplitDriverConfig {
+    /// Queries for `suffix` and all its subdomains will be sent to the `handle`
+    pub fn add_rule(&mut self, suffix: &str, handle: Option<ResolverHandle>) {
+        let suffix = suffix.trim_matches('.').to_ascii_lowercase();
+        self.rules.retain(|(s, _)| s.ne(&suffix));
+        self.rules.push((suffix, handle));
+    }
+
+    pub fn set_default_handle(&mut self, handle: Option<ResolverHandle>) {
+        self.default_handle = handle;
+    }
+
+    pub fn set_negative_ttl(&mut self, ttl: u32) {
+        self.negative_ttl = ttl;
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> BoxResolverDriver {
+        let mut rules = self.rules.clone();
+        // the longest suffix should be matched first
+        rules.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        Box::new(SplitResolver {
+            rules,
+            default: self.default_handle.clone(),
+            negative_ttl: self.negative_ttl,
+        })
+    }
+}
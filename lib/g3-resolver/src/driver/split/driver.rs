@@ -0,0 +1,137 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{
+    ResolveDriver, ResolveJob, ResolveJobRecvResult, ResolveLocalError, ResolvedRecord,
+    ResolverHandle,
+};
+
+pub(super) struct SplitResolver {
+    pub(super) rules: Vec<(String, Option<ResolverHandle>)>,
+    pub(super) default: Option<ResolverHandle>,
+    pub(super) negative_ttl: u32,
+}
+
+fn suffix_match(domain: &str, suffix: &str) -> bool {
+    if suffix.is_empty() {
+        return true;
+    }
+    match domain.strip_suffix(suffix) {
+        Some("") => true,
+        Some(prefix) => prefix.ends_with('.'),
+        None => false,
+    }
+}
+
+impl SplitResolver {
+    fn select_handle(&self, domain: &str) -> Option<&ResolverHandle> {
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        for (suffix, handle) in &self.rules {
+            if suffix_match(&domain, suffix) {
+                return handle.as_ref();
+            }
+        }
+        self.default.as_ref()
+    }
+
+    fn new_job(
+        &self,
+        domain: &Arc<str>,
+        config: &ResolverRuntimeConfig,
+        v4: bool,
+    ) -> SplitResolverJob {
+        let job = self.select_handle(domain).and_then(|handle| {
+            if v4 {
+                handle.get_v4(domain.clone()).ok()
+            } else {
+                handle.get_v6(domain.clone()).ok()
+            }
+        });
+        SplitResolverJob {
+            job,
+            job_timeout: config.protective_query_timeout,
+            negative_ttl: self.negative_ttl,
+        }
+    }
+}
+
+struct SplitResolverJob {
+    job: Option<ResolveJob>,
+    job_timeout: Duration,
+    negative_ttl: u32,
+}
+
+impl SplitResolverJob {
+    fn normalize_job_recv_result(
+        &self,
+        domain: Arc<str>,
+        result: ResolveJobRecvResult,
+    ) -> ResolvedRecord {
+        match result {
+            Ok((r, _)) => r.as_ref().clone(),
+            Err(e) => ResolvedRecord::failed(domain, self.negative_ttl, e.into()),
+        }
+    }
+
+    async fn resolve_protective(mut self, domain: Arc<str>) -> ResolvedRecord {
+        let Some(mut job) = self.job.take() else {
+            return self
+                .normalize_job_recv_result(domain, Err(ResolveLocalError::NoResolverRunning));
+        };
+        match tokio::time::timeout(self.job_timeout, job.recv()).await {
+            Ok(r) => self.normalize_job_recv_result(domain, r),
+            Err(_) => ResolvedRecord::timed_out(domain, self.negative_ttl),
+        }
+    }
+}
+
+impl ResolveDriver for SplitResolver {
+    fn query_v4(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let job = self.new_job(&domain, config, true);
+        tokio::spawn(async move {
+            let record = job.resolve_protective(domain).await;
+            let _ = sender.send(ResolveDriverResponse::V4(record));
+        });
+    }
+
+    fn query_v6(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let job = self.new_job(&domain, config, false);
+        tokio::spawn(async move {
+            let record = job.resolve_protective(domain).await;
+            let _ = sender.send(ResolveDriverResponse::V6(record));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_suffix() {
+        assert!(suffix_match("corp.example.net", "corp.example.net"));
+        assert!(suffix_match("a.corp.example.net", "corp.example.net"));
+        assert!(!suffix_match("acorp.example.net", "corp.example.net"));
+        assert!(!suffix_match("example.net", "corp.example.net"));
+        assert!(suffix_match("example.net", ""));
+    }
+}
@@ -14,7 +14,7 @@ mod linux;
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) use linux::{
     get_incoming_cpu, set_bind_address_no_port, set_incoming_cpu, set_ip_transparent_v6,
-    set_tcp_quick_ack,
+    set_recv_orig_dst_addr_v4, set_recv_orig_dst_addr_v6, set_tcp_quick_ack,
 };
 
 #[cfg(target_os = "freebsd")]
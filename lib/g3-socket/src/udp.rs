@@ -112,6 +112,23 @@ pub fn new_std_bind_listen(config: &UdpListenConfig) -> io::Result<UdpSocket> {
     if let Some(enable) = config.is_ipv6only() {
         super::listen::set_only_v6(&socket, addr, enable)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        match family {
+            AddressFamily::Ipv4 => {
+                socket.set_ip_transparent_v4(true)?;
+                crate::sockopt::set_recv_orig_dst_addr_v4(&socket, true)?;
+            }
+            AddressFamily::Ipv6 => {
+                crate::sockopt::set_ip_transparent_v6(&socket, true)?;
+                crate::sockopt::set_recv_orig_dst_addr_v6(&socket, true)?;
+                // for ipv4-mapped ipv6 addresses on dual stack sockets
+                if config.is_ipv6only() != Some(true) {
+                    crate::sockopt::set_recv_orig_dst_addr_v4(&socket, true)?;
+                }
+            }
+        }
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -151,6 +168,29 @@ pub fn new_std_rebind_listen(config: &UdpListenConfig, addr: SocketAddr) -> io::
     Ok(UdpSocket::from(socket))
 }
 
+/// Create a socket with a non-local source address, which can be used to send
+/// replies to clients of transparent proxied udp flows
+#[cfg(target_os = "linux")]
+pub fn new_std_transparent_bind_connect(
+    local_addr: SocketAddr,
+    peer_addr: SocketAddr,
+    buf_conf: SocketBufferConfig,
+    misc_opts: UdpMiscSockOpts,
+) -> io::Result<UdpSocket> {
+    let family = AddressFamily::from(&local_addr);
+    let socket = new_udp_socket(family, buf_conf)?;
+    match family {
+        AddressFamily::Ipv4 => socket.set_ip_transparent_v4(true)?,
+        AddressFamily::Ipv6 => crate::sockopt::set_ip_transparent_v6(&socket, true)?,
+    }
+    // the listen socket may also be bound to the same address
+    socket.set_reuse_address(true)?;
+    socket.bind(&SockAddr::from(local_addr))?;
+    RawSocket::from(&socket).set_udp_misc_opts(peer_addr, misc_opts)?;
+    socket.connect(&SockAddr::from(peer_addr))?;
+    Ok(UdpSocket::from(socket))
+}
+
 fn new_udp_socket(family: AddressFamily, buf_conf: SocketBufferConfig) -> io::Result<Socket> {
     let socket = new_nonblocking_udp_socket(family)?;
     RawSocket::from(&socket).set_buf_opts(buf_conf)?;
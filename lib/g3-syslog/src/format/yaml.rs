@@ -9,7 +9,7 @@ use yaml_rust::Yaml;
 use super::SyslogFormatterKind;
 
 impl SyslogFormatterKind {
-    pub(crate) fn parse_rfc5424_yaml(value: &Yaml) -> anyhow::Result<Self> {
+    pub fn parse_rfc5424_yaml(value: &Yaml) -> anyhow::Result<Self> {
         let mut enterprise_id = 0i32;
         let mut message_id: Option<String> = None;
 
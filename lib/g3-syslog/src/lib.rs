@@ -3,6 +3,8 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use slog::{OwnedKVList, Record};
+
 use g3_types::log::AsyncLogConfig;
 
 mod async_streamer;
@@ -31,6 +33,23 @@ pub struct SyslogHeader {
     pub pid: u32,
 }
 
+pub struct SyslogLineFormatter {
+    header: SyslogHeader,
+    formatter: BoxSyslogFormatter,
+}
+
+impl SyslogLineFormatter {
+    pub fn format_slog(
+        &self,
+        w: &mut Vec<u8>,
+        record: &Record,
+        logger_values: &OwnedKVList,
+    ) -> Result<(), slog::Error> {
+        self.formatter
+            .format_slog(w, &self.header, record, logger_values)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SyslogBuilder {
     ident: &'static str,
@@ -85,7 +104,7 @@ impl SyslogBuilder {
         self.append_report_ts = enable;
     }
 
-    pub fn start_async(self, async_conf: &AsyncLogConfig) -> AsyncSyslogStreamer {
+    fn build_header_formatter(&self) -> (SyslogHeader, BoxSyslogFormatter) {
         let hostname = if self.emit_hostname {
             Some(g3_compat::hostname().to_string_lossy().to_string())
         } else {
@@ -99,25 +118,36 @@ impl SyslogBuilder {
             pid: std::process::id(),
         };
 
-        let mut formatter = match self.format {
+        let mut formatter = match &self.format {
             SyslogFormatterKind::Rfc3164 => {
                 let formatter = format::FormatterRfc3164::new();
                 Box::new(formatter) as BoxSyslogFormatter
             }
             SyslogFormatterKind::Rfc3164Cee(event_flag) => {
-                let formatter = format::FormatterRfc3164Cee::new(event_flag);
+                let formatter = format::FormatterRfc3164Cee::new(event_flag.clone());
                 Box::new(formatter) as BoxSyslogFormatter
             }
             SyslogFormatterKind::Rfc5424(eid, mid) => {
-                let formatter = format::FormatterRfc5424::new(eid, mid);
+                let formatter = format::FormatterRfc5424::new(*eid, mid.clone());
                 Box::new(formatter) as BoxSyslogFormatter
             }
             SyslogFormatterKind::Rfc5424Cee(mid, event_flag) => {
-                let formatter = format::FormatterRfc5424Cee::new(mid, event_flag);
+                let formatter = format::FormatterRfc5424Cee::new(mid.clone(), event_flag.clone());
                 Box::new(formatter) as BoxSyslogFormatter
             }
         };
         formatter.append_report_ts(self.append_report_ts);
+        (header, formatter)
+    }
+
+    pub fn start_async(self, async_conf: &AsyncLogConfig) -> AsyncSyslogStreamer {
+        let (header, formatter) = self.build_header_formatter();
         AsyncSyslogStreamer::new(async_conf, header, formatter, &self.backend)
     }
+
+    /// Build a formatter which output syslog messages without sending them to any backend
+    pub fn build_line_formatter(&self) -> SyslogLineFormatter {
+        let (header, formatter) = self.build_header_formatter();
+        SyslogLineFormatter { header, formatter }
+    }
 }
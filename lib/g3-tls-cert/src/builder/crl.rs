@@ -0,0 +1,364 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use chrono::{Days, Utc};
+use libc::c_int;
+use openssl::asn1::{Asn1Integer, Asn1Time};
+use openssl::error::ErrorStack;
+use openssl::foreign_types::ForeignType;
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::x509::{X509Crl, X509Ref};
+
+use super::asn1_time_from_chrono;
+use crate::ext::{default_sign_digest, ffi};
+
+/// CRL reason codes defined in RFC 5280
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrlReason {
+    Unspecified = 0,
+    KeyCompromise = 1,
+    CaCompromise = 2,
+    AffiliationChanged = 3,
+    Superseded = 4,
+    CessationOfOperation = 5,
+    CertificateHold = 6,
+    RemoveFromCrl = 8,
+    PrivilegeWithdrawn = 9,
+    AaCompromise = 10,
+}
+
+impl CrlReason {
+    #[inline]
+    pub fn code(&self) -> i32 {
+        *self as i32
+    }
+
+    /// The names used in the OpenSSL CA index file
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrlReason::Unspecified => "unspecified",
+            CrlReason::KeyCompromise => "keyCompromise",
+            CrlReason::CaCompromise => "CACompromise",
+            CrlReason::AffiliationChanged => "affiliationChanged",
+            CrlReason::Superseded => "superseded",
+            CrlReason::CessationOfOperation => "cessationOfOperation",
+            CrlReason::CertificateHold => "certificateHold",
+            CrlReason::RemoveFromCrl => "removeFromCRL",
+            CrlReason::PrivilegeWithdrawn => "privilegeWithdrawn",
+            CrlReason::AaCompromise => "AACompromise",
+        }
+    }
+}
+
+impl FromStr for CrlReason {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['_', '-'], "").as_str() {
+            "unspecified" => Ok(CrlReason::Unspecified),
+            "keycompromise" => Ok(CrlReason::KeyCompromise),
+            "cacompromise" => Ok(CrlReason::CaCompromise),
+            "affiliationchanged" => Ok(CrlReason::AffiliationChanged),
+            "superseded" => Ok(CrlReason::Superseded),
+            "cessationofoperation" => Ok(CrlReason::CessationOfOperation),
+            "certificatehold" => Ok(CrlReason::CertificateHold),
+            "removefromcrl" => Ok(CrlReason::RemoveFromCrl),
+            "privilegewithdrawn" => Ok(CrlReason::PrivilegeWithdrawn),
+            "aacompromise" => Ok(CrlReason::AaCompromise),
+            _ => Err(()),
+        }
+    }
+}
+
+struct RevokedEntry {
+    serial: Asn1Integer,
+    revocation_date: Asn1Time,
+    reason: Option<CrlReason>,
+}
+
+pub struct CrlBuilder {
+    crl_number: Option<Asn1Integer>,
+    last_update: Asn1Time,
+    next_update: Asn1Time,
+    revoked: Vec<RevokedEntry>,
+}
+
+fn cvt(r: c_int) -> Result<c_int, ErrorStack> {
+    if r <= 0 {
+        Err(ErrorStack::get())
+    } else {
+        Ok(r)
+    }
+}
+
+impl CrlBuilder {
+    pub fn new(next_update_days: u32) -> anyhow::Result<Self> {
+        let time_now = Utc::now();
+        let time_next = time_now
+            .checked_add_days(Days::new(next_update_days as u64))
+            .ok_or(anyhow!("unable to get next update date"))?;
+        let last_update =
+            asn1_time_from_chrono(&time_now).context("failed to get LastUpdate time")?;
+        let next_update =
+            asn1_time_from_chrono(&time_next).context("failed to get NextUpdate time")?;
+
+        Ok(CrlBuilder {
+            crl_number: None,
+            last_update,
+            next_update,
+            revoked: Vec::new(),
+        })
+    }
+
+    pub fn set_crl_number(&mut self, number: Asn1Integer) {
+        self.crl_number = Some(number);
+    }
+
+    pub fn add_revoked(
+        &mut self,
+        serial: Asn1Integer,
+        revocation_date: Asn1Time,
+        reason: Option<CrlReason>,
+    ) {
+        self.revoked.push(RevokedEntry {
+            serial,
+            revocation_date,
+            reason,
+        });
+    }
+
+    pub fn build(
+        &self,
+        ca_cert: &X509Ref,
+        ca_key: &PKey<Private>,
+        sign_digest: Option<MessageDigest>,
+    ) -> anyhow::Result<X509Crl> {
+        let digest = sign_digest.unwrap_or_else(|| default_sign_digest(ca_key));
+
+        unsafe {
+            let ptr = ffi::X509_CRL_new();
+            if ptr.is_null() {
+                return Err(anyhow!("failed to create x509 crl: {}", ErrorStack::get()));
+            }
+            let crl = X509Crl::from_ptr(ptr);
+
+            cvt(ffi::X509_CRL_set_version(crl.as_ptr(), 1))
+                .map_err(|e| anyhow!("failed to set crl version 2: {e}"))?;
+            cvt(ffi::X509_CRL_set_issuer_name(
+                crl.as_ptr(),
+                ca_cert.subject_name().as_ptr(),
+            ))
+            .map_err(|e| anyhow!("failed to set issuer name: {e}"))?;
+            cvt(ffi::X509_CRL_set1_lastUpdate(
+                crl.as_ptr(),
+                self.last_update.as_ptr(),
+            ))
+            .map_err(|e| anyhow!("failed to set LastUpdate: {e}"))?;
+            cvt(ffi::X509_CRL_set1_nextUpdate(
+                crl.as_ptr(),
+                self.next_update.as_ptr(),
+            ))
+            .map_err(|e| anyhow!("failed to set NextUpdate: {e}"))?;
+
+            for entry in &self.revoked {
+                add_revoked_entry(crl.as_ptr(), entry)?;
+            }
+            cvt(ffi::X509_CRL_sort(crl.as_ptr()))
+                .map_err(|e| anyhow!("failed to sort revoked entries: {e}"))?;
+
+            if let Some(number) = &self.crl_number {
+                cvt(ffi::X509_CRL_add1_ext_i2d(
+                    crl.as_ptr(),
+                    Nid::CRL_NUMBER.as_raw(),
+                    number.as_ptr().cast(),
+                    0,
+                    0,
+                ))
+                .map_err(|e| anyhow!("failed to add CRLNumber extension: {e}"))?;
+            }
+
+            cvt(ffi::X509_CRL_sign(
+                crl.as_ptr(),
+                ca_key.as_ptr(),
+                digest.as_ptr(),
+            ))
+            .map_err(|e| anyhow!("failed to sign: {e}"))?;
+
+            Ok(crl)
+        }
+    }
+}
+
+unsafe fn add_revoked_entry(
+    crl: *mut openssl_sys::X509_CRL,
+    entry: &RevokedEntry,
+) -> anyhow::Result<()> {
+    unsafe {
+        let rev = ffi::X509_REVOKED_new();
+        if rev.is_null() {
+            return Err(anyhow!(
+                "failed to create revoked entry: {}",
+                ErrorStack::get()
+            ));
+        }
+
+        let r = set_revoked_entry(rev, entry).and_then(|_| {
+            cvt(ffi::X509_CRL_add0_revoked(crl, rev))
+                .map_err(|e| anyhow!("failed to add revoked entry: {e}"))
+        });
+        if r.is_err() {
+            ffi::X509_REVOKED_free(rev);
+        }
+        r.map(|_| ())
+    }
+}
+
+unsafe fn set_revoked_entry(
+    rev: *mut openssl_sys::X509_REVOKED,
+    entry: &RevokedEntry,
+) -> anyhow::Result<c_int> {
+    unsafe {
+        cvt(ffi::X509_REVOKED_set_serialNumber(
+            rev,
+            entry.serial.as_ptr(),
+        ))
+        .map_err(|e| anyhow!("failed to set serial number: {e}"))?;
+        cvt(ffi::X509_REVOKED_set_revocationDate(
+            rev,
+            entry.revocation_date.as_ptr(),
+        ))
+        .map_err(|e| anyhow!("failed to set revocation date: {e}"))?;
+
+        if let Some(reason) = entry.reason {
+            let e = ffi::ASN1_ENUMERATED_new();
+            if e.is_null() {
+                return Err(anyhow!(
+                    "failed to create asn1 enumerated: {}",
+                    ErrorStack::get()
+                ));
+            }
+            let r = cvt(ffi::ASN1_ENUMERATED_set(e, reason.code() as _)).and_then(|_| {
+                cvt(ffi::X509_REVOKED_add1_ext_i2d(
+                    rev,
+                    Nid::CRL_REASON.as_raw(),
+                    e,
+                    0,
+                    0,
+                ))
+            });
+            ffi::ASN1_ENUMERATED_free(e);
+            r.map_err(|e| anyhow!("failed to add CRLReason extension: {e}"))?;
+        }
+        Ok(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::bn::BigNum;
+    use openssl::x509::X509RevokedRef;
+
+    use crate::builder::test_util;
+
+    fn revoked_reason(rev: &X509RevokedRef) -> Option<i64> {
+        unsafe {
+            let e = ffi::X509_REVOKED_get_ext_d2i(
+                rev.as_ptr(),
+                Nid::CRL_REASON.as_raw(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            if e.is_null() {
+                return None;
+            }
+            let v = ffi::ASN1_ENUMERATED_get(e);
+            ffi::ASN1_ENUMERATED_free(e);
+            Some(v as i64)
+        }
+    }
+
+    fn crl_number(crl: &X509Crl) -> Option<BigNum> {
+        unsafe {
+            let ptr = ffi::X509_CRL_get_ext_d2i(
+                crl.as_ptr(),
+                Nid::CRL_NUMBER.as_raw(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            if ptr.is_null() {
+                return None;
+            }
+            let number = Asn1Integer::from_ptr(ptr.cast());
+            Some(number.to_bn().unwrap())
+        }
+    }
+
+    #[test]
+    fn build_and_parse() {
+        let (ca_cert, ca_key) = test_util::root_ca();
+
+        let mut builder = CrlBuilder::new(7).unwrap();
+        builder.set_crl_number(BigNum::from_u32(42).unwrap().to_asn1_integer().unwrap());
+        let revocation_date = Asn1Time::days_from_now(0).unwrap();
+        builder.add_revoked(
+            BigNum::from_u32(0x1002).unwrap().to_asn1_integer().unwrap(),
+            Asn1Time::days_from_now(0).unwrap(),
+            Some(CrlReason::KeyCompromise),
+        );
+        builder.add_revoked(
+            BigNum::from_u32(0x1001).unwrap().to_asn1_integer().unwrap(),
+            revocation_date,
+            None,
+        );
+        let crl = builder.build(&ca_cert, &ca_key, None).unwrap();
+
+        let der = crl.to_der().unwrap();
+        let crl = X509Crl::from_der(&der).unwrap();
+        assert!(crl.verify(&ca_cert.public_key().unwrap()).unwrap());
+        assert_eq!(
+            crl.issuer_name().to_der().unwrap(),
+            ca_cert.subject_name().to_der().unwrap()
+        );
+        assert_eq!(crl_number(&crl).unwrap(), BigNum::from_u32(42).unwrap());
+
+        let revoked = crl.get_revoked().unwrap();
+        assert_eq!(revoked.len(), 2);
+        // the entries are sorted by serial
+        let first = revoked.get(0).unwrap();
+        assert_eq!(
+            first.serial_number().to_bn().unwrap(),
+            BigNum::from_u32(0x1001).unwrap()
+        );
+        assert_eq!(revoked_reason(first), None);
+        let second = revoked.get(1).unwrap();
+        assert_eq!(
+            second.serial_number().to_bn().unwrap(),
+            BigNum::from_u32(0x1002).unwrap()
+        );
+        assert_eq!(
+            revoked_reason(second),
+            Some(CrlReason::KeyCompromise.code() as i64)
+        );
+    }
+
+    #[test]
+    fn reason_from_str() {
+        assert_eq!(
+            CrlReason::from_str("key-compromise"),
+            Ok(CrlReason::KeyCompromise)
+        );
+        assert_eq!(
+            CrlReason::from_str("CACompromise"),
+            Ok(CrlReason::CaCompromise)
+        );
+        assert!(CrlReason::from_str("unknown").is_err());
+    }
+}
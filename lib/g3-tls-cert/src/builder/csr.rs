@@ -0,0 +1,264 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use chrono::{Days, Utc};
+use openssl::asn1::{Asn1Integer, Asn1IntegerRef, Asn1ObjectRef, Asn1Time};
+use openssl::foreign_types::{ForeignType, ForeignTypeRef};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{Id, PKey, Private};
+use openssl::x509::extension::{
+    AuthorityKeyIdentifier, ExtendedKeyUsage, SubjectAlternativeName, SubjectKeyIdentifier,
+};
+use openssl::x509::{X509, X509Builder, X509Extension, X509Ref, X509ReqRef};
+
+use super::{KeyUsageBuilder, asn1_time_from_chrono};
+use crate::ext::{X509BuilderExt, ffi};
+
+/// Build certificates for the public keys in externally supplied CSRs
+pub struct CsrCertBuilder {
+    serial: Asn1Integer,
+    ext_key_usage: X509Extension,
+    not_before: Asn1Time,
+    not_after: Asn1Time,
+}
+
+impl CsrCertBuilder {
+    pub fn new_tls_server() -> anyhow::Result<Self> {
+        let ext_key_usage = ExtendedKeyUsage::new()
+            .server_auth()
+            .build()
+            .map_err(|e| anyhow!("failed to build ExtendedKeyUsage extension: {e}"))?;
+        CsrCertBuilder::new(ext_key_usage)
+    }
+
+    pub fn new_tls_client() -> anyhow::Result<Self> {
+        let ext_key_usage = ExtendedKeyUsage::new()
+            .client_auth()
+            .build()
+            .map_err(|e| anyhow!("failed to build ExtendedKeyUsage extension: {e}"))?;
+        CsrCertBuilder::new(ext_key_usage)
+    }
+
+    fn new(ext_key_usage: X509Extension) -> anyhow::Result<Self> {
+        let serial = super::serial::random_16()?;
+        let (not_before, not_after) = validity_time(365)?;
+
+        Ok(CsrCertBuilder {
+            serial,
+            ext_key_usage,
+            not_before,
+            not_after,
+        })
+    }
+
+    #[inline]
+    pub fn serial(&self) -> &Asn1IntegerRef {
+        &self.serial
+    }
+
+    pub fn set_serial(&mut self, serial: Asn1Integer) {
+        self.serial = serial;
+    }
+
+    pub fn set_valid_days(&mut self, days: u32) -> anyhow::Result<()> {
+        let (not_before, not_after) = validity_time(days)?;
+        self.not_before = not_before;
+        self.not_after = not_after;
+        Ok(())
+    }
+
+    /// Build the certificate. The SubjectAlternativeName extension in the CSR will be used if
+    /// no `subject_alt_name` is specified, and all other requested extensions will be ignored.
+    pub fn build(
+        &self,
+        req: &X509ReqRef,
+        subject_alt_name: Option<SubjectAlternativeName>,
+        ca_cert: &X509Ref,
+        ca_key: &PKey<Private>,
+        sign_digest: Option<MessageDigest>,
+    ) -> anyhow::Result<X509> {
+        let pkey = req
+            .public_key()
+            .map_err(|e| anyhow!("failed to get public key from CSR: {e}"))?;
+        let verified = req
+            .verify(&pkey)
+            .map_err(|e| anyhow!("failed to verify CSR signature: {e}"))?;
+        if !verified {
+            return Err(anyhow!("invalid CSR signature"));
+        }
+
+        let key_usage = match pkey.id() {
+            Id::ED25519 => KeyUsageBuilder::ed_dsa(),
+            #[cfg(not(any(libressl, boringssl, awslc)))]
+            Id::ED448 => KeyUsageBuilder::ed_dsa(),
+            Id::X25519 => KeyUsageBuilder::x_dh(),
+            #[cfg(not(any(libressl, boringssl, awslc)))]
+            Id::X448 => KeyUsageBuilder::x_dh(),
+            _ => KeyUsageBuilder::tls_general(),
+        }
+        .build()
+        .map_err(|e| anyhow!("failed to build KeyUsage extension: {e}"))?;
+
+        let mut builder =
+            X509Builder::new().map_err(|e| anyhow!("failed to create x509 builder {e}"))?;
+        builder
+            .set_pubkey(&pkey)
+            .map_err(|e| anyhow!("failed to set pub key: {e}"))?;
+        builder
+            .set_serial_number(&self.serial)
+            .map_err(|e| anyhow!("failed to set serial number: {e}"))?;
+
+        let not_before = if ca_cert.not_before() > self.not_before {
+            ca_cert.not_before()
+        } else {
+            &self.not_before
+        };
+        builder
+            .set_not_before(not_before)
+            .map_err(|e| anyhow!("failed to set NotBefore: {e}"))?;
+        let not_after = if ca_cert.not_after() < self.not_after {
+            ca_cert.not_after()
+        } else {
+            &self.not_after
+        };
+        builder
+            .set_not_after(not_after)
+            .map_err(|e| anyhow!("failed to set NotAfter: {e}"))?;
+
+        builder
+            .set_version(2)
+            .map_err(|e| anyhow!("failed to set x509 version 3: {e}"))?;
+        builder
+            .append_extension(key_usage)
+            .map_err(|e| anyhow!("failed to append KeyUsage extension: {e}"))?;
+        builder
+            .append_extension2(&self.ext_key_usage)
+            .map_err(|e| anyhow!("failed to append ExtendedKeyUsage extension: {e}"))?;
+
+        builder
+            .set_subject_name(req.subject_name())
+            .map_err(|e| anyhow!("failed to set subject name: {e}"))?;
+
+        let v3_ctx = builder.x509v3_context(Some(ca_cert), None);
+        let san =
+            match subject_alt_name {
+                Some(san) => Some(san.build(&v3_ctx).map_err(|e| {
+                    anyhow!("failed to build SubjectAlternativeName extension: {e}")
+                })?),
+                None => requested_subject_alt_name(req),
+            };
+        let ski = SubjectKeyIdentifier::new()
+            .build(&v3_ctx)
+            .map_err(|e| anyhow!("failed to build SubjectKeyIdentifier extension: {e} "))?;
+        let mut aki_builder = AuthorityKeyIdentifier::new();
+        aki_builder.keyid(false);
+        let aki = aki_builder
+            .build(&v3_ctx)
+            .map_err(|e| anyhow!("failed to build AuthorityKeyIdentifier extension: {e}"))?;
+
+        if let Some(san) = san {
+            builder
+                .append_extension(san)
+                .map_err(|e| anyhow!("failed to append SubjectAlternativeName extension: {e}"))?;
+        }
+        builder
+            .append_extension(ski)
+            .map_err(|e| anyhow!("failed to append SubjectKeyIdentifier extension: {e}"))?;
+        builder
+            .append_extension(aki)
+            .map_err(|e| anyhow!("failed to append AuthorityKeyIdentifier extension: {e}"))?;
+
+        builder
+            .set_issuer_name(ca_cert.subject_name())
+            .map_err(|e| anyhow!("failed to set issuer name: {e}"))?;
+        builder
+            .sign_with_optional_digest(ca_key, sign_digest)
+            .map_err(|e| anyhow!("failed to sign: {e}"))?;
+
+        Ok(builder.build())
+    }
+}
+
+fn validity_time(days: u32) -> anyhow::Result<(Asn1Time, Asn1Time)> {
+    let time_now = Utc::now();
+    let time_before = time_now
+        .checked_sub_days(Days::new(1))
+        .ok_or(anyhow!("unable to get time before date"))?;
+    let time_after = time_now
+        .checked_add_days(Days::new(days as u64))
+        .ok_or(anyhow!("unable to get time after date"))?;
+
+    let not_before = asn1_time_from_chrono(&time_before).context("failed to get NotBefore time")?;
+    let not_after = asn1_time_from_chrono(&time_after).context("failed to get NotAfter time")?;
+    Ok((not_before, not_after))
+}
+
+fn requested_subject_alt_name(req: &X509ReqRef) -> Option<X509Extension> {
+    // there will be an error if no extensions present in the CSR
+    let extensions = req.extensions().ok()?;
+    extensions.into_iter().find(|ext| {
+        let obj = unsafe {
+            let ptr = ffi::X509_EXTENSION_get_object(ext.as_ptr());
+            Asn1ObjectRef::from_ptr(ptr)
+        };
+        obj.nid() == Nid::SUBJECT_ALT_NAME
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::x509::X509VerifyResult;
+
+    use crate::builder::test_util;
+
+    #[test]
+    fn sign_csr() {
+        let (ca_cert, ca_key) = test_util::root_ca();
+        let req = test_util::csr("www.example.net");
+
+        let builder = CsrCertBuilder::new_tls_server().unwrap();
+        let cert = builder.build(&req, None, &ca_cert, &ca_key, None).unwrap();
+
+        assert_eq!(ca_cert.issued(&cert), X509VerifyResult::OK);
+        let ca_pkey = ca_cert.public_key().unwrap();
+        assert!(cert.verify(&ca_pkey).unwrap());
+        assert_eq!(
+            cert.serial_number().to_bn().unwrap(),
+            builder.serial().to_bn().unwrap()
+        );
+        let cn = cert
+            .subject_name()
+            .entries_by_nid(Nid::COMMONNAME)
+            .next()
+            .unwrap();
+        assert_eq!(cn.data().as_slice(), b"www.example.net");
+        assert!(cert.not_before() >= ca_cert.not_before());
+        assert!(cert.not_after() <= ca_cert.not_after());
+        let req_pkey = req.public_key().unwrap();
+        assert!(cert.public_key().unwrap().public_eq(&req_pkey));
+    }
+
+    #[test]
+    fn invalid_csr_signature() {
+        let (ca_cert, ca_key) = test_util::root_ca();
+        let req = test_util::csr("www.example.net");
+        let other = test_util::csr("www.example.net");
+
+        // use the signature from another CSR
+        let mut builder = openssl::x509::X509Req::builder().unwrap();
+        builder.set_pubkey(&other.public_key().unwrap()).unwrap();
+        builder.set_subject_name(req.subject_name()).unwrap();
+        let unsigned = builder.build();
+        let builder = CsrCertBuilder::new_tls_server().unwrap();
+        assert!(
+            builder
+                .build(&unsigned, None, &ca_cert, &ca_key, None)
+                .is_err()
+        );
+    }
+}
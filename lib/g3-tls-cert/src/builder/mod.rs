@@ -33,3 +33,55 @@ pub use intermediate::IntermediateCertBuilder;
 
 mod mimic;
 pub use mimic::MimicCertBuilder;
+
+mod csr;
+pub use csr::CsrCertBuilder;
+
+mod crl;
+pub use crl::{CrlBuilder, CrlReason};
+
+mod ocsp;
+pub use ocsp::{CertRevocationStatus, OcspResponseBuilder};
+
+#[cfg(test)]
+mod test_util {
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::{PKey, Private};
+    use openssl::x509::{X509, X509NameBuilder, X509Req};
+
+    use super::{CsrCertBuilder, RootCertBuilder};
+
+    pub(super) fn root_ca() -> (X509, PKey<Private>) {
+        let mut builder = RootCertBuilder::new_ec256().unwrap();
+        builder
+            .subject_builder_mut()
+            .set_common_name("Test CA".to_string());
+        let cert = builder.build(None).unwrap();
+        (cert, builder.pkey().clone())
+    }
+
+    pub(super) fn csr(common_name: &str) -> X509Req {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let pkey = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, common_name)
+            .unwrap();
+        let name = name.build();
+
+        let mut builder = X509Req::builder().unwrap();
+        builder.set_pubkey(&pkey).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.sign(&pkey, MessageDigest::sha256()).unwrap();
+        builder.build()
+    }
+
+    pub(super) fn issue(ca_cert: &X509, ca_key: &PKey<Private>, common_name: &str) -> X509 {
+        let req = csr(common_name);
+        CsrCertBuilder::new_tls_server()
+            .unwrap()
+            .build(&req, None, ca_cert, ca_key, None)
+            .unwrap()
+    }
+}
@@ -0,0 +1,350 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+#[cfg(not(any(boringssl, awslc)))]
+use anyhow::Context;
+use anyhow::anyhow;
+#[cfg(not(any(boringssl, awslc)))]
+use chrono::{TimeDelta, Utc};
+#[cfg(not(any(boringssl, awslc)))]
+use openssl::asn1::Asn1ObjectRef;
+use openssl::asn1::{Asn1IntegerRef, Asn1Time};
+#[cfg(not(any(boringssl, awslc)))]
+use openssl::error::ErrorStack;
+#[cfg(not(any(boringssl, awslc)))]
+use openssl::foreign_types::{ForeignType, ForeignTypeRef};
+use openssl::hash::MessageDigest;
+#[cfg(not(any(boringssl, awslc)))]
+use openssl::ocsp::{
+    OcspBasicResponse, OcspCertId, OcspCertStatus, OcspRequest, OcspResponse, OcspResponseStatus,
+};
+use openssl::pkey::{PKey, Private};
+use openssl::x509::X509Ref;
+
+use super::CrlReason;
+#[cfg(not(any(boringssl, awslc)))]
+use super::asn1_time_from_chrono;
+#[cfg(not(any(boringssl, awslc)))]
+use crate::ext::{default_sign_digest, ffi};
+
+pub enum CertRevocationStatus {
+    Good,
+    Revoked {
+        revocation_time: Asn1Time,
+        reason: Option<CrlReason>,
+    },
+    Unknown,
+}
+
+/// Build OCSP responses for certificates directly issued by the CA
+pub struct OcspResponseBuilder {
+    valid_hours: Option<u32>,
+}
+
+impl Default for OcspResponseBuilder {
+    fn default() -> Self {
+        OcspResponseBuilder::new()
+    }
+}
+
+impl OcspResponseBuilder {
+    pub fn new() -> Self {
+        OcspResponseBuilder { valid_hours: None }
+    }
+
+    /// Set the NextUpdate field to be `hours` after ThisUpdate
+    pub fn set_valid_hours(&mut self, hours: u32) {
+        self.valid_hours = Some(hours);
+    }
+
+    /// Build the DER encoded OCSP response for the DER encoded OCSP request
+    #[cfg(not(any(boringssl, awslc)))]
+    pub fn build<F>(
+        &self,
+        req_der: &[u8],
+        ca_cert: &X509Ref,
+        ca_key: &PKey<Private>,
+        sign_digest: Option<MessageDigest>,
+        lookup: F,
+    ) -> anyhow::Result<Vec<u8>>
+    where
+        F: Fn(&Asn1IntegerRef) -> CertRevocationStatus,
+    {
+        let Ok(req) = OcspRequest::from_der(req_der) else {
+            return error_response(OcspResponseStatus::MALFORMED_REQUEST);
+        };
+        let count = unsafe { ffi::OCSP_request_onereq_count(req.as_ptr()) };
+        if count <= 0 {
+            return error_response(OcspResponseStatus::MALFORMED_REQUEST);
+        }
+
+        let time_now = Utc::now();
+        let this_update =
+            asn1_time_from_chrono(&time_now).context("failed to get ThisUpdate time")?;
+        let next_update = match self.valid_hours {
+            Some(hours) => {
+                let time_next = time_now
+                    .checked_add_signed(TimeDelta::hours(hours as i64))
+                    .ok_or(anyhow!("unable to get next update time"))?;
+                let t =
+                    asn1_time_from_chrono(&time_next).context("failed to get NextUpdate time")?;
+                Some(t)
+            }
+            None => None,
+        };
+
+        let basic = unsafe {
+            let ptr = ffi::OCSP_BASICRESP_new();
+            if ptr.is_null() {
+                return Err(anyhow!(
+                    "failed to create ocsp basic response: {}",
+                    ErrorStack::get()
+                ));
+            }
+            OcspBasicResponse::from_ptr(ptr)
+        };
+
+        for i in 0..count {
+            unsafe {
+                let one = ffi::OCSP_request_onereq_get0(req.as_ptr(), i);
+                let cid = ffi::OCSP_onereq_get0_id(one);
+
+                let mut md_obj = std::ptr::null_mut();
+                let mut serial = std::ptr::null_mut();
+                ffi::OCSP_id_get0_info(
+                    std::ptr::null_mut(),
+                    &mut md_obj,
+                    std::ptr::null_mut(),
+                    &mut serial,
+                    cid,
+                );
+                if md_obj.is_null() || serial.is_null() {
+                    return error_response(OcspResponseStatus::MALFORMED_REQUEST);
+                }
+                let md_nid = Asn1ObjectRef::from_ptr(md_obj).nid();
+                let Some(md) = MessageDigest::from_nid(md_nid) else {
+                    return error_response(OcspResponseStatus::MALFORMED_REQUEST);
+                };
+
+                let ca_id = ffi::OCSP_cert_to_id(md.as_ptr(), std::ptr::null(), ca_cert.as_ptr());
+                if ca_id.is_null() {
+                    return error_response(OcspResponseStatus::INTERNAL_ERROR);
+                }
+                let ca_id = OcspCertId::from_ptr(ca_id);
+
+                let status = if ffi::OCSP_id_issuer_cmp(ca_id.as_ptr(), cid) == 0 {
+                    lookup(Asn1IntegerRef::from_ptr(serial))
+                } else {
+                    CertRevocationStatus::Unknown
+                };
+                let (status, reason, revtime) = match &status {
+                    CertRevocationStatus::Good => (OcspCertStatus::GOOD, -1, std::ptr::null_mut()),
+                    CertRevocationStatus::Revoked {
+                        revocation_time,
+                        reason,
+                    } => (
+                        OcspCertStatus::REVOKED,
+                        reason.map(|r| r.code()).unwrap_or(-1),
+                        revocation_time.as_ptr(),
+                    ),
+                    CertRevocationStatus::Unknown => {
+                        (OcspCertStatus::UNKNOWN, -1, std::ptr::null_mut())
+                    }
+                };
+                let next_update = next_update
+                    .as_ref()
+                    .map(|t| t.as_ptr())
+                    .unwrap_or(std::ptr::null_mut());
+                let single = ffi::OCSP_basic_add1_status(
+                    basic.as_ptr(),
+                    cid,
+                    status.as_raw(),
+                    reason,
+                    revtime,
+                    this_update.as_ptr(),
+                    next_update,
+                );
+                if single.is_null() {
+                    return Err(anyhow!(
+                        "failed to add single response: {}",
+                        ErrorStack::get()
+                    ));
+                }
+            }
+        }
+
+        let digest = sign_digest.unwrap_or_else(|| default_sign_digest(ca_key));
+        unsafe {
+            // the nonce extension is optional, so it's ok to fail here
+            ffi::OCSP_copy_nonce(basic.as_ptr(), req.as_ptr());
+            if ffi::OCSP_basic_sign(
+                basic.as_ptr(),
+                ca_cert.as_ptr(),
+                ca_key.as_ptr(),
+                digest.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+            ) <= 0
+            {
+                return Err(anyhow!("failed to sign: {}", ErrorStack::get()));
+            }
+        }
+
+        let rsp = OcspResponse::create(OcspResponseStatus::SUCCESSFUL, Some(&*basic))
+            .map_err(|e| anyhow!("failed to create ocsp response: {e}"))?;
+        rsp.to_der()
+            .map_err(|e| anyhow!("failed to encode ocsp response: {e}"))
+    }
+
+    /// Build the DER encoded OCSP response for the DER encoded OCSP request
+    #[cfg(any(boringssl, awslc))]
+    pub fn build<F>(
+        &self,
+        _req_der: &[u8],
+        _ca_cert: &X509Ref,
+        _ca_key: &PKey<Private>,
+        _sign_digest: Option<MessageDigest>,
+        _lookup: F,
+    ) -> anyhow::Result<Vec<u8>>
+    where
+        F: Fn(&Asn1IntegerRef) -> CertRevocationStatus,
+    {
+        Err(anyhow!("OCSP is not supported with this TLS library"))
+    }
+
+    /// Build the DER encoded OCSP response with internalError status
+    #[cfg(not(any(boringssl, awslc)))]
+    pub fn build_internal_error(&self) -> anyhow::Result<Vec<u8>> {
+        error_response(OcspResponseStatus::INTERNAL_ERROR)
+    }
+
+    /// Build the DER encoded OCSP response with internalError status
+    #[cfg(any(boringssl, awslc))]
+    pub fn build_internal_error(&self) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow!("OCSP is not supported with this TLS library"))
+    }
+}
+
+#[cfg(not(any(boringssl, awslc)))]
+fn error_response(status: OcspResponseStatus) -> anyhow::Result<Vec<u8>> {
+    let rsp = OcspResponse::create(status, None)
+        .map_err(|e| anyhow!("failed to create ocsp response: {e}"))?;
+    rsp.to_der()
+        .map_err(|e| anyhow!("failed to encode ocsp response: {e}"))
+}
+
+#[cfg(all(test, not(any(boringssl, awslc))))]
+mod tests {
+    use super::*;
+    use openssl::ocsp::{OcspFlag, OcspRevokedStatus};
+    use openssl::stack::Stack;
+    use openssl::x509::X509;
+    use openssl::x509::store::X509StoreBuilder;
+
+    use crate::builder::test_util;
+
+    fn cert_id(cert: &X509, ca_cert: &X509) -> OcspCertId {
+        OcspCertId::from_cert(MessageDigest::sha1(), cert, ca_cert).unwrap()
+    }
+
+    #[test]
+    fn build_response() {
+        let (ca_cert, ca_key) = test_util::root_ca();
+        let good = test_util::issue(&ca_cert, &ca_key, "good.example.net");
+        let revoked = test_util::issue(&ca_cert, &ca_key, "revoked.example.net");
+        let revoked_serial = revoked.serial_number().to_bn().unwrap();
+
+        let mut req = OcspRequest::new().unwrap();
+        req.add_id(cert_id(&good, &ca_cert)).unwrap();
+        req.add_id(cert_id(&revoked, &ca_cert)).unwrap();
+        let mut nonce = *b"0123456789abcdef";
+        let r = unsafe {
+            ffi::OCSP_request_add1_nonce(req.as_ptr(), nonce.as_mut_ptr(), nonce.len() as _)
+        };
+        assert_eq!(r, 1);
+        let req_der = req.to_der().unwrap();
+
+        let mut builder = OcspResponseBuilder::new();
+        builder.set_valid_hours(1);
+        let rsp_der = builder
+            .build(&req_der, &ca_cert, &ca_key, None, |serial| {
+                if serial.to_bn().unwrap() == revoked_serial {
+                    CertRevocationStatus::Revoked {
+                        revocation_time: Asn1Time::days_from_now(0).unwrap(),
+                        reason: Some(CrlReason::KeyCompromise),
+                    }
+                } else {
+                    CertRevocationStatus::Good
+                }
+            })
+            .unwrap();
+
+        let rsp = OcspResponse::from_der(&rsp_der).unwrap();
+        assert_eq!(rsp.status(), OcspResponseStatus::SUCCESSFUL);
+        let basic = rsp.basic().unwrap();
+
+        let mut store = X509StoreBuilder::new().unwrap();
+        store.add_cert(ca_cert.clone()).unwrap();
+        let store = store.build();
+        let certs = Stack::new().unwrap();
+        basic.verify(&certs, &store, OcspFlag::empty()).unwrap();
+
+        let r = unsafe { ffi::OCSP_check_nonce(req.as_ptr(), basic.as_ptr()) };
+        assert_eq!(r, 1);
+
+        let id = cert_id(&good, &ca_cert);
+        let status = basic.find_status(&id).unwrap();
+        assert_eq!(status.status, OcspCertStatus::GOOD);
+        status.check_validity(60, None).unwrap();
+
+        let id = cert_id(&revoked, &ca_cert);
+        let status = basic.find_status(&id).unwrap();
+        assert_eq!(status.status, OcspCertStatus::REVOKED);
+        assert_eq!(status.reason, OcspRevokedStatus::KEY_COMPROMISE);
+        assert!(status.revocation_time.is_some());
+    }
+
+    #[test]
+    fn unknown_issuer() {
+        let (ca_cert, ca_key) = test_util::root_ca();
+        let (other_ca_cert, other_ca_key) = test_util::root_ca();
+        let cert = test_util::issue(&other_ca_cert, &other_ca_key, "www.example.net");
+
+        let mut req = OcspRequest::new().unwrap();
+        req.add_id(cert_id(&cert, &other_ca_cert)).unwrap();
+        let req_der = req.to_der().unwrap();
+
+        let rsp_der = OcspResponseBuilder::new()
+            .build(&req_der, &ca_cert, &ca_key, None, |_| {
+                CertRevocationStatus::Good
+            })
+            .unwrap();
+        let rsp = OcspResponse::from_der(&rsp_der).unwrap();
+        let basic = rsp.basic().unwrap();
+        let id = cert_id(&cert, &other_ca_cert);
+        let status = basic.find_status(&id).unwrap();
+        assert_eq!(status.status, OcspCertStatus::UNKNOWN);
+    }
+
+    #[test]
+    fn malformed_request() {
+        let (ca_cert, ca_key) = test_util::root_ca();
+        let rsp_der = OcspResponseBuilder::new()
+            .build(b"invalid", &ca_cert, &ca_key, None, |_| {
+                CertRevocationStatus::Good
+            })
+            .unwrap();
+        let rsp = OcspResponse::from_der(&rsp_der).unwrap();
+        assert_eq!(rsp.status(), OcspResponseStatus::MALFORMED_REQUEST);
+    }
+
+    #[test]
+    fn internal_error() {
+        let rsp_der = OcspResponseBuilder::new().build_internal_error().unwrap();
+        let rsp = OcspResponse::from_der(&rsp_der).unwrap();
+        assert_eq!(rsp.status(), OcspResponseStatus::INTERNAL_ERROR);
+        assert!(rsp.basic().is_err());
+    }
+}
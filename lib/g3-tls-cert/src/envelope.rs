@@ -0,0 +1,242 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! Envelope encryption for private key files.
+//!
+//! The private key files are stored as encrypted PKCS#8 PEM files, using the
+//! base64 encoded data key as the passphrase. The data key itself is stored
+//! wrapped by a key derived from a passphrase using PBKDF2-HMAC-SHA256 and
+//! AES-256-GCM.
+
+use anyhow::anyhow;
+use openssl::hash::MessageDigest;
+use openssl::symm::Cipher;
+
+const DATA_KEY_SIZE: usize = 32;
+const WRAP_VERSION: u8 = 1;
+const WRAP_SALT_SIZE: usize = 16;
+const WRAP_IV_SIZE: usize = 12;
+const WRAP_TAG_SIZE: usize = 16;
+const WRAP_HEADER_SIZE: usize = 1 + 4 + WRAP_SALT_SIZE + WRAP_IV_SIZE;
+const WRAP_TOTAL_SIZE: usize = WRAP_HEADER_SIZE + DATA_KEY_SIZE + WRAP_TAG_SIZE;
+const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+const MIN_PBKDF2_ITERATIONS: u32 = 100_000;
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+const PEM_BEGIN: &str = "-----BEGIN G3 WRAPPED DATA KEY-----";
+const PEM_END: &str = "-----END G3 WRAPPED DATA KEY-----";
+
+pub struct DataKey {
+    key: [u8; DATA_KEY_SIZE],
+}
+
+impl Drop for DataKey {
+    fn drop(&mut self) {
+        self.key.fill(0);
+    }
+}
+
+impl DataKey {
+    pub fn generate() -> anyhow::Result<Self> {
+        let mut key = [0u8; DATA_KEY_SIZE];
+        openssl::rand::rand_bytes(&mut key)
+            .map_err(|e| anyhow!("failed to generate random data key: {e}"))?;
+        Ok(DataKey { key })
+    }
+
+    /// The passphrase to be used for the encrypted PKCS#8 private key files
+    pub fn passphrase(&self) -> String {
+        openssl::base64::encode_block(&self.key)
+    }
+
+    fn derive_wrap_key(
+        passphrase: &[u8],
+        salt: &[u8],
+        iterations: u32,
+    ) -> anyhow::Result<[u8; DATA_KEY_SIZE]> {
+        let mut wrap_key = [0u8; DATA_KEY_SIZE];
+        openssl::pkcs5::pbkdf2_hmac(
+            passphrase,
+            salt,
+            iterations as usize,
+            MessageDigest::sha256(),
+            &mut wrap_key,
+        )
+        .map_err(|e| anyhow!("failed to derive wrap key: {e}"))?;
+        Ok(wrap_key)
+    }
+
+    /// Wrap the data key with the passphrase, and return the PEM encoded content
+    pub fn wrap_pem(&self, passphrase: &[u8]) -> anyhow::Result<String> {
+        let mut buf = Vec::with_capacity(WRAP_TOTAL_SIZE);
+        buf.push(WRAP_VERSION);
+        buf.extend_from_slice(&DEFAULT_PBKDF2_ITERATIONS.to_be_bytes());
+        let mut salt_iv = [0u8; WRAP_SALT_SIZE + WRAP_IV_SIZE];
+        openssl::rand::rand_bytes(&mut salt_iv)
+            .map_err(|e| anyhow!("failed to generate random salt and iv: {e}"))?;
+        buf.extend_from_slice(&salt_iv);
+
+        let (salt, iv) = salt_iv.split_at(WRAP_SALT_SIZE);
+        let mut wrap_key = Self::derive_wrap_key(passphrase, salt, DEFAULT_PBKDF2_ITERATIONS)?;
+        let mut tag = [0u8; WRAP_TAG_SIZE];
+        let r = openssl::symm::encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &wrap_key,
+            Some(iv),
+            &buf[..WRAP_HEADER_SIZE],
+            &self.key,
+            &mut tag,
+        );
+        wrap_key.fill(0);
+        let encrypted = r.map_err(|e| anyhow!("failed to encrypt data key: {e}"))?;
+        buf.extend_from_slice(&encrypted);
+        buf.extend_from_slice(&tag);
+
+        let encoded = openssl::base64::encode_block(&buf);
+        let mut pem = String::with_capacity(encoded.len() + 128);
+        pem.push_str(PEM_BEGIN);
+        pem.push('\n');
+        for line in encoded.as_bytes().chunks(64) {
+            // base64 output is always valid ascii
+            pem.push_str(std::str::from_utf8(line).unwrap());
+            pem.push('\n');
+        }
+        pem.push_str(PEM_END);
+        pem.push('\n');
+        Ok(pem)
+    }
+
+    /// Unwrap the data key from the PEM encoded content with the passphrase
+    pub fn unwrap_pem(pem: &str, passphrase: &[u8]) -> anyhow::Result<Self> {
+        let pem = pem.trim();
+        let encoded = pem
+            .strip_prefix(PEM_BEGIN)
+            .and_then(|s| s.strip_suffix(PEM_END))
+            .ok_or_else(|| anyhow!("no wrapped data key pem block found"))?;
+        let encoded: String = encoded.split_whitespace().collect();
+        let buf = openssl::base64::decode_block(&encoded)
+            .map_err(|e| anyhow!("invalid base64 encoded data: {e}"))?;
+        if buf.len() != WRAP_TOTAL_SIZE {
+            return Err(anyhow!("invalid wrapped data key length {}", buf.len()));
+        }
+        if buf[0] != WRAP_VERSION {
+            return Err(anyhow!("unsupported wrapped data key version {}", buf[0]));
+        }
+
+        let iterations = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]);
+        if !(MIN_PBKDF2_ITERATIONS..=MAX_PBKDF2_ITERATIONS).contains(&iterations) {
+            return Err(anyhow!(
+                "invalid PBKDF2 iterations {iterations}, should be in range {MIN_PBKDF2_ITERATIONS}-{MAX_PBKDF2_ITERATIONS}"
+            ));
+        }
+        let salt = &buf[5..5 + WRAP_SALT_SIZE];
+        let iv = &buf[5 + WRAP_SALT_SIZE..WRAP_HEADER_SIZE];
+        let encrypted = &buf[WRAP_HEADER_SIZE..WRAP_HEADER_SIZE + DATA_KEY_SIZE];
+        let tag = &buf[WRAP_HEADER_SIZE + DATA_KEY_SIZE..];
+
+        let mut wrap_key = Self::derive_wrap_key(passphrase, salt, iterations)?;
+        let r = openssl::symm::decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &wrap_key,
+            Some(iv),
+            &buf[..WRAP_HEADER_SIZE],
+            encrypted,
+            tag,
+        );
+        wrap_key.fill(0);
+        let mut decrypted =
+            r.map_err(|_| anyhow!("failed to decrypt data key, maybe wrong passphrase"))?;
+
+        let mut key = [0u8; DATA_KEY_SIZE];
+        key.copy_from_slice(&decrypted);
+        decrypted.fill(0);
+        Ok(DataKey { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modify_pem<F>(pem: &str, f: F) -> String
+    where
+        F: FnOnce(&mut Vec<u8>),
+    {
+        let encoded: String = pem
+            .trim()
+            .strip_prefix(PEM_BEGIN)
+            .and_then(|s| s.strip_suffix(PEM_END))
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        let mut buf = openssl::base64::decode_block(&encoded).unwrap();
+        f(&mut buf);
+        format!(
+            "{PEM_BEGIN}\n{}\n{PEM_END}\n",
+            openssl::base64::encode_block(&buf)
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = DataKey::generate().unwrap();
+        let pem = key.wrap_pem(b"test passphrase").unwrap();
+        assert!(pem.starts_with(PEM_BEGIN));
+        assert!(pem.trim_end().ends_with(PEM_END));
+
+        let unwrapped = DataKey::unwrap_pem(&pem, b"test passphrase").unwrap();
+        assert_eq!(unwrapped.key, key.key);
+        assert_eq!(unwrapped.passphrase(), key.passphrase());
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let key = DataKey::generate().unwrap();
+        let pem = key.wrap_pem(b"test passphrase").unwrap();
+        assert!(DataKey::unwrap_pem(&pem, b"wrong passphrase").is_err());
+        assert!(DataKey::unwrap_pem(&pem, b"").is_err());
+    }
+
+    #[test]
+    fn tampered() {
+        let key = DataKey::generate().unwrap();
+        let pem = key.wrap_pem(b"test passphrase").unwrap();
+
+        for offset in [
+            5,
+            WRAP_HEADER_SIZE - 1,
+            WRAP_HEADER_SIZE,
+            WRAP_TOTAL_SIZE - 1,
+        ] {
+            let tampered = modify_pem(&pem, |buf| buf[offset] ^= 0x01);
+            assert!(DataKey::unwrap_pem(&tampered, b"test passphrase").is_err());
+        }
+
+        let tampered = modify_pem(&pem, |buf| buf[0] = WRAP_VERSION + 1);
+        assert!(DataKey::unwrap_pem(&tampered, b"test passphrase").is_err());
+
+        let truncated = modify_pem(&pem, |buf| {
+            buf.pop();
+        });
+        assert!(DataKey::unwrap_pem(&truncated, b"test passphrase").is_err());
+
+        let no_end = pem.replace(PEM_END, "");
+        assert!(DataKey::unwrap_pem(&no_end, b"test passphrase").is_err());
+    }
+
+    #[test]
+    fn invalid_iterations() {
+        let key = DataKey::generate().unwrap();
+        let pem = key.wrap_pem(b"test passphrase").unwrap();
+
+        for iterations in [0u32, MIN_PBKDF2_ITERATIONS - 1, u32::MAX] {
+            let tampered = modify_pem(&pem, |buf| {
+                buf[1..5].copy_from_slice(&iterations.to_be_bytes())
+            });
+            let e = DataKey::unwrap_pem(&tampered, b"test passphrase").unwrap_err();
+            assert!(e.to_string().contains("iterations"));
+        }
+    }
+}
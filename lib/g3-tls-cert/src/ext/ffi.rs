@@ -3,8 +3,15 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use libc::{c_int, c_uchar, c_uint};
-use openssl_sys::RSA;
+use libc::{c_int, c_long, c_uchar, c_uint, c_ulong, c_void};
+use openssl_sys::{
+    ASN1_INTEGER, ASN1_OBJECT, ASN1_TIME, EVP_MD, EVP_PKEY, RSA, X509_CRL, X509_EXTENSION,
+    X509_NAME, X509_REVOKED,
+};
+#[cfg(not(any(boringssl, awslc)))]
+use openssl_sys::{
+    ASN1_OCTET_STRING, OCSP_BASICRESP, OCSP_CERTID, OCSP_REQUEST, X509, stack_st_X509,
+};
 
 unsafe extern "C" {
 
@@ -16,4 +23,107 @@ unsafe extern "C" {
         siglen: *mut c_uint,
         rsa: *mut RSA,
     ) -> c_int;
+
+    pub fn X509_EXTENSION_get_object(ex: *mut X509_EXTENSION) -> *mut ASN1_OBJECT;
+
+    pub fn X509_CRL_new() -> *mut X509_CRL;
+    pub fn X509_CRL_set_version(crl: *mut X509_CRL, version: c_long) -> c_int;
+    pub fn X509_CRL_set_issuer_name(crl: *mut X509_CRL, name: *const X509_NAME) -> c_int;
+    pub fn X509_CRL_set1_lastUpdate(crl: *mut X509_CRL, tm: *const ASN1_TIME) -> c_int;
+    pub fn X509_CRL_set1_nextUpdate(crl: *mut X509_CRL, tm: *const ASN1_TIME) -> c_int;
+    pub fn X509_CRL_add0_revoked(crl: *mut X509_CRL, rev: *mut X509_REVOKED) -> c_int;
+    pub fn X509_CRL_sort(crl: *mut X509_CRL) -> c_int;
+    pub fn X509_CRL_add1_ext_i2d(
+        x: *mut X509_CRL,
+        nid: c_int,
+        value: *mut c_void,
+        crit: c_int,
+        flags: c_ulong,
+    ) -> c_int;
+    pub fn X509_CRL_sign(x: *mut X509_CRL, pkey: *mut EVP_PKEY, md: *const EVP_MD) -> c_int;
+
+    pub fn X509_REVOKED_new() -> *mut X509_REVOKED;
+    pub fn X509_REVOKED_free(rev: *mut X509_REVOKED);
+    pub fn X509_REVOKED_set_serialNumber(
+        rev: *mut X509_REVOKED,
+        serial: *const ASN1_INTEGER,
+    ) -> c_int;
+    pub fn X509_REVOKED_set_revocationDate(rev: *mut X509_REVOKED, tm: *const ASN1_TIME) -> c_int;
+    pub fn X509_REVOKED_add1_ext_i2d(
+        x: *mut X509_REVOKED,
+        nid: c_int,
+        value: *mut c_void,
+        crit: c_int,
+        flags: c_ulong,
+    ) -> c_int;
+
+    pub fn ASN1_ENUMERATED_new() -> *mut c_void;
+    pub fn ASN1_ENUMERATED_free(a: *mut c_void);
+    pub fn ASN1_ENUMERATED_set(a: *mut c_void, v: c_long) -> c_int;
+}
+
+#[cfg(test)]
+unsafe extern "C" {
+
+    pub fn X509_CRL_get_ext_d2i(
+        x: *const X509_CRL,
+        nid: c_int,
+        crit: *mut c_int,
+        idx: *mut c_int,
+    ) -> *mut c_void;
+    pub fn X509_REVOKED_get_ext_d2i(
+        x: *const X509_REVOKED,
+        nid: c_int,
+        crit: *mut c_int,
+        idx: *mut c_int,
+    ) -> *mut c_void;
+    pub fn ASN1_ENUMERATED_get(a: *const c_void) -> c_long;
+}
+
+#[cfg(not(any(boringssl, awslc)))]
+unsafe extern "C" {
+
+    pub fn OCSP_request_onereq_count(req: *mut OCSP_REQUEST) -> c_int;
+    pub fn OCSP_request_onereq_get0(req: *mut OCSP_REQUEST, i: c_int) -> *mut c_void;
+    pub fn OCSP_onereq_get0_id(one: *mut c_void) -> *mut OCSP_CERTID;
+    pub fn OCSP_id_get0_info(
+        pi_name_hash: *mut *mut ASN1_OCTET_STRING,
+        pmd: *mut *mut ASN1_OBJECT,
+        pi_key_hash: *mut *mut ASN1_OCTET_STRING,
+        pserial: *mut *mut ASN1_INTEGER,
+        cid: *mut OCSP_CERTID,
+    ) -> c_int;
+    pub fn OCSP_cert_to_id(
+        dgst: *const EVP_MD,
+        subject: *const X509,
+        issuer: *const X509,
+    ) -> *mut OCSP_CERTID;
+    pub fn OCSP_id_issuer_cmp(a: *const OCSP_CERTID, b: *const OCSP_CERTID) -> c_int;
+
+    pub fn OCSP_BASICRESP_new() -> *mut OCSP_BASICRESP;
+    pub fn OCSP_basic_add1_status(
+        rsp: *mut OCSP_BASICRESP,
+        cid: *mut OCSP_CERTID,
+        status: c_int,
+        reason: c_int,
+        revtime: *mut ASN1_TIME,
+        thisupd: *mut ASN1_TIME,
+        nextupd: *mut ASN1_TIME,
+    ) -> *mut c_void;
+    pub fn OCSP_copy_nonce(resp: *mut OCSP_BASICRESP, req: *mut OCSP_REQUEST) -> c_int;
+    pub fn OCSP_basic_sign(
+        brsp: *mut OCSP_BASICRESP,
+        signer: *mut X509,
+        key: *mut EVP_PKEY,
+        dgst: *const EVP_MD,
+        certs: *mut stack_st_X509,
+        flags: c_ulong,
+    ) -> c_int;
+}
+
+#[cfg(all(test, not(any(boringssl, awslc))))]
+unsafe extern "C" {
+
+    pub fn OCSP_request_add1_nonce(req: *mut OCSP_REQUEST, val: *mut c_uchar, len: c_int) -> c_int;
+    pub fn OCSP_check_nonce(req: *mut OCSP_REQUEST, bs: *mut OCSP_BASICRESP) -> c_int;
 }
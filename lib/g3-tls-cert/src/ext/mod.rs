@@ -3,10 +3,11 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-mod ffi;
+pub(crate) mod ffi;
 
 mod x509_builder;
 pub use x509_builder::X509BuilderExt;
+pub(crate) use x509_builder::default_sign_digest;
 
 mod x509;
 pub use x509::X509Ext;
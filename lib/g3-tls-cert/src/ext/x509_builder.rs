@@ -22,24 +22,28 @@ impl X509BuilderExt for X509Builder {
         key: &PKeyRef<T>,
         digest: Option<MessageDigest>,
     ) -> Result<(), ErrorStack> {
-        use openssl::pkey::Id;
+        let digest = digest.unwrap_or_else(|| default_sign_digest(key));
+        self.sign(key, digest)
+    }
+}
 
-        let digest = digest.unwrap_or_else(|| match key.id() {
-            // see https://www.openssl.org/docs/manmaster/man3/EVP_DigestSign.html
-            #[cfg(not(osslconf = "OPENSSL_NO_SM2"))]
-            Id::SM2 => MessageDigest::sm3(),
-            Id::ED25519 => null_message_digest(),
-            #[cfg(not(any(libressl, boringssl, awslc)))]
-            Id::ED448 => MessageDigest::null(),
-            id => {
-                if id.as_raw() == -1 {
-                    null_message_digest()
-                } else {
-                    MessageDigest::sha256()
-                }
+pub(crate) fn default_sign_digest<T: HasPrivate>(key: &PKeyRef<T>) -> MessageDigest {
+    use openssl::pkey::Id;
+
+    match key.id() {
+        // see https://www.openssl.org/docs/manmaster/man3/EVP_DigestSign.html
+        #[cfg(not(osslconf = "OPENSSL_NO_SM2"))]
+        Id::SM2 => MessageDigest::sm3(),
+        Id::ED25519 => null_message_digest(),
+        #[cfg(not(any(libressl, boringssl, awslc)))]
+        Id::ED448 => MessageDigest::null(),
+        id => {
+            if id.as_raw() == -1 {
+                null_message_digest()
+            } else {
+                MessageDigest::sha256()
             }
-        });
-        self.sign(key, digest)
+        }
     }
 }
 
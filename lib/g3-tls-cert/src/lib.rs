@@ -6,3 +6,5 @@
 pub mod builder;
 
 pub mod ext;
+
+pub mod envelope;
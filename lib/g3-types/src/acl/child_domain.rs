@@ -4,7 +4,7 @@
  */
 
 use super::{AclAction, AclRadixTrieRule, AclRadixTrieRuleBuilder, ActionContract};
-use crate::resolve::reverse_idna_domain;
+use crate::resolve::{reverse_idna_domain, reverse_to_idna_domain};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AclChildDomainRuleBuilder<Action = AclAction>(AclRadixTrieRuleBuilder<String, Action>);
@@ -30,6 +30,13 @@ impl<Action: ActionContract> AclChildDomainRuleBuilder<Action> {
         self.0.missed_action()
     }
 
+    /// Iterate over all the parent domains and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (String, Action)> {
+        self.0
+            .iter()
+            .map(|(reversed, action)| (reverse_to_idna_domain(reversed), action))
+    }
+
     #[inline]
     pub fn build(&self) -> AclChildDomainRule<Action> {
         AclChildDomainRule(self.0.build())
@@ -61,4 +68,13 @@ mod tests {
         assert_eq!(rule.check("a.fooz.com"), (false, AclAction::Forbid));
         assert_eq!(rule.check("a.zfoo.com"), (false, AclAction::Forbid));
     }
+
+    #[test]
+    fn iter() {
+        let mut builder = AclChildDomainRuleBuilder::new(AclAction::Forbid);
+        builder.add_node(".foo.com", AclAction::Permit);
+
+        let all: Vec<_> = builder.iter().collect();
+        assert_eq!(all, vec![("foo.com".to_string(), AclAction::Permit)]);
+    }
 }
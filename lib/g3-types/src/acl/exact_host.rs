@@ -55,6 +55,16 @@ impl<Action: ActionContract> AclExactHostRule<Action> {
         self.missed_action
     }
 
+    #[inline]
+    pub fn iter_domain(&self) -> impl Iterator<Item = (&Arc<str>, Action)> {
+        self.domain.iter()
+    }
+
+    #[inline]
+    pub fn iter_ip(&self) -> impl Iterator<Item = (&IpAddr, Action)> {
+        self.ip.iter()
+    }
+
     #[inline]
     pub fn check_domain(&self, domain: &str) -> (bool, Action) {
         self.domain.check(domain)
@@ -41,6 +41,10 @@ where
         self.missed_action = action;
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Action)> {
+        self.inner.iter().map(|(k, v)| (k, *v))
+    }
+
     pub fn check<Q>(&self, node: &Q) -> (bool, Action)
     where
         K: Borrow<Q>,
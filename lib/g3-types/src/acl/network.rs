@@ -41,6 +41,10 @@ impl<Action: ActionContract> AclNetworkRuleBuilder<Action> {
         self.missed_action = action;
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&IpNetwork, Action)> {
+        self.inner.iter().map(|(k, v)| (k, *v))
+    }
+
     pub fn build(&self) -> AclNetworkRule<Action> {
         let mut inner = IpNetworkTable::new();
         for (net, action) in &self.inner {
@@ -47,6 +47,10 @@ where
         self.missed_action
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Action)> {
+        self.inner.iter().map(|(k, v)| (k, *v))
+    }
+
     pub fn build(&self) -> AclRadixTrieRule<K, Action> {
         let mut trie = Trie::new();
 
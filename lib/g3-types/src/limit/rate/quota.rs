@@ -52,6 +52,16 @@ impl RateLimitQuota {
     pub fn allow_burst(&mut self, max_burst: NonZeroU32) {
         self.max_burst = max_burst;
     }
+
+    /// Get the time needed to replenish the max burst.
+    /// A limiter that has been idle for this duration is the same as a new one.
+    pub fn full_replenish_duration(&self) -> Duration {
+        Duration::from_nanos(
+            self.replenish_nanos
+                .get()
+                .saturating_mul(self.max_burst.get() as u64),
+        )
+    }
 }
 
 impl FromStr for RateLimitQuota {
@@ -100,4 +110,13 @@ mod tests {
         v.allow_burst(NonZeroU32::new(3600).unwrap());
         assert_eq!(RateLimitQuota::from_str("3600/h").unwrap(), v);
     }
+
+    #[test]
+    fn full_replenish() {
+        let v = RateLimitQuota::from_str("30/m").unwrap();
+        assert_eq!(v.full_replenish_duration(), Duration::from_secs(60));
+
+        let v = RateLimitQuota::from_str("100").unwrap();
+        assert_eq!(v.full_replenish_duration(), Duration::from_secs(1));
+    }
 }
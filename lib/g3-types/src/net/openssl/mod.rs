@@ -11,9 +11,9 @@ pub use client::{
 
 mod server;
 pub use server::{
-    OpensslInterceptionServerConfig, OpensslInterceptionServerConfigBuilder, OpensslServerConfig,
-    OpensslServerConfigBuilder, OpensslServerSessionCache, OpensslSessionIdContext,
-    OpensslTicketKey, OpensslTicketKeyBuilder,
+    OpensslInterceptionServerConfig, OpensslInterceptionServerConfigBuilder, OpensslKeylogCallback,
+    OpensslServerConfig, OpensslServerConfigBuilder, OpensslServerSessionCache,
+    OpensslSessionIdContext, OpensslTicketKey, OpensslTicketKeyBuilder,
 };
 
 mod cert_pair;
@@ -13,6 +13,9 @@ use openssl::ssl::{AlpnError, Ssl, SslAcceptor, SslAcceptorBuilder, SslContext,
 use super::{DEFAULT_ACCEPT_TIMEOUT, MINIMAL_ACCEPT_TIMEOUT, OpensslTicketKey};
 use crate::net::RollingTicketer;
 
+/// Callback to receive the TLS key log lines in the SSLKEYLOGFILE format
+pub type OpensslKeylogCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
 pub struct OpensslInterceptionServerConfig {
     alpn_name_index: Index<Ssl, Vec<u8>>,
     pub ssl_context: SslContext,
@@ -60,12 +63,13 @@ impl OpensslInterceptionServerConfigBuilder {
     }
 
     pub fn build(&self) -> anyhow::Result<OpensslInterceptionServerConfig> {
-        self.build_with_ticketer(None)
+        self.build_with_ticketer(None, None)
     }
 
     pub fn build_with_ticketer(
         &self,
         ticketer: Option<&Arc<RollingTicketer<OpensslTicketKey>>>,
+        keylog: Option<&OpensslKeylogCallback>,
     ) -> anyhow::Result<OpensslInterceptionServerConfig> {
         let alpn_name_index: Index<Ssl, Vec<u8>> =
             Ssl::new_ex_index().map_err(|e| anyhow!("failed to create ex index: {e}"))?;
@@ -80,6 +84,9 @@ impl OpensslInterceptionServerConfigBuilder {
                     builder.set_ex_data(ticket_key_index, ticketer.clone());
                     super::set_ticket_key_callback(&mut builder, ticket_key_index)?;
                 }
+                if let Some(keylog) = keylog {
+                    set_keylog_callback(&mut builder, keylog.clone());
+                }
                 builder.build().into_context()
             }};
         }
@@ -118,6 +125,16 @@ fn build_tlcp_context() -> anyhow::Result<SslAcceptorBuilder> {
     SslAcceptor::tongsuo_tlcp().map_err(|e| anyhow!("failed to get tlcp acceptor builder: {e}"))
 }
 
+#[cfg(not(libressl))]
+fn set_keylog_callback(builder: &mut SslAcceptorBuilder, keylog: OpensslKeylogCallback) {
+    builder.set_keylog_callback(move |_ssl, line| keylog(line));
+}
+
+#[cfg(libressl)]
+fn set_keylog_callback(_builder: &mut SslAcceptorBuilder, _keylog: OpensslKeylogCallback) {
+    // key log callback is not supported by LibreSSL
+}
+
 fn set_alpn_select_callback(
     builder: &mut SslAcceptorBuilder,
     alpn_name_index: Index<Ssl, Vec<u8>>,
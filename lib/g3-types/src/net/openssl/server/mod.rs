@@ -21,7 +21,9 @@ use super::{OpensslCertificatePair, OpensslTlcpCertificatePair};
 use crate::net::{AlpnProtocol, RollingTicketer};
 
 mod intercept;
-pub use intercept::{OpensslInterceptionServerConfig, OpensslInterceptionServerConfigBuilder};
+pub use intercept::{
+    OpensslInterceptionServerConfig, OpensslInterceptionServerConfigBuilder, OpensslKeylogCallback,
+};
 
 mod ticket_key;
 pub use ticket_key::{OpensslTicketKey, OpensslTicketKeyBuilder};
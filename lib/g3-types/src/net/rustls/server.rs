@@ -79,6 +79,11 @@ impl RustlsServerConfigBuilder {
         self.client_auth = true;
     }
 
+    #[inline]
+    pub fn client_auth_enabled(&self) -> bool {
+        self.client_auth
+    }
+
     pub fn set_client_auth_certificates(&mut self, certs: Vec<CertificateDer<'static>>) {
         self.client_auth_certs = Some(certs);
     }
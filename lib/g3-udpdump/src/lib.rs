@@ -8,6 +8,6 @@ pub use dissector::ExportedPduDissectorHint;
 
 mod stream;
 pub use stream::{
-    StreamDumpConfig, StreamDumpProxyAddresses, StreamDumper, ToClientStreamDumpWriter,
-    ToRemoteStreamDumpWriter,
+    PcapngDumpConfig, StreamDumpConfig, StreamDumpKeylog, StreamDumpProxyAddresses, StreamDumper,
+    ToClientStreamDumpWriter, ToRemoteStreamDumpWriter,
 };
@@ -4,19 +4,23 @@
  */
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
 
 use g3_types::net::{SocketBufferConfig, UdpMiscSockOpts};
 
 #[cfg(feature = "yaml")]
 mod yaml;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StreamDumpConfig {
     pub peer: SocketAddr,
     pub buffer: SocketBufferConfig,
     pub opts: UdpMiscSockOpts,
     pub packet_size: usize,
     pub client_side: bool,
+    /// write to local pcapng files instead of sending to the udpdump peer
+    pub pcapng: Option<PcapngDumpConfig>,
 }
 
 impl Default for StreamDumpConfig {
@@ -27,6 +31,39 @@ impl Default for StreamDumpConfig {
             opts: UdpMiscSockOpts::default(),
             packet_size: 1480,
             client_side: false,
+            pcapng: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PcapngDumpConfig {
+    pub dir: PathBuf,
+    /// create a new file for each task instead of rotating by size
+    pub per_task: bool,
+    pub rotate_size: u64,
+    /// close the per-task file if no more data received in this duration
+    pub task_idle_timeout: Duration,
+    /// also write the TLS session keys in SSLKEYLOGFILE format
+    pub keylog: bool,
+}
+
+impl PcapngDumpConfig {
+    pub fn new(dir: PathBuf) -> Self {
+        PcapngDumpConfig {
+            dir,
+            per_task: false,
+            rotate_size: 64 * 1024 * 1024,
+            task_idle_timeout: Duration::from_secs(60),
+            keylog: false,
+        }
+    }
+
+    pub fn keylog_file(&self) -> Option<PathBuf> {
+        if self.keylog {
+            Some(self.dir.join("sslkeylog.txt"))
+        } else {
+            None
         }
     }
 }
@@ -6,7 +6,61 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
-use super::StreamDumpConfig;
+use super::{PcapngDumpConfig, StreamDumpConfig};
+
+impl PcapngDumpConfig {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut dir = None;
+                let mut config = PcapngDumpConfig::new(Default::default());
+
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "dir" | "directory" => {
+                        let path = g3_yaml::value::as_absolute_path(v)
+                            .context(format!("invalid absolute path value for key {k}"))?;
+                        dir = Some(path);
+                        Ok(())
+                    }
+                    "per_task" => {
+                        config.per_task = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid boolean value for key {k}"))?;
+                        Ok(())
+                    }
+                    "rotate_size" => {
+                        config.rotate_size = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "task_idle_timeout" => {
+                        config.task_idle_timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "keylog" => {
+                        config.keylog = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid boolean value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+
+                let Some(dir) = dir else {
+                    return Err(anyhow!("no dir set"));
+                };
+                config.dir = dir;
+                Ok(config)
+            }
+            Yaml::String(_) => {
+                let dir = g3_yaml::value::as_absolute_path(value)?;
+                Ok(PcapngDumpConfig::new(dir))
+            }
+            _ => Err(anyhow!(
+                "yaml type for 'pcapng dump config' should be 'map' or 'string'"
+            )),
+        }
+    }
+}
 
 impl StreamDumpConfig {
     pub fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
@@ -37,6 +91,12 @@ impl StreamDumpConfig {
                         config.client_side = g3_yaml::value::as_bool(v)?;
                         Ok(())
                     }
+                    "pcapng" => {
+                        let pcapng = PcapngDumpConfig::parse_yaml(v)
+                            .context(format!("invalid pcapng dump config value for key {k}"))?;
+                        config.pcapng = Some(pcapng);
+                        Ok(())
+                    }
                     _ => Err(anyhow!("invalid key {k}")),
                 })?;
 
@@ -61,7 +121,9 @@ mod tests {
     use super::*;
     use g3_yaml::{yaml_doc, yaml_str};
     use std::net::SocketAddr;
+    use std::path::PathBuf;
     use std::str::FromStr;
+    use std::time::Duration;
     use yaml_rust::YamlLoader;
 
     #[test]
@@ -134,6 +196,54 @@ mod tests {
         assert!(StreamDumpConfig::parse_yaml(&yaml).is_err());
     }
 
+    #[test]
+    fn parse_pcapng() {
+        let yaml = yaml_doc!(
+            r#"
+                pcapng:
+                  dir: /tmp/dump
+                  per_task: true
+                  task_idle_timeout: 30s
+                  keylog: true
+            "#
+        );
+        let config = StreamDumpConfig::parse_yaml(&yaml).unwrap();
+        let pcapng = config.pcapng.unwrap();
+        assert_eq!(pcapng.dir, PathBuf::from("/tmp/dump"));
+        assert!(pcapng.per_task);
+        assert_eq!(pcapng.task_idle_timeout, Duration::from_secs(30));
+        assert_eq!(
+            pcapng.keylog_file(),
+            Some(PathBuf::from("/tmp/dump/sslkeylog.txt"))
+        );
+
+        let yaml = yaml_doc!(
+            r#"
+                pcapng: /tmp/dump
+            "#
+        );
+        let config = StreamDumpConfig::parse_yaml(&yaml).unwrap();
+        let pcapng = config.pcapng.unwrap();
+        assert!(!pcapng.per_task);
+        assert_eq!(pcapng.rotate_size, 64 * 1024 * 1024);
+        assert!(pcapng.keylog_file().is_none());
+
+        let yaml = yaml_doc!(
+            r#"
+                pcapng:
+                  per_task: true
+            "#
+        );
+        assert!(StreamDumpConfig::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+                pcapng: dump
+            "#
+        );
+        assert!(StreamDumpConfig::parse_yaml(&yaml).is_err());
+    }
+
     #[test]
     fn parse_string() {
         // Valid address
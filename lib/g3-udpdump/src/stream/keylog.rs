@@ -0,0 +1,43 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use log::warn;
+
+/// Writer for TLS session keys in the SSLKEYLOGFILE format
+pub struct StreamDumpKeylog {
+    file: Mutex<File>,
+}
+
+impl StreamDumpKeylog {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(StreamDumpKeylog {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append a key log line, which should not contain the line ending
+    pub fn write_line(&self, line: &str) {
+        let mut buf = Vec::with_capacity(line.len() + 1);
+        buf.extend_from_slice(line.as_bytes());
+        buf.push(b'\n');
+
+        let mut file = match self.file.lock() {
+            Ok(file) => file,
+            Err(e) => e.into_inner(),
+        };
+        if let Err(e) = file.write_all(&buf) {
+            warn!("failed to write to tls key log file: {e}");
+        }
+    }
+}
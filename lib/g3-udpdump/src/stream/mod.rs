@@ -14,11 +14,17 @@ use tokio::sync::mpsc;
 use crate::ExportedPduDissectorHint;
 
 mod config;
-pub use config::StreamDumpConfig;
+pub use config::{PcapngDumpConfig, StreamDumpConfig};
 
 mod sink;
 use sink::Sinker;
 
+mod pcapng;
+use pcapng::PcapngSinker;
+
+mod keylog;
+pub use keylog::StreamDumpKeylog;
+
 mod header;
 use header::PduHeader;
 pub use header::{
@@ -48,6 +54,16 @@ pub struct StreamDumper {
 
 impl StreamDumper {
     pub fn new(config: StreamDumpConfig, runtime: &Handle) -> io::Result<Self> {
+        if let Some(pcapng) = &config.pcapng {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            let sinker = PcapngSinker::new(receiver, pcapng.clone())?;
+            let handle = runtime.clone();
+            std::thread::Builder::new()
+                .name("stream-dump".to_string())
+                .spawn(move || sinker.into_running(handle))?;
+            return Ok(StreamDumper { config, sender });
+        }
+
         let socket = g3_socket::udp::new_std_socket_to(
             config.peer,
             &Default::default(),
@@ -0,0 +1,94 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BLOCK_TYPE_SHB: u32 = 0x0A0D_0D0A;
+const BLOCK_TYPE_IDB: u32 = 0x0000_0001;
+const BLOCK_TYPE_EPB: u32 = 0x0000_0006;
+
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const LINKTYPE_RAW: u16 = 101;
+
+pub(super) struct PcapngFile {
+    writer: BufWriter<File>,
+    written_size: u64,
+}
+
+impl PcapngFile {
+    pub(super) fn create(path: &Path) -> io::Result<Self> {
+        let file = File::create(path)?;
+        let mut pcapng = PcapngFile {
+            writer: BufWriter::new(file),
+            written_size: 0,
+        };
+        pcapng.write_header()?;
+        Ok(pcapng)
+    }
+
+    #[inline]
+    pub(super) fn written_size(&self) -> u64 {
+        self.written_size
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        // Section Header Block
+        let mut shb = Vec::with_capacity(28);
+        shb.extend_from_slice(&BLOCK_TYPE_SHB.to_le_bytes());
+        shb.extend_from_slice(&28u32.to_le_bytes());
+        shb.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        shb.extend_from_slice(&1u16.to_le_bytes()); // major version
+        shb.extend_from_slice(&0u16.to_le_bytes()); // minor version
+        shb.extend_from_slice(&u64::MAX.to_le_bytes()); // section length not specified
+        shb.extend_from_slice(&28u32.to_le_bytes());
+        self.write_all(&shb)?;
+
+        // Interface Description Block, the timestamp resolution is microseconds by default
+        let mut idb = Vec::with_capacity(20);
+        idb.extend_from_slice(&BLOCK_TYPE_IDB.to_le_bytes());
+        idb.extend_from_slice(&20u32.to_le_bytes());
+        idb.extend_from_slice(&LINKTYPE_RAW.to_le_bytes());
+        idb.extend_from_slice(&0u16.to_le_bytes()); // reserved
+        idb.extend_from_slice(&0u32.to_le_bytes()); // no snap length limit
+        idb.extend_from_slice(&20u32.to_le_bytes());
+        self.write_all(&idb)
+    }
+
+    /// Write an Enhanced Packet Block
+    pub(super) fn write_packet(&mut self, time: SystemTime, packet: &[u8]) -> io::Result<()> {
+        let ts = time
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_micros() as u64)
+            .unwrap_or_default();
+        let padded_len = packet.len().next_multiple_of(4);
+        let block_len = (32 + padded_len) as u32;
+
+        let mut hdr = Vec::with_capacity(28);
+        hdr.extend_from_slice(&BLOCK_TYPE_EPB.to_le_bytes());
+        hdr.extend_from_slice(&block_len.to_le_bytes());
+        hdr.extend_from_slice(&0u32.to_le_bytes()); // interface id
+        hdr.extend_from_slice(&((ts >> 32) as u32).to_le_bytes());
+        hdr.extend_from_slice(&(ts as u32).to_le_bytes());
+        hdr.extend_from_slice(&(packet.len() as u32).to_le_bytes()); // captured length
+        hdr.extend_from_slice(&(packet.len() as u32).to_le_bytes()); // original length
+        self.write_all(&hdr)?;
+        self.write_all(packet)?;
+        self.write_all(&[0u8; 3][..padded_len - packet.len()])?;
+        self.write_all(&block_len.to_le_bytes())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.writer.write_all(buf)?;
+        self.written_size += buf.len() as u64;
+        Ok(())
+    }
+
+    pub(super) fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,160 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::warn;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+
+use super::PcapngDumpConfig;
+
+mod file;
+use file::PcapngFile;
+
+mod packet;
+use packet::TcpSegment;
+
+const BATCH_RECV_SIZE: usize = 32;
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+static FILE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+fn new_file_path(dir: &Path, prefix: &str) -> PathBuf {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let seq = FILE_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    dir.join(format!("{prefix}-{ts}-{}-{seq}.pcapng", std::process::id()))
+}
+
+struct TaskFile {
+    file: PcapngFile,
+    last_active: Instant,
+}
+
+pub(super) struct PcapngSinker {
+    receiver: mpsc::UnboundedReceiver<Vec<u8>>,
+    config: PcapngDumpConfig,
+    shared_file: Option<PcapngFile>,
+    task_files: HashMap<(SocketAddr, SocketAddr), TaskFile>,
+    packet_buf: Vec<u8>,
+}
+
+impl PcapngSinker {
+    pub(super) fn new(
+        receiver: mpsc::UnboundedReceiver<Vec<u8>>,
+        config: PcapngDumpConfig,
+    ) -> io::Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+        Ok(PcapngSinker {
+            receiver,
+            config,
+            shared_file: None,
+            task_files: HashMap::new(),
+            packet_buf: Vec::with_capacity(2048),
+        })
+    }
+
+    /// Run in a dedicated thread, as all file operations are blocking
+    pub(super) fn into_running(mut self, runtime: Handle) {
+        let mut buf = Vec::with_capacity(BATCH_RECV_SIZE);
+        loop {
+            let nr = match runtime.block_on(tokio::time::timeout(
+                IDLE_CHECK_INTERVAL,
+                self.receiver.recv_many(&mut buf, BATCH_RECV_SIZE),
+            )) {
+                Ok(0) => break,
+                Ok(nr) => nr,
+                Err(_) => 0,
+            };
+
+            if nr > 0 {
+                let time = SystemTime::now();
+                for pdu in &buf {
+                    self.dump_pdu(time, pdu);
+                }
+                buf.clear();
+            }
+
+            self.flush_all();
+            if self.config.per_task {
+                let timeout = self.config.task_idle_timeout;
+                self.task_files
+                    .retain(|_, f| f.last_active.elapsed() < timeout);
+            }
+        }
+        self.flush_all();
+    }
+
+    fn dump_pdu(&mut self, time: SystemTime, pdu: &[u8]) {
+        let Some(segment) = TcpSegment::parse_exported_pdu(pdu) else {
+            return;
+        };
+        self.packet_buf.clear();
+        segment.build_raw_ip_packet(&mut self.packet_buf);
+
+        let r = if self.config.per_task {
+            self.write_task_file(&segment, time)
+        } else {
+            self.write_shared_file(time)
+        };
+        if let Err(e) = r {
+            warn!("failed to write pcapng stream dump file: {e}");
+        }
+    }
+
+    fn write_task_file(&mut self, segment: &TcpSegment<'_>, time: SystemTime) -> io::Result<()> {
+        let key = if segment.src < segment.dst {
+            (segment.src, segment.dst)
+        } else {
+            (segment.dst, segment.src)
+        };
+        let task_file = match self.task_files.entry(key) {
+            Entry::Occupied(o) => o.into_mut(),
+            Entry::Vacant(v) => {
+                let file = PcapngFile::create(&new_file_path(&self.config.dir, "task"))?;
+                v.insert(TaskFile {
+                    file,
+                    last_active: Instant::now(),
+                })
+            }
+        };
+        task_file.last_active = Instant::now();
+        task_file.file.write_packet(time, &self.packet_buf)
+    }
+
+    fn write_shared_file(&mut self, time: SystemTime) -> io::Result<()> {
+        let rotate_size = self.config.rotate_size;
+        let mut file = match self.shared_file.take() {
+            Some(f) if rotate_size == 0 || f.written_size() < rotate_size => f,
+            Some(mut f) => {
+                let _ = f.flush();
+                PcapngFile::create(&new_file_path(&self.config.dir, "stream"))?
+            }
+            None => PcapngFile::create(&new_file_path(&self.config.dir, "stream"))?,
+        };
+        let r = file.write_packet(time, &self.packet_buf);
+        self.shared_file = Some(file);
+        r
+    }
+
+    fn flush_all(&mut self) {
+        if let Some(f) = &mut self.shared_file
+            && let Err(e) = f.flush()
+        {
+            warn!("failed to flush pcapng stream dump file: {e}");
+            self.shared_file = None;
+        }
+        self.task_files.retain(|_, f| f.file.flush().is_ok());
+    }
+}
@@ -0,0 +1,244 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+const EXP_PDU_TAG_END_OF_OPT: u16 = 0;
+const EXP_PDU_TAG_DISSECTOR_NAME: u16 = 12;
+const EXP_PDU_TAG_IPV4_SRC: u16 = 20;
+const EXP_PDU_TAG_IPV4_DST: u16 = 21;
+const EXP_PDU_TAG_IPV6_SRC: u16 = 22;
+const EXP_PDU_TAG_IPV6_DST: u16 = 23;
+const EXP_PDU_TAG_SRC_PORT: u16 = 25;
+const EXP_PDU_TAG_DST_PORT: u16 = 26;
+const EXP_PDU_TAG_TCP_INFO_DATA: u16 = 34;
+
+const IP_PROTO_TCP: u8 = 6;
+const IP_DEFAULT_TTL: u8 = 64;
+const TCP_FLAG_PSH_ACK: u8 = 0x18;
+
+/// The TCP data segment carried in an exported PDU packet built by the stream dump writers
+pub(super) struct TcpSegment<'a> {
+    pub(super) src: SocketAddr,
+    pub(super) dst: SocketAddr,
+    seq: u32,
+    ack: u32,
+    payload: &'a [u8],
+}
+
+impl<'a> TcpSegment<'a> {
+    /// Parse the exported PDU packet back to TCP segment.
+    ///
+    /// For proxy pairs, there will be an outer `exported_pdu` tag block before the real one,
+    /// only the addresses in the inner block will be used.
+    pub(super) fn parse_exported_pdu(buf: &'a [u8]) -> Option<Self> {
+        let mut offset = 0;
+        loop {
+            let mut src_ip = None;
+            let mut dst_ip = None;
+            let mut src_port = 0u16;
+            let mut dst_port = 0u16;
+            let mut seq = 1u32;
+            let mut ack = 1u32;
+            let mut is_outer = false;
+
+            loop {
+                let hdr = buf.get(offset..offset + 4)?;
+                let tag = u16::from_be_bytes([hdr[0], hdr[1]]);
+                let len = u16::from_be_bytes([hdr[2], hdr[3]]) as usize;
+                offset += 4;
+                let v = buf.get(offset..offset + len)?;
+                offset += len;
+
+                match tag {
+                    EXP_PDU_TAG_END_OF_OPT => break,
+                    EXP_PDU_TAG_DISSECTOR_NAME => is_outer = v == b"exported_pdu",
+                    EXP_PDU_TAG_IPV4_SRC => src_ip = ipv4_from_slice(v),
+                    EXP_PDU_TAG_IPV4_DST => dst_ip = ipv4_from_slice(v),
+                    EXP_PDU_TAG_IPV6_SRC => src_ip = ipv6_from_slice(v),
+                    EXP_PDU_TAG_IPV6_DST => dst_ip = ipv6_from_slice(v),
+                    EXP_PDU_TAG_SRC_PORT if len == 4 => {
+                        src_port = u16::from_be_bytes([v[2], v[3]]);
+                    }
+                    EXP_PDU_TAG_DST_PORT if len == 4 => {
+                        dst_port = u16::from_be_bytes([v[2], v[3]]);
+                    }
+                    EXP_PDU_TAG_TCP_INFO_DATA if len >= 14 => {
+                        seq = u32::from_be_bytes([v[2], v[3], v[4], v[5]]);
+                        ack = u32::from_be_bytes([v[10], v[11], v[12], v[13]]);
+                    }
+                    _ => {}
+                }
+            }
+
+            if is_outer {
+                continue;
+            }
+
+            return Some(TcpSegment {
+                src: SocketAddr::new(src_ip?, src_port),
+                dst: SocketAddr::new(dst_ip?, dst_port),
+                seq,
+                ack,
+                payload: &buf[offset..],
+            });
+        }
+    }
+
+    /// Build a raw IP packet (LINKTYPE_RAW) with synthetic IP and TCP headers
+    pub(super) fn build_raw_ip_packet(&self, buf: &mut Vec<u8>) {
+        let tcp_len = 20 + self.payload.len();
+        match (self.src.ip(), self.dst.ip()) {
+            (IpAddr::V4(src), IpAddr::V4(dst)) => {
+                let ip_hdr_start = buf.len();
+                buf.extend_from_slice(&[0x45, 0x00]);
+                buf.extend_from_slice(&((20 + tcp_len) as u16).to_be_bytes());
+                buf.extend_from_slice(&[0x00, 0x00, 0x40, 0x00]); // id 0, DF
+                buf.extend_from_slice(&[IP_DEFAULT_TTL, IP_PROTO_TCP, 0x00, 0x00]);
+                buf.extend_from_slice(&src.octets());
+                buf.extend_from_slice(&dst.octets());
+                let checksum = finish_checksum(sum_be_words(0, &buf[ip_hdr_start..]));
+                buf[ip_hdr_start + 10..ip_hdr_start + 12].copy_from_slice(&checksum.to_be_bytes());
+
+                let mut pseudo = sum_be_words(0, &src.octets());
+                pseudo = sum_be_words(pseudo, &dst.octets());
+                self.push_tcp(buf, pseudo, tcp_len);
+            }
+            (src, dst) => {
+                let src = to_ipv6(src);
+                let dst = to_ipv6(dst);
+                buf.extend_from_slice(&[0x60, 0x00, 0x00, 0x00]);
+                buf.extend_from_slice(&(tcp_len as u16).to_be_bytes());
+                buf.extend_from_slice(&[IP_PROTO_TCP, IP_DEFAULT_TTL]);
+                buf.extend_from_slice(&src.octets());
+                buf.extend_from_slice(&dst.octets());
+
+                let mut pseudo = sum_be_words(0, &src.octets());
+                pseudo = sum_be_words(pseudo, &dst.octets());
+                self.push_tcp(buf, pseudo, tcp_len);
+            }
+        }
+    }
+
+    fn push_tcp(&self, buf: &mut Vec<u8>, pseudo_sum: u32, tcp_len: usize) {
+        let tcp_hdr_start = buf.len();
+        buf.extend_from_slice(&self.src.port().to_be_bytes());
+        buf.extend_from_slice(&self.dst.port().to_be_bytes());
+        buf.extend_from_slice(&self.seq.to_be_bytes());
+        buf.extend_from_slice(&self.ack.to_be_bytes());
+        buf.extend_from_slice(&[0x50, TCP_FLAG_PSH_ACK, 0xFF, 0xFF]); // data offset, flags, window
+        buf.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // checksum, urgent pointer
+        buf.extend_from_slice(self.payload);
+
+        let mut sum = pseudo_sum + IP_PROTO_TCP as u32 + tcp_len as u32;
+        sum = sum_be_words(sum, &buf[tcp_hdr_start..]);
+        let checksum = finish_checksum(sum);
+        buf[tcp_hdr_start + 16..tcp_hdr_start + 18].copy_from_slice(&checksum.to_be_bytes());
+    }
+}
+
+fn ipv4_from_slice(v: &[u8]) -> Option<IpAddr> {
+    let octets: [u8; 4] = v.try_into().ok()?;
+    Some(IpAddr::V4(Ipv4Addr::from(octets)))
+}
+
+fn ipv6_from_slice(v: &[u8]) -> Option<IpAddr> {
+    let octets: [u8; 16] = v.try_into().ok()?;
+    Some(IpAddr::V6(Ipv6Addr::from(octets)))
+}
+
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip4) => ip4.to_ipv6_mapped(),
+        IpAddr::V6(ip6) => ip6,
+    }
+}
+
+fn sum_be_words(mut sum: u32, data: &[u8]) -> u32 {
+    let mut chunks = data.chunks_exact(2);
+    for c in &mut chunks {
+        sum += u16::from_be_bytes([c[0], c[1]]) as u32;
+    }
+    if let [last] = chunks.remainder() {
+        sum += (*last as u32) << 8;
+    }
+    sum
+}
+
+fn finish_checksum(mut sum: u32) -> u16 {
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExportedPduDissectorHint;
+    use crate::stream::PduHeader;
+    use crate::stream::header::{self, StreamDumpProxyAddresses};
+    use std::str::FromStr;
+
+    fn build_exported_pdu<H: PduHeader>(mut header: H, data: &[u8]) -> Vec<u8> {
+        let mut buf = header.new_header(1500);
+        buf.extend_from_slice(data);
+        header.update_tcp_dissector_data(&mut buf, data.len());
+        buf
+    }
+
+    #[test]
+    fn parse_direct() {
+        let client = SocketAddr::from_str("192.168.1.2:40000").unwrap();
+        let remote = SocketAddr::from_str("10.0.0.1:443").unwrap();
+        let (_to_c, to_r) =
+            header::new_pair(client, remote, ExportedPduDissectorHint::TlsPort(443));
+        let pdu = build_exported_pdu(to_r, b"hello");
+
+        let segment = TcpSegment::parse_exported_pdu(&pdu).unwrap();
+        assert_eq!(segment.src, client);
+        assert_eq!(segment.dst, remote);
+        assert_eq!(segment.seq, 1);
+        assert_eq!(segment.payload, b"hello");
+
+        let mut packet = Vec::new();
+        segment.build_raw_ip_packet(&mut packet);
+        assert_eq!(packet.len(), 20 + 20 + 5);
+        assert_eq!(packet[0], 0x45);
+        // checksum of a valid header should be zero
+        assert_eq!(finish_checksum(sum_be_words(0, &packet[0..20])), 0);
+    }
+
+    #[test]
+    fn parse_proxy() {
+        let addresses = StreamDumpProxyAddresses {
+            client: SocketAddr::from_str("192.168.1.2:40000").unwrap(),
+            local_server: SocketAddr::from_str("192.168.1.1:3128").unwrap(),
+            local_client: SocketAddr::from_str("[2001:db8::1]:50000").unwrap(),
+            remote: SocketAddr::from_str("[2001:db8::2]:443").unwrap(),
+        };
+        let (to_c, _to_r) =
+            header::new_proxy_pair(addresses, ExportedPduDissectorHint::TlsPort(443));
+        let pdu = build_exported_pdu(to_c, b"world");
+
+        let segment = TcpSegment::parse_exported_pdu(&pdu).unwrap();
+        assert_eq!(segment.src, addresses.remote);
+        assert_eq!(segment.dst, addresses.local_client);
+        assert_eq!(segment.payload, b"world");
+
+        let mut packet = Vec::new();
+        segment.build_raw_ip_packet(&mut packet);
+        assert_eq!(packet.len(), 40 + 20 + 5);
+        assert_eq!(packet[0] >> 4, 6);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(TcpSegment::parse_exported_pdu(&[]).is_none());
+        assert!(TcpSegment::parse_exported_pdu(&[0x00, 0x14, 0x00, 0x04, 0x01]).is_none());
+        // no address tags
+        assert!(TcpSegment::parse_exported_pdu(&[0x00, 0x00, 0x00, 0x00]).is_none());
+    }
+}